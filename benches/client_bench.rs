@@ -20,7 +20,7 @@ use std::hint::black_box;
 
 fn customer_serialization(c: &mut Criterion) {
     let customer = Customer {
-        id: Some("cust-12345".to_string()),
+        id: Some("cust-12345".into()),
         customer_number: Some("C001".to_string()),
         name: Some("Acme Corporation AB".to_string()),
         email: Some("contact@acme.se".to_string()),
@@ -57,15 +57,15 @@ fn customer_deserialization(c: &mut Criterion) {
 
 fn invoice_with_rows_serialization(c: &mut Criterion) {
     let invoice = Invoice {
-        id: Some("inv-12345".to_string()),
+        id: Some("inv-12345".into()),
         invoice_number: Some("1001".to_string()),
-        customer_id: Some("cust-12345".to_string()),
+        customer_id: Some("cust-12345".into()),
         total_amount: Some(money!(10000.00)),
         total_vat_amount: Some(money!(2500.00)),
         total_amount_including_vat: Some(money!(12500.00)),
         rows: vec![InvoiceRow {
             id: Some("row-0".to_string()),
-            article_id: Some("art-0".to_string()),
+            article_id: Some("art-0".into()),
             text: Some("Product description".to_string()),
             unit_price: Some(money!(100.0)),
             quantity: Some(money!(2.0)),
@@ -155,7 +155,7 @@ fn paginated_response_deserialization(c: &mut Criterion) {
 
 fn article_serialization(c: &mut Criterion) {
     let article = Article {
-        id: Some("art-12345".to_string()),
+        id: Some("art-12345".into()),
         article_number: Some("ART001".to_string()),
         name: Some("Premium Widget".to_string()),
         unit: Some("pcs".to_string()),
@@ -0,0 +1,41 @@
+//! Client setup shared by the `examples-full` examples.
+
+use spiris::auth::OAuth2Config;
+use spiris::{AccessToken, Client, ClientConfig, RateLimitConfig, RetryConfig};
+use std::time::Duration;
+
+/// Build a client from the environment.
+///
+/// `SPIRIS_ACCESS_TOKEN` is required. With `SPIRIS_REFRESH_TOKEN`,
+/// `SPIRIS_CLIENT_ID` and `SPIRIS_CLIENT_SECRET` also set, the client
+/// refreshes the token when it expires or is rejected, so long runs keep
+/// working. `SPIRIS_BASE_URL` points the client at another server, such as
+/// a sandbox or a local mock.
+pub fn client_from_env() -> Client {
+    let access_token = std::env::var("SPIRIS_ACCESS_TOKEN")
+        .expect("Please set SPIRIS_ACCESS_TOKEN environment variable");
+    let refresh_token = std::env::var("SPIRIS_REFRESH_TOKEN").ok();
+
+    let mut config = ClientConfig::new()
+        .retry_config(
+            RetryConfig::new()
+                .max_retries(5)
+                .initial_interval(Duration::from_millis(500))
+                .max_interval(Duration::from_secs(30)),
+        )
+        .rate_limit_config(RateLimitConfig::new(600));
+
+    if let (Ok(client_id), Ok(client_secret)) = (
+        std::env::var("SPIRIS_CLIENT_ID"),
+        std::env::var("SPIRIS_CLIENT_SECRET"),
+    ) {
+        let redirect_uri = std::env::var("SPIRIS_REDIRECT_URI")
+            .unwrap_or_else(|_| "http://localhost:8080/callback".to_string());
+        config = config.oauth_config(OAuth2Config::new(client_id, client_secret, redirect_uri));
+    }
+    if let Ok(base_url) = std::env::var("SPIRIS_BASE_URL") {
+        config = config.base_url(base_url);
+    }
+
+    Client::with_config(AccessToken::new(access_token, 3600, refresh_token), config)
+}
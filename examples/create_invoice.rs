@@ -26,8 +26,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Create a new invoice
     let new_invoice = Invoice {
-        customer_id: Some(customer_id),
-        invoice_date: Some(Utc::now()),
+        customer_id: Some(customer_id.into()),
+        invoice_date: Some(Utc::now().date_naive()),
         currency_code: Some("SEK".to_string()),
         remarks: Some("Thank you for your business!".to_string()),
         rows: vec![
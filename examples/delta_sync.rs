@@ -0,0 +1,179 @@
+//! Example: Nightly delta sync of customers and invoices
+//!
+//! Every night, fetches the customers and invoices changed since the last
+//! run and appends them as JSON lines to files in the output directory,
+//! one file per resource and day. The time of the last successful run is
+//! kept in `state.json` in the same directory; the first run exports
+//! everything. A failed run leaves the state alone, so the next one picks
+//! up the same changes.
+//!
+//! Run with:
+//! ```
+//! cargo run --example delta_sync --features examples-full -- [output-dir] [--once]
+//! ```
+//!
+//! `SPIRIS_SYNC_HOUR` sets the hour (UTC) of the nightly run, 2 by default.
+
+mod common;
+
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use spiris::pagination::StreamOptions;
+use spiris::query::Filter;
+use spiris::{Client, QueryParams};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SyncState {
+    last_sync: Option<DateTime<Utc>>,
+}
+
+impl SyncState {
+    fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Records changed since `since`, or all records on the first run.
+fn changed_since(since: Option<DateTime<Utc>>) -> QueryParams {
+    match since {
+        Some(since) => QueryParams::new().filter_by(Filter::field("ModifiedUtc").gt(since)),
+        None => QueryParams::new(),
+    }
+}
+
+/// Append `records` to `<dir>/<name>-<date>.jsonl`.
+fn export<T: Serialize>(
+    dir: &Path,
+    name: &str,
+    now: DateTime<Utc>,
+    records: &[T],
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let path = dir.join(format!("{}-{}.jsonl", name, now.format("%Y-%m-%d")));
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    for record in records {
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+    }
+    Ok(path)
+}
+
+async fn sync(client: &Client, dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let state_path = dir.join("state.json");
+    let mut state = SyncState::load(&state_path)?;
+    // Taken before fetching, so changes made during the run are fetched again
+    let started = Utc::now();
+    let options = StreamOptions::new().page_size(500).prefetch(2);
+
+    let query = changed_since(state.last_sync);
+    let changed_customers: Vec<_> = client
+        .customers()
+        .stream_with(query.clone(), options)
+        .try_collect()
+        .await?;
+    let changed_invoices: Vec<_> = client
+        .invoices()
+        .stream_with(query, options)
+        .try_collect()
+        .await?;
+
+    let customer_file = export(dir, "customers", started, &changed_customers)?;
+    let invoice_file = export(dir, "invoices", started, &changed_invoices)?;
+    println!(
+        "{}: {} customers to {}, {} invoices to {}",
+        started,
+        changed_customers.len(),
+        customer_file.display(),
+        changed_invoices.len(),
+        invoice_file.display()
+    );
+
+    state.last_sync = Some(started);
+    state.save(&state_path)
+}
+
+/// The next time the clock reaches `hour`:00 UTC.
+fn next_run(now: DateTime<Utc>, hour: u32) -> DateTime<Utc> {
+    let at = NaiveTime::from_hms_opt(hour, 0, 0).expect("SPIRIS_SYNC_HOUR must be 0-23");
+    let today = now.date_naive().and_time(at).and_utc();
+    if today > now {
+        today
+    } else {
+        today + Duration::days(1)
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let once = args.iter().any(|arg| arg == "--once");
+    let dir = PathBuf::from(
+        args.iter()
+            .find(|arg| !arg.starts_with("--"))
+            .map_or("sync", String::as_str),
+    );
+    let hour = std::env::var("SPIRIS_SYNC_HOUR")
+        .ok()
+        .map(|hour| hour.parse())
+        .transpose()?
+        .unwrap_or(2);
+
+    std::fs::create_dir_all(&dir)?;
+    let client = common::client_from_env();
+
+    loop {
+        if let Err(e) = sync(&client, &dir).await {
+            if once {
+                return Err(e);
+            }
+            eprintln!("Sync failed, retrying at the next run: {}", e);
+        }
+        if once {
+            return Ok(());
+        }
+
+        let next = next_run(Utc::now(), hour);
+        println!("Next sync at {}", next);
+        let wait = (next - Utc::now()).to_std().unwrap_or_default();
+        tokio::time::sleep(wait).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_next_run() {
+        let evening = Utc.with_ymd_and_hms(2025, 3, 14, 21, 30, 0).unwrap();
+        let night = Utc.with_ymd_and_hms(2025, 3, 15, 1, 0, 0).unwrap();
+        let expected = Utc.with_ymd_and_hms(2025, 3, 15, 2, 0, 0).unwrap();
+        assert_eq!(next_run(evening, 2), expected);
+        assert_eq!(next_run(night, 2), expected);
+        assert_eq!(next_run(expected, 2), expected + Duration::days(1));
+    }
+
+    #[test]
+    fn test_changed_since() {
+        assert!(changed_since(None).filter.is_none());
+        let since = Utc.with_ymd_and_hms(2025, 3, 14, 2, 0, 0).unwrap();
+        assert!(changed_since(Some(since))
+            .filter
+            .unwrap()
+            .starts_with("ModifiedUtc gt "));
+    }
+}
@@ -0,0 +1,187 @@
+//! Example: Create customer invoices from a CSV file
+//!
+//! Each line of the file is one invoice row:
+//!
+//! ```text
+//! customer_number,article_number,quantity,unit_price,text
+//! 1001,A1,40,950,Consulting March
+//! 1001,,1,450,Travel
+//! 1002,A2,1,,
+//! ```
+//!
+//! Rows for the same customer become one invoice. An empty article number
+//! makes a free-text row; an empty price uses the article's price. Fields
+//! are split on commas, so texts must not contain any. Every invoice is
+//! validated before anything is created.
+//!
+//! Run with:
+//! ```
+//! cargo run --example invoice_from_csv --features examples-full -- invoices.csv [--dry-run]
+//! ```
+
+mod common;
+
+use chrono::Utc;
+use spiris::{ArticleId, Invoice, InvoiceRow, ListScope, Money, Validate};
+use std::collections::HashMap;
+
+/// One line of the CSV file.
+struct CsvRow {
+    line: usize,
+    customer_number: String,
+    article_number: Option<String>,
+    quantity: Money,
+    unit_price: Option<Money>,
+    text: Option<String>,
+}
+
+fn parse_csv(content: &str) -> Result<Vec<CsvRow>, String> {
+    let mut rows = Vec::new();
+    for (index, line) in content.lines().enumerate().skip(1) {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "line {}: expected 5 fields, found {}",
+                line_number,
+                fields.len()
+            ));
+        }
+        let optional = |value: &str| (!value.is_empty()).then(|| value.to_string());
+        let amount = |name: &str, value: &str| {
+            value
+                .parse::<Money>()
+                .map_err(|_| format!("line {}: invalid {} '{}'", line_number, name, value))
+        };
+        if fields[0].is_empty() {
+            return Err(format!("line {}: customer number is required", line_number));
+        }
+        rows.push(CsvRow {
+            line: line_number,
+            customer_number: fields[0].to_string(),
+            article_number: optional(fields[1]),
+            quantity: amount("quantity", fields[2])?,
+            unit_price: optional(fields[3])
+                .map(|price| amount("unit price", &price))
+                .transpose()?,
+            text: optional(fields[4]),
+        });
+    }
+    Ok(rows)
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let path = args
+        .next()
+        .expect("Usage: invoice_from_csv <file.csv> [--dry-run]");
+    let dry_run = args.any(|arg| arg == "--dry-run");
+
+    let rows = parse_csv(&std::fs::read_to_string(&path)?)?;
+    let client = common::client_from_env();
+
+    // Group rows by customer, keeping the order of the file
+    let mut invoices: Vec<(String, Invoice)> = Vec::new();
+    let mut article_ids: HashMap<String, ArticleId> = HashMap::new();
+    for row in rows {
+        let index = match invoices
+            .iter()
+            .position(|(number, _)| *number == row.customer_number)
+        {
+            Some(index) => index,
+            None => {
+                let customer = client
+                    .customers()
+                    .get_by_number(&row.customer_number, ListScope::ActiveOnly)
+                    .await?
+                    .ok_or_else(|| {
+                        format!("line {}: no customer {}", row.line, row.customer_number)
+                    })?;
+                let invoice = Invoice {
+                    customer_id: customer.id,
+                    invoice_date: Some(Utc::now().date_naive()),
+                    ..Default::default()
+                };
+                invoices.push((row.customer_number.clone(), invoice));
+                invoices.len() - 1
+            }
+        };
+
+        let article_id = match &row.article_number {
+            Some(number) => match article_ids.get(number) {
+                Some(id) => Some(id.clone()),
+                None => {
+                    let article = client
+                        .articles()
+                        .get_by_number(number, ListScope::ActiveOnly)
+                        .await?
+                        .ok_or_else(|| format!("line {}: no article {}", row.line, number))?;
+                    let id = article.id.ok_or("article without ID")?;
+                    article_ids.insert(number.clone(), id.clone());
+                    Some(id)
+                }
+            },
+            None => None,
+        };
+        invoices[index].1.rows.push(InvoiceRow {
+            article_id,
+            text: row.text,
+            quantity: Some(row.quantity),
+            unit_price: row.unit_price,
+            ..Default::default()
+        });
+    }
+
+    for (number, invoice) in &invoices {
+        invoice
+            .validate()
+            .map_err(|e| format!("invoice for customer {}: {}", number, e))?;
+    }
+
+    for (number, invoice) in &invoices {
+        if dry_run {
+            println!(
+                "Would create invoice for customer {} with {} rows",
+                number,
+                invoice.rows.len()
+            );
+            continue;
+        }
+        let created = client.invoices().create(invoice).await?;
+        println!(
+            "Created invoice {:?} for customer {}: {:?} incl. VAT",
+            created.invoice_number, number, created.total_amount_including_vat
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let rows = parse_csv(
+            "customer_number,article_number,quantity,unit_price,text\n\
+             1001,A1,40,950,Consulting March\n\
+             \n\
+             1001,,1,450,Travel\n",
+        )
+        .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].article_number.as_deref(), Some("A1"));
+        assert_eq!(rows[1].line, 4);
+        assert!(rows[1].article_number.is_none());
+        assert_eq!(rows[1].text.as_deref(), Some("Travel"));
+
+        let err = parse_csv("header\n1001,A1,many,950,\n").err().unwrap();
+        assert_eq!(err, "line 2: invalid quantity 'many'");
+        assert!(parse_csv("header\n1001,A1,1\n").is_err());
+    }
+}
@@ -0,0 +1,192 @@
+//! Example: Webhook echo server
+//!
+//! Listens for webhook deliveries, checks their signature and prints each
+//! event. Events for a customer or invoice are followed by the current
+//! record, fetched from the API. The event is echoed back as the response
+//! body; deliveries with a missing or wrong signature get 401.
+//!
+//! Run with:
+//! ```
+//! export SPIRIS_WEBHOOK_SECRET="your_webhook_secret"
+//! cargo run --example webhook_echo --features examples-full -- [127.0.0.1:8090]
+//! ```
+//!
+//! Records are only fetched when `SPIRIS_ACCESS_TOKEN` is set.
+
+mod common;
+
+use spiris::webhooks::{Action, Event, WebhookConfig, WebhookHandler};
+use spiris::Client;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Largest request accepted, headers included.
+const MAX_REQUEST_BYTES: usize = 1024 * 1024;
+
+/// A received HTTP request: its headers and body.
+struct Request {
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Request> {
+    let invalid = |message: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let header_end = loop {
+        if let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end + 4;
+        }
+        if buffer.len() > MAX_REQUEST_BYTES {
+            return Err(invalid("headers too large"));
+        }
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(invalid("connection closed before the headers ended"));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).into_owned();
+    let headers: Vec<(String, String)> = head
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect();
+    let length: usize = headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("Content-Length"))
+        .and_then(|(_, value)| value.parse().ok())
+        .unwrap_or(0);
+    if header_end + length > MAX_REQUEST_BYTES {
+        return Err(invalid("body too large"));
+    }
+
+    let mut body = buffer.split_off(header_end);
+    while body.len() < length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(invalid("connection closed before the body ended"));
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(length);
+    Ok(Request { headers, body })
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, body: &str) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await
+}
+
+/// Print the record an event is about.
+async fn show_resource(client: &Client, event: &Event) -> spiris::Result<()> {
+    match event {
+        Event::Customer(event) if event.action != Action::Deleted => {
+            let Some(id) = &event.id else {
+                return Ok(());
+            };
+            let customer = client.customers().get(id).await?;
+            println!(
+                "  customer {:?}: {:?}",
+                customer.customer_number, customer.name
+            );
+        }
+        Event::Invoice(event) if event.action != Action::Deleted => {
+            let Some(id) = &event.id else {
+                return Ok(());
+            };
+            let invoice = client.invoices().get(id).await?;
+            println!(
+                "  invoice {:?}: {:?} incl. VAT, {:?} remaining",
+                invoice.invoice_number,
+                invoice.total_amount_including_vat,
+                invoice.remaining_amount
+            );
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+async fn handle(
+    mut stream: TcpStream,
+    handler: Arc<WebhookHandler>,
+    client: Option<Arc<Client>>,
+) -> std::io::Result<()> {
+    let request = read_request(&mut stream).await?;
+    let Some(signature) = request.header(handler.signature_header()) else {
+        return respond(
+            &mut stream,
+            "401 Unauthorized",
+            r#"{"error":"missing signature"}"#,
+        )
+        .await;
+    };
+    let event = match handler.verify_and_parse(&request.body, signature) {
+        Ok(event) => event,
+        Err(e) => {
+            eprintln!("Rejected delivery: {}", e);
+            let body = serde_json::json!({ "error": e.to_string() }).to_string();
+            return respond(&mut stream, "401 Unauthorized", &body).await;
+        }
+    };
+
+    println!(
+        "{} {} ({:?})",
+        event.id, event.event_type, event.resource_id
+    );
+    if let (Some(client), Ok(typed)) = (&client, Event::try_from(event.clone())) {
+        if let Err(e) = show_resource(client, &typed).await {
+            eprintln!("  could not fetch the record: {}", e);
+        }
+    }
+
+    let body = serde_json::to_string(&event).unwrap_or_default();
+    respond(&mut stream, "200 OK", &body).await
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let secret = std::env::var("SPIRIS_WEBHOOK_SECRET")
+        .expect("Please set SPIRIS_WEBHOOK_SECRET environment variable");
+    let addr = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:8090".to_string());
+
+    let handler = Arc::new(WebhookHandler::new(WebhookConfig::new(secret)));
+    let client = std::env::var("SPIRIS_ACCESS_TOKEN")
+        .is_ok()
+        .then(|| Arc::new(common::client_from_env()));
+
+    let listener = TcpListener::bind(&addr).await?;
+    println!("Listening for webhooks on http://{}", addr);
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let handler = handler.clone();
+        let client = client.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle(stream, handler, client).await {
+                eprintln!("{}: {}", peer, e);
+            }
+        });
+    }
+}
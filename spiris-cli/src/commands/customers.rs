@@ -0,0 +1,293 @@
+//! `spiris-cli customers` - plan and apply customer master data changes.
+//!
+//! `diff` compares a local file with the customers in the API and prints the
+//! planned creates and updates; `apply` plans the same way and carries the
+//! changes out. Customers are matched by customer number and only the
+//! columns present in the file are compared.
+//!
+//! The file is JSON (an array of customers as returned by the API) or CSV
+//! with API field names as headers, nested fields joined with a dot:
+//!
+//! ```text
+//! CustomerNumber,Name,Email,PaymentTermsInDays,InvoiceAddress.City
+//! 1001,Acme AB,billing@acme.example,30,Malmö
+//! ```
+
+use crate::output::{emit, Align, OutputFormat, Table};
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use spiris::diff::{self, Plan};
+use spiris::{Client, Customer, CustomerId};
+use std::path::{Path, PathBuf};
+
+/// Customer fields that are not text.
+const BOOL_FIELDS: [&str; 2] = ["IsActive", "IsPrivatePerson"];
+const NUMBER_FIELDS: [&str; 1] = ["PaymentTermsInDays"];
+
+#[derive(Debug, Subcommand)]
+pub enum CustomersCommand {
+    /// Show the changes needed to match a local file
+    Diff {
+        /// Customers as CSV or JSON
+        #[arg(long)]
+        file: PathBuf,
+    },
+    /// Create and update customers to match a local file
+    Apply {
+        /// Customers as CSV or JSON
+        #[arg(long)]
+        file: PathBuf,
+    },
+}
+
+/// Outcome of one planned change.
+#[derive(Debug, Serialize)]
+struct Applied {
+    action: &'static str,
+    customer: String,
+    error: Option<String>,
+}
+
+pub async fn run(client: &Client, command: CustomersCommand, format: OutputFormat) -> Result<()> {
+    match command {
+        CustomersCommand::Diff { file } => {
+            let plan = diff::plan_customers(client, &read_customers(&file)?).await?;
+            emit(format, &plan, &plan_table(&plan))?;
+            if format == OutputFormat::Table {
+                eprintln!("{}", summary(&plan));
+            }
+            Ok(())
+        }
+        CustomersCommand::Apply { file } => {
+            let plan = diff::plan_customers(client, &read_customers(&file)?).await?;
+            let results = apply(client, &plan).await;
+            emit(format, &results, &results_table(&results))?;
+
+            let failed = results.iter().filter(|r| r.error.is_some()).count();
+            if failed > 0 {
+                bail!("{} of {} change(s) failed", failed, results.len());
+            }
+            if format == OutputFormat::Table {
+                eprintln!("{}", summary(&plan));
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn apply(client: &Client, plan: &Plan<Customer, CustomerId>) -> Vec<Applied> {
+    let mut results = Vec::new();
+    for create in &plan.creates {
+        let outcome = client.customers().create(&create.record).await;
+        results.push(Applied {
+            action: "create",
+            customer: label(create.key.as_deref(), &create.record),
+            error: outcome.err().map(|e| e.to_string()),
+        });
+    }
+    for update in &plan.updates {
+        let outcome = client.customers().update(&update.id, &update.record).await;
+        results.push(Applied {
+            action: "update",
+            customer: label(Some(&update.key), &update.record),
+            error: outcome.err().map(|e| e.to_string()),
+        });
+    }
+    results
+}
+
+fn summary(plan: &Plan<Customer, CustomerId>) -> String {
+    format!(
+        "{} to create, {} to update, {} unchanged",
+        plan.creates.len(),
+        plan.updates.len(),
+        plan.unchanged
+    )
+}
+
+fn label(key: Option<&str>, customer: &Customer) -> String {
+    match (key, customer.name.as_deref()) {
+        (Some(key), Some(name)) => format!("{} {}", key, name),
+        (Some(key), None) => key.to_string(),
+        (None, name) => name.unwrap_or("(unnamed)").to_string(),
+    }
+}
+
+fn plan_table(plan: &Plan<Customer, CustomerId>) -> Table {
+    let mut table = Table::new()
+        .column("Action", Align::Left)
+        .column("Customer", Align::Left)
+        .column("Field", Align::Left)
+        .column("Current", Align::Left)
+        .column("Desired", Align::Left);
+
+    for create in &plan.creates {
+        table.row(vec![
+            "create".to_string(),
+            label(create.key.as_deref(), &create.record),
+            String::new(),
+            String::new(),
+            String::new(),
+        ]);
+    }
+    for update in &plan.updates {
+        for change in &update.changes {
+            table.row(vec![
+                "update".to_string(),
+                label(Some(&update.key), &update.record),
+                change.field.clone(),
+                cell(&change.current),
+                cell(&change.desired),
+            ]);
+        }
+    }
+    table
+}
+
+fn results_table(results: &[Applied]) -> Table {
+    let mut table = Table::new()
+        .column("Action", Align::Left)
+        .column("Customer", Align::Left)
+        .column("Result", Align::Left);
+    for result in results {
+        table.row(vec![
+            result.action.to_string(),
+            result.customer.clone(),
+            result.error.clone().unwrap_or_else(|| "ok".to_string()),
+        ]);
+    }
+    table
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn read_customers(path: &Path) -> Result<Vec<Customer>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    if is_json {
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid JSON in {}", path.display()))
+    } else {
+        parse_csv(&content).with_context(|| format!("Invalid CSV in {}", path.display()))
+    }
+}
+
+/// Parse CSV rows into customers. Empty cells are left unset.
+fn parse_csv(content: &str) -> Result<Vec<Customer>> {
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let headers = reader.headers()?.clone();
+
+    let mut customers = Vec::new();
+    for (line, record) in reader.records().enumerate() {
+        let record = record?;
+        let mut object = Map::new();
+        for (header, raw) in headers.iter().zip(record.iter()) {
+            let raw = raw.trim();
+            if raw.is_empty() {
+                continue;
+            }
+            let field = header.trim().rsplit('.').next().unwrap_or_default();
+            let value = if BOOL_FIELDS.contains(&field) {
+                Value::Bool(
+                    raw.parse()
+                        .with_context(|| format!("{} must be true or false", header))?,
+                )
+            } else if NUMBER_FIELDS.contains(&field) {
+                Value::from(
+                    raw.parse::<u64>()
+                        .with_context(|| format!("{} must be a number", header))?,
+                )
+            } else {
+                Value::String(raw.to_string())
+            };
+            insert(&mut object, header.trim(), value);
+        }
+        let customer = serde_json::from_value(Value::Object(object))
+            .with_context(|| format!("row {}", line + 2))?;
+        customers.push(customer);
+    }
+    Ok(customers)
+}
+
+fn insert(object: &mut Map<String, Value>, path: &str, value: Value) {
+    match path.split_once('.') {
+        Some((head, rest)) => {
+            let child = object
+                .entry(head)
+                .or_insert_with(|| Value::Object(Map::new()));
+            if let Value::Object(child) = child {
+                insert(child, rest, value);
+            }
+        }
+        None => {
+            object.insert(path.to_string(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let customers = parse_csv(
+            "CustomerNumber,Name,IsActive,PaymentTermsInDays,InvoiceAddress.City\n\
+             1001,Acme AB,true,30,Malmö\n\
+             1002,Beta AB,,,\n",
+        )
+        .unwrap();
+
+        assert_eq!(customers.len(), 2);
+        assert_eq!(customers[0].customer_number.as_deref(), Some("1001"));
+        assert_eq!(customers[0].is_active, Some(true));
+        assert_eq!(customers[0].payment_terms_in_days, Some(30));
+        assert_eq!(
+            customers[0]
+                .invoice_address
+                .as_ref()
+                .unwrap()
+                .city
+                .as_deref(),
+            Some("Malmö")
+        );
+        assert_eq!(customers[1].is_active, None);
+        assert!(parse_csv("Name,IsActive\nAcme,yes\n").is_err());
+    }
+
+    #[test]
+    fn test_plan_table() {
+        let current = vec![Customer {
+            id: Some("c1".into()),
+            customer_number: Some("1001".to_string()),
+            name: Some("Acme AB".to_string()),
+            ..Default::default()
+        }];
+        let desired =
+            parse_csv("CustomerNumber,Email\n1001,billing@acme.example\n1002,\n").unwrap();
+        let plan = diff::plan(
+            &desired,
+            &current,
+            |c| c.customer_number.clone(),
+            |c| c.id.clone(),
+        )
+        .unwrap();
+
+        let csv = plan_table(&plan).to_csv().unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "Action,Customer,Field,Current,Desired");
+        assert_eq!(lines[1], "create,1002,,,");
+        assert_eq!(lines[2], "update,1001 Acme AB,Email,,billing@acme.example");
+    }
+}
@@ -0,0 +1,634 @@
+//! `spiris-cli doctor` - diagnose common setup problems.
+//!
+//! Each check produces a [`Finding`] with a suggested fix. The API probe
+//! talks to the server directly rather than through [`spiris::Client`] since
+//! it needs the raw response headers for clock skew and rate limit checks.
+
+use crate::output::{emit, Align, OutputFormat, Table};
+use crate::session::{self, TokenSource, TOKEN_ENV};
+use anyhow::Result;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+use spiris::client::DEFAULT_BASE_URL;
+use spiris::AccessToken;
+use std::path::{Path, PathBuf};
+
+/// Scopes the CLI needs for its commands.
+const REQUIRED_SCOPES: &[&str] = &["ea:api", "ea:sales"];
+
+/// Clock skew that starts to affect token expiry handling.
+const CLOCK_SKEW_WARN_SECS: i64 = 30;
+
+/// Clock skew large enough to make tokens look expired or valid when they are not.
+const CLOCK_SKEW_FAIL_SECS: i64 = 300;
+
+/// Top-level sections of the TUI's config file, one per field of its `Config`.
+const TUI_CONFIG_SECTIONS: &[&str] = &[
+    "auth",
+    "display",
+    "pagination",
+    "export",
+    "theme",
+    "accessibility",
+    "approvals",
+    "dunning",
+    "macros",
+    "companies",
+];
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    Warning,
+    Failed,
+    Skipped,
+}
+
+impl Status {
+    fn label(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Warning => "warn",
+            Status::Failed => "FAIL",
+            Status::Skipped => "skip",
+        }
+    }
+}
+
+/// Result of a diagnostic check with an optional fix.
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub check: &'static str,
+    pub status: Status,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl Finding {
+    fn new(check: &'static str, status: Status, detail: impl Into<String>) -> Self {
+        Self {
+            check,
+            status,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+pub async fn run(
+    token_file: Option<&Path>,
+    base_url: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    let now = Utc::now();
+    let mut findings = vec![check_base_url(base_url)];
+
+    match session::load_token(token_file) {
+        Ok((token, source)) => {
+            findings.push(check_token(&token, &source, now));
+            if let TokenSource::File(path) = &source {
+                findings.extend(check_token_file_permissions(path));
+            }
+            findings.push(check_scopes(&token.token));
+
+            // Joined the same way as spiris::Client does
+            match reqwest::Url::parse(base_url.unwrap_or(DEFAULT_BASE_URL))
+                .and_then(|base| base.join("companysettings"))
+            {
+                Ok(url) => findings.extend(probe(url.as_str(), &token.token, now).await),
+                Err(e) => {
+                    for check in ["api", "rate_limit", "clock"] {
+                        findings.push(Finding::new(
+                            check,
+                            Status::Skipped,
+                            format!("Invalid base URL: {}", e),
+                        ));
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            findings.push(
+                Finding::new("token", Status::Failed, format!("{:#}", e)).fix(format!(
+                    "Sign in with spiris-tui or set {} to an access token",
+                    TOKEN_ENV
+                )),
+            );
+            for check in ["scopes", "api", "rate_limit", "clock"] {
+                findings.push(Finding::new(check, Status::Skipped, "No access token"));
+            }
+        }
+    }
+
+    findings.push(check_tui_config(tui_config_path().as_deref()));
+
+    let mut table = Table::new()
+        .column("Status", Align::Left)
+        .column("Check", Align::Left)
+        .column("Detail", Align::Left)
+        .column("Fix", Align::Left);
+    for finding in &findings {
+        table.row(vec![
+            finding.status.label().to_string(),
+            finding.check.to_string(),
+            finding.detail.clone(),
+            finding.fix.clone().unwrap_or_default(),
+        ]);
+    }
+    emit(format, &findings, &table)?;
+
+    let failed = findings
+        .iter()
+        .filter(|f| f.status == Status::Failed)
+        .count();
+    if failed > 0 {
+        anyhow::bail!("{} check(s) failed", failed);
+    }
+    Ok(())
+}
+
+fn check_base_url(base_url: Option<&str>) -> Finding {
+    let Some(url) = base_url else {
+        return Finding::new("base_url", Status::Ok, DEFAULT_BASE_URL);
+    };
+
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Finding::new("base_url", Status::Failed, format!("{} is not a URL", url))
+            .fix("Use a full URL such as https://eaccountingapi.vismaonline.com/v2/");
+    }
+    if !url.ends_with('/') {
+        // Url::join replaces the last path segment when the base has no trailing slash
+        return Finding::new(
+            "base_url",
+            Status::Failed,
+            format!(
+                "{} has no trailing slash, the version segment would be dropped",
+                url
+            ),
+        )
+        .fix(format!("Use {}/", url));
+    }
+    if url.starts_with("http://") {
+        return Finding::new("base_url", Status::Warning, format!("{} is not HTTPS", url))
+            .fix("Only use plain HTTP against local test servers");
+    }
+    Finding::new("base_url", Status::Ok, url)
+}
+
+fn check_token(token: &AccessToken, source: &TokenSource, now: DateTime<Utc>) -> Finding {
+    if *source == TokenSource::Environment {
+        return Finding::new(
+            "token",
+            Status::Ok,
+            format!("From {} (expiry unknown)", TOKEN_ENV),
+        );
+    }
+
+    let remaining = token.expires_at - now;
+    if remaining <= Duration::zero() {
+        let finding = Finding::new(
+            "token",
+            Status::Failed,
+            format!(
+                "Expired {} minutes ago",
+                (now - token.expires_at).num_minutes()
+            ),
+        );
+        return if token.refresh_token.is_some() {
+            finding.fix("Open spiris-tui to refresh the token")
+        } else {
+            finding.fix("Sign in again with spiris-tui")
+        };
+    }
+    if remaining < Duration::minutes(5) {
+        return Finding::new(
+            "token",
+            Status::Warning,
+            format!("Expires in {} seconds", remaining.num_seconds()),
+        )
+        .fix("Open spiris-tui to refresh the token before long running commands");
+    }
+    if token.refresh_token.is_none() {
+        return Finding::new(
+            "token",
+            Status::Warning,
+            format!(
+                "Valid for {} minutes but has no refresh token",
+                remaining.num_minutes()
+            ),
+        )
+        .fix("Sign in with the offline_access scope to get a refresh token");
+    }
+    Finding::new(
+        "token",
+        Status::Ok,
+        format!("Valid for {} minutes", remaining.num_minutes()),
+    )
+}
+
+#[cfg(unix)]
+fn check_token_file_permissions(path: &Path) -> Option<Finding> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = std::fs::metadata(path).ok()?.permissions().mode();
+    if mode & 0o077 != 0 {
+        return Some(
+            Finding::new(
+                "token_file",
+                Status::Warning,
+                format!("{} is readable by other users", path.display()),
+            )
+            .fix(format!("chmod 600 {}", path.display())),
+        );
+    }
+    Some(Finding::new(
+        "token_file",
+        Status::Ok,
+        path.display().to_string(),
+    ))
+}
+
+#[cfg(not(unix))]
+fn check_token_file_permissions(path: &Path) -> Option<Finding> {
+    Some(Finding::new(
+        "token_file",
+        Status::Ok,
+        path.display().to_string(),
+    ))
+}
+
+/// Read the granted scopes from a JWT access token.
+fn token_scopes(token: &str) -> Option<Vec<String>> {
+    let payload = token.split('.').nth(1)?;
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&bytes).ok()?;
+
+    match claims.get("scope")? {
+        serde_json::Value::String(s) => Some(s.split_whitespace().map(String::from).collect()),
+        serde_json::Value::Array(values) => Some(
+            values
+                .iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+fn check_scopes(token: &str) -> Finding {
+    let Some(scopes) = token_scopes(token) else {
+        return Finding::new(
+            "scopes",
+            Status::Skipped,
+            "Token does not carry readable scope claims",
+        );
+    };
+
+    let missing: Vec<&str> = REQUIRED_SCOPES
+        .iter()
+        .copied()
+        .filter(|required| !scopes.iter().any(|s| s == required))
+        .collect();
+    if missing.is_empty() {
+        Finding::new("scopes", Status::Ok, scopes.join(" "))
+    } else {
+        Finding::new(
+            "scopes",
+            Status::Failed,
+            format!("Missing {}", missing.join(", ")),
+        )
+        .fix("Sign in again and grant the missing scopes")
+    }
+}
+
+/// Call the API once and check reachability, rate limit headroom and clock skew.
+async fn probe(url: &str, token: &str, now: DateTime<Utc>) -> Vec<Finding> {
+    let response = match reqwest::Client::new()
+        .get(url)
+        .bearer_auth(token)
+        .timeout(std::time::Duration::from_secs(15))
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            return vec![
+                Finding::new("api", Status::Failed, format!("{} unreachable: {}", url, e))
+                    .fix("Check network access, proxy settings and --base-url"),
+                Finding::new("rate_limit", Status::Skipped, "API unreachable"),
+                Finding::new("clock", Status::Skipped, "API unreachable"),
+            ]
+        }
+    };
+
+    let status = response.status();
+    let api = match status.as_u16() {
+        200..=299 => Finding::new("api", Status::Ok, format!("{} answered {}", url, status)),
+        401 => Finding::new("api", Status::Failed, "Token was rejected (401)")
+            .fix("Sign in again with spiris-tui"),
+        403 => Finding::new("api", Status::Failed, "Access denied (403)").fix(
+            "Check that the user has access to the company and the token has the required scopes",
+        ),
+        429 => Finding::new("api", Status::Warning, "Rate limited (429)")
+            .fix("Wait a minute before retrying and reduce parallel requests"),
+        _ => Finding::new(
+            "api",
+            Status::Failed,
+            format!("{} answered {}", url, status),
+        )
+        .fix("Check the API status page and --base-url"),
+    };
+
+    vec![
+        api,
+        check_rate_limit(response.headers()),
+        check_clock(response.headers(), now),
+    ]
+}
+
+fn header_number(headers: &HeaderMap, names: &[&str]) -> Option<u64> {
+    names
+        .iter()
+        .find_map(|name| headers.get(*name))
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+}
+
+fn check_rate_limit(headers: &HeaderMap) -> Finding {
+    let remaining = header_number(headers, &["x-ratelimit-remaining", "ratelimit-remaining"]);
+    let limit = header_number(headers, &["x-ratelimit-limit", "ratelimit-limit"]);
+
+    match (remaining, limit) {
+        (Some(remaining), Some(limit)) if limit > 0 => {
+            let detail = format!("{} of {} requests left", remaining, limit);
+            if remaining * 10 < limit {
+                Finding::new("rate_limit", Status::Warning, detail)
+                    .fix("Pause other integrations using the same credentials")
+            } else {
+                Finding::new("rate_limit", Status::Ok, detail)
+            }
+        }
+        (Some(remaining), _) => Finding::new(
+            "rate_limit",
+            Status::Ok,
+            format!("{} requests left", remaining),
+        ),
+        _ => Finding::new(
+            "rate_limit",
+            Status::Skipped,
+            "API did not report rate limit headers (limit is 600 requests/minute)",
+        ),
+    }
+}
+
+fn check_clock(headers: &HeaderMap, now: DateTime<Utc>) -> Finding {
+    let Some(server_time) = headers
+        .get(reqwest::header::DATE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+    else {
+        return Finding::new("clock", Status::Skipped, "No Date header in response");
+    };
+
+    let skew = (now - server_time.with_timezone(&Utc)).num_seconds();
+    let detail = format!("Local clock differs from server by {}s", skew);
+    if skew.abs() > CLOCK_SKEW_FAIL_SECS {
+        Finding::new("clock", Status::Failed, detail)
+            .fix("Enable time synchronisation (NTP) on this machine")
+    } else if skew.abs() > CLOCK_SKEW_WARN_SECS {
+        Finding::new("clock", Status::Warning, detail)
+            .fix("Enable time synchronisation (NTP) on this machine")
+    } else {
+        Finding::new("clock", Status::Ok, detail)
+    }
+}
+
+/// Location of the spiris-tui configuration file.
+fn tui_config_path() -> Option<PathBuf> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .ok()?;
+    Some(
+        PathBuf::from(home)
+            .join(".config")
+            .join("spiris-tui")
+            .join("config.toml"),
+    )
+}
+
+fn check_tui_config(path: Option<&Path>) -> Finding {
+    let Some(path) = path.filter(|p| p.exists()) else {
+        return Finding::new("config", Status::Skipped, "No spiris-tui config file");
+    };
+
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            return Finding::new(
+                "config",
+                Status::Failed,
+                format!("Cannot read {}: {}", path.display(), e),
+            )
+            .fix(format!("Check the permissions of {}", path.display()))
+        }
+    };
+    let config: toml::Table = match toml::from_str(&contents) {
+        Ok(config) => config,
+        Err(e) => {
+            return Finding::new(
+                "config",
+                Status::Failed,
+                format!("{} is not valid TOML: {}", path.display(), e.message()),
+            )
+            .fix(format!(
+                "Fix the syntax or delete {} to recreate the defaults",
+                path.display()
+            ))
+        }
+    };
+
+    let unknown: Vec<&str> = config
+        .keys()
+        .map(String::as_str)
+        .filter(|k| !TUI_CONFIG_SECTIONS.contains(k))
+        .collect();
+    if !unknown.is_empty() {
+        return Finding::new(
+            "config",
+            Status::Warning,
+            format!("Unknown sections: {}", unknown.join(", ")),
+        )
+        .fix(format!("Remove them from {}", path.display()));
+    }
+
+    let page_size = config
+        .get("pagination")
+        .and_then(|p| p.get("default_page_size"))
+        .and_then(|v| v.as_integer());
+    if page_size.is_some_and(|size| size <= 0) {
+        return Finding::new(
+            "config",
+            Status::Failed,
+            "pagination.default_page_size must be positive",
+        )
+        .fix(format!("Set default_page_size = 50 in {}", path.display()));
+    }
+
+    Finding::new("config", Status::Ok, path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn jwt(claims: serde_json::Value) -> String {
+        let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        format!(
+            "{}.{}.signature",
+            engine.encode(br#"{"alg":"RS256"}"#),
+            engine.encode(claims.to_string())
+        )
+    }
+
+    #[test]
+    fn test_check_base_url() {
+        assert_eq!(check_base_url(None).status, Status::Ok);
+        assert_eq!(
+            check_base_url(Some("https://example.com/v2")).status,
+            Status::Failed
+        );
+        assert_eq!(
+            check_base_url(Some("http://localhost:8080/")).status,
+            Status::Warning
+        );
+    }
+
+    #[test]
+    fn test_check_token_expiry() {
+        let now = Utc::now();
+        let source = TokenSource::File(PathBuf::from("token.json"));
+        let mut token = AccessToken::new("t".to_string(), 3600, Some("r".to_string()));
+
+        assert_eq!(check_token(&token, &source, now).status, Status::Ok);
+
+        token.expires_at = now - Duration::minutes(10);
+        let finding = check_token(&token, &source, now);
+        assert_eq!(finding.status, Status::Failed);
+        assert!(finding.fix.unwrap().contains("refresh"));
+
+        token.expires_at = now + Duration::minutes(2);
+        assert_eq!(check_token(&token, &source, now).status, Status::Warning);
+    }
+
+    #[test]
+    fn test_check_scopes() {
+        let token = jwt(serde_json::json!({"scope": "ea:api ea:sales offline_access"}));
+        assert_eq!(check_scopes(&token).status, Status::Ok);
+
+        let token = jwt(serde_json::json!({"scope": ["ea:api"]}));
+        let finding = check_scopes(&token);
+        assert_eq!(finding.status, Status::Failed);
+        assert!(finding.detail.contains("ea:sales"));
+
+        assert_eq!(check_scopes("opaque-token").status, Status::Skipped);
+    }
+
+    #[test]
+    fn test_check_rate_limit() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(check_rate_limit(&headers).status, Status::Skipped);
+
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("600"));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("30"));
+        assert_eq!(check_rate_limit(&headers).status, Status::Warning);
+
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("450"));
+        assert_eq!(check_rate_limit(&headers).status, Status::Ok);
+    }
+
+    #[test]
+    fn test_check_clock() {
+        let server = DateTime::parse_from_rfc2822("Wed, 15 Jan 2025 12:00:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            reqwest::header::DATE,
+            HeaderValue::from_static("Wed, 15 Jan 2025 12:00:00 GMT"),
+        );
+
+        assert_eq!(check_clock(&headers, server).status, Status::Ok);
+        assert_eq!(
+            check_clock(&headers, server + Duration::seconds(90)).status,
+            Status::Warning
+        );
+        assert_eq!(
+            check_clock(&headers, server - Duration::minutes(10)).status,
+            Status::Failed
+        );
+    }
+
+    #[test]
+    fn test_check_tui_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        assert_eq!(check_tui_config(Some(&path)).status, Status::Skipped);
+
+        std::fs::write(&path, "[pagination]\ndefault_page_size = 50\n").unwrap();
+        assert_eq!(check_tui_config(Some(&path)).status, Status::Ok);
+
+        std::fs::write(&path, "[pagination\n").unwrap();
+        assert_eq!(check_tui_config(Some(&path)).status, Status::Failed);
+
+        std::fs::write(&path, "[pagination]\ndefault_page_size = 0\n").unwrap();
+        assert_eq!(check_tui_config(Some(&path)).status, Status::Failed);
+    }
+
+    #[test]
+    fn test_check_tui_config_accepts_every_section() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        std::fs::write(
+            &path,
+            "[auth]\n[accessibility]\n[macros]\n[[companies]]\nname = \"Acme\"\n",
+        )
+        .unwrap();
+        assert_eq!(check_tui_config(Some(&path)).status, Status::Ok);
+
+        std::fs::write(&path, "[colours]\n").unwrap();
+        assert_eq!(check_tui_config(Some(&path)).status, Status::Warning);
+    }
+
+    #[test]
+    fn test_tui_config_sections_match_config_fields() {
+        let source = include_str!("../../../spiris-tui/src/config.rs");
+        let body = source
+            .split("pub struct Config {")
+            .nth(1)
+            .and_then(|rest| rest.split("\n}").next())
+            .expect("spiris-tui Config struct");
+        let fields: Vec<&str> = body
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("pub "))
+            .filter_map(|field| field.split(':').next())
+            .collect();
+
+        assert!(!fields.is_empty());
+        assert_eq!(fields, TUI_CONFIG_SECTIONS);
+    }
+}
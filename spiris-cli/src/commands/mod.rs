@@ -0,0 +1,6 @@
+//! Subcommand implementations.
+
+pub mod customers;
+pub mod doctor;
+pub mod report;
+pub mod webhooks;
@@ -0,0 +1,274 @@
+//! `spiris-cli report` - financial reports.
+
+use crate::output::{amount, emit, Align, OutputFormat, Table};
+use crate::period::Period;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use clap::Subcommand;
+use spiris::budget::{self, Budget, BudgetVsActual};
+use spiris::reports::{self, AgeingBucket, AgeingReport, ProfitAndLoss, VatSummary};
+use spiris::{Client, Money};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Subcommand)]
+pub enum ReportCommand {
+    /// Accounts receivable ageing per customer
+    AgedReceivables {
+        /// Date to age invoices at (defaults to today)
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        as_of: Option<NaiveDate>,
+    },
+    /// Profit and loss per account for a period
+    Pnl {
+        /// Period: YYYY, YYYY-Qn, YYYY-MM or YYYY-MM-DD..YYYY-MM-DD (defaults to this month)
+        #[arg(long)]
+        period: Option<Period>,
+    },
+    /// Output and input VAT for a period
+    VatSummary {
+        /// Period: YYYY, YYYY-Qn, YYYY-MM or YYYY-MM-DD..YYYY-MM-DD (defaults to this month)
+        #[arg(long)]
+        period: Option<Period>,
+    },
+    /// Budget vs actual result per budget line for a period
+    Budget {
+        /// Budget JSON file
+        #[arg(long, value_name = "FILE")]
+        budget: PathBuf,
+        /// Period: YYYY, YYYY-Qn, YYYY-MM or YYYY-MM-DD..YYYY-MM-DD (defaults to this month)
+        #[arg(long)]
+        period: Option<Period>,
+    },
+}
+
+pub async fn run(client: &Client, command: ReportCommand, format: OutputFormat) -> Result<()> {
+    let today = client.clock().today();
+
+    match command {
+        ReportCommand::AgedReceivables { as_of } => {
+            let report = reports::ar_ageing(client, as_of.unwrap_or(today)).await?;
+            emit(format, &report, &ageing_table(&report))
+        }
+        ReportCommand::Pnl { period } => {
+            let period = period.unwrap_or_else(|| Period::month_of(today));
+            let report = reports::profit_and_loss(client, period.start, period.end).await?;
+            emit(format, &report, &pnl_table(&report))
+        }
+        ReportCommand::VatSummary { period } => {
+            let period = period.unwrap_or_else(|| Period::month_of(today));
+            let report = reports::vat_summary(client, period.start, period.end).await?;
+            emit(format, &report, &vat_table(&report))
+        }
+        ReportCommand::Budget { budget, period } => {
+            let budget = read_budget(&budget)?;
+            let period = period.unwrap_or_else(|| Period::month_of(today));
+            let report =
+                budget::budget_vs_actual(client, &budget, period.start, period.end).await?;
+            emit(format, &report, &budget_table(&report))
+        }
+    }
+}
+
+fn read_budget(path: &Path) -> Result<Budget> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let budget: Budget = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid budget in {}", path.display()))?;
+    budget
+        .validate()
+        .with_context(|| format!("Invalid budget in {}", path.display()))?;
+    Ok(budget)
+}
+
+fn ageing_table(report: &AgeingReport) -> Table {
+    let mut table = Table::new().column("Customer", Align::Left);
+    for bucket in AgeingBucket::ALL {
+        table = table.column(bucket.label(), Align::Right);
+    }
+    table = table
+        .column("Total", Align::Right)
+        .column("On hold", Align::Right);
+
+    for row in &report.rows {
+        let mut cells = vec![row
+            .customer_name
+            .clone()
+            .unwrap_or_else(|| row.customer_id.to_string())];
+        cells.extend(
+            AgeingBucket::ALL
+                .iter()
+                .map(|b| amount(row.buckets.get(*b))),
+        );
+        cells.push(amount(row.buckets.total()));
+        cells.push(amount(row.on_hold));
+        table.row(cells);
+    }
+
+    let mut totals = vec!["Total".to_string()];
+    totals.extend(
+        AgeingBucket::ALL
+            .iter()
+            .map(|b| amount(report.totals.get(*b))),
+    );
+    totals.push(amount(report.totals.total()));
+    totals.push(amount(report.on_hold));
+    table.footer(totals);
+    table
+}
+
+fn pnl_table(report: &ProfitAndLoss) -> Table {
+    let mut table = Table::new()
+        .column("Account", Align::Left)
+        .column("Name", Align::Left)
+        .column("Section", Align::Left)
+        .column("Amount", Align::Right);
+
+    for line in &report.lines {
+        table.row(vec![
+            line.account_number.clone(),
+            line.name.clone().unwrap_or_default(),
+            format!("{:?}", line.section),
+            amount(line.amount),
+        ]);
+    }
+    table.footer(vec![
+        "Net result".to_string(),
+        String::new(),
+        String::new(),
+        amount(report.net_result),
+    ]);
+    table
+}
+
+fn vat_table(report: &VatSummary) -> Table {
+    let mut table = Table::new()
+        .column("Account", Align::Left)
+        .column("Name", Align::Left)
+        .column("Kind", Align::Left)
+        .column("Amount", Align::Right);
+
+    for line in &report.lines {
+        table.row(vec![
+            line.account_number.clone(),
+            line.name.clone().unwrap_or_default(),
+            format!("{:?}", line.section),
+            amount(line.amount),
+        ]);
+    }
+    table.footer(vec![
+        "Net payable".to_string(),
+        String::new(),
+        String::new(),
+        amount(report.net_payable),
+    ]);
+    table
+}
+
+fn budget_table(report: &BudgetVsActual) -> Table {
+    let mut table = Table::new()
+        .column("Accounts", Align::Left)
+        .column("Name", Align::Left)
+        .column("Budget", Align::Right)
+        .column("Actual", Align::Right)
+        .column("Variance", Align::Right);
+
+    for line in &report.lines {
+        table.row(vec![
+            line.accounts.to_string(),
+            line.label.clone().unwrap_or_default(),
+            amount(line.budget),
+            amount(line.actual),
+            amount(line.variance),
+        ]);
+    }
+    if report.unbudgeted != Money::default() {
+        table.row(vec![
+            String::new(),
+            "Not budgeted".to_string(),
+            amount(Money::default()),
+            amount(report.unbudgeted),
+            amount(report.unbudgeted),
+        ]);
+    }
+    table.footer(vec![
+        "Total".to_string(),
+        String::new(),
+        amount(report.total_budget),
+        amount(report.total_actual),
+        amount(report.total_variance),
+    ]);
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spiris::{AccountBalance, Invoice};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_ageing_table_has_bucket_columns_and_totals() {
+        let invoice = Invoice {
+            customer_id: Some("c1".into()),
+            due_date: Some(date(2025, 1, 1)),
+            remaining_amount: Some("150".parse().unwrap()),
+            ..Default::default()
+        };
+        let report = AgeingReport::from_invoices(date(2025, 3, 1), &[invoice]);
+
+        let csv = ageing_table(&report).to_csv().unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Customer,Not due,0-30,31-60,61-90,90+,Total,On hold"
+        );
+        assert_eq!(lines[1], "c1,0.00,0.00,150.00,0.00,0.00,150.00,0.00");
+        assert_eq!(lines[2], "Total,0.00,0.00,150.00,0.00,0.00,150.00,0.00");
+    }
+
+    #[test]
+    fn test_vat_table_footer() {
+        let closing = vec![AccountBalance {
+            account_number: Some("2611".to_string()),
+            name: Some("Utgående moms 25%".to_string()),
+            balance: Some("-250".parse().unwrap()),
+        }];
+        let report = VatSummary::from_balances(date(2025, 1, 1), date(2025, 1, 31), &[], &closing);
+
+        let text = vat_table(&report).to_text();
+        assert!(text.contains("Utgående moms 25%"));
+        assert!(text.trim_end().ends_with("250.00"));
+    }
+
+    #[test]
+    fn test_budget_table_includes_unbudgeted_result() {
+        let budget: Budget = serde_json::from_str(
+            r#"{"name": "2025", "lines": [{"accounts": "3000-3999", "label": "Sales", "months": {"2025-01-01": 1000.0}}]}"#,
+        )
+        .unwrap();
+        let closing = vec![
+            AccountBalance {
+                account_number: Some("3001".to_string()),
+                name: None,
+                balance: Some("-1200".parse().unwrap()),
+            },
+            AccountBalance {
+                account_number: Some("6110".to_string()),
+                name: None,
+                balance: Some("300".parse().unwrap()),
+            },
+        ];
+        let pnl = ProfitAndLoss::from_balances(date(2025, 1, 1), date(2025, 1, 31), &[], &closing);
+        let report = BudgetVsActual::from_pnl(&budget, &pnl).unwrap();
+
+        let csv = budget_table(&report).to_csv().unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "Accounts,Name,Budget,Actual,Variance");
+        assert_eq!(lines[1], "3000-3999,Sales,1000.00,1200.00,200.00");
+        assert_eq!(lines[2], ",Not budgeted,0.00,-300.00,-300.00");
+        assert_eq!(lines[3], "Total,,1000.00,900.00,-100.00");
+    }
+}
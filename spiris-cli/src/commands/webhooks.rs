@@ -0,0 +1,273 @@
+//! `spiris-cli webhooks` - run a webhook receiver.
+//!
+//! The Spiris API does not expose an endpoint for registering webhook
+//! subscriptions, so the callback URL has to be configured with whatever
+//! service delivers the events. `listen` then receives the callbacks,
+//! verifies their HMAC signature and pipes each event to a user command.
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use spiris::webhooks::{WebhookConfig, WebhookEvent, WebhookHandler};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use tiny_http::{Header, Method, Response, Server};
+
+/// Environment variable holding the webhook signing secret.
+pub const SECRET_ENV: &str = "SPIRIS_WEBHOOK_SECRET";
+
+#[derive(Debug, Subcommand)]
+pub enum WebhooksCommand {
+    /// Receive webhook events and run a command for each one
+    ///
+    /// The event JSON is written to the command's stdin and the event id and
+    /// type are exported as SPIRIS_EVENT_ID and SPIRIS_EVENT_TYPE. A non-zero
+    /// exit status is answered with HTTP 500 so the sender can retry.
+    Listen {
+        /// Port to listen on
+        #[arg(long, default_value_t = 8787)]
+        port: u16,
+
+        /// Address to bind to
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: String,
+
+        /// Path that receives callbacks
+        #[arg(long, default_value = "/webhooks")]
+        path: String,
+
+        /// Shell command to run per event
+        #[arg(long)]
+        exec: String,
+
+        /// Signing secret used to verify signatures
+        #[arg(long, env = SECRET_ENV, hide_env_values = true)]
+        secret: String,
+
+        /// Header carrying the signature
+        #[arg(long, default_value = "X-Webhook-Signature")]
+        signature_header: String,
+    },
+}
+
+pub async fn run(command: WebhooksCommand) -> Result<()> {
+    match command {
+        WebhooksCommand::Listen {
+            port,
+            bind,
+            path,
+            exec,
+            secret,
+            signature_header,
+        } => {
+            let receiver = Receiver {
+                handler: WebhookHandler::new(
+                    WebhookConfig::new(secret).signature_header(signature_header),
+                ),
+                path,
+                exec,
+            };
+            let address = format!("{}:{}", bind, port);
+
+            tokio::task::spawn_blocking(move || receiver.listen(&address)).await?
+        }
+    }
+}
+
+struct Receiver {
+    handler: WebhookHandler,
+    path: String,
+    exec: String,
+}
+
+impl Receiver {
+    fn listen(&self, address: &str) -> Result<()> {
+        let server = Server::http(address)
+            .map_err(|e| anyhow::anyhow!("Failed to listen on {}: {}", address, e))?;
+
+        eprintln!("Listening for webhooks on http://{}{}", address, self.path);
+        eprintln!("Configure this URL with the service sending the events.");
+
+        for mut request in server.incoming_requests() {
+            let mut body = Vec::new();
+            let read = request.as_reader().read_to_end(&mut body);
+
+            let signature = request
+                .headers()
+                .iter()
+                .find(|h| {
+                    h.field
+                        .as_str()
+                        .as_str()
+                        .eq_ignore_ascii_case(self.handler.signature_header())
+                })
+                .map(|h| h.value.as_str().to_string());
+
+            let (status, message) = match read {
+                Ok(_) => self.handle(request.method(), request.url(), signature.as_deref(), &body),
+                Err(e) => (400, format!("Failed to read body: {}", e)),
+            };
+
+            eprintln!(
+                "{} {} -> {} {}",
+                request.method(),
+                request.url(),
+                status,
+                message
+            );
+
+            let response = Response::from_string(message)
+                .with_status_code(status)
+                .with_header(
+                    Header::from_bytes("Content-Type", "text/plain; charset=utf-8")
+                        .expect("static header is valid"),
+                );
+            if let Err(e) = request.respond(response) {
+                eprintln!("Failed to send response: {}", e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate a callback and run the command, returning the HTTP status and message.
+    fn handle(
+        &self,
+        method: &Method,
+        url: &str,
+        signature: Option<&str>,
+        body: &[u8],
+    ) -> (u16, String) {
+        let path = url.split('?').next().unwrap_or(url);
+        if path != self.path {
+            return (404, "Not found".to_string());
+        }
+        if *method != Method::Post {
+            return (405, "Method not allowed".to_string());
+        }
+        let Some(signature) = signature else {
+            return (401, "Missing signature".to_string());
+        };
+
+        let event = match self.handler.verify_and_parse(body, signature) {
+            Ok(event) => event,
+            Err(spiris::Error::AuthError(e)) => return (401, e),
+            Err(e) => return (400, e.to_string()),
+        };
+
+        match self.dispatch(&event, body) {
+            Ok(()) => (200, format!("Handled {}", event.event_type)),
+            Err(e) => (500, format!("{:#}", e)),
+        }
+    }
+
+    /// Run the user command with the event on stdin.
+    fn dispatch(&self, event: &WebhookEvent, body: &[u8]) -> Result<()> {
+        let mut child = shell(&self.exec)
+            .env("SPIRIS_EVENT_ID", &event.id)
+            .env("SPIRIS_EVENT_TYPE", &event.event_type)
+            .stdin(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run '{}'", self.exec))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            // A handler that ignores stdin may exit before reading it
+            let _ = stdin.write_all(body);
+        }
+
+        let status = child.wait()?;
+        if !status.success() {
+            anyhow::bail!("'{}' exited with {}", self.exec, status);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    const SECRET: &str = "test_secret";
+    const EVENT: &[u8] =
+        br#"{"id":"evt_1","eventType":"invoice.created","timestamp":1700000000,"data":{}}"#;
+
+    fn receiver(exec: &str) -> Receiver {
+        Receiver {
+            handler: WebhookHandler::new(WebhookConfig::new(SECRET)),
+            path: "/webhooks".to_string(),
+            exec: exec.to_string(),
+        }
+    }
+
+    fn sign(body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(body);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn test_handle_runs_command_with_event() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("event.json");
+        let exec = format!(
+            "test \"$SPIRIS_EVENT_TYPE\" = invoice.created && cat > {}",
+            out.display()
+        );
+
+        let (status, _) =
+            receiver(&exec).handle(&Method::Post, "/webhooks", Some(&sign(EVENT)), EVENT);
+
+        assert_eq!(status, 200);
+        assert_eq!(std::fs::read(out).unwrap(), EVENT);
+    }
+
+    #[test]
+    fn test_handle_rejects_bad_signature() {
+        let (status, _) =
+            receiver("true").handle(&Method::Post, "/webhooks", Some("deadbeef"), EVENT);
+        assert_eq!(status, 401);
+
+        let (status, _) = receiver("true").handle(&Method::Post, "/webhooks", None, EVENT);
+        assert_eq!(status, 401);
+    }
+
+    #[test]
+    fn test_handle_reports_command_failure() {
+        let (status, message) =
+            receiver("exit 3").handle(&Method::Post, "/webhooks", Some(&sign(EVENT)), EVENT);
+
+        assert_eq!(status, 500);
+        assert!(message.contains("exit"));
+    }
+
+    #[test]
+    fn test_handle_checks_path_and_method() {
+        let signature = sign(EVENT);
+        let r = receiver("true");
+
+        assert_eq!(
+            r.handle(&Method::Post, "/other", Some(&signature), EVENT).0,
+            404
+        );
+        assert_eq!(
+            r.handle(&Method::Get, "/webhooks", Some(&signature), EVENT)
+                .0,
+            405
+        );
+    }
+}
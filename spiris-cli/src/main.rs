@@ -0,0 +1,133 @@
+//! Spiris CLI - command line access to the Spiris Bokföring och Fakturering API
+//!
+//! ## Authentication
+//!
+//! The CLI reads an access token from `SPIRIS_ACCESS_TOKEN`, or from the
+//! `.spiris_token.json` file written by spiris-tui after signing in
+//! (override with `--token-file`).
+//!
+//! ## Commands
+//!
+//! - `report aged-receivables [--as-of DATE]`
+//! - `report pnl [--period PERIOD]`
+//! - `report vat-summary [--period PERIOD]`
+//! - `report budget --budget FILE [--period PERIOD]` compares a budget JSON
+//!   file with the actual result
+//!
+//! All reports accept `--format table|json|csv`.
+//!
+//! - `customers diff --file FILE` shows the changes needed to match a CSV or
+//!   JSON file, `customers apply --file FILE` makes them
+//! - `webhooks listen --exec CMD [--port PORT]`
+//! - `doctor` checks the token, API access and local setup
+
+mod commands;
+mod output;
+mod period;
+mod session;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use commands::customers::CustomersCommand;
+use commands::report::ReportCommand;
+use commands::webhooks::WebhooksCommand;
+use output::OutputFormat;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "spiris-cli",
+    version,
+    about = "Command line tool for Spiris Bokföring och Fakturering"
+)]
+struct Cli {
+    /// Output format
+    #[arg(long, short, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    /// Token file (defaults to .spiris_token.json in the current directory)
+    #[arg(long, global = true, env = "SPIRIS_TOKEN_FILE")]
+    token_file: Option<PathBuf>,
+
+    /// Override the API base URL
+    #[arg(long, global = true, env = "SPIRIS_BASE_URL")]
+    base_url: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Financial reports
+    #[command(subcommand)]
+    Report(ReportCommand),
+
+    /// Plan and apply customer master data from a file
+    #[command(subcommand)]
+    Customers(CustomersCommand),
+
+    /// Webhook receiver
+    #[command(subcommand)]
+    Webhooks(WebhooksCommand),
+
+    /// Diagnose token, connectivity and configuration problems
+    Doctor,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Report(command) => {
+            let client = session::build_client(cli.token_file.as_deref(), cli.base_url.as_deref())?;
+            commands::report::run(&client, command, cli.format).await
+        }
+        Command::Customers(command) => {
+            let client = session::build_client(cli.token_file.as_deref(), cli.base_url.as_deref())?;
+            commands::customers::run(&client, command, cli.format).await
+        }
+        Command::Webhooks(command) => commands::webhooks::run(command).await,
+        Command::Doctor => {
+            commands::doctor::run(
+                cli.token_file.as_deref(),
+                cli.base_url.as_deref(),
+                cli.format,
+            )
+            .await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_report_command() {
+        let cli = Cli::try_parse_from([
+            "spiris-cli",
+            "report",
+            "pnl",
+            "--period",
+            "2025-Q1",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        assert_eq!(cli.format, OutputFormat::Json);
+        match cli.command {
+            Command::Report(ReportCommand::Pnl { period }) => {
+                assert_eq!(period.unwrap().to_string(), "2025-01-01..2025-03-31");
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Output rendering for table, JSON and CSV formats.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use spiris::Money;
+
+/// Output format selected with `--format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned table for terminals
+    Table,
+    /// Pretty-printed JSON of the full report
+    Json,
+    /// CSV rows matching the table columns
+    Csv,
+}
+
+/// Column alignment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// A simple table of string cells.
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    headers: Vec<(String, Align)>,
+    rows: Vec<Vec<String>>,
+    footer: Option<Vec<String>>,
+}
+
+impl Table {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a column header.
+    pub fn column(mut self, name: &str, align: Align) -> Self {
+        self.headers.push((name.to_string(), align));
+        self
+    }
+
+    /// Add a data row.
+    pub fn row(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+    }
+
+    /// Set a totals row, rendered below a separator.
+    pub fn footer(&mut self, cells: Vec<String>) {
+        self.footer = Some(cells);
+    }
+
+    /// Render as an aligned text table.
+    pub fn to_text(&self) -> String {
+        let mut widths: Vec<usize> = self
+            .headers
+            .iter()
+            .map(|(h, _)| h.chars().count())
+            .collect();
+        for row in self.rows.iter().chain(self.footer.iter()) {
+            for (i, cell) in row.iter().enumerate() {
+                if let Some(w) = widths.get_mut(i) {
+                    *w = (*w).max(cell.chars().count());
+                }
+            }
+        }
+
+        let format_row = |cells: &[String]| -> String {
+            cells
+                .iter()
+                .zip(self.headers.iter().zip(&widths))
+                .map(|(cell, ((_, align), width))| match align {
+                    Align::Left => format!("{:<width$}", cell, width = width),
+                    Align::Right => format!("{:>width$}", cell, width = width),
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        };
+        let separator = widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        let headers: Vec<String> = self.headers.iter().map(|(h, _)| h.clone()).collect();
+        let mut lines = vec![format_row(&headers), separator.clone()];
+        lines.extend(self.rows.iter().map(|r| format_row(r)));
+        if let Some(footer) = &self.footer {
+            lines.push(separator);
+            lines.push(format_row(footer));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Render as CSV including the header and totals rows.
+    pub fn to_csv(&self) -> Result<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(self.headers.iter().map(|(h, _)| h))?;
+        for row in self.rows.iter().chain(self.footer.iter()) {
+            writer.write_record(row)?;
+        }
+        Ok(String::from_utf8(writer.into_inner()?)?)
+    }
+}
+
+/// Print a report in the requested format.
+///
+/// JSON output serializes the full report so no detail is lost; table and
+/// CSV output use the flattened `table`.
+pub fn emit<T: Serialize>(format: OutputFormat, report: &T, table: &Table) -> Result<()> {
+    match format {
+        OutputFormat::Table => print!("{}", table.to_text()),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(report)?),
+        OutputFormat::Csv => print!("{}", table.to_csv()?),
+    }
+    Ok(())
+}
+
+/// Format a monetary amount with two decimals.
+pub fn amount(value: Money) -> String {
+    format!("{:.2}", value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Table {
+        let mut table = Table::new()
+            .column("Customer", Align::Left)
+            .column("Amount", Align::Right);
+        table.row(vec!["Acme".into(), "100.00".into()]);
+        table.row(vec!["Beta Industries".into(), "5.50".into()]);
+        table.footer(vec!["Total".into(), "105.50".into()]);
+        table
+    }
+
+    #[test]
+    fn test_table_text_alignment() {
+        let text = sample().to_text();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "Customer         Amount");
+        assert_eq!(lines[2], "Acme             100.00");
+        assert_eq!(lines[3], "Beta Industries    5.50");
+        assert_eq!(lines[5], "Total            105.50");
+    }
+
+    #[test]
+    fn test_table_csv() {
+        let csv = sample().to_csv().unwrap();
+        assert_eq!(
+            csv,
+            "Customer,Amount\nAcme,100.00\nBeta Industries,5.50\nTotal,105.50\n"
+        );
+    }
+
+    #[test]
+    fn test_amount_formatting() {
+        assert_eq!(amount("1234.5".parse().unwrap()), "1234.50");
+    }
+}
@@ -0,0 +1,163 @@
+//! Reporting period parsing.
+//!
+//! Accepted forms:
+//!
+//! - `2025` - a calendar year
+//! - `2025-Q1` - a quarter
+//! - `2025-03` - a month
+//! - `2025-01-01..2025-03-31` - an explicit inclusive date range
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{Datelike, NaiveDate};
+use std::fmt;
+use std::str::FromStr;
+
+/// An inclusive date range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl Period {
+    /// The month containing `date`.
+    pub fn month_of(date: NaiveDate) -> Self {
+        let start = NaiveDate::from_ymd_opt(date.year(), date.month(), 1).unwrap();
+        Self {
+            start,
+            end: last_day_of_month(date.year(), date.month()),
+        }
+    }
+}
+
+fn last_day_of_month(year: i32, month: u32) -> NaiveDate {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|d| d.pred_opt())
+        .unwrap()
+}
+
+fn month_range(year: i32, first: u32, last: u32) -> Result<Period> {
+    let start = NaiveDate::from_ymd_opt(year, first, 1)
+        .ok_or_else(|| anyhow!("invalid month {}-{:02}", year, first))?;
+    Ok(Period {
+        start,
+        end: last_day_of_month(year, last),
+    })
+}
+
+impl FromStr for Period {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+
+        if let Some((from, to)) = s.split_once("..") {
+            let start = NaiveDate::parse_from_str(from, "%Y-%m-%d")
+                .with_context(|| format!("invalid start date '{}'", from))?;
+            let end = NaiveDate::parse_from_str(to, "%Y-%m-%d")
+                .with_context(|| format!("invalid end date '{}'", to))?;
+            if end < start {
+                bail!("period end {} is before start {}", end, start);
+            }
+            return Ok(Period { start, end });
+        }
+
+        let parts: Vec<&str> = s.split('-').collect();
+        let year: i32 = parts[0]
+            .parse()
+            .with_context(|| format!("invalid period '{}'", s))?;
+
+        match parts.as_slice() {
+            [_] => month_range(year, 1, 12),
+            [_, q] if q.starts_with(['Q', 'q']) => {
+                let quarter: u32 = q[1..]
+                    .parse()
+                    .with_context(|| format!("invalid quarter '{}'", q))?;
+                if !(1..=4).contains(&quarter) {
+                    bail!("quarter must be between Q1 and Q4");
+                }
+                month_range(year, quarter * 3 - 2, quarter * 3)
+            }
+            [_, m] => {
+                let month: u32 = m
+                    .parse()
+                    .with_context(|| format!("invalid month '{}'", m))?;
+                if !(1..=12).contains(&month) {
+                    bail!("month must be between 01 and 12");
+                }
+                month_range(year, month, month)
+            }
+            _ => bail!(
+                "invalid period '{}', expected YYYY, YYYY-Qn, YYYY-MM or YYYY-MM-DD..YYYY-MM-DD",
+                s
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_year() {
+        let p: Period = "2025".parse().unwrap();
+        assert_eq!(p.start, date(2025, 1, 1));
+        assert_eq!(p.end, date(2025, 12, 31));
+    }
+
+    #[test]
+    fn test_parse_quarter() {
+        let p: Period = "2024-Q1".parse().unwrap();
+        assert_eq!(p.start, date(2024, 1, 1));
+        assert_eq!(p.end, date(2024, 3, 31));
+
+        let p: Period = "2024-q4".parse().unwrap();
+        assert_eq!(p.end, date(2024, 12, 31));
+    }
+
+    #[test]
+    fn test_parse_month() {
+        let p: Period = "2024-02".parse().unwrap();
+        assert_eq!(p.start, date(2024, 2, 1));
+        assert_eq!(p.end, date(2024, 2, 29));
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let p: Period = "2025-01-15..2025-02-14".parse().unwrap();
+        assert_eq!(p.start, date(2025, 1, 15));
+        assert_eq!(p.end, date(2025, 2, 14));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!("".parse::<Period>().is_err());
+        assert!("2025-13".parse::<Period>().is_err());
+        assert!("2025-Q5".parse::<Period>().is_err());
+        assert!("2025-02-01..2025-01-01".parse::<Period>().is_err());
+        assert!("last-month".parse::<Period>().is_err());
+    }
+
+    #[test]
+    fn test_month_of() {
+        let p = Period::month_of(date(2025, 12, 10));
+        assert_eq!(p.start, date(2025, 12, 1));
+        assert_eq!(p.end, date(2025, 12, 31));
+    }
+}
@@ -0,0 +1,60 @@
+//! Access token loading and client construction.
+
+use anyhow::{bail, Context, Result};
+use spiris::{AccessToken, Client, ClientConfig};
+use std::path::{Path, PathBuf};
+
+/// Environment variable holding a raw access token.
+pub const TOKEN_ENV: &str = "SPIRIS_ACCESS_TOKEN";
+
+/// Default token file, shared with spiris-tui.
+pub const DEFAULT_TOKEN_FILE: &str = ".spiris_token.json";
+
+/// Where the access token was loaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    Environment,
+    File(PathBuf),
+}
+
+/// Load the access token from the environment or a token file.
+///
+/// `SPIRIS_ACCESS_TOKEN` takes precedence. Otherwise the token file written
+/// by spiris-tui after the OAuth flow is used.
+pub fn load_token(token_file: Option<&Path>) -> Result<(AccessToken, TokenSource)> {
+    if let Ok(raw) = std::env::var(TOKEN_ENV) {
+        if !raw.trim().is_empty() {
+            return Ok((
+                AccessToken::new(raw.trim().to_string(), 3600, None),
+                TokenSource::Environment,
+            ));
+        }
+    }
+
+    let path = token_file
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_TOKEN_FILE));
+    if !path.exists() {
+        bail!(
+            "No access token found. Set {} or authenticate with spiris-tui to create {}",
+            TOKEN_ENV,
+            path.display()
+        );
+    }
+
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let token: AccessToken = serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid token file {}", path.display()))?;
+    Ok((token, TokenSource::File(path)))
+}
+
+/// Build an API client from the loaded token.
+pub fn build_client(token_file: Option<&Path>, base_url: Option<&str>) -> Result<Client> {
+    let (token, _) = load_token(token_file)?;
+    let mut config = ClientConfig::new();
+    if let Some(url) = base_url {
+        config = config.base_url(url);
+    }
+    Ok(Client::with_config(token, config))
+}
@@ -3,33 +3,67 @@
 //! This module contains the main application state (`App`) and all the logic
 //! for handling user input, navigating screens, and managing data.
 
-use anyhow::Result;
-use spiris::{AccessToken, Article, Client, Customer, Invoice, InvoiceRow, PaginationParams};
+use anyhow::{Context, Result};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use spiris::endpoints::approvals::ApprovalAction;
+use spiris::{
+    AccessToken, Article, ArticleId, Client, Customer, CustomerId, Invoice, InvoiceId, InvoiceRow,
+    Money, PaginationParams,
+};
 use std::path::PathBuf;
 use crate::config::Config;
+use crate::paging::{PageRequest, PagedWindow};
+use crate::macros::{MacroKey, MacroKeys, MacroPrompt, MacroState};
+use crate::screens::approvals::{ApprovalsState, Decision};
+use crate::screens::cash_flow::CashFlowState;
+use crate::screens::dunning::DunningState;
+use crate::screens::fiscal_years::FiscalYearsState;
+use crate::screens::invoice_wizard::{InvoiceWizardState, WizardStep};
+use crate::screens::reconciliation::ReconciliationState;
+use crate::screens::reports::ReportsState;
+use crate::screens::setup::{Check, PendingAuth, SetupState, SetupStep};
+use crate::tabs::{TabRecord, Tabs};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Screen {
     Home,
     Dashboard,
-    Auth,
+    Setup,
     Customers,
     CustomerCreate,
-    CustomerEdit(String),
-    CustomerDetail(String),
+    CustomerEdit(CustomerId),
+    CustomerDetail(CustomerId),
     Invoices,
     InvoiceCreate,
-    InvoiceEdit(String),
-    InvoiceDetail(String),
+    InvoiceEdit(InvoiceId),
+    InvoiceDetail(InvoiceId),
+    InvoiceWizard,
     Articles,
     ArticleCreate,
-    ArticleEdit(String),
-    ArticleDetail(String),
+    ArticleEdit(ArticleId),
+    ArticleDetail(ArticleId),
     Search,
     Export,
+    CashFlow,
+    Approvals,
+    Reconciliation,
+    Dunning,
+    FiscalYears,
+    Reports,
     Help,
 }
 
+/// The company the client is logged in to.
+#[derive(Debug, Clone, Default)]
+pub struct ActiveCompany {
+    pub name: String,
+    /// Accent color from config.toml
+    pub accent: Option<ratatui::style::Color>,
+    /// Changes are blocked
+    pub read_only: bool,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum InputMode {
     Normal,
@@ -43,6 +77,8 @@ pub struct App {
     pub client: Option<Client>,
     pub token: Option<AccessToken>,
     pub config: Config,
+    /// Company of the logged in account, once loaded
+    pub company: Option<ActiveCompany>,
 
     // Screen state
     pub customers: Vec<Customer>,
@@ -65,6 +101,8 @@ pub struct App {
     pub current_page: u32,
     pub total_pages: u32,
     pub page_size: u32,
+    /// Invoice pages loaded while scrolling
+    pub invoice_window: PagedWindow,
 
     // Search state
     pub search_query: String,
@@ -90,8 +128,8 @@ pub struct App {
     pub stats_total_invoices: usize,
     pub stats_total_articles: usize,
     pub stats_active_customers: usize,
-    pub stats_total_revenue: f64,
-    pub stats_average_invoice: f64,
+    pub stats_total_revenue: Money,
+    pub stats_average_invoice: Money,
     pub stats_recent_invoices_7d: usize,
     pub stats_recent_invoices_30d: usize,
 
@@ -113,9 +151,40 @@ pub struct App {
     // Confirmation state
     pub confirm_delete: Option<(String, String)>, // (entity_type, entity_id)
 
-    // OAuth state
-    pub oauth_url: Option<String>,
-    pub oauth_waiting: bool,
+    // Setup screen state
+    pub setup: SetupState,
+    /// Showing sample data without an API client
+    pub demo: bool,
+
+    // Cash flow screen state
+    pub cash_flow: CashFlowState,
+
+    // Approvals screen state
+    pub approvals: ApprovalsState,
+
+    // Reconciliation screen state
+    pub reconciliation: ReconciliationState,
+
+    // Dunning screen state
+    pub dunning: DunningState,
+
+    // Invoice wizard state
+    pub invoice_wizard: InvoiceWizardState,
+
+    // Keyboard macro recording and playback
+    pub macros: MacroState,
+
+    // Fiscal years screen state, also used for period lock checks
+    pub fiscal_years: FiscalYearsState,
+
+    // Budget vs actual report
+    pub reports: ReportsState,
+
+    // Customers, invoices and articles open in tabs
+    pub tabs: Tabs,
+
+    // Write the next drawn screen to a text file
+    pub screen_dump_requested: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -168,13 +237,18 @@ impl App {
         // Load configuration
         let config = Config::load().unwrap_or_default();
 
+        let setup = SetupState::new(&config.auth);
         let screen = if token.is_some() {
             Screen::Home
         } else {
-            Screen::Auth
+            Screen::Setup
         };
 
         let page_size = config.pagination.default_page_size;
+        let invoice_window = PagedWindow {
+            max_items: config.pagination.max_items as usize,
+            ..Default::default()
+        };
         let export_format = match config.export.default_format.as_str() {
             "json" => ExportFormat::Json,
             _ => ExportFormat::Csv,
@@ -187,6 +261,7 @@ impl App {
             client,
             token,
             config,
+            company: None,
             customers: Vec::new(),
             selected_customer: 0,
             invoices: Vec::new(),
@@ -201,6 +276,7 @@ impl App {
             current_page: 1,
             total_pages: 1,
             page_size,
+            invoice_window,
             search_query: String::new(),
             search_results_customers: Vec::new(),
             search_results_invoices: Vec::new(),
@@ -218,8 +294,8 @@ impl App {
             stats_total_invoices: 0,
             stats_total_articles: 0,
             stats_active_customers: 0,
-            stats_total_revenue: 0.0,
-            stats_average_invoice: 0.0,
+            stats_total_revenue: Money::default(),
+            stats_average_invoice: Money::default(),
             stats_recent_invoices_7d: 0,
             stats_recent_invoices_30d: 0,
             input: String::new(),
@@ -232,13 +308,240 @@ impl App {
             loading: false,
             needs_refresh: false,
             confirm_delete: None,
-            oauth_url: None,
-            oauth_waiting: false,
+            setup,
+            demo: false,
+            cash_flow: CashFlowState::default(),
+            approvals: ApprovalsState::default(),
+            reconciliation: ReconciliationState::default(),
+            dunning: DunningState::default(),
+            invoice_wizard: InvoiceWizardState::default(),
+            macros: MacroState::default(),
+            fiscal_years: FiscalYearsState::default(),
+            reports: ReportsState::default(),
+            tabs: Tabs::default(),
+            screen_dump_requested: false,
+        }
+    }
+
+    /// Whether accessibility mode is on
+    pub fn accessible(&self) -> bool {
+        self.config.accessibility.enabled
+    }
+
+    /// Handle a mouse event: the wheel moves the selection, and clicking a
+    /// row in the customer, invoice or article list selects it, or opens it
+    /// if it is already selected.
+    ///
+    /// `area` is the whole terminal.
+    pub async fn handle_mouse(&mut self, mouse: MouseEvent, area: Rect) -> Result<()> {
+        if self.macros.prompt.is_some() || self.confirm_delete.is_some() || self.loading {
+            return Ok(());
+        }
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.handle_up(),
+            MouseEventKind::ScrollDown => self.handle_down(),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let (len, selected) = match self.screen {
+                    Screen::Customers => (self.customers.len(), self.selected_customer),
+                    Screen::Invoices => (self.invoices.len(), self.selected_invoice),
+                    Screen::Articles => (self.articles.len(), self.selected_article),
+                    _ => return Ok(()),
+                };
+                let list = crate::ui::content_area(area);
+                let row_height = self.config.accessibility.row_height;
+                let Some(index) = crate::ui::list_row_at(list, mouse.row, len, selected, row_height) else {
+                    return Ok(());
+                };
+                if index == selected {
+                    return self.handle_enter().await;
+                }
+                match self.screen {
+                    Screen::Customers => self.selected_customer = index,
+                    Screen::Invoices => {
+                        self.selected_invoice = index;
+                        self.invoice_window.on_select(index, self.invoices.len());
+                    }
+                    _ => self.selected_article = index,
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Write a plain-text copy of the screen to the export directory
+    pub fn save_screen_dump(&mut self, text: &str) {
+        let result = self.export_dir().and_then(|dir| {
+            let path = dir.join(format!("screen_{}.txt", chrono::Local::now().format("%Y%m%d_%H%M%S")));
+            std::fs::write(&path, text)?;
+            Ok(path)
+        });
+        match result {
+            Ok(path) => self.set_status(format!("Screen saved to {}", path.display())),
+            Err(e) => self.set_error(format!("Failed to save screen: {}", e)),
         }
     }
 
     pub fn can_quit(&self) -> bool {
-        self.input_mode == InputMode::Normal
+        self.input_mode == InputMode::Normal && self.macros.prompt.is_none()
+    }
+
+    /// Handle a key press, recording it if a macro is being recorded.
+    ///
+    /// `M` and `@` only control macros outside text input, so they can still
+    /// be typed into forms and searches.
+    pub async fn handle_key(&mut self, key: MacroKey) -> Result<()> {
+        if let Some(prompt) = self.macros.prompt.take() {
+            return self.handle_macro_prompt(prompt, key).await;
+        }
+
+        let typing = self.input_mode == InputMode::Editing || self.search_input_mode;
+        match key {
+            MacroKey::Char('M') if !typing && !self.macros.playing => {
+                match self.macros.recording.take() {
+                    None => {
+                        self.macros.recording = Some(Vec::new());
+                        self.set_status("Recording macro... press M to stop".to_string());
+                    }
+                    Some(keys) if keys.is_empty() => self.set_status("Macro recording cancelled".to_string()),
+                    Some(keys) => {
+                        self.macros.prompt = Some(MacroPrompt::Bind(keys));
+                        self.set_status("Press a key to bind the macro to (ESC: discard)".to_string());
+                    }
+                }
+                Ok(())
+            }
+            MacroKey::Char('@') if !typing && !self.macros.playing => {
+                self.macros.prompt = Some(MacroPrompt::Play);
+                self.set_status("Press the key of the macro to play".to_string());
+                Ok(())
+            }
+            key => {
+                if let Some(recording) = self.macros.recording.as_mut() {
+                    if !self.macros.playing {
+                        recording.push(key);
+                    }
+                }
+                self.dispatch_key(key).await
+            }
+        }
+    }
+
+    async fn dispatch_key(&mut self, key: MacroKey) -> Result<()> {
+        match key {
+            MacroKey::Esc => self.handle_escape(),
+            MacroKey::Enter => self.handle_enter().await?,
+            MacroKey::Tab => self.next_screen(),
+            MacroKey::BackTab => self.previous_screen(),
+            MacroKey::Up => self.handle_up(),
+            MacroKey::Down => self.handle_down(),
+            MacroKey::Left => self.handle_left(),
+            MacroKey::Right => self.handle_right(),
+            MacroKey::Char(c) => self.handle_char(c),
+            MacroKey::Alt(c) => self.handle_alt(c),
+            MacroKey::Backspace => self.handle_backspace(),
+        }
+        Ok(())
+    }
+
+    /// Alt+1..9 switches to an open tab.
+    fn handle_alt(&mut self, c: char) {
+        let typing = self.input_mode == InputMode::Editing || self.search_input_mode;
+        let Some(number) = c.to_digit(10).filter(|n| *n > 0) else {
+            return;
+        };
+        if typing || self.confirm_delete.is_some() {
+            return;
+        }
+        match self.tabs.get(number as usize) {
+            Some(tab) => {
+                if let Some(screen) = tab.record.screen() {
+                    self.previous_screen = tab.back.clone();
+                    self.screen = screen;
+                    self.error_message = None;
+                }
+            }
+            None => self.set_status(format!("No tab {}", number)),
+        }
+    }
+
+    /// Show a record's detail screen and open it in a tab.
+    fn open_detail(&mut self, record: TabRecord) {
+        let Some(screen) = record.screen() else {
+            return;
+        };
+        let back = self.screen.clone();
+        if !self.tabs.open(record, Some(back.clone())) {
+            self.set_status(format!(
+                "{} tabs open - press w on a tab to close it",
+                crate::tabs::MAX_TABS
+            ));
+        }
+        self.previous_screen = Some(back);
+        self.screen = screen;
+    }
+
+    /// Customer with `id` from the loaded list or an open tab.
+    pub fn customer(&self, id: &str) -> Option<&Customer> {
+        self.customers
+            .iter()
+            .find(|c| c.id.as_deref() == Some(id))
+            .or_else(|| self.tabs.customer(id))
+    }
+
+    /// Invoice with `id` from the loaded list or an open tab.
+    pub fn invoice(&self, id: &str) -> Option<&Invoice> {
+        self.invoices
+            .iter()
+            .find(|i| i.id.as_deref() == Some(id))
+            .or_else(|| self.tabs.invoice(id))
+    }
+
+    /// Article with `id` from the loaded list or an open tab.
+    pub fn article(&self, id: &str) -> Option<&Article> {
+        self.articles
+            .iter()
+            .find(|a| a.id.as_deref() == Some(id))
+            .or_else(|| self.tabs.article(id))
+    }
+
+    async fn handle_macro_prompt(&mut self, prompt: MacroPrompt, key: MacroKey) -> Result<()> {
+        let MacroKey::Char(c) = key else {
+            self.set_status("Macro cancelled".to_string());
+            return Ok(());
+        };
+
+        match prompt {
+            MacroPrompt::Bind(keys) => {
+                let count = keys.len();
+                self.config.macros.insert(c.to_string(), MacroKeys(keys));
+                match self.config.save() {
+                    Ok(()) => self.set_status(format!("Macro with {} key(s) saved to @{}", count, c)),
+                    Err(e) => self.set_error(format!("Macro bound to @{} but not saved: {}", c, e)),
+                }
+            }
+            MacroPrompt::Play => {
+                let Some(MacroKeys(keys)) = self.config.macros.get(&c.to_string()).cloned() else {
+                    self.set_error(format!("No macro bound to @{}", c));
+                    return Ok(());
+                };
+                self.macros.playing = true;
+                let mut result = Ok(());
+                for key in keys {
+                    // Let screens load before the next key acts on them
+                    result = match self.dispatch_key(key).await {
+                        Ok(()) => self.refresh_if_needed().await,
+                        Err(e) => Err(e),
+                    };
+                    if result.is_err() {
+                        break;
+                    }
+                }
+                self.macros.playing = false;
+                result?;
+            }
+        }
+        Ok(())
     }
 
     /// Get context-aware keyboard shortcuts for status bar
@@ -261,6 +564,11 @@ impl App {
         } else if self.input_mode == InputMode::Editing {
             self.input_mode = InputMode::Normal;
             self.input.clear();
+            self.approvals.decision = None;
+        } else if self.screen == Screen::Dunning && self.dunning.confirming {
+            self.dunning.confirming = false;
+        } else if self.screen == Screen::InvoiceWizard && self.invoice_wizard.back() {
+            // Stepped back within the wizard
         } else if let Some(prev) = self.previous_screen.take() {
             self.screen = prev;
             self.error_message = None;
@@ -270,6 +578,9 @@ impl App {
     }
 
     pub async fn handle_enter(&mut self) -> Result<()> {
+        if self.screen == Screen::Setup {
+            return self.handle_setup_enter().await;
+        }
         if self.input_mode == InputMode::Editing {
             // Validate current input before proceeding
             if !self.validate_current_input() {
@@ -291,40 +602,42 @@ impl App {
                 Screen::Home => self.handle_home_enter(),
                 Screen::Dashboard => self.handle_dashboard_enter().await?,
                 Screen::Customers => {
-                    if !self.customers.is_empty() {
-                        let customer = &self.customers[self.selected_customer];
-                        if let Some(id) = &customer.id {
-                            self.previous_screen = Some(Screen::Customers);
-                            self.screen = Screen::CustomerDetail(id.clone());
-                        }
+                    if let Some(customer) = self.customers.get(self.selected_customer) {
+                        self.open_detail(TabRecord::Customer(customer.clone()));
                     }
                 }
                 Screen::Invoices => {
-                    if !self.invoices.is_empty() {
-                        let invoice = &self.invoices[self.selected_invoice];
-                        if let Some(id) = &invoice.id {
-                            self.previous_screen = Some(Screen::Invoices);
-                            self.screen = Screen::InvoiceDetail(id.clone());
-                        }
+                    if let Some(invoice) = self.invoices.get(self.selected_invoice) {
+                        self.open_detail(TabRecord::Invoice(invoice.clone()));
                     }
                 }
                 Screen::Articles => {
-                    if !self.articles.is_empty() {
-                        let article = &self.articles[self.selected_article];
-                        if let Some(id) = &article.id {
-                            self.previous_screen = Some(Screen::Articles);
-                            self.screen = Screen::ArticleDetail(id.clone());
-                        }
-                    }
-                }
-                Screen::Auth => {
-                    if !self.oauth_waiting {
-                        self.start_oauth().await?;
+                    if let Some(article) = self.articles.get(self.selected_article) {
+                        self.open_detail(TabRecord::Article(article.clone()));
                     }
                 }
                 Screen::Search => {
                     self.perform_search().await?;
                 }
+                Screen::Reconciliation => {
+                    self.confirm_reconciliation_match().await?;
+                }
+                Screen::Dunning => {
+                    if self.dunning.confirming {
+                        self.run_dunning().await?;
+                    } else if self.dunning.included().is_empty() {
+                        self.set_error("No reminders to send".to_string());
+                    } else {
+                        self.dunning.confirming = true;
+                    }
+                }
+                Screen::InvoiceWizard => {
+                    if self.invoice_wizard.step == WizardStep::Send {
+                        self.finish_invoice_wizard().await?;
+                    } else if let Err(message) = self.invoice_wizard.advance() {
+                        self.set_error(message);
+                    }
+                }
                 Screen::Export => {
                     // Toggle format or export based on selection
                     match self.export_selection {
@@ -356,7 +669,32 @@ impl App {
             3 => self.screen = Screen::Articles,
             4 => self.screen = Screen::Search,
             5 => self.screen = Screen::Export,
-            6 => self.screen = Screen::Help,
+            6 => {
+                self.screen = Screen::CashFlow;
+                self.needs_refresh = true;
+            }
+            7 => {
+                self.screen = Screen::Approvals;
+                self.needs_refresh = true;
+            }
+            8 => self.screen = Screen::Reconciliation,
+            9 => {
+                self.screen = Screen::Dunning;
+                self.needs_refresh = true;
+            }
+            10 => {
+                self.screen = Screen::FiscalYears;
+                self.needs_refresh = true;
+            }
+            11 => {
+                self.screen = Screen::InvoiceWizard;
+                self.needs_refresh = true;
+            }
+            12 => {
+                self.screen = Screen::Reports;
+                self.needs_refresh = true;
+            }
+            13 => self.screen = Screen::Help,
             _ => {}
         }
     }
@@ -372,6 +710,7 @@ impl App {
                 if self.selected_invoice > 0 {
                     self.selected_invoice -= 1;
                 }
+                self.invoice_window.on_select(self.selected_invoice, self.invoices.len());
             }
             Screen::Articles if !self.articles.is_empty() => {
                 if self.selected_article > 0 {
@@ -388,6 +727,11 @@ impl App {
                     self.export_selection -= 1;
                 }
             }
+            Screen::Approvals => self.approvals.select_previous(),
+            Screen::Reconciliation => self.reconciliation.select_previous(),
+            Screen::Dunning if !self.dunning.confirming => self.dunning.select_previous(),
+            Screen::InvoiceWizard => self.invoice_wizard.select_previous(),
+            Screen::FiscalYears => self.fiscal_years.select_previous(),
             _ => {}
         }
     }
@@ -403,6 +747,7 @@ impl App {
                 if self.selected_invoice < self.invoices.len() - 1 {
                     self.selected_invoice += 1;
                 }
+                self.invoice_window.on_select(self.selected_invoice, self.invoices.len());
             }
             Screen::Articles if !self.articles.is_empty() => {
                 if self.selected_article < self.articles.len() - 1 {
@@ -410,7 +755,7 @@ impl App {
                 }
             }
             Screen::Home => {
-                if self.selected_customer < 6 {
+                if self.selected_customer < 13 {
                     self.selected_customer += 1;
                 }
             }
@@ -424,11 +769,27 @@ impl App {
                     self.export_selection += 1;
                 }
             }
+            Screen::Approvals => self.approvals.select_next(),
+            Screen::Reconciliation => self.reconciliation.select_next(),
+            Screen::Dunning if !self.dunning.confirming => self.dunning.select_next(),
+            Screen::InvoiceWizard => self.invoice_wizard.select_next(),
+            Screen::FiscalYears => self.fiscal_years.select_next(),
             _ => {}
         }
     }
 
     pub fn handle_left(&mut self) {
+        if self.screen == Screen::CashFlow {
+            self.cash_flow.previous_horizon();
+            self.needs_refresh = true;
+            return;
+        }
+        if self.screen == Screen::Reports {
+            self.reports.previous_month(chrono::Local::now().date_naive());
+            self.needs_refresh = true;
+            return;
+        }
+
         // Previous page
         if self.current_page > 1 {
             self.current_page -= 1;
@@ -437,6 +798,17 @@ impl App {
     }
 
     pub fn handle_right(&mut self) {
+        if self.screen == Screen::CashFlow {
+            self.cash_flow.next_horizon();
+            self.needs_refresh = true;
+            return;
+        }
+        if self.screen == Screen::Reports {
+            self.reports.next_month(chrono::Local::now().date_naive());
+            self.needs_refresh = true;
+            return;
+        }
+
         // Next page
         if self.current_page < self.total_pages {
             self.current_page += 1;
@@ -462,7 +834,23 @@ impl App {
                 }
                 _ => {}
             }
+        } else if self.screen == Screen::Setup && c == 'D' {
+            self.start_demo();
         } else {
+            // Keys that start a change are refused up front in read-only companies
+            let changes = match c {
+                'n' => matches!(self.screen, Screen::Customers | Screen::Invoices | Screen::Articles),
+                'e' | 'x' => matches!(
+                    self.screen,
+                    Screen::CustomerDetail(_) | Screen::InvoiceDetail(_) | Screen::ArticleDetail(_)
+                ),
+                'A' | 'R' => self.screen == Screen::Approvals,
+                _ => false,
+            };
+            if changes && !self.ensure_writable() {
+                return;
+            }
+
             match c {
                 'r' => {
                     if self.client.is_some() {
@@ -513,13 +901,13 @@ impl App {
                     // Delete key - show confirmation dialog
                     match &self.screen {
                         Screen::CustomerDetail(ref id) => {
-                            self.confirm_delete = Some(("customer".to_string(), id.clone()));
+                            self.confirm_delete = Some(("customer".to_string(), id.to_string()));
                         }
                         Screen::InvoiceDetail(ref id) => {
-                            self.confirm_delete = Some(("invoice".to_string(), id.clone()));
+                            self.confirm_delete = Some(("invoice".to_string(), id.to_string()));
                         }
                         Screen::ArticleDetail(ref id) => {
-                            self.confirm_delete = Some(("article".to_string(), id.clone()));
+                            self.confirm_delete = Some(("article".to_string(), id.to_string()));
                         }
                         _ => {}
                     }
@@ -581,6 +969,8 @@ impl App {
                     // Toggle item selection in batch mode
                     if self.batch_mode {
                         self.toggle_item_selection();
+                    } else if self.screen == Screen::Dunning && !self.dunning.confirming {
+                        self.dunning.toggle_selected();
                     }
                 }
                 'f' => {
@@ -589,6 +979,46 @@ impl App {
                         self.toggle_filter_panel();
                     }
                 }
+                'l' => {
+                    // Load a bank file for reconciliation
+                    if self.screen == Screen::Reconciliation {
+                        self.start_form();
+                        self.input = self.reconciliation.file.clone().unwrap_or_default();
+                    }
+                }
+                '+' | '-' => {
+                    // Change the quantity of the selected article
+                    if self.screen == Screen::InvoiceWizard {
+                        self.invoice_wizard.adjust_quantity(Money::from(if c == '+' { 1 } else { -1 }));
+                    }
+                }
+                'V' => {
+                    self.config.accessibility.enabled = !self.config.accessibility.enabled;
+                    self.set_status(format!(
+                        "Accessibility mode {}",
+                        if self.accessible() { "on" } else { "off" }
+                    ));
+                }
+                'P' => self.screen_dump_requested = true,
+                'w' => {
+                    // Close the tab shown
+                    if let Some(tab) = self.tabs.close(&self.screen) {
+                        self.screen = tab.back.unwrap_or(Screen::Home);
+                        self.previous_screen = None;
+                        self.set_status(format!("Closed {}", tab.record.title()));
+                    }
+                }
+                'A' | 'R' => {
+                    // Approve/reject the selected supplier invoice
+                    if self.screen == Screen::Approvals && self.approvals.selected_invoice().is_some() {
+                        self.approvals.decision = Some(if c == 'A' {
+                            Decision::Approve
+                        } else {
+                            Decision::Reject
+                        });
+                        self.start_form();
+                    }
+                }
                 _ => {}
             }
         }
@@ -682,7 +1112,7 @@ impl App {
     }
 
     pub fn next_screen(&mut self) {
-        if self.client.is_some() {
+        if self.client.is_some() || self.demo {
             self.screen = match &self.screen {
                 Screen::Home => Screen::Dashboard,
                 Screen::Dashboard => Screen::Customers,
@@ -697,7 +1127,7 @@ impl App {
     }
 
     pub fn previous_screen(&mut self) {
-        if self.client.is_some() {
+        if self.client.is_some() || self.demo {
             self.screen = match &self.screen {
                 Screen::Home => Screen::Help,
                 Screen::Dashboard => Screen::Home,
@@ -726,7 +1156,7 @@ impl App {
 
         // Pre-populate form data with existing customer data
         if let Screen::CustomerEdit(ref id) = self.screen {
-            if let Some(customer) = self.customers.iter().find(|c| c.id.as_deref() == Some(id)) {
+            if let Some(customer) = self.customer(id).cloned() {
                 self.form_data.push(customer.name.clone().unwrap_or_default());
                 self.form_data.push(customer.email.clone().unwrap_or_default());
                 self.form_data.push(customer.phone.clone().unwrap_or_default());
@@ -744,7 +1174,7 @@ impl App {
 
         // Pre-populate form data with existing article data
         if let Screen::ArticleEdit(ref id) = self.screen {
-            if let Some(article) = self.articles.iter().find(|a| a.id.as_deref() == Some(id)) {
+            if let Some(article) = self.article(id).cloned() {
                 self.form_data.push(article.name.clone().unwrap_or_default());
                 self.form_data.push(
                     article
@@ -765,11 +1195,11 @@ impl App {
 
         // Pre-populate form data with existing invoice data
         if let Screen::InvoiceEdit(ref id) = self.screen {
-            if let Some(invoice) = self.invoices.iter().find(|i| i.id.as_deref() == Some(id)) {
-                self.form_data.push(invoice.customer_id.clone().unwrap_or_default());
+            if let Some(invoice) = self.invoice(id).cloned() {
+                self.form_data.push(invoice.customer_id.clone().map(String::from).unwrap_or_default());
                 self.form_data.push(invoice.remarks.clone().unwrap_or_default());
                 // Calculate amount from total
-                let amount = invoice.total_amount.unwrap_or(0.0);
+                let amount = invoice.total_amount.unwrap_or_default();
                 self.form_data.push(amount.to_string());
                 self.input_field = 3; // Start at the end to submit immediately or edit
             }
@@ -806,7 +1236,7 @@ impl App {
     }
 
     fn validate_number(s: &str) -> bool {
-        s.parse::<f64>().is_ok() && s.parse::<f64>().unwrap_or(0.0) >= 0.0
+        s.parse::<Money>().is_ok_and(|value| value >= Money::default())
     }
 
     fn validate_current_input(&mut self) -> bool {
@@ -887,6 +1317,20 @@ impl App {
                     _ => {}
                 }
             }
+            Screen::Reconciliation => {
+                // Bank file path validation
+                if !std::path::Path::new(self.input.trim()).is_file() {
+                    self.validation_error = Some("Bank file not found".to_string());
+                    return false;
+                }
+            }
+            Screen::Approvals => {
+                // Comment is required when rejecting
+                if self.approvals.decision == Some(Decision::Reject) && self.input.trim().is_empty() {
+                    self.validation_error = Some("A comment is required when rejecting".to_string());
+                    return false;
+                }
+            }
             _ => {}
         }
 
@@ -898,11 +1342,27 @@ impl App {
             Screen::CustomerCreate | Screen::CustomerEdit(_) => self.input_field >= 4, // name, email, phone, website
             Screen::InvoiceCreate | Screen::InvoiceEdit(_) => self.input_field >= 3,   // customer_id, description, amount
             Screen::ArticleCreate | Screen::ArticleEdit(_) => self.input_field >= 2,  // name, price
+            Screen::Approvals => self.input_field >= 1,                              // comment
+            Screen::Reconciliation => self.input_field >= 1,                         // bank file path
             _ => false,
         }
     }
 
     async fn submit_form(&mut self) -> Result<()> {
+        if !self.ensure_writable() {
+            self.form_data.clear();
+            self.input_field = 0;
+            self.approvals.decision = None;
+            return Ok(());
+        }
+        if let Some(date) = self.form_booking_date() {
+            if !self.ensure_period_open(date).await {
+                self.form_data.clear();
+                self.input_field = 0;
+                return Ok(());
+            }
+        }
+
         if let Some(client) = &self.client {
             match &self.screen.clone() {
                 Screen::CustomerCreate => {
@@ -957,7 +1417,7 @@ impl App {
                     }
                 }
                 Screen::ArticleCreate => {
-                    let price: f64 = self.form_data[1].parse().unwrap_or(0.0);
+                    let price: Money = self.form_data[1].parse().unwrap_or_default();
                     let article = Article {
                         name: Some(self.form_data[0].clone()),
                         sales_price: Some(price),
@@ -977,7 +1437,7 @@ impl App {
                     }
                 }
                 Screen::ArticleEdit(id) => {
-                    let price: f64 = self.form_data[1].parse().unwrap_or(0.0);
+                    let price: Money = self.form_data[1].parse().unwrap_or_default();
                     let article = Article {
                         id: Some(id.clone()),
                         name: Some(self.form_data[0].clone()),
@@ -999,13 +1459,13 @@ impl App {
                 }
                 Screen::InvoiceCreate => {
                     if self.form_data.len() >= 3 {
-                        let amount: f64 = self.form_data[2].parse().unwrap_or(0.0);
+                        let amount: Money = self.form_data[2].parse().unwrap_or_default();
                         let invoice = Invoice {
-                            customer_id: Some(self.form_data[0].clone()),
+                            customer_id: Some(self.form_data[0].clone().into()),
                             remarks: Some(self.form_data[1].clone()),
                             rows: vec![InvoiceRow {
                                 text: Some(self.form_data[1].clone()),
-                                quantity: Some(1.0),
+                                quantity: Some(Money::from(1)),
                                 unit_price: Some(amount),
                                 ..Default::default()
                             }],
@@ -1026,14 +1486,14 @@ impl App {
                 }
                 Screen::InvoiceEdit(id) => {
                     if self.form_data.len() >= 3 {
-                        let amount: f64 = self.form_data[2].parse().unwrap_or(0.0);
+                        let amount: Money = self.form_data[2].parse().unwrap_or_default();
                         let invoice = Invoice {
                             id: Some(id.clone()),
-                            customer_id: Some(self.form_data[0].clone()),
+                            customer_id: Some(self.form_data[0].clone().into()),
                             remarks: Some(self.form_data[1].clone()),
                             rows: vec![InvoiceRow {
                                 text: Some(self.form_data[1].clone()),
-                                quantity: Some(1.0),
+                                quantity: Some(Money::from(1)),
                                 unit_price: Some(amount),
                                 ..Default::default()
                             }],
@@ -1052,6 +1512,49 @@ impl App {
                         }
                     }
                 }
+                Screen::Reconciliation => {
+                    let path = self.form_data[0].trim().to_string();
+                    let parsed = std::fs::read_to_string(&path)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|content| Ok(spiris::bank_files::parse(&content)?));
+                    match parsed {
+                        Ok(transactions) => {
+                            self.reconciliation.file = Some(path);
+                            self.reconciliation.transactions = transactions;
+                            self.reconciliation.selected = 0;
+                            self.load_reconciliation().await?;
+                        }
+                        Err(e) => {
+                            self.set_error(format!("Failed to load bank file: {}", e));
+                        }
+                    }
+                }
+                Screen::Approvals => {
+                    let id = self.approvals.selected_invoice().and_then(|i| i.id.clone());
+                    if let (Some(id), Some(decision)) = (id, self.approvals.decision.take()) {
+                        let comment = self.form_data[0].trim().to_string();
+                        let action = match decision {
+                            Decision::Approve => {
+                                ApprovalAction::approve(Some(comment).filter(|c| !c.is_empty()))
+                            }
+                            Decision::Reject => ApprovalAction::reject(comment),
+                        };
+
+                        match client.approvals().approve_supplier_invoice(&id, &action).await {
+                            Ok(_) => {
+                                let verb = match decision {
+                                    Decision::Approve => "approved",
+                                    Decision::Reject => "rejected",
+                                };
+                                self.set_status(format!("Supplier invoice {}", verb));
+                                self.load_approvals().await?;
+                            }
+                            Err(e) => {
+                                self.set_error(format!("Failed to submit approval: {}", e));
+                            }
+                        }
+                    }
+                }
                 _ => {}
             }
             self.form_data.clear();
@@ -1229,6 +1732,9 @@ impl App {
             match client.customers().list(Some(params)).await {
                 Ok(response) => {
                     self.customers = response.data;
+                    if !self.tabs.is_empty() {
+                        self.tabs.refresh(self.customers.iter().cloned().map(TabRecord::Customer));
+                    }
                     self.sort_customers(); // Apply current sort
                     // Update total pages based on metadata if available
                     // For now, just assume there might be more pages
@@ -1253,7 +1759,11 @@ impl App {
                 .page(self.current_page);
             match client.invoices().list(Some(params)).await {
                 Ok(response) => {
+                    self.invoice_window.reset(self.current_page, response.meta.has_next_page);
                     self.invoices = response.data;
+                    if !self.tabs.is_empty() {
+                        self.tabs.refresh(self.invoices.iter().cloned().map(TabRecord::Invoice));
+                    }
                     self.sort_invoices(); // Apply current sort
                     self.total_pages = self.current_page + 1;
                     self.loading = false;
@@ -1268,6 +1778,40 @@ impl App {
         Ok(())
     }
 
+    /// Load the invoice page the selection has scrolled towards.
+    async fn load_invoice_page(&mut self, request: PageRequest) -> Result<()> {
+        if let Some(client) = &self.client {
+            let (PageRequest::Next(page) | PageRequest::Previous(page)) = request;
+            let params = PaginationParams::new().pagesize(self.page_size).page(page);
+            match client.invoices().list(Some(params)).await {
+                Ok(response) => {
+                    let selected_id = self.invoices.get(self.selected_invoice).and_then(|i| i.id.clone());
+                    let selected = self.invoice_window.merge(
+                        &mut self.invoices,
+                        request,
+                        response.data,
+                        response.meta.has_next_page,
+                        self.selected_invoice,
+                        |invoice| invoice.id.clone(),
+                    );
+                    self.sort_invoices();
+                    // Sorting moves the new rows in among the loaded ones
+                    self.selected_invoice = self
+                        .invoices
+                        .iter()
+                        .position(|i| i.id.is_some() && i.id == selected_id)
+                        .unwrap_or(selected.min(self.invoices.len().saturating_sub(1)));
+                    self.current_page = self.invoice_window.last_page;
+                    self.total_pages = self.current_page + 1;
+                }
+                Err(e) => {
+                    self.set_error(format!("Failed to load more invoices: {}", e));
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub async fn load_articles(&mut self) -> Result<()> {
         if let Some(client) = &self.client {
             self.loading = true;
@@ -1277,6 +1821,9 @@ impl App {
             match client.articles().list(Some(params)).await {
                 Ok(response) => {
                     self.articles = response.data;
+                    if !self.tabs.is_empty() {
+                        self.tabs.refresh(self.articles.iter().cloned().map(TabRecord::Article));
+                    }
                     self.sort_articles(); // Apply current sort
                     self.stats_total_articles = self.articles.len();
                     self.total_pages = self.current_page + 1;
@@ -1371,9 +1918,9 @@ impl App {
         Ok(())
     }
 
-    fn export_data(&mut self) -> Result<()> {
-        // Get export directory from config
-        let export_dir = if self.config.export.export_directory == "." {
+    /// Export directory from config, created if missing
+    fn export_dir(&self) -> Result<PathBuf> {
+        let dir = if self.config.export.export_directory == "." {
             std::env::current_dir()?
         } else {
             let path = PathBuf::from(&self.config.export.export_directory);
@@ -1393,6 +1940,11 @@ impl App {
             }
             expanded
         };
+        Ok(dir)
+    }
+
+    fn export_data(&mut self) -> Result<()> {
+        let export_dir = self.export_dir()?;
 
         let timestamp_str = if self.config.export.include_timestamp {
             format!("_{}", chrono::Utc::now().format("%Y%m%d_%H%M%S"))
@@ -1550,7 +2102,8 @@ impl App {
     }
 
     pub async fn load_dashboard_stats(&mut self) -> Result<()> {
-        if self.client.is_some() {
+        // Demo data is already loaded, so only the statistics are computed
+        if self.client.is_some() || self.demo {
             // Load minimal data to get counts
             self.load_customers().await?;
             self.load_invoices().await?;
@@ -1565,18 +2118,18 @@ impl App {
             self.stats_active_customers = self.customers.iter().filter(|c| c.is_active.unwrap_or(false)).count();
 
             // Revenue calculations
-            let total: f64 = self.invoices.iter()
+            let total: Money = self.invoices.iter()
                 .filter_map(|inv| inv.total_amount_including_vat)
                 .sum();
             self.stats_total_revenue = total;
             self.stats_average_invoice = if self.stats_total_invoices > 0 {
-                total / self.stats_total_invoices as f64
+                total / Money::from(self.stats_total_invoices as u32)
             } else {
-                0.0
+                Money::default()
             };
 
             // Recent invoices (7 and 30 days)
-            let now = chrono::Utc::now();
+            let now = chrono::Local::now().date_naive();
             let seven_days_ago = now - chrono::Duration::days(7);
             let thirty_days_ago = now - chrono::Duration::days(30);
 
@@ -1603,7 +2156,383 @@ impl App {
         Ok(())
     }
 
+    pub async fn load_cash_flow(&mut self) -> Result<()> {
+        if let Some(client) = &self.client {
+            self.loading = true;
+            let today = chrono::Local::now().date_naive();
+            match spiris::forecast::weekly(client, today, self.cash_flow.horizon_weeks).await {
+                Ok(forecast) => {
+                    self.cash_flow.forecast = Some(forecast);
+                    self.set_status(format!(
+                        "Loaded {}-week cash flow forecast",
+                        self.cash_flow.horizon_weeks
+                    ));
+                }
+                Err(e) => {
+                    self.set_error(format!("Failed to load cash flow forecast: {}", e));
+                }
+            }
+            self.loading = false;
+        }
+        Ok(())
+    }
+
+    pub async fn load_approvals(&mut self) -> Result<()> {
+        if let Some(client) = &self.client {
+            self.loading = true;
+            let user_id = self.config.approvals.user_id.as_deref();
+            match client.approvals().pending_supplier_invoices(user_id).await {
+                Ok(invoices) => {
+                    // Supplier names and attachment metadata are best effort;
+                    // the list is still usable with IDs only.
+                    for invoice in &invoices {
+                        if let Some(supplier_id) = &invoice.supplier_id {
+                            if !self.approvals.supplier_names.contains_key(supplier_id) {
+                                if let Ok(supplier) = client.suppliers().get(supplier_id).await {
+                                    if let Some(name) = supplier.name {
+                                        self.approvals.supplier_names.insert(supplier_id.clone(), name);
+                                    }
+                                }
+                            }
+                        }
+                        for attachment_id in &invoice.attachments {
+                            if !self.approvals.attachments.contains_key(attachment_id) {
+                                if let Ok(attachment) = client.attachments().get(attachment_id).await {
+                                    self.approvals.attachments.insert(attachment_id.clone(), attachment);
+                                }
+                            }
+                        }
+                    }
+
+                    self.approvals.selected = self.approvals.selected.min(invoices.len().saturating_sub(1));
+                    self.approvals.invoices = invoices;
+                    self.set_status(format!(
+                        "{} supplier invoice(s) awaiting approval",
+                        self.approvals.invoices.len()
+                    ));
+                }
+                Err(e) => {
+                    self.set_error(format!("Failed to load approvals: {}", e));
+                }
+            }
+            self.loading = false;
+        }
+        Ok(())
+    }
+
+    pub async fn load_reconciliation(&mut self) -> Result<()> {
+        if let Some(client) = &self.client {
+            self.loading = true;
+            match spiris::reconciliation::open_items(client).await {
+                Ok(items) => {
+                    self.reconciliation.items = items;
+                    self.reconciliation.update_suggestions();
+                    self.set_status(format!(
+                        "{} transaction(s), {} suggested match(es)",
+                        self.reconciliation.transactions.len(),
+                        self.reconciliation.suggestions.len()
+                    ));
+                }
+                Err(e) => {
+                    self.set_error(format!("Failed to load open items: {}", e));
+                }
+            }
+            self.loading = false;
+        }
+        Ok(())
+    }
+
+    async fn confirm_reconciliation_match(&mut self) -> Result<()> {
+        let Some(suggestion) = self.reconciliation.selected_suggestion().cloned() else {
+            self.set_error("No match suggested for this transaction".to_string());
+            return Ok(());
+        };
+        let payment_date = self.reconciliation.transactions[suggestion.transaction].booking_date;
+        if !self.ensure_writable() || !self.ensure_period_open(payment_date).await {
+            return Ok(());
+        }
+
+        if let Some(client) = &self.client {
+            let transaction = &self.reconciliation.transactions[suggestion.transaction];
+            let item = &self.reconciliation.items[suggestion.item];
+            let message = format!(
+                "Registered {:.2} on invoice {}",
+                transaction.amount.abs(),
+                item.invoice_number.as_deref().unwrap_or(&item.invoice_id)
+            );
+
+            match spiris::reconciliation::register_match(client, transaction, item).await {
+                Ok(()) => {
+                    self.reconciliation.apply_match(&suggestion);
+                    self.set_status(message);
+                }
+                Err(e) => {
+                    self.set_error(format!("Failed to register payment: {}", e));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn load_dunning(&mut self) -> Result<()> {
+        if let Some(client) = &self.client {
+            self.loading = true;
+            let today = client.clock().today();
+            match spiris::dunning::preview(client, &self.config.dunning, today).await {
+                Ok(candidates) => {
+                    self.dunning.set_candidates(candidates);
+                    self.set_status(format!(
+                        "{} invoice(s) due for a reminder",
+                        self.dunning.candidates.len()
+                    ));
+                }
+                Err(e) => {
+                    self.set_error(format!("Failed to load overdue invoices: {}", e));
+                }
+            }
+            self.loading = false;
+        }
+        Ok(())
+    }
+
+    async fn run_dunning(&mut self) -> Result<()> {
+        self.dunning.confirming = false;
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        if let Some(client) = &self.client {
+            let candidates = self.dunning.included();
+            self.loading = true;
+            let today = client.clock().today();
+            let results = spiris::dunning::run(client, &candidates, today).await;
+            self.loading = false;
+
+            let sent = results.iter().filter(|r| r.is_ok()).count();
+            let message = format!("Sent {} of {} reminder(s)", sent, results.len());
+            if sent == results.len() {
+                self.set_status(message);
+            } else {
+                self.set_error(message);
+            }
+            self.dunning.results = results;
+            // Sent reminders move the invoices out of the preview
+            self.load_dunning().await?;
+        }
+        Ok(())
+    }
+
+    pub async fn load_invoice_wizard(&mut self) -> Result<()> {
+        if let Some(client) = &self.client {
+            self.loading = true;
+            let params = PaginationParams::new().pagesize(500);
+            let loaded = async {
+                let customers = client.customers().list(Some(params.clone())).await?;
+                let articles = client.articles().list(Some(params.clone())).await?;
+                let vat_codes = client.vat_codes().list(Some(params)).await?;
+                Ok::<_, spiris::Error>((customers, articles, vat_codes))
+            }
+            .await;
+            match loaded {
+                Ok((customers, articles, vat_codes)) => {
+                    let articles = articles
+                        .data
+                        .into_iter()
+                        .filter(|a| a.is_active != Some(false))
+                        .collect();
+                    self.invoice_wizard.reset(customers.data, articles, vat_codes.data);
+                }
+                Err(e) => {
+                    self.set_error(format!("Failed to load customers and articles: {}", e));
+                }
+            }
+            self.loading = false;
+        }
+        Ok(())
+    }
+
+    /// Create the wizard's invoice and send it with the chosen method.
+    async fn finish_invoice_wizard(&mut self) -> Result<()> {
+        let today = chrono::Local::now().date_naive();
+        if !self.ensure_writable() || !self.ensure_period_open(today).await {
+            return Ok(());
+        }
+        if let Some(client) = &self.client {
+            self.loading = true;
+            let invoice = self.invoice_wizard.invoice();
+            let method = self.invoice_wizard.send_method();
+            let result = match client.invoices().create(&invoice).await {
+                Ok(created) => {
+                    let number = created
+                        .invoice_number
+                        .clone()
+                        .or(created.id.clone().map(String::from))
+                        .unwrap_or_default();
+                    match (method.options(), created.id.as_ref()) {
+                        (Some(options), Some(id)) => match client.invoices().send(id, &options).await {
+                            Ok(()) => Ok(format!("Invoice {} created and sent", number)),
+                            Err(e) => Err(format!("Invoice {} created but not sent: {}", number, e)),
+                        },
+                        _ => Ok(format!("Invoice {} created", number)),
+                    }
+                }
+                Err(e) => Err(format!("Failed to create invoice: {}", e)),
+            };
+            self.loading = false;
+
+            match &result {
+                Ok(message) => self.set_status(message.clone()),
+                Err(message) => self.set_error(message.clone()),
+            }
+            self.invoice_wizard.result = Some(result);
+            self.invoice_wizard.step = WizardStep::Done;
+        }
+        Ok(())
+    }
+
+    pub async fn load_fiscal_years(&mut self) -> Result<()> {
+        if let Some(client) = &self.client {
+            self.loading = true;
+            match spiris::periods::PeriodLocks::load(client).await {
+                Ok(locks) => {
+                    let count = locks.fiscal_years.len();
+                    self.fiscal_years.selected = self.fiscal_years.selected.min(count.saturating_sub(1));
+                    self.fiscal_years.locks = Some(locks);
+                    self.set_status(format!("{} fiscal year(s) loaded", count));
+                }
+                Err(e) => {
+                    self.set_error(format!("Failed to load fiscal years: {}", e));
+                }
+            }
+            self.loading = false;
+        }
+        Ok(())
+    }
+
+    pub async fn load_reports(&mut self) -> Result<()> {
+        if let Some(client) = &self.client {
+            let (start, end) = self.reports.period(chrono::Local::now().date_naive());
+            let budget = match Config::budget_path().and_then(|path| {
+                let contents = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                serde_json::from_str::<spiris::budget::Budget>(&contents)
+                    .with_context(|| format!("Invalid budget in {}", path.display()))
+            }) {
+                Ok(budget) => budget,
+                Err(e) => {
+                    self.reports.report = None;
+                    self.set_error(format!("{:#}", e));
+                    return Ok(());
+                }
+            };
+
+            self.loading = true;
+            match spiris::budget::budget_vs_actual(client, &budget, start, end).await {
+                Ok(report) => {
+                    self.set_status(format!(
+                        "Budget '{}' compared with {} - {}",
+                        report.budget_name, start, end
+                    ));
+                    self.reports.report = Some(report);
+                }
+                Err(e) => {
+                    self.reports.report = None;
+                    self.set_error(format!("Failed to load budget vs actual: {}", e));
+                }
+            }
+            self.loading = false;
+        }
+        Ok(())
+    }
+
+    /// Date the form being submitted would be booked on, if it is dated.
+    fn form_booking_date(&self) -> Option<chrono::NaiveDate> {
+        let today = chrono::Local::now().date_naive();
+        match &self.screen {
+            Screen::InvoiceCreate => Some(today),
+            Screen::InvoiceEdit(id) => Some(
+                self.invoices
+                    .iter()
+                    .find(|i| i.id.as_deref() == Some(id))
+                    .and_then(|i| i.invoice_date)
+                    .unwrap_or(today),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Warn and return false if the company is marked read-only in
+    /// config.toml.
+    fn ensure_writable(&mut self) -> bool {
+        match &self.company {
+            Some(company) if company.read_only => {
+                self.set_error(format!("{} is read-only", company.name));
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// Look up the logged in company and its settings in config.toml.
+    async fn load_company(&mut self) {
+        let Some(client) = &self.client else {
+            return;
+        };
+        self.company = Some(match client.company_settings().get().await {
+            Ok(settings) => {
+                let configured = self.config.company(&settings);
+                ActiveCompany {
+                    name: settings.company_name.clone().unwrap_or_default(),
+                    accent: configured
+                        .and_then(|c| c.accent_color.as_deref())
+                        .and_then(|color| color.parse().ok()),
+                    read_only: configured.is_some_and(|c| c.read_only),
+                }
+            }
+            // Unknown company: stay on the safe side if any company is read-only
+            Err(_) => ActiveCompany {
+                name: "This company".to_string(),
+                accent: None,
+                read_only: self.config.companies.iter().any(|c| c.read_only),
+            },
+        });
+    }
+
+    /// Warn and return false if `date` is in a locked period, so the request
+    /// is not sent. Locks are loaded on first use; if they cannot be loaded
+    /// the API has the final say.
+    async fn ensure_period_open(&mut self, date: chrono::NaiveDate) -> bool {
+        if self.fiscal_years.locks.is_none() {
+            if let Some(client) = &self.client {
+                if let Ok(locks) = spiris::periods::PeriodLocks::load(client).await {
+                    self.fiscal_years.locks = Some(locks);
+                }
+            }
+        }
+
+        match self.fiscal_years.locks.as_ref().map(|locks| locks.check(date)) {
+            Some(Err(e)) => {
+                self.set_error(e.to_string());
+                false
+            }
+            _ => true,
+        }
+    }
+
     pub async fn refresh_if_needed(&mut self) -> Result<()> {
+        if self.screen == Screen::Setup {
+            return self.poll_setup().await;
+        }
+
+        if self.company.is_none() {
+            self.load_company().await;
+        }
+
+        if self.screen == Screen::Invoices && !self.needs_refresh {
+            if let Some(request) = self.invoice_window.pending.take() {
+                self.load_invoice_page(request).await?;
+            }
+        }
+
         if !self.needs_refresh {
             return Ok(());
         }
@@ -1613,6 +2542,13 @@ impl App {
             Screen::Invoices => self.load_invoices().await?,
             Screen::Articles => self.load_articles().await?,
             Screen::Dashboard => self.load_dashboard_stats().await?,
+            Screen::CashFlow => self.load_cash_flow().await?,
+            Screen::Approvals => self.load_approvals().await?,
+            Screen::Reconciliation if self.reconciliation.file.is_some() => self.load_reconciliation().await?,
+            Screen::Dunning => self.load_dunning().await?,
+            Screen::InvoiceWizard => self.load_invoice_wizard().await?,
+            Screen::FiscalYears => self.load_fiscal_years().await?,
+            Screen::Reports => self.load_reports().await?,
             _ => {}
         }
 
@@ -1642,17 +2578,22 @@ impl App {
     }
 
     fn execute_delete(&mut self) {
+        if !self.ensure_writable() {
+            self.confirm_delete = None;
+            return;
+        }
         if let Some((entity_type, id)) = self.confirm_delete.take() {
+            self.tabs.close(&self.screen);
             match entity_type.as_str() {
-                "customer" => self.delete_customer(id),
-                "invoice" => self.delete_invoice(id),
-                "article" => self.delete_article(id),
+                "customer" => self.delete_customer(id.into()),
+                "invoice" => self.delete_invoice(id.into()),
+                "article" => self.delete_article(id.into()),
                 _ => {}
             }
         }
     }
 
-    fn delete_customer(&mut self, id: String) {
+    fn delete_customer(&mut self, id: CustomerId) {
         if let Some(client) = &self.client {
             let client = client.clone();
             let id_clone = id.clone();
@@ -1669,7 +2610,7 @@ impl App {
         }
     }
 
-    fn delete_invoice(&mut self, id: String) {
+    fn delete_invoice(&mut self, id: InvoiceId) {
         if let Some(client) = &self.client {
             let client = client.clone();
             let id_clone = id.clone();
@@ -1686,7 +2627,7 @@ impl App {
         }
     }
 
-    fn delete_article(&mut self, id: String) {
+    fn delete_article(&mut self, id: ArticleId) {
         if let Some(client) = &self.client {
             let client = client.clone();
             let id_clone = id.clone();
@@ -1703,38 +2644,162 @@ impl App {
         }
     }
 
-    async fn start_oauth(&mut self) -> Result<()> {
-        self.oauth_waiting = true;
-        self.status_message = Some("Starting OAuth flow...".to_string());
-
-        // Get credentials from environment
-        let client_id = std::env::var("SPIRIS_CLIENT_ID")
-            .unwrap_or_else(|_| "your_client_id".to_string());
-        let client_secret = std::env::var("SPIRIS_CLIENT_SECRET")
-            .unwrap_or_else(|_| "your_client_secret".to_string());
-        let redirect_uri = std::env::var("SPIRIS_REDIRECT_URI")
-            .unwrap_or_else(|_| "http://localhost:8080/callback".to_string());
-
-        // Use the auth module helper
-        let (auth_url, _csrf, _verifier) = crate::auth::start_oauth_flow(
-            client_id,
-            client_secret,
-            redirect_uri,
-        ).await?;
+    /// Check the saved sign-in at startup. An expired token is refreshed
+    /// if possible; otherwise setup starts again.
+    pub async fn check_sign_in(&mut self) {
+        let expired = self.client.as_ref().is_some_and(|c| c.is_token_expired());
+        if !expired {
+            return;
+        }
+        if let Err(e) = self.refresh_token_if_needed().await {
+            self.client = None;
+            self.token = None;
+            self.screen = Screen::Setup;
+            self.set_error(format!("Saved sign-in has expired: {}", e));
+        }
+    }
 
-        self.oauth_url = Some(auth_url);
-        self.status_message = Some("Copy the URL above and open in browser".to_string());
+    /// Handle Enter on the setup screen
+    async fn handle_setup_enter(&mut self) -> Result<()> {
+        match self.setup.step {
+            SetupStep::ClientId | SetupStep::ClientSecret => {
+                if self.input_mode == InputMode::Normal {
+                    self.input_mode = InputMode::Editing;
+                    self.input.clear();
+                    return Ok(());
+                }
+                match self.setup.enter(&self.input) {
+                    Ok(()) => {
+                        self.input.clear();
+                        if !self.setup.wants_input() {
+                            self.input_mode = InputMode::Normal;
+                        }
+                    }
+                    Err(e) => self.set_error(e),
+                }
+            }
+            SetupStep::Authorize if self.setup.pending.is_none() => self.start_oauth().await?,
+            SetupStep::Authorize => {}
+            SetupStep::Verify if !self.loading => self.verify_api_access().await,
+            SetupStep::Verify => {}
+            SetupStep::Done => {
+                self.screen = Screen::Home;
+                self.needs_refresh = true;
+            }
+        }
+        Ok(())
+    }
+
+    /// Start the authorization in the browser and wait for the callback
+    async fn start_oauth(&mut self) -> Result<()> {
+        let Some((client_id, client_secret, redirect_uri)) = self.setup.credentials() else {
+            return Ok(());
+        };
+        let (url, csrf, pkce_verifier) =
+            crate::auth::start_oauth_flow(client_id, client_secret, redirect_uri.clone()).await?;
+
+        match crate::screens::setup::listen_for_callback(&redirect_uri, csrf) {
+            Ok(callback) => {
+                self.setup.authorization = Check::Pending;
+                self.setup.pending = Some(PendingAuth { url, pkce_verifier, callback });
+                self.set_status("Open the URL in your browser to sign in".to_string());
+            }
+            Err(e) => {
+                self.setup.authorization = Check::Failed(e.to_string());
+                self.set_error(format!("{} (press D for demo mode)", e));
+            }
+        }
+        Ok(())
+    }
 
+    /// Pick up the authorization code once the browser has been redirected
+    async fn poll_setup(&mut self) -> Result<()> {
+        let Some(pending) = &self.setup.pending else {
+            return Ok(());
+        };
+        let Some(result) = pending.callback.take() else {
+            return Ok(());
+        };
+        let pkce_verifier = pending.pkce_verifier.clone();
+        self.setup.pending = None;
+
+        let code = match result {
+            Ok(code) => code,
+            Err(e) => {
+                self.setup.authorization = Check::Failed(e.clone());
+                self.set_error(format!("Sign-in failed: {}", e));
+                return Ok(());
+            }
+        };
+        if let Err(e) = self.complete_oauth(code, pkce_verifier).await {
+            self.setup.authorization = Check::Failed(e.to_string());
+            self.set_error(format!("Sign-in failed: {}", e));
+            return Ok(());
+        }
+        self.setup.authorization = Check::Passed("token saved".to_string());
+        self.setup.step = SetupStep::Verify;
+        self.verify_api_access().await;
         Ok(())
     }
 
+    /// Make a test call with the new token and write config.toml
+    async fn verify_api_access(&mut self) {
+        let Some(client) = &self.client else {
+            return;
+        };
+        self.loading = true;
+        let result = client.company_settings().get().await;
+        self.loading = false;
+        match result {
+            Ok(settings) => {
+                let company = settings.company_name.unwrap_or_else(|| "company settings read".to_string());
+                self.setup.api_access = Check::Passed(company);
+            }
+            Err(e) => {
+                self.setup.api_access = Check::Failed(e.to_string());
+                self.set_error(format!("API check failed: {} (Enter: retry, D: demo mode)", e));
+                return;
+            }
+        }
+
+        let existed = Config::config_path().is_ok_and(|p| p.exists());
+        self.config.auth = self.setup.auth_config(&self.config.auth);
+        self.setup.config_file = match self.config.save().and_then(|()| Config::config_path()) {
+            Ok(path) => {
+                let action = if existed { "Updated" } else { "Created" };
+                Check::Passed(format!("{} {}", action, path.display()))
+            }
+            Err(e) => Check::Failed(e.to_string()),
+        };
+        self.setup.step = SetupStep::Done;
+        self.set_status("Setup complete".to_string());
+    }
+
+    /// Leave setup and browse sample data without an API client
+    fn start_demo(&mut self) {
+        let (customers, invoices, articles) = crate::screens::setup::demo_data();
+        self.customers = customers;
+        self.invoices = invoices;
+        self.articles = articles;
+        self.demo = true;
+        self.setup.pending = None;
+        self.input_mode = InputMode::Normal;
+        self.input.clear();
+        self.company = Some(ActiveCompany {
+            name: "Demo company".to_string(),
+            accent: None,
+            read_only: true,
+        });
+        self.screen = Screen::Home;
+        self.set_status("Demo mode: sample data, nothing is sent to Spiris".to_string());
+    }
+
     /// Complete OAuth flow and save token
-    #[allow(dead_code)]
     pub async fn complete_oauth(&mut self, code: String, pkce_verifier: String) -> Result<()> {
-        let client_id = std::env::var("SPIRIS_CLIENT_ID")?;
-        let client_secret = std::env::var("SPIRIS_CLIENT_SECRET")?;
-        let redirect_uri = std::env::var("SPIRIS_REDIRECT_URI")
-            .unwrap_or_else(|_| "http://localhost:8080/callback".to_string());
+        let (client_id, client_secret, redirect_uri) = self
+            .setup
+            .credentials()
+            .ok_or_else(|| anyhow::anyhow!("No client credentials"))?;
 
         let token = crate::auth::exchange_code(
             client_id,
@@ -1747,38 +2812,35 @@ impl App {
         self.token = Some(token.clone());
         self.client = Some(Client::new(token));
         self.save_token()?;
-        self.oauth_waiting = false;
-        self.screen = Screen::Home;
-        self.set_status("Authentication successful!".to_string());
 
         Ok(())
     }
 
     /// Refresh the OAuth token if it's expired
-    #[allow(dead_code)]
     pub async fn refresh_token_if_needed(&mut self) -> Result<()> {
         if let Some(client) = &self.client {
             if client.is_token_expired() {
-                if let Some(token) = &self.token {
-                    if let Some(refresh_token) = &token.refresh_token {
-                        let client_id = std::env::var("SPIRIS_CLIENT_ID")?;
-                        let client_secret = std::env::var("SPIRIS_CLIENT_SECRET")?;
-                        let redirect_uri = std::env::var("SPIRIS_REDIRECT_URI")
-                            .unwrap_or_else(|_| "http://localhost:8080/callback".to_string());
-
-                        let new_token = crate::auth::refresh_token(
-                            client_id,
-                            client_secret,
-                            redirect_uri,
-                            refresh_token.clone(),
-                        ).await?;
-
-                        self.token = Some(new_token.clone());
-                        self.client = Some(Client::new(new_token));
-                        self.save_token()?;
-                        self.set_status("Token refreshed successfully".to_string());
-                    }
-                }
+                let Some(refresh_token) = self.token.as_ref().and_then(|t| t.refresh_token.clone()) else {
+                    anyhow::bail!("no refresh token");
+                };
+                let (client_id, client_secret, redirect_uri) = self
+                    .setup
+                    .credentials()
+                    .ok_or_else(|| anyhow::anyhow!("no client credentials to refresh it with"))?;
+
+                let new_token = crate::auth::refresh_token(
+                    client_id,
+                    client_secret,
+                    redirect_uri,
+                    refresh_token,
+                ).await?;
+
+                // Update the shared client so clones held by running tasks
+                // get the new token too
+                client.set_access_token(new_token.clone());
+                self.token = Some(new_token);
+                self.save_token()?;
+                self.set_status("Token refreshed successfully".to_string());
             }
         }
         Ok(())
@@ -1791,7 +2853,6 @@ impl App {
         Ok(token)
     }
 
-    #[allow(dead_code)]
     pub fn save_token(&self) -> Result<()> {
         if let Some(token) = &self.token {
             let token_path = Self::token_path();
@@ -1814,9 +2875,10 @@ impl Clone for App {
             screen: self.screen.clone(),
             previous_screen: self.previous_screen.clone(),
             input_mode: self.input_mode.clone(),
-            client: self.client.as_ref().map(|c| Client::new(c.get_access_token().clone())),
+            client: self.client.clone(),
             token: self.token.clone(),
             config: self.config.clone(),
+            company: self.company.clone(),
             customers: self.customers.clone(),
             selected_customer: self.selected_customer,
             invoices: self.invoices.clone(),
@@ -1831,6 +2893,7 @@ impl Clone for App {
             current_page: self.current_page,
             total_pages: self.total_pages,
             page_size: self.page_size,
+            invoice_window: self.invoice_window.clone(),
             search_query: self.search_query.clone(),
             search_results_customers: self.search_results_customers.clone(),
             search_results_invoices: self.search_results_invoices.clone(),
@@ -1862,8 +2925,18 @@ impl Clone for App {
             loading: self.loading,
             needs_refresh: self.needs_refresh,
             confirm_delete: self.confirm_delete.clone(),
-            oauth_url: self.oauth_url.clone(),
-            oauth_waiting: self.oauth_waiting,
+            setup: self.setup.clone(),
+            demo: self.demo,
+            cash_flow: self.cash_flow.clone(),
+            approvals: self.approvals.clone(),
+            reconciliation: self.reconciliation.clone(),
+            dunning: self.dunning.clone(),
+            invoice_wizard: self.invoice_wizard.clone(),
+            macros: self.macros.clone(),
+            fiscal_years: self.fiscal_years.clone(),
+            reports: self.reports.clone(),
+            tabs: self.tabs.clone(),
+            screen_dump_requested: self.screen_dump_requested,
         }
     }
 }
@@ -1973,6 +3046,238 @@ mod tests {
         assert!(app.needs_refresh);
     }
 
+    #[tokio::test]
+    async fn test_read_only_company_blocks_changes() {
+        let mut app = App::new();
+        app.company = Some(ActiveCompany {
+            name: "Acme AB".to_string(),
+            accent: None,
+            read_only: true,
+        });
+
+        app.screen = Screen::Customers;
+        app.handle_char('n');
+        assert_eq!(app.screen, Screen::Customers);
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(app.error_message.as_deref(), Some("Acme AB is read-only"));
+
+        app.screen = Screen::InvoiceDetail("inv-1".into());
+        app.handle_char('x');
+        assert!(app.confirm_delete.is_none());
+
+        // Browsing still works
+        app.handle_char('c');
+        assert_eq!(app.screen, Screen::Customers);
+    }
+
+    #[test]
+    fn test_invoice_scroll_requests_next_page() {
+        let mut app = App::new();
+        app.screen = Screen::Invoices;
+        app.invoices = vec![Invoice::default(); 50];
+        app.invoice_window.reset(1, true);
+        app.selected_invoice = 38;
+
+        app.handle_down();
+        assert_eq!(app.invoice_window.pending, None);
+        app.handle_down();
+        assert_eq!(app.invoice_window.pending, Some(PageRequest::Next(2)));
+
+        // Moving away from the end drops the request
+        app.handle_up();
+        assert_eq!(app.invoice_window.pending, None);
+    }
+
+    #[tokio::test]
+    async fn test_approval_decision_flow() {
+        let mut app = App::new();
+        app.screen = Screen::Approvals;
+
+        // Nothing to decide on without a selected invoice
+        app.handle_char('A');
+        assert_eq!(app.input_mode, InputMode::Normal);
+
+        app.approvals.invoices = vec![spiris::SupplierInvoice {
+            id: Some("si-1".to_string()),
+            ..Default::default()
+        }];
+        app.handle_char('R');
+        assert_eq!(app.input_mode, InputMode::Editing);
+        assert_eq!(app.approvals.decision, Some(Decision::Reject));
+
+        // Rejecting without a comment is refused
+        app.handle_enter().await.unwrap();
+        assert!(app.validation_error.is_some());
+        assert_eq!(app.input_mode, InputMode::Editing);
+
+        app.handle_escape();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(app.approvals.decision, None);
+    }
+
+    #[tokio::test]
+    async fn test_reconciliation_file_must_exist() {
+        let mut app = App::new();
+        app.screen = Screen::Reconciliation;
+
+        app.handle_char('l');
+        assert_eq!(app.input_mode, InputMode::Editing);
+        app.input = "/nonexistent/camt054.xml".to_string();
+        app.handle_enter().await.unwrap();
+
+        assert_eq!(app.validation_error.as_deref(), Some("Bank file not found"));
+        assert!(app.reconciliation.file.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_invoice_in_locked_period_is_not_submitted() {
+        use chrono::{Datelike, TimeZone, Utc};
+
+        let mut app = App::new();
+        let year = chrono::Local::now().year();
+        app.fiscal_years.locks = Some(spiris::periods::PeriodLocks::new(
+            vec![spiris::FiscalYear {
+                start_date: Some(Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap()),
+                end_date: Some(Utc.with_ymd_and_hms(year, 12, 31, 0, 0, 0).unwrap()),
+                is_locked: Some(true),
+                ..Default::default()
+            }],
+            None,
+        ));
+
+        app.screen = Screen::InvoiceCreate;
+        app.start_form();
+        for value in ["cust-1", "Consulting", "1000"] {
+            app.input = value.to_string();
+            app.handle_enter().await.unwrap();
+        }
+
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(app.form_data.is_empty());
+        assert!(app
+            .error_message
+            .as_deref()
+            .is_some_and(|e| e.contains("locked fiscal year")));
+    }
+
+    #[tokio::test]
+    async fn test_dunning_run_needs_confirmation() {
+        let mut app = App::new();
+        app.screen = Screen::Dunning;
+        app.dunning.set_candidates(vec![spiris::dunning::DunningCandidate {
+            invoice_id: "inv-1".into(),
+            invoice_number: Some("1001".to_string()),
+            customer_id: None,
+            due_date: chrono::NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(),
+            days_overdue: 20,
+            remaining: spiris::money!(500.0),
+            level: spiris::ReminderLevel::FIRST,
+            fee: Money::default(),
+            text: None,
+        }]);
+
+        app.handle_enter().await.unwrap();
+        assert!(app.dunning.confirming);
+
+        // ESC cancels the confirmation but stays on the screen
+        app.handle_escape();
+        assert!(!app.dunning.confirming);
+        assert_eq!(app.screen, Screen::Dunning);
+
+        // Nothing to run once every invoice is excluded
+        app.handle_char(' ');
+        app.handle_enter().await.unwrap();
+        assert!(!app.dunning.confirming);
+        assert_eq!(app.error_message.as_deref(), Some("No reminders to send"));
+    }
+
+    #[tokio::test]
+    async fn test_invoice_wizard_steps_back_before_leaving() {
+        let mut app = App::new();
+        app.previous_screen = Some(Screen::Home);
+        app.screen = Screen::InvoiceWizard;
+        app.invoice_wizard.reset(
+            vec![Customer {
+                id: Some("cust-1".into()),
+                ..Default::default()
+            }],
+            Vec::new(),
+            Vec::new(),
+        );
+
+        app.handle_enter().await.unwrap();
+        assert_eq!(app.invoice_wizard.step, WizardStep::Articles);
+
+        // No articles picked, so the wizard stays on the step
+        app.handle_enter().await.unwrap();
+        assert_eq!(app.invoice_wizard.step, WizardStep::Articles);
+        assert!(app.error_message.as_deref().is_some_and(|e| e.contains("at least one article")));
+
+        app.handle_escape();
+        assert_eq!(app.screen, Screen::InvoiceWizard);
+        assert_eq!(app.invoice_wizard.step, WizardStep::Customer);
+
+        app.handle_escape();
+        assert_eq!(app.screen, Screen::Home);
+    }
+
+    #[tokio::test]
+    async fn test_macro_record_and_play() {
+        let mut app = App::new();
+        app.screen = Screen::Home;
+        app.selected_customer = 0;
+
+        app.handle_key(MacroKey::Char('M')).await.unwrap();
+        app.handle_key(MacroKey::Down).await.unwrap();
+        app.handle_key(MacroKey::Down).await.unwrap();
+        app.handle_key(MacroKey::Char('M')).await.unwrap();
+        assert_eq!(app.selected_customer, 2);
+        assert_eq!(
+            app.macros.prompt,
+            Some(MacroPrompt::Bind(vec![MacroKey::Down, MacroKey::Down]))
+        );
+
+        // ESC discards the recording instead of binding it
+        app.handle_key(MacroKey::Esc).await.unwrap();
+        assert!(app.macros.prompt.is_none());
+        assert!(app.macros.recording.is_none());
+
+        app.config.macros.insert("1".to_string(), MacroKeys(vec![MacroKey::Up, MacroKey::Up]));
+        app.handle_key(MacroKey::Char('@')).await.unwrap();
+        assert!(!app.can_quit());
+        app.handle_key(MacroKey::Char('1')).await.unwrap();
+        assert_eq!(app.selected_customer, 0);
+        assert_eq!(app.screen, Screen::Home);
+
+        // M is plain text while typing
+        app.screen = Screen::Search;
+        app.search_input_mode = true;
+        app.handle_key(MacroKey::Char('M')).await.unwrap();
+        assert!(app.macros.recording.is_none());
+        assert_eq!(app.input, "M");
+    }
+
+    #[test]
+    fn test_cash_flow_horizon_cycle() {
+        let mut app = App::new();
+        app.screen = Screen::CashFlow;
+        app.current_page = 2;
+        assert_eq!(app.cash_flow.horizon_weeks, 4);
+
+        app.handle_right();
+        assert_eq!(app.cash_flow.horizon_weeks, 8);
+        app.handle_right();
+        assert_eq!(app.cash_flow.horizon_weeks, 13);
+        app.handle_right();
+        assert_eq!(app.cash_flow.horizon_weeks, 4);
+
+        app.handle_left();
+        assert_eq!(app.cash_flow.horizon_weeks, 13);
+        assert!(app.needs_refresh);
+        // Pagination is left alone on the cash flow screen
+        assert_eq!(app.current_page, 2);
+    }
+
     #[test]
     fn test_message_timer() {
         let mut app = App::new();
@@ -2091,6 +3396,71 @@ mod tests {
         assert!(!app.show_filter_panel);
     }
 
+    #[test]
+    fn test_demo_mode() {
+        let mut app = App::new();
+        app.client = None;
+        app.screen = Screen::Setup;
+
+        app.handle_char('D');
+        assert_eq!(app.screen, Screen::Home);
+        assert!(app.demo);
+        assert!(!app.customers.is_empty());
+
+        // Demo data can be browsed but not changed
+        app.next_screen();
+        assert_eq!(app.screen, Screen::Dashboard);
+        app.screen = Screen::Customers;
+        app.handle_char('n');
+        assert_eq!(app.screen, Screen::Customers);
+    }
+
+    #[tokio::test]
+    async fn test_detail_tabs() {
+        let mut app = App::new();
+        app.client = None;
+        app.screen = Screen::Setup;
+        app.handle_char('D');
+
+        // Open two customers and an invoice
+        app.screen = Screen::Customers;
+        for index in 0..2 {
+            app.selected_customer = index;
+            app.handle_key(MacroKey::Enter).await.unwrap();
+            app.handle_key(MacroKey::Esc).await.unwrap();
+        }
+        app.screen = Screen::Invoices;
+        app.selected_invoice = 0;
+        app.handle_key(MacroKey::Enter).await.unwrap();
+        let invoice_screen = app.screen.clone();
+        assert!(matches!(invoice_screen, Screen::InvoiceDetail(_)));
+
+        let first_id = app.customers[0].id.clone().unwrap();
+        let second_id = app.customers[1].id.clone().unwrap();
+
+        // The list moving on doesn't lose the tab's record
+        app.customers.clear();
+        app.handle_key(MacroKey::Alt('1')).await.unwrap();
+        assert_eq!(app.screen, Screen::CustomerDetail(first_id.clone()));
+        assert!(app.customer(&first_id).is_some());
+        app.handle_key(MacroKey::Alt('3')).await.unwrap();
+        assert_eq!(app.screen, invoice_screen);
+        app.handle_key(MacroKey::Alt('9')).await.unwrap();
+        assert_eq!(app.screen, invoice_screen);
+
+        // ESC returns to the list the tab was opened from
+        app.handle_key(MacroKey::Alt('2')).await.unwrap();
+        app.handle_key(MacroKey::Esc).await.unwrap();
+        assert_eq!(app.screen, Screen::Customers);
+
+        // Closing a tab renumbers the rest
+        app.handle_key(MacroKey::Alt('1')).await.unwrap();
+        app.handle_key(MacroKey::Char('w')).await.unwrap();
+        assert_eq!(app.screen, Screen::Customers);
+        app.handle_key(MacroKey::Alt('1')).await.unwrap();
+        assert_eq!(app.screen, Screen::CustomerDetail(second_id));
+    }
+
     #[test]
     fn test_config_loading() {
         let app = App::new();
@@ -4,12 +4,18 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use crate::macros::MacroKeys;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
 /// Application configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// API client credentials; the environment variables take precedence
+    #[serde(default, skip_serializing_if = "AuthConfig::is_empty")]
+    pub auth: AuthConfig,
+
     /// Display settings
     #[serde(default)]
     pub display: DisplayConfig,
@@ -25,6 +31,26 @@ pub struct Config {
     /// Theme settings
     #[serde(default)]
     pub theme: ThemeConfig,
+
+    /// Accessibility settings
+    #[serde(default)]
+    pub accessibility: AccessibilityConfig,
+
+    /// Approval inbox settings
+    #[serde(default)]
+    pub approvals: ApprovalsConfig,
+
+    /// Dunning policy for the reminder run
+    #[serde(default)]
+    pub dunning: spiris::dunning::DunningPolicy,
+
+    /// Recorded keyboard macros by the key they are bound to
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub macros: BTreeMap<String, MacroKeys>,
+
+    /// Per-company accent color and read-only flag
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub companies: Vec<CompanyConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -83,6 +109,84 @@ pub struct ThemeConfig {
     pub success_color: String,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuthConfig {
+    /// OAuth2 client ID (SPIRIS_CLIENT_ID)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+
+    /// OAuth2 client secret (SPIRIS_CLIENT_SECRET)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+
+    /// Redirect URI registered for the client (SPIRIS_REDIRECT_URI)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirect_uri: Option<String>,
+}
+
+impl AuthConfig {
+    fn is_empty(&self) -> bool {
+        self.client_id.is_none() && self.client_secret.is_none() && self.redirect_uri.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessibilityConfig {
+    /// Mark states with symbols as well as colors and stop the cursor blinking
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Lines per row in the customer, invoice and article lists; taller rows
+    /// are easier to click
+    #[serde(default = "default_row_height")]
+    pub row_height: u16,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApprovalsConfig {
+    /// Only show invoices assigned to this approver (all pending if unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+}
+
+/// Settings for one company, matched against the company settings of the
+/// logged in account.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompanyConfig {
+    /// Corporate identity number (organisationsnummer)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub corporate_identity_number: Option<String>,
+
+    /// Company name, used when no identity number is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Accent color for the header, e.g. "magenta" or "#ff8800"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent_color: Option<String>,
+
+    /// Block creating, changing and deleting anything in this company
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+impl CompanyConfig {
+    fn matches(&self, settings: &spiris::CompanySettings) -> bool {
+        let digits = |s: &str| s.chars().filter(char::is_ascii_digit).collect::<String>();
+        match (&self.corporate_identity_number, &self.name) {
+            (Some(number), _) => settings
+                .corporate_identity_number
+                .as_deref()
+                .is_some_and(|n| digits(n) == digits(number)),
+            (None, Some(name)) => settings
+                .company_name
+                .as_deref()
+                .is_some_and(|n| n.trim().eq_ignore_ascii_case(name.trim())),
+            (None, None) => false,
+        }
+    }
+}
+
 // Default value functions
 fn default_true() -> bool {
     true
@@ -116,6 +220,10 @@ fn default_success_color() -> String {
     "green".to_string()
 }
 
+fn default_row_height() -> u16 {
+    1
+}
+
 impl Default for DisplayConfig {
     fn default() -> Self {
         Self {
@@ -155,19 +263,35 @@ impl Default for ThemeConfig {
     }
 }
 
+impl Default for AccessibilityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            row_height: 1,
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
+            auth: AuthConfig::default(),
             display: DisplayConfig::default(),
             pagination: PaginationConfig::default(),
             export: ExportConfig::default(),
             theme: ThemeConfig::default(),
+            accessibility: AccessibilityConfig::default(),
+            approvals: ApprovalsConfig::default(),
+            dunning: spiris::dunning::DunningPolicy::default(),
+            macros: BTreeMap::new(),
+            companies: Vec::new(),
         }
     }
 }
 
 impl Config {
-    /// Load configuration from file, or create default if not exists
+    /// Load configuration from file, or the defaults if there is none yet.
+    /// The file is created by the setup screen.
     pub fn load() -> Result<Self> {
         let config_path = Self::config_path()?;
 
@@ -178,10 +302,7 @@ impl Config {
                 .context("Failed to parse config file")?;
             Ok(config)
         } else {
-            // Create default config
-            let config = Config::default();
-            config.save()?;
-            Ok(config)
+            Ok(Config::default())
         }
     }
 
@@ -203,6 +324,11 @@ impl Config {
         Ok(())
     }
 
+    /// Settings for the company with these company settings, if configured
+    pub fn company(&self, settings: &spiris::CompanySettings) -> Option<&CompanyConfig> {
+        self.companies.iter().find(|company| company.matches(settings))
+    }
+
     /// Get the path to the configuration file
     pub fn config_path() -> Result<PathBuf> {
         let config_dir = if let Ok(home) = std::env::var("HOME") {
@@ -215,6 +341,12 @@ impl Config {
 
         Ok(config_dir.join("config.toml"))
     }
+
+    /// Path of the budget shown on the Reports screen, next to config.toml
+    pub fn budget_path() -> Result<PathBuf> {
+        let config_path = Self::config_path()?;
+        Ok(config_path.with_file_name("budget.json"))
+    }
 }
 
 #[cfg(test)]
@@ -265,5 +397,59 @@ mod tests {
         assert_eq!(config.export.default_format, "json");
         assert!(!config.display.show_line_numbers);
         assert_eq!(config.display.auto_refresh_interval, 30);
+        assert!(!config.accessibility.enabled);
+        assert_eq!(config.accessibility.row_height, 1);
+
+        let config: Config = toml::from_str("[accessibility]\nenabled = true\nrow_height = 2").unwrap();
+        assert!(config.accessibility.enabled);
+        assert_eq!(config.accessibility.row_height, 2);
+        let config: Config = toml::from_str("[auth]\nclient_id = \"abc\"").unwrap();
+        assert_eq!(config.auth.client_id.as_deref(), Some("abc"));
+        assert!(config.auth.client_secret.is_none());
+        assert!(!toml::to_string(&Config::default()).unwrap().contains("[auth]"));
+    }
+
+    #[test]
+    fn test_company_matching() {
+        let toml_str = r#"
+            [[companies]]
+            corporate_identity_number = "556677-8899"
+            accent_color = "magenta"
+            read_only = true
+
+            [[companies]]
+            name = "Ek Konsult AB"
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let settings = spiris::CompanySettings {
+            company_name: Some("Acme AB".to_string()),
+            corporate_identity_number: Some("5566778899".to_string()),
+            ..Default::default()
+        };
+        assert!(config.company(&settings).unwrap().read_only);
+
+        let settings = spiris::CompanySettings {
+            company_name: Some("EK KONSULT AB".to_string()),
+            ..Default::default()
+        };
+        let company = config.company(&settings).unwrap();
+        assert!(!company.read_only);
+        assert_eq!(company.accent_color, None);
+    }
+
+    #[test]
+    fn test_macros_round_trip() {
+        let toml_str = r#"
+            [macros]
+            1 = ["c", "<Enter>", "i", "n"]
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.macros["1"].0.len(), 4);
+
+        let saved = toml::to_string_pretty(&config).unwrap();
+        let reloaded: Config = toml::from_str(&saved).unwrap();
+        assert_eq!(reloaded.macros, config.macros);
     }
 }
@@ -135,6 +135,8 @@ pub fn get_screen_help(screen: &Screen) -> ScreenHelp {
             shortcuts: vec![
                 ("e", "Edit customer"),
                 ("x", "Delete customer (with confirmation)"),
+                ("w", "Close this tab"),
+                ("Alt+1-9", "Switch to another open tab"),
                 ("Esc", "Back to customers list"),
             ],
             tips: vec![
@@ -148,6 +150,8 @@ pub fn get_screen_help(screen: &Screen) -> ScreenHelp {
             shortcuts: vec![
                 ("e", "Edit invoice"),
                 ("x", "Delete invoice (with confirmation)"),
+                ("w", "Close this tab"),
+                ("Alt+1-9", "Switch to another open tab"),
                 ("Esc", "Back to invoices list"),
             ],
             tips: vec![
@@ -161,6 +165,8 @@ pub fn get_screen_help(screen: &Screen) -> ScreenHelp {
             shortcuts: vec![
                 ("e", "Edit article"),
                 ("x", "Delete article (with confirmation)"),
+                ("w", "Close this tab"),
+                ("Alt+1-9", "Switch to another open tab"),
                 ("Esc", "Back to articles list"),
             ],
             tips: vec![
@@ -213,6 +219,110 @@ pub fn get_screen_help(screen: &Screen) -> ScreenHelp {
                 "Use descriptive names for better organization",
             ],
         },
+        Screen::CashFlow => ScreenHelp {
+            title: "Cash Flow",
+            description: "Weekly forecast of payments from open customer and supplier invoices",
+            shortcuts: vec![
+                ("←/→", "Switch horizon (4, 8 or 13 weeks)"),
+                ("r", "Reload forecast"),
+                ("Esc", "Back"),
+            ],
+            tips: vec![
+                "Overdue invoices are counted in the first week",
+                "Green bars are inflows, red bars outflows",
+                "The table lists the largest items within the horizon",
+            ],
+        },
+        Screen::Approvals => ScreenHelp {
+            title: "Approvals",
+            description: "Supplier invoices waiting for your approval",
+            shortcuts: vec![
+                ("↑/↓", "Select invoice"),
+                ("A", "Approve with optional comment"),
+                ("R", "Reject with comment"),
+                ("r", "Reload"),
+                ("Esc", "Cancel decision / Back"),
+            ],
+            tips: vec![
+                "Rejecting requires a comment for the submitter",
+                "Set [approvals] user_id in config.toml to only see your own queue",
+                "Attachment file names and types are shown for the selected invoice",
+            ],
+        },
+        Screen::Reconciliation => ScreenHelp {
+            title: "Reconciliation",
+            description: "Match bank file transactions against open invoices",
+            shortcuts: vec![
+                ("l", "Load camt.054 or BgMax file"),
+                ("↑/↓", "Select transaction"),
+                ("Enter", "Confirm suggested match and register payment"),
+                ("r", "Reload open items"),
+                ("Esc", "Back"),
+            ],
+            tips: vec![
+                "Incoming payments match customer invoices, outgoing ones supplier invoices",
+                "OCR references give the most confident matches",
+                "Partial payments leave the invoice open with a lower balance",
+            ],
+        },
+        Screen::Dunning => ScreenHelp {
+            title: "Dunning",
+            description: "Send payment reminders for overdue invoices",
+            shortcuts: vec![
+                ("↑/↓", "Select invoice"),
+                ("Space", "Include/exclude invoice"),
+                ("Enter", "Run, then Enter again to confirm"),
+                ("r", "Reload candidates"),
+                ("Esc", "Cancel confirmation / Back"),
+            ],
+            tips: vec![
+                "Levels, fees and minimum amount come from [dunning] in config.toml",
+                "Each run escalates an invoice by at most one level",
+                "Failed reminders are listed with the error and stay in the preview",
+            ],
+        },
+        Screen::FiscalYears => ScreenHelp {
+            title: "Fiscal Years",
+            description: "Fiscal years, monthly periods and lock status",
+            shortcuts: vec![
+                ("↑/↓", "Select fiscal year"),
+                ("r", "Reload"),
+                ("Esc", "Back"),
+            ],
+            tips: vec![
+                "Invoices and payments dated in a locked period are stopped before they reach the API",
+                "The company lock date locks every period up to and including it",
+            ],
+        },
+        Screen::Reports => ScreenHelp {
+            title: "Reports",
+            description: "Budget vs actual result for one month",
+            shortcuts: vec![
+                ("←/→", "Previous/next month"),
+                ("r", "Reload"),
+                ("Esc", "Back"),
+            ],
+            tips: vec![
+                "The budget is read from budget.json next to config.toml",
+                "Income is positive and costs negative, so a negative variance is unfavourable",
+                "The same file works with `spiris-cli report budget --budget FILE`",
+            ],
+        },
+        Screen::InvoiceWizard => ScreenHelp {
+            title: "New Invoice",
+            description: "Create and send an invoice step by step",
+            shortcuts: vec![
+                ("↑/↓", "Select customer, article or send method"),
+                ("+/-", "Change the quantity of the selected article"),
+                ("Enter", "Next step; on the last step, create the invoice"),
+                ("Esc", "Previous step"),
+            ],
+            tips: vec![
+                "The VAT on the review step is an estimate; Spiris calculates the final amounts",
+                "Sending by email uses the customer's email address",
+                "Invoices are dated today, so a locked period stops the wizard",
+            ],
+        },
         Screen::Help => ScreenHelp {
             title: "Help & Keyboard Shortcuts",
             description: "Comprehensive help and shortcut reference",
@@ -226,17 +336,18 @@ pub fn get_screen_help(screen: &Screen) -> ScreenHelp {
                 "Configuration file: ~/.config/spiris-tui/config.toml",
             ],
         },
-        Screen::Auth => ScreenHelp {
-            title: "Authentication",
-            description: "OAuth2 authentication flow",
+        Screen::Setup => ScreenHelp {
+            title: "Setup",
+            description: "Client credentials, sign-in and API check",
             shortcuts: vec![
-                ("Enter", "Start OAuth flow"),
-                ("Esc", "Cancel"),
+                ("Enter", "Continue with the next step"),
+                ("D", "Demo mode with sample data"),
+                ("q", "Quit"),
             ],
             tips: vec![
-                "Requires SPIRIS_CLIENT_ID environment variable",
+                "Credentials are read from SPIRIS_CLIENT_ID and SPIRIS_CLIENT_SECRET, then from [auth] in config.toml",
+                "The redirect URI must match the one registered for the client",
                 "Token is saved locally for future sessions",
-                "Open the provided URL in your browser",
             ],
         },
     }
@@ -279,6 +390,7 @@ pub fn get_context_shortcuts(screen: &Screen, batch_mode: bool) -> Vec<String> {
         Screen::CustomerDetail(_) | Screen::InvoiceDetail(_) | Screen::ArticleDetail(_) => {
             shortcuts.push("e:Edit".to_string());
             shortcuts.push("x:Delete".to_string());
+            shortcuts.push("w:Close tab".to_string());
             shortcuts.push("Esc:Back".to_string());
         }
         Screen::Search => {
@@ -289,6 +401,35 @@ pub fn get_context_shortcuts(screen: &Screen, batch_mode: bool) -> Vec<String> {
             shortcuts.push("Enter:Export".to_string());
             shortcuts.push("Esc:Cancel".to_string());
         }
+        Screen::CashFlow => {
+            shortcuts.push("←→:Horizon".to_string());
+            shortcuts.push("r:Refresh".to_string());
+        }
+        Screen::Approvals => {
+            shortcuts.push("A:Approve".to_string());
+            shortcuts.push("R:Reject".to_string());
+        }
+        Screen::Reconciliation => {
+            shortcuts.push("l:Load file".to_string());
+            shortcuts.push("Enter:Confirm".to_string());
+        }
+        Screen::Dunning => {
+            shortcuts.push("Space:Exclude".to_string());
+            shortcuts.push("Enter:Run".to_string());
+        }
+        Screen::FiscalYears => {
+            shortcuts.push("↑↓:Select year".to_string());
+            shortcuts.push("r:Refresh".to_string());
+        }
+        Screen::Reports => {
+            shortcuts.push("←→:Month".to_string());
+            shortcuts.push("r:Refresh".to_string());
+        }
+        Screen::InvoiceWizard => {
+            shortcuts.push("+/-:Quantity".to_string());
+            shortcuts.push("Enter:Next".to_string());
+            shortcuts.push("Esc:Previous".to_string());
+        }
         _ => {
             shortcuts.push("Esc:Back".to_string());
         }
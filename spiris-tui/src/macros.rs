@@ -0,0 +1,191 @@
+//! Keyboard macros.
+//!
+//! A macro is a recorded sequence of key presses bound to a single key and
+//! saved in the `[macros]` table of config.toml. Press `M` to start and stop
+//! recording, then the key to bind it to; press `@` followed by that key to
+//! play it back. Macros can also be written by hand:
+//!
+//! ```toml
+//! [macros]
+//! # Open the first customer and start a new invoice
+//! 1 = ["c", "<Enter>", "i", "n"]
+//! ```
+//!
+//! Named keys are written in angle brackets, Alt combinations as `<A-1>`;
+//! any other string is typed one character at a time.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A key press that can be recorded and replayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacroKey {
+    Enter,
+    Esc,
+    Tab,
+    BackTab,
+    Up,
+    Down,
+    Left,
+    Right,
+    Backspace,
+    Char(char),
+    /// A character typed with Alt held down.
+    Alt(char),
+}
+
+impl MacroKey {
+    const NAMED: [(MacroKey, &'static str); 9] = [
+        (MacroKey::Enter, "<Enter>"),
+        (MacroKey::Esc, "<Esc>"),
+        (MacroKey::Tab, "<Tab>"),
+        (MacroKey::BackTab, "<BackTab>"),
+        (MacroKey::Up, "<Up>"),
+        (MacroKey::Down, "<Down>"),
+        (MacroKey::Left, "<Left>"),
+        (MacroKey::Right, "<Right>"),
+        (MacroKey::Backspace, "<Backspace>"),
+    ];
+
+    pub fn from_key(code: KeyCode, modifiers: KeyModifiers) -> Option<Self> {
+        Some(match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::ALT) => MacroKey::Alt(c),
+            KeyCode::Enter => MacroKey::Enter,
+            KeyCode::Esc => MacroKey::Esc,
+            KeyCode::Tab => MacroKey::Tab,
+            KeyCode::BackTab => MacroKey::BackTab,
+            KeyCode::Up => MacroKey::Up,
+            KeyCode::Down => MacroKey::Down,
+            KeyCode::Left => MacroKey::Left,
+            KeyCode::Right => MacroKey::Right,
+            KeyCode::Backspace => MacroKey::Backspace,
+            KeyCode::Char(c) => MacroKey::Char(c),
+            _ => return None,
+        })
+    }
+
+    fn name(&self) -> String {
+        match self {
+            MacroKey::Char(c) => c.to_string(),
+            MacroKey::Alt(c) => format!("<A-{}>", c),
+            key => Self::NAMED
+                .iter()
+                .find(|(named, _)| named == key)
+                .map(|(_, name)| name.to_string())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// The keys of a macro as written in config.toml.
+///
+/// Consecutive characters are joined into one string so typed text stays
+/// readable.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MacroKeys(pub Vec<MacroKey>);
+
+impl MacroKeys {
+    pub fn parse(entries: &[String]) -> Result<Self, String> {
+        let mut keys = Vec::new();
+        for entry in entries {
+            let alt = entry
+                .strip_prefix("<A-")
+                .or_else(|| entry.strip_prefix("<a-"))
+                .and_then(|rest| rest.strip_suffix('>'));
+            let mut alt_chars = alt.into_iter().flat_map(str::chars);
+            if let (Some(c), None) = (alt_chars.next(), alt_chars.next()) {
+                keys.push(MacroKey::Alt(c));
+            } else if entry.starts_with('<') && entry.ends_with('>') && entry.len() > 2 {
+                let key = MacroKey::NAMED
+                    .iter()
+                    .find(|(_, name)| name.eq_ignore_ascii_case(entry))
+                    .map(|(key, _)| *key)
+                    .ok_or_else(|| format!("unknown key {}", entry))?;
+                keys.push(key);
+            } else {
+                keys.extend(entry.chars().map(MacroKey::Char));
+            }
+        }
+        Ok(Self(keys))
+    }
+
+    pub fn entries(&self) -> Vec<String> {
+        let mut entries: Vec<String> = Vec::new();
+        let mut text = false;
+        for key in &self.0 {
+            match key {
+                // '<' would read back as the start of a named key
+                MacroKey::Char(c) if text && *c != '<' => entries.last_mut().unwrap().push(*c),
+                MacroKey::Char(c) => {
+                    entries.push(c.to_string());
+                    text = *c != '<';
+                }
+                key => {
+                    entries.push(key.name());
+                    text = false;
+                }
+            }
+        }
+        entries
+    }
+}
+
+impl Serialize for MacroKeys {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.entries().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MacroKeys {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries = Vec::<String>::deserialize(deserializer)?;
+        Self::parse(&entries).map_err(serde::de::Error::custom)
+    }
+}
+
+/// What the next key press is used for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MacroPrompt {
+    /// Bind the recorded keys to the next key.
+    Bind(Vec<MacroKey>),
+    /// Play the macro bound to the next key.
+    Play,
+}
+
+/// Recording and playback state.
+#[derive(Debug, Clone, Default)]
+pub struct MacroState {
+    /// Keys recorded so far, while recording.
+    pub recording: Option<Vec<MacroKey>>,
+    pub prompt: Option<MacroPrompt>,
+    /// Set while a macro is replayed, so its keys are not recorded again.
+    pub playing: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_round_trip() {
+        let keys = MacroKeys(vec![
+            MacroKey::Char('s'),
+            MacroKey::Char('A'),
+            MacroKey::Char('c'),
+            MacroKey::Enter,
+            MacroKey::Down,
+            MacroKey::Char('<'),
+            MacroKey::Char('n'),
+            MacroKey::Alt('2'),
+        ]);
+
+        let entries = keys.entries();
+        assert_eq!(entries, ["sAc", "<Enter>", "<Down>", "<", "n", "<A-2>"]);
+        assert_eq!(MacroKeys::parse(&entries).unwrap(), keys);
+        assert!(MacroKeys::parse(&["<Home>".to_string()]).is_err());
+        assert_eq!(
+            MacroKey::from_key(KeyCode::Char('3'), KeyModifiers::ALT),
+            Some(MacroKey::Alt('3'))
+        );
+    }
+}
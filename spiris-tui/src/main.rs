@@ -8,7 +8,8 @@
 //! - Full CRUD operations for customers, invoices, and articles
 //! - Search and filtering capabilities
 //! - Data export to CSV and JSON
-//! - OAuth2 authentication
+//! - Guided setup: client credentials, OAuth2 sign-in and an API check,
+//!   with a demo mode when there is no API access
 //! - Real-time validation
 //! - Sorting and pagination
 //!
@@ -30,17 +31,32 @@
 //! - `a`: Articles (when not in input mode)
 //! - `h` or `?`: Help
 //! - `Esc`: Go back/Cancel
-//! - `Enter`: Confirm/Open detail view
+//! - `Enter`: Confirm/Open detail view (also opens it in a tab)
+//! - `Alt+1`..`Alt+9`: Switch to an open tab, `w`: Close the current tab
+//! - `M`: Start/stop recording a macro, `@` + key: Play a macro
+//! - `V`: Toggle accessibility mode
+//! - `P`: Save a plain-text copy of the screen (for screen readers)
+//! - `D`: Demo mode with sample data (on the setup screen)
+//!
+//! ## Mouse
+//!
+//! The wheel moves the selection. Clicking a row in the customer, invoice
+//! or article list selects it; clicking the selected row opens it. Set
+//! `row_height` under `[accessibility]` in config.toml for taller rows.
 
 mod app;
 mod auth;
 mod config;
 mod help;
+mod macros;
+mod paging;
 mod screens;
+mod tabs;
 mod ui;
 
 use anyhow::Result;
 use app::App;
+use macros::MacroKey;
 use crossterm::{
     event::{self as terminal_event, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
     execute,
@@ -60,6 +76,7 @@ async fn main() -> Result<()> {
 
     // Create app and run it
     let mut app = App::new();
+    app.check_sign_in().await;
     let res = run_app(&mut terminal, &mut app).await;
 
     // Restore terminal
@@ -83,30 +100,33 @@ async fn run_app<B: ratatui::backend::Backend>(
     app: &mut App,
 ) -> Result<()> {
     loop {
-        terminal.draw(|f| ui::draw(f, app))?;
+        let frame = terminal.draw(|f| ui::draw(f, app))?;
+        if app.screen_dump_requested {
+            app.screen_dump_requested = false;
+            let text = ui::plain_text(frame.buffer);
+            app.save_screen_dump(&text);
+        }
 
         // Handle refresh if needed
         app.refresh_if_needed().await?;
 
         // Poll for events
         if terminal_event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = terminal_event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    match key.code {
-                        KeyCode::Char('q') if app.can_quit() => return Ok(()),
-                        KeyCode::Esc => app.handle_escape(),
-                        KeyCode::Enter => app.handle_enter().await?,
-                        KeyCode::Tab => app.next_screen(),
-                        KeyCode::BackTab => app.previous_screen(),
-                        KeyCode::Up => app.handle_up(),
-                        KeyCode::Down => app.handle_down(),
-                        KeyCode::Left => app.handle_left(),
-                        KeyCode::Right => app.handle_right(),
-                        KeyCode::Char(c) => app.handle_char(c),
-                        KeyCode::Backspace => app.handle_backspace(),
-                        _ => {}
+            match terminal_event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                    KeyCode::Char('q') if app.can_quit() => return Ok(()),
+                    code => {
+                        if let Some(key) = MacroKey::from_key(code, key.modifiers) {
+                            app.handle_key(key).await?;
+                        }
                     }
+                },
+                Event::Mouse(mouse) => {
+                    let size = terminal.size()?;
+                    app.handle_mouse(mouse, ratatui::layout::Rect::new(0, 0, size.width, size.height))
+                        .await?;
                 }
+                _ => {}
             }
         }
 
@@ -0,0 +1,178 @@
+//! Infinite scrolling for long lists.
+//!
+//! A [`PagedWindow`] tracks which API pages are loaded into a list. Pages are
+//! fetched as the selection gets close to either end of the loaded items,
+//! and pages at the other end are dropped once more than
+//! `pagination.max_items` are loaded, so memory stays bounded however far
+//! the user scrolls.
+
+use std::ops::Range;
+
+/// Load the next page when the selection is this close to the end.
+const PREFETCH: usize = 10;
+
+/// Which page to fetch next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageRequest {
+    /// Append the page after the loaded ones.
+    Next(u32),
+    /// Prepend the page before the loaded ones.
+    Previous(u32),
+}
+
+/// The contiguous range of pages loaded into a list.
+#[derive(Debug, Clone)]
+pub struct PagedWindow {
+    /// First loaded page.
+    pub first_page: u32,
+    /// Last loaded page.
+    pub last_page: u32,
+    /// Whether the API has pages after `last_page`.
+    pub has_more: bool,
+    /// Page requested by the last selection change, not loaded yet.
+    pub pending: Option<PageRequest>,
+    /// Most items to keep loaded.
+    pub max_items: usize,
+}
+
+impl Default for PagedWindow {
+    fn default() -> Self {
+        Self {
+            first_page: 1,
+            last_page: 1,
+            has_more: false,
+            pending: None,
+            max_items: 1000,
+        }
+    }
+}
+
+impl PagedWindow {
+    /// Start over with only `page` loaded.
+    pub fn reset(&mut self, page: u32, has_more: bool) {
+        *self = Self {
+            first_page: page,
+            last_page: page,
+            has_more,
+            pending: None,
+            max_items: self.max_items,
+        };
+    }
+
+    /// Request a page if the selection is close to an end of the loaded
+    /// items.
+    pub fn on_select(&mut self, selected: usize, len: usize) {
+        self.pending = if self.has_more && selected + PREFETCH >= len {
+            Some(PageRequest::Next(self.last_page + 1))
+        } else if self.first_page > 1 && selected < PREFETCH {
+            Some(PageRequest::Previous(self.first_page - 1))
+        } else {
+            None
+        };
+    }
+
+    /// Merge a fetched page into `items`, dropping pages from the other end
+    /// to stay within [`max_items`](Self::max_items). `key` identifies
+    /// items, so rows already loaded are not listed twice. Returns the new
+    /// selection.
+    pub fn merge<T, K: PartialEq>(
+        &mut self,
+        items: &mut Vec<T>,
+        request: PageRequest,
+        page: Vec<T>,
+        has_more: bool,
+        selected: usize,
+        key: impl Fn(&T) -> K,
+    ) -> usize {
+        let page_len = page.len().max(1);
+        let page: Vec<T> = page
+            .into_iter()
+            .filter(|item| !items.iter().any(|existing| key(existing) == key(item)))
+            .collect();
+
+        match request {
+            PageRequest::Next(number) => {
+                items.extend(page);
+                self.last_page = number;
+                self.has_more = has_more;
+
+                let mut selected = selected;
+                while items.len() > self.max_items && self.first_page < self.last_page {
+                    let dropped = page_len.min(items.len());
+                    items.drain(..dropped);
+                    selected = selected.saturating_sub(dropped);
+                    self.first_page += 1;
+                }
+                selected
+            }
+            PageRequest::Previous(number) => {
+                let added = page.len();
+                items.splice(0..0, page);
+                self.first_page = number;
+
+                while items.len() > self.max_items && self.last_page > self.first_page {
+                    let keep = items.len().saturating_sub(page_len);
+                    items.truncate(keep);
+                    self.last_page -= 1;
+                    self.has_more = true;
+                }
+                selected + added
+            }
+        }
+    }
+}
+
+/// Rows of a list that fit in `height` lines, keeping `selected` in view.
+pub fn visible_range(len: usize, selected: usize, height: usize) -> Range<usize> {
+    if len <= height {
+        return 0..len;
+    }
+    let start = selected.saturating_sub(height / 2).min(len - height);
+    start..start + height
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrolls_forward_within_max_items() {
+        let mut window = PagedWindow {
+            max_items: 100,
+            ..Default::default()
+        };
+        window.reset(1, true);
+        let mut items: Vec<u32> = (0..50).collect();
+
+        window.on_select(35, items.len());
+        assert_eq!(window.pending, None);
+        window.on_select(45, items.len());
+        assert_eq!(window.pending, Some(PageRequest::Next(2)));
+
+        let selected = window.merge(&mut items, PageRequest::Next(2), (50..100).collect(), true, 45, |i| *i);
+        assert_eq!((items.len(), selected), (100, 45));
+
+        // Over the limit, the first page is dropped and the selection follows
+        let selected = window.merge(&mut items, PageRequest::Next(3), (100..150).collect(), false, 95, |i| *i);
+        assert_eq!(items.first(), Some(&50));
+        assert_eq!(selected, 45);
+        assert_eq!((window.first_page, window.last_page), (2, 3));
+
+        // Near the top again, the dropped page is loaded back
+        window.on_select(5, items.len());
+        assert_eq!(window.pending, Some(PageRequest::Previous(1)));
+        let selected = window.merge(&mut items, PageRequest::Previous(1), (0..50).collect(), true, 5, |i| *i);
+        assert_eq!(items[selected], 55);
+        assert_eq!(items.len(), 100);
+        assert!(window.has_more);
+        assert_eq!((window.first_page, window.last_page), (1, 2));
+    }
+
+    #[test]
+    fn test_visible_range() {
+        assert_eq!(visible_range(5, 3, 10), 0..5);
+        assert_eq!(visible_range(1000, 0, 20), 0..20);
+        assert_eq!(visible_range(1000, 500, 20), 490..510);
+        assert_eq!(visible_range(1000, 999, 20), 980..1000);
+    }
+}
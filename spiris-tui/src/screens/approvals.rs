@@ -0,0 +1,250 @@
+//! Approvals screen.
+//!
+//! Lists supplier invoices waiting for approval together with the metadata of
+//! their attached documents. Invoices are approved or rejected with a comment
+//! typed into the input box at the bottom.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use spiris::{Attachment, SupplierInvoice};
+use std::collections::HashMap;
+
+/// Decision being entered for the selected invoice.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Decision {
+    Approve,
+    Reject,
+}
+
+/// State for the approvals screen.
+#[derive(Debug, Clone, Default)]
+pub struct ApprovalsState {
+    pub invoices: Vec<SupplierInvoice>,
+    pub selected: usize,
+    /// Supplier names by supplier ID.
+    pub supplier_names: HashMap<String, String>,
+    /// Attachment metadata by attachment ID.
+    pub attachments: HashMap<String, Attachment>,
+    pub decision: Option<Decision>,
+}
+
+impl ApprovalsState {
+    pub fn selected_invoice(&self) -> Option<&SupplierInvoice> {
+        self.invoices.get(self.selected)
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.invoices.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Supplier name, falling back to the supplier ID.
+    pub fn supplier_name(&self, invoice: &SupplierInvoice) -> String {
+        let id = invoice.supplier_id.clone().unwrap_or_default();
+        self.supplier_names.get(&id).cloned().unwrap_or(id)
+    }
+}
+
+pub fn draw(f: &mut Frame, area: Rect, state: &ApprovalsState, input: &str, loading: bool) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(8),
+            Constraint::Length(7),
+            Constraint::Length(3),
+        ])
+        .split(area);
+
+    if state.invoices.is_empty() {
+        let message = if loading {
+            "Loading approvals..."
+        } else {
+            "No supplier invoices waiting for approval - press 'r' to reload"
+        };
+        let paragraph = Paragraph::new(message)
+            .block(Block::default().borders(Borders::ALL).title("Approvals"));
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    draw_invoices(f, chunks[0], state);
+    draw_attachments(f, chunks[1], state);
+    draw_comment(f, chunks[2], state, input);
+}
+
+fn draw_invoices(f: &mut Frame, area: Rect, state: &ApprovalsState) {
+    let rows: Vec<Row> = state
+        .invoices
+        .iter()
+        .map(|invoice| {
+            let amount = invoice.total_amount_including_vat.unwrap_or_default();
+            Row::new(vec![
+                invoice.invoice_number.clone().unwrap_or_default(),
+                state.supplier_name(invoice),
+                invoice
+                    .due_date
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default(),
+                format!("{:>12.2}", amount),
+                invoice.currency_code.clone().unwrap_or_default(),
+                invoice.attachments.len().to_string(),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(12),
+            Constraint::Min(20),
+            Constraint::Length(12),
+            Constraint::Length(14),
+            Constraint::Length(5),
+            Constraint::Length(6),
+        ],
+    )
+    .header(
+        Row::new(vec!["Invoice", "Supplier", "Due", "Amount", "Cur", "Files"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Awaiting approval ({})", state.invoices.len())),
+    )
+    .highlight_style(Style::default().bg(Color::Blue).add_modifier(Modifier::BOLD))
+    .highlight_symbol(">> ");
+
+    f.render_stateful_widget(
+        table,
+        area,
+        &mut TableState::default().with_selected(Some(state.selected)),
+    );
+}
+
+fn draw_attachments(f: &mut Frame, area: Rect, state: &ApprovalsState) {
+    let ids = state
+        .selected_invoice()
+        .map(|invoice| invoice.attachments.as_slice())
+        .unwrap_or_default();
+
+    let lines: Vec<Line> = if ids.is_empty() {
+        vec![Line::from(Span::styled(
+            "No attachments",
+            Style::default().fg(Color::Gray),
+        ))]
+    } else {
+        ids.iter()
+            .map(|id| match state.attachments.get(id) {
+                Some(attachment) => Line::from(vec![
+                    Span::styled(
+                        attachment.file_name.clone().unwrap_or_else(|| id.clone()),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::raw(format!(
+                        "  {}  {}",
+                        attachment.content_type.as_deref().unwrap_or("unknown type"),
+                        attachment
+                            .created_utc
+                            .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_default()
+                    )),
+                ]),
+                None => Line::from(Span::styled(
+                    format!("{} (metadata not available)", id),
+                    Style::default().fg(Color::Gray),
+                )),
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("Attachments"));
+    f.render_widget(paragraph, area);
+}
+
+fn draw_comment(f: &mut Frame, area: Rect, state: &ApprovalsState, input: &str) {
+    let (title, color) = match state.decision {
+        Some(Decision::Approve) => ("Approve - comment (optional)", Color::Green),
+        Some(Decision::Reject) => ("Reject - comment (required)", Color::Red),
+        None => ("Press A to approve or R to reject", Color::Gray),
+    };
+
+    let paragraph = Paragraph::new(input)
+        .style(Style::default().fg(color))
+        .block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn state() -> ApprovalsState {
+        let invoice = SupplierInvoice {
+            id: Some("si-1".to_string()),
+            supplier_id: Some("sup-1".to_string()),
+            invoice_number: Some("F-2041".to_string()),
+            total_amount_including_vat: Some(spiris::money!(12500.0)),
+            attachments: vec!["att-1".to_string()],
+            ..Default::default()
+        };
+        let attachment = Attachment {
+            id: Some("att-1".to_string()),
+            file_name: Some("scan.pdf".to_string()),
+            content_type: Some("application/pdf".to_string()),
+            ..Default::default()
+        };
+
+        ApprovalsState {
+            invoices: vec![invoice.clone(), invoice],
+            supplier_names: HashMap::from([("sup-1".to_string(), "Kontorsgruppen AB".to_string())]),
+            attachments: HashMap::from([("att-1".to_string(), attachment)]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_selection_stays_in_bounds() {
+        let mut state = state();
+        state.select_previous();
+        assert_eq!(state.selected, 0);
+        state.select_next();
+        state.select_next();
+        assert_eq!(state.selected, 1);
+    }
+
+    #[test]
+    fn test_draw_approvals() {
+        let mut state = state();
+        state.decision = Some(Decision::Reject);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal
+            .draw(|f| draw(f, f.area(), &state, "Wrong cost center", false))
+            .unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(content.contains("Kontorsgruppen AB"));
+        assert!(content.contains("12500.00"));
+        assert!(content.contains("scan.pdf"));
+        assert!(content.contains("Wrong cost center"));
+    }
+}
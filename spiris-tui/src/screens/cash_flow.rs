@@ -0,0 +1,248 @@
+//! Cash flow screen.
+//!
+//! Shows the weekly forecast from `spiris::forecast` as a bar chart of
+//! inflows and outflows, with the largest upcoming items below it.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Paragraph, Row, Table},
+    Frame,
+};
+use rust_decimal::prelude::ToPrimitive;
+use spiris::forecast::{CashFlowForecast, FlowDirection};
+use spiris::Money;
+
+/// Selectable forecast horizons in weeks.
+pub const HORIZONS: [u32; 3] = [4, 8, 13];
+
+/// Number of items shown in the largest items table.
+const LARGEST_ITEMS: usize = 10;
+
+/// State for the cash flow screen.
+#[derive(Debug, Clone)]
+pub struct CashFlowState {
+    pub horizon_weeks: u32,
+    pub forecast: Option<CashFlowForecast>,
+}
+
+impl Default for CashFlowState {
+    fn default() -> Self {
+        Self {
+            horizon_weeks: HORIZONS[0],
+            forecast: None,
+        }
+    }
+}
+
+impl CashFlowState {
+    /// Switch to the next longer horizon, wrapping around.
+    pub fn next_horizon(&mut self) {
+        let index = HORIZONS.iter().position(|&h| h == self.horizon_weeks).unwrap_or(0);
+        self.horizon_weeks = HORIZONS[(index + 1) % HORIZONS.len()];
+    }
+
+    /// Switch to the next shorter horizon, wrapping around.
+    pub fn previous_horizon(&mut self) {
+        let index = HORIZONS.iter().position(|&h| h == self.horizon_weeks).unwrap_or(0);
+        self.horizon_weeks = HORIZONS[(index + HORIZONS.len() - 1) % HORIZONS.len()];
+    }
+}
+
+pub fn draw(f: &mut Frame, area: Rect, state: &CashFlowState, loading: bool) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),
+            Constraint::Min(10),
+            Constraint::Length(LARGEST_ITEMS as u16 + 3),
+        ])
+        .split(area);
+
+    let Some(forecast) = &state.forecast else {
+        let message = if loading {
+            "Loading forecast..."
+        } else {
+            "No forecast loaded - press 'r' to load"
+        };
+        let paragraph = Paragraph::new(message)
+            .block(Block::default().borders(Borders::ALL).title("Cash Flow"));
+        f.render_widget(paragraph, area);
+        return;
+    };
+
+    draw_summary(f, chunks[0], state, forecast);
+    draw_chart(f, chunks[1], forecast);
+    draw_largest_items(f, chunks[2], forecast);
+}
+
+fn draw_summary(f: &mut Frame, area: Rect, state: &CashFlowState, forecast: &CashFlowForecast) {
+    let inflow = forecast.total_inflow();
+    let outflow = forecast.total_outflow();
+    let net = inflow - outflow;
+
+    let horizons: Vec<Span> = HORIZONS
+        .iter()
+        .flat_map(|&weeks| {
+            let style = if weeks == state.horizon_weeks {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            [Span::styled(format!(" {} weeks ", weeks), style), Span::raw(" ")]
+        })
+        .collect();
+
+    let text = vec![
+        Line::from(horizons),
+        Line::from(vec![
+            Span::styled("In: ", Style::default().fg(Color::Yellow)),
+            Span::styled(format!("{:.2}", inflow), Style::default().fg(Color::Green)),
+            Span::styled(" | Out: ", Style::default().fg(Color::Yellow)),
+            Span::styled(format!("{:.2}", outflow), Style::default().fg(Color::Red)),
+            Span::styled(" | Net: ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                format!("{:.2}", net),
+                Style::default()
+                    .fg(if net < Money::ZERO { Color::Red } else { Color::Green })
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Cash Flow from {}", forecast.as_of)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_chart(f: &mut Frame, area: Rect, forecast: &CashFlowForecast) {
+    let mut chart = BarChart::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Weekly inflow (green) / outflow (red)"),
+        )
+        .bar_width(3)
+        .bar_gap(0)
+        .group_gap(2)
+        .value_style(Style::default().fg(Color::Black));
+
+    for week in &forecast.weeks {
+        let bars = [
+            Bar::default()
+                .value(week.inflow.round().to_u64().unwrap_or(0))
+                .text_value(String::new())
+                .style(Style::default().fg(Color::Green)),
+            Bar::default()
+                .value(week.outflow.round().to_u64().unwrap_or(0))
+                .text_value(String::new())
+                .style(Style::default().fg(Color::Red)),
+        ];
+        chart = chart.data(
+            BarGroup::default()
+                .label(Line::from(week.week_start.format("%d/%m").to_string()))
+                .bars(&bars),
+        );
+    }
+
+    f.render_widget(chart, area);
+}
+
+fn draw_largest_items(f: &mut Frame, area: Rect, forecast: &CashFlowForecast) {
+    let rows: Vec<Row> = forecast
+        .largest_items(LARGEST_ITEMS)
+        .into_iter()
+        .map(|item| {
+            let (kind, color) = match item.direction {
+                FlowDirection::Inflow => ("In", Color::Green),
+                FlowDirection::Outflow => ("Out", Color::Red),
+            };
+            let due = if item.overdue {
+                format!("{} (overdue)", item.due_date)
+            } else {
+                item.due_date.to_string()
+            };
+            Row::new(vec![
+                due,
+                kind.to_string(),
+                item.invoice_number.clone().unwrap_or_default(),
+                item.counterparty_id.clone().unwrap_or_default(),
+                format!("{:>12.2}", item.amount),
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(22),
+            Constraint::Length(5),
+            Constraint::Length(12),
+            Constraint::Min(20),
+            Constraint::Length(14),
+        ],
+    )
+    .header(
+        Row::new(vec!["Due", "Type", "Invoice", "Customer/Supplier", "Amount"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Largest upcoming items"),
+    );
+
+    f.render_widget(table, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use ratatui::{backend::TestBackend, Terminal};
+    use spiris::Invoice;
+
+    #[test]
+    fn test_horizon_cycle() {
+        let mut state = CashFlowState::default();
+        state.previous_horizon();
+        assert_eq!(state.horizon_weeks, 13);
+        state.next_horizon();
+        assert_eq!(state.horizon_weeks, 4);
+    }
+
+    #[test]
+    fn test_draw_forecast() {
+        let invoice = Invoice {
+            invoice_number: Some("1001".to_string()),
+            due_date: NaiveDate::from_ymd_opt(2025, 3, 14),
+            remaining_amount: Some(spiris::money!(1500.0)),
+            ..Default::default()
+        };
+        let as_of = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+        let state = CashFlowState {
+            horizon_weeks: 4,
+            forecast: Some(CashFlowForecast::from_invoices(as_of, 4, &[invoice], &[])),
+        };
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal
+            .draw(|f| draw(f, f.area(), &state, false))
+            .unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(content.contains("1500.00"));
+        assert!(content.contains("1001"));
+    }
+}
@@ -0,0 +1,286 @@
+//! Dunning run screen.
+//!
+//! Previews the overdue invoices that would receive a reminder under the
+//! configured dunning policy. Invoices can be excluded before the run, and
+//! the outcome is listed per invoice afterwards.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use spiris::dunning::{DunningCandidate, DunningResult};
+use spiris::InvoiceId;
+use std::collections::HashSet;
+
+/// State for the dunning screen.
+#[derive(Debug, Clone, Default)]
+pub struct DunningState {
+    pub candidates: Vec<DunningCandidate>,
+    /// Invoice IDs excluded from the run.
+    pub excluded: HashSet<InvoiceId>,
+    pub selected: usize,
+    /// Results of the last run.
+    pub results: Vec<DunningResult>,
+    /// Waiting for the user to confirm the run.
+    pub confirming: bool,
+}
+
+impl DunningState {
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.candidates.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Include or exclude the selected invoice.
+    pub fn toggle_selected(&mut self) {
+        if let Some(candidate) = self.candidates.get(self.selected) {
+            if !self.excluded.remove(&candidate.invoice_id) {
+                self.excluded.insert(candidate.invoice_id.clone());
+            }
+        }
+    }
+
+    /// Candidates that are not excluded.
+    pub fn included(&self) -> Vec<DunningCandidate> {
+        self.candidates
+            .iter()
+            .filter(|c| !self.excluded.contains(&c.invoice_id))
+            .cloned()
+            .collect()
+    }
+
+    /// Replace the candidates, keeping exclusions for invoices still listed.
+    pub fn set_candidates(&mut self, candidates: Vec<DunningCandidate>) {
+        self.excluded
+            .retain(|id| candidates.iter().any(|c| &c.invoice_id == id));
+        self.selected = self.selected.min(candidates.len().saturating_sub(1));
+        self.candidates = candidates;
+        self.confirming = false;
+    }
+}
+
+pub fn draw(f: &mut Frame, area: Rect, state: &DunningState, loading: bool) {
+    if state.candidates.is_empty() && state.results.is_empty() {
+        let message = if loading {
+            "Loading overdue invoices..."
+        } else {
+            "No overdue invoices need a reminder under the current dunning policy"
+        };
+        let paragraph =
+            Paragraph::new(message).block(Block::default().borders(Borders::ALL).title("Dunning"));
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let results_height = if state.results.is_empty() {
+        0
+    } else {
+        (state.results.len() as u16 + 3).min(area.height / 2)
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(6),
+            Constraint::Length(results_height),
+        ])
+        .split(area);
+
+    draw_summary(f, chunks[0], state);
+    draw_candidates(f, chunks[1], state);
+    if !state.results.is_empty() {
+        draw_results(f, chunks[2], state);
+    }
+}
+
+fn draw_summary(f: &mut Frame, area: Rect, state: &DunningState) {
+    let included = state.included();
+    let fees: spiris::Money = included.iter().map(|c| c.fee).sum();
+
+    let text = if state.confirming {
+        Line::from(Span::styled(
+            format!(
+                "Send {} reminder(s)? Press Enter to confirm or ESC to cancel",
+                included.len()
+            ),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ))
+    } else {
+        Line::from(vec![
+            Span::styled("Candidates: ", Style::default().fg(Color::Yellow)),
+            Span::raw(state.candidates.len().to_string()),
+            Span::styled(" | Included: ", Style::default().fg(Color::Yellow)),
+            Span::raw(included.len().to_string()),
+            Span::styled(" | Fees: ", Style::default().fg(Color::Yellow)),
+            Span::raw(format!("{:.2}", fees)),
+        ])
+    };
+
+    let paragraph =
+        Paragraph::new(text).block(Block::default().borders(Borders::ALL).title("Dunning"));
+    f.render_widget(paragraph, area);
+}
+
+fn draw_candidates(f: &mut Frame, area: Rect, state: &DunningState) {
+    let rows: Vec<Row> = state
+        .candidates
+        .iter()
+        .map(|candidate| {
+            let included = !state.excluded.contains(&candidate.invoice_id);
+            let style = if included {
+                Style::default()
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            Row::new(vec![
+                if included { "[x]" } else { "[ ]" }.to_string(),
+                candidate.invoice_number.clone().unwrap_or_default(),
+                candidate.customer_id.clone().map(String::from).unwrap_or_default(),
+                candidate.due_date.to_string(),
+                format!("{:>5}", candidate.days_overdue),
+                format!("{:>12.2}", candidate.remaining),
+                format!("{:>5}", candidate.level),
+                format!("{:>8.2}", candidate.fee),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(3),
+            Constraint::Length(10),
+            Constraint::Min(12),
+            Constraint::Length(10),
+            Constraint::Length(5),
+            Constraint::Length(13),
+            Constraint::Length(5),
+            Constraint::Length(9),
+        ],
+    )
+    .header(
+        Row::new(vec!["", "Invoice", "Customer", "Due", "Days", "Remaining", "Level", "Fee"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title("Reminders to send"))
+    .highlight_style(Style::default().bg(Color::Blue).add_modifier(Modifier::BOLD))
+    .highlight_symbol(">> ");
+
+    f.render_stateful_widget(
+        table,
+        area,
+        &mut TableState::default().with_selected(Some(state.selected)),
+    );
+}
+
+fn draw_results(f: &mut Frame, area: Rect, state: &DunningState) {
+    let rows: Vec<Row> = state
+        .results
+        .iter()
+        .map(|result| {
+            let outcome = match &result.error {
+                None => Span::styled("Sent", Style::default().fg(Color::Green)),
+                Some(error) => Span::styled(error.clone(), Style::default().fg(Color::Red)),
+            };
+            Row::new(vec![
+                Line::from(
+                    result
+                        .invoice_number
+                        .clone()
+                        .unwrap_or_else(|| result.invoice_id.to_string()),
+                ),
+                Line::from(format!("{:>5}", result.level)),
+                Line::from(outcome),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [Constraint::Length(10), Constraint::Length(5), Constraint::Min(20)],
+    )
+    .header(
+        Row::new(vec!["Invoice", "Level", "Result"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title("Last run"));
+
+    f.render_widget(table, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use ratatui::{backend::TestBackend, Terminal};
+    use spiris::ReminderLevel;
+
+    fn candidate(id: &str, number: &str) -> DunningCandidate {
+        DunningCandidate {
+            invoice_id: id.into(),
+            invoice_number: Some(number.to_string()),
+            customer_id: Some("cust-1".into()),
+            due_date: NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(),
+            days_overdue: 58,
+            remaining: spiris::money!(1200.0),
+            level: ReminderLevel::new(2),
+            fee: spiris::money!(60.0),
+            text: None,
+        }
+    }
+
+    fn state() -> DunningState {
+        let mut state = DunningState::default();
+        state.set_candidates(vec![candidate("inv-1", "1001"), candidate("inv-2", "1002")]);
+        state
+    }
+
+    #[test]
+    fn test_exclude_and_reload() {
+        let mut state = state();
+        state.select_next();
+        state.toggle_selected();
+
+        let included: Vec<_> = state.included().into_iter().map(|c| c.invoice_id).collect();
+        assert_eq!(included, ["inv-1"]);
+
+        // The excluded invoice is gone after the reload, so is its exclusion
+        state.set_candidates(vec![candidate("inv-1", "1001")]);
+        assert!(state.excluded.is_empty());
+        assert_eq!(state.selected, 0);
+    }
+
+    #[test]
+    fn test_draw_dunning() {
+        let mut state = state();
+        state.results = vec![DunningResult {
+            invoice_id: "inv-3".into(),
+            invoice_number: Some("1003".to_string()),
+            level: ReminderLevel::FIRST,
+            error: Some("Customer has no address".to_string()),
+        }];
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        terminal.draw(|f| draw(f, f.area(), &state, false)).unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(content.contains("Included: 2"));
+        assert!(content.contains("Fees: 120.00"));
+        assert!(content.contains("Customer has no address"));
+    }
+}
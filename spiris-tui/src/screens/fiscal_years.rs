@@ -0,0 +1,186 @@
+//! Fiscal years screen.
+//!
+//! Lists the fiscal years with their monthly periods and lock status. The
+//! loaded locks are also used to warn before submitting something dated in a
+//! locked period.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use spiris::periods::PeriodLocks;
+
+/// State for the fiscal years screen.
+#[derive(Debug, Clone, Default)]
+pub struct FiscalYearsState {
+    /// Loaded on first use, by this screen or by a lock check.
+    pub locks: Option<PeriodLocks>,
+    /// Index of the selected fiscal year.
+    pub selected: usize,
+}
+
+impl FiscalYearsState {
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        let count = self.locks.as_ref().map_or(0, |l| l.fiscal_years.len());
+        if self.selected + 1 < count {
+            self.selected += 1;
+        }
+    }
+}
+
+fn lock_span(locked: bool) -> Span<'static> {
+    if locked {
+        Span::styled("Locked", Style::default().fg(Color::Red))
+    } else {
+        Span::styled("Open", Style::default().fg(Color::Green))
+    }
+}
+
+pub fn draw(f: &mut Frame, area: Rect, state: &FiscalYearsState, loading: bool) {
+    let Some(locks) = &state.locks else {
+        let message = if loading {
+            "Loading fiscal years..."
+        } else {
+            "No fiscal years loaded - press 'r' to refresh"
+        };
+        let paragraph = Paragraph::new(message)
+            .block(Block::default().borders(Borders::ALL).title("Fiscal Years"));
+        f.render_widget(paragraph, area);
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(6)])
+        .split(area);
+
+    let locked_through = match locks.locked_through {
+        Some(date) => Span::styled(date.to_string(), Style::default().fg(Color::Red)),
+        None => Span::styled("not set", Style::default().fg(Color::Gray)),
+    };
+    let summary = Paragraph::new(Line::from(vec![
+        Span::styled("Bookkeeping locked through: ", Style::default().fg(Color::Yellow)),
+        locked_through,
+    ]))
+    .block(Block::default().borders(Borders::ALL).title("Fiscal Years"));
+    f.render_widget(summary, chunks[0]);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(chunks[1]);
+
+    let date = |d: Option<chrono::DateTime<chrono::Utc>>| {
+        d.map(|d| d.date_naive().to_string()).unwrap_or_default()
+    };
+    let year_rows: Vec<Row> = locks
+        .fiscal_years
+        .iter()
+        .map(|year| {
+            Row::new(vec![
+                Line::from(date(year.start_date)),
+                Line::from(date(year.end_date)),
+                Line::from(match year.bookkeeping_method {
+                    Some(1) => "Invoice",
+                    Some(2) => "Cash",
+                    _ => "",
+                }),
+                Line::from(lock_span(year.is_locked == Some(true))),
+            ])
+        })
+        .collect();
+
+    let years = Table::new(
+        year_rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(7),
+        ],
+    )
+    .header(
+        Row::new(vec!["Start", "End", "Method", "Status"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title("Fiscal years"))
+    .highlight_style(Style::default().bg(Color::Blue).add_modifier(Modifier::BOLD))
+    .highlight_symbol(">> ");
+
+    f.render_stateful_widget(
+        years,
+        columns[0],
+        &mut TableState::default().with_selected(Some(state.selected)),
+    );
+
+    let period_rows: Vec<Row> = locks
+        .fiscal_years
+        .get(state.selected)
+        .map(|year| locks.periods(year))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|period| {
+            Row::new(vec![
+                Line::from(period.start.format("%Y-%m").to_string()),
+                Line::from(format!("{} - {}", period.start, period.end)),
+                Line::from(lock_span(period.is_locked)),
+            ])
+        })
+        .collect();
+
+    let periods = Table::new(
+        period_rows,
+        [Constraint::Length(8), Constraint::Length(23), Constraint::Length(7)],
+    )
+    .header(
+        Row::new(vec!["Period", "Dates", "Status"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title("Periods"));
+
+    f.render_widget(periods, columns[1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use ratatui::{backend::TestBackend, Terminal};
+    use spiris::FiscalYear;
+
+    #[test]
+    fn test_draw_fiscal_years() {
+        let year = FiscalYear {
+            id: Some("fy-2025".to_string()),
+            start_date: Some(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()),
+            end_date: Some(Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap()),
+            is_locked: Some(false),
+            bookkeeping_method: Some(1),
+        };
+        let state = FiscalYearsState {
+            locks: Some(PeriodLocks::new(vec![year], NaiveDate::from_ymd_opt(2025, 2, 28))),
+            selected: 0,
+        };
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 24)).unwrap();
+        terminal.draw(|f| draw(f, f.area(), &state, false)).unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(content.contains("locked through: 2025-02-28"));
+        assert!(content.contains("2025-02-01 - 2025-02-28 Locked"));
+        assert!(content.contains("2025-12-01 - 2025-12-31 Open"));
+    }
+}
@@ -0,0 +1,537 @@
+//! Quote-to-cash wizard.
+//!
+//! Walks through creating an invoice one step at a time: pick the customer,
+//! pick articles and quantities, review the totals and choose how to send it.
+//! Meant for users who do not want to deal with the separate customer,
+//! article and invoice screens.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Row, Table, TableState, Wrap},
+    Frame,
+};
+use spiris::{Article, Customer, Invoice, InvoiceRow, Money, SendInvoiceOptions, VatCode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WizardStep {
+    #[default]
+    Customer,
+    Articles,
+    Review,
+    Send,
+    Done,
+}
+
+impl WizardStep {
+    const TITLES: [&'static str; 4] = ["1 Customer", "2 Articles", "3 Review", "4 Send"];
+
+    fn index(&self) -> usize {
+        match self {
+            WizardStep::Customer => 0,
+            WizardStep::Articles => 1,
+            WizardStep::Review => 2,
+            WizardStep::Send | WizardStep::Done => 3,
+        }
+    }
+}
+
+/// How the finished invoice is delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendMethod {
+    /// Create the invoice without sending it.
+    CreateOnly,
+    Email,
+    EInvoice,
+}
+
+impl SendMethod {
+    pub const ALL: [SendMethod; 3] = [SendMethod::CreateOnly, SendMethod::Email, SendMethod::EInvoice];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SendMethod::CreateOnly => "Create only - send it later",
+            SendMethod::Email => "Create and send by email",
+            SendMethod::EInvoice => "Create and send as e-invoice",
+        }
+    }
+
+    /// Send options, `None` when the invoice is only created.
+    pub fn options(&self) -> Option<SendInvoiceOptions> {
+        match self {
+            SendMethod::CreateOnly => None,
+            SendMethod::Email => Some(SendInvoiceOptions::email()),
+            SendMethod::EInvoice => Some(SendInvoiceOptions::einvoice()),
+        }
+    }
+}
+
+/// State for the invoice wizard.
+#[derive(Debug, Clone, Default)]
+pub struct InvoiceWizardState {
+    pub step: WizardStep,
+    pub customers: Vec<Customer>,
+    pub articles: Vec<Article>,
+    pub vat_codes: Vec<VatCode>,
+    /// Cursor in the list of the current step.
+    pub selected: usize,
+    pub customer: Option<Customer>,
+    /// Quantity per article, by index into `articles`.
+    pub quantities: Vec<Money>,
+    pub send_method: usize,
+    /// Outcome shown on the last step.
+    pub result: Option<Result<String, String>>,
+}
+
+impl InvoiceWizardState {
+    /// Start over with freshly loaded customers and articles.
+    pub fn reset(&mut self, customers: Vec<Customer>, articles: Vec<Article>, vat_codes: Vec<VatCode>) {
+        *self = Self {
+            quantities: vec![Money::ZERO; articles.len()],
+            customers,
+            articles,
+            vat_codes,
+            ..Self::default()
+        };
+    }
+
+    fn list_len(&self) -> usize {
+        match self.step {
+            WizardStep::Customer => self.customers.len(),
+            WizardStep::Articles => self.articles.len(),
+            WizardStep::Send => SendMethod::ALL.len(),
+            WizardStep::Review | WizardStep::Done => 0,
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if self.step == WizardStep::Send {
+            self.send_method = self.send_method.saturating_sub(1);
+        } else {
+            self.selected = self.selected.saturating_sub(1);
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.step == WizardStep::Send {
+            if self.send_method + 1 < SendMethod::ALL.len() {
+                self.send_method += 1;
+            }
+        } else if self.selected + 1 < self.list_len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Change the quantity of the selected article by `delta`, never below zero.
+    pub fn adjust_quantity(&mut self, delta: Money) {
+        if self.step != WizardStep::Articles {
+            return;
+        }
+        if let Some(quantity) = self.quantities.get_mut(self.selected) {
+            *quantity = (*quantity + delta).max(Money::ZERO);
+        }
+    }
+
+    pub fn send_method(&self) -> SendMethod {
+        SendMethod::ALL[self.send_method]
+    }
+
+    /// Move to the next step. Returns an error message when the current
+    /// step is not complete.
+    pub fn advance(&mut self) -> Result<(), String> {
+        match self.step {
+            WizardStep::Customer => {
+                let customer = self
+                    .customers
+                    .get(self.selected)
+                    .cloned()
+                    .ok_or_else(|| "Pick a customer first".to_string())?;
+                self.customer = Some(customer);
+                self.step = WizardStep::Articles;
+                self.selected = 0;
+            }
+            WizardStep::Articles => {
+                if self.lines().is_empty() {
+                    return Err("Add at least one article with +".to_string());
+                }
+                self.step = WizardStep::Review;
+            }
+            WizardStep::Review => self.step = WizardStep::Send,
+            WizardStep::Send | WizardStep::Done => {}
+        }
+        Ok(())
+    }
+
+    /// Go back one step. Returns false on the first step, where going back
+    /// leaves the wizard.
+    pub fn back(&mut self) -> bool {
+        self.step = match self.step {
+            WizardStep::Customer => return false,
+            WizardStep::Articles => {
+                self.selected = self
+                    .customer
+                    .as_ref()
+                    .and_then(|c| self.customers.iter().position(|x| x.id == c.id))
+                    .unwrap_or(0);
+                WizardStep::Customer
+            }
+            WizardStep::Review => WizardStep::Articles,
+            WizardStep::Send => WizardStep::Review,
+            // The invoice exists, so start a new one
+            WizardStep::Done => {
+                let (customers, articles, vat_codes) = (
+                    std::mem::take(&mut self.customers),
+                    std::mem::take(&mut self.articles),
+                    std::mem::take(&mut self.vat_codes),
+                );
+                self.reset(customers, articles, vat_codes);
+                WizardStep::Customer
+            }
+        };
+        true
+    }
+
+    /// Articles with a quantity, and the quantity.
+    pub fn lines(&self) -> Vec<(&Article, Money)> {
+        self.articles
+            .iter()
+            .zip(&self.quantities)
+            .filter(|(_, quantity)| **quantity > Money::ZERO)
+            .map(|(article, quantity)| (article, *quantity))
+            .collect()
+    }
+
+    fn vat_rate(&self, article: &Article) -> Money {
+        article
+            .vat_rate_id
+            .as_deref()
+            .and_then(|id| self.vat_codes.iter().find(|code| code.id.as_deref() == Some(id)))
+            .and_then(|code| code.vat_rate)
+            .unwrap_or_default()
+    }
+
+    /// Net amount, VAT and total of the selected articles. VAT is an
+    /// estimate from the articles' VAT codes; Spiris calculates the final
+    /// amounts.
+    pub fn totals(&self) -> (Money, Money, Money) {
+        let (net, vat) = self.lines().iter().fold((Money::ZERO, Money::ZERO), |(net, vat), (article, quantity)| {
+            let amount = article.sales_price.unwrap_or_default() * quantity;
+            (net + amount, vat + amount * self.vat_rate(article))
+        });
+        (net, vat, net + vat)
+    }
+
+    /// The invoice to create.
+    pub fn invoice(&self) -> Invoice {
+        Invoice {
+            customer_id: self.customer.as_ref().and_then(|c| c.id.clone()),
+            rows: self
+                .lines()
+                .into_iter()
+                .map(|(article, quantity)| InvoiceRow {
+                    article_id: article.id.clone(),
+                    text: article.name.clone(),
+                    unit_price: article.sales_price,
+                    quantity: Some(quantity),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+fn name_or_id(name: &Option<String>, id: &Option<impl AsRef<str>>) -> String {
+    name.clone().or_else(|| id.as_ref().map(|id| id.as_ref().to_string())).unwrap_or_default()
+}
+
+pub fn draw(f: &mut Frame, area: Rect, state: &InvoiceWizardState, loading: bool, accessible: bool) {
+    if loading && state.customers.is_empty() {
+        let paragraph = Paragraph::new("Loading customers and articles...")
+            .block(Block::default().borders(Borders::ALL).title("New Invoice"));
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(6)])
+        .split(area);
+
+    draw_progress(f, chunks[0], state, accessible);
+    match state.step {
+        WizardStep::Customer => draw_customers(f, chunks[1], state),
+        WizardStep::Articles => draw_articles(f, chunks[1], state),
+        WizardStep::Review => draw_review(f, chunks[1], state),
+        WizardStep::Send => draw_send(f, chunks[1], state),
+        WizardStep::Done => draw_done(f, chunks[1], state, accessible),
+    }
+}
+
+fn draw_progress(f: &mut Frame, area: Rect, state: &InvoiceWizardState, accessible: bool) {
+    let current = state.step.index();
+    let mut spans = Vec::new();
+    for (index, title) in WizardStep::TITLES.iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::styled("  →  ", Style::default().fg(Color::DarkGray)));
+        }
+        let (marker, style) = match index.cmp(&current) {
+            std::cmp::Ordering::Less => ("✓ ", Style::default().fg(Color::Green)),
+            std::cmp::Ordering::Equal => ("▶ ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            std::cmp::Ordering::Greater => ("", Style::default().fg(Color::DarkGray)),
+        };
+        if accessible {
+            spans.push(Span::styled(marker, style));
+        }
+        spans.push(Span::styled(*title, style));
+    }
+    if let Some(customer) = &state.customer {
+        spans.push(Span::raw("   |   "));
+        spans.push(Span::raw(name_or_id(&customer.name, &customer.id)));
+    }
+
+    let paragraph = Paragraph::new(Line::from(spans)).block(Block::default().borders(Borders::ALL).title("New Invoice"));
+    f.render_widget(paragraph, area);
+}
+
+fn draw_customers(f: &mut Frame, area: Rect, state: &InvoiceWizardState) {
+    let items: Vec<ListItem> = state
+        .customers
+        .iter()
+        .map(|c| {
+            ListItem::new(format!(
+                "{:<10} {}",
+                c.customer_number.clone().unwrap_or_default(),
+                name_or_id(&c.name, &c.id)
+            ))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Who is the invoice for?"))
+        .highlight_style(Style::default().bg(Color::Blue).add_modifier(Modifier::BOLD))
+        .highlight_symbol(">> ");
+    f.render_stateful_widget(list, area, &mut ListState::default().with_selected(Some(state.selected)));
+}
+
+fn draw_articles(f: &mut Frame, area: Rect, state: &InvoiceWizardState) {
+    let rows: Vec<Row> = state
+        .articles
+        .iter()
+        .zip(&state.quantities)
+        .map(|(article, quantity)| {
+            let style = if *quantity > Money::ZERO {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default()
+            };
+            Row::new(vec![
+                name_or_id(&article.name, &article.id),
+                article.unit.clone().unwrap_or_default(),
+                format!("{:>10.2}", article.sales_price.unwrap_or_default()),
+                format!("{:>6}", quantity),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let (net, _, _) = state.totals();
+    let table = Table::new(
+        rows,
+        [Constraint::Min(20), Constraint::Length(6), Constraint::Length(11), Constraint::Length(7)],
+    )
+    .header(Row::new(vec!["Article", "Unit", "Price", "Qty"]).style(Style::default().add_modifier(Modifier::BOLD)))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("What are you selling? (net {:.2} SEK)", net)),
+    )
+    .highlight_style(Style::default().bg(Color::Blue).add_modifier(Modifier::BOLD))
+    .highlight_symbol(">> ");
+
+    f.render_stateful_widget(table, area, &mut TableState::default().with_selected(Some(state.selected)));
+}
+
+fn draw_review(f: &mut Frame, area: Rect, state: &InvoiceWizardState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(4), Constraint::Length(5)])
+        .split(area);
+
+    let rows: Vec<Row> = state
+        .lines()
+        .into_iter()
+        .map(|(article, quantity)| {
+            let price = article.sales_price.unwrap_or_default();
+            Row::new(vec![
+                name_or_id(&article.name, &article.id),
+                format!("{:>6}", quantity),
+                format!("{:>10.2}", price),
+                format!("{:>12.2}", price * quantity),
+            ])
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [Constraint::Min(20), Constraint::Length(7), Constraint::Length(11), Constraint::Length(13)],
+    )
+    .header(Row::new(vec!["Article", "Qty", "Price", "Amount"]).style(Style::default().add_modifier(Modifier::BOLD)))
+    .block(Block::default().borders(Borders::ALL).title("Check the invoice"));
+    f.render_widget(table, chunks[0]);
+
+    let (net, vat, total) = state.totals();
+    let text = vec![
+        Line::from(format!("Net:   {:>12.2} SEK", net)),
+        Line::from(format!("VAT:   {:>12.2} SEK (estimate)", vat)),
+        Line::from(Span::styled(
+            format!("Total: {:>12.2} SEK", total),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+    ];
+    let paragraph = Paragraph::new(text).block(Block::default().borders(Borders::ALL).title("Totals"));
+    f.render_widget(paragraph, chunks[1]);
+}
+
+fn draw_send(f: &mut Frame, area: Rect, state: &InvoiceWizardState) {
+    let email = state.customer.as_ref().and_then(|c| c.email.clone());
+    let items: Vec<ListItem> = SendMethod::ALL
+        .iter()
+        .map(|method| match (method, &email) {
+            (SendMethod::Email, Some(email)) => ListItem::new(format!("{} ({})", method.label(), email)),
+            (SendMethod::Email, None) => ListItem::new(format!("{} (customer has no email address)", method.label()))
+                .style(Style::default().fg(Color::DarkGray)),
+            _ => ListItem::new(method.label()),
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("How should it be sent? (Enter creates the invoice)"))
+        .highlight_style(Style::default().bg(Color::Blue).add_modifier(Modifier::BOLD))
+        .highlight_symbol(">> ");
+    f.render_stateful_widget(list, area, &mut ListState::default().with_selected(Some(state.send_method)));
+}
+
+fn draw_done(f: &mut Frame, area: Rect, state: &InvoiceWizardState, accessible: bool) {
+    let (ok, failed) = if accessible { ("✓ ", "✗ ") } else { ("", "") };
+    let line = match &state.result {
+        Some(Ok(message)) => Span::styled(format!("{}{}", ok, message), Style::default().fg(Color::Green)),
+        Some(Err(message)) => Span::styled(format!("{}{}", failed, message), Style::default().fg(Color::Red)),
+        None => Span::raw("Creating invoice..."),
+    };
+    let text = vec![
+        Line::from(line),
+        Line::from(""),
+        Line::from("Press ESC to start another invoice"),
+    ];
+    let paragraph = Paragraph::new(text)
+        .block(Block::default().borders(Borders::ALL).title("Done"))
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn state() -> InvoiceWizardState {
+        let mut state = InvoiceWizardState::default();
+        state.reset(
+            vec![Customer {
+                id: Some("cust-1".into()),
+                name: Some("Acme AB".to_string()),
+                ..Default::default()
+            }],
+            vec![
+                Article {
+                    id: Some("art-1".into()),
+                    name: Some("Consulting".to_string()),
+                    sales_price: Some(spiris::money!(1000.0)),
+                    vat_rate_id: Some("vat-25".to_string()),
+                    ..Default::default()
+                },
+                Article {
+                    id: Some("art-2".into()),
+                    name: Some("Book".to_string()),
+                    sales_price: Some(spiris::money!(200.0)),
+                    vat_rate_id: Some("vat-6".to_string()),
+                    ..Default::default()
+                },
+            ],
+            vec![
+                VatCode {
+                    id: Some("vat-25".to_string()),
+                    vat_rate: Some(spiris::money!(0.25)),
+                    ..Default::default()
+                },
+                VatCode {
+                    id: Some("vat-6".to_string()),
+                    vat_rate: Some(spiris::money!(0.06)),
+                    ..Default::default()
+                },
+            ],
+        );
+        state
+    }
+
+    #[test]
+    fn test_steps_and_totals() {
+        let mut state = state();
+        state.advance().unwrap();
+        assert_eq!(state.step, WizardStep::Articles);
+
+        // Needs at least one article
+        assert!(state.advance().is_err());
+        state.adjust_quantity(Money::from(2));
+        state.select_next();
+        state.adjust_quantity(Money::from(1));
+        state.adjust_quantity(Money::from(-5));
+        state.adjust_quantity(Money::from(3));
+        state.advance().unwrap();
+        assert_eq!(state.step, WizardStep::Review);
+
+        let (net, vat, total) = state.totals();
+        assert_eq!(net, spiris::money!(2600.0));
+        assert_eq!(vat, spiris::money!(536.0));
+        assert_eq!(total, spiris::money!(3136.0));
+
+        let invoice = state.invoice();
+        assert_eq!(invoice.customer_id.as_deref(), Some("cust-1"));
+        assert_eq!(invoice.rows.len(), 2);
+        assert_eq!(invoice.rows[1].quantity, Some(Money::from(3)));
+
+        state.advance().unwrap();
+        state.select_next();
+        assert_eq!(state.send_method(), SendMethod::Email);
+
+        // Back through the steps keeps the picked articles
+        assert!(state.back());
+        assert!(state.back());
+        assert!(state.back());
+        assert_eq!(state.step, WizardStep::Customer);
+        assert_eq!(state.lines().len(), 2);
+        assert!(!state.back());
+    }
+
+    #[test]
+    fn test_draw_review() {
+        let mut state = state();
+        state.advance().unwrap();
+        state.adjust_quantity(Money::from(1));
+        state.advance().unwrap();
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|f| draw(f, f.area(), &state, false, false)).unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(content.contains("Acme AB"));
+        assert!(content.contains("1250.00 SEK"));
+    }
+}
@@ -1,5 +1,14 @@
 // Screen modules
 // Each screen can have its own module for complex logic
 
-// Currently all screen rendering is handled in ui.rs
-// This module is reserved for future screen-specific logic
+// Most screen rendering is still handled in ui.rs; newer screens keep their
+// state and drawing code here.
+
+pub mod approvals;
+pub mod cash_flow;
+pub mod dunning;
+pub mod fiscal_years;
+pub mod invoice_wizard;
+pub mod reconciliation;
+pub mod reports;
+pub mod setup;
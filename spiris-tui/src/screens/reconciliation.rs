@@ -0,0 +1,397 @@
+//! Reconciliation screen.
+//!
+//! Shows transactions from a camt.054 or BgMax file next to the open customer
+//! and supplier invoices, with the suggested match for each transaction.
+//! Confirming a suggestion registers the payment on the invoice.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use spiris::bank_files::BankTransaction;
+use spiris::reconciliation::{suggest_matches, MatchReason, MatchSuggestion, OpenItem, OpenItemKind};
+
+/// State for the reconciliation screen.
+#[derive(Debug, Clone, Default)]
+pub struct ReconciliationState {
+    /// Path of the loaded bank file.
+    pub file: Option<String>,
+    /// Transactions not yet reconciled.
+    pub transactions: Vec<BankTransaction>,
+    pub items: Vec<OpenItem>,
+    pub suggestions: Vec<MatchSuggestion>,
+    pub selected: usize,
+    /// Number of payments registered in this session.
+    pub reconciled: usize,
+}
+
+impl ReconciliationState {
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.transactions.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Recompute the match suggestions.
+    pub fn update_suggestions(&mut self) {
+        self.suggestions = suggest_matches(&self.transactions, &self.items);
+        self.selected = self.selected.min(self.transactions.len().saturating_sub(1));
+    }
+
+    /// Suggestion for the selected transaction.
+    pub fn selected_suggestion(&self) -> Option<&MatchSuggestion> {
+        self.suggestions.iter().find(|s| s.transaction == self.selected)
+    }
+
+    /// Remove a registered match: the transaction is dropped and the open
+    /// item reduced by the paid amount.
+    pub fn apply_match(&mut self, suggestion: &MatchSuggestion) {
+        let transaction = self.transactions.remove(suggestion.transaction);
+        let item = &mut self.items[suggestion.item];
+        item.remaining -= transaction.amount.abs();
+        if item.remaining <= spiris::Money::ZERO {
+            self.items.remove(suggestion.item);
+        }
+        self.reconciled += 1;
+        self.update_suggestions();
+    }
+}
+
+/// Draw the screen; `input` is the bank file path being typed, if any.
+pub fn draw(
+    f: &mut Frame,
+    area: Rect,
+    state: &ReconciliationState,
+    input: Option<&str>,
+    loading: bool,
+    accessible: bool,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(8),
+            Constraint::Length(4),
+        ])
+        .split(area);
+
+    if let Some(input) = input {
+        let paragraph = Paragraph::new(input)
+            .style(Style::default().fg(Color::Yellow))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Bank file path (camt.054 or BgMax)"),
+            );
+        f.render_widget(paragraph, chunks[0]);
+    } else if state.file.is_none() {
+        let message = if loading {
+            "Loading bank file..."
+        } else {
+            "No bank file loaded - press 'l' to load a camt.054 or BgMax file"
+        };
+        let paragraph = Paragraph::new(message)
+            .block(Block::default().borders(Borders::ALL).title("Reconciliation"));
+        f.render_widget(paragraph, area);
+        return;
+    } else {
+        draw_summary(f, chunks[0], state);
+    }
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(chunks[1]);
+    draw_transactions(f, columns[0], state, accessible);
+    draw_open_items(f, columns[1], state);
+
+    draw_suggestion(f, chunks[2], state, accessible);
+}
+
+fn draw_summary(f: &mut Frame, area: Rect, state: &ReconciliationState) {
+    let text = Line::from(vec![
+        Span::styled("File: ", Style::default().fg(Color::Yellow)),
+        Span::raw(state.file.clone().unwrap_or_default()),
+        Span::styled(" | Open: ", Style::default().fg(Color::Yellow)),
+        Span::raw(state.transactions.len().to_string()),
+        Span::styled(" | Suggested: ", Style::default().fg(Color::Yellow)),
+        Span::raw(state.suggestions.len().to_string()),
+        Span::styled(" | Registered: ", Style::default().fg(Color::Yellow)),
+        Span::styled(state.reconciled.to_string(), Style::default().fg(Color::Green)),
+    ]);
+
+    let paragraph =
+        Paragraph::new(text).block(Block::default().borders(Borders::ALL).title("Reconciliation"));
+    f.render_widget(paragraph, area);
+}
+
+fn confidence_color(confidence: f64) -> Color {
+    if confidence >= 0.8 {
+        Color::Green
+    } else if confidence >= 0.5 {
+        Color::Yellow
+    } else {
+        Color::Red
+    }
+}
+
+/// Marker and word for the confidence band, for when color is not enough.
+fn confidence_band(confidence: f64) -> (&'static str, &'static str) {
+    if confidence >= 0.8 {
+        ("+", "high")
+    } else if confidence >= 0.5 {
+        ("~", "medium")
+    } else {
+        ("?", "low")
+    }
+}
+
+fn draw_transactions(f: &mut Frame, area: Rect, state: &ReconciliationState, accessible: bool) {
+    let rows: Vec<Row> = state
+        .transactions
+        .iter()
+        .enumerate()
+        .map(|(index, transaction)| {
+            let suggestion = state.suggestions.iter().find(|s| s.transaction == index);
+            let matched = match suggestion {
+                Some(s) => Span::styled(
+                    format!(
+                        "{:>3.0}%{}",
+                        s.confidence * 100.0,
+                        if accessible { confidence_band(s.confidence).0 } else { "" }
+                    ),
+                    Style::default().fg(confidence_color(s.confidence)),
+                ),
+                None => Span::styled("  -", Style::default().fg(Color::Gray)),
+            };
+            Row::new(vec![
+                Line::from(transaction.booking_date.to_string()),
+                Line::from(format!("{:>12.2}", transaction.amount)),
+                Line::from(
+                    transaction
+                        .reference
+                        .clone()
+                        .or_else(|| transaction.message.clone())
+                        .unwrap_or_default(),
+                ),
+                Line::from(transaction.counterparty.clone().unwrap_or_default()),
+                Line::from(matched),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(13),
+            Constraint::Min(12),
+            Constraint::Length(16),
+            Constraint::Length(if accessible { 6 } else { 5 }),
+        ],
+    )
+    .header(
+        Row::new(vec!["Date", "Amount", "Reference", "Counterparty", "Match"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title("Bank transactions"))
+    .highlight_style(Style::default().bg(Color::Blue).add_modifier(Modifier::BOLD))
+    .highlight_symbol(">> ");
+
+    f.render_stateful_widget(
+        table,
+        area,
+        &mut TableState::default().with_selected(Some(state.selected)),
+    );
+}
+
+fn draw_open_items(f: &mut Frame, area: Rect, state: &ReconciliationState) {
+    let suggested_item = state.selected_suggestion().map(|s| s.item);
+
+    let rows: Vec<Row> = state
+        .items
+        .iter()
+        .map(|item| {
+            let kind = match item.kind {
+                OpenItemKind::CustomerInvoice => "AR",
+                OpenItemKind::SupplierInvoice => "AP",
+            };
+            Row::new(vec![
+                kind.to_string(),
+                item.invoice_number.clone().unwrap_or_default(),
+                item.ocr_number.clone().unwrap_or_default(),
+                item.due_date.map(|d| d.to_string()).unwrap_or_default(),
+                format!("{:>12.2}", item.remaining),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(3),
+            Constraint::Length(10),
+            Constraint::Min(10),
+            Constraint::Length(10),
+            Constraint::Length(13),
+        ],
+    )
+    .header(
+        Row::new(vec!["", "Invoice", "OCR", "Due", "Remaining"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title("Open items"))
+    .highlight_style(Style::default().bg(Color::Green).fg(Color::Black))
+    .highlight_symbol("=> ");
+
+    f.render_stateful_widget(
+        table,
+        area,
+        &mut TableState::default().with_selected(suggested_item),
+    );
+}
+
+fn draw_suggestion(f: &mut Frame, area: Rect, state: &ReconciliationState, accessible: bool) {
+    let lines = match state.selected_suggestion() {
+        Some(suggestion) => {
+            let item = &state.items[suggestion.item];
+            let reasons: Vec<&str> = suggestion
+                .reasons
+                .iter()
+                .map(|reason| match reason {
+                    MatchReason::Reference => "reference matches",
+                    MatchReason::InvoiceNumberInMessage => "invoice number in message",
+                    MatchReason::Amount => "amount matches",
+                })
+                .collect();
+            vec![
+                Line::from(vec![
+                    Span::raw("Suggested: invoice "),
+                    Span::styled(
+                        item.invoice_number.clone().unwrap_or_else(|| item.invoice_id.clone()),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        if accessible {
+                            format!(
+                                " ({:.0}% confidence, {})",
+                                suggestion.confidence * 100.0,
+                                confidence_band(suggestion.confidence).1
+                            )
+                        } else {
+                            format!(" ({:.0}% confidence)", suggestion.confidence * 100.0)
+                        },
+                        Style::default().fg(confidence_color(suggestion.confidence)),
+                    ),
+                ]),
+                Line::from(format!("{} - press Enter to register the payment", reasons.join(", "))),
+            ]
+        }
+        None => vec![Line::from(Span::styled(
+            "No match suggested for this transaction",
+            Style::default().fg(Color::Gray),
+        ))],
+    };
+
+    let paragraph =
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Match"));
+    f.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn transaction(amount: i32, reference: &str) -> BankTransaction {
+        BankTransaction {
+            booking_date: NaiveDate::from_ymd_opt(2025, 3, 14).unwrap(),
+            amount: amount.into(),
+            currency: "SEK".to_string(),
+            reference: Some(reference.to_string()),
+            message: None,
+            counterparty: Some("Kundbolaget AB".to_string()),
+            bank_reference: None,
+        }
+    }
+
+    fn item(number: &str, remaining: i32) -> OpenItem {
+        OpenItem {
+            kind: OpenItemKind::CustomerInvoice,
+            invoice_id: format!("id-{}", number),
+            invoice_number: Some(number.to_string()),
+            ocr_number: None,
+            counterparty_id: None,
+            due_date: None,
+            remaining: remaining.into(),
+            currency_code: Some("SEK".to_string()),
+        }
+    }
+
+    fn state() -> ReconciliationState {
+        let mut state = ReconciliationState {
+            file: Some("camt054.xml".to_string()),
+            transactions: vec![transaction(400, "1001"), transaction(750, "1002")],
+            items: vec![item("1001", 1000), item("1002", 750)],
+            ..Default::default()
+        };
+        state.update_suggestions();
+        state
+    }
+
+    #[test]
+    fn test_apply_partial_and_full_match() {
+        let mut state = state();
+        assert_eq!(state.suggestions.len(), 2);
+
+        let partial = state.selected_suggestion().cloned().unwrap();
+        state.apply_match(&partial);
+        assert_eq!(state.transactions.len(), 1);
+        assert_eq!(state.items[0].remaining, spiris::Money::from(600));
+
+        let full = state.selected_suggestion().cloned().unwrap();
+        state.apply_match(&full);
+        assert!(state.transactions.is_empty());
+        assert_eq!(state.items.len(), 1);
+        assert_eq!(state.reconciled, 2);
+    }
+
+    #[test]
+    fn test_draw_reconciliation() {
+        let state = state();
+
+        let mut terminal = Terminal::new(TestBackend::new(140, 30)).unwrap();
+        terminal.draw(|f| draw(f, f.area(), &state, None, false, false)).unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(content.contains("camt054.xml"));
+        assert!(content.contains("Kundbolaget AB"));
+        assert!(content.contains("60% confidence"));
+        assert!(!content.contains("60% confidence, medium"));
+
+        terminal.draw(|f| draw(f, f.area(), &state, None, false, true)).unwrap();
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(content.contains("60% confidence, medium"));
+        assert!(content.contains("60%~"));
+    }
+}
@@ -0,0 +1,198 @@
+//! Reports screen.
+//!
+//! Compares the budget in `budget.json` next to config.toml with the actual
+//! result for one month, using `spiris::budget`.
+
+use chrono::{Datelike, Months, NaiveDate};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table},
+    Frame,
+};
+use spiris::budget::BudgetVsActual;
+use spiris::Money;
+
+/// State for the reports screen.
+#[derive(Debug, Clone, Default)]
+pub struct ReportsState {
+    /// First day of the month shown; the current month until changed.
+    pub month: Option<NaiveDate>,
+    pub report: Option<BudgetVsActual>,
+}
+
+impl ReportsState {
+    /// The month shown, as its first and last day.
+    pub fn period(&self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let start = self.month.unwrap_or_else(|| today.with_day(1).unwrap());
+        let end = start + Months::new(1) - chrono::Days::new(1);
+        (start, end)
+    }
+
+    pub fn previous_month(&mut self, today: NaiveDate) {
+        let (start, _) = self.period(today);
+        self.month = Some(start - Months::new(1));
+    }
+
+    pub fn next_month(&mut self, today: NaiveDate) {
+        let (start, _) = self.period(today);
+        self.month = Some(start + Months::new(1));
+    }
+}
+
+fn amount(value: Money) -> String {
+    format!("{:.2}", value)
+}
+
+fn variance_span(value: Money) -> Span<'static> {
+    let color = if value < Money::ZERO { Color::Red } else { Color::Green };
+    Span::styled(amount(value), Style::default().fg(color))
+}
+
+pub fn draw(f: &mut Frame, area: Rect, state: &ReportsState, loading: bool) {
+    let Some(report) = &state.report else {
+        let message = if loading {
+            "Loading budget vs actual..."
+        } else {
+            "No report loaded - add budget.json next to config.toml and press 'r'"
+        };
+        let paragraph = Paragraph::new(message)
+            .block(Block::default().borders(Borders::ALL).title("Reports"));
+        f.render_widget(paragraph, area);
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(6)])
+        .split(area);
+
+    let summary = Paragraph::new(Line::from(vec![
+        Span::styled("Budget vs actual: ", Style::default().fg(Color::Yellow)),
+        Span::raw(format!(
+            "{} | {} - {} | Variance ",
+            report.budget_name, report.period_start, report.period_end
+        )),
+        variance_span(report.total_variance),
+    ]))
+    .block(Block::default().borders(Borders::ALL).title("Reports"));
+    f.render_widget(summary, chunks[0]);
+
+    let mut rows: Vec<Row> = report
+        .lines
+        .iter()
+        .map(|line| {
+            Row::new(vec![
+                Line::from(line.accounts.to_string()),
+                Line::from(line.label.clone().unwrap_or_default()),
+                Line::from(amount(line.budget)).right_aligned(),
+                Line::from(amount(line.actual)).right_aligned(),
+                Line::from(variance_span(line.variance)).right_aligned(),
+            ])
+        })
+        .collect();
+    if report.unbudgeted != Money::ZERO {
+        rows.push(Row::new(vec![
+            Line::from(""),
+            Line::from("Not budgeted"),
+            Line::from(amount(Money::ZERO)).right_aligned(),
+            Line::from(amount(report.unbudgeted)).right_aligned(),
+            Line::from(variance_span(report.unbudgeted)).right_aligned(),
+        ]));
+    }
+    rows.push(
+        Row::new(vec![
+            Line::from("Total"),
+            Line::from(""),
+            Line::from(amount(report.total_budget)).right_aligned(),
+            Line::from(amount(report.total_actual)).right_aligned(),
+            Line::from(variance_span(report.total_variance)).right_aligned(),
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD)),
+    );
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Min(16),
+            Constraint::Length(14),
+            Constraint::Length(14),
+            Constraint::Length(14),
+        ],
+    )
+    .header(
+        Row::new(vec!["Accounts", "Name", "Budget", "Actual", "Variance"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title("Budget lines"));
+
+    f.render_widget(table, chunks[1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+    use spiris::budget::{AccountRange, Budget};
+    use spiris::reports::ProfitAndLoss;
+    use spiris::AccountBalance;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_month_navigation() {
+        let today = date(2025, 3, 14);
+        let mut state = ReportsState::default();
+        assert_eq!(state.period(today), (date(2025, 3, 1), date(2025, 3, 31)));
+
+        state.previous_month(today);
+        assert_eq!(state.period(today), (date(2025, 2, 1), date(2025, 2, 28)));
+        state.next_month(today);
+        state.next_month(today);
+        assert_eq!(state.period(today), (date(2025, 4, 1), date(2025, 4, 30)));
+    }
+
+    #[test]
+    fn test_draw_budget_vs_actual() {
+        let budget = Budget::new("2025")
+            .month(AccountRange::new(3000, 3999).unwrap(), date(2025, 1, 1), spiris::money!(1000.0))
+            .label(AccountRange::new(3000, 3999).unwrap(), "Sales");
+        let closing = [
+            AccountBalance {
+                account_number: Some("3001".to_string()),
+                name: None,
+                balance: Some(spiris::money!(-800.0)),
+            },
+            AccountBalance {
+                account_number: Some("6110".to_string()),
+                name: None,
+                balance: Some(spiris::money!(50.0)),
+            },
+        ];
+        let pnl = ProfitAndLoss::from_balances(date(2025, 1, 1), date(2025, 1, 31), &[], &closing);
+        let state = ReportsState {
+            month: Some(date(2025, 1, 1)),
+            report: Some(BudgetVsActual::from_pnl(&budget, &pnl).unwrap()),
+        };
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 14)).unwrap();
+        terminal.draw(|f| draw(f, f.area(), &state, false)).unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(content.contains("2025-01-01 - 2025-01-31 | Variance -250.00"));
+        assert!(content.contains("3000-3999"));
+        assert!(content.contains("Sales"));
+        assert!(content.contains("Not budgeted"));
+        assert!(content.contains("-200.00"));
+    }
+}
@@ -0,0 +1,434 @@
+//! Setup screen, shown when there is no usable sign-in.
+//!
+//! Walks through the four things the TUI needs before it can talk to the
+//! API: client credentials, authorization in the browser, a test call, and
+//! the config file. Credentials come from `SPIRIS_CLIENT_ID`,
+//! `SPIRIS_CLIENT_SECRET` and `SPIRIS_REDIRECT_URI`, then from the `[auth]`
+//! table in config.toml, and are asked for if neither has them. Demo mode,
+//! with sample data and no API access, is offered at every step.
+
+use chrono::{Duration, NaiveDate};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+use spiris::{Article, Customer, Invoice, Money};
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+use crate::config::AuthConfig;
+
+pub const DEFAULT_REDIRECT_URI: &str = "http://localhost:8080/callback";
+
+/// How long to wait for the browser to come back with a code.
+const CALLBACK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SetupStep {
+    #[default]
+    ClientId,
+    ClientSecret,
+    Authorize,
+    Verify,
+    Done,
+}
+
+/// Outcome of one setup check.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Check {
+    #[default]
+    Pending,
+    Passed(String),
+    Failed(String),
+}
+
+/// Where the authorization code from the browser ends up.
+#[derive(Debug, Clone, Default)]
+pub struct CallbackSlot(Arc<Mutex<Option<Result<String, String>>>>);
+
+impl CallbackSlot {
+    pub fn take(&self) -> Option<Result<String, String>> {
+        self.0.lock().unwrap().take()
+    }
+}
+
+/// An authorization started in the browser.
+#[derive(Debug, Clone)]
+pub struct PendingAuth {
+    pub url: String,
+    pub pkce_verifier: String,
+    pub callback: CallbackSlot,
+}
+
+/// State for the setup screen.
+#[derive(Debug, Clone, Default)]
+pub struct SetupState {
+    pub step: SetupStep,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub redirect_uri: String,
+    /// Credentials were typed in and should be saved to config.toml
+    pub entered: bool,
+    pub credentials: Check,
+    pub authorization: Check,
+    pub api_access: Check,
+    pub config_file: Check,
+    pub pending: Option<PendingAuth>,
+}
+
+impl SetupState {
+    /// Start setup with the credentials found in the environment or config.
+    pub fn new(auth: &AuthConfig) -> Self {
+        let lookup = |var: &str, configured: &Option<String>| {
+            match std::env::var(var).ok().filter(|v| !v.trim().is_empty()) {
+                Some(value) => Some((value, "environment")),
+                None => configured.clone().filter(|v| !v.trim().is_empty()).map(|v| (v, "config.toml")),
+            }
+        };
+        let id = lookup("SPIRIS_CLIENT_ID", &auth.client_id);
+        let secret = lookup("SPIRIS_CLIENT_SECRET", &auth.client_secret);
+        let redirect_uri = lookup("SPIRIS_REDIRECT_URI", &auth.redirect_uri)
+            .map_or_else(|| DEFAULT_REDIRECT_URI.to_string(), |(uri, _)| uri);
+
+        let mut state = Self {
+            redirect_uri,
+            ..Default::default()
+        };
+        match (&id, &secret) {
+            (Some((_, id_source)), Some((_, secret_source))) => {
+                let source = if id_source == secret_source {
+                    format!("from {}", id_source)
+                } else {
+                    format!("from {} and {}", id_source, secret_source)
+                };
+                state.credentials = Check::Passed(source);
+                state.step = SetupStep::Authorize;
+            }
+            (Some(_), None) => state.step = SetupStep::ClientSecret,
+            _ => state.step = SetupStep::ClientId,
+        }
+        state.client_id = id.map(|(v, _)| v);
+        state.client_secret = secret.map(|(v, _)| v);
+        state
+    }
+
+    /// Whether the current step takes typed input.
+    pub fn wants_input(&self) -> bool {
+        matches!(self.step, SetupStep::ClientId | SetupStep::ClientSecret)
+    }
+
+    /// Store a typed client ID or secret and move on.
+    pub fn enter(&mut self, value: &str) -> Result<(), String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err("A value is required".to_string());
+        }
+        match self.step {
+            SetupStep::ClientId => {
+                self.client_id = Some(value.to_string());
+                self.step = SetupStep::ClientSecret;
+                if self.client_secret.is_some() {
+                    self.step = SetupStep::Authorize;
+                }
+            }
+            SetupStep::ClientSecret => {
+                self.client_secret = Some(value.to_string());
+                self.step = SetupStep::Authorize;
+            }
+            _ => return Ok(()),
+        }
+        self.entered = true;
+        if self.step == SetupStep::Authorize {
+            self.credentials = Check::Passed("entered here".to_string());
+        }
+        Ok(())
+    }
+
+    /// Client ID, secret and redirect URI, once all are known.
+    pub fn credentials(&self) -> Option<(String, String, String)> {
+        Some((self.client_id.clone()?, self.client_secret.clone()?, self.redirect_uri.clone()))
+    }
+
+    /// The `[auth]` table to save, keeping typed credentials only.
+    pub fn auth_config(&self, current: &AuthConfig) -> AuthConfig {
+        if !self.entered {
+            return current.clone();
+        }
+        AuthConfig {
+            client_id: self.client_id.clone(),
+            client_secret: self.client_secret.clone(),
+            redirect_uri: Some(self.redirect_uri.clone()),
+        }
+    }
+}
+
+/// Wait in the background for the browser to be redirected back with an
+/// authorization code.
+pub fn listen_for_callback(redirect_uri: &str, csrf: String) -> anyhow::Result<CallbackSlot> {
+    let redirect = Url::parse(redirect_uri)?;
+    let host = redirect.host_str().unwrap_or("localhost").to_string();
+    let port = redirect.port_or_known_default().unwrap_or(80);
+    let server = tiny_http::Server::http((host.as_str(), port))
+        .map_err(|e| anyhow::anyhow!("Cannot listen on {}:{} for the sign-in: {}", host, port, e))?;
+
+    let slot = CallbackSlot::default();
+    let result = slot.clone();
+    let path = redirect.path().to_string();
+    std::thread::spawn(move || {
+        let deadline = std::time::Instant::now() + CALLBACK_TIMEOUT;
+        let outcome = loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            let request = match server.recv_timeout(remaining) {
+                Ok(Some(request)) => request,
+                Ok(None) => break Err("No sign-in from the browser within 5 minutes".to_string()),
+                Err(e) => break Err(e.to_string()),
+            };
+            // Browsers also ask for /favicon.ico and the like
+            if !request.url().starts_with(&path) {
+                let _ = request.respond(tiny_http::Response::empty(404));
+                continue;
+            }
+            let outcome = parse_callback(request.url(), &csrf);
+            let page = match &outcome {
+                Ok(_) => "Signed in. You can close this tab and return to the terminal.",
+                Err(_) => "Sign-in failed. See the terminal for details.",
+            };
+            let _ = request.respond(tiny_http::Response::from_string(page));
+            break outcome;
+        };
+        *result.0.lock().unwrap() = Some(outcome);
+    });
+    Ok(slot)
+}
+
+/// The authorization code from a callback path such as
+/// `/callback?code=...&state=...`.
+fn parse_callback(path: &str, csrf: &str) -> Result<String, String> {
+    let url = Url::parse(&format!("http://localhost{}", path)).map_err(|e| e.to_string())?;
+    let param = |name: &str| url.query_pairs().find(|(k, _)| k == name).map(|(_, v)| v.into_owned());
+
+    if let Some(error) = param("error") {
+        return Err(param("error_description").unwrap_or(error));
+    }
+    if param("state").as_deref() != Some(csrf) {
+        return Err("The sign-in response did not match this request".to_string());
+    }
+    param("code").ok_or_else(|| "The sign-in response had no authorization code".to_string())
+}
+
+/// Sample customers, invoices and articles for demo mode.
+pub fn demo_data() -> (Vec<Customer>, Vec<Invoice>, Vec<Article>) {
+    let customers: Vec<Customer> = [
+        ("1001", "Kundbolaget AB", "ekonomi@kundbolaget.example"),
+        ("1002", "Ek Konsult AB", "faktura@ekkonsult.example"),
+        ("1003", "Norrsken Design", "hej@norrsken.example"),
+        ("1004", "Sjöberg Bygg AB", "info@sjobergbygg.example"),
+    ]
+    .iter()
+    .enumerate()
+    .map(|(index, (number, name, email))| Customer {
+        id: Some(format!("demo-customer-{}", index + 1).into()),
+        customer_number: Some(number.to_string()),
+        name: Some(name.to_string()),
+        email: Some(email.to_string()),
+        is_active: Some(true),
+        ..Default::default()
+    })
+    .collect();
+
+    let articles = [("A1", "Consulting hour", 950), ("A2", "Design workshop", 12000), ("A3", "Travel", 450)]
+        .iter()
+        .enumerate()
+        .map(|(index, (number, name, price))| Article {
+            id: Some(format!("demo-article-{}", index + 1).into()),
+            article_number: Some(number.to_string()),
+            name: Some(name.to_string()),
+            sales_price: Some(Money::from(*price)),
+            is_active: Some(true),
+            ..Default::default()
+        })
+        .collect();
+
+    let start = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+    let invoices = [(0, 11875, 0), (1, 15000, 15000), (2, 4750, 4750), (0, 2375, 0), (3, 36250, 36250)]
+        .iter()
+        .enumerate()
+        .map(|(index, (customer, total, remaining))| {
+            let date = start + Duration::days(index as i64 * 14);
+            Invoice {
+                id: Some(format!("demo-invoice-{}", index + 1).into()),
+                invoice_number: Some((5001 + index).to_string()),
+                customer_id: customers[*customer].id.clone(),
+                invoice_date: Some(date),
+                due_date: Some(date + Duration::days(30)),
+                total_amount_including_vat: Some(Money::from(*total)),
+                remaining_amount: Some(Money::from(*remaining)),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    (customers, invoices, articles)
+}
+
+fn check_line(label: &str, check: &Check) -> Line<'static> {
+    let (mark, color, detail) = match check {
+        Check::Pending => ("[ ]", Color::Gray, String::new()),
+        Check::Passed(detail) => ("[✓]", Color::Green, detail.clone()),
+        Check::Failed(detail) => ("[✗]", Color::Red, detail.clone()),
+    };
+    Line::from(vec![
+        Span::styled(format!("{} ", mark), Style::default().fg(color)),
+        Span::styled(format!("{:<20}", label), Style::default().add_modifier(Modifier::BOLD)),
+        Span::styled(detail, Style::default().fg(color)),
+    ])
+}
+
+/// Draw the setup screen; `input` is the text being typed, if any.
+pub fn draw(f: &mut Frame, area: Rect, state: &SetupState, input: Option<&str>, loading: bool) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(6), Constraint::Min(6)])
+        .split(area);
+
+    let checks = Paragraph::new(vec![
+        check_line("Client credentials", &state.credentials),
+        check_line("Authorization", &state.authorization),
+        check_line("API access", &state.api_access),
+        check_line("Config file", &state.config_file),
+    ])
+    .block(Block::default().borders(Borders::ALL).title("Setup"));
+    f.render_widget(checks, chunks[0]);
+
+    let mut text = Vec::new();
+    match state.step {
+        SetupStep::ClientId | SetupStep::ClientSecret => {
+            let field = if state.step == SetupStep::ClientId { "Client ID" } else { "Client secret" };
+            text.push(Line::from(
+                "No API client credentials were found in SPIRIS_CLIENT_ID / SPIRIS_CLIENT_SECRET or config.toml.",
+            ));
+            text.push(Line::from(
+                "Register an application in the Visma Developer Portal to get them, or press D for demo mode.",
+            ));
+            text.push(Line::from(""));
+            match input {
+                Some(input) => {
+                    // Show how much of the secret was typed, not the secret
+                    let shown = if state.step == SetupStep::ClientSecret {
+                        "*".repeat(input.chars().count())
+                    } else {
+                        input.to_string()
+                    };
+                    text.push(Line::from(vec![
+                        Span::styled(format!("{}: ", field), Style::default().fg(Color::Yellow)),
+                        Span::raw(shown),
+                        Span::raw("_"),
+                    ]));
+                }
+                None => text.push(Line::from(format!("Press Enter to type the {}", field.to_lowercase()))),
+            }
+        }
+        SetupStep::Authorize => match &state.pending {
+            Some(pending) => {
+                text.push(Line::from("Open this URL in your browser and sign in:"));
+                text.push(Line::from(""));
+                text.push(Line::from(Span::styled(pending.url.clone(), Style::default().fg(Color::Yellow))));
+                text.push(Line::from(""));
+                text.push(Line::from(format!("Waiting for the browser to return to {} ...", state.redirect_uri)));
+            }
+            None => {
+                text.push(Line::from("Press Enter to sign in to Spiris in your browser."));
+                text.push(Line::from(format!("The browser is sent back to {}.", state.redirect_uri)));
+            }
+        },
+        SetupStep::Verify => {
+            text.push(Line::from(if loading {
+                "Checking API access..."
+            } else {
+                "Press Enter to check API access again."
+            }));
+        }
+        SetupStep::Done => {
+            text.push(Line::from("Setup is complete. Press Enter to start."));
+        }
+    }
+
+    let body = Paragraph::new(text)
+        .block(Block::default().borders(Borders::ALL).title("Next step"))
+        .wrap(Wrap { trim: false });
+    f.render_widget(body, chunks[1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_enter_credentials() {
+        let mut state = SetupState {
+            redirect_uri: DEFAULT_REDIRECT_URI.to_string(),
+            ..Default::default()
+        };
+        assert!(state.enter("  ").is_err());
+        state.enter("my-client").unwrap();
+        assert_eq!(state.step, SetupStep::ClientSecret);
+        assert_eq!(state.credentials, Check::Pending);
+        state.enter("s3cret").unwrap();
+        assert_eq!(state.step, SetupStep::Authorize);
+        assert_eq!(state.credentials, Check::Passed("entered here".to_string()));
+
+        let saved = state.auth_config(&AuthConfig::default());
+        assert_eq!(saved.client_id.as_deref(), Some("my-client"));
+        assert_eq!(saved.redirect_uri.as_deref(), Some(DEFAULT_REDIRECT_URI));
+    }
+
+    #[test]
+    fn test_draw_setup() {
+        let state = SetupState {
+            step: SetupStep::ClientSecret,
+            client_id: Some("my-client".to_string()),
+            redirect_uri: DEFAULT_REDIRECT_URI.to_string(),
+            authorization: Check::Failed("access denied".to_string()),
+            ..Default::default()
+        };
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|f| draw(f, f.area(), &state, Some("abc"), false)).unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(content.contains("[ ] Client credentials"));
+        assert!(content.contains("[✗] Authorization       access denied"));
+        assert!(content.contains("Client secret: ***_"));
+        assert!(!content.contains("abc"));
+    }
+
+    #[test]
+    fn test_parse_callback() {
+        assert_eq!(parse_callback("/callback?code=abc&state=xyz", "xyz"), Ok("abc".to_string()));
+        assert!(parse_callback("/callback?code=abc&state=other", "xyz").is_err());
+        assert_eq!(
+            parse_callback("/callback?error=access_denied&error_description=User+cancelled", "xyz"),
+            Err("User cancelled".to_string())
+        );
+    }
+
+    #[test]
+    fn test_demo_data() {
+        let (customers, invoices, articles) = demo_data();
+        assert_eq!(customers.len(), 4);
+        assert_eq!(articles.len(), 3);
+        assert!(invoices
+            .iter()
+            .all(|i| customers.iter().any(|c| c.id == i.customer_id)));
+    }
+}
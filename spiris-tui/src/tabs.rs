@@ -0,0 +1,180 @@
+//! Detail view tabs.
+//!
+//! Opening a customer, invoice or article from a list also opens it in a
+//! tab. Tabs stay open while moving around the app: Alt+1..9 switches to a
+//! tab and `w` closes the one shown. Each tab keeps a copy of its record and
+//! the screen ESC returns to, so it still shows the record after the list
+//! has moved to another page.
+
+use crate::app::Screen;
+use spiris::{Article, Customer, Invoice};
+
+/// Tabs beyond this can't be reached with Alt+number.
+pub const MAX_TABS: usize = 9;
+
+/// The record shown in a tab.
+#[derive(Debug, Clone)]
+pub enum TabRecord {
+    Customer(Customer),
+    Invoice(Invoice),
+    Article(Article),
+}
+
+impl TabRecord {
+    /// The detail screen showing this record.
+    pub fn screen(&self) -> Option<Screen> {
+        Some(match self {
+            TabRecord::Customer(c) => Screen::CustomerDetail(c.id.clone()?),
+            TabRecord::Invoice(i) => Screen::InvoiceDetail(i.id.clone()?),
+            TabRecord::Article(a) => Screen::ArticleDetail(a.id.clone()?),
+        })
+    }
+
+    /// Short name for the tab bar.
+    pub fn title(&self) -> String {
+        match self {
+            TabRecord::Customer(c) => c.name.clone().unwrap_or_else(|| "Customer".to_string()),
+            TabRecord::Invoice(i) => match &i.invoice_number {
+                Some(number) => format!("Invoice {}", number),
+                None => "Invoice".to_string(),
+            },
+            TabRecord::Article(a) => a.name.clone().unwrap_or_else(|| "Article".to_string()),
+        }
+    }
+}
+
+/// An open tab.
+#[derive(Debug, Clone)]
+pub struct Tab {
+    pub record: TabRecord,
+    /// Screen ESC returns to from this tab.
+    pub back: Option<Screen>,
+}
+
+/// The open tabs, in the order they were opened.
+#[derive(Debug, Clone, Default)]
+pub struct Tabs {
+    open: Vec<Tab>,
+}
+
+impl Tabs {
+    pub fn iter(&self) -> impl Iterator<Item = &Tab> {
+        self.open.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.open.is_empty()
+    }
+
+    /// Tab number `number`, counting from 1.
+    pub fn get(&self, number: usize) -> Option<&Tab> {
+        self.open.get(number.checked_sub(1)?)
+    }
+
+    /// Index of the tab showing `screen`.
+    pub fn position(&self, screen: &Screen) -> Option<usize> {
+        self.open
+            .iter()
+            .position(|tab| tab.record.screen().as_ref() == Some(screen))
+    }
+
+    /// Open `record` in a tab, or update the tab already showing it.
+    ///
+    /// Returns false if all tabs are in use.
+    pub fn open(&mut self, record: TabRecord, back: Option<Screen>) -> bool {
+        let Some(screen) = record.screen() else {
+            return false;
+        };
+        if let Some(index) = self.position(&screen) {
+            self.open[index] = Tab { record, back };
+            return true;
+        }
+        if self.open.len() >= MAX_TABS {
+            return false;
+        }
+        self.open.push(Tab { record, back });
+        true
+    }
+
+    /// Close the tab showing `screen`, if any.
+    pub fn close(&mut self, screen: &Screen) -> Option<Tab> {
+        let index = self.position(screen)?;
+        Some(self.open.remove(index))
+    }
+
+    /// Replace the copies in open tabs with freshly loaded records.
+    pub fn refresh(&mut self, loaded: impl IntoIterator<Item = TabRecord>) {
+        for record in loaded {
+            let screen = record.screen();
+            if let Some(tab) = self.open.iter_mut().find(|tab| tab.record.screen() == screen) {
+                tab.record = record;
+            }
+        }
+    }
+
+    pub fn customer(&self, id: &str) -> Option<&Customer> {
+        self.open.iter().find_map(|tab| match &tab.record {
+            TabRecord::Customer(c) if c.id.as_deref() == Some(id) => Some(c),
+            _ => None,
+        })
+    }
+
+    pub fn invoice(&self, id: &str) -> Option<&Invoice> {
+        self.open.iter().find_map(|tab| match &tab.record {
+            TabRecord::Invoice(i) if i.id.as_deref() == Some(id) => Some(i),
+            _ => None,
+        })
+    }
+
+    pub fn article(&self, id: &str) -> Option<&Article> {
+        self.open.iter().find_map(|tab| match &tab.record {
+            TabRecord::Article(a) if a.id.as_deref() == Some(id) => Some(a),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn customer(id: &str, name: &str) -> TabRecord {
+        TabRecord::Customer(Customer {
+            id: Some(id.into()),
+            name: Some(name.to_string()),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_open_reuses_tab_and_stops_at_limit() {
+        let mut tabs = Tabs::default();
+        assert!(tabs.open(customer("c1", "Acme AB"), Some(Screen::Customers)));
+        assert!(tabs.open(customer("c1", "Acme Sverige AB"), Some(Screen::Search)));
+        assert_eq!(tabs.iter().count(), 1);
+        assert_eq!(tabs.get(1).unwrap().record.title(), "Acme Sverige AB");
+        assert_eq!(tabs.get(1).unwrap().back, Some(Screen::Search));
+        assert!(tabs.get(0).is_none());
+
+        for n in 2..=MAX_TABS {
+            assert!(tabs.open(customer(&format!("c{}", n), "Beta AB"), None));
+        }
+        assert!(!tabs.open(customer("c10", "Gamma AB"), None));
+
+        let closed = tabs.close(&Screen::CustomerDetail("c1".into())).unwrap();
+        assert_eq!(closed.record.title(), "Acme Sverige AB");
+        assert_eq!(tabs.position(&Screen::CustomerDetail("c2".into())), Some(0));
+        assert!(tabs.customer("c1").is_none());
+    }
+
+    #[test]
+    fn test_refresh_updates_open_tabs_only() {
+        let mut tabs = Tabs::default();
+        tabs.open(customer("c1", "Acme AB"), None);
+
+        tabs.refresh([customer("c1", "Acme Sverige AB"), customer("c2", "Beta AB")]);
+
+        assert_eq!(tabs.customer("c1").unwrap().name.as_deref(), Some("Acme Sverige AB"));
+        assert!(tabs.customer("c2").is_none());
+    }
+}
@@ -1,11 +1,17 @@
 use crate::app::{App, InputMode, Screen, SortOrder, CustomerSortField, InvoiceSortField, ArticleSortField};
+use crate::macros::MacroPrompt;
+use crate::screens::invoice_wizard::WizardStep;
+use crate::screens::setup::SetupStep;
+use crate::paging::visible_range;
 use ratatui::{
+    buffer::Buffer,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
     Frame,
 };
+use std::ops::Range;
 
 fn get_sort_indicator(order: &SortOrder) -> &str {
     match order {
@@ -42,15 +48,89 @@ fn get_article_sort_info(app: &App) -> String {
     format!("{} {}", field, get_sort_indicator(&app.article_sort_order))
 }
 
-pub fn draw(f: &mut Frame, app: &App) {
-    let chunks = Layout::default()
+/// Header, content and footer areas of the screen.
+fn screen_layout(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),
             Constraint::Min(0),
             Constraint::Length(3),
         ])
-        .split(f.area());
+        .split(area)
+}
+
+/// The area screens draw their content in.
+pub fn content_area(area: Rect) -> Rect {
+    screen_layout(area)[1]
+}
+
+/// Rows of a bordered list in `area` that are in view, `row_height` lines
+/// each.
+pub fn list_window(area: Rect, len: usize, selected: usize, row_height: u16) -> Range<usize> {
+    let rows = area.height.saturating_sub(2) / row_height.max(1);
+    visible_range(len, selected, rows as usize)
+}
+
+/// The list row drawn on terminal line `y`, if any.
+pub fn list_row_at(area: Rect, y: u16, len: usize, selected: usize, row_height: u16) -> Option<usize> {
+    if y <= area.y || y + 1 >= area.bottom() {
+        return None;
+    }
+    let window = list_window(area, len, selected, row_height);
+    let index = window.start + ((y - area.y - 1) / row_height.max(1)) as usize;
+    window.contains(&index).then_some(index)
+}
+
+/// A list row padded to the configured row height.
+fn list_row(text: String, app: &App) -> ListItem<'static> {
+    let mut lines = vec![Line::from(text)];
+    lines.resize(app.config.accessibility.row_height.max(1) as usize, Line::default());
+    ListItem::new(lines)
+}
+
+/// Input cursor; steady in accessibility mode.
+fn cursor(app: &App) -> Span<'static> {
+    if app.accessible() {
+        Span::styled("_", Style::default().add_modifier(Modifier::BOLD))
+    } else {
+        Span::styled("_", Style::default().add_modifier(Modifier::SLOW_BLINK))
+    }
+}
+
+/// The screen as plain text for screen readers: borders become spaces and
+/// trailing blanks and repeated empty lines are dropped.
+pub fn plain_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut lines: Vec<String> = Vec::new();
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        let mut skip = 0;
+        for x in area.left()..area.right() {
+            if skip > 0 {
+                // Cells covered by a wide character
+                skip -= 1;
+                continue;
+            }
+            let symbol = buffer[(x, y)].symbol();
+            skip = Span::raw(symbol).width().saturating_sub(1);
+            if symbol.chars().all(|c| ('\u{2500}'..='\u{257f}').contains(&c)) {
+                line.push(' ');
+            } else {
+                line.push_str(symbol);
+            }
+        }
+        let line = line.trim_end().to_string();
+        if !(line.is_empty() && lines.last().is_some_and(String::is_empty)) {
+            lines.push(line);
+        }
+    }
+    let text = lines.join("\n");
+    format!("{}\n", text.trim_matches('\n'))
+}
+
+pub fn draw(f: &mut Frame, app: &App) {
+    let chunks = screen_layout(f.area());
 
     // Header
     draw_header(f, chunks[0], app);
@@ -59,7 +139,10 @@ pub fn draw(f: &mut Frame, app: &App) {
     match &app.screen {
         Screen::Home => draw_home(f, chunks[1], app),
         Screen::Dashboard => draw_dashboard(f, chunks[1], app),
-        Screen::Auth => draw_auth(f, chunks[1], app),
+        Screen::Setup => {
+            let input = (app.input_mode == InputMode::Editing).then_some(app.input.as_str());
+            crate::screens::setup::draw(f, chunks[1], &app.setup, input, app.loading)
+        }
         Screen::Customers => draw_customers(f, chunks[1], app),
         Screen::CustomerCreate => draw_customer_form(f, chunks[1], app),
         Screen::CustomerEdit(id) => draw_customer_edit_form(f, chunks[1], app, id),
@@ -74,6 +157,16 @@ pub fn draw(f: &mut Frame, app: &App) {
         Screen::ArticleDetail(id) => draw_article_detail(f, chunks[1], app, id),
         Screen::Search => draw_search(f, chunks[1], app),
         Screen::Export => draw_export(f, chunks[1], app),
+        Screen::CashFlow => crate::screens::cash_flow::draw(f, chunks[1], &app.cash_flow, app.loading),
+        Screen::Approvals => crate::screens::approvals::draw(f, chunks[1], &app.approvals, &app.input, app.loading),
+        Screen::Reconciliation => {
+            let input = (app.input_mode == InputMode::Editing).then_some(app.input.as_str());
+            crate::screens::reconciliation::draw(f, chunks[1], &app.reconciliation, input, app.loading, app.accessible())
+        }
+        Screen::Dunning => crate::screens::dunning::draw(f, chunks[1], &app.dunning, app.loading),
+        Screen::FiscalYears => crate::screens::fiscal_years::draw(f, chunks[1], &app.fiscal_years, app.loading),
+        Screen::Reports => crate::screens::reports::draw(f, chunks[1], &app.reports, app.loading),
+        Screen::InvoiceWizard => crate::screens::invoice_wizard::draw(f, chunks[1], &app.invoice_wizard, app.loading, app.accessible()),
         Screen::Help => draw_help(f, chunks[1]),
     }
 
@@ -89,13 +182,34 @@ pub fn draw(f: &mut Frame, app: &App) {
 fn draw_header(f: &mut Frame, area: Rect, app: &App) {
     let (title, color) = match app.client {
         Some(_) => ("Spiris Bokföring och Fakturering - TUI ✓", Color::Green),
+        None if app.demo => ("Spiris Bokföring och Fakturering - TUI (Demo mode)", Color::Yellow),
         None => ("Spiris Bokföring och Fakturering - TUI (Not Authenticated)", Color::Red),
     };
 
-    let mut header_lines = vec![Line::from(Span::styled(
+    let mut title_spans = vec![Span::styled(
         title,
         Style::default().fg(color).add_modifier(Modifier::BOLD),
-    ))];
+    )];
+    let mut border = Style::default();
+    if let Some(company) = &app.company {
+        let accent = company.accent.unwrap_or(Color::Cyan);
+        title_spans.push(Span::raw(" | "));
+        title_spans.push(Span::styled(
+            company.name.clone(),
+            Style::default().fg(accent).add_modifier(Modifier::BOLD),
+        ));
+        if company.read_only {
+            title_spans.push(Span::styled(
+                " [READ-ONLY]",
+                Style::default().fg(Color::Black).bg(accent),
+            ));
+        }
+        if company.accent.is_some() {
+            border = border.fg(accent);
+        }
+    }
+
+    let mut header_lines = vec![Line::from(title_spans)];
 
     // Show status/error messages in header
     if let Some(msg) = &app.status_message {
@@ -110,17 +224,48 @@ fn draw_header(f: &mut Frame, area: Rect, app: &App) {
         )));
     }
 
+    let mut block = Block::default().borders(Borders::ALL).border_style(border);
+    if !app.tabs.is_empty() {
+        block = block.title(tab_bar(app));
+    }
     let header = Paragraph::new(header_lines)
         .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL));
+        .block(block);
 
     f.render_widget(header, area);
 }
 
+/// Open tabs as `1:Acme AB │ 2:Invoice 1001`, the one shown highlighted.
+fn tab_bar(app: &App) -> Line<'static> {
+    let mut spans = vec![Span::raw(" ")];
+    for (index, tab) in app.tabs.iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::raw(" │ "));
+        }
+        let label = format!("{}:{}", index + 1, tab.record.title());
+        if tab.record.screen().as_ref() == Some(&app.screen) {
+            let label = if app.accessible() { format!("[{}]", label) } else { label };
+            spans.push(Span::styled(
+                label,
+                Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ));
+        } else {
+            spans.push(Span::raw(label));
+        }
+    }
+    spans.push(Span::raw(" "));
+    Line::from(spans)
+}
+
 fn draw_footer(f: &mut Frame, area: Rect, app: &App) {
     let keys = if app.confirm_delete.is_some() {
         // Confirmation dialog is active
         "Y: Confirm deletion | N/ESC: Cancel"
+    } else if let Some(prompt) = &app.macros.prompt {
+        match prompt {
+            MacroPrompt::Bind(_) => "Press a key to bind the macro to | ESC: Discard",
+            MacroPrompt::Play => "Press the key of the macro to play | ESC: Cancel",
+        }
     } else if app.search_input_mode {
         // Search input mode
         "Type to search | Enter: Execute search | ESC: Stop typing"
@@ -153,6 +298,9 @@ fn draw_footer(f: &mut Frame, area: Rect, app: &App) {
                             _ => "Enter: Submit | ESC: Cancel",
                         }
                     }
+                    Screen::Approvals => "Comment | Enter: Submit decision | ESC: Cancel",
+                    Screen::Reconciliation => "Bank file path (camt.054 or BgMax) | Enter: Load | ESC: Cancel",
+                    Screen::Setup => "Enter: Save | ESC: Cancel",
                     _ => "Enter: Next field | ESC: Cancel",
                 }
             }
@@ -162,21 +310,46 @@ fn draw_footer(f: &mut Frame, area: Rect, app: &App) {
                     Screen::Home => "↑↓: Navigate | Enter: Select | c/i/a: Quick jump | q: Quit | h: Help",
                     Screen::Dashboard => "↑↓: Navigate | Enter: Select | c/i/a: Quick jump | r: Refresh | h: Help",
                     Screen::Customers => "↑↓: Select | ←→: Page | o: Sort | Enter: View | n: New | r: Refresh | s: Search | q: Quit",
-                    Screen::CustomerDetail(_) => "e: Edit | x: Delete | ESC: Back | s: Search | d: Dashboard",
+                    Screen::CustomerDetail(_) => "e: Edit | x: Delete | w: Close tab | Alt+1-9: Tabs | ESC: Back | d: Dashboard",
                     Screen::Invoices => "↑↓: Select | ←→: Page | o: Sort | Enter: View | n: New | r: Refresh | s: Search | q: Quit",
-                    Screen::InvoiceDetail(_) => "e: Edit | x: Delete | ESC: Back | s: Search | d: Dashboard",
+                    Screen::InvoiceDetail(_) => "e: Edit | x: Delete | w: Close tab | Alt+1-9: Tabs | ESC: Back | d: Dashboard",
                     Screen::Articles => "↑↓: Select | ←→: Page | o: Sort | Enter: View | n: New | r: Refresh | s: Search | q: Quit",
-                    Screen::ArticleDetail(_) => "e: Edit | x: Delete | ESC: Back | s: Search | d: Dashboard",
+                    Screen::ArticleDetail(_) => "e: Edit | x: Delete | w: Close tab | Alt+1-9: Tabs | ESC: Back | d: Dashboard",
                     Screen::Search => "Start typing to search | Enter: Execute | ESC: Back | d: Dashboard",
                     Screen::Export => "↑↓: Navigate | Enter: Select/Toggle | ESC: Back | d: Dashboard",
+                    Screen::CashFlow => "←→: Horizon (4/8/13 weeks) | r: Refresh | ESC: Back | d: Dashboard",
+                    Screen::Approvals => "↑↓: Select | A: Approve | R: Reject | r: Refresh | ESC: Back",
+                    Screen::Reconciliation => "↑↓: Select | Enter: Confirm match | l: Load bank file | r: Reload items | ESC: Back",
+                    Screen::Dunning if app.dunning.confirming => "Enter: Send reminders | ESC: Cancel",
+                    Screen::Dunning => "↑↓: Select | Space: Include/exclude | Enter: Run | r: Refresh | ESC: Back",
+                    Screen::FiscalYears => "↑↓: Select fiscal year | r: Refresh | ESC: Back",
+                    Screen::Reports => "←→: Month | r: Refresh | ESC: Back | d: Dashboard",
+                    Screen::InvoiceWizard => match app.invoice_wizard.step {
+                        WizardStep::Articles => "↑↓: Select | +/-: Quantity | Enter: Review | ESC: Previous step",
+                        WizardStep::Send => "↑↓: Select | Enter: Create invoice | ESC: Previous step",
+                        WizardStep::Done => "ESC: New invoice | d: Dashboard",
+                        _ => "↑↓: Select | Enter: Next step | ESC: Previous step",
+                    },
                     Screen::Help => "ESC: Back | d: Dashboard | s: Search",
-                    Screen::Auth => "Enter: Start OAuth | q: Quit",
+                    Screen::Setup => match app.setup.step {
+                        SetupStep::Authorize if app.setup.pending.is_some() => "Waiting for the browser | D: Demo mode | q: Quit",
+                        SetupStep::Done => "Enter: Start | q: Quit",
+                        _ => "Enter: Continue | D: Demo mode | q: Quit",
+                    },
                     _ => "ESC: Back | s: Search | d: Dashboard | h: Help",
                 }
             }
         }
     };
 
+    let keys = match &app.macros.recording {
+        Some(recorded) => Line::from(vec![
+            Span::styled(format!("● REC {} | M: Stop | ", recorded.len()), Style::default().fg(Color::Red)),
+            Span::raw(keys),
+        ]),
+        None => Line::from(keys),
+    };
+
     let footer = Paragraph::new(keys)
         .style(Style::default().fg(Color::Gray))
         .alignment(Alignment::Center)
@@ -193,6 +366,13 @@ fn draw_home(f: &mut Frame, area: Rect, app: &App) {
         ListItem::new("Articles - Browse and manage products/articles"),
         ListItem::new("Search - Search across all entities"),
         ListItem::new("Export - Export data to JSON"),
+        ListItem::new("Cash Flow - Weekly forecast of incoming and outgoing payments"),
+        ListItem::new("Approvals - Supplier invoices awaiting your approval"),
+        ListItem::new("Reconciliation - Match bank files against open invoices"),
+        ListItem::new("Dunning - Send payment reminders for overdue invoices"),
+        ListItem::new("Fiscal Years - Periods and lock status"),
+        ListItem::new("New Invoice - Step by step from customer to sent invoice"),
+        ListItem::new("Reports - Budget vs actual per month"),
         ListItem::new("Help - View keyboard shortcuts"),
     ];
 
@@ -212,44 +392,6 @@ fn draw_home(f: &mut Frame, area: Rect, app: &App) {
     );
 }
 
-fn draw_auth(f: &mut Frame, area: Rect, app: &App) {
-    let mut text = vec![
-        Line::from("OAuth2 Authentication Required"),
-        Line::from(""),
-        Line::from("Press Enter to start OAuth2 flow"),
-        Line::from(""),
-    ];
-
-    if let Some(url) = &app.oauth_url {
-        text.push(Line::from(""));
-        text.push(Line::from("Open this URL in your browser:"));
-        text.push(Line::from(""));
-        text.push(Line::from(Span::styled(
-            url.clone(),
-            Style::default().fg(Color::Yellow),
-        )));
-        text.push(Line::from(""));
-        text.push(Line::from(
-            "After authorization, you'll receive a code. Use the CLI to complete the flow.",
-        ));
-    }
-
-    if let Some(msg) = &app.status_message {
-        text.push(Line::from(""));
-        text.push(Line::from(Span::styled(
-            msg.clone(),
-            Style::default().fg(Color::Green),
-        )));
-    }
-
-    let paragraph = Paragraph::new(text)
-        .block(Block::default().borders(Borders::ALL).title("Authentication"))
-        .wrap(Wrap { trim: true })
-        .alignment(Alignment::Center);
-
-    f.render_widget(paragraph, area);
-}
-
 fn draw_customers(f: &mut Frame, area: Rect, app: &App) {
     if app.loading {
         let loading_text = vec![
@@ -291,8 +433,8 @@ fn draw_customers(f: &mut Frame, area: Rect, app: &App) {
         return;
     }
 
-    let items: Vec<ListItem> = app
-        .customers
+    let visible = list_window(area, app.customers.len(), app.selected_customer, app.config.accessibility.row_height);
+    let items: Vec<ListItem> = app.customers[visible.clone()]
         .iter()
         .map(|c| {
             let name = c.name.as_deref().unwrap_or("N/A");
@@ -303,7 +445,7 @@ fn draw_customers(f: &mut Frame, area: Rect, app: &App) {
                 .map(|n| n.to_string())
                 .unwrap_or_else(|| "N/A".to_string());
 
-            ListItem::new(format!("[{}] {} - {}", customer_number, name, email))
+            list_row(format!("[{}] {} - {}", customer_number, name, email), app)
         })
         .collect();
 
@@ -329,7 +471,7 @@ fn draw_customers(f: &mut Frame, area: Rect, app: &App) {
     f.render_stateful_widget(
         list,
         area,
-        &mut ratatui::widgets::ListState::default().with_selected(Some(app.selected_customer)),
+        &mut ratatui::widgets::ListState::default().with_selected(Some(app.selected_customer - visible.start)),
     );
 }
 
@@ -352,7 +494,7 @@ fn draw_customer_form(f: &mut Frame, area: Rect, app: &App) {
             Line::from(vec![
                 Span::styled(format!("{}: ", field), Style::default().fg(Color::Yellow)),
                 Span::raw(&app.input),
-                Span::styled("_", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+                cursor(app),
             ])
         } else {
             Line::from(format!("{}: {}", field, value))
@@ -388,7 +530,7 @@ fn draw_customer_form(f: &mut Frame, area: Rect, app: &App) {
 }
 
 fn draw_customer_detail(f: &mut Frame, area: Rect, app: &App, id: &str) {
-    let customer = app.customers.iter().find(|c| c.id.as_deref() == Some(id));
+    let customer = app.customer(id);
 
     let text = if let Some(c) = customer {
         vec![
@@ -471,8 +613,9 @@ fn draw_invoices(f: &mut Frame, area: Rect, app: &App) {
         return;
     }
 
-    let items: Vec<ListItem> = app
-        .invoices
+    // Only the rows in view are built, so long lists stay cheap to draw
+    let visible = list_window(area, app.invoices.len(), app.selected_invoice, app.config.accessibility.row_height);
+    let items: Vec<ListItem> = app.invoices[visible.clone()]
         .iter()
         .map(|inv| {
             let number = inv
@@ -485,14 +628,28 @@ fn draw_invoices(f: &mut Frame, area: Rect, app: &App) {
                 .map(|t| format!("{:.2}", t))
                 .unwrap_or_else(|| "N/A".to_string());
             let customer_id = inv.customer_id.as_deref().unwrap_or("N/A");
+            let line = format!("[{}] Customer: {} - {} SEK", number, customer_id, total);
 
-            ListItem::new(format!("[{}] Customer: {} - {} SEK", number, customer_id, total))
+            match inv.hold() {
+                Some(hold) => list_row(format!("{} ⚑ {}", line, hold), app)
+                    .style(Style::default().fg(Color::Yellow)),
+                None => list_row(line, app),
+            }
         })
         .collect();
 
+    let window = &app.invoice_window;
+    let pages = if window.first_page == window.last_page {
+        format!("Page {}", window.first_page)
+    } else {
+        format!("Pages {}-{}", window.first_page, window.last_page)
+    };
     let title = format!(
-        "Invoices (Page {} | Sort: {} | o: change sort | ↑↓: select, ←→: page)",
-        app.current_page,
+        "Invoices ({}{} | {}/{} | Sort: {} | o: change sort | ↑↓: select, ←→: page)",
+        pages,
+        if window.has_more { "+" } else { "" },
+        app.selected_invoice + 1,
+        app.invoices.len(),
         get_invoice_sort_info(app)
     );
 
@@ -512,7 +669,7 @@ fn draw_invoices(f: &mut Frame, area: Rect, app: &App) {
     f.render_stateful_widget(
         list,
         area,
-        &mut ratatui::widgets::ListState::default().with_selected(Some(app.selected_invoice)),
+        &mut ratatui::widgets::ListState::default().with_selected(Some(app.selected_invoice - visible.start)),
     );
 }
 
@@ -535,7 +692,7 @@ fn draw_invoice_form(f: &mut Frame, area: Rect, app: &App) {
             Line::from(vec![
                 Span::styled(format!("{}: ", field), Style::default().fg(Color::Yellow)),
                 Span::raw(&app.input),
-                Span::styled("_", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+                cursor(app),
             ])
         } else {
             Line::from(format!("{}: {}", field, value))
@@ -571,7 +728,7 @@ fn draw_invoice_form(f: &mut Frame, area: Rect, app: &App) {
 }
 
 fn draw_invoice_detail(f: &mut Frame, area: Rect, app: &App, id: &str) {
-    let invoice = app.invoices.iter().find(|inv| inv.id.as_deref() == Some(id));
+    let invoice = app.invoice(id);
 
     let text = if let Some(inv) = invoice {
         vec![
@@ -614,6 +771,13 @@ fn draw_invoice_detail(f: &mut Frame, area: Rect, app: &App, id: &str) {
                 "Remarks: {}",
                 inv.remarks.as_deref().unwrap_or("N/A")
             )),
+            match inv.hold() {
+                Some(hold) => Line::from(Span::styled(
+                    format!("⚑ {} (excluded from reminders)", hold),
+                    Style::default().fg(Color::Yellow),
+                )),
+                None => Line::from(""),
+            },
         ]
     } else {
         vec![Line::from("Invoice not found")]
@@ -649,7 +813,7 @@ fn draw_invoice_edit_form(f: &mut Frame, area: Rect, app: &App, _id: &str) {
             Line::from(vec![
                 Span::styled(format!("{}: ", field), Style::default().fg(Color::Yellow)),
                 Span::raw(&app.input),
-                Span::styled("_", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+                cursor(app),
             ])
         } else {
             Line::from(format!("{}: {}", field, value))
@@ -709,6 +873,20 @@ fn draw_help(f: &mut Frame, area: Rect) {
         Line::from("  s or /         - Search"),
         Line::from("  h or ?         - Show this help"),
         Line::from(""),
+        Line::from("Tabs:"),
+        Line::from("  Enter          - Opening a customer, invoice or article opens a tab"),
+        Line::from("  Alt+1..9       - Switch to tab 1-9"),
+        Line::from("  w              - Close the tab shown"),
+        Line::from(""),
+        Line::from("Macros:"),
+        Line::from("  M              - Start/stop recording, then press a key to bind it"),
+        Line::from("  @ + key        - Play the macro bound to key"),
+        Line::from(""),
+        Line::from("Accessibility:"),
+        Line::from("  V              - Toggle symbols for colored states and a steady cursor"),
+        Line::from("  P              - Save the screen as plain text in the export directory"),
+        Line::from("  Mouse          - Wheel to move, click to select, click again to open"),
+        Line::from(""),
         Line::from("Screens:"),
         Line::from("  Home           - Main menu"),
         Line::from("  Dashboard      - Statistics and quick access"),
@@ -717,6 +895,13 @@ fn draw_help(f: &mut Frame, area: Rect) {
         Line::from("  Articles       - View and manage articles/products"),
         Line::from("  Search         - Search across all entities"),
         Line::from("  Export         - Export data to JSON files"),
+        Line::from("  Cash Flow      - Weekly inflow/outflow forecast"),
+        Line::from("  Approvals      - Approve or reject supplier invoices"),
+        Line::from("  Reconciliation - Match bank payments to open invoices"),
+        Line::from("  Dunning        - Preview and send payment reminders"),
+        Line::from("  Fiscal Years   - Fiscal years, periods and locks"),
+        Line::from("  New Invoice    - Guided invoice from customer to sending"),
+        Line::from("  Reports        - Budget vs actual for a month"),
         Line::from("  Help           - This screen"),
         Line::from(""),
         Line::from(Span::styled(
@@ -863,8 +1048,8 @@ fn draw_articles(f: &mut Frame, area: Rect, app: &App) {
         return;
     }
 
-    let items: Vec<ListItem> = app
-        .articles
+    let visible = list_window(area, app.articles.len(), app.selected_article, app.config.accessibility.row_height);
+    let items: Vec<ListItem> = app.articles[visible.clone()]
         .iter()
         .map(|article| {
             let name = article.name.as_deref().unwrap_or("N/A");
@@ -878,7 +1063,7 @@ fn draw_articles(f: &mut Frame, area: Rect, app: &App) {
                 .map(|n| n.to_string())
                 .unwrap_or_else(|| "N/A".to_string());
 
-            ListItem::new(format!("[{}] {} - {}", article_number, name, price))
+            list_row(format!("[{}] {} - {}", article_number, name, price), app)
         })
         .collect();
 
@@ -904,12 +1089,12 @@ fn draw_articles(f: &mut Frame, area: Rect, app: &App) {
     f.render_stateful_widget(
         list,
         area,
-        &mut ratatui::widgets::ListState::default().with_selected(Some(app.selected_article)),
+        &mut ratatui::widgets::ListState::default().with_selected(Some(app.selected_article - visible.start)),
     );
 }
 
 fn draw_article_detail(f: &mut Frame, area: Rect, app: &App, id: &str) {
-    let article = app.articles.iter().find(|a| a.id.as_deref() == Some(id));
+    let article = app.article(id);
 
     let text = if let Some(art) = article {
         vec![
@@ -974,7 +1159,7 @@ fn draw_article_edit_form(f: &mut Frame, area: Rect, app: &App, _id: &str) {
             Line::from(vec![
                 Span::styled(format!("{}: ", field), Style::default().fg(Color::Yellow)),
                 Span::raw(&app.input),
-                Span::styled("_", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+                cursor(app),
             ])
         } else {
             Line::from(format!("{}: {}", field, value))
@@ -1024,7 +1209,7 @@ fn draw_article_form(f: &mut Frame, area: Rect, app: &App) {
             Line::from(vec![
                 Span::styled(format!("{}: ", field), Style::default().fg(Color::Yellow)),
                 Span::raw(&app.input),
-                Span::styled("_", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+                cursor(app),
             ])
         } else {
             Line::from(format!("{}: {}", field, value))
@@ -1078,7 +1263,7 @@ fn draw_customer_edit_form(f: &mut Frame, area: Rect, app: &App, _id: &str) {
             Line::from(vec![
                 Span::styled(format!("{}: ", field), Style::default().fg(Color::Yellow)),
                 Span::raw(&app.input),
-                Span::styled("_", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+                cursor(app),
             ])
         } else {
             Line::from(format!("{}: {}", field, value))
@@ -1132,7 +1317,7 @@ fn draw_search(f: &mut Frame, area: Rect, app: &App) {
         text.push(Line::from(vec![
             Span::styled("Query: ", Style::default().fg(Color::Yellow)),
             Span::raw(&app.input),
-            Span::styled("_", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+            cursor(app),
         ]));
     } else {
         text.push(Line::from(format!("Query: {}", app.search_query)));
@@ -1273,3 +1458,55 @@ fn draw_confirmation_dialog(f: &mut Frame, app: &App) {
         f.render_widget(paragraph, popup_area);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_list_row_at() {
+        let area = Rect::new(0, 3, 80, 12);
+        // 10 inner lines hold 5 rows of 2 lines; the 4th of 20 is selected
+        assert_eq!(list_window(area, 20, 3, 2), 1..6);
+        assert_eq!(list_row_at(area, 3, 20, 3, 2), None);
+        assert_eq!(list_row_at(area, 4, 20, 3, 2), Some(1));
+        assert_eq!(list_row_at(area, 5, 20, 3, 2), Some(1));
+        assert_eq!(list_row_at(area, 13, 20, 3, 2), Some(5));
+        assert_eq!(list_row_at(area, 14, 20, 3, 2), None);
+        assert_eq!(list_row_at(area, 6, 1, 0, 2), None);
+    }
+
+    #[test]
+    fn test_plain_text() {
+        let mut terminal = Terminal::new(TestBackend::new(30, 6)).unwrap();
+        let frame = terminal
+            .draw(|f| {
+                let text = vec![Line::from("✓ Saved 🧾"), Line::from(""), Line::from(""), Line::from("Done")];
+                f.render_widget(Paragraph::new(text).block(Block::default().borders(Borders::ALL).title("Status")), f.area());
+            })
+            .unwrap();
+
+        assert_eq!(plain_text(frame.buffer), " Status\n ✓ Saved 🧾\n\n Done\n");
+    }
+
+    #[test]
+    fn test_tab_bar() {
+        use crate::tabs::TabRecord;
+
+        let mut app = App::new();
+        for (id, name) in [("c1", "Acme AB"), ("c2", "Beta AB")] {
+            let customer = spiris::Customer {
+                id: Some(id.into()),
+                name: Some(name.to_string()),
+                ..Default::default()
+            };
+            app.tabs.open(TabRecord::Customer(customer), Some(Screen::Customers));
+        }
+        app.screen = Screen::CustomerDetail("c2".into());
+        app.config.accessibility.enabled = true;
+
+        let text: String = tab_bar(&app).spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(text, " 1:Acme AB │ [2:Beta AB] ");
+    }
+}
@@ -1,5 +1,6 @@
 //! OAuth2 authentication for the Spiris Bokföring och Fakturering API.
 
+use crate::clock::{Clock, SystemClock};
 use crate::error::{Error, Result};
 use chrono::{DateTime, Duration, Utc};
 use oauth2::{
@@ -7,10 +8,55 @@ use oauth2::{
     EndpointNotSet, EndpointSet, PkceCodeChallenge, RedirectUrl, Scope, TokenResponse, TokenUrl,
 };
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 #[cfg(feature = "tracing")]
 use tracing::{debug, error, info};
 
+/// Scopes requested unless [`OAuth2Config::scopes`] is changed: API access,
+/// sales (invoicing) and a refresh token.
+pub const DEFAULT_SCOPES: [&str; 3] = ["ea:api", "ea:sales", "offline_access"];
+
+/// Visma identity server and API to sign in to and call.
+///
+/// Sandbox companies from the developer portal can only be reached through
+/// the sandbox identity server and API, so test against
+/// [`Environment::Sandbox`] before going live.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Environment {
+    #[default]
+    Production,
+    Sandbox,
+}
+
+impl Environment {
+    /// Authorization endpoint URL.
+    pub fn auth_url(self) -> &'static str {
+        match self {
+            Environment::Production => "https://identity.vismaonline.com/connect/authorize",
+            Environment::Sandbox => {
+                "https://identity-sandbox.test.vismaonline.com/connect/authorize"
+            }
+        }
+    }
+
+    /// Token endpoint URL.
+    pub fn token_url(self) -> &'static str {
+        match self {
+            Environment::Production => "https://identity.vismaonline.com/connect/token",
+            Environment::Sandbox => "https://identity-sandbox.test.vismaonline.com/connect/token",
+        }
+    }
+
+    /// Base URL of the API.
+    pub fn api_base_url(self) -> &'static str {
+        match self {
+            Environment::Production => crate::client::DEFAULT_BASE_URL,
+            Environment::Sandbox => crate::client::SANDBOX_BASE_URL,
+        }
+    }
+}
+
 /// OAuth2 configuration for Spiris Bokföring och Fakturering.
 ///
 /// You can obtain OAuth2 credentials by registering your application
@@ -32,16 +78,21 @@ pub struct OAuth2Config {
 
     /// Token endpoint URL.
     pub token_url: String,
+
+    /// Scopes requested when authorizing, [`DEFAULT_SCOPES`] by default.
+    pub scopes: Vec<String>,
 }
 
 impl Default for OAuth2Config {
     fn default() -> Self {
+        let environment = Environment::default();
         Self {
             client_id: String::new(),
             client_secret: String::new(),
             redirect_uri: String::new(),
-            auth_url: "https://identity.vismaonline.com/connect/authorize".to_string(),
-            token_url: "https://identity.vismaonline.com/connect/token".to_string(),
+            auth_url: environment.auth_url().to_string(),
+            token_url: environment.token_url().to_string(),
+            scopes: DEFAULT_SCOPES.iter().map(|s| s.to_string()).collect(),
         }
     }
 }
@@ -74,6 +125,43 @@ impl OAuth2Config {
             ..Default::default()
         }
     }
+
+    /// Use the identity server of `environment`.
+    ///
+    /// Pair this with [`ClientConfig::environment`](crate::ClientConfig::environment)
+    /// so tokens are used against the matching API.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spiris::auth::{Environment, OAuth2Config};
+    ///
+    /// let config = OAuth2Config::new(
+    ///     "your_client_id".to_string(),
+    ///     "your_client_secret".to_string(),
+    ///     "http://localhost:8080/callback".to_string(),
+    /// )
+    /// .environment(Environment::Sandbox);
+    /// assert!(config.token_url.contains("sandbox"));
+    /// ```
+    pub fn environment(mut self, environment: Environment) -> Self {
+        self.auth_url = environment.auth_url().to_string();
+        self.token_url = environment.token_url().to_string();
+        self
+    }
+
+    /// Request `scopes` instead of [`DEFAULT_SCOPES`].
+    ///
+    /// Leave out `offline_access` and no refresh token is issued, so the
+    /// user has to sign in again when the access token expires.
+    pub fn scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
 }
 
 /// Access token with expiration tracking.
@@ -94,6 +182,10 @@ pub struct AccessToken {
 
     /// Token type (usually "Bearer").
     pub token_type: String,
+
+    /// Scopes granted with the token, if the token response listed them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
 }
 
 impl AccessToken {
@@ -118,12 +210,81 @@ impl AccessToken {
     /// );
     /// ```
     pub fn new(token: String, expires_in: i64, refresh_token: Option<String>) -> Self {
-        let expires_at = Utc::now() + Duration::seconds(expires_in);
+        Self::issued_at(Utc::now(), token, expires_in, refresh_token)
+    }
+
+    /// Create a token issued at `now` rather than at the system time.
+    pub fn issued_at(
+        now: DateTime<Utc>,
+        token: String,
+        expires_in: i64,
+        refresh_token: Option<String>,
+    ) -> Self {
+        let expires_at = now + Duration::seconds(expires_in);
         Self {
             token,
             expires_at,
             refresh_token,
             token_type: "Bearer".to_string(),
+            scopes: None,
+        }
+    }
+
+    /// Record the scopes granted with the token.
+    pub fn with_scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes = Some(scopes.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Scopes granted with the token.
+    ///
+    /// Uses [`scopes`](Self::scopes) when set, otherwise the `scope` claim
+    /// of the token itself, so tokens loaded from storage as a bare string
+    /// can still be checked. `None` if neither is available.
+    pub fn granted_scopes(&self) -> Option<Vec<String>> {
+        self.scopes.clone().or_else(|| jwt_scopes(&self.token))
+    }
+
+    /// Required scopes the token was not granted.
+    ///
+    /// Empty if every scope is granted or the granted scopes are unknown.
+    pub fn missing_scopes(&self, required: &[String]) -> Vec<String> {
+        let Some(granted) = self.granted_scopes() else {
+            return Vec::new();
+        };
+        required
+            .iter()
+            .filter(|scope| !granted.contains(scope))
+            .cloned()
+            .collect()
+    }
+
+    /// Fail with [`Error::InsufficientScopes`] unless every required scope
+    /// is granted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spiris::{AccessToken, Error};
+    ///
+    /// let token = AccessToken::new("token".to_string(), 3600, None).with_scopes(["ea:api"]);
+    /// let required = vec!["ea:api".to_string(), "ea:sales".to_string()];
+    ///
+    /// match token.require_scopes(&required) {
+    ///     Err(Error::InsufficientScopes { missing }) => assert_eq!(missing, ["ea:sales"]),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn require_scopes(&self, required: &[String]) -> Result<()> {
+        let missing = self.missing_scopes(required);
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InsufficientScopes { missing })
         }
     }
 
@@ -141,8 +302,14 @@ impl AccessToken {
     /// }
     /// ```
     pub fn is_expired(&self) -> bool {
+        self.is_expired_at(Utc::now())
+    }
+
+    /// Check if the token is expired or will expire within 5 minutes of
+    /// `now`.
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
         let buffer = Duration::minutes(5);
-        Utc::now() + buffer >= self.expires_at
+        now + buffer >= self.expires_at
     }
 
     /// Get the authorization header value.
@@ -154,6 +321,37 @@ impl AccessToken {
     }
 }
 
+/// The `scope` claim of a JWT access token, as a space separated string or
+/// an array.
+fn jwt_scopes(token: &str) -> Option<Vec<String>> {
+    use base64::Engine;
+
+    let payload = token.split('.').nth(1)?;
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&bytes).ok()?;
+    match claims.get("scope")? {
+        serde_json::Value::String(scopes) => {
+            Some(scopes.split_whitespace().map(str::to_string).collect())
+        }
+        serde_json::Value::Array(scopes) => Some(
+            scopes
+                .iter()
+                .filter_map(|s| s.as_str().map(str::to_string))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Scopes listed in a token response.
+fn response_scopes(response: &impl TokenResponse) -> Option<Vec<String>> {
+    response
+        .scopes()
+        .map(|scopes| scopes.iter().map(|s| s.to_string()).collect())
+}
+
 /// Type alias for the configured OAuth2 client.
 type ConfiguredClient = oauth2::Client<
     oauth2::StandardErrorResponse<oauth2::basic::BasicErrorResponseType>,
@@ -173,9 +371,10 @@ type ConfiguredClient = oauth2::Client<
 
 /// OAuth2 authentication handler.
 pub struct OAuth2Handler {
-    #[allow(dead_code)]
     config: OAuth2Config,
     client: ConfiguredClient,
+    clock: Arc<dyn Clock>,
+    http_client: reqwest::Client,
 }
 
 impl OAuth2Handler {
@@ -196,7 +395,25 @@ impl OAuth2Handler {
                     .map_err(|e| Error::InvalidConfig(format!("Invalid redirect URI: {}", e)))?,
             );
 
-        Ok(Self { config, client })
+        Ok(Self {
+            config,
+            client,
+            clock: Arc::new(SystemClock),
+            http_client: reqwest::Client::new(),
+        })
+    }
+
+    /// Date issued tokens from `clock` instead of the system time.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Call the token endpoint with `http_client`, e.g. to go through a
+    /// proxy.
+    pub fn with_http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = http_client;
+        self
     }
 
     /// Generate an authorization URL for the OAuth2 flow.
@@ -212,9 +429,7 @@ impl OAuth2Handler {
         let (auth_url, csrf_token) = self
             .client
             .authorize_url(CsrfToken::new_random)
-            .add_scope(Scope::new("ea:api".to_string()))
-            .add_scope(Scope::new("ea:sales".to_string()))
-            .add_scope(Scope::new("offline_access".to_string()))
+            .add_scopes(self.config.scopes.iter().cloned().map(Scope::new))
             .set_pkce_challenge(pkce_challenge)
             .url();
 
@@ -248,12 +463,11 @@ impl OAuth2Handler {
         #[cfg(feature = "tracing")]
         info!("Exchanging authorization code for access token");
 
-        let http_client = reqwest::Client::new();
         let token_result = self
             .client
             .exchange_code(AuthorizationCode::new(code))
             .set_pkce_verifier(PkceCodeVerifier::new(pkce_verifier))
-            .request_async(&http_client)
+            .request_async(&self.http_client)
             .await
             .map_err(|e| {
                 #[cfg(feature = "tracing")]
@@ -269,11 +483,14 @@ impl OAuth2Handler {
         #[cfg(feature = "tracing")]
         info!(expires_in_secs = expires_in, "Token exchange successful");
 
-        Ok(AccessToken::new(
+        let mut token = AccessToken::issued_at(
+            self.clock.now(),
             token_result.access_token().secret().to_string(),
             expires_in,
             token_result.refresh_token().map(|t| t.secret().to_string()),
-        ))
+        );
+        token.scopes = response_scopes(&token_result);
+        Ok(token)
     }
 
     /// Refresh an access token using a refresh token.
@@ -300,11 +517,10 @@ impl OAuth2Handler {
         #[cfg(feature = "tracing")]
         debug!("Refreshing access token using refresh token");
 
-        let http_client = reqwest::Client::new();
         let token_result = self
             .client
             .exchange_refresh_token(&RefreshToken::new(refresh_token))
-            .request_async(&http_client)
+            .request_async(&self.http_client)
             .await
             .map_err(|e| {
                 #[cfg(feature = "tracing")]
@@ -320,11 +536,14 @@ impl OAuth2Handler {
         #[cfg(feature = "tracing")]
         info!(expires_in_secs = expires_in, "Token refresh successful");
 
-        Ok(AccessToken::new(
+        let mut token = AccessToken::issued_at(
+            self.clock.now(),
             token_result.access_token().secret().to_string(),
             expires_in,
             token_result.refresh_token().map(|t| t.secret().to_string()),
-        ))
+        );
+        token.scopes = response_scopes(&token_result);
+        Ok(token)
     }
 }
 
@@ -343,6 +562,24 @@ mod tests {
         assert!(expired_token.is_expired());
     }
 
+    #[test]
+    fn test_missing_scopes() {
+        use base64::Engine;
+
+        let required = vec!["ea:api".to_string(), "ea:sales".to_string()];
+
+        let token = AccessToken::new("opaque".to_string(), 3600, None);
+        assert!(token.missing_scopes(&required).is_empty());
+
+        let token = token.with_scopes(["ea:api", "offline_access"]);
+        assert_eq!(token.missing_scopes(&required), ["ea:sales"]);
+
+        let claims = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(r#"{"sub":"user","scope":["ea:api","ea:sales"]}"#);
+        let jwt = AccessToken::new(format!("header.{}.signature", claims), 3600, None);
+        assert!(jwt.require_scopes(&required).is_ok());
+    }
+
     #[test]
     fn test_authorization_header() {
         let token = AccessToken::new("test_token_123".to_string(), 3600, None);
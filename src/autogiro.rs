@@ -0,0 +1,373 @@
+//! Autogiro direct debit.
+//!
+//! Tracks which customers have approved an Autogiro mandate, writes the
+//! Bankgirot payment file (betalningsunderlag) charging their due invoices,
+//! and marks the charged invoices as pending direct debit so
+//! [`dunning`](crate::dunning) leaves them alone.
+//!
+//! The API has no place for mandates, so the [`MandateRegistry`] is kept by
+//! the application, for example as a JSON file with
+//! [`MandateRegistry::load`] and [`MandateRegistry::save`]. Mandates must
+//! already be approved by Bankgirot; registering them is not covered here.
+//!
+//! Requires the `bank-files` feature.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::autogiro::{self, ChargeFile, MandateRegistry};
+//! use spiris::{AccessToken, Client};
+//! use chrono::NaiveDate;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+//! let registry = MandateRegistry::load("mandates.json")?;
+//! let charge_date = NaiveDate::from_ymd_opt(2025, 4, 28).unwrap();
+//!
+//! let charges = autogiro::due_charges(&client, &registry, charge_date).await?;
+//! let file = ChargeFile::new("123456", "5555-5555").write(charge_date, &charges)?;
+//! std::fs::write("autogiro.txt", file)?;
+//!
+//! for (invoice_id, result) in autogiro::mark_pending(&client, &charges).await {
+//!     if let Err(error) = result {
+//!         println!("Could not mark {}: {}", invoice_id, error);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::disputes::{self, InvoiceHold};
+use crate::endpoints::collect_pages;
+use crate::error::{Error, Result};
+use crate::types::{CustomerId, Invoice, InvoiceId, Money};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A customer's approval to be charged by Autogiro.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mandate {
+    /// Customer ID.
+    pub customer_id: CustomerId,
+    /// Payer number registered with Bankgirot, usually the customer number.
+    pub payer_number: String,
+    /// Date Bankgirot approved the mandate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approved_on: Option<NaiveDate>,
+    /// Whether the mandate can be charged.
+    #[serde(default = "active")]
+    pub active: bool,
+}
+
+fn active() -> bool {
+    true
+}
+
+impl Mandate {
+    /// An active mandate.
+    pub fn new(customer_id: impl Into<CustomerId>, payer_number: impl Into<String>) -> Self {
+        Self {
+            customer_id: customer_id.into(),
+            payer_number: payer_number.into(),
+            approved_on: None,
+            active: true,
+        }
+    }
+
+    /// Set the approval date.
+    pub fn approved_on(mut self, date: NaiveDate) -> Self {
+        self.approved_on = Some(date);
+        self
+    }
+}
+
+/// Autogiro mandates, one per customer.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MandateRegistry {
+    /// Mandates by customer.
+    pub mandates: Vec<Mandate>,
+}
+
+impl MandateRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read a registry saved with [`save`](Self::save). A missing file gives
+    /// an empty registry.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Write the registry as JSON.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let content = serde_json::to_string_pretty(self)?;
+        Ok(std::fs::write(path, content)?)
+    }
+
+    /// Add a mandate, replacing the customer's previous one.
+    pub fn register(&mut self, mandate: Mandate) {
+        self.mandates
+            .retain(|m| m.customer_id != mandate.customer_id);
+        self.mandates.push(mandate);
+    }
+
+    /// Deactivate a customer's mandate. Returns whether there was one.
+    pub fn revoke(&mut self, customer_id: &CustomerId) -> bool {
+        match self
+            .mandates
+            .iter_mut()
+            .find(|m| &m.customer_id == customer_id)
+        {
+            Some(mandate) => {
+                mandate.active = false;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The customer's mandate, if it is active.
+    pub fn active_for(&self, customer_id: &CustomerId) -> Option<&Mandate> {
+        self.mandates
+            .iter()
+            .find(|m| &m.customer_id == customer_id && m.active)
+    }
+
+    /// Charges for the open invoices of customers with an active mandate
+    /// that are due on or before `charge_date`.
+    ///
+    /// Invoices already pending direct debit, and other held invoices, are
+    /// left out.
+    pub fn charges(&self, invoices: &[Invoice], charge_date: NaiveDate) -> Vec<Charge> {
+        invoices
+            .iter()
+            .filter(|invoice| !invoice.is_held())
+            .filter_map(|invoice| {
+                let mandate = self.active_for(invoice.customer_id.as_ref()?)?;
+                let amount = invoice.remaining_amount?;
+                if amount <= Money::default() || invoice.due_date? > charge_date {
+                    return None;
+                }
+                Some(Charge {
+                    invoice_id: invoice.id.clone()?,
+                    payer_number: mandate.payer_number.clone(),
+                    amount,
+                    reference: invoice
+                        .ocr_number
+                        .clone()
+                        .or_else(|| invoice.invoice_number.clone())
+                        .unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// One invoice to charge.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Charge {
+    /// Invoice ID.
+    pub invoice_id: InvoiceId,
+    /// Payer number of the mandate.
+    pub payer_number: String,
+    /// Amount to charge.
+    #[serde(with = "crate::types::money_serde")]
+    pub amount: Money,
+    /// Reference shown to the payer: OCR or invoice number.
+    pub reference: String,
+}
+
+/// Sender details of an Autogiro payment file.
+#[derive(Debug, Clone)]
+pub struct ChargeFile {
+    /// Bankgirot customer number (6 digits).
+    pub customer_number: String,
+    /// Payee bankgiro number.
+    pub bankgiro: String,
+}
+
+impl ChargeFile {
+    /// Create with the payee's Bankgirot customer number and bankgiro number.
+    pub fn new(customer_number: impl Into<String>, bankgiro: impl Into<String>) -> Self {
+        Self {
+            customer_number: customer_number.into(),
+            bankgiro: bankgiro.into(),
+        }
+    }
+
+    /// Write the payment file charging `charges` on `charge_date`.
+    ///
+    /// The file has an opening record (01) followed by one payment record
+    /// (82) per charge, in 80 character lines.
+    pub fn write(&self, charge_date: NaiveDate, charges: &[Charge]) -> Result<String> {
+        let customer_number = digits(&self.customer_number, 6, "Bankgirot customer number")?;
+        let bankgiro = digits(&self.bankgiro, 10, "bankgiro number")?;
+        let date = charge_date.format("%Y%m%d").to_string();
+
+        let mut lines = vec![format!(
+            "01{}AUTOGIRO{:44}{}{}",
+            date, "", customer_number, bankgiro
+        )];
+        for charge in charges {
+            let payer = digits(&charge.payer_number, 16, "payer number")?;
+            let ore = to_ore(charge.amount).ok_or_else(|| {
+                Error::InvalidRequest(format!(
+                    "invalid Autogiro amount {} for invoice {}",
+                    charge.amount, charge.invoice_id
+                ))
+            })?;
+            let reference: String = charge.reference.chars().take(16).collect();
+            lines.push(format!(
+                "82{}0{:4}{}{:012}{}{:<16}",
+                date, "", payer, ore, bankgiro, reference
+            ));
+        }
+
+        Ok(lines
+            .into_iter()
+            .map(|line| format!("{:<80}\r\n", line))
+            .collect())
+    }
+}
+
+/// `value` without separators, zero-padded to `width` digits.
+fn digits(value: &str, width: usize, what: &str) -> Result<String> {
+    let digits: String = value.chars().filter(|c| !matches!(c, '-' | ' ')).collect();
+    if digits.is_empty() || digits.len() > width || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::InvalidRequest(format!(
+            "invalid {}: {}",
+            what, value
+        )));
+    }
+    Ok(format!("{:0>width$}", digits))
+}
+
+fn to_ore(amount: Money) -> Option<u64> {
+    let text = format!("{:.2}", amount);
+    if text.starts_with('-') {
+        return None;
+    }
+    text.replace('.', "").parse().ok().filter(|ore| *ore > 0)
+}
+
+/// Load open invoices and select the charges due on `charge_date`.
+pub async fn due_charges(
+    client: &Client,
+    registry: &MandateRegistry,
+    charge_date: NaiveDate,
+) -> Result<Vec<Charge>> {
+    let invoices = collect_pages(|p| async move { client.invoices().list(Some(p)).await }).await?;
+    Ok(registry.charges(&invoices, charge_date))
+}
+
+/// Mark charged invoices as pending direct debit.
+///
+/// A failing invoice does not stop the others; every charge gets a result.
+pub async fn mark_pending(
+    client: &Client,
+    charges: &[Charge],
+) -> Vec<(InvoiceId, Result<Invoice>)> {
+    let mut results = Vec::with_capacity(charges.len());
+    for charge in charges {
+        let hold = InvoiceHold::direct_debit(format!("payer {}", charge.payer_number));
+        let result = disputes::place_hold(client, &charge.invoice_id, &hold).await;
+        results.push((charge.invoice_id.clone(), result));
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invoice(id: &str, customer: &str, due: u32, remaining: Money) -> Invoice {
+        Invoice {
+            id: Some(id.into()),
+            customer_id: Some(customer.into()),
+            invoice_number: Some(format!("F{}", id)),
+            due_date: Some(NaiveDate::from_ymd_opt(2025, 4, due).unwrap()),
+            remaining_amount: Some(remaining),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_charges_for_active_mandates() {
+        let mut registry = MandateRegistry::new();
+        registry.register(Mandate::new("c1", "1001"));
+        registry.register(Mandate::new("c2", "1002"));
+        registry.revoke(&"c2".into());
+
+        let mut pending = invoice("4", "c1", 20, crate::money!(100));
+        pending.remarks = Some(InvoiceHold::direct_debit("").marker());
+        let invoices = vec![
+            invoice("1", "c1", 25, crate::money!(450.5)),
+            invoice("2", "c1", 30, crate::money!(100)),
+            invoice("3", "c2", 25, crate::money!(100)),
+            pending,
+        ];
+
+        let charges = registry.charges(&invoices, NaiveDate::from_ymd_opt(2025, 4, 28).unwrap());
+
+        assert_eq!(charges.len(), 1);
+        assert_eq!(charges[0].invoice_id, "1");
+        assert_eq!(charges[0].reference, "F1");
+    }
+
+    #[test]
+    fn test_write_charge_file() {
+        let charges = vec![Charge {
+            invoice_id: "inv-1".into(),
+            payer_number: "1001".to_string(),
+            amount: crate::money!(450.5),
+            reference: "12345678".to_string(),
+        }];
+
+        let file = ChargeFile::new("123456", "5555-5555")
+            .write(NaiveDate::from_ymd_opt(2025, 4, 28).unwrap(), &charges)
+            .unwrap();
+        let lines: Vec<&str> = file.split("\r\n").collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[..2].iter().all(|line| line.len() == 80));
+        assert_eq!(
+            lines[0].trim_end(),
+            format!("0120250428AUTOGIRO{}1234560055555555", " ".repeat(44))
+        );
+        assert_eq!(
+            lines[1].trim_end(),
+            "82202504280    0000000000001001000000045050005555555512345678"
+        );
+        assert!(ChargeFile::new("12345", "5555-5555x")
+            .write(NaiveDate::MIN, &[])
+            .is_err());
+    }
+    #[test]
+    fn test_registry_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("spiris-autogiro-{}", std::process::id()));
+        let path = dir.join("mandates.json");
+        assert!(MandateRegistry::load(&path).unwrap().mandates.is_empty());
+        assert!(matches!(
+            MandateRegistry::new().save(&path),
+            Err(Error::Io(e)) if e.kind() == std::io::ErrorKind::NotFound
+        ));
+
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut registry = MandateRegistry::new();
+        registry.register(Mandate::new("cust-1", "1001"));
+        registry.save(&path).unwrap();
+        assert_eq!(MandateRegistry::load(&path).unwrap().mandates.len(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
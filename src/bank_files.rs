@@ -0,0 +1,540 @@
+//! Parsing of bank payment files.
+//!
+//! Supports ISO 20022 camt.054 debit/credit notifications and Bankgirot
+//! BgMax files. Both are turned into a flat list of [`BankTransaction`]s
+//! that can be matched against open invoices with
+//! [`reconciliation`](crate::reconciliation).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::bank_files;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let content = std::fs::read_to_string("BGMAX20250314.txt")?;
+//! for transaction in bank_files::parse(&content)? {
+//!     println!(
+//!         "{} {} {:?}",
+//!         transaction.booking_date, transaction.amount, transaction.reference
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::types::Money;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// A single booked payment from a bank file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BankTransaction {
+    /// Date the payment was booked on the account.
+    pub booking_date: NaiveDate,
+    /// Amount; positive for incoming and negative for outgoing payments.
+    #[serde(with = "crate::types::money_serde")]
+    pub amount: Money,
+    /// Currency code (ISO 4217).
+    pub currency: String,
+    /// Structured reference, e.g. an OCR number.
+    pub reference: Option<String>,
+    /// Free text message from the payer.
+    pub message: Option<String>,
+    /// Name of the payer or payee.
+    pub counterparty: Option<String>,
+    /// The bank's own reference for the transaction.
+    pub bank_reference: Option<String>,
+}
+
+impl BankTransaction {
+    /// Whether money came in to the account.
+    pub fn is_incoming(&self) -> bool {
+        self.amount > Money::default()
+    }
+}
+
+/// Supported bank file formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BankFileFormat {
+    /// ISO 20022 camt.054 notification (XML).
+    Camt054,
+    /// Bankgirot BgMax (fixed width text).
+    BgMax,
+}
+
+impl BankFileFormat {
+    /// Detect the format from the file content.
+    pub fn detect(content: &str) -> Option<Self> {
+        let start = content.trim_start_matches('\u{feff}').trim_start();
+        if start.starts_with("01BGMAX") {
+            Some(Self::BgMax)
+        } else if start.starts_with('<') && content.contains("BkToCstmrDbtCdtNtfctn") {
+            Some(Self::Camt054)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parse a bank file, detecting its format from the content.
+pub fn parse(content: &str) -> Result<Vec<BankTransaction>> {
+    match BankFileFormat::detect(content) {
+        Some(BankFileFormat::Camt054) => parse_camt054(content),
+        Some(BankFileFormat::BgMax) => parse_bgmax(content),
+        None => Err(Error::InvalidRequest(
+            "Unrecognized bank file format (expected camt.054 or BgMax)".into(),
+        )),
+    }
+}
+
+// =============================================================================
+// camt.054
+// =============================================================================
+
+#[derive(Deserialize)]
+struct Document {
+    #[serde(rename = "BkToCstmrDbtCdtNtfctn")]
+    notification: CamtNotificationMessage,
+}
+
+#[derive(Deserialize)]
+struct CamtNotificationMessage {
+    #[serde(rename = "Ntfctn", default)]
+    notifications: Vec<CamtNotification>,
+}
+
+#[derive(Deserialize)]
+struct CamtNotification {
+    #[serde(rename = "Ntry", default)]
+    entries: Vec<CamtEntry>,
+}
+
+#[derive(Deserialize)]
+struct CamtEntry {
+    #[serde(rename = "Amt")]
+    amount: CamtAmount,
+    #[serde(rename = "CdtDbtInd")]
+    credit_debit: String,
+    #[serde(rename = "BookgDt")]
+    booking_date: Option<CamtDate>,
+    #[serde(rename = "ValDt")]
+    value_date: Option<CamtDate>,
+    #[serde(rename = "AcctSvcrRef")]
+    bank_reference: Option<String>,
+    #[serde(rename = "NtryDtls", default)]
+    details: Vec<CamtEntryDetails>,
+}
+
+#[derive(Deserialize)]
+struct CamtEntryDetails {
+    #[serde(rename = "TxDtls", default)]
+    transactions: Vec<CamtTransaction>,
+}
+
+#[derive(Deserialize)]
+struct CamtTransaction {
+    #[serde(rename = "Refs")]
+    refs: Option<CamtRefs>,
+    #[serde(rename = "Amt")]
+    amount: Option<CamtAmount>,
+    #[serde(rename = "RltdPties")]
+    parties: Option<CamtParties>,
+    #[serde(rename = "RmtInf")]
+    remittance: Option<CamtRemittance>,
+}
+
+#[derive(Deserialize)]
+struct CamtAmount {
+    #[serde(rename = "@Ccy")]
+    currency: String,
+    #[serde(rename = "$text")]
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct CamtDate {
+    #[serde(rename = "Dt")]
+    date: Option<String>,
+    #[serde(rename = "DtTm")]
+    date_time: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CamtRefs {
+    #[serde(rename = "AcctSvcrRef")]
+    bank_reference: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CamtParties {
+    #[serde(rename = "Dbtr")]
+    debtor: Option<CamtParty>,
+    #[serde(rename = "Cdtr")]
+    creditor: Option<CamtParty>,
+}
+
+#[derive(Deserialize)]
+struct CamtParty {
+    #[serde(rename = "Nm")]
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CamtRemittance {
+    #[serde(rename = "Ustrd", default)]
+    unstructured: Vec<String>,
+    #[serde(rename = "Strd", default)]
+    structured: Vec<CamtStructuredRemittance>,
+}
+
+#[derive(Deserialize)]
+struct CamtStructuredRemittance {
+    #[serde(rename = "CdtrRefInf")]
+    creditor_reference: Option<CamtCreditorReference>,
+}
+
+#[derive(Deserialize)]
+struct CamtCreditorReference {
+    #[serde(rename = "Ref")]
+    reference: Option<String>,
+}
+
+impl CamtDate {
+    fn parse(&self) -> Option<NaiveDate> {
+        let value = self.date.as_deref().or(self.date_time.as_deref())?;
+        NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()
+    }
+}
+
+/// Parse a camt.054 debit/credit notification.
+///
+/// Every transaction detail of an entry becomes one transaction; entries
+/// without details are returned as a single transaction. Debit entries
+/// get a negative amount.
+pub fn parse_camt054(xml: &str) -> Result<Vec<BankTransaction>> {
+    let document: Document = quick_xml::de::from_str(xml)
+        .map_err(|e| Error::InvalidRequest(format!("Failed to parse camt.054 file: {}", e)))?;
+
+    let mut transactions = Vec::new();
+    for entry in document
+        .notification
+        .notifications
+        .iter()
+        .flat_map(|n| &n.entries)
+    {
+        let is_debit = match entry.credit_debit.as_str() {
+            "CRDT" => false,
+            "DBIT" => true,
+            other => {
+                return Err(Error::InvalidRequest(format!(
+                    "Invalid credit/debit indicator in camt.054 file: {}",
+                    other
+                )))
+            }
+        };
+        let booking_date = entry
+            .booking_date
+            .as_ref()
+            .or(entry.value_date.as_ref())
+            .and_then(CamtDate::parse)
+            .ok_or_else(|| {
+                Error::InvalidRequest("camt.054 entry is missing a booking date".into())
+            })?;
+
+        let details: Vec<&CamtTransaction> =
+            entry.details.iter().flat_map(|d| &d.transactions).collect();
+        if details.is_empty() {
+            transactions.push(BankTransaction {
+                booking_date,
+                amount: signed_amount(&entry.amount.value, is_debit)?,
+                currency: entry.amount.currency.clone(),
+                reference: None,
+                message: None,
+                counterparty: None,
+                bank_reference: entry.bank_reference.clone(),
+            });
+            continue;
+        }
+
+        for tx in details {
+            let amount = tx.amount.as_ref().unwrap_or(&entry.amount);
+            let remittance = tx.remittance.as_ref();
+            let counterparty = tx.parties.as_ref().and_then(|p| {
+                let party = if is_debit { &p.creditor } else { &p.debtor };
+                party.as_ref()?.name.clone()
+            });
+
+            transactions.push(BankTransaction {
+                booking_date,
+                amount: signed_amount(&amount.value, is_debit)?,
+                currency: amount.currency.clone(),
+                reference: remittance.and_then(|r| {
+                    r.structured
+                        .iter()
+                        .find_map(|s| s.creditor_reference.as_ref()?.reference.clone())
+                }),
+                message: remittance
+                    .map(|r| r.unstructured.join(" "))
+                    .filter(|m| !m.is_empty()),
+                counterparty,
+                bank_reference: tx
+                    .refs
+                    .as_ref()
+                    .and_then(|r| r.bank_reference.clone())
+                    .or_else(|| entry.bank_reference.clone()),
+            });
+        }
+    }
+
+    Ok(transactions)
+}
+
+fn signed_amount(value: &str, negative: bool) -> Result<Money> {
+    let amount: Money = value
+        .trim()
+        .parse()
+        .map_err(|_| Error::InvalidRequest(format!("Invalid amount in bank file: {}", value)))?;
+    Ok(if negative { -amount } else { amount })
+}
+
+// =============================================================================
+// BgMax
+// =============================================================================
+
+/// Parse a Bankgirot BgMax file.
+///
+/// Payment records (20) are returned as incoming transactions and deduction
+/// records (21) as outgoing ones. The booking date and currency come from
+/// the deposit record (15) closing each section. Name (26) and information
+/// (25) records are attached to the preceding payment.
+pub fn parse_bgmax(text: &str) -> Result<Vec<BankTransaction>> {
+    let mut transactions = Vec::new();
+    // Payments of the current section, dated by its deposit record
+    let mut section: Vec<BankTransaction> = Vec::new();
+    let mut section_currency = String::from("SEK");
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim_start_matches('\u{feff}').trim_end_matches('\r');
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_number = index + 1;
+
+        match field(line, 1, 2) {
+            "05" => {
+                let currency = field(line, 23, 25).trim();
+                if !currency.is_empty() {
+                    section_currency = currency.to_string();
+                }
+            }
+            code @ ("20" | "21") => {
+                let reference = field(line, 13, 37).trim();
+                let ore = field(line, 38, 55);
+                let amount = ore_to_money(ore).ok_or_else(|| {
+                    Error::InvalidRequest(format!(
+                        "Invalid amount on line {} of BgMax file: {}",
+                        line_number, ore
+                    ))
+                })?;
+                let bgc_number = field(line, 58, 69).trim();
+
+                section.push(BankTransaction {
+                    // Set from the deposit record
+                    booking_date: NaiveDate::MIN,
+                    amount: if code == "21" { -amount } else { amount },
+                    currency: section_currency.clone(),
+                    reference: (!reference.is_empty()).then(|| reference.to_string()),
+                    message: None,
+                    counterparty: None,
+                    bank_reference: (!bgc_number.is_empty()).then(|| bgc_number.to_string()),
+                });
+            }
+            "25" => {
+                if let Some(last) = section.last_mut() {
+                    let info = field(line, 3, 52).trim();
+                    if !info.is_empty() {
+                        last.message = Some(match last.message.take() {
+                            Some(message) => format!("{} {}", message, info),
+                            None => info.to_string(),
+                        });
+                    }
+                }
+            }
+            "26" => {
+                if let Some(last) = section.last_mut() {
+                    let name = field(line, 3, 37).trim();
+                    if !name.is_empty() {
+                        last.counterparty = Some(name.to_string());
+                    }
+                }
+            }
+            "15" => {
+                let date = field(line, 38, 45);
+                let booking_date = NaiveDate::parse_from_str(date, "%Y%m%d").map_err(|_| {
+                    Error::InvalidRequest(format!(
+                        "Invalid payment date on line {} of BgMax file: {}",
+                        line_number, date
+                    ))
+                })?;
+                let currency = field(line, 69, 71).trim();
+                for mut transaction in section.drain(..) {
+                    transaction.booking_date = booking_date;
+                    if !currency.is_empty() {
+                        transaction.currency = currency.to_string();
+                    }
+                    transactions.push(transaction);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !section.is_empty() {
+        return Err(Error::InvalidRequest(
+            "BgMax file ends with payments that have no deposit record".into(),
+        ));
+    }
+    Ok(transactions)
+}
+
+/// Characters `from..=to` (1-based, as in the BgMax specification).
+fn field(line: &str, from: usize, to: usize) -> &str {
+    line.get(from - 1..to.min(line.len())).unwrap_or("")
+}
+
+fn ore_to_money(digits: &str) -> Option<Money> {
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let ore: u64 = digits.parse().ok()?;
+    format!("{}.{:02}", ore / 100, ore % 100).parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn money(value: &str) -> Money {
+        value.parse().unwrap()
+    }
+
+    const CAMT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.054.001.02">
+  <BkToCstmrDbtCdtNtfctn>
+    <GrpHdr><MsgId>MSG-1</MsgId></GrpHdr>
+    <Ntfctn>
+      <Id>N-1</Id>
+      <Ntry>
+        <Amt Ccy="SEK">1750.00</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd>
+        <BookgDt><Dt>2025-03-14</Dt></BookgDt>
+        <AcctSvcrRef>ENTRY-1</AcctSvcrRef>
+        <NtryDtls>
+          <TxDtls>
+            <Refs><AcctSvcrRef>TX-1</AcctSvcrRef></Refs>
+            <Amt Ccy="SEK">1250.00</Amt>
+            <RltdPties><Dbtr><Nm>Kundbolaget AB</Nm></Dbtr></RltdPties>
+            <RmtInf><Strd><CdtrRefInf><Ref>10014</Ref></CdtrRefInf></Strd></RmtInf>
+          </TxDtls>
+          <TxDtls>
+            <Amt Ccy="SEK">500.00</Amt>
+            <RmtInf><Ustrd>Faktura 1002</Ustrd></RmtInf>
+          </TxDtls>
+        </NtryDtls>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="SEK">320.50</Amt>
+        <CdtDbtInd>DBIT</CdtDbtInd>
+        <BookgDt><DtTm>2025-03-15T10:00:00</DtTm></BookgDt>
+      </Ntry>
+    </Ntfctn>
+  </BkToCstmrDbtCdtNtfctn>
+</Document>"#;
+
+    fn bgmax() -> String {
+        [
+            "01BGMAX               0120250314173035010331P",
+            "050009912346          SEK",
+            "20000000000010014                    000000000000012500230000000000010",
+            "26Kundbolaget AB",
+            "210000000000                         000000000000003000130000000000020",
+            "25Ref 2041",
+            "15000000000000000000000000000000012342025031400001000000000000009500SEK00000002",
+            "70000000010000000100000000000000001",
+        ]
+        .join("\r\n")
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(BankFileFormat::detect(CAMT), Some(BankFileFormat::Camt054));
+        assert_eq!(
+            BankFileFormat::detect(&bgmax()),
+            Some(BankFileFormat::BgMax)
+        );
+        assert_eq!(BankFileFormat::detect("Date;Amount"), None);
+        assert!(parse("Date;Amount").is_err());
+    }
+
+    #[test]
+    fn test_parse_camt054() {
+        let transactions = parse(CAMT).unwrap();
+        assert_eq!(transactions.len(), 3);
+
+        let first = &transactions[0];
+        assert_eq!(first.amount, money("1250"));
+        assert_eq!(first.reference.as_deref(), Some("10014"));
+        assert_eq!(first.counterparty.as_deref(), Some("Kundbolaget AB"));
+        assert_eq!(first.bank_reference.as_deref(), Some("TX-1"));
+        assert_eq!(
+            first.booking_date,
+            NaiveDate::from_ymd_opt(2025, 3, 14).unwrap()
+        );
+
+        assert_eq!(transactions[1].message.as_deref(), Some("Faktura 1002"));
+        assert_eq!(transactions[1].bank_reference.as_deref(), Some("ENTRY-1"));
+
+        let debit = &transactions[2];
+        assert_eq!(debit.amount, money("-320.50"));
+        assert!(!debit.is_incoming());
+        assert_eq!(
+            debit.booking_date,
+            NaiveDate::from_ymd_opt(2025, 3, 15).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_bgmax() {
+        let transactions = parse(&bgmax()).unwrap();
+        assert_eq!(transactions.len(), 2);
+
+        let payment = &transactions[0];
+        assert_eq!(payment.amount, money("125"));
+        assert_eq!(payment.reference.as_deref(), Some("10014"));
+        assert_eq!(payment.counterparty.as_deref(), Some("Kundbolaget AB"));
+        assert_eq!(payment.currency, "SEK");
+        assert_eq!(
+            payment.booking_date,
+            NaiveDate::from_ymd_opt(2025, 3, 14).unwrap()
+        );
+
+        let deduction = &transactions[1];
+        assert_eq!(deduction.amount, money("-30"));
+        assert_eq!(deduction.message.as_deref(), Some("Ref 2041"));
+    }
+
+    #[test]
+    fn test_bgmax_invalid_amount() {
+        let file = bgmax().replace("000000000000012500", "0000000000000125X0");
+        let err = parse_bgmax(&file).unwrap_err();
+        assert!(err.to_string().contains("line 3"));
+    }
+
+    #[test]
+    fn test_bgmax_missing_deposit_record() {
+        let file = bgmax();
+        let truncated: Vec<&str> = file.lines().take(4).collect();
+        assert!(parse_bgmax(&truncated.join("\n")).is_err());
+    }
+}
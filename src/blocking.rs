@@ -0,0 +1,707 @@
+//! Blocking client for programs without an async runtime.
+//!
+//! [`Client`] has the same endpoints and methods as [`crate::Client`], but
+//! each call blocks the calling thread until the response arrives. It runs
+//! the async client on a private tokio runtime, like `reqwest::blocking`.
+//!
+//! Don't use it from async code: blocking inside a tokio runtime panics.
+//! Helpers outside the endpoints, such as [`crate::dunning`] or
+//! [`crate::reports`], can be run with [`Client::block_on`].
+//!
+//! Requires the `blocking` feature.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::blocking::Client;
+//! use spiris::{AccessToken, CustomerId};
+//!
+//! let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+//! let customer = client.customers().get(&CustomerId::from("customer-id"))?;
+//! println!("{:?}", customer.name);
+//! # Ok::<(), spiris::Error>(())
+//! ```
+
+use crate::auth::AccessToken;
+use crate::client::ClientConfig;
+use crate::endpoints::approvals::ApprovalAction;
+use crate::endpoints::customers::CustomerMerge;
+use crate::endpoints::fiscal_years::OpeningBalance;
+use crate::endpoints::invoices::CreatedInvoice;
+use crate::endpoints::vouchers::{Atomicity, VoucherBatchResult};
+use crate::endpoints::ReferenceData;
+use crate::error::Result;
+use crate::retry::RateLimitStatus;
+use crate::types::{
+    Account, AccountBalance, AccountType, AllocationPeriod, Article, ArticleAccountCoding,
+    ArticleId, ArticleLabel, ArticlePatch, Attachment, AttachmentLink, AttachmentUpload, Bank,
+    BankAccount, CompanySettings, ConvertDraftOptions, CostCenter, CostCenterItem, Country,
+    CreateInvoiceOptions, Currency, Customer, CustomerId, CustomerInvoiceDraft, CustomerLabel,
+    CustomerLedgerItem, CustomerPatch, DeliveryMethod, DeliveryTerm, Document, FiscalYear,
+    ForeignPaymentCode, Invoice, InvoiceId, InvoicePatch, InvoicePayment, InvoiceReminder,
+    ListAllOptions, ListScope, Logotype, Message, MessageThread, NoteTarget, Order,
+    PaginatedResponse, PaginationParams, Project, QueryParams, Quotation, SendInvoiceOptions,
+    Supplier, SupplierInvoice, SupplierInvoiceDraft, SupplierInvoicePayment, SupplierLabel,
+    SupplierLedgerItem, TermsOfPayment, Unit, User, VatCode, VatReport, Versioned, Voucher,
+};
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// Blocking API client.
+///
+/// Cloning is cheap; clones share the async client and the runtime.
+#[derive(Clone)]
+pub struct Client {
+    inner: crate::Client,
+    runtime: Arc<Runtime>,
+}
+
+impl Client {
+    /// Create a new blocking client with an access token.
+    pub fn new(access_token: AccessToken) -> Self {
+        Self::from(crate::Client::new(access_token))
+    }
+
+    /// Create a new blocking client with custom configuration.
+    pub fn with_config(access_token: AccessToken, config: ClientConfig) -> Self {
+        Self::from(crate::Client::with_config(access_token, config))
+    }
+
+    /// The async client requests are sent with.
+    pub fn as_async(&self) -> &crate::Client {
+        &self.inner
+    }
+
+    /// Run `future` to completion on the client's runtime.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use spiris::blocking::Client;
+    /// # fn example(client: &Client) -> spiris::Result<()> {
+    /// let customers = client.block_on(client.as_async().customers().list_all(None))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Update the access token.
+    pub fn set_access_token(&self, token: AccessToken) {
+        self.inner.set_access_token(token);
+    }
+
+    /// Get the current access token.
+    pub fn get_access_token(&self) -> AccessToken {
+        self.inner.get_access_token()
+    }
+
+    /// Base URL requests are sent to.
+    pub fn base_url(&self) -> &str {
+        self.inner.base_url()
+    }
+
+    /// Check if the current access token is expired.
+    pub fn is_token_expired(&self) -> bool {
+        self.inner.is_token_expired()
+    }
+
+    /// Remaining request quota reported by the last response.
+    pub fn rate_limit_status(&self) -> Option<RateLimitStatus> {
+        self.inner.rate_limit_status()
+    }
+
+    /// Access the quotations endpoint (alias for [`quotations`](Self::quotations)).
+    pub fn quotes(&self) -> QuotationsEndpoint<'_> {
+        self.quotations()
+    }
+}
+
+impl From<crate::Client> for Client {
+    /// Run `client` on a new runtime with one worker thread.
+    fn from(client: crate::Client) -> Self {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("spiris-blocking")
+            .enable_all()
+            .build()
+            .expect("Failed to build blocking client runtime");
+        Self {
+            inner: client,
+            runtime: Arc::new(runtime),
+        }
+    }
+}
+
+/// Define a blocking endpoint that forwards each method to the async one.
+macro_rules! blocking_endpoint {
+    (
+        $name:ident => $accessor:ident {
+            $(fn $method:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;)*
+        }
+    ) => {
+        #[doc = concat!(
+            "Blocking version of [`", stringify!($name), "`](crate::endpoints::",
+            stringify!($name), ")."
+        )]
+        #[derive(Clone, Copy)]
+        pub struct $name<'a> {
+            client: &'a Client,
+        }
+
+        impl $name<'_> {
+            $(
+                #[doc = concat!(
+                    "Blocking version of [`", stringify!($name), "::", stringify!($method),
+                    "`](crate::endpoints::", stringify!($name), "::", stringify!($method), ")."
+                )]
+                pub fn $method(&self, $($arg: $ty),*) $(-> $ret)? {
+                    self.client
+                        .block_on(self.client.inner.$accessor().$method($($arg),*))
+                }
+            )*
+        }
+
+        impl Client {
+            #[doc = concat!("Access the blocking ", stringify!($accessor), " endpoint.")]
+            pub fn $accessor(&self) -> $name<'_> {
+                $name { client: self }
+            }
+        }
+    };
+}
+
+blocking_endpoint! {
+    CustomersEndpoint => customers {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<Customer>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<Customer>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<Customer>>;
+        fn get(id: &CustomerId) -> Result<Customer>;
+        fn get_versioned(id: &CustomerId) -> Result<Versioned<Customer>>;
+        fn create(customer: &Customer) -> Result<Customer>;
+        fn update(id: &CustomerId, customer: &Customer) -> Result<Customer>;
+        fn update_if_match(id: &CustomerId, item: &Versioned<Customer>) -> Result<Versioned<Customer>>;
+        fn update_partial(id: &CustomerId, patch: &CustomerPatch) -> Result<Customer>;
+        fn delete(id: &CustomerId) -> Result<()>;
+        fn search(query: QueryParams, pagination: Option<PaginationParams>) -> Result<PaginatedResponse<Customer>>;
+        fn get_by_number(number: &str, scope: ListScope) -> Result<Option<Customer>>;
+        fn merge(primary_id: &CustomerId, duplicate_id: &CustomerId) -> Result<CustomerMerge>;
+    }
+}
+
+blocking_endpoint! {
+    InvoicesEndpoint => invoices {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<Invoice>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<Invoice>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<Invoice>>;
+        fn get(id: &InvoiceId) -> Result<Invoice>;
+        fn get_versioned(id: &InvoiceId) -> Result<Versioned<Invoice>>;
+        fn create(invoice: &Invoice) -> Result<Invoice>;
+        fn create_with(invoice: &Invoice, options: &CreateInvoiceOptions) -> Result<CreatedInvoice>;
+        fn update(id: &InvoiceId, invoice: &Invoice) -> Result<Invoice>;
+        fn update_if_match(id: &InvoiceId, item: &Versioned<Invoice>) -> Result<Versioned<Invoice>>;
+        fn update_partial(id: &InvoiceId, patch: &InvoicePatch) -> Result<Invoice>;
+        fn delete(id: &InvoiceId) -> Result<()>;
+        fn search(query: QueryParams, pagination: Option<PaginationParams>) -> Result<PaginatedResponse<Invoice>>;
+        fn register_payment(invoice_id: &InvoiceId, payment: &InvoicePayment) -> Result<()>;
+        fn pdf(invoice_id: &InvoiceId) -> Result<Bytes>;
+        fn get_pdf(invoice_id: &InvoiceId) -> Result<Vec<u8>>;
+        fn download_pdf_to(invoice_id: &InvoiceId, path: impl AsRef<Path>) -> Result<()>;
+        fn send_einvoice(invoice_id: &InvoiceId) -> Result<()>;
+        fn send(invoice_id: &InvoiceId, options: &SendInvoiceOptions) -> Result<()>;
+        fn send_reminder(invoice_id: &InvoiceId, reminder: &InvoiceReminder) -> Result<InvoiceReminder>;
+        fn list_reminders(invoice_id: &InvoiceId) -> Result<Vec<InvoiceReminder>>;
+    }
+}
+
+blocking_endpoint! {
+    ArticlesEndpoint => articles {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<Article>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<Article>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<Article>>;
+        fn get(id: &ArticleId) -> Result<Article>;
+        fn get_versioned(id: &ArticleId) -> Result<Versioned<Article>>;
+        fn create(article: &Article) -> Result<Article>;
+        fn update(id: &ArticleId, article: &Article) -> Result<Article>;
+        fn update_if_match(id: &ArticleId, item: &Versioned<Article>) -> Result<Versioned<Article>>;
+        fn update_partial(id: &ArticleId, patch: &ArticlePatch) -> Result<Article>;
+        fn delete(id: &ArticleId) -> Result<()>;
+        fn search(query: QueryParams, pagination: Option<PaginationParams>) -> Result<PaginatedResponse<Article>>;
+        fn get_by_number(number: &str, scope: ListScope) -> Result<Option<Article>>;
+    }
+}
+
+blocking_endpoint! {
+    CustomerInvoiceDraftsEndpoint => customer_invoice_drafts {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<CustomerInvoiceDraft>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<CustomerInvoiceDraft>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<CustomerInvoiceDraft>>;
+        fn get(id: &str) -> Result<CustomerInvoiceDraft>;
+        fn get_versioned(id: &str) -> Result<Versioned<CustomerInvoiceDraft>>;
+        fn create(draft: &CustomerInvoiceDraft) -> Result<CustomerInvoiceDraft>;
+        fn update(id: &str, draft: &CustomerInvoiceDraft) -> Result<CustomerInvoiceDraft>;
+        fn update_if_match(id: &str, item: &Versioned<CustomerInvoiceDraft>) -> Result<Versioned<CustomerInvoiceDraft>>;
+        fn delete(id: &str) -> Result<()>;
+        fn convert(id: &str, options: Option<ConvertDraftOptions>) -> Result<Invoice>;
+        fn search(query: QueryParams, pagination: Option<PaginationParams>) -> Result<PaginatedResponse<CustomerInvoiceDraft>>;
+    }
+}
+
+blocking_endpoint! {
+    CustomerLedgerItemsEndpoint => customer_ledger_items {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<CustomerLedgerItem>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<CustomerLedgerItem>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<CustomerLedgerItem>>;
+        fn get(id: &str) -> Result<CustomerLedgerItem>;
+        fn create(item: &CustomerLedgerItem) -> Result<CustomerLedgerItem>;
+        fn search(query: QueryParams, pagination: Option<PaginationParams>) -> Result<PaginatedResponse<CustomerLedgerItem>>;
+    }
+}
+
+blocking_endpoint! {
+    CustomerLabelsEndpoint => customer_labels {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<CustomerLabel>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<CustomerLabel>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<CustomerLabel>>;
+        fn get(id: &str) -> Result<CustomerLabel>;
+        fn get_versioned(id: &str) -> Result<Versioned<CustomerLabel>>;
+        fn create(item: &CustomerLabel) -> Result<CustomerLabel>;
+        fn update(id: &str, item: &CustomerLabel) -> Result<CustomerLabel>;
+        fn update_if_match(id: &str, item: &Versioned<CustomerLabel>) -> Result<Versioned<CustomerLabel>>;
+        fn delete(id: &str) -> Result<()>;
+    }
+}
+
+blocking_endpoint! {
+    SuppliersEndpoint => suppliers {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<Supplier>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<Supplier>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<Supplier>>;
+        fn get(id: &str) -> Result<Supplier>;
+        fn get_versioned(id: &str) -> Result<Versioned<Supplier>>;
+        fn create(supplier: &Supplier) -> Result<Supplier>;
+        fn update(id: &str, supplier: &Supplier) -> Result<Supplier>;
+        fn update_if_match(id: &str, item: &Versioned<Supplier>) -> Result<Versioned<Supplier>>;
+        fn delete(id: &str) -> Result<()>;
+        fn search(query: QueryParams, pagination: Option<PaginationParams>) -> Result<PaginatedResponse<Supplier>>;
+        fn get_by_number(number: &str, scope: ListScope) -> Result<Option<Supplier>>;
+    }
+}
+
+blocking_endpoint! {
+    SupplierInvoicesEndpoint => supplier_invoices {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<SupplierInvoice>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<SupplierInvoice>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<SupplierInvoice>>;
+        fn get(id: &str) -> Result<SupplierInvoice>;
+        fn get_versioned(id: &str) -> Result<Versioned<SupplierInvoice>>;
+        fn create(invoice: &SupplierInvoice) -> Result<SupplierInvoice>;
+        fn update(id: &str, invoice: &SupplierInvoice) -> Result<SupplierInvoice>;
+        fn update_if_match(id: &str, item: &Versioned<SupplierInvoice>) -> Result<Versioned<SupplierInvoice>>;
+        fn delete(id: &str) -> Result<()>;
+        fn register_payment(invoice_id: &str, payment: &InvoicePayment) -> Result<()>;
+        fn search(query: QueryParams, pagination: Option<PaginationParams>) -> Result<PaginatedResponse<SupplierInvoice>>;
+    }
+}
+
+blocking_endpoint! {
+    AccountsEndpoint => accounts {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<Account>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<Account>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<Account>>;
+        fn list_by_fiscal_year(fiscal_year_id: &str, params: Option<PaginationParams>) -> Result<PaginatedResponse<Account>>;
+        fn get(fiscal_year_id: &str, account_number: &str) -> Result<Account>;
+        fn create(account: &Account) -> Result<Account>;
+        fn update(fiscal_year_id: &str, account_number: &str, account: &Account) -> Result<Account>;
+        fn get_standard_accounts() -> Result<PaginatedResponse<Account>>;
+        fn get_balances(date: &str) -> Result<PaginatedResponse<AccountBalance>>;
+        fn get_balances_with_params(date: &str, params: PaginationParams) -> Result<PaginatedResponse<AccountBalance>>;
+        fn get_balance(account_number: &str, date: &str) -> Result<AccountBalance>;
+        fn get_account_types() -> Result<PaginatedResponse<AccountType>>;
+    }
+}
+
+blocking_endpoint! {
+    FiscalYearsEndpoint => fiscal_years {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<FiscalYear>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<FiscalYear>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<FiscalYear>>;
+        fn get(id: &str) -> Result<FiscalYear>;
+        fn create(fiscal_year: &FiscalYear) -> Result<FiscalYear>;
+        fn get_opening_balances() -> Result<Vec<OpeningBalance>>;
+        fn update_opening_balances(balances: &[OpeningBalance]) -> Result<Vec<OpeningBalance>>;
+    }
+}
+
+blocking_endpoint! {
+    VatCodesEndpoint => vat_codes {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<VatCode>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<VatCode>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<VatCode>>;
+        fn get(id: &str) -> Result<VatCode>;
+        fn get_versioned(id: &str) -> Result<Versioned<VatCode>>;
+    }
+}
+
+blocking_endpoint! {
+    VatReportsEndpoint => vat_reports {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<VatReport>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<VatReport>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<VatReport>>;
+        fn get(id: &str) -> Result<VatReport>;
+        fn get_versioned(id: &str) -> Result<Versioned<VatReport>>;
+    }
+}
+
+blocking_endpoint! {
+    VouchersEndpoint => vouchers {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<Voucher>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<Voucher>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<Voucher>>;
+        fn get(id: &str) -> Result<Voucher>;
+        fn get_versioned(id: &str) -> Result<Versioned<Voucher>>;
+        fn create(voucher: &Voucher) -> Result<Voucher>;
+        fn create_batch(vouchers: &[Voucher], atomicity: Atomicity) -> Result<VoucherBatchResult>;
+        fn update(id: &str, voucher: &Voucher) -> Result<Voucher>;
+        fn update_if_match(id: &str, item: &Versioned<Voucher>) -> Result<Versioned<Voucher>>;
+        fn delete(id: &str) -> Result<()>;
+        fn search(query: QueryParams, pagination: Option<PaginationParams>) -> Result<PaginatedResponse<Voucher>>;
+    }
+}
+
+blocking_endpoint! {
+    BankAccountsEndpoint => bank_accounts {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<BankAccount>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<BankAccount>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<BankAccount>>;
+        fn get(id: &str) -> Result<BankAccount>;
+        fn get_versioned(id: &str) -> Result<Versioned<BankAccount>>;
+        fn create(item: &BankAccount) -> Result<BankAccount>;
+        fn update(id: &str, item: &BankAccount) -> Result<BankAccount>;
+        fn update_if_match(id: &str, item: &Versioned<BankAccount>) -> Result<Versioned<BankAccount>>;
+        fn delete(id: &str) -> Result<()>;
+        fn list_active() -> Result<Vec<BankAccount>>;
+        fn for_currency(currency_code: &str) -> Result<Option<BankAccount>>;
+        fn find_by_number(number: &str) -> Result<Option<BankAccount>>;
+    }
+}
+
+blocking_endpoint! {
+    ReferenceDataEndpoint => reference_data {
+        fn load_all() -> Result<Arc<ReferenceData>>;
+        fn refresh() -> Result<Arc<ReferenceData>>;
+        fn invalidate();
+    }
+}
+
+blocking_endpoint! {
+    ProjectsEndpoint => projects {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<Project>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<Project>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<Project>>;
+        fn get(id: &str) -> Result<Project>;
+        fn get_versioned(id: &str) -> Result<Versioned<Project>>;
+        fn create(item: &Project) -> Result<Project>;
+        fn update(id: &str, item: &Project) -> Result<Project>;
+        fn update_if_match(id: &str, item: &Versioned<Project>) -> Result<Versioned<Project>>;
+        fn delete(id: &str) -> Result<()>;
+        fn search(query: QueryParams, pagination: Option<PaginationParams>) -> Result<PaginatedResponse<Project>>;
+    }
+}
+
+blocking_endpoint! {
+    CostCentersEndpoint => cost_centers {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<CostCenter>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<CostCenter>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<CostCenter>>;
+        fn update(id: &str, item: &CostCenter) -> Result<CostCenter>;
+        fn update_if_match(id: &str, item: &Versioned<CostCenter>) -> Result<Versioned<CostCenter>>;
+        fn list_items(params: Option<PaginationParams>) -> Result<PaginatedResponse<CostCenterItem>>;
+        fn get_item(id: &str) -> Result<CostCenterItem>;
+        fn create_item(item: &CostCenterItem) -> Result<CostCenterItem>;
+        fn update_item(id: &str, item: &CostCenterItem) -> Result<CostCenterItem>;
+    }
+}
+
+blocking_endpoint! {
+    AllocationPeriodsEndpoint => allocation_periods {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<AllocationPeriod>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<AllocationPeriod>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<AllocationPeriod>>;
+        fn get(id: &str) -> Result<AllocationPeriod>;
+        fn get_versioned(id: &str) -> Result<Versioned<AllocationPeriod>>;
+        fn create(item: &AllocationPeriod) -> Result<AllocationPeriod>;
+    }
+}
+
+blocking_endpoint! {
+    OrdersEndpoint => orders {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<Order>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<Order>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<Order>>;
+        fn get(id: &str) -> Result<Order>;
+        fn get_versioned(id: &str) -> Result<Versioned<Order>>;
+        fn create(order: &Order) -> Result<Order>;
+        fn update(id: &str, order: &Order) -> Result<Order>;
+        fn update_if_match(id: &str, item: &Versioned<Order>) -> Result<Versioned<Order>>;
+        fn delete(id: &str) -> Result<()>;
+        fn search(query: QueryParams, pagination: Option<PaginationParams>) -> Result<PaginatedResponse<Order>>;
+        fn convert_to_invoice_draft(id: &str) -> Result<CustomerInvoiceDraft>;
+    }
+}
+
+blocking_endpoint! {
+    QuotationsEndpoint => quotations {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<Quotation>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<Quotation>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<Quotation>>;
+        fn get(id: &str) -> Result<Quotation>;
+        fn get_versioned(id: &str) -> Result<Versioned<Quotation>>;
+        fn create(quotation: &Quotation) -> Result<Quotation>;
+        fn update(id: &str, quotation: &Quotation) -> Result<Quotation>;
+        fn update_if_match(id: &str, item: &Versioned<Quotation>) -> Result<Versioned<Quotation>>;
+        fn delete(id: &str) -> Result<()>;
+        fn search(query: QueryParams, pagination: Option<PaginationParams>) -> Result<PaginatedResponse<Quotation>>;
+        fn accept(id: &str) -> Result<Quotation>;
+        fn convert_to_order(id: &str) -> Result<Order>;
+        fn convert_to_invoice_draft(id: &str) -> Result<CustomerInvoiceDraft>;
+    }
+}
+
+blocking_endpoint! {
+    SupplierInvoiceDraftsEndpoint => supplier_invoice_drafts {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<SupplierInvoiceDraft>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<SupplierInvoiceDraft>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<SupplierInvoiceDraft>>;
+        fn get(id: &str) -> Result<SupplierInvoiceDraft>;
+        fn get_versioned(id: &str) -> Result<Versioned<SupplierInvoiceDraft>>;
+        fn create(draft: &SupplierInvoiceDraft) -> Result<SupplierInvoiceDraft>;
+        fn update(id: &str, draft: &SupplierInvoiceDraft) -> Result<SupplierInvoiceDraft>;
+        fn update_if_match(id: &str, item: &Versioned<SupplierInvoiceDraft>) -> Result<Versioned<SupplierInvoiceDraft>>;
+        fn delete(id: &str) -> Result<()>;
+        fn convert(id: &str) -> Result<SupplierInvoice>;
+        fn search(query: QueryParams, pagination: Option<PaginationParams>) -> Result<PaginatedResponse<SupplierInvoiceDraft>>;
+    }
+}
+
+blocking_endpoint! {
+    SupplierInvoicePaymentsEndpoint => supplier_invoice_payments {
+        fn register(invoice_id: &str, payment: &SupplierInvoicePayment) -> Result<SupplierInvoicePayment>;
+        fn register_for(invoice: &SupplierInvoice, payment: SupplierInvoicePayment) -> Result<SupplierInvoicePayment>;
+    }
+}
+
+blocking_endpoint! {
+    SupplierLedgerItemsEndpoint => supplier_ledger_items {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<SupplierLedgerItem>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<SupplierLedgerItem>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<SupplierLedgerItem>>;
+        fn get(id: &str) -> Result<SupplierLedgerItem>;
+        fn create(item: &SupplierLedgerItem) -> Result<SupplierLedgerItem>;
+        fn search(query: QueryParams, pagination: Option<PaginationParams>) -> Result<PaginatedResponse<SupplierLedgerItem>>;
+    }
+}
+
+blocking_endpoint! {
+    SupplierLabelsEndpoint => supplier_labels {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<SupplierLabel>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<SupplierLabel>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<SupplierLabel>>;
+        fn get(id: &str) -> Result<SupplierLabel>;
+        fn get_versioned(id: &str) -> Result<Versioned<SupplierLabel>>;
+        fn create(item: &SupplierLabel) -> Result<SupplierLabel>;
+        fn update(id: &str, item: &SupplierLabel) -> Result<SupplierLabel>;
+        fn update_if_match(id: &str, item: &Versioned<SupplierLabel>) -> Result<Versioned<SupplierLabel>>;
+        fn delete(id: &str) -> Result<()>;
+    }
+}
+
+blocking_endpoint! {
+    ArticleLabelsEndpoint => article_labels {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<ArticleLabel>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<ArticleLabel>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<ArticleLabel>>;
+        fn get(id: &str) -> Result<ArticleLabel>;
+        fn get_versioned(id: &str) -> Result<Versioned<ArticleLabel>>;
+        fn create(item: &ArticleLabel) -> Result<ArticleLabel>;
+        fn update(id: &str, item: &ArticleLabel) -> Result<ArticleLabel>;
+        fn update_if_match(id: &str, item: &Versioned<ArticleLabel>) -> Result<Versioned<ArticleLabel>>;
+        fn delete(id: &str) -> Result<()>;
+    }
+}
+
+blocking_endpoint! {
+    ArticleAccountCodingsEndpoint => article_account_codings {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<ArticleAccountCoding>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<ArticleAccountCoding>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<ArticleAccountCoding>>;
+        fn get(id: &str) -> Result<ArticleAccountCoding>;
+        fn get_versioned(id: &str) -> Result<Versioned<ArticleAccountCoding>>;
+    }
+}
+
+blocking_endpoint! {
+    UnitsEndpoint => units {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<Unit>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<Unit>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<Unit>>;
+        fn get(id: &str) -> Result<Unit>;
+        fn get_versioned(id: &str) -> Result<Versioned<Unit>>;
+        fn create(item: &Unit) -> Result<Unit>;
+        fn update(id: &str, item: &Unit) -> Result<Unit>;
+        fn update_if_match(id: &str, item: &Versioned<Unit>) -> Result<Versioned<Unit>>;
+        fn delete(id: &str) -> Result<()>;
+    }
+}
+
+blocking_endpoint! {
+    DeliveryMethodsEndpoint => delivery_methods {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<DeliveryMethod>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<DeliveryMethod>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<DeliveryMethod>>;
+        fn get(id: &str) -> Result<DeliveryMethod>;
+        fn get_versioned(id: &str) -> Result<Versioned<DeliveryMethod>>;
+    }
+}
+
+blocking_endpoint! {
+    DeliveryTermsEndpoint => delivery_terms {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<DeliveryTerm>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<DeliveryTerm>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<DeliveryTerm>>;
+        fn get(id: &str) -> Result<DeliveryTerm>;
+        fn get_versioned(id: &str) -> Result<Versioned<DeliveryTerm>>;
+    }
+}
+
+blocking_endpoint! {
+    TermsOfPaymentEndpoint => terms_of_payment {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<TermsOfPayment>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<TermsOfPayment>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<TermsOfPayment>>;
+        fn get(id: &str) -> Result<TermsOfPayment>;
+        fn get_versioned(id: &str) -> Result<Versioned<TermsOfPayment>>;
+        fn create(item: &TermsOfPayment) -> Result<TermsOfPayment>;
+        fn update(id: &str, item: &TermsOfPayment) -> Result<TermsOfPayment>;
+        fn update_if_match(id: &str, item: &Versioned<TermsOfPayment>) -> Result<Versioned<TermsOfPayment>>;
+        fn delete(id: &str) -> Result<()>;
+    }
+}
+
+blocking_endpoint! {
+    AttachmentsEndpoint => attachments {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<Attachment>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<Attachment>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<Attachment>>;
+        fn get(id: &str) -> Result<Attachment>;
+        fn delete(id: &str) -> Result<()>;
+        fn get_content(id: &str) -> Result<Vec<u8>>;
+        fn upload(upload: &AttachmentUpload) -> Result<Attachment>;
+        fn upload_file(path: impl AsRef<Path>) -> Result<Attachment>;
+        fn create_link(link: &AttachmentLink) -> Result<AttachmentLink>;
+        fn link_to_supplier_invoice(attachment_id: &str, supplier_invoice_id: &str) -> Result<AttachmentLink>;
+        fn link_to_voucher(attachment_id: &str, voucher_id: &str) -> Result<AttachmentLink>;
+        fn delete_link(id: &str) -> Result<()>;
+    }
+}
+
+blocking_endpoint! {
+    LogotypesEndpoint => logotypes {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<Logotype>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<Logotype>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<Logotype>>;
+        fn get(id: &str) -> Result<Logotype>;
+        fn get_content(id: &str) -> Result<Vec<u8>>;
+    }
+}
+
+blocking_endpoint! {
+    DocumentsEndpoint => documents {
+        fn get(id: &str) -> Result<Document>;
+        fn get_versioned(id: &str) -> Result<Versioned<Document>>;
+    }
+}
+
+blocking_endpoint! {
+    CompanySettingsEndpoint => company_settings {
+        fn get() -> Result<CompanySettings>;
+        fn update(settings: &CompanySettings) -> Result<CompanySettings>;
+    }
+}
+
+blocking_endpoint! {
+    CountriesEndpoint => countries {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<Country>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<Country>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<Country>>;
+        fn get(id: &str) -> Result<Country>;
+        fn get_versioned(id: &str) -> Result<Versioned<Country>>;
+    }
+}
+
+blocking_endpoint! {
+    CurrenciesEndpoint => currencies {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<Currency>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<Currency>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<Currency>>;
+    }
+}
+
+blocking_endpoint! {
+    UsersEndpoint => users {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<User>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<User>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<User>>;
+        fn get(id: &str) -> Result<User>;
+        fn get_versioned(id: &str) -> Result<Versioned<User>>;
+    }
+}
+
+blocking_endpoint! {
+    BanksEndpoint => banks {
+        fn list(params: Option<PaginationParams>) -> Result<PaginatedResponse<Bank>>;
+        fn list_all(query: Option<QueryParams>) -> Result<Vec<Bank>>;
+        fn list_all_with(query: Option<QueryParams>, options: ListAllOptions) -> Result<Vec<Bank>>;
+        fn list_foreign_payment_codes() -> Result<PaginatedResponse<ForeignPaymentCode>>;
+    }
+}
+
+blocking_endpoint! {
+    MessagesEndpoint => messages {
+        fn list_threads(params: Option<PaginationParams>) -> Result<PaginatedResponse<MessageThread>>;
+        fn search_threads(query: QueryParams, pagination: Option<PaginationParams>) -> Result<PaginatedResponse<MessageThread>>;
+        fn get_thread(id: &str) -> Result<MessageThread>;
+        fn create_thread(thread: &MessageThread) -> Result<MessageThread>;
+        fn update_thread(id: &str, thread: &MessageThread) -> Result<MessageThread>;
+        fn add_message(thread_id: &str, message: &Message) -> Result<Message>;
+        fn notes(target: &NoteTarget) -> Result<Vec<MessageThread>>;
+        fn add_note(target: &NoteTarget, subject: &str, content: &str) -> Result<MessageThread>;
+    }
+}
+
+blocking_endpoint! {
+    ApprovalsEndpoint => approvals {
+        fn approve_vat_report(id: &str, action: &ApprovalAction) -> Result<()>;
+        fn approve_supplier_invoice(id: &str, action: &ApprovalAction) -> Result<()>;
+        fn request_supplier_invoice_approval(id: &str, approver_user_id: &str) -> Result<SupplierInvoice>;
+        fn pending_supplier_invoices(user_id: Option<&str>) -> Result<Vec<SupplierInvoice>>;
+    }
+}
+
+impl UsersEndpoint<'_> {
+    /// Blocking version of
+    /// [`UsersEndpoint::resolve_users`](crate::endpoints::UsersEndpoint::resolve_users).
+    pub fn resolve_users<I, S>(&self, ids: I) -> Result<HashMap<String, String>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.client
+            .block_on(self.client.inner.users().resolve_users(ids))
+    }
+}
@@ -0,0 +1,463 @@
+//! Monthly budgets and budget-vs-actual reports.
+//!
+//! A [`Budget`] holds monthly amounts for result accounts (BAS classes 3-8),
+//! either per account or for a range of accounts such as `5000-5999`.
+//! Amounts are signed as in [`ProfitAndLoss`]: income positive and costs
+//! negative, so a positive variance is always in the company's favour.
+//!
+//! Budgets are plain serde types and are stored by the application, e.g. as
+//! JSON:
+//!
+//! ```json
+//! {
+//!   "name": "2025",
+//!   "lines": [
+//!     { "accounts": "3000-3999", "label": "Sales", "months": { "2025-01-01": 120000.0 } },
+//!     { "accounts": "5010", "label": "Rent", "months": { "2025-01-01": -15000.0 } }
+//!   ]
+//! }
+//! ```
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::budget::{self, AccountRange, Budget};
+//! use spiris::{money, AccessToken, Client};
+//! use chrono::NaiveDate;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+//! let january = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+//! let budget = Budget::new("2025")
+//!     .month("3000-3999".parse()?, january, money!(120000.0))
+//!     .month(AccountRange::account(5010), january, money!(-15000.0));
+//!
+//! let end = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+//! let report = budget::budget_vs_actual(&client, &budget, january, end).await?;
+//! for line in &report.lines {
+//!     println!("{}: {} vs {} ({})", line.accounts, line.actual, line.budget, line.variance);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::reports::{self, ProfitAndLoss};
+use crate::types::Money;
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Result accounts a budget may cover.
+const RESULT_ACCOUNTS: std::ops::RangeInclusive<u32> = 3000..=8999;
+
+/// A single account or an inclusive range of accounts.
+///
+/// Written as `5010` or `5000-5999`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AccountRange {
+    first: u32,
+    last: u32,
+}
+
+impl AccountRange {
+    /// A single account.
+    pub fn account(number: u32) -> Self {
+        Self {
+            first: number,
+            last: number,
+        }
+    }
+
+    /// Accounts `first` through `last`.
+    pub fn new(first: u32, last: u32) -> Result<Self> {
+        if last < first {
+            return Err(Error::InvalidRequest(format!(
+                "account range {}-{} ends before it starts",
+                first, last
+            )));
+        }
+        Ok(Self { first, last })
+    }
+
+    /// Whether `account_number` is in the range.
+    pub fn contains(&self, account_number: u32) -> bool {
+        (self.first..=self.last).contains(&account_number)
+    }
+
+    fn overlaps(&self, other: &AccountRange) -> bool {
+        self.first <= other.last && other.first <= self.last
+    }
+}
+
+impl fmt::Display for AccountRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.first == self.last {
+            write!(f, "{}", self.first)
+        } else {
+            write!(f, "{}-{}", self.first, self.last)
+        }
+    }
+}
+
+impl FromStr for AccountRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let number = |part: &str| {
+            part.trim().parse::<u32>().map_err(|_| {
+                Error::InvalidRequest(format!("invalid account or account range '{}'", s))
+            })
+        };
+        match s.split_once('-') {
+            Some((first, last)) => Self::new(number(first)?, number(last)?),
+            None => Ok(Self::account(number(s)?)),
+        }
+    }
+}
+
+impl TryFrom<String> for AccountRange {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<AccountRange> for String {
+    fn from(range: AccountRange) -> Self {
+        range.to_string()
+    }
+}
+
+/// Monthly amounts for one account or account range.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetLine {
+    /// Accounts the line covers.
+    pub accounts: AccountRange,
+    /// Name shown in reports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Amount per month, keyed by the first day of the month.
+    #[serde(default)]
+    pub months: BTreeMap<NaiveDate, Money>,
+}
+
+impl BudgetLine {
+    /// Budgeted amount for the months from `start` through `end`.
+    pub fn amount(&self, start: NaiveDate, end: NaiveDate) -> Money {
+        self.months
+            .range(start..=end)
+            .map(|(_, amount)| *amount)
+            .sum()
+    }
+}
+
+/// A budget: monthly amounts per account or account range.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Budget {
+    /// Name of the budget, e.g. the fiscal year it covers.
+    pub name: String,
+    /// Budget lines; each account is covered by at most one line.
+    #[serde(default)]
+    pub lines: Vec<BudgetLine>,
+}
+
+impl Budget {
+    /// An empty budget.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Set the amount for `accounts` in the month containing `month`.
+    pub fn month(mut self, accounts: AccountRange, month: NaiveDate, amount: Money) -> Self {
+        let key = first_of_month(month);
+        match self.lines.iter_mut().find(|l| l.accounts == accounts) {
+            Some(line) => {
+                line.months.insert(key, amount);
+            }
+            None => self.lines.push(BudgetLine {
+                accounts,
+                label: None,
+                months: BTreeMap::from([(key, amount)]),
+            }),
+        }
+        self
+    }
+
+    /// Set the report name of the line for `accounts`.
+    pub fn label(mut self, accounts: AccountRange, label: impl Into<String>) -> Self {
+        if let Some(line) = self.lines.iter_mut().find(|l| l.accounts == accounts) {
+            line.label = Some(label.into());
+        }
+        self
+    }
+
+    /// Check that lines cover result accounts only, do not overlap and are
+    /// keyed by the first day of each month.
+    pub fn validate(&self) -> Result<()> {
+        for (index, line) in self.lines.iter().enumerate() {
+            if !RESULT_ACCOUNTS.contains(&line.accounts.first)
+                || !RESULT_ACCOUNTS.contains(&line.accounts.last)
+            {
+                return Err(Error::InvalidRequest(format!(
+                    "budget line {} is outside the result accounts 3000-8999",
+                    line.accounts
+                )));
+            }
+            if let Some(other) = self.lines[..index]
+                .iter()
+                .find(|other| other.accounts.overlaps(&line.accounts))
+            {
+                return Err(Error::InvalidRequest(format!(
+                    "budget lines {} and {} overlap",
+                    other.accounts, line.accounts
+                )));
+            }
+            if let Some(month) = line.months.keys().find(|m| m.day() != 1) {
+                return Err(Error::InvalidRequest(format!(
+                    "budget line {} has month {} that is not the first of a month",
+                    line.accounts, month
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Budget and actual amount for one budget line.
+#[derive(Debug, Clone, Serialize)]
+pub struct VarianceLine {
+    /// Accounts of the budget line.
+    pub accounts: AccountRange,
+    /// Name of the budget line.
+    pub label: Option<String>,
+    /// Budgeted amount for the period.
+    #[serde(with = "crate::types::money_serde")]
+    pub budget: Money,
+    /// Actual result for the period.
+    #[serde(with = "crate::types::money_serde")]
+    pub actual: Money,
+    /// `actual - budget`; positive is favourable.
+    #[serde(with = "crate::types::money_serde")]
+    pub variance: Money,
+}
+
+/// Budget compared with the actual result for a period.
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetVsActual {
+    /// Name of the budget.
+    pub budget_name: String,
+    /// First day of the period.
+    pub period_start: NaiveDate,
+    /// Last day of the period.
+    pub period_end: NaiveDate,
+    /// One line per budget line.
+    pub lines: Vec<VarianceLine>,
+    /// Result on accounts no budget line covers.
+    #[serde(with = "crate::types::money_serde")]
+    pub unbudgeted: Money,
+    /// Total budgeted result.
+    #[serde(with = "crate::types::money_serde")]
+    pub total_budget: Money,
+    /// Total actual result, including unbudgeted accounts.
+    #[serde(with = "crate::types::money_serde")]
+    pub total_actual: Money,
+    /// `total_actual - total_budget`.
+    #[serde(with = "crate::types::money_serde")]
+    pub total_variance: Money,
+}
+
+impl BudgetVsActual {
+    /// Compare a budget with a profit and loss statement for whole months.
+    pub fn from_pnl(budget: &Budget, pnl: &ProfitAndLoss) -> Result<Self> {
+        budget.validate()?;
+        check_whole_months(pnl.period_start, pnl.period_end)?;
+
+        let mut unbudgeted = Money::default();
+        let mut actuals = vec![Money::default(); budget.lines.len()];
+        for line in &pnl.lines {
+            let covering = line.account_number.parse::<u32>().ok().and_then(|number| {
+                budget
+                    .lines
+                    .iter()
+                    .position(|b| b.accounts.contains(number))
+            });
+            match covering {
+                Some(index) => actuals[index] += line.amount,
+                None => unbudgeted += line.amount,
+            }
+        }
+
+        let lines: Vec<VarianceLine> = budget
+            .lines
+            .iter()
+            .zip(actuals)
+            .map(|(line, actual)| {
+                let amount = line.amount(pnl.period_start, pnl.period_end);
+                VarianceLine {
+                    accounts: line.accounts,
+                    label: line.label.clone(),
+                    budget: amount,
+                    actual,
+                    variance: actual - amount,
+                }
+            })
+            .collect();
+        let total_budget: Money = lines.iter().map(|l| l.budget).sum();
+        let total_actual = lines.iter().map(|l| l.actual).sum::<Money>() + unbudgeted;
+
+        Ok(Self {
+            budget_name: budget.name.clone(),
+            period_start: pnl.period_start,
+            period_end: pnl.period_end,
+            lines,
+            unbudgeted,
+            total_budget,
+            total_actual,
+            total_variance: total_actual - total_budget,
+        })
+    }
+}
+
+/// Compare `budget` with the actual result for `start..=end`, which must
+/// cover whole months.
+pub async fn budget_vs_actual(
+    client: &Client,
+    budget: &Budget,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<BudgetVsActual> {
+    budget.validate()?;
+    check_whole_months(start, end)?;
+    let pnl = reports::profit_and_loss(client, start, end).await?;
+    BudgetVsActual::from_pnl(budget, &pnl)
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// Budgets are monthly, so a period must start and end on month boundaries.
+fn check_whole_months(start: NaiveDate, end: NaiveDate) -> Result<()> {
+    let ends_month = end.succ_opt().is_some_and(|next| next.day() == 1);
+    if start.day() != 1 || !ends_month || end < start {
+        return Err(Error::InvalidRequest(format!(
+            "budget periods must cover whole months, got {}..{}",
+            start, end
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AccountBalance;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn balance(account: &str, amount: Money) -> AccountBalance {
+        AccountBalance {
+            account_number: Some(account.to_string()),
+            name: None,
+            balance: Some(amount),
+        }
+    }
+
+    fn budget() -> Budget {
+        let sales = AccountRange::new(3000, 3999).unwrap();
+        let rent = AccountRange::account(5010);
+        Budget::new("2025")
+            .month(sales, date(2025, 1, 15), crate::money!(10000.0))
+            .month(sales, date(2025, 2, 1), crate::money!(12000.0))
+            .month(rent, date(2025, 1, 1), crate::money!(-3000.0))
+            .label(sales, "Sales")
+    }
+
+    #[test]
+    fn test_account_range_parsing() {
+        assert_eq!(
+            "5010".parse::<AccountRange>().unwrap(),
+            AccountRange::account(5010)
+        );
+        let range: AccountRange = " 5000 - 5999".parse().unwrap();
+        assert!(range.contains(5410));
+        assert!(!range.contains(6000));
+        assert_eq!(range.to_string(), "5000-5999");
+        assert!("5999-5000".parse::<AccountRange>().is_err());
+        assert!("rent".parse::<AccountRange>().is_err());
+    }
+
+    #[test]
+    fn test_budget_round_trips_as_json() {
+        let budget = budget();
+        let json = serde_json::to_value(&budget).unwrap();
+        assert_eq!(json["lines"][0]["accounts"], "3000-3999");
+        assert!(!json["lines"][0]["months"]["2025-01-01"].is_null());
+
+        let parsed: Budget = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, budget);
+    }
+
+    #[test]
+    fn test_validate_rejects_overlaps_and_balance_accounts() {
+        assert!(budget().validate().is_ok());
+
+        let overlapping = budget().month(
+            AccountRange::new(3500, 3600).unwrap(),
+            date(2025, 1, 1),
+            crate::money!(1.0),
+        );
+        assert!(overlapping.validate().is_err());
+
+        let bank = Budget::new("x").month(
+            AccountRange::account(1930),
+            date(2025, 1, 1),
+            crate::money!(1.0),
+        );
+        assert!(bank.validate().is_err());
+    }
+
+    #[test]
+    fn test_budget_vs_actual() {
+        let closing = [
+            balance("3001", crate::money!(-9000.0)),
+            balance("3041", crate::money!(-500.0)),
+            balance("5010", crate::money!(3000.0)),
+            balance("6110", crate::money!(250.0)),
+        ];
+        let pnl = ProfitAndLoss::from_balances(date(2025, 1, 1), date(2025, 1, 31), &[], &closing);
+
+        let report = BudgetVsActual::from_pnl(&budget(), &pnl).unwrap();
+
+        assert_eq!(report.lines.len(), 2);
+        let sales = &report.lines[0];
+        assert_eq!(sales.label.as_deref(), Some("Sales"));
+        assert_eq!(sales.budget, crate::money!(10000.0));
+        assert_eq!(sales.actual, crate::money!(9500.0));
+        assert_eq!(sales.variance, crate::money!(-500.0));
+        assert_eq!(report.lines[1].variance, Money::default());
+        assert_eq!(report.unbudgeted, crate::money!(-250.0));
+        assert_eq!(report.total_budget, crate::money!(7000.0));
+        assert_eq!(report.total_actual, crate::money!(6250.0));
+        assert_eq!(report.total_variance, crate::money!(-750.0));
+    }
+
+    #[test]
+    fn test_partial_months_are_rejected() {
+        let pnl = ProfitAndLoss::from_balances(date(2025, 1, 1), date(2025, 1, 15), &[], &[]);
+        assert!(BudgetVsActual::from_pnl(&budget(), &pnl).is_err());
+        assert!(check_whole_months(date(2025, 1, 1), date(2025, 3, 31)).is_ok());
+        assert!(check_whole_months(date(2024, 2, 1), date(2024, 2, 29)).is_ok());
+    }
+}
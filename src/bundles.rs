@@ -0,0 +1,343 @@
+//! Article bundles.
+//!
+//! A [`Bundle`] sells several articles as one item, such as a service package
+//! made up of installation hours, a device and a support subscription. Bundles
+//! exist only in the client, so the article register keeps just the real
+//! articles.
+//!
+//! On invoices a bundle is expanded into a text row naming it followed by one
+//! row per component. [`BundleCatalog::collapse`] recognises such rows again,
+//! which [`SalesByItem`](crate::reports::SalesByItem) uses to report bundles as
+//! single items.
+//!
+//! # Example
+//!
+//! ```
+//! use spiris::bundles::Bundle;
+//! use spiris::{Invoice, Money};
+//!
+//! let package = Bundle::new("PKG-1", "Office starter package")
+//!     .component("art-install", Money::from(2))
+//!     .component("art-router", Money::from(1))
+//!     .priced_component("art-support", Money::from(12), Money::from(99));
+//!
+//! let invoice = Invoice {
+//!     customer_id: Some("cust-1".into()),
+//!     ..Default::default()
+//! }
+//! .add_bundle(&package, Money::from(3));
+//!
+//! assert_eq!(invoice.rows.len(), 4);
+//! assert_eq!(invoice.rows[1].quantity, Some(Money::from(6)));
+//! ```
+
+use crate::types::{
+    ArticleId, CustomerInvoiceDraft, CustomerInvoiceDraftRow, Invoice, InvoiceRow, Money,
+};
+use std::collections::HashMap;
+
+/// An article and its quantity in one bundle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BundleComponent {
+    /// Article ID.
+    pub article_id: ArticleId,
+    /// Quantity per bundle.
+    pub quantity: Money,
+    /// Unit price on invoices; the article's sales price when `None`.
+    pub unit_price: Option<Money>,
+}
+
+/// Several articles sold as one item.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bundle {
+    /// Bundle number, unique within a [`BundleCatalog`].
+    pub number: String,
+    /// Name printed on invoices.
+    pub name: String,
+    /// Articles in the bundle, in invoice row order.
+    pub components: Vec<BundleComponent>,
+}
+
+impl Bundle {
+    /// Create a bundle without components.
+    pub fn new(number: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            number: number.into(),
+            name: name.into(),
+            components: Vec::new(),
+        }
+    }
+
+    /// Add an article sold at its own sales price.
+    pub fn component(mut self, article_id: impl Into<ArticleId>, quantity: Money) -> Self {
+        self.components.push(BundleComponent {
+            article_id: article_id.into(),
+            quantity,
+            unit_price: None,
+        });
+        self
+    }
+
+    /// Add an article sold at a bundle specific unit price.
+    pub fn priced_component(
+        mut self,
+        article_id: impl Into<ArticleId>,
+        quantity: Money,
+        unit_price: Money,
+    ) -> Self {
+        self.components.push(BundleComponent {
+            article_id: article_id.into(),
+            quantity,
+            unit_price: Some(unit_price),
+        });
+        self
+    }
+
+    /// Text of the row that introduces the bundle on an invoice.
+    pub fn header_text(&self) -> String {
+        format!("{} ({})", self.name, self.number)
+    }
+
+    /// Invoice rows for `quantity` bundles: the header text row, then one row
+    /// per component.
+    pub fn invoice_rows(&self, quantity: Money) -> Vec<InvoiceRow> {
+        let header = InvoiceRow {
+            text: Some(self.header_text()),
+            ..Default::default()
+        };
+        let components = self.components.iter().map(|c| InvoiceRow {
+            article_id: Some(c.article_id.clone()),
+            quantity: Some(c.quantity * quantity),
+            unit_price: c.unit_price,
+            ..Default::default()
+        });
+        std::iter::once(header).chain(components).collect()
+    }
+
+    /// Invoice draft rows for `quantity` bundles, laid out as
+    /// [`invoice_rows`](Self::invoice_rows).
+    pub fn draft_rows(&self, quantity: Money) -> Vec<CustomerInvoiceDraftRow> {
+        self.invoice_rows(quantity)
+            .into_iter()
+            .map(|row| CustomerInvoiceDraftRow {
+                article_id: row.article_id,
+                text: row.text,
+                unit_price: row.unit_price,
+                quantity: row.quantity,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    /// Number of bundles `rows` hold if they are this bundle's component
+    /// rows, in order and in proportion.
+    fn match_components(&self, rows: &[InvoiceRow]) -> Option<Money> {
+        if self.components.is_empty() || rows.len() < self.components.len() {
+            return None;
+        }
+        let first = &self.components[0];
+        let first_quantity = rows[0].quantity?;
+        if first.quantity == Money::default() {
+            return None;
+        }
+        for (component, row) in self.components.iter().zip(rows) {
+            let quantity = row.quantity?;
+            // Cross-multiplied to avoid rounding in the division
+            if row.article_id.as_ref() != Some(&component.article_id)
+                || quantity * first.quantity != first_quantity * component.quantity
+            {
+                return None;
+            }
+        }
+        Some(first_quantity / first.quantity)
+    }
+}
+
+/// Bundles by number.
+#[derive(Debug, Clone, Default)]
+pub struct BundleCatalog {
+    bundles: HashMap<String, Bundle>,
+}
+
+/// An invoice row, or the rows of one bundle, after
+/// [`BundleCatalog::collapse`].
+#[derive(Debug, Clone)]
+pub enum CollapsedRow<'a> {
+    /// Rows expanded from a bundle.
+    Bundle {
+        /// The bundle.
+        bundle: &'a Bundle,
+        /// Number of bundles sold.
+        quantity: Money,
+        /// Total of the component rows, excluding VAT.
+        amount: Money,
+    },
+    /// Any other row.
+    Row(&'a InvoiceRow),
+}
+
+impl BundleCatalog {
+    /// Create an empty catalog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a bundle, replacing one with the same number.
+    pub fn bundle(mut self, bundle: Bundle) -> Self {
+        self.bundles.insert(bundle.number.clone(), bundle);
+        self
+    }
+
+    /// Bundle by number.
+    pub fn get(&self, number: &str) -> Option<&Bundle> {
+        self.bundles.get(number)
+    }
+
+    /// Number of bundles.
+    pub fn len(&self) -> usize {
+        self.bundles.len()
+    }
+
+    /// Whether the catalog has no bundles.
+    pub fn is_empty(&self) -> bool {
+        self.bundles.is_empty()
+    }
+
+    /// Group the rows of bundles in `rows` back into one entry per bundle.
+    ///
+    /// A bundle is recognised by its header text row followed by its component
+    /// rows with quantities in proportion. Rows that were edited after
+    /// expansion no longer match and are returned as they are.
+    pub fn collapse<'a>(&'a self, rows: &'a [InvoiceRow]) -> Vec<CollapsedRow<'a>> {
+        let mut collapsed = Vec::new();
+        let mut i = 0;
+
+        while i < rows.len() {
+            let matched = self.header_bundle(&rows[i]).and_then(|bundle| {
+                let components = &rows[i + 1..];
+                let quantity = bundle.match_components(components)?;
+                let count = bundle.components.len();
+                let amount = components[..count].iter().map(row_amount).sum();
+                Some((bundle, quantity, amount, count))
+            });
+
+            match matched {
+                Some((bundle, quantity, amount, count)) => {
+                    collapsed.push(CollapsedRow::Bundle {
+                        bundle,
+                        quantity,
+                        amount,
+                    });
+                    i += count + 1;
+                }
+                None => {
+                    collapsed.push(CollapsedRow::Row(&rows[i]));
+                    i += 1;
+                }
+            }
+        }
+        collapsed
+    }
+
+    fn header_bundle(&self, row: &InvoiceRow) -> Option<&Bundle> {
+        if row.article_id.is_some() {
+            return None;
+        }
+        let text = row.text.as_deref()?.strip_suffix(')')?;
+        let number = &text[text.rfind('(')? + 1..];
+        self.get(number)
+            .filter(|bundle| row.text.as_deref() == Some(bundle.header_text().as_str()))
+    }
+}
+
+/// Row amount excluding VAT, computed from price, quantity and discount when
+/// the API has not filled in the total.
+pub(crate) fn row_amount(row: &InvoiceRow) -> Money {
+    row.total_amount.unwrap_or_else(|| {
+        let gross = row.unit_price.unwrap_or_default() * row.quantity.unwrap_or_default();
+        let discount = row.discount_percentage.unwrap_or_default();
+        gross - gross * discount / Money::from(100)
+    })
+}
+
+impl Invoice {
+    /// Append the rows for `quantity` of `bundle`.
+    pub fn add_bundle(mut self, bundle: &Bundle, quantity: Money) -> Self {
+        self.rows.extend(bundle.invoice_rows(quantity));
+        self
+    }
+}
+
+impl CustomerInvoiceDraft {
+    /// Append the rows for `quantity` of `bundle`.
+    pub fn add_bundle(mut self, bundle: &Bundle, quantity: Money) -> Self {
+        self.rows.extend(bundle.draft_rows(quantity));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package() -> Bundle {
+        Bundle::new("PKG-1", "Starter (small office)")
+            .component("art-install", Money::from(2))
+            .priced_component("art-support", Money::from(12), Money::from(99))
+    }
+
+    #[test]
+    fn test_collapse_round_trips_expanded_rows() {
+        let catalog = BundleCatalog::new().bundle(package());
+        let loose = InvoiceRow {
+            article_id: Some("art-install".into()),
+            quantity: Some(Money::from(1)),
+            unit_price: Some(Money::from(500)),
+            ..Default::default()
+        };
+        let mut rows = package().invoice_rows(Money::from(2));
+        rows[1].unit_price = Some(Money::from(500));
+        rows.push(loose.clone());
+
+        let collapsed = catalog.collapse(&rows);
+
+        assert_eq!(collapsed.len(), 2);
+        let CollapsedRow::Bundle {
+            bundle,
+            quantity,
+            amount,
+        } = &collapsed[0]
+        else {
+            panic!("expected a bundle, got {:?}", collapsed[0]);
+        };
+        assert_eq!(bundle.number, "PKG-1");
+        assert_eq!(*quantity, Money::from(2));
+        assert_eq!(*amount, Money::from(4 * 500 + 24 * 99));
+        assert!(matches!(collapsed[1], CollapsedRow::Row(row) if row.quantity == loose.quantity));
+    }
+
+    #[test]
+    fn test_edited_bundle_rows_are_not_collapsed() {
+        let catalog = BundleCatalog::new().bundle(package());
+        let mut rows = package().invoice_rows(Money::from(1));
+        rows[2].quantity = Some(Money::from(6));
+
+        let collapsed = catalog.collapse(&rows);
+
+        assert_eq!(collapsed.len(), 3);
+        assert!(collapsed
+            .iter()
+            .all(|row| matches!(row, CollapsedRow::Row(_))));
+    }
+
+    #[test]
+    fn test_draft_rows_match_invoice_rows() {
+        let draft = CustomerInvoiceDraft::default().add_bundle(&package(), Money::from(1));
+        assert_eq!(draft.rows.len(), 3);
+        assert_eq!(
+            draft.rows[0].text.as_deref(),
+            Some("Starter (small office) (PKG-1)")
+        );
+        assert_eq!(draft.rows[2].unit_price, Some(Money::from(99)));
+    }
+}
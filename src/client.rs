@@ -1,14 +1,23 @@
 //! Core HTTP client for the Spiris Bokföring och Fakturering API.
 
 use crate::auth::{AccessToken, OAuth2Config, OAuth2Handler};
-use crate::error::{Error, Result};
+use crate::clock::{Clock, SystemClock};
+use crate::error::{Error, ErrorContext, Result};
+use crate::http_cache::{CachedResponse, ResponseCache};
 use crate::middleware::{MiddlewareStack, RequestContext, RequestTimer, ResponseContext};
+use crate::redact::Redactor;
 use crate::retry::RetryConfig;
+use crate::types::Versioned;
+use bytes::Bytes;
 use reqwest::{header, Client as HttpClient, Method, RequestBuilder, Response, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
 use url::Url;
 
 #[cfg(feature = "tracing")]
@@ -18,27 +27,89 @@ use tracing::{debug, error, info, warn};
 /// Note: The API endpoint remains the same as the former Visma eAccounting.
 pub const DEFAULT_BASE_URL: &str = "https://eaccountingapi.vismaonline.com/v2/";
 
+/// Base URL of the sandbox API used with developer sandbox companies.
+pub const SANDBOX_BASE_URL: &str = "https://eaccountingapi-sandbox.test.vismaonline.com/v2/";
+
 /// Rate limit: 600 requests per minute per client per endpoint.
 pub const RATE_LIMIT_PER_MINUTE: u32 = 600;
 
+/// Language the API answers in.
+///
+/// Applies to error messages and to display texts such as VAT code and
+/// terms of payment descriptions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    Swedish,
+    English,
+}
+
+impl Language {
+    /// Language tag sent in the `Accept-Language` header.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Language::Swedish => "sv-SE",
+            Language::English => "en-GB",
+        }
+    }
+
+    /// Parse a language tag or header value such as `sv`, `en-US` or
+    /// `sv-SE,sv;q=0.9`, going by its first language.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.split([',', ';', '-', '_']).next()?.trim();
+        if primary.eq_ignore_ascii_case("sv") {
+            Some(Language::Swedish)
+        } else if primary.eq_ignore_ascii_case("en") {
+            Some(Language::English)
+        } else {
+            None
+        }
+    }
+}
+
+/// Function called with the new token each time the client refreshes it.
+///
+/// Set with [`ClientConfig::on_token_refresh`].
+#[derive(Clone)]
+pub struct TokenRefreshCallback(Arc<dyn Fn(&AccessToken) + Send + Sync>);
+
+impl std::fmt::Debug for TokenRefreshCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TokenRefreshCallback")
+    }
+}
+
 /// Configuration for the API client.
 #[derive(Debug, Clone)]
 pub struct ClientConfig {
     /// Base URL for the API.
     pub base_url: String,
 
+    /// API version segment, such as `v3`, replacing the one at the end of
+    /// [`base_url`](Self::base_url); the base URL is used as is when `None`.
+    pub api_version: Option<String>,
+
     /// User agent string.
     pub user_agent: String,
 
     /// Request timeout in seconds.
     pub timeout_seconds: u64,
 
+    /// Ask for gzip, Brotli or deflate compressed responses and decompress
+    /// them (requires `compression` feature).
+    #[cfg(feature = "compression")]
+    pub compression: bool,
+
     /// Retry configuration.
     pub retry_config: RetryConfig,
 
-    /// Enable request/response logging.
+    /// Emit a `tracing` span per API call and per HTTP attempt (requires
+    /// `tracing` feature).
     pub enable_tracing: bool,
 
+    /// Log request bodies, masked by [`redactor`](Self::redactor), at
+    /// `DEBUG` level inside the call's span.
+    pub trace_bodies: bool,
+
     /// OAuth2 configuration for automatic token refresh.
     /// When set, the client will automatically refresh expired tokens.
     pub oauth_config: Option<OAuth2Config>,
@@ -49,20 +120,72 @@ pub struct ClientConfig {
 
     /// Middleware stack for request/response interception.
     pub middleware: MiddlewareStack,
+
+    /// Validate request bodies before create and update calls.
+    pub validate_requests: bool,
+
+    /// How long reference data loaded by
+    /// [`ReferenceDataEndpoint::load_all`](crate::endpoints::ReferenceDataEndpoint::load_all)
+    /// is reused, in seconds.
+    pub reference_data_ttl_seconds: u64,
+
+    /// Masks personal data in bodies and errors passed to tracing and
+    /// middleware.
+    pub redactor: Redactor,
+
+    /// Scopes the application needs. Requests fail with
+    /// [`Error::InsufficientScopes`] if the token was granted fewer.
+    pub required_scopes: Vec<String>,
+
+    /// Time source for token expiry, cache ages and retry backoff.
+    pub clock: Arc<dyn Clock>,
+
+    /// Preferred response language; the API's default when `None`.
+    pub language: Option<Language>,
+
+    /// Called after each token refresh with the new token.
+    pub on_token_refresh: Option<TokenRefreshCallback>,
+
+    /// HTTP client to send requests with, instead of one built from
+    /// [`timeout_seconds`](Self::timeout_seconds).
+    pub http_client: Option<HttpClient>,
+
+    /// Cache for revalidating GET responses; see [`crate::http_cache`].
+    pub response_cache: Option<Arc<dyn ResponseCache>>,
+
+    /// Cassette recording or replaying every HTTP attempt (requires `vcr`
+    /// feature); see [`crate::vcr`].
+    #[cfg(feature = "vcr")]
+    pub cassette: Option<crate::vcr::Cassette>,
 }
 
 impl Default for ClientConfig {
     fn default() -> Self {
         Self {
             base_url: DEFAULT_BASE_URL.to_string(),
+            api_version: None,
             user_agent: format!("spiris-bokforing-rust/{}", env!("CARGO_PKG_VERSION")),
             timeout_seconds: 30,
+            #[cfg(feature = "compression")]
+            compression: true,
             retry_config: RetryConfig::default(),
             enable_tracing: true,
+            trace_bodies: true,
             oauth_config: None,
             #[cfg(feature = "rate-limit")]
             rate_limit_config: None,
             middleware: MiddlewareStack::new(),
+            validate_requests: false,
+            reference_data_ttl_seconds: 900,
+            redactor: Redactor::default(),
+            required_scopes: Vec::new(),
+            clock: Arc::new(SystemClock),
+            language: None,
+            on_token_refresh: None,
+            http_client: None,
+            response_cache: None,
+            #[cfg(feature = "vcr")]
+            cassette: None,
         }
     }
 }
@@ -79,12 +202,64 @@ impl ClientConfig {
         self
     }
 
+    /// Call the API of `environment`.
+    ///
+    /// Sets [`base_url`](Self::base_url) to [`DEFAULT_BASE_URL`] or
+    /// [`SANDBOX_BASE_URL`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spiris::{ClientConfig, Environment};
+    ///
+    /// let config = ClientConfig::new().environment(Environment::Sandbox);
+    /// assert_eq!(config.base_url, spiris::client::SANDBOX_BASE_URL);
+    /// ```
+    pub fn environment(mut self, environment: crate::auth::Environment) -> Self {
+        self.base_url = environment.api_base_url().to_string();
+        self
+    }
+
+    /// Call version `version` of the API, e.g. `v3`, in whichever
+    /// environment or base URL is configured.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spiris::{AccessToken, Client, ClientConfig, Environment};
+    ///
+    /// let config = ClientConfig::new()
+    ///     .api_version("v3")
+    ///     .environment(Environment::Sandbox);
+    /// let client = Client::with_config(AccessToken::new("token".to_string(), 3600, None), config);
+    /// assert_eq!(
+    ///     client.base_url(),
+    ///     "https://eaccountingapi-sandbox.test.vismaonline.com/v3/"
+    /// );
+    /// ```
+    pub fn api_version(mut self, version: impl Into<String>) -> Self {
+        self.api_version = Some(version.into());
+        self
+    }
+
     /// Set the request timeout.
     pub fn timeout_seconds(mut self, seconds: u64) -> Self {
         self.timeout_seconds = seconds;
         self
     }
 
+    /// Ask for compressed responses, or turn compression off.
+    ///
+    /// On by default. Large lists such as invoices shrink several times
+    /// over gzip or Brotli; turning it off can help when inspecting traffic
+    /// with a proxy. Requires the `compression` feature. Browsers always
+    /// negotiate compression themselves, so this has no effect on wasm.
+    #[cfg(feature = "compression")]
+    pub fn compression(mut self, enable: bool) -> Self {
+        self.compression = enable;
+        self
+    }
+
     /// Set the retry configuration.
     pub fn retry_config(mut self, retry_config: RetryConfig) -> Self {
         self.retry_config = retry_config;
@@ -92,24 +267,81 @@ impl ClientConfig {
     }
 
     /// Enable or disable tracing.
+    ///
+    /// Each API call gets an `api_request` span with `method`, `path`,
+    /// `status`, `duration_ms`, `attempts` and the API's `correlation_id`;
+    /// each HTTP attempt within it gets an `api_attempt` span with the
+    /// `attempt` number and that attempt's status, duration and correlation
+    /// id.
     pub fn enable_tracing(mut self, enable: bool) -> Self {
         self.enable_tracing = enable;
         self
     }
 
+    /// Log request bodies in the call's span, or leave them out.
+    ///
+    /// Logged bodies are masked by the [`redactor`](Self::redactor).
+    pub fn trace_bodies(mut self, enable: bool) -> Self {
+        self.trace_bodies = enable;
+        self
+    }
+
     /// Set OAuth2 configuration for automatic token refresh.
     ///
     /// When configured, the client will automatically refresh expired tokens
-    /// using the refresh token, if available.
+    /// using the refresh token, if available. A request rejected with 401
+    /// Unauthorized is retried once after refreshing the token.
     pub fn oauth_config(mut self, oauth_config: OAuth2Config) -> Self {
         self.oauth_config = Some(oauth_config);
         self
     }
 
+    /// Send requests, including token refreshes, with `http_client`.
+    ///
+    /// Use this to configure proxies, TLS roots, connection pools or DNS
+    /// overrides. [`timeout_seconds`](Self::timeout_seconds) and
+    /// `compression` are ignored; set them on the client's builder instead.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use spiris::ClientConfig;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let http_client = reqwest::Client::builder()
+    ///     .proxy(reqwest::Proxy::https("http://proxy.example.com:3128")?)
+    ///     .timeout(std::time::Duration::from_secs(30))
+    ///     .build()?;
+    /// let config = ClientConfig::new().with_http_client(http_client);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_http_client(mut self, http_client: HttpClient) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Keep GET responses in `cache` and revalidate them with the API.
+    ///
+    /// See [`crate::http_cache`].
+    pub fn response_cache(mut self, cache: impl ResponseCache + 'static) -> Self {
+        self.response_cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Record HTTP interactions to `cassette`, or replay them from it.
+    ///
+    /// See [`crate::vcr`].
+    #[cfg(feature = "vcr")]
+    pub fn cassette(mut self, cassette: crate::vcr::Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
     /// Set rate limiting configuration.
     ///
     /// When configured, the client will limit request rates to avoid
-    /// exceeding API quotas.
+    /// exceeding API quotas. Clones of the client share one limiter.
     #[cfg(feature = "rate-limit")]
     pub fn rate_limit_config(mut self, config: crate::rate_limit::RateLimitConfig) -> Self {
         self.rate_limit_config = Some(config);
@@ -141,6 +373,149 @@ impl ClientConfig {
         self.middleware = stack;
         self
     }
+
+    /// Validate request bodies before they are sent.
+    ///
+    /// When enabled, creating or updating a type implementing
+    /// [`Validate`](crate::validation::Validate) fails with
+    /// [`Error::Validation`](crate::Error::Validation) listing every problem
+    /// instead of making the request.
+    pub fn validate_requests(mut self, enable: bool) -> Self {
+        self.validate_requests = enable;
+        self
+    }
+
+    /// Set how long cached reference data is reused.
+    ///
+    /// Zero disables the cache.
+    pub fn reference_data_ttl_seconds(mut self, seconds: u64) -> Self {
+        self.reference_data_ttl_seconds = seconds;
+        self
+    }
+
+    /// Set which fields are masked in logged bodies and errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spiris::redact::Redactor;
+    /// use spiris::ClientConfig;
+    ///
+    /// let config = ClientConfig::new().redactor(Redactor::default().field("YourReference"));
+    /// ```
+    pub fn redactor(mut self, redactor: Redactor) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
+    /// Declare the scopes the application needs.
+    ///
+    /// The access token is checked when the client first uses it and after
+    /// every refresh, so a token granted fewer scopes fails with
+    /// [`Error::InsufficientScopes`] naming them, rather than with a 403 from
+    /// whichever endpoint happens to need them. Tokens whose scopes are
+    /// unknown are not checked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spiris::ClientConfig;
+    ///
+    /// let config = ClientConfig::new().required_scopes(["ea:api", "ea:sales"]);
+    /// ```
+    pub fn required_scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.required_scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the time source.
+    ///
+    /// Tests can pass a [`MockClock`](crate::clock::MockClock) to expire
+    /// tokens and cached data, and to skip retry backoff, without waiting.
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Ask the API to answer in `language`.
+    ///
+    /// Error messages and display texts come back in this language, and
+    /// [`ApiErrorResponse::language`](crate::ApiErrorResponse::language)
+    /// records the language an error message is in.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spiris::{ClientConfig, Language};
+    ///
+    /// let config = ClientConfig::new().language(Language::English);
+    /// ```
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// Call `callback` with the new token whenever the client refreshes it.
+    ///
+    /// Refresh tokens are usually single use, so an application that keeps
+    /// tokens between runs must save the one it is given here. The callback
+    /// runs while concurrent requests wait for the refresh, so it should be
+    /// quick; hand slow work such as database writes to another task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spiris::ClientConfig;
+    ///
+    /// let config = ClientConfig::new().on_token_refresh(|token| {
+    ///     let json = serde_json::to_string(token).unwrap();
+    ///     std::fs::write("token.json", json).ok();
+    /// });
+    /// ```
+    pub fn on_token_refresh(
+        mut self,
+        callback: impl Fn(&AccessToken) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_token_refresh = Some(TokenRefreshCallback(Arc::new(callback)));
+        self
+    }
+}
+
+/// Overrides for the calls made through one client handle.
+///
+/// Set with [`Client::with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Timeout for each HTTP attempt, instead of
+    /// [`ClientConfig::timeout_seconds`].
+    pub timeout: Option<Duration>,
+
+    /// Token that aborts the call, including retries and token refresh,
+    /// with [`Error::Cancelled`] when cancelled.
+    pub cancellation: Option<CancellationToken>,
+}
+
+impl RequestOptions {
+    /// Create options that change nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the timeout for each HTTP attempt.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Abort calls when `token` is cancelled.
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
 }
 
 /// Main API client for Spiris Bokföring och Fakturering.
@@ -148,6 +523,14 @@ impl ClientConfig {
 /// The client handles authentication, rate limiting, and HTTP communication
 /// with the Spiris API.
 ///
+/// With a refresh token and [`ClientConfig::oauth_config`], the access token
+/// is refreshed shortly before it expires and when the API rejects it, so a
+/// long-running service can keep using the same client.
+///
+/// Cloning is cheap: clones share the connection pool, token, rate limiter
+/// and caches, so there is no need to wrap it in `Arc`; hand a clone to each
+/// task. A token refreshed through one clone is used by all of them.
+///
 /// # Example
 ///
 /// ```no_run
@@ -164,16 +547,41 @@ impl ClientConfig {
 /// ```
 #[derive(Clone)]
 pub struct Client {
+    inner: Arc<ClientInner>,
+    options: RequestOptions,
+}
+
+/// State shared by all clones of a [`Client`].
+struct ClientInner {
     http_client: HttpClient,
     config: ClientConfig,
-    access_token: Arc<RwLock<AccessToken>>,
+    access_token: RwLock<AccessToken>,
     /// Mutex to prevent concurrent token refresh operations.
-    refresh_lock: Arc<Mutex<()>>,
+    refresh_lock: Mutex<()>,
     /// Rate limiter for API requests (requires `rate-limit` feature).
     #[cfg(feature = "rate-limit")]
     rate_limiter: Option<crate::rate_limit::ApiRateLimiter>,
     /// Middleware stack for request/response interception.
     middleware: MiddlewareStack,
+    reference_data: crate::endpoints::reference_data::ReferenceDataCache,
+    /// Quota from the rate limit headers of the latest response.
+    rate_limit_status: RwLock<Option<crate::retry::RateLimitStatus>>,
+}
+
+/// `base_url` with its trailing version segment (`v2`) replaced by
+/// `version`, or with `version` appended if it has none.
+fn with_api_version(base_url: &str, version: &str) -> String {
+    let base = base_url.trim_end_matches('/');
+    let is_version = |segment: &str| {
+        segment.len() > 1
+            && segment.starts_with('v')
+            && segment[1..].chars().all(|c| c.is_ascii_digit())
+    };
+    let base = match base.rsplit_once('/') {
+        Some((rest, last)) if is_version(last) => rest,
+        _ => base,
+    };
+    format!("{}/{}/", base, version.trim_matches('/'))
 }
 
 impl Client {
@@ -183,11 +591,24 @@ impl Client {
     }
 
     /// Create a new API client with custom configuration.
-    pub fn with_config(access_token: AccessToken, config: ClientConfig) -> Self {
-        let http_client = HttpClient::builder()
-            .timeout(std::time::Duration::from_secs(config.timeout_seconds))
-            .build()
-            .expect("Failed to build HTTP client");
+    pub fn with_config(access_token: AccessToken, mut config: ClientConfig) -> Self {
+        if let Some(version) = &config.api_version {
+            config.base_url = with_api_version(&config.base_url, version);
+        }
+        // The fetch backend has no client-wide timeout; see `build_request`
+        #[cfg(not(target_arch = "wasm32"))]
+        let http_client = config.http_client.clone().unwrap_or_else(|| {
+            let builder = HttpClient::builder()
+                .timeout(std::time::Duration::from_secs(config.timeout_seconds));
+            #[cfg(feature = "compression")]
+            let builder = builder
+                .gzip(config.compression)
+                .brotli(config.compression)
+                .deflate(config.compression);
+            builder.build().expect("Failed to build HTTP client")
+        });
+        #[cfg(target_arch = "wasm32")]
+        let http_client = config.http_client.clone().unwrap_or_default();
 
         #[cfg(feature = "rate-limit")]
         let rate_limiter = config
@@ -198,13 +619,45 @@ impl Client {
         let middleware = config.middleware.clone();
 
         Self {
-            http_client,
-            config,
-            access_token: Arc::new(RwLock::new(access_token)),
-            refresh_lock: Arc::new(Mutex::new(())),
-            #[cfg(feature = "rate-limit")]
-            rate_limiter,
-            middleware,
+            inner: Arc::new(ClientInner {
+                http_client,
+                config,
+                access_token: RwLock::new(access_token),
+                refresh_lock: Mutex::new(()),
+                #[cfg(feature = "rate-limit")]
+                rate_limiter,
+                middleware,
+                reference_data: Default::default(),
+                rate_limit_status: RwLock::new(None),
+            }),
+            options: RequestOptions::default(),
+        }
+    }
+
+    /// A handle to this client whose calls use `options`.
+    ///
+    /// The handle shares everything else with this client, and calls made
+    /// through this client are unaffected.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use spiris::{CancellationToken, Client, RequestOptions};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example(client: Client) -> spiris::Result<()> {
+    /// let cancel = CancellationToken::new();
+    /// let options = RequestOptions::new()
+    ///     .timeout(Duration::from_secs(120))
+    ///     .cancellation(cancel.clone());
+    /// let invoices = client.with_options(options).invoices().list(None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_options(&self, options: RequestOptions) -> Client {
+        Client {
+            inner: Arc::clone(&self.inner),
+            options,
         }
     }
 
@@ -212,18 +665,55 @@ impl Client {
     ///
     /// This is useful when refreshing expired tokens.
     pub fn set_access_token(&self, token: AccessToken) {
-        let mut access_token = self.access_token.write().unwrap();
+        let mut access_token = self.inner.access_token.write().unwrap();
         *access_token = token;
     }
 
     /// Get the current access token.
     pub fn get_access_token(&self) -> AccessToken {
-        self.access_token.read().unwrap().clone()
+        self.inner.access_token.read().unwrap().clone()
+    }
+
+    /// Base URL requests are sent to.
+    pub fn base_url(&self) -> &str {
+        &self.inner.config.base_url
     }
 
     /// Check if the current access token is expired.
     pub fn is_token_expired(&self) -> bool {
-        self.access_token.read().unwrap().is_expired()
+        self.inner
+            .access_token
+            .read()
+            .unwrap()
+            .is_expired_at(self.inner.config.clock.now())
+    }
+
+    /// Request quota the API reported on its latest response.
+    ///
+    /// `None` until a response carries `X-RateLimit-*` headers. Shared by
+    /// all clones of the client.
+    pub fn rate_limit_status(&self) -> Option<crate::retry::RateLimitStatus> {
+        self.inner.rate_limit_status.read().unwrap().clone()
+    }
+
+    /// The client's time source.
+    pub fn clock(&self) -> &dyn Clock {
+        self.inner.config.clock.as_ref()
+    }
+
+    /// Check the current access token against
+    /// [`ClientConfig::required_scopes`].
+    ///
+    /// Requests do this too; call it after loading a token to fail early.
+    pub fn check_scopes(&self) -> Result<()> {
+        if self.inner.config.required_scopes.is_empty() {
+            return Ok(());
+        }
+        self.inner
+            .access_token
+            .read()
+            .unwrap()
+            .require_scopes(&self.inner.config.required_scopes)
     }
 
     /// Ensure the access token is valid, refreshing if necessary.
@@ -234,10 +724,16 @@ impl Client {
     /// 3. A refresh token is available
     ///
     /// If any of these conditions are not met and the token is expired,
-    /// returns `Error::TokenExpired`.
+    /// returns `Error::TokenExpired`. A token lacking required scopes
+    /// returns `Error::InsufficientScopes`.
     ///
     /// Uses a mutex to prevent multiple concurrent refresh operations.
     async fn ensure_valid_token(&self) -> Result<()> {
+        self.refresh_if_expired().await?;
+        self.check_scopes()
+    }
+
+    async fn refresh_if_expired(&self) -> Result<()> {
         // Quick check without lock - if token is valid, we're done
         if !self.is_token_expired() {
             return Ok(());
@@ -246,18 +742,42 @@ impl Client {
         #[cfg(feature = "tracing")]
         debug!("Token expired, attempting refresh");
 
-        // Token is expired, acquire refresh lock to prevent concurrent refreshes
-        let _guard = self.refresh_lock.lock().await;
+        self.refresh_access_token(None).await
+    }
+
+    /// Whether a rejected token can be replaced by refreshing it.
+    fn can_refresh(&self) -> bool {
+        self.inner.config.oauth_config.is_some()
+            && self
+                .inner
+                .access_token
+                .read()
+                .unwrap()
+                .refresh_token
+                .is_some()
+    }
+
+    /// Refresh the access token.
+    ///
+    /// With `rejected`, the token the API refused is refreshed even if it has
+    /// not expired yet; without, only an expired token is.
+    async fn refresh_access_token(&self, rejected: Option<&str>) -> Result<()> {
+        // Acquire refresh lock to prevent concurrent refreshes
+        let _guard = self.inner.refresh_lock.lock().await;
 
         // Double-check after acquiring lock (another request may have refreshed)
-        if !self.is_token_expired() {
+        let refreshed = match rejected {
+            Some(rejected) => self.inner.access_token.read().unwrap().token != rejected,
+            None => !self.is_token_expired(),
+        };
+        if refreshed {
             #[cfg(feature = "tracing")]
             debug!("Token was refreshed by another request");
             return Ok(());
         }
 
         // Check if we can refresh
-        let oauth_config = match &self.config.oauth_config {
+        let oauth_config = match &self.inner.config.oauth_config {
             Some(config) => config.clone(),
             None => {
                 #[cfg(feature = "tracing")]
@@ -266,10 +786,10 @@ impl Client {
             }
         };
 
-        let refresh_token = {
-            let token = self.access_token.read().unwrap();
+        let (refresh_token, scopes) = {
+            let token = self.inner.access_token.read().unwrap();
             match &token.refresh_token {
-                Some(rt) => rt.clone(),
+                Some(rt) => (rt.clone(), token.scopes.clone()),
                 None => {
                     #[cfg(feature = "tracing")]
                     warn!("Token expired but no refresh token available");
@@ -282,21 +802,30 @@ impl Client {
         #[cfg(feature = "tracing")]
         info!("Refreshing access token");
 
-        let handler = OAuth2Handler::new(oauth_config)?;
-        let new_token = handler.refresh_token(refresh_token).await?;
+        let handler = OAuth2Handler::new(oauth_config)?
+            .with_clock(Arc::clone(&self.inner.config.clock))
+            .with_http_client(self.inner.http_client.clone());
+        let mut new_token = handler.refresh_token(refresh_token).await?;
+        // A refresh response without scopes keeps the original grant
+        if new_token.scopes.is_none() {
+            new_token.scopes = scopes;
+        }
 
         #[cfg(feature = "tracing")]
         info!("Token refreshed successfully");
 
         // Update the token
-        self.set_access_token(new_token);
+        self.set_access_token(new_token.clone());
+        if let Some(callback) = &self.inner.config.on_token_refresh {
+            (callback.0)(&new_token);
+        }
 
         Ok(())
     }
 
     /// Build a URL for an API endpoint.
     fn build_url(&self, path: &str) -> Result<Url> {
-        let base = Url::parse(&self.config.base_url)?;
+        let base = Url::parse(&self.inner.config.base_url)?;
         // Strip leading "/" to ensure proper joining with base URL
         let path = path.strip_prefix('/').unwrap_or(path);
         let url = base.join(path)?;
@@ -305,44 +834,195 @@ impl Client {
 
     /// Build a request with authentication headers.
     fn build_request(&self, method: Method, url: Url) -> Result<RequestBuilder> {
-        let token = self.access_token.read().unwrap();
+        let token = self.inner.access_token.read().unwrap();
 
-        if token.is_expired() {
+        if token.is_expired_at(self.inner.config.clock.now()) {
             return Err(Error::TokenExpired);
         }
 
-        let request = self
+        let mut request = self
+            .inner
             .http_client
             .request(method, url)
             .header(header::AUTHORIZATION, token.authorization_header())
-            .header(header::USER_AGENT, &self.config.user_agent)
+            .header(header::USER_AGENT, &self.inner.config.user_agent)
             .header(header::ACCEPT, "application/json");
+        if let Some(language) = self.inner.config.language {
+            request = request.header(header::ACCEPT_LANGUAGE, language.tag());
+        }
+        if let Some(timeout) = self.options.timeout {
+            request = request.timeout(timeout);
+        }
+        #[cfg(target_arch = "wasm32")]
+        if self.options.timeout.is_none() && self.inner.config.http_client.is_none() {
+            request = request.timeout(Duration::from_secs(self.inner.config.timeout_seconds));
+        }
 
         Ok(request)
     }
 
+    /// Authenticate, build and execute a request to `path`, in the call's
+    /// `api_request` span.
+    ///
+    /// `prepare` adds the query and body; it runs again if the request is
+    /// retried after a 401 Unauthorized made the client refresh its token.
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<String>,
+        prepare: impl Fn(RequestBuilder) -> RequestBuilder,
+    ) -> Result<Response> {
+        let attempts = AtomicU32::new(0);
+        let call = self.dispatch(method.clone(), path, body, prepare, &attempts);
+
+        #[cfg(feature = "tracing")]
+        let span = match self.inner.config.enable_tracing {
+            true => tracing::info_span!(
+                "api_request",
+                method = method.as_str(),
+                path = path
+                    .split('?')
+                    .next()
+                    .unwrap_or_default()
+                    .trim_start_matches('/'),
+                status = tracing::field::Empty,
+                duration_ms = tracing::field::Empty,
+                attempts = tracing::field::Empty,
+                correlation_id = tracing::field::Empty,
+            ),
+            false => tracing::Span::none(),
+        };
+        #[cfg(feature = "tracing")]
+        let call = tracing::Instrument::instrument(call, span.clone());
+
+        #[cfg(feature = "tracing")]
+        let timer = RequestTimer::start();
+        let result = call.await;
+
+        #[cfg(feature = "tracing")]
+        {
+            span.record("duration_ms", timer.elapsed().as_millis() as u64);
+            span.record("attempts", attempts.load(Ordering::Relaxed));
+            record_outcome(&span, &result);
+        }
+        result
+    }
+
+    /// [`send`](Self::send) without the span; `attempts` counts HTTP
+    /// attempts, including retries and the one after a token refresh.
+    async fn dispatch(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<String>,
+        prepare: impl Fn(RequestBuilder) -> RequestBuilder,
+        attempts: &AtomicU32,
+    ) -> Result<Response> {
+        self.ensure_valid_token().await?;
+        let url = self.build_url(path)?;
+        let url_str = url.to_string();
+        let token = self.inner.access_token.read().unwrap().token.clone();
+        let request = prepare(self.build_request(method.clone(), url.clone())?);
+        let context = ErrorContext::new(method_name(&method), path);
+        let cache = self.cache_lookup(&method, &request).await;
+        let conditional = |request: RequestBuilder| match &cache {
+            Some((_, _, Some(cached))) => cached.conditional(request),
+            _ => request,
+        };
+
+        let result = match self
+            .execute_request(
+                method.as_str(),
+                &url_str,
+                body.clone(),
+                conditional(request),
+                &context,
+                attempts,
+            )
+            .await
+        {
+            Err(err)
+                if matches!(err.root(), Error::AuthError(message) if message == "Unauthorized")
+                    && self.can_refresh() =>
+            {
+                #[cfg(feature = "tracing")]
+                warn!("Access token rejected, refreshing and retrying once");
+
+                self.refresh_access_token(Some(&token)).await?;
+                self.check_scopes()?;
+                let request = prepare(self.build_request(method.clone(), url)?);
+                self.execute_request(
+                    method.as_str(),
+                    &url_str,
+                    body,
+                    conditional(request),
+                    &context,
+                    attempts,
+                )
+                .await
+            }
+            result => result,
+        };
+
+        match cache {
+            Some((cache, key, cached)) => {
+                let now = self.inner.config.clock.now();
+                crate::http_cache::store_or_replay(cache.as_ref(), &key, cached, result?, now).await
+            }
+            None => result,
+        }
+    }
+
+    /// The response cache, the key a GET `request` is cached under and its
+    /// entry, if the client has a cache.
+    async fn cache_lookup(
+        &self,
+        method: &Method,
+        request: &RequestBuilder,
+    ) -> Option<(Arc<dyn ResponseCache>, String, Option<CachedResponse>)> {
+        let cache = self.inner.config.response_cache.as_ref()?;
+        if method != Method::GET {
+            return None;
+        }
+        let request = request.try_clone()?.build().ok()?;
+        let key = crate::http_cache::cache_key(&request);
+        let cached = cache.get(&key).await;
+        Some((Arc::clone(cache), key, cached))
+    }
+
     /// Execute a request and handle the response with automatic retry on transient errors.
+    ///
+    /// `body` is the redacted request body, for tracing and middleware.
     async fn execute_request(
         &self,
         method: &str,
         url: &str,
+        body: Option<String>,
         request: RequestBuilder,
+        context: &ErrorContext,
+        attempts: &AtomicU32,
     ) -> Result<Response> {
         #[cfg(feature = "tracing")]
-        let span = tracing::info_span!("api_request", %method, %url);
-        #[cfg(feature = "tracing")]
-        let _guard = span.enter();
+        let traced = self.inner.config.enable_tracing;
 
         #[cfg(feature = "tracing")]
-        debug!("Sending API request");
+        match &body {
+            Some(body) if traced && self.inner.config.trace_bodies => {
+                debug!(%url, %body, "Sending API request")
+            }
+            _ if traced => debug!(%url, "Sending API request"),
+            _ => {}
+        }
 
         // Create middleware context
         let mut ctx = RequestContext::new(method, url);
+        ctx.body = body;
         let timer = RequestTimer::start();
 
         // Process middleware on_request handlers
-        if !self.middleware.is_empty() {
-            self.middleware.process_request(&mut ctx)?;
+        if !self.inner.middleware.is_empty() {
+            self.inner.middleware.process_request(&mut ctx)?;
         }
 
         // Apply any headers added by middleware
@@ -351,21 +1031,18 @@ impl Client {
             .iter()
             .fold(request, |req, (k, v)| req.header(k.as_str(), v.as_str()));
 
-        // Apply rate limiting if configured
-        #[cfg(feature = "rate-limit")]
-        if let Some(ref limiter) = self.rate_limiter {
-            #[cfg(feature = "tracing")]
-            debug!("Waiting for rate limiter");
-            limiter.acquire().await;
-        }
-
         // Execute the request
-        let result = self.execute_request_inner(request).await;
+        let result = self.execute_request_inner(request, context, attempts).await;
         let elapsed = timer.elapsed();
+        let error = result
+            .as_ref()
+            .err()
+            .map(|err| self.inner.config.redactor.redact(&err.to_string()));
 
         // Log the result
         #[cfg(feature = "tracing")]
         match &result {
+            _ if !traced => {}
             Ok(response) => {
                 info!(
                     status = response.status().as_u16(),
@@ -373,9 +1050,9 @@ impl Client {
                     "API request completed"
                 );
             }
-            Err(err) => {
+            Err(_) => {
                 error!(
-                    error = %err,
+                    error = error.as_deref().unwrap_or_default(),
                     duration_ms = elapsed.as_millis() as u64,
                     "API request failed"
                 );
@@ -383,7 +1060,7 @@ impl Client {
         }
 
         // Process middleware on_response handlers
-        if !self.middleware.is_empty() {
+        if !self.inner.middleware.is_empty() {
             let response_ctx = match &result {
                 Ok(response) => ResponseContext::new(
                     method.to_string(),
@@ -392,26 +1069,97 @@ impl Client {
                     elapsed,
                     ctx.extensions,
                 ),
-                Err(err) => ResponseContext::with_error(
+                Err(_) => ResponseContext::with_error(
                     method.to_string(),
                     url.to_string(),
                     elapsed,
-                    err.to_string(),
+                    error.unwrap_or_default(),
                     ctx.extensions,
                 ),
             };
-            self.middleware.process_response(&response_ctx);
+            self.inner.middleware.process_response(&response_ctx);
         }
 
         result
     }
 
+    /// Send one attempt of a request in its `api_attempt` span.
+    ///
+    /// Every attempt goes through here so retries count against the rate
+    /// limit and are numbered in `attempts`.
+    async fn send_throttled(
+        &self,
+        request: RequestBuilder,
+        attempts: &AtomicU32,
+    ) -> Result<Response> {
+        #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+        let attempt = attempts.fetch_add(1, Ordering::Relaxed) + 1;
+        let send = self.send_attempt(request);
+
+        #[cfg(feature = "tracing")]
+        let span = match self.inner.config.enable_tracing {
+            true => tracing::debug_span!(
+                "api_attempt",
+                attempt,
+                status = tracing::field::Empty,
+                duration_ms = tracing::field::Empty,
+                correlation_id = tracing::field::Empty,
+            ),
+            false => tracing::Span::none(),
+        };
+        #[cfg(feature = "tracing")]
+        let send = tracing::Instrument::instrument(send, span.clone());
+
+        #[cfg(feature = "tracing")]
+        let timer = RequestTimer::start();
+        let result = send.await;
+
+        #[cfg(feature = "tracing")]
+        {
+            span.record("duration_ms", timer.elapsed().as_millis() as u64);
+            if let Ok(response) = &result {
+                span.record("status", response.status().as_u16());
+                if let Some(id) = correlation_id(response.headers()) {
+                    span.record("correlation_id", id.as_str());
+                }
+            }
+        }
+        result
+    }
+
+    /// Wait for the rate limiter, if configured, then send `request`.
+    async fn send_attempt(&self, request: RequestBuilder) -> Result<Response> {
+        // A replayed cassette answers without the network, so skips the limiter
+        #[cfg(feature = "vcr")]
+        if let Some(cassette) = &self.inner.config.cassette {
+            if cassette.mode() == crate::vcr::VcrMode::Replay {
+                return cassette.send(request).await;
+            }
+        }
+        #[cfg(feature = "rate-limit")]
+        if let Some(ref limiter) = self.inner.rate_limiter {
+            #[cfg(feature = "tracing")]
+            debug!("Waiting for rate limiter");
+            limiter.acquire().await;
+        }
+        #[cfg(feature = "vcr")]
+        if let Some(cassette) = &self.inner.config.cassette {
+            return cassette.send(request).await;
+        }
+        Ok(request.send().await?)
+    }
+
     /// Inner request execution with retry logic.
-    async fn execute_request_inner(&self, request: RequestBuilder) -> Result<Response> {
+    async fn execute_request_inner(
+        &self,
+        request: RequestBuilder,
+        context: &ErrorContext,
+        attempts: &AtomicU32,
+    ) -> Result<Response> {
         // If retries are disabled, just send directly
-        if self.config.retry_config.max_retries == 0 {
-            let response = request.send().await?;
-            return self.handle_response(response).await;
+        if self.inner.config.retry_config.max_retries == 0 {
+            let response = self.send_throttled(request, attempts).await?;
+            return self.handle_response(response, context).await;
         }
 
         // Clone the request for potential retries
@@ -420,70 +1168,141 @@ impl Client {
             Error::InvalidRequest("Request body cannot be cloned for retry".into())
         })?;
 
-        // Try the first request
-        let response = request.send().await?;
-        let first_result = self.handle_response(response).await;
+        // Try the first request; the retry deadline includes it
+        let started = self.inner.config.clock.now();
+        let response = self.send_throttled(request, attempts).await?;
+        let first_result = self.handle_response(response, context).await;
 
-        match first_result {
-            Ok(response) => Ok(response),
-            Err(err) if crate::retry::is_retryable_error(&err) => {
-                #[cfg(feature = "tracing")]
-                warn!(error = %err, "Request failed, will retry");
-
-                // Use retry logic for retryable errors
-                crate::retry::retry_request(&self.config.retry_config, || async {
-                    // We need to rebuild the request each time
-                    let url = request_clone
-                        .try_clone()
-                        .ok_or_else(|| Error::InvalidRequest("Request cannot be cloned".into()))?;
-                    let response = url.send().await?;
-                    self.handle_response(response).await
-                })
-                .await
+        #[cfg(feature = "tracing")]
+        if let Err(err) = &first_result {
+            if self.inner.config.retry_config.should_retry(err) {
+                let error = self.inner.config.redactor.redact(&err.to_string());
+                warn!(%error, "Request failed, will retry");
             }
-            Err(err) => Err(err),
         }
+
+        crate::retry::retry_request_after(
+            &self.inner.config.retry_config,
+            self.inner.config.clock.as_ref(),
+            started,
+            first_result,
+            self.inner.config.retry_config.max_retries,
+            || async {
+                // We need to rebuild the request each time
+                let url = request_clone
+                    .try_clone()
+                    .ok_or_else(|| Error::InvalidRequest("Request cannot be cloned".into()))?;
+                let response = self.send_throttled(url, attempts).await?;
+                self.handle_response(response, context).await
+            },
+        )
+        .await
     }
 
     /// Handle API response, checking for errors.
     ///
     /// This method parses error responses into structured `ApiErrorResponse` objects
     /// when possible, providing access to error codes and field-level validation errors.
-    async fn handle_response(&self, response: Response) -> Result<Response> {
+    /// A 400 Bad Request becomes an [`Error::ApiError`] so its validation errors
+    /// can be shown to the user. Errors carry `context` with the full URL and
+    /// the API's correlation id added.
+    async fn handle_response(
+        &self,
+        response: Response,
+        context: &ErrorContext,
+    ) -> Result<Response> {
         let status = response.status();
+        let now = self.inner.config.clock.now();
+        if let Some(quota) = crate::retry::RateLimitStatus::from_headers(response.headers(), now) {
+            *self.inner.rate_limit_status.write().unwrap() = Some(quota);
+        }
 
-        match status {
-            StatusCode::OK | StatusCode::CREATED | StatusCode::NO_CONTENT => Ok(response),
-            StatusCode::UNAUTHORIZED => Err(Error::AuthError("Unauthorized".to_string())),
-            StatusCode::FORBIDDEN => Err(Error::AuthError("Forbidden".to_string())),
+        // 304 only answers a request made conditional by the response cache
+        if matches!(
+            status,
+            StatusCode::OK
+                | StatusCode::CREATED
+                | StatusCode::NO_CONTENT
+                | StatusCode::NOT_MODIFIED
+        ) {
+            return Ok(response);
+        }
+
+        let context = context
+            .clone()
+            .url(response.url().as_str())
+            .correlation_id(correlation_id(response.headers()));
+        let error = match status {
+            StatusCode::UNAUTHORIZED => Error::AuthError("Unauthorized".to_string()),
+            StatusCode::FORBIDDEN => Error::AuthError("Forbidden".to_string()),
             StatusCode::NOT_FOUND => {
                 let raw_body = response.text().await.unwrap_or_default();
-                Err(Error::NotFound(raw_body))
+                Error::NotFound(raw_body)
             }
             StatusCode::TOO_MANY_REQUESTS => {
+                let retry_after = crate::retry::retry_after(response.headers(), now);
                 let raw_body = response.text().await.unwrap_or_default();
-                Err(Error::RateLimitExceeded(raw_body))
+                Error::RateLimitExceeded {
+                    message: raw_body,
+                    retry_after,
+                }
             }
-            StatusCode::BAD_REQUEST => {
+            StatusCode::PRECONDITION_FAILED => {
                 let raw_body = response.text().await.unwrap_or_default();
-                Err(Error::InvalidRequest(raw_body))
+                Error::Conflict(raw_body)
             }
             _ => {
+                // The API may answer in another language than asked for
+                let language = response
+                    .headers()
+                    .get(header::CONTENT_LANGUAGE)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(Language::from_tag)
+                    .or(self.inner.config.language);
                 let raw_body = response.text().await.unwrap_or_default();
-                Err(Error::from_api_response(status.as_u16(), raw_body))
+                Error::from_api_response_in(status.as_u16(), raw_body, language)
             }
+        };
+        Err(error.with_context(context))
+    }
+
+    /// Serialize and redact a request body if tracing or middleware records it.
+    fn logged_body<B: Serialize>(&self, body: &B) -> Option<String> {
+        #[cfg(feature = "tracing")]
+        let traced = self.inner.config.enable_tracing
+            && self.inner.config.trace_bodies
+            && tracing::enabled!(tracing::Level::DEBUG);
+        #[cfg(not(feature = "tracing"))]
+        let traced = false;
+
+        if !traced && self.inner.middleware.is_empty() {
+            return None;
+        }
+        let body = serde_json::to_string(body).ok()?;
+        Some(self.inner.config.redactor.redact(&body))
+    }
+
+    /// Run `call`, unless the handle's cancellation token fires first.
+    async fn cancellable<T>(&self, call: impl Future<Output = Result<T>>) -> Result<T> {
+        match &self.options.cancellation {
+            Some(token) => tokio::select! {
+                biased;
+                _ = token.cancelled() => Err(Error::Cancelled),
+                result = call => result,
+            },
+            None => call.await,
         }
     }
 
     /// Make a GET request to an API endpoint.
     pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
-        self.ensure_valid_token().await?;
-        let url = self.build_url(path)?;
-        let url_str = url.to_string();
-        let request = self.build_request(Method::GET, url)?;
-        let response = self.execute_request("GET", &url_str, request).await?;
-        let data = response.json().await?;
-        Ok(data)
+        self.cancellable(async {
+            let response = self.send(Method::GET, path, None, |r| r).await?;
+            let data = response.json().await?;
+            Ok(data)
+        })
+        .await
+        .map_err(|e: Error| e.with_context(ErrorContext::new("GET", path)))
     }
 
     /// Make a GET request with query parameters.
@@ -492,65 +1311,242 @@ impl Client {
         path: &str,
         params: &P,
     ) -> Result<T> {
-        self.ensure_valid_token().await?;
-        let url = self.build_url(path)?;
-        let url_str = url.to_string();
-        let request = self.build_request(Method::GET, url)?.query(params);
-        let response = self.execute_request("GET", &url_str, request).await?;
-        let data = response.json().await?;
-        Ok(data)
+        self.cancellable(async {
+            let response = self
+                .send(Method::GET, path, None, |r| r.query(params))
+                .await?;
+            let data = response.json().await?;
+            Ok(data)
+        })
+        .await
+        .map_err(|e: Error| {
+            e.with_context(ErrorContext::new("GET", path).page(requested_page(params)))
+        })
+    }
+
+    /// Make a GET request with query parameters and return the response
+    /// unread, for callers that consume the body as a stream.
+    #[cfg(feature = "stream")]
+    pub(crate) async fn get_response_with_params<P: Serialize>(
+        &self,
+        path: &str,
+        params: &P,
+    ) -> Result<Response> {
+        self.cancellable(self.send(Method::GET, path, None, |r| r.query(params)))
+            .await
+            .map_err(|e: Error| {
+                e.with_context(ErrorContext::new("GET", path).page(requested_page(params)))
+            })
+    }
+
+    /// Whether [`ClientConfig::validate_requests`] is enabled.
+    pub(crate) fn validates_requests(&self) -> bool {
+        self.inner.config.validate_requests
+    }
+
+    /// Validate a request body if [`ClientConfig::validate_requests`] is enabled.
+    pub(crate) fn validate_request<T: crate::validation::Validate>(&self, body: &T) -> Result<()> {
+        if self.inner.config.validate_requests {
+            body.validate()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The reference data cache and how long its contents stay fresh.
+    pub(crate) fn reference_data_cache(
+        &self,
+    ) -> (
+        &crate::endpoints::reference_data::ReferenceDataCache,
+        std::time::Duration,
+    ) {
+        (
+            &self.inner.reference_data,
+            std::time::Duration::from_secs(self.inner.config.reference_data_ttl_seconds),
+        )
+    }
+
+    /// Make a GET request, keeping the response's `ETag`.
+    pub async fn get_versioned<T: DeserializeOwned>(&self, path: &str) -> Result<Versioned<T>> {
+        self.cancellable(async {
+            let response = self.send(Method::GET, path, None, |r| r).await?;
+            let etag = etag(&response);
+            let value = response.json().await?;
+            Ok(Versioned { value, etag })
+        })
+        .await
+        .map_err(|e: Error| e.with_context(ErrorContext::new("GET", path)))
+    }
+
+    /// Make a PUT request that only applies if the resource still has the
+    /// version in `body`, sending it as `If-Match`.
+    ///
+    /// Fails with [`Error::Conflict`] if the resource changed since. Without
+    /// an ETag in `body` the update is unconditional.
+    pub async fn put_if_match<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &Versioned<B>,
+    ) -> Result<Versioned<T>> {
+        self.cancellable(async {
+            let response = self
+                .send(Method::PUT, path, self.logged_body(&body.value), |r| {
+                    let r = r
+                        .header(header::CONTENT_TYPE, "application/json")
+                        .json(&body.value);
+                    match &body.etag {
+                        Some(etag) => r.header(header::IF_MATCH, etag),
+                        None => r,
+                    }
+                })
+                .await?;
+            let etag = etag(&response);
+            let value = response.json().await?;
+            Ok(Versioned { value, etag })
+        })
+        .await
+        .map_err(|e: Error| e.with_context(ErrorContext::new("PUT", path)))
     }
 
     /// Make a POST request to create a resource.
     pub async fn post<T: DeserializeOwned, B: Serialize>(&self, path: &str, body: &B) -> Result<T> {
-        self.ensure_valid_token().await?;
-        let url = self.build_url(path)?;
-        let url_str = url.to_string();
-        let request = self
-            .build_request(Method::POST, url)?
-            .header(header::CONTENT_TYPE, "application/json")
-            .json(body);
-        let response = self.execute_request("POST", &url_str, request).await?;
-        let data = response.json().await?;
-        Ok(data)
+        self.cancellable(async {
+            let response = self
+                .send(Method::POST, path, self.logged_body(body), |r| {
+                    r.header(header::CONTENT_TYPE, "application/json")
+                        .json(body)
+                })
+                .await?;
+            let data = response.json().await?;
+            Ok(data)
+        })
+        .await
+        .map_err(|e: Error| e.with_context(ErrorContext::new("POST", path)))
     }
 
     /// Make a PUT request to update a resource.
     pub async fn put<T: DeserializeOwned, B: Serialize>(&self, path: &str, body: &B) -> Result<T> {
-        self.ensure_valid_token().await?;
-        let url = self.build_url(path)?;
-        let url_str = url.to_string();
-        let request = self
-            .build_request(Method::PUT, url)?
-            .header(header::CONTENT_TYPE, "application/json")
-            .json(body);
-        let response = self.execute_request("PUT", &url_str, request).await?;
-        let data = response.json().await?;
-        Ok(data)
+        self.cancellable(async {
+            let response = self
+                .send(Method::PUT, path, self.logged_body(body), |r| {
+                    r.header(header::CONTENT_TYPE, "application/json")
+                        .json(body)
+                })
+                .await?;
+            let data = response.json().await?;
+            Ok(data)
+        })
+        .await
+        .map_err(|e: Error| e.with_context(ErrorContext::new("PUT", path)))
+    }
+
+    /// Make a PATCH request to change some fields of a resource.
+    pub async fn patch<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        self.cancellable(async {
+            let response = self
+                .send(Method::PATCH, path, self.logged_body(body), |r| {
+                    r.header(header::CONTENT_TYPE, "application/json")
+                        .json(body)
+                })
+                .await?;
+            let data = response.json().await?;
+            Ok(data)
+        })
+        .await
+        .map_err(|e: Error| e.with_context(ErrorContext::new("PATCH", path)))
     }
 
     /// Make a DELETE request to remove a resource.
     pub async fn delete(&self, path: &str) -> Result<()> {
-        self.ensure_valid_token().await?;
-        let url = self.build_url(path)?;
-        let url_str = url.to_string();
-        let request = self.build_request(Method::DELETE, url)?;
-        self.execute_request("DELETE", &url_str, request).await?;
-        Ok(())
+        self.cancellable(async {
+            self.send(Method::DELETE, path, None, |r| r).await?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: Error| e.with_context(ErrorContext::new("DELETE", path)))
     }
 
     /// Make a GET request that returns raw bytes (for binary data like PDFs).
     pub async fn get_bytes(&self, path: &str) -> Result<Vec<u8>> {
-        self.ensure_valid_token().await?;
-        let url = self.build_url(path)?;
-        let url_str = url.to_string();
-        let request = self.build_request(Method::GET, url)?;
-        let response = self.execute_request("GET", &url_str, request).await?;
-        let bytes = response.bytes().await?;
-        Ok(bytes.to_vec())
+        self.get_body(path).await.map(|bytes| bytes.to_vec())
+    }
+
+    /// Make a GET request and return the response body without copying it.
+    pub(crate) async fn get_body(&self, path: &str) -> Result<Bytes> {
+        self.cancellable(async {
+            let response = self.send(Method::GET, path, None, |r| r).await?;
+            Ok(response.bytes().await?)
+        })
+        .await
+        .map_err(|e: Error| e.with_context(ErrorContext::new("GET", path)))
     }
 }
 
+/// The `ETag` header of a response.
+fn etag(response: &Response) -> Option<String> {
+    response
+        .headers()
+        .get(header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Page number in serialized list parameters, for error context.
+/// Headers the API's request id may arrive in.
+const CORRELATION_HEADERS: [&str; 3] = ["x-correlation-id", "x-request-id", "request-id"];
+
+/// The API's id for a request, for support cases and log correlation.
+fn correlation_id(headers: &header::HeaderMap) -> Option<String> {
+    CORRELATION_HEADERS
+        .iter()
+        .find_map(|name| headers.get(*name)?.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Record the status and correlation id of a finished call in `span`.
+#[cfg(feature = "tracing")]
+fn record_outcome(span: &tracing::Span, result: &Result<Response>) {
+    let (status, id) = match result {
+        Ok(response) => (
+            Some(response.status().as_u16()),
+            correlation_id(response.headers()),
+        ),
+        Err(err) => (
+            err.status_code(),
+            err.context()
+                .and_then(|context| context.correlation_id.clone()),
+        ),
+    };
+    if let Some(status) = status {
+        span.record("status", status);
+    }
+    if let Some(id) = id {
+        span.record("correlation_id", id.as_str());
+    }
+}
+
+/// `method` as the static name [`ErrorContext`] records.
+fn method_name(method: &Method) -> &'static str {
+    match method.as_str() {
+        "GET" => "GET",
+        "POST" => "POST",
+        "PUT" => "PUT",
+        "DELETE" => "DELETE",
+        "PATCH" => "PATCH",
+        "HEAD" => "HEAD",
+        _ => "OTHER",
+    }
+}
+
+fn requested_page<P: Serialize>(params: &P) -> Option<u32> {
+    let params = serde_json::to_value(params).ok()?;
+    params.get("page")?.as_u64()?.try_into().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -567,6 +1563,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_api_version_replaces_version_segment() {
+        assert_eq!(
+            with_api_version(DEFAULT_BASE_URL, "v3"),
+            "https://eaccountingapi.vismaonline.com/v3/"
+        );
+        assert_eq!(
+            with_api_version("http://127.0.0.1:1234", "v3"),
+            "http://127.0.0.1:1234/v3/"
+        );
+        assert_eq!(
+            with_api_version("https://proxy.example/visma/", "/v2/"),
+            "https://proxy.example/visma/v2/"
+        );
+
+        let token = AccessToken::new("test".to_string(), 3600, None);
+        let config = ClientConfig::new()
+            .api_version("v3")
+            .base_url(SANDBOX_BASE_URL);
+        let client = Client::with_config(token, config);
+        let url = client.build_url("/customers").unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://eaccountingapi-sandbox.test.vismaonline.com/v3/customers"
+        );
+    }
+
     #[test]
     fn test_token_expiration_check() {
         let expired_token = AccessToken::new("test".to_string(), 0, None);
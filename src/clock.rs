@@ -0,0 +1,151 @@
+//! Time source used by the client.
+//!
+//! The client reads the time through a [`Clock`] when it checks token
+//! expiry, ages cached reference data and waits between retries. The
+//! default [`SystemClock`] uses the system time; tests can install a
+//! [`MockClock`] with [`ClientConfig::clock`](crate::ClientConfig::clock)
+//! and move time forward instead of sleeping.
+//!
+//! # Example
+//!
+//! ```
+//! use spiris::clock::MockClock;
+//! use spiris::{AccessToken, Client, ClientConfig};
+//! use std::time::Duration;
+//!
+//! let clock = MockClock::new(chrono::Utc::now());
+//! let client = Client::with_config(
+//!     AccessToken::new("token".to_string(), 3600, None),
+//!     ClientConfig::new().clock(clock.clone()),
+//! );
+//!
+//! assert!(!client.is_token_expired());
+//! clock.advance(Duration::from_secs(3600));
+//! assert!(client.is_token_expired());
+//! ```
+
+use chrono::{DateTime, NaiveDate, Utc};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Future returned by [`Clock::sleep`].
+#[cfg(not(target_arch = "wasm32"))]
+pub type Sleep<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+/// Future returned by [`Clock::sleep`].
+///
+/// Browser timers cannot move between threads, so on WASM it is not `Send`.
+#[cfg(target_arch = "wasm32")]
+pub type Sleep<'a> = Pin<Box<dyn Future<Output = ()> + 'a>>;
+
+/// A source of the current time.
+pub trait Clock: Send + Sync + fmt::Debug {
+    /// The current time.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Today's date, as used for invoice, due and reminder dates.
+    fn today(&self) -> NaiveDate {
+        self.now().date_naive()
+    }
+
+    /// Wait for `duration`.
+    fn sleep(&self, duration: Duration) -> Sleep<'_>;
+
+    /// Time passed since `earlier`, zero if `earlier` is in the future.
+    fn elapsed_since(&self, earlier: DateTime<Utc>) -> Duration {
+        (self.now() - earlier).to_std().unwrap_or_default()
+    }
+}
+
+/// The system clock. Today's date is the local date.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn today(&self) -> NaiveDate {
+        chrono::Local::now().date_naive()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn sleep(&self, duration: Duration) -> Sleep<'_> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn sleep(&self, duration: Duration) -> Sleep<'_> {
+        Box::pin(gloo_timers::future::sleep(duration))
+    }
+}
+
+/// A clock that only moves when told to.
+///
+/// Clones share the same time, so a test can keep one and hand another to
+/// the client. [`sleep`](Clock::sleep) returns at once after advancing the
+/// time by the requested duration.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl MockClock {
+    /// A clock stopped at `now`.
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    /// Set the time.
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    /// Move the time forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        let step = chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::MAX);
+        let mut now = self.now.lock().unwrap();
+        *now = now
+            .checked_add_signed(step)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep<'_> {
+        self.advance(duration);
+        Box::pin(tokio::task::yield_now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[tokio::test]
+    async fn test_mock_clock_sleep_advances() {
+        let start = Utc.with_ymd_and_hms(2025, 3, 31, 23, 59, 0).unwrap();
+        let clock = MockClock::new(start);
+        let shared = clock.clone();
+
+        shared.sleep(Duration::from_secs(120)).await;
+
+        assert_eq!(clock.elapsed_since(start), Duration::from_secs(120));
+        assert_eq!(clock.today(), NaiveDate::from_ymd_opt(2025, 4, 1).unwrap());
+        assert_eq!(
+            clock.elapsed_since(clock.now() + chrono::Duration::hours(1)),
+            Duration::ZERO
+        );
+    }
+}
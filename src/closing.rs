@@ -0,0 +1,421 @@
+//! Month-end closing checklist.
+//!
+//! [`Checklist`] runs a configurable set of checks against a period and
+//! returns a [`ClosingReport`] with a pass/fail status per check. The report
+//! is serializable, so it can be stored or emitted as JSON from automation,
+//! and [`ClosingReport::is_passed`] gives a single verdict for CI-style gates.
+//!
+//! Unmatched bank transactions are not checked: the API does not expose the
+//! bank transactions of a company or whether they have been matched.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::closing::{Check, Checklist};
+//! use spiris::{AccessToken, Client};
+//! use chrono::NaiveDate;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+//! let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+//! let end = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+//!
+//! let report = Checklist::new()
+//!     .without(Check::OverdueInvoices)
+//!     .run(&client, start, end)
+//!     .await?;
+//!
+//! for result in &report.results {
+//!     println!("{:?}: {:?} - {}", result.check, result.status, result.message);
+//! }
+//! if !report.is_passed() {
+//!     std::process::exit(1);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::endpoints::collect_pages;
+use crate::error::Result;
+use crate::query::Filter;
+use crate::types::{Money, QueryParams};
+use chrono::NaiveDate;
+use serde::Serialize;
+
+/// A single closing check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Check {
+    /// Customer and supplier invoice drafts dated within or before the period.
+    UnbookedDrafts,
+    /// Customer invoices past due at period end with an amount remaining.
+    OverdueInvoices,
+    /// VAT reports whose VAT period ends within the period and that are not
+    /// approved. A VAT report that has not been created yet is not noticed.
+    VatReportStatus,
+    /// Vouchers in the period whose debit and credit do not balance.
+    UnbalancedVouchers,
+}
+
+impl Check {
+    /// All available checks, in the order they are run.
+    pub const ALL: [Check; 4] = [
+        Check::UnbookedDrafts,
+        Check::OverdueInvoices,
+        Check::VatReportStatus,
+        Check::UnbalancedVouchers,
+    ];
+}
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// Nothing needs attention.
+    Passed,
+    /// One or more items need attention before closing.
+    Failed,
+}
+
+/// Result of a single check.
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    /// The check that was run.
+    pub check: Check,
+    /// The outcome.
+    pub status: CheckStatus,
+    /// Human readable summary.
+    pub message: String,
+    /// Identifiers of the items that caused a failure.
+    pub items: Vec<String>,
+}
+
+impl CheckResult {
+    fn from_items(check: Check, items: Vec<String>, what: &str) -> Self {
+        let (status, message) = if items.is_empty() {
+            (CheckStatus::Passed, format!("No {}", what))
+        } else {
+            (CheckStatus::Failed, format!("{} {}", items.len(), what))
+        };
+        Self {
+            check,
+            status,
+            message,
+            items,
+        }
+    }
+}
+
+/// Report produced by [`Checklist::run`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ClosingReport {
+    /// First day of the period.
+    pub period_start: NaiveDate,
+    /// Last day of the period.
+    pub period_end: NaiveDate,
+    /// Results in the order the checks were run.
+    pub results: Vec<CheckResult>,
+}
+
+impl ClosingReport {
+    /// Whether the period is ready to close.
+    pub fn is_passed(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// Results of checks that failed.
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.results
+            .iter()
+            .filter(|r| r.status == CheckStatus::Failed)
+    }
+
+    /// Result of a specific check, if it was run.
+    pub fn result(&self, check: Check) -> Option<&CheckResult> {
+        self.results.iter().find(|r| r.check == check)
+    }
+}
+
+/// Configurable set of month-end closing checks.
+#[derive(Debug, Clone)]
+pub struct Checklist {
+    checks: Vec<Check>,
+    overdue_grace_days: i64,
+}
+
+impl Default for Checklist {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checklist {
+    /// Create a checklist running [all checks](Check::ALL).
+    pub fn new() -> Self {
+        Self {
+            checks: Check::ALL.to_vec(),
+            overdue_grace_days: 0,
+        }
+    }
+
+    /// Create a checklist running only the given checks.
+    pub fn with_checks(checks: impl IntoIterator<Item = Check>) -> Self {
+        Self {
+            checks: checks.into_iter().collect(),
+            ..Self::new()
+        }
+    }
+
+    /// Remove a check from the checklist.
+    pub fn without(mut self, check: Check) -> Self {
+        self.checks.retain(|c| *c != check);
+        self
+    }
+
+    /// Days past the due date before an unpaid invoice counts as overdue.
+    pub fn overdue_grace_days(mut self, days: i64) -> Self {
+        self.overdue_grace_days = days;
+        self
+    }
+
+    /// Run the checks for the period `start..=end`.
+    pub async fn run(
+        &self,
+        client: &Client,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<ClosingReport> {
+        let mut results = Vec::with_capacity(self.checks.len());
+
+        for check in &self.checks {
+            let result = match check {
+                Check::UnbookedDrafts => unbooked_drafts(client, end).await?,
+                Check::OverdueInvoices => {
+                    overdue_invoices(client, end, self.overdue_grace_days).await?
+                }
+                Check::VatReportStatus => unapproved_vat_reports(client, start, end).await?,
+                Check::UnbalancedVouchers => unbalanced_vouchers(client, start, end).await?,
+            };
+            results.push(result);
+        }
+
+        Ok(ClosingReport {
+            period_start: start,
+            period_end: end,
+            results,
+        })
+    }
+}
+
+fn display_id(id: Option<&str>, number: &Option<String>) -> String {
+    number
+        .clone()
+        .or_else(|| id.map(str::to_string))
+        .unwrap_or_else(|| "<unknown>".to_string())
+}
+
+async fn unbooked_drafts(client: &Client, end: NaiveDate) -> Result<CheckResult> {
+    let customer_drafts =
+        collect_pages(|p| async move { client.customer_invoice_drafts().list(Some(p)).await })
+            .await?;
+    let supplier_drafts =
+        collect_pages(|p| async move { client.supplier_invoice_drafts().list(Some(p)).await })
+            .await?;
+
+    let in_period = |date: Option<NaiveDate>| date.is_none_or(|d| d <= end);
+
+    let items = customer_drafts
+        .iter()
+        .filter(|d| in_period(d.invoice_date))
+        .map(|d| format!("customer draft {}", display_id(d.id.as_deref(), &None)))
+        .chain(
+            supplier_drafts
+                .iter()
+                .filter(|d| in_period(d.invoice_date))
+                .map(|d| {
+                    format!(
+                        "supplier draft {}",
+                        display_id(d.id.as_deref(), &d.invoice_number)
+                    )
+                }),
+        )
+        .collect();
+
+    Ok(CheckResult::from_items(
+        Check::UnbookedDrafts,
+        items,
+        "unbooked invoice drafts",
+    ))
+}
+
+async fn overdue_invoices(client: &Client, end: NaiveDate, grace_days: i64) -> Result<CheckResult> {
+    let cutoff = end - chrono::Duration::days(grace_days);
+    let filter = Filter::field("DueDate").lt(cutoff);
+
+    let invoices = collect_pages(|p| {
+        let query = QueryParams::new().filter_by(filter.clone());
+        async move { client.invoices().search(query, Some(p)).await }
+    })
+    .await?;
+
+    let items = invoices
+        .iter()
+        .filter(|i| i.due_date.is_some_and(|d| d < cutoff))
+        .filter(|i| i.remaining_amount.is_some_and(|r| r > Money::default()))
+        .map(|i| format!("invoice {}", display_id(i.id.as_deref(), &i.invoice_number)))
+        .collect();
+
+    Ok(CheckResult::from_items(
+        Check::OverdueInvoices,
+        items,
+        "overdue unpaid invoices",
+    ))
+}
+
+async fn unapproved_vat_reports(
+    client: &Client,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<CheckResult> {
+    let reports =
+        collect_pages(|p| async move { client.vat_reports().list(Some(p)).await }).await?;
+
+    let items = reports
+        .iter()
+        .filter(|r| r.end_date.is_some_and(|d| (start..=end).contains(&d)))
+        .filter(|r| !r.is_approved())
+        .map(|r| format!("VAT report {}", display_id(r.id.as_deref(), &r.name)))
+        .collect();
+
+    Ok(CheckResult::from_items(
+        Check::VatReportStatus,
+        items,
+        "unapproved VAT reports",
+    ))
+}
+
+async fn unbalanced_vouchers(
+    client: &Client,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<CheckResult> {
+    let filter = Filter::field("VoucherDate")
+        .ge(start)
+        .and(Filter::field("VoucherDate").le(end));
+
+    let vouchers = collect_pages(|p| {
+        let query = QueryParams::new().filter_by(filter.clone());
+        async move { client.vouchers().search(query, Some(p)).await }
+    })
+    .await?;
+
+    let tolerance = crate::money!(0.005);
+    let items = vouchers
+        .iter()
+        .filter(|v| {
+            v.voucher_date
+                .is_some_and(|d| (start..=end).contains(&d.date_naive()))
+        })
+        .filter(|v| {
+            let debit: Money = v.rows.iter().filter_map(|r| r.debit_amount).sum();
+            let credit: Money = v.rows.iter().filter_map(|r| r.credit_amount).sum();
+            (debit - credit).abs() > tolerance
+        })
+        .map(|v| format!("voucher {}", display_id(v.id.as_deref(), &v.voucher_number)))
+        .collect();
+
+    Ok(CheckResult::from_items(
+        Check::UnbalancedVouchers,
+        items,
+        "unbalanced vouchers",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(check: Check, status: CheckStatus) -> CheckResult {
+        CheckResult {
+            check,
+            status,
+            message: String::new(),
+            items: Vec::new(),
+        }
+    }
+
+    fn report(results: Vec<CheckResult>) -> ClosingReport {
+        ClosingReport {
+            period_start: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            results,
+        }
+    }
+
+    #[test]
+    fn test_checklist_configuration() {
+        let checklist = Checklist::new();
+        assert_eq!(checklist.checks, Check::ALL);
+
+        let checklist = Checklist::new().without(Check::OverdueInvoices);
+        assert_eq!(
+            checklist.checks,
+            vec![
+                Check::UnbookedDrafts,
+                Check::VatReportStatus,
+                Check::UnbalancedVouchers
+            ]
+        );
+
+        let checklist = Checklist::with_checks([Check::UnbalancedVouchers]);
+        assert_eq!(checklist.checks, vec![Check::UnbalancedVouchers]);
+    }
+
+    #[test]
+    fn test_report_passes_without_failures() {
+        let report = report(vec![
+            result(Check::UnbookedDrafts, CheckStatus::Passed),
+            result(Check::VatReportStatus, CheckStatus::Passed),
+        ]);
+        assert!(report.is_passed());
+        assert_eq!(report.failures().count(), 0);
+    }
+
+    #[test]
+    fn test_report_fails_on_failed_check() {
+        let report = report(vec![
+            result(Check::UnbookedDrafts, CheckStatus::Passed),
+            result(Check::UnbalancedVouchers, CheckStatus::Failed),
+        ]);
+        assert!(!report.is_passed());
+        assert_eq!(
+            report.failures().next().unwrap().check,
+            Check::UnbalancedVouchers
+        );
+        assert!(report.result(Check::OverdueInvoices).is_none());
+    }
+
+    #[test]
+    fn test_check_result_from_items() {
+        let passed = CheckResult::from_items(Check::UnbookedDrafts, vec![], "drafts");
+        assert_eq!(passed.status, CheckStatus::Passed);
+
+        let failed = CheckResult::from_items(
+            Check::UnbookedDrafts,
+            vec!["a".into(), "b".into()],
+            "drafts",
+        );
+        assert_eq!(failed.status, CheckStatus::Failed);
+        assert_eq!(failed.message, "2 drafts");
+    }
+
+    #[test]
+    fn test_report_serializes_for_automation() {
+        let report = report(vec![result(Check::UnbalancedVouchers, CheckStatus::Failed)]);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["period_start"], "2025-01-01");
+        assert_eq!(json["results"][0]["check"], "unbalanced_vouchers");
+        assert_eq!(json["results"][0]["status"], "failed");
+    }
+}
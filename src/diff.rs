@@ -0,0 +1,283 @@
+//! Compare local master data against the API and plan the changes.
+//!
+//! A [`Plan`] lists the records to create and, for records that already
+//! exist, the fields that differ. Only fields set in the local record are
+//! compared, so a file with a few columns never clears the others. Review
+//! the plan, then apply it with the endpoint's `create` and `update`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::{diff, AccessToken, Client, Customer};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+//! let desired = vec![Customer {
+//!     customer_number: Some("1001".to_string()),
+//!     email: Some("billing@acme.example".to_string()),
+//!     ..Default::default()
+//! }];
+//!
+//! let plan = diff::plan_customers(&client, &desired).await?;
+//! for update in &plan.updates {
+//!     for change in &update.changes {
+//!         println!("{} {}: {} -> {}", update.key, change.field, change.current, change.desired);
+//!     }
+//! }
+//! for update in &plan.updates {
+//!     client.customers().update(&update.id, &update.record).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::endpoints::collect_pages;
+use crate::error::{Error, Result};
+use crate::types::{Customer, CustomerId};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+/// A field whose value would change.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    /// API field name, nested fields joined with a dot.
+    pub field: String,
+    /// Value in the API, `null` if unset.
+    pub current: Value,
+    /// Value from the local record.
+    pub desired: Value,
+}
+
+/// A record that does not exist in the API yet.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedCreate<T> {
+    /// Key of the record, if it has one.
+    pub key: Option<String>,
+    /// Record to create.
+    pub record: T,
+}
+
+/// An existing record with changed fields.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedUpdate<T, I = String> {
+    /// Key of the record.
+    pub key: String,
+    /// ID of the record in the API.
+    pub id: I,
+    /// The API record with the local fields applied, ready to send.
+    pub record: T,
+    /// Fields that change.
+    pub changes: Vec<FieldChange>,
+}
+
+/// Changes needed to bring the API in line with local records.
+#[derive(Debug, Clone, Serialize)]
+pub struct Plan<T, I = String> {
+    /// Records to create.
+    pub creates: Vec<PlannedCreate<T>>,
+    /// Records to update.
+    pub updates: Vec<PlannedUpdate<T, I>>,
+    /// Number of local records that already match.
+    pub unchanged: usize,
+}
+
+impl<T, I> Plan<T, I> {
+    /// Whether the plan changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.creates.is_empty() && self.updates.is_empty()
+    }
+}
+
+/// Plan the changes turning `current` into `desired`.
+///
+/// Records are matched by `key`; local records without a key are created.
+/// `id` gives the API ID of a current record. Fails if two local records
+/// share a key.
+pub fn plan<T, I>(
+    desired: &[T],
+    current: &[T],
+    key: impl Fn(&T) -> Option<String>,
+    id: impl Fn(&T) -> Option<I>,
+) -> Result<Plan<T, I>>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    let by_key: HashMap<String, &T> = current
+        .iter()
+        .filter_map(|record| Some((key(record)?, record)))
+        .collect();
+
+    let mut seen = HashSet::new();
+    let mut plan = Plan {
+        creates: Vec::new(),
+        updates: Vec::new(),
+        unchanged: 0,
+    };
+
+    for record in desired {
+        let record_key = key(record);
+        if let Some(k) = &record_key {
+            if !seen.insert(k.clone()) {
+                return Err(Error::InvalidRequest(format!(
+                    "duplicate key {} in local records",
+                    k
+                )));
+            }
+        }
+
+        let Some((k, existing)) = record_key.and_then(|k| Some((k.clone(), *by_key.get(&k)?)))
+        else {
+            plan.creates.push(PlannedCreate {
+                key: key(record),
+                record: record.clone(),
+            });
+            continue;
+        };
+
+        let mut merged = serde_json::to_value(existing)?;
+        let mut changes = Vec::new();
+        merge(&mut merged, serde_json::to_value(record)?, "", &mut changes);
+        if changes.is_empty() {
+            plan.unchanged += 1;
+            continue;
+        }
+
+        plan.updates.push(PlannedUpdate {
+            id: id(existing).ok_or_else(|| {
+                Error::InvalidRequest(format!("record {} has no ID in the API", k))
+            })?,
+            key: k,
+            record: serde_json::from_value(merged)?,
+            changes,
+        });
+    }
+
+    Ok(plan)
+}
+
+/// Overlay the set fields of `desired` on `current`, recording changes.
+fn merge(current: &mut Value, desired: Value, path: &str, changes: &mut Vec<FieldChange>) {
+    let Value::Object(desired) = desired else {
+        return;
+    };
+    if !current.is_object() {
+        *current = Value::Object(Map::new());
+    }
+    let current = current.as_object_mut().expect("object");
+
+    for (field, value) in desired {
+        if value.is_null() {
+            continue;
+        }
+        let field_path = if path.is_empty() {
+            field.clone()
+        } else {
+            format!("{}.{}", path, field)
+        };
+        let slot = current.entry(field).or_insert(Value::Null);
+
+        if value.is_object() {
+            merge(slot, value, &field_path, changes);
+        } else if !same(slot, &value) {
+            changes.push(FieldChange {
+                field: field_path,
+                current: slot.clone(),
+                desired: value.clone(),
+            });
+            *slot = value;
+        }
+    }
+}
+
+/// Equal values, comparing numbers by value so `100` matches `100.0`.
+fn same(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => a == b,
+    }
+}
+
+/// Plan customer changes, matching customers by customer number.
+pub async fn plan_customers(
+    client: &Client,
+    desired: &[Customer],
+) -> Result<Plan<Customer, CustomerId>> {
+    let current = collect_pages(|p| async move { client.customers().list(Some(p)).await }).await?;
+    plan(
+        desired,
+        &current,
+        |c| c.customer_number.clone(),
+        |c| c.id.clone(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Address;
+
+    fn customer(number: &str, name: &str) -> Customer {
+        Customer {
+            customer_number: Some(number.to_string()),
+            name: Some(name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_plan_creates_and_updates() {
+        let mut acme = customer("1001", "Acme AB");
+        acme.id = Some("c1".into());
+        acme.email = Some("info@acme.example".to_string());
+        acme.payment_terms_in_days = Some(30);
+        let mut beta = customer("1002", "Beta AB");
+        beta.id = Some("c2".into());
+
+        let mut acme_local = customer("1001", "Acme AB");
+        acme_local.payment_terms_in_days = Some(30);
+        acme_local.invoice_address = Some(Address {
+            city: Some("Malmö".to_string()),
+            ..Default::default()
+        });
+        let desired = vec![
+            acme_local,
+            customer("1002", "Beta AB"),
+            customer("1003", "Gamma AB"),
+        ];
+
+        let plan = plan(
+            &desired,
+            &[acme, beta],
+            |c| c.customer_number.clone(),
+            |c| c.id.clone(),
+        )
+        .unwrap();
+
+        assert_eq!(plan.unchanged, 1);
+        assert_eq!(plan.creates.len(), 1);
+        assert_eq!(plan.creates[0].key.as_deref(), Some("1003"));
+
+        let update = &plan.updates[0];
+        assert_eq!(update.id, "c1");
+        assert_eq!(update.changes.len(), 1);
+        assert_eq!(update.changes[0].field, "InvoiceAddress.City");
+        assert_eq!(update.changes[0].current, Value::Null);
+        // Fields missing locally are kept
+        assert_eq!(update.record.email.as_deref(), Some("info@acme.example"));
+    }
+
+    #[test]
+    fn test_duplicate_keys_are_rejected() {
+        let desired = vec![customer("1001", "Acme AB"), customer("1001", "Acme")];
+        assert!(plan(
+            &desired,
+            &[],
+            |c| c.customer_number.clone(),
+            |c| c.id.clone()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,224 @@
+//! Disputed and on-hold invoices.
+//!
+//! The API has no field for invoices the customer disputes or that should not
+//! be chased for another reason, so the hold is stored as a marker line in the
+//! invoice remarks:
+//!
+//! ```text
+//! [DISPUTED] Customer says the delivery was incomplete
+//! ```
+//!
+//! Remarks can be printed on the invoice, so place holds on invoices that have
+//! already been sent. Held invoices are skipped by
+//! [`dunning`](crate::dunning) and counted separately in the
+//! [ageing report](crate::reports::AgeingReport).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::disputes::{self, InvoiceHold};
+//! use spiris::{AccessToken, Client, InvoiceId};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+//! let id = InvoiceId::from("invoice-id");
+//! let hold = InvoiceHold::disputed("Customer says the delivery was incomplete");
+//! let invoice = disputes::place_hold(&client, &id, &hold).await?;
+//! assert!(invoice.hold().is_some());
+//!
+//! // Once settled
+//! disputes::release_hold(&client, &id).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::error::Result;
+use crate::types::{Invoice, InvoiceId};
+use serde::Serialize;
+use std::fmt;
+
+/// Why an invoice is held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HoldKind {
+    /// The customer disputes the invoice.
+    Disputed,
+    /// Held for another reason, such as an agreed payment plan.
+    OnHold,
+    /// Being collected by direct debit (see [`autogiro`](crate::autogiro)).
+    DirectDebit,
+}
+
+impl HoldKind {
+    const ALL: [HoldKind; 3] = [HoldKind::Disputed, HoldKind::OnHold, HoldKind::DirectDebit];
+
+    /// Tag used in the remarks marker.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            HoldKind::Disputed => "[DISPUTED]",
+            HoldKind::OnHold => "[ON HOLD]",
+            HoldKind::DirectDebit => "[DIRECT DEBIT]",
+        }
+    }
+
+    /// Short label for display.
+    pub fn label(&self) -> &'static str {
+        match self {
+            HoldKind::Disputed => "Disputed",
+            HoldKind::OnHold => "On hold",
+            HoldKind::DirectDebit => "Pending direct debit",
+        }
+    }
+}
+
+/// A hold on an invoice and its reason.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InvoiceHold {
+    /// Kind of hold.
+    pub kind: HoldKind,
+    /// Reason, may be empty.
+    pub reason: String,
+}
+
+impl InvoiceHold {
+    /// The customer disputes the invoice.
+    pub fn disputed(reason: impl Into<String>) -> Self {
+        Self {
+            kind: HoldKind::Disputed,
+            reason: reason.into(),
+        }
+    }
+
+    /// The invoice is held for another reason.
+    pub fn on_hold(reason: impl Into<String>) -> Self {
+        Self {
+            kind: HoldKind::OnHold,
+            reason: reason.into(),
+        }
+    }
+
+    /// The invoice is being collected by direct debit.
+    pub fn direct_debit(reason: impl Into<String>) -> Self {
+        Self {
+            kind: HoldKind::DirectDebit,
+            reason: reason.into(),
+        }
+    }
+
+    /// Find the hold marker in invoice remarks.
+    pub fn parse(remarks: &str) -> Option<Self> {
+        remarks.lines().find_map(|line| {
+            let line = line.trim();
+            HoldKind::ALL.into_iter().find_map(|kind| {
+                let reason = line.strip_prefix(kind.tag())?;
+                Some(Self {
+                    kind,
+                    reason: reason.trim().to_string(),
+                })
+            })
+        })
+    }
+
+    /// Marker line stored in the remarks.
+    pub fn marker(&self) -> String {
+        if self.reason.is_empty() {
+            self.kind.tag().to_string()
+        } else {
+            format!("{} {}", self.kind.tag(), self.reason.trim())
+        }
+    }
+
+    /// `remarks` with this hold's marker, replacing an existing marker.
+    pub fn apply(&self, remarks: Option<&str>) -> String {
+        match Self::remove(remarks) {
+            Some(rest) => format!("{}\n{}", self.marker(), rest),
+            None => self.marker(),
+        }
+    }
+
+    /// `remarks` without a hold marker, `None` if nothing else remains.
+    pub fn remove(remarks: Option<&str>) -> Option<String> {
+        let rest = remarks?
+            .lines()
+            .filter(|line| Self::parse(line).is_none())
+            .collect::<Vec<_>>()
+            .join("\n");
+        (!rest.trim().is_empty()).then_some(rest)
+    }
+}
+
+impl fmt::Display for InvoiceHold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.reason.is_empty() {
+            f.write_str(self.kind.label())
+        } else {
+            write!(f, "{}: {}", self.kind.label(), self.reason)
+        }
+    }
+}
+
+impl Invoice {
+    /// The hold on this invoice, if any.
+    pub fn hold(&self) -> Option<InvoiceHold> {
+        InvoiceHold::parse(self.remarks.as_deref()?)
+    }
+
+    /// Whether the invoice is disputed, on hold or pending direct debit.
+    pub fn is_held(&self) -> bool {
+        self.hold().is_some()
+    }
+}
+
+/// Place a hold on an invoice, replacing any existing hold.
+pub async fn place_hold(
+    client: &Client,
+    invoice_id: &InvoiceId,
+    hold: &InvoiceHold,
+) -> Result<Invoice> {
+    let mut invoice = client.invoices().get(invoice_id).await?;
+    invoice.remarks = Some(hold.apply(invoice.remarks.as_deref()));
+    client.invoices().update(invoice_id, &invoice).await
+}
+
+/// Release the hold on an invoice. Does nothing if it has none.
+pub async fn release_hold(client: &Client, invoice_id: &InvoiceId) -> Result<Invoice> {
+    let mut invoice = client.invoices().get(invoice_id).await?;
+    if !invoice.is_held() {
+        return Ok(invoice);
+    }
+    invoice.remarks = InvoiceHold::remove(invoice.remarks.as_deref());
+    client.invoices().update(invoice_id, &invoice).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marker_round_trip() {
+        let hold = InvoiceHold::disputed("Wrong quantity");
+        let remarks = hold.apply(Some("Thank you for your business!"));
+
+        assert_eq!(
+            remarks,
+            "[DISPUTED] Wrong quantity\nThank you for your business!"
+        );
+        assert_eq!(InvoiceHold::parse(&remarks), Some(hold));
+        assert_eq!(
+            InvoiceHold::remove(Some(&remarks)).as_deref(),
+            Some("Thank you for your business!")
+        );
+    }
+
+    #[test]
+    fn test_apply_replaces_existing_hold() {
+        let remarks = InvoiceHold::disputed("Wrong quantity").apply(None);
+        let remarks = InvoiceHold::on_hold("").apply(Some(&remarks));
+
+        assert_eq!(remarks, "[ON HOLD]");
+        assert_eq!(InvoiceHold::parse(&remarks).unwrap().to_string(), "On hold");
+        assert_eq!(InvoiceHold::remove(Some(&remarks)), None);
+        assert_eq!(InvoiceHold::parse("Disputed by phone"), None);
+    }
+}
@@ -0,0 +1,571 @@
+//! Payment reminders (dunning) for overdue customer invoices.
+//!
+//! A [`DunningPolicy`] defines reminder levels by days overdue. Each run
+//! escalates an invoice by at most one level, so an invoice that was never
+//! reminded gets the first reminder even if it is far overdue. Disputed and
+//! on-hold invoices (see [`disputes`](crate::disputes)) are never reminded.
+//!
+//! An [`InterestPolicy`] charges late payment interest on overdue invoices
+//! with separate interest invoices, see [`create_interest_invoices`].
+//!
+//! Every function takes the date to act on. Pass
+//! [`Client::clock`](crate::Client::clock)`().today()` for the current date,
+//! or a fixed date to test level boundaries.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::dunning::{self, DunningPolicy};
+//! use spiris::{AccessToken, Client};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+//! let today = client.clock().today();
+//! let policy = DunningPolicy::default();
+//!
+//! let candidates = dunning::preview(&client, &policy, today).await?;
+//! for result in dunning::run(&client, &candidates, today).await {
+//!     match &result.error {
+//!         None => println!("Reminded {}", result.invoice_id),
+//!         Some(error) => println!("Failed {}: {}", result.invoice_id, error),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::endpoints::collect_pages;
+use crate::error::Result;
+use crate::types::{
+    ArticleId, CustomerId, Invoice, InvoiceId, InvoiceReminder, InvoiceRow, Money, ReminderLevel,
+};
+use chrono::{NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+/// A reminder level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DunningLevel {
+    /// Days past the due date before this level is sent.
+    pub days_overdue: i64,
+    /// Reminder fee.
+    #[serde(default, with = "crate::types::money_serde")]
+    pub fee: Money,
+    /// Text printed on the reminder.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// Rules for which overdue invoices receive reminders.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DunningPolicy {
+    /// Reminder levels, ordered by days overdue.
+    pub levels: Vec<DunningLevel>,
+    /// Invoices with a smaller remaining amount are not reminded.
+    #[serde(with = "crate::types::money_serde")]
+    pub min_remaining: Money,
+    /// Minimum number of days between two reminders for the same invoice.
+    pub min_days_between: i64,
+}
+
+impl Default for DunningPolicy {
+    /// A first reminder without fee after 8 days and a second one with the
+    /// statutory Swedish reminder fee of 60 SEK after 30 days.
+    fn default() -> Self {
+        Self {
+            levels: vec![
+                DunningLevel {
+                    days_overdue: 8,
+                    fee: Money::default(),
+                    text: None,
+                },
+                DunningLevel {
+                    days_overdue: 30,
+                    fee: crate::money!(60.0),
+                    text: None,
+                },
+            ],
+            min_remaining: crate::money!(50.0),
+            min_days_between: 14,
+        }
+    }
+}
+
+/// An invoice that would receive a reminder.
+#[derive(Debug, Clone, Serialize)]
+pub struct DunningCandidate {
+    /// Invoice ID.
+    pub invoice_id: InvoiceId,
+    /// Invoice number.
+    pub invoice_number: Option<String>,
+    /// Customer ID.
+    pub customer_id: Option<CustomerId>,
+    /// Due date of the invoice.
+    pub due_date: NaiveDate,
+    /// Days past the due date.
+    pub days_overdue: i64,
+    /// Remaining amount.
+    #[serde(with = "crate::types::money_serde")]
+    pub remaining: Money,
+    /// Reminder level to send.
+    pub level: ReminderLevel,
+    /// Fee for the level.
+    #[serde(with = "crate::types::money_serde")]
+    pub fee: Money,
+    /// Text for the level.
+    pub text: Option<String>,
+}
+
+/// Outcome of sending one reminder.
+#[derive(Debug, Clone, Serialize)]
+pub struct DunningResult {
+    /// Invoice ID.
+    pub invoice_id: InvoiceId,
+    /// Invoice number.
+    pub invoice_number: Option<String>,
+    /// Reminder level sent.
+    pub level: ReminderLevel,
+    /// Error message if the reminder could not be sent.
+    pub error: Option<String>,
+}
+
+impl DunningResult {
+    /// Whether the reminder was sent.
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+impl DunningPolicy {
+    /// Select the invoices that should be reminded at `as_of`, most overdue
+    /// first.
+    pub fn candidates(&self, invoices: &[Invoice], as_of: NaiveDate) -> Vec<DunningCandidate> {
+        let mut candidates: Vec<DunningCandidate> = invoices
+            .iter()
+            .filter_map(|invoice| self.candidate(invoice, as_of))
+            .collect();
+        candidates.sort_by_key(|c| std::cmp::Reverse(c.days_overdue));
+        candidates
+    }
+
+    fn candidate(&self, invoice: &Invoice, as_of: NaiveDate) -> Option<DunningCandidate> {
+        if invoice.is_held() {
+            return None;
+        }
+        let remaining = invoice.remaining_amount?;
+        if remaining <= Money::default() || remaining < self.min_remaining {
+            return None;
+        }
+
+        let due_date = invoice.due_date?;
+        let days_overdue = (as_of - due_date).num_days();
+        if days_overdue <= 0 {
+            return None;
+        }
+
+        if let Some(last) = invoice.last_reminder_date {
+            if (as_of - last.date_naive()).num_days() < self.min_days_between {
+                return None;
+            }
+        }
+
+        let sent = invoice.reminder_level.unwrap_or_default();
+        let mut levels = self.levels.clone();
+        levels.sort_by_key(|level| level.days_overdue);
+        let next = levels.get(usize::from(sent.get()))?;
+        if days_overdue < next.days_overdue {
+            return None;
+        }
+
+        Some(DunningCandidate {
+            invoice_id: invoice.id.clone()?,
+            invoice_number: invoice.invoice_number.clone(),
+            customer_id: invoice.customer_id.clone(),
+            due_date,
+            days_overdue,
+            remaining,
+            level: sent.next(),
+            fee: next.fee,
+            text: next.text.clone(),
+        })
+    }
+}
+
+/// Load open invoices and select reminder candidates under `policy`.
+pub async fn preview(
+    client: &Client,
+    policy: &DunningPolicy,
+    as_of: NaiveDate,
+) -> Result<Vec<DunningCandidate>> {
+    let invoices = collect_pages(|p| async move { client.invoices().list(Some(p)).await }).await?;
+    Ok(policy.candidates(&invoices, as_of))
+}
+
+/// Send reminders for the candidates.
+///
+/// A failing invoice does not stop the run; every candidate gets a result.
+pub async fn run(
+    client: &Client,
+    candidates: &[DunningCandidate],
+    as_of: NaiveDate,
+) -> Vec<DunningResult> {
+    let mut results = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        let reminder = InvoiceReminder {
+            customer_invoice_id: Some(candidate.invoice_id.clone()),
+            level: Some(candidate.level),
+            fee: (candidate.fee > Money::default()).then_some(candidate.fee),
+            text: candidate.text.clone(),
+            reminder_date: Some(as_of.and_time(NaiveTime::MIN).and_utc()),
+            ..Default::default()
+        };
+        let outcome = client
+            .invoices()
+            .send_reminder(&candidate.invoice_id, &reminder)
+            .await;
+
+        results.push(DunningResult {
+            invoice_id: candidate.invoice_id.clone(),
+            invoice_number: candidate.invoice_number.clone(),
+            level: candidate.level,
+            error: outcome.err().map(|e| e.to_string()),
+        });
+    }
+    results
+}
+
+/// Late payment interest charged with interest invoices.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterestPolicy {
+    /// Annual interest rate in percent.
+    #[serde(with = "crate::types::money_serde")]
+    pub annual_rate: Money,
+    /// Invoices are not charged interest for this many days after the due
+    /// date.
+    #[serde(default)]
+    pub grace_days: i64,
+    /// Interest below this amount is not invoiced.
+    #[serde(default, with = "crate::types::money_serde")]
+    pub min_interest: Money,
+    /// Article used for the interest row, typically one without VAT.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub article_id: Option<ArticleId>,
+}
+
+impl InterestPolicy {
+    /// Charge `annual_rate` percent per year from the due date.
+    pub fn new(annual_rate: Money) -> Self {
+        Self {
+            annual_rate,
+            grace_days: 0,
+            min_interest: Money::default(),
+            article_id: None,
+        }
+    }
+
+    /// Interest on `amount` for `days` days, rounded to cents.
+    pub fn interest(&self, amount: Money, days: i64) -> Money {
+        round_cents(amount * self.annual_rate * money_from(days) / money_from(100 * 365))
+    }
+
+    /// Select the invoices to charge interest at `as_of`, most overdue
+    /// first. Interest runs on the remaining amount from the due date.
+    pub fn candidates(&self, invoices: &[Invoice], as_of: NaiveDate) -> Vec<InterestCandidate> {
+        let mut candidates: Vec<InterestCandidate> = invoices
+            .iter()
+            .filter_map(|invoice| self.candidate(invoice, as_of))
+            .collect();
+        candidates.sort_by_key(|c| std::cmp::Reverse(c.days_overdue));
+        candidates
+    }
+
+    fn candidate(&self, invoice: &Invoice, as_of: NaiveDate) -> Option<InterestCandidate> {
+        if invoice.is_held() {
+            return None;
+        }
+        let remaining = invoice.remaining_amount?;
+        let due_date = invoice.due_date?;
+        let days_overdue = (as_of - due_date).num_days();
+        if days_overdue <= self.grace_days {
+            return None;
+        }
+
+        let interest = self.interest(remaining, days_overdue);
+        if interest <= Money::default() || interest < self.min_interest {
+            return None;
+        }
+
+        Some(InterestCandidate {
+            invoice_id: invoice.id.clone()?,
+            invoice_number: invoice.invoice_number.clone(),
+            customer_id: invoice.customer_id.clone()?,
+            due_date,
+            days_overdue,
+            remaining,
+            interest,
+        })
+    }
+
+    /// The interest invoice for a candidate, dated `as_of`.
+    ///
+    /// The interest row is VAT free.
+    pub fn interest_invoice(&self, candidate: &InterestCandidate, as_of: NaiveDate) -> Invoice {
+        let invoice = candidate
+            .invoice_number
+            .as_deref()
+            .unwrap_or(&candidate.invoice_id);
+        let row = InvoiceRow {
+            article_id: self.article_id.clone(),
+            text: Some(format!(
+                "Interest on invoice {}, {} days at {}%",
+                invoice, candidate.days_overdue, self.annual_rate
+            )),
+            unit_price: Some(candidate.interest),
+            quantity: Some(crate::money!(1.0)),
+            is_vat_free: Some(true),
+            ..Default::default()
+        };
+
+        Invoice {
+            customer_id: Some(candidate.customer_id.clone()),
+            invoice_date: Some(as_of),
+            rows: vec![row],
+            remarks: Some(format!(
+                "Late payment interest, invoice {} due {}",
+                invoice, candidate.due_date
+            )),
+            ..Default::default()
+        }
+    }
+}
+
+/// An invoice that would be charged interest.
+#[derive(Debug, Clone, Serialize)]
+pub struct InterestCandidate {
+    /// Invoice ID.
+    pub invoice_id: InvoiceId,
+    /// Invoice number.
+    pub invoice_number: Option<String>,
+    /// Customer ID.
+    pub customer_id: CustomerId,
+    /// Due date of the invoice.
+    pub due_date: NaiveDate,
+    /// Days past the due date.
+    pub days_overdue: i64,
+    /// Remaining amount interest is charged on.
+    #[serde(with = "crate::types::money_serde")]
+    pub remaining: Money,
+    /// Interest to invoice.
+    #[serde(with = "crate::types::money_serde")]
+    pub interest: Money,
+}
+
+/// Outcome of creating one interest invoice.
+#[derive(Debug, Clone, Serialize)]
+pub struct InterestResult {
+    /// ID of the overdue invoice.
+    pub invoice_id: InvoiceId,
+    /// Number of the overdue invoice.
+    pub invoice_number: Option<String>,
+    /// ID of the created interest invoice.
+    pub interest_invoice_id: Option<InvoiceId>,
+    /// Error message if the interest invoice could not be created.
+    pub error: Option<String>,
+}
+
+impl InterestResult {
+    /// Whether the interest invoice was created.
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Load open invoices and select interest candidates under `policy`.
+pub async fn preview_interest(
+    client: &Client,
+    policy: &InterestPolicy,
+    as_of: NaiveDate,
+) -> Result<Vec<InterestCandidate>> {
+    let invoices = collect_pages(|p| async move { client.invoices().list(Some(p)).await }).await?;
+    Ok(policy.candidates(&invoices, as_of))
+}
+
+/// Create interest invoices for the candidates.
+///
+/// A failing invoice does not stop the run; every candidate gets a result.
+pub async fn create_interest_invoices(
+    client: &Client,
+    policy: &InterestPolicy,
+    candidates: &[InterestCandidate],
+    as_of: NaiveDate,
+) -> Vec<InterestResult> {
+    let mut results = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        let outcome = client
+            .invoices()
+            .create(&policy.interest_invoice(candidate, as_of))
+            .await;
+
+        let (interest_invoice_id, error) = match outcome {
+            Ok(invoice) => (invoice.id, None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        results.push(InterestResult {
+            invoice_id: candidate.invoice_id.clone(),
+            invoice_number: candidate.invoice_number.clone(),
+            interest_invoice_id,
+            error,
+        });
+    }
+    results
+}
+
+fn money_from(value: i64) -> Money {
+    #[cfg(feature = "decimal")]
+    {
+        Money::from(value)
+    }
+    #[cfg(not(feature = "decimal"))]
+    {
+        value as Money
+    }
+}
+
+/// Round to whole cents.
+pub(crate) fn round_cents(value: Money) -> Money {
+    #[cfg(feature = "decimal")]
+    {
+        value.round_dp(2)
+    }
+    #[cfg(not(feature = "decimal"))]
+    {
+        (value * 100.0).round() / 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn money(value: &str) -> Money {
+        value.parse().unwrap()
+    }
+
+    fn invoice(id: &str, due: (i32, u32, u32), remaining: &str) -> Invoice {
+        Invoice {
+            id: Some(id.into()),
+            due_date: Some(NaiveDate::from_ymd_opt(due.0, due.1, due.2).unwrap()),
+            remaining_amount: Some(money(remaining)),
+            ..Default::default()
+        }
+    }
+
+    fn as_of() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, 31).unwrap()
+    }
+
+    #[test]
+    fn test_candidates_by_days_overdue() {
+        let invoices = vec![
+            invoice("not-due", (2025, 4, 10), "100"),
+            invoice("grace", (2025, 3, 28), "100"),
+            invoice("first", (2025, 3, 20), "100"),
+            invoice("far-overdue", (2025, 1, 15), "100"),
+            invoice("small", (2025, 1, 15), "10"),
+            invoice("paid", (2025, 1, 15), "0"),
+        ];
+
+        let candidates = DunningPolicy::default().candidates(&invoices, as_of());
+        let ids: Vec<_> = candidates.iter().map(|c| c.invoice_id.as_str()).collect();
+
+        assert_eq!(ids, ["far-overdue", "first"]);
+        // Never reminded, so it starts at the first level
+        assert_eq!(candidates[0].level, ReminderLevel::FIRST);
+        assert_eq!(candidates[0].fee, Money::default());
+        assert_eq!(candidates[1].days_overdue, 11);
+    }
+
+    #[test]
+    fn test_escalates_one_level_at_a_time() {
+        let mut reminded = invoice("inv-1", (2025, 2, 1), "100");
+        reminded.reminder_level = Some(ReminderLevel::FIRST);
+        reminded.last_reminder_date = Some(Utc.with_ymd_and_hms(2025, 2, 10, 0, 0, 0).unwrap());
+
+        let candidates = DunningPolicy::default().candidates(&[reminded.clone()], as_of());
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].level, ReminderLevel::new(2));
+        assert_eq!(candidates[0].fee, money("60"));
+
+        // Last level already sent
+        reminded.reminder_level = Some(ReminderLevel::new(2));
+        assert!(DunningPolicy::default()
+            .candidates(&[reminded], as_of())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_held_invoices_are_skipped() {
+        let mut disputed = invoice("inv-1", (2025, 2, 1), "100");
+        disputed.remarks = Some("Order 55\n[DISPUTED] Damaged goods".to_string());
+
+        assert!(DunningPolicy::default()
+            .candidates(&[disputed], as_of())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_min_days_between_reminders() {
+        let mut recent = invoice("inv-1", (2025, 2, 1), "100");
+        recent.reminder_level = Some(ReminderLevel::FIRST);
+        recent.last_reminder_date = Some(Utc.with_ymd_and_hms(2025, 3, 25, 0, 0, 0).unwrap());
+
+        assert!(DunningPolicy::default()
+            .candidates(&[recent], as_of())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_interest_candidates() {
+        let mut overdue = invoice("inv-1", (2025, 1, 30), "10000");
+        overdue.invoice_number = Some("1001".to_string());
+        overdue.customer_id = Some("cust-1".into());
+        let mut recent = invoice("inv-2", (2025, 3, 28), "10000");
+        recent.customer_id = Some("cust-1".into());
+
+        let mut policy = InterestPolicy::new(money("10.5"));
+        policy.grace_days = 5;
+        let candidates = policy.candidates(&[overdue, recent], as_of());
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].days_overdue, 60);
+        // 10000 * 10.5% * 60 / 365
+        assert_eq!(candidates[0].interest, money("172.60"));
+
+        let interest_invoice = policy.interest_invoice(&candidates[0], as_of());
+        assert_eq!(interest_invoice.customer_id.as_deref(), Some("cust-1"));
+        assert_eq!(interest_invoice.rows[0].unit_price, Some(money("172.60")));
+        assert_eq!(
+            interest_invoice.rows[0].text.as_deref(),
+            Some("Interest on invoice 1001, 60 days at 10.5%")
+        );
+
+        policy.min_interest = money("200");
+        assert!(policy
+            .candidates(&[invoice("inv-1", (2025, 1, 30), "10000")], as_of())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_policy_from_toml_style_json() {
+        let policy: DunningPolicy = serde_json::from_str(
+            r#"{"levels": [{"days_overdue": 5, "fee": 25.0, "text": "Please pay"}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(policy.levels.len(), 1);
+        assert_eq!(policy.levels[0].fee, money("25"));
+        assert_eq!(policy.min_days_between, 14);
+    }
+}
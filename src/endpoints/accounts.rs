@@ -26,6 +26,8 @@ impl<'a> AccountsEndpoint<'a> {
         }
     }
 
+    crate::__list_all_impl!("/accounts", Account);
+
     /// List accounts for a specific fiscal year.
     pub async fn list_by_fiscal_year(
         &self,
@@ -73,6 +75,16 @@ impl<'a> AccountsEndpoint<'a> {
         self.client.get(&path).await
     }
 
+    /// Get account balances at a specific date with pagination.
+    pub async fn get_balances_with_params(
+        &self,
+        date: &str,
+        params: PaginationParams,
+    ) -> Result<PaginatedResponse<AccountBalance>> {
+        let path = format!("/accountbalances/{}", date);
+        self.client.get_with_params(&path, &params).await
+    }
+
     /// Get balance for a specific account at a specific date.
     pub async fn get_balance(&self, account_number: &str, date: &str) -> Result<AccountBalance> {
         let path = format!("/accountbalances/{}/{}", account_number, date);
@@ -1,9 +1,54 @@
 //! Approval endpoints API.
+//!
+//! A supplier invoice goes through approval in three steps:
+//!
+//! 1. [`request_supplier_invoice_approval`](ApprovalsEndpoint::request_supplier_invoice_approval)
+//!    assigns an approver and marks the invoice as waiting.
+//! 2. The approver finds it with
+//!    [`pending_supplier_invoices`](ApprovalsEndpoint::pending_supplier_invoices).
+//! 3. [`approve_supplier_invoice`](ApprovalsEndpoint::approve_supplier_invoice)
+//!    records the decision. A rejection must say why.
+//!
+//! ```no_run
+//! use spiris::endpoints::approvals::ApprovalAction;
+//! use spiris::{AccessToken, Client};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+//! let approvals = client.approvals();
+//! approvals.request_supplier_invoice_approval("si-1", "user-1").await?;
+//!
+//! for invoice in approvals.pending_supplier_invoices(Some("user-1")).await? {
+//!     let id = invoice.id.as_deref().unwrap_or_default();
+//!     approvals
+//!         .approve_supplier_invoice(id, &ApprovalAction::reject("Wrong cost center"))
+//!         .await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The API has no approval flow for vouchers; only supplier invoices and VAT
+//! reports can be approved.
 
 use crate::client::Client;
-use crate::error::Result;
+use crate::endpoints::collect_pages;
+use crate::error::{Error, Result};
+use crate::types::SupplierInvoice;
 use serde::{Deserialize, Serialize};
 
+/// Supplier invoice approval status: no approval required.
+pub const APPROVAL_STATUS_NONE: i32 = 0;
+
+/// Supplier invoice approval status: waiting for approval.
+pub const APPROVAL_STATUS_PENDING: i32 = 1;
+
+/// Supplier invoice approval status: approved.
+pub const APPROVAL_STATUS_APPROVED: i32 = 2;
+
+/// Supplier invoice approval status: rejected.
+pub const APPROVAL_STATUS_REJECTED: i32 = 3;
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ApprovalAction {
@@ -11,6 +56,38 @@ pub struct ApprovalAction {
     pub comment: Option<String>,
 }
 
+impl ApprovalAction {
+    /// Approve, optionally with a comment.
+    pub fn approve(comment: Option<String>) -> Self {
+        Self {
+            is_approved: Some(true),
+            comment,
+        }
+    }
+
+    /// Reject with a comment explaining why.
+    pub fn reject(comment: impl Into<String>) -> Self {
+        Self {
+            is_approved: Some(false),
+            comment: Some(comment.into()),
+        }
+    }
+
+    /// Rejections need a comment so the submitter knows what to fix.
+    fn check(&self) -> Result<()> {
+        let has_comment = self
+            .comment
+            .as_deref()
+            .is_some_and(|c| !c.trim().is_empty());
+        if self.is_approved == Some(false) && !has_comment {
+            return Err(Error::InvalidRequest(
+                "a rejection needs a comment".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
 pub struct ApprovalsEndpoint<'a> {
     client: &'a Client,
 }
@@ -21,16 +98,83 @@ impl<'a> ApprovalsEndpoint<'a> {
     }
 
     pub async fn approve_vat_report(&self, id: &str, action: &ApprovalAction) -> Result<()> {
+        action.check()?;
         self.client
             .put::<(), _>(&format!("/approval/vatreport/{}", id), action)
             .await?;
         Ok(())
     }
 
+    /// Approve or reject a supplier invoice.
+    ///
+    /// Rejections without a comment are refused before reaching the API.
     pub async fn approve_supplier_invoice(&self, id: &str, action: &ApprovalAction) -> Result<()> {
+        action.check()?;
         self.client
             .put::<(), _>(&format!("/approval/supplierinvoice/{}", id), action)
             .await?;
         Ok(())
     }
+
+    /// Send a supplier invoice to `approver_user_id` for approval.
+    ///
+    /// Invoices that are already approved are left alone; a rejected invoice
+    /// can be sent again after it has been corrected.
+    pub async fn request_supplier_invoice_approval(
+        &self,
+        id: &str,
+        approver_user_id: &str,
+    ) -> Result<SupplierInvoice> {
+        let mut invoice = self.client.supplier_invoices().get(id).await?;
+        if invoice.approval_status == Some(APPROVAL_STATUS_APPROVED) {
+            return Err(Error::InvalidRequest(format!(
+                "supplier invoice {} is already approved",
+                id
+            )));
+        }
+        invoice.approval_status = Some(APPROVAL_STATUS_PENDING);
+        invoice.approver_user_id = Some(approver_user_id.to_string());
+        self.client.supplier_invoices().update(id, &invoice).await
+    }
+
+    /// List supplier invoices waiting for approval, ordered by due date.
+    ///
+    /// With a `user_id` only invoices assigned to that approver are returned.
+    pub async fn pending_supplier_invoices(
+        &self,
+        user_id: Option<&str>,
+    ) -> Result<Vec<SupplierInvoice>> {
+        let client = self.client;
+        let invoices =
+            collect_pages(|p| async move { client.supplier_invoices().list(Some(p)).await })
+                .await?;
+
+        let mut pending: Vec<SupplierInvoice> = invoices
+            .into_iter()
+            .filter(|invoice| invoice.approval_status == Some(APPROVAL_STATUS_PENDING))
+            .filter(|invoice| {
+                user_id.is_none_or(|user| invoice.approver_user_id.as_deref() == Some(user))
+            })
+            .collect();
+        pending.sort_by_key(|invoice| invoice.due_date);
+        Ok(pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejection_needs_comment() {
+        assert!(ApprovalAction::reject("Wrong cost center").check().is_ok());
+        assert!(ApprovalAction::reject("  ").check().is_err());
+        assert!(ApprovalAction::approve(None).check().is_ok());
+
+        let bare = ApprovalAction {
+            is_approved: Some(false),
+            comment: None,
+        };
+        assert!(bare.check().is_err());
+    }
 }
@@ -2,7 +2,10 @@
 
 use crate::client::Client;
 use crate::error::Result;
-use crate::types::{Article, PaginatedResponse, PaginationParams, QueryParams};
+use crate::query::Filter;
+use crate::types::{
+    Article, ArticleId, ArticlePatch, ListScope, PaginatedResponse, PaginationParams, QueryParams,
+};
 
 /// Articles endpoint for managing products and services.
 ///
@@ -61,6 +64,10 @@ impl<'a> ArticlesEndpoint<'a> {
         }
     }
 
+    crate::__list_all_impl!("/articles", Article);
+
+    crate::__sync_impl!("/articles", Article);
+
     /// Get a specific article by ID.
     ///
     /// # Arguments
@@ -70,14 +77,14 @@ impl<'a> ArticlesEndpoint<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use spiris::Client;
+    /// # use spiris::{Client, ArticleId};
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// let article = client.articles().get("article-id-123").await?;
+    /// let article = client.articles().get(&ArticleId::from("article-id-123")).await?;
     /// println!("Article: {:?}", article.name);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get(&self, id: &str) -> Result<Article> {
+    pub async fn get(&self, id: &ArticleId) -> Result<Article> {
         let path = format!("/articles/{}", id);
         self.client.get(&path).await
     }
@@ -104,6 +111,7 @@ impl<'a> ArticlesEndpoint<'a> {
     /// # }
     /// ```
     pub async fn create(&self, article: &Article) -> Result<Article> {
+        self.client.validate_request(article)?;
         self.client.post("/articles", article).await
     }
 
@@ -125,11 +133,35 @@ impl<'a> ArticlesEndpoint<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn update(&self, id: &str, article: &Article) -> Result<Article> {
+    pub async fn update(&self, id: &ArticleId, article: &Article) -> Result<Article> {
+        self.client.validate_request(article)?;
         let path = format!("/articles/{}", id);
         self.client.put(&path, article).await
     }
 
+    /// Change only the fields set or cleared in `patch`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use spiris::{money, ArticleId, ArticlePatch, Client};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let patch = ArticlePatch::new().sales_price(money!(1200.0));
+    /// client
+    ///     .articles()
+    ///     .update_partial(&ArticleId::from("article-id-123"), &patch)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update_partial(&self, id: &ArticleId, patch: &ArticlePatch) -> Result<Article> {
+        let path = format!("/articles/{}", id);
+        self.client.patch(&path, patch).await
+    }
+
+    crate::__get_versioned_impl!("/articles", Article, ArticleId);
+    crate::__update_if_match_impl!("/articles", Article, ArticleId, validate);
+
     /// Delete an article.
     ///
     /// # Arguments
@@ -139,13 +171,13 @@ impl<'a> ArticlesEndpoint<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use spiris::Client;
+    /// # use spiris::{Client, ArticleId};
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// client.articles().delete("article-id-123").await?;
+    /// client.articles().delete(&ArticleId::from("article-id-123")).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn delete(&self, id: &str) -> Result<()> {
+    pub async fn delete(&self, id: &ArticleId) -> Result<()> {
         let path = format!("/articles/{}", id);
         self.client.delete(&path).await
     }
@@ -182,10 +214,26 @@ impl<'a> ArticlesEndpoint<'a> {
             pagination: Option<PaginationParams>,
         }
 
+        let (query, pagination) = query.resolve_scope(pagination);
         let params = CombinedParams { query, pagination };
         self.client.get_with_params("/articles", &params).await
     }
 
+    /// Find a article by article number.
+    ///
+    /// Deactivated articles keep their number, so use [`ListScope::ActiveOnly`]
+    /// unless an inactive article is acceptable. With [`ListScope::All`] an
+    /// active match is preferred over an inactive one.
+    pub async fn get_by_number(&self, number: &str, scope: ListScope) -> Result<Option<Article>> {
+        let query = QueryParams::new()
+            .filter_by(Filter::field("ArticleNumber").eq(number))
+            .scope(scope);
+        let matches = self.search(query, None).await?.data;
+        Ok(crate::endpoints::pick_in_scope(matches, scope, |m| {
+            m.is_active
+        }))
+    }
+
     /// Stream all articles, automatically paginating through results.
     ///
     /// Requires the `stream` feature.
@@ -204,4 +252,53 @@ impl<'a> ArticlesEndpoint<'a> {
     ) -> impl futures::Stream<Item = Result<Article>> + '_ {
         crate::paginated_stream!(page_size, |params| self.list(Some(params)))
     }
+
+    /// Stream all articles with a custom page size and prefetching.
+    ///
+    /// Requires the `stream` feature.
+    #[cfg(feature = "stream")]
+    pub fn list_stream_with(
+        &self,
+        options: crate::pagination::StreamOptions,
+    ) -> impl futures::Stream<Item = Result<Article>> + '_ {
+        crate::pagination::paginated_stream_with(options, move |params| self.list(Some(params)))
+    }
+
+    /// Stream the articles matching `query`, following pages as the stream
+    /// is consumed.
+    ///
+    /// Requires the `stream` feature.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::TryStreamExt;
+    /// use spiris::{AccessToken, Client, QueryParams};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+    /// let query = QueryParams::new().filter("IsActive eq true");
+    /// let articles: Vec<_> = client.articles().stream(query).try_collect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "stream")]
+    pub fn stream(&self, query: QueryParams) -> impl futures::Stream<Item = Result<Article>> + '_ {
+        self.stream_with(query, crate::pagination::StreamOptions::default())
+    }
+
+    /// Stream the articles matching `query` with a custom page size and
+    /// prefetching.
+    ///
+    /// Requires the `stream` feature.
+    #[cfg(feature = "stream")]
+    pub fn stream_with(
+        &self,
+        query: QueryParams,
+        options: crate::pagination::StreamOptions,
+    ) -> impl futures::Stream<Item = Result<Article>> + '_ {
+        crate::pagination::paginated_stream_with(options, move |params| {
+            self.search(query.clone(), Some(params))
+        })
+    }
 }
@@ -1,9 +1,28 @@
 //! Attachments API endpoint.
 
 use crate::client::Client;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::error::Error;
 use crate::error::Result;
-use crate::types::{Attachment, AttachmentLink, PaginatedResponse, PaginationParams};
+use crate::types::{
+    Attachment, AttachmentLink, AttachmentUpload, PaginatedResponse, PaginationParams,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
 
+/// Attachment link document type: supplier invoice.
+pub const DOCUMENT_TYPE_SUPPLIER_INVOICE: i32 = 1;
+
+/// Attachment link document type: receipt.
+pub const DOCUMENT_TYPE_RECEIPT: i32 = 2;
+
+/// Attachment link document type: voucher.
+pub const DOCUMENT_TYPE_VOUCHER: i32 = 3;
+
+/// Attachment link document type: supplier invoice draft.
+pub const DOCUMENT_TYPE_SUPPLIER_INVOICE_DRAFT: i32 = 4;
+
+/// Attachments endpoint for receipts, scanned invoices and other files.
 pub struct AttachmentsEndpoint<'a> {
     client: &'a Client,
 }
@@ -13,6 +32,7 @@ impl<'a> AttachmentsEndpoint<'a> {
         Self { client }
     }
 
+    /// List attachments with optional pagination.
     pub async fn list(
         &self,
         params: Option<PaginationParams>,
@@ -24,27 +44,141 @@ impl<'a> AttachmentsEndpoint<'a> {
         }
     }
 
+    crate::__list_all_impl!("/attachments", Attachment);
+
+    /// Get an attachment's metadata.
     pub async fn get(&self, id: &str) -> Result<Attachment> {
         self.client.get(&format!("/attachments/{}", id)).await
     }
 
+    /// Delete an attachment.
     pub async fn delete(&self, id: &str) -> Result<()> {
         self.client.delete(&format!("/attachments/{}", id)).await
     }
 
+    /// Download an attachment's content.
     pub async fn get_content(&self, id: &str) -> Result<Vec<u8>> {
         self.client
             .get_bytes(&format!("/attachments/{}/content", id))
             .await
     }
 
+    /// Upload a file.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use spiris::{AttachmentUpload, Client};
+    /// # async fn example(client: &Client, pdf: Vec<u8>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let upload = AttachmentUpload::new("invoice-1042.pdf", "application/pdf", &pdf);
+    /// let attachment = client.attachments().upload(&upload).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn upload(&self, upload: &AttachmentUpload) -> Result<Attachment> {
+        self.client.post("/attachments", upload).await
+    }
+
+    /// Upload a file from disk.
+    ///
+    /// The content type is taken from the file extension; PDF and common image
+    /// formats are supported.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn upload_file(&self, path: impl AsRef<Path>) -> Result<Attachment> {
+        let path = path.as_ref();
+        let content_type = content_type_for(path).ok_or_else(|| {
+            Error::InvalidRequest(format!("unsupported attachment type: {}", path.display()))
+        })?;
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let content = tokio::fs::read(path).await.map_err(|e| {
+            Error::InvalidRequest(format!("failed to read {}: {}", path.display(), e))
+        })?;
+        self.upload(&AttachmentUpload::new(file_name, content_type, &content))
+            .await
+    }
+
+    /// Create a link between an attachment and a document.
     pub async fn create_link(&self, link: &AttachmentLink) -> Result<AttachmentLink> {
         self.client.post("/attachmentlinks", link).await
     }
 
+    /// Attach to a supplier invoice.
+    pub async fn link_to_supplier_invoice(
+        &self,
+        attachment_id: &str,
+        supplier_invoice_id: &str,
+    ) -> Result<AttachmentLink> {
+        self.link(
+            attachment_id,
+            supplier_invoice_id,
+            DOCUMENT_TYPE_SUPPLIER_INVOICE,
+        )
+        .await
+    }
+
+    /// Attach to a voucher.
+    pub async fn link_to_voucher(
+        &self,
+        attachment_id: &str,
+        voucher_id: &str,
+    ) -> Result<AttachmentLink> {
+        self.link(attachment_id, voucher_id, DOCUMENT_TYPE_VOUCHER)
+            .await
+    }
+
+    async fn link(
+        &self,
+        attachment_id: &str,
+        document_id: &str,
+        document_type: i32,
+    ) -> Result<AttachmentLink> {
+        self.create_link(&AttachmentLink {
+            attachment_id: Some(attachment_id.to_string()),
+            document_id: Some(document_id.to_string()),
+            document_type: Some(document_type),
+        })
+        .await
+    }
+
+    /// Delete an attachment link.
     pub async fn delete_link(&self, id: &str) -> Result<()> {
         self.client
             .delete(&format!("/attachmentlinks/{}", id))
             .await
     }
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+fn content_type_for(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    Some(match extension.as_str() {
+        "pdf" => "application/pdf",
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "tif" | "tiff" => "image/tiff",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_type_for() {
+        assert_eq!(
+            content_type_for(Path::new("kvitto.JPG")),
+            Some("image/jpeg")
+        );
+        assert_eq!(
+            content_type_for(Path::new("/tmp/faktura.pdf")),
+            Some("application/pdf")
+        );
+        assert_eq!(content_type_for(Path::new("notes.txt")), None);
+        assert_eq!(content_type_for(Path::new("README")), None);
+    }
+}
@@ -1,9 +1,122 @@
 //! Bank accounts API endpoint.
 
+use crate::endpoints::collect_pages;
 use crate::types::BankAccount;
 
 crate::define_endpoint! {
     /// Bank accounts endpoint for managing payment accounts.
     BankAccountsEndpoint, "/bankaccounts", BankAccount,
-    caps: [list, get, create, update, delete]
+    caps: [list, get, delete],
+    extra: {
+        /// Create a new bank account.
+        ///
+        /// The IBAN, BIC, bankgiro and plusgiro numbers are checked and
+        /// sent normalized; see [`validation`](crate::validation).
+        pub async fn create(&self, item: &BankAccount) -> crate::error::Result<BankAccount> {
+            self.client.validate_request(item)?;
+            let item = crate::validation::normalize_bank_account(item)?;
+            self.client.post("/bankaccounts", &*item).await
+        }
+
+        /// Update an existing bank account.
+        ///
+        /// The account numbers are checked and sent normalized, as in
+        /// [`create`](Self::create).
+        pub async fn update(
+            &self,
+            id: &str,
+            item: &BankAccount,
+        ) -> crate::error::Result<BankAccount> {
+            self.client.validate_request(item)?;
+            let item = crate::validation::normalize_bank_account(item)?;
+            self.client.put(&format!("/bankaccounts/{}", id), &*item).await
+        }
+
+        crate::__update_if_match_impl!("/bankaccounts", BankAccount, validate);
+
+        /// List all active bank accounts, following every page.
+        pub async fn list_active(&self) -> crate::error::Result<Vec<BankAccount>> {
+            let accounts = collect_pages(|p| async move { self.list(Some(p)).await }).await?;
+            Ok(accounts
+                .into_iter()
+                .filter(|a| a.is_active != Some(false))
+                .collect())
+        }
+
+        /// Pick the account to register a payment in `currency_code` on.
+        ///
+        /// Prefers the default account among those in the currency. Accounts
+        /// without a currency code are used if none matches.
+        pub async fn for_currency(
+            &self,
+            currency_code: &str,
+        ) -> crate::error::Result<Option<BankAccount>> {
+            let accounts = self.list_active().await?;
+            Ok(select_for_currency(&accounts, currency_code).cloned())
+        }
+
+        /// Find an active account by bankgiro, plusgiro, IBAN or account
+        /// number. Spaces and dashes are ignored.
+        pub async fn find_by_number(
+            &self,
+            number: &str,
+        ) -> crate::error::Result<Option<BankAccount>> {
+            let accounts = self.list_active().await?;
+            Ok(accounts.into_iter().find(|a| a.has_number(number)))
+        }
+    }
+}
+
+fn select_for_currency<'a>(
+    accounts: &'a [BankAccount],
+    currency_code: &str,
+) -> Option<&'a BankAccount> {
+    let prefer_default = |candidates: Vec<&'a BankAccount>| {
+        candidates
+            .iter()
+            .find(|a| a.is_default == Some(true))
+            .or(candidates.first())
+            .copied()
+    };
+
+    let (in_currency, without_currency): (Vec<_>, Vec<_>) = accounts
+        .iter()
+        .filter(|a| {
+            a.currency_code
+                .as_deref()
+                .is_none_or(|code| code.eq_ignore_ascii_case(currency_code))
+        })
+        .partition(|a| a.currency_code.is_some());
+
+    prefer_default(in_currency).or_else(|| prefer_default(without_currency))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(id: &str, currency: Option<&str>, is_default: bool) -> BankAccount {
+        BankAccount {
+            id: Some(id.to_string()),
+            currency_code: currency.map(str::to_string),
+            is_default: Some(is_default),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_select_for_currency() {
+        let accounts = vec![
+            account("sek", Some("SEK"), false),
+            account("sek-default", Some("SEK"), true),
+            account("eur", Some("EUR"), false),
+            account("any", None, true),
+        ];
+
+        let id = |currency| select_for_currency(&accounts, currency).and_then(|a| a.id.as_deref());
+        assert_eq!(id("SEK"), Some("sek-default"));
+        assert_eq!(id("eur"), Some("eur"));
+        assert_eq!(id("USD"), Some("any"));
+        assert!(select_for_currency(&accounts[..3], "USD").is_none());
+    }
 }
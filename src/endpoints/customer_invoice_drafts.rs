@@ -31,6 +31,8 @@ impl<'a> CustomerInvoiceDraftsEndpoint<'a> {
         }
     }
 
+    crate::__list_all_impl!("/customerinvoicedrafts", CustomerInvoiceDraft);
+
     /// Get a specific customer invoice draft by ID.
     pub async fn get(&self, id: &str) -> Result<CustomerInvoiceDraft> {
         let path = format!("/customerinvoicedrafts/{}", id);
@@ -52,6 +54,9 @@ impl<'a> CustomerInvoiceDraftsEndpoint<'a> {
         self.client.put(&path, draft).await
     }
 
+    crate::__get_versioned_impl!("/customerinvoicedrafts", CustomerInvoiceDraft);
+    crate::__update_if_match_impl!("/customerinvoicedrafts", CustomerInvoiceDraft);
+
     /// Delete a customer invoice draft.
     pub async fn delete(&self, id: &str) -> Result<()> {
         let path = format!("/customerinvoicedrafts/{}", id);
@@ -84,6 +89,7 @@ impl<'a> CustomerInvoiceDraftsEndpoint<'a> {
             pagination: Option<PaginationParams>,
         }
 
+        let (query, pagination) = query.resolve_scope(pagination);
         let params = CombinedParams { query, pagination };
         self.client
             .get_with_params("/customerinvoicedrafts", &params)
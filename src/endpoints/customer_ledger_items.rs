@@ -28,6 +28,8 @@ impl<'a> CustomerLedgerItemsEndpoint<'a> {
         }
     }
 
+    crate::__list_all_impl!("/customerledgeritems", CustomerLedgerItem);
+
     /// Get a specific customer ledger item by ID.
     pub async fn get(&self, id: &str) -> Result<CustomerLedgerItem> {
         let path = format!("/customerledgeritems/{}", id);
@@ -53,6 +55,7 @@ impl<'a> CustomerLedgerItemsEndpoint<'a> {
             pagination: Option<PaginationParams>,
         }
 
+        let (query, pagination) = query.resolve_scope(pagination);
         let params = CombinedParams { query, pagination };
         self.client
             .get_with_params("/customerledgeritems", &params)
@@ -1,8 +1,229 @@
 //! Customers API endpoint.
 
 use crate::client::Client;
-use crate::error::Result;
-use crate::types::{Customer, PaginatedResponse, PaginationParams, QueryParams};
+use crate::endpoints::collect_pages;
+use crate::error::{Error, Result};
+use crate::query::Filter;
+use crate::types::{
+    Customer, CustomerId, CustomerPatch, InvoiceId, ListScope, PaginatedResponse, PaginationParams,
+    QueryParams,
+};
+
+/// Kind of document moved by [`CustomersEndpoint::merge`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergedDocumentKind {
+    /// Customer invoice draft.
+    InvoiceDraft,
+    /// Sales order.
+    Order,
+    /// Sales quotation.
+    Quotation,
+}
+
+/// A document that belonged to the duplicate customer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergedDocument {
+    /// Kind of document.
+    pub kind: MergedDocumentKind,
+    /// Document ID.
+    pub id: String,
+}
+
+/// A document [`CustomersEndpoint::merge`] could not move.
+#[derive(Debug)]
+pub struct MergeFailure {
+    /// The document, still on the duplicate.
+    pub document: MergedDocument,
+    /// The error from the API.
+    pub error: Error,
+}
+
+/// Outcome of [`CustomersEndpoint::merge`].
+#[derive(Debug)]
+pub struct CustomerMerge {
+    /// The primary customer as saved after the merge.
+    pub primary: Customer,
+    /// The duplicate as saved after the merge. Still active unless
+    /// [`is_complete`](Self::is_complete).
+    pub duplicate: Customer,
+    /// Fields copied from the duplicate because the primary had no value.
+    pub copied_fields: Vec<&'static str>,
+    /// Documents moved to the primary.
+    pub moved: Vec<MergedDocument>,
+    /// Documents the API refused to move.
+    pub failures: Vec<MergeFailure>,
+    /// IDs of invoices left on the duplicate. Posted invoices cannot change
+    /// customer; they stay in the duplicate's ledger.
+    pub invoices_kept: Vec<InvoiceId>,
+}
+
+impl CustomerMerge {
+    /// Whether every open document was moved.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Options for [`CustomersEndpoint::bulk_create`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BulkOptions {
+    /// Customers taken from the input at a time.
+    pub chunk_size: usize,
+    /// Create requests in flight at once.
+    pub concurrency: usize,
+    /// Skip customers whose customer number or organisation number is
+    /// already in the API.
+    pub skip_existing: bool,
+}
+
+impl Default for BulkOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: 100,
+            concurrency: 4,
+            skip_existing: true,
+        }
+    }
+}
+
+impl BulkOptions {
+    /// Chunks of 100, 4 requests at a time, skipping existing customers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how many customers are taken from the input at a time.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Set how many create requests run at once.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Set whether customers already in the API are skipped. Checking
+    /// lists every customer once before the import starts.
+    pub fn skip_existing(mut self, skip_existing: bool) -> Self {
+        self.skip_existing = skip_existing;
+        self
+    }
+}
+
+/// What happened to one customer in [`CustomersEndpoint::bulk_create`].
+#[derive(Debug)]
+pub enum BulkOutcome {
+    /// Created; the customer as saved.
+    Created(Box<Customer>),
+    /// Skipped because the customer at this input position has the same
+    /// customer number or organisation number.
+    DuplicateOf(usize),
+    /// Skipped because a customer with the same customer number or
+    /// organisation number is already in the API.
+    AlreadyExists(Option<CustomerId>),
+    /// Refused by validation or the API.
+    Failed(Error),
+}
+
+/// A customer in a [`BulkReport`].
+#[derive(Debug)]
+pub struct BulkItem {
+    /// Position of the customer in the input.
+    pub index: usize,
+    /// What happened to it.
+    pub outcome: BulkOutcome,
+}
+
+/// Outcome of [`CustomersEndpoint::bulk_create`].
+#[derive(Debug, Default)]
+pub struct BulkReport {
+    /// Every input customer, in input order.
+    pub items: Vec<BulkItem>,
+}
+
+impl BulkReport {
+    /// Customers created.
+    pub fn created(&self) -> impl Iterator<Item = &Customer> {
+        self.items.iter().filter_map(|item| match &item.outcome {
+            BulkOutcome::Created(customer) => Some(customer.as_ref()),
+            _ => None,
+        })
+    }
+
+    /// Input positions and errors of the customers that failed.
+    pub fn failures(&self) -> impl Iterator<Item = (usize, &Error)> {
+        self.items.iter().filter_map(|item| match &item.outcome {
+            BulkOutcome::Failed(error) => Some((item.index, error)),
+            _ => None,
+        })
+    }
+
+    /// Whether no customer failed. Skipped duplicates don't count as
+    /// failures.
+    pub fn is_complete(&self) -> bool {
+        self.failures().next().is_none()
+    }
+}
+
+/// Customer numbers and organisation numbers already claimed during a bulk
+/// import.
+#[cfg(feature = "stream")]
+#[derive(Debug, Default)]
+struct BulkKeys {
+    claimed: std::collections::HashMap<String, usize>,
+    existing: std::collections::HashMap<String, Option<CustomerId>>,
+}
+
+#[cfg(feature = "stream")]
+impl BulkKeys {
+    fn keys(customer: &Customer) -> Vec<String> {
+        let normalize = |value: &str| -> String {
+            value
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_uppercase)
+                .collect()
+        };
+        let mut keys = Vec::new();
+        if let Some(number) = customer.customer_number.as_deref().map(str::trim) {
+            if !number.is_empty() {
+                keys.push(format!("number:{}", number));
+            }
+        }
+        if let Some(org) = customer.corporate_identity_number.as_deref().map(normalize) {
+            if !org.is_empty() {
+                keys.push(format!("org:{}", org));
+            }
+        }
+        keys
+    }
+
+    fn add_existing(&mut self, customer: &Customer) {
+        for key in Self::keys(customer) {
+            self.existing.insert(key, customer.id.clone());
+        }
+    }
+
+    /// Claim the keys of the input customer at `index`, or say why it is
+    /// skipped.
+    fn claim(&mut self, index: usize, customer: &Customer) -> Option<BulkOutcome> {
+        let keys = Self::keys(customer);
+        for key in &keys {
+            if let Some(id) = self.existing.get(key) {
+                return Some(BulkOutcome::AlreadyExists(id.clone()));
+            }
+            if let Some(first) = self.claimed.get(key) {
+                return Some(BulkOutcome::DuplicateOf(*first));
+            }
+        }
+        for key in keys {
+            self.claimed.insert(key, index);
+        }
+        None
+    }
+}
 
 /// Customers endpoint for managing customer records.
 ///
@@ -61,6 +282,10 @@ impl<'a> CustomersEndpoint<'a> {
         }
     }
 
+    crate::__list_all_impl!("/customers", Customer);
+
+    crate::__sync_impl!("/customers", Customer);
+
     /// Get a specific customer by ID.
     ///
     /// # Arguments
@@ -70,20 +295,27 @@ impl<'a> CustomersEndpoint<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use spiris::Client;
+    /// # use spiris::{Client, CustomerId};
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// let customer = client.customers().get("customer-id-123").await?;
+    /// let customer = client.customers().get(&CustomerId::from("customer-id-123")).await?;
     /// println!("Customer: {:?}", customer.name);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get(&self, id: &str) -> Result<Customer> {
+    pub async fn get(&self, id: &CustomerId) -> Result<Customer> {
         let path = format!("/customers/{}", id);
         self.client.get(&path).await
     }
 
     /// Create a new customer.
     ///
+    /// With [`ClientConfig::validate_requests`](crate::ClientConfig::validate_requests)
+    /// enabled, the organisation or personal number in
+    /// `corporate_identity_number` of a customer with a Swedish invoice
+    /// address (country code `SE`) is checked and sent in the normalized
+    /// form `NNNNNN-NNNN`; an invalid one fails with [`Error::Validation`](crate::Error::Validation)
+    /// before anything is sent. See [`IdentityNumber`](crate::validation::IdentityNumber).
+    ///
     /// # Arguments
     ///
     /// * `customer` - The customer data to create
@@ -103,7 +335,12 @@ impl<'a> CustomersEndpoint<'a> {
     /// # }
     /// ```
     pub async fn create(&self, customer: &Customer) -> Result<Customer> {
-        self.client.post("/customers", customer).await
+        self.client.validate_request(customer)?;
+        if !self.client.validates_requests() {
+            return self.client.post("/customers", customer).await;
+        }
+        let customer = crate::validation::normalize_customer(customer)?;
+        self.client.post("/customers", &*customer).await
     }
 
     /// Update an existing customer.
@@ -116,19 +353,49 @@ impl<'a> CustomersEndpoint<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use spiris::Client;
+    /// # use spiris::{Client, CustomerId};
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// let mut customer = client.customers().get("customer-id-123").await?;
+    /// let id = CustomerId::from("customer-id-123");
+    /// let mut customer = client.customers().get(&id).await?;
     /// customer.email = Some("newemail@acme.com".to_string());
-    /// let updated = client.customers().update("customer-id-123", &customer).await?;
+    /// let updated = client.customers().update(&id, &customer).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn update(&self, id: &str, customer: &Customer) -> Result<Customer> {
+    pub async fn update(&self, id: &CustomerId, customer: &Customer) -> Result<Customer> {
+        self.client.validate_request(customer)?;
         let path = format!("/customers/{}", id);
         self.client.put(&path, customer).await
     }
 
+    /// Change only the fields set or cleared in `patch`.
+    ///
+    /// The customer doesn't have to be fetched first, and fields left out of
+    /// the patch keep their current values.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use spiris::{Client, CustomerId, CustomerPatch};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let patch = CustomerPatch::new()
+    ///     .email("billing@acme.se")
+    ///     .clear_phone();
+    /// let updated = client
+    ///     .customers()
+    ///     .update_partial(&CustomerId::from("customer-id-123"), &patch)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update_partial(&self, id: &CustomerId, patch: &CustomerPatch) -> Result<Customer> {
+        let path = format!("/customers/{}", id);
+        self.client.patch(&path, patch).await
+    }
+
+    crate::__get_versioned_impl!("/customers", Customer, CustomerId);
+    crate::__update_if_match_impl!("/customers", Customer, CustomerId, validate);
+
     /// Delete a customer.
     ///
     /// # Arguments
@@ -138,13 +405,13 @@ impl<'a> CustomersEndpoint<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use spiris::Client;
+    /// # use spiris::{Client, CustomerId};
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// client.customers().delete("customer-id-123").await?;
+    /// client.customers().delete(&CustomerId::from("customer-id-123")).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn delete(&self, id: &str) -> Result<()> {
+    pub async fn delete(&self, id: &CustomerId) -> Result<()> {
         let path = format!("/customers/{}", id);
         self.client.delete(&path).await
     }
@@ -181,10 +448,168 @@ impl<'a> CustomersEndpoint<'a> {
             pagination: Option<PaginationParams>,
         }
 
+        let (query, pagination) = query.resolve_scope(pagination);
         let params = CombinedParams { query, pagination };
         self.client.get_with_params("/customers", &params).await
     }
 
+    /// Find a customer by customer number.
+    ///
+    /// Deactivated customers keep their number, so use [`ListScope::ActiveOnly`]
+    /// unless an inactive customer is acceptable. With [`ListScope::All`] an
+    /// active match is preferred over an inactive one.
+    pub async fn get_by_number(&self, number: &str, scope: ListScope) -> Result<Option<Customer>> {
+        let query = QueryParams::new()
+            .filter_by(Filter::field("CustomerNumber").eq(number))
+            .scope(scope);
+        let matches = self.search(query, None).await?.data;
+        Ok(crate::endpoints::pick_in_scope(matches, scope, |m| {
+            m.is_active
+        }))
+    }
+
+    /// Merge a duplicate customer into a primary one.
+    ///
+    /// Contact fields the primary lacks are copied from the duplicate, and
+    /// the duplicate's invoice drafts, orders and quotations are moved to the
+    /// primary where the API allows it, and a note about the merge is added
+    /// to both customers. Documents that could not be moved and posted
+    /// invoices stay on the duplicate and are listed in the result.
+    ///
+    /// The duplicate is deactivated only if every document was moved and
+    /// after everything else is saved. If some documents failed, or an error
+    /// stops the merge part way, it stays active so the remaining documents
+    /// can be handled and the merge run again.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use spiris::{Client, CustomerId};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let primary = CustomerId::from("customer-id-123");
+    /// let duplicate = CustomerId::from("customer-id-456");
+    /// let merge = client.customers().merge(&primary, &duplicate).await?;
+    /// for failure in &merge.failures {
+    ///     println!("{:?} {} not moved: {}", failure.document.kind, failure.document.id, failure.error);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn merge(
+        &self,
+        primary_id: &CustomerId,
+        duplicate_id: &CustomerId,
+    ) -> Result<CustomerMerge> {
+        if primary_id == duplicate_id {
+            return Err(Error::InvalidRequest(
+                "cannot merge a customer into itself".to_string(),
+            ));
+        }
+
+        let mut primary = self.get(primary_id).await?;
+        let mut duplicate = self.get(duplicate_id).await?;
+        let copied_fields = copy_missing_fields(&mut primary, &duplicate);
+
+        let filter = Filter::field("CustomerId").eq(duplicate_id);
+        let belongs = |customer_id: &Option<CustomerId>| customer_id.as_ref() == Some(duplicate_id);
+        let mut attempts = Vec::new();
+
+        let drafts = collect_pages(|p| {
+            let query = QueryParams::new().filter_by(filter.clone());
+            async move {
+                self.client
+                    .customer_invoice_drafts()
+                    .search(query, Some(p))
+                    .await
+            }
+        })
+        .await?;
+        for mut draft in drafts.into_iter().filter(|d| belongs(&d.customer_id)) {
+            let Some(id) = draft.id.clone() else { continue };
+            draft.customer_id = Some(primary_id.clone());
+            let result = self
+                .client
+                .customer_invoice_drafts()
+                .update(&id, &draft)
+                .await;
+            attempts.push((MergedDocumentKind::InvoiceDraft, id, result.map(drop)));
+        }
+
+        let orders = collect_pages(|p| {
+            let query = QueryParams::new().filter_by(filter.clone());
+            async move { self.client.orders().search(query, Some(p)).await }
+        })
+        .await?;
+        for mut order in orders.into_iter().filter(|o| belongs(&o.customer_id)) {
+            let Some(id) = order.id.clone() else { continue };
+            order.customer_id = Some(primary_id.clone());
+            let result = self.client.orders().update(&id, &order).await;
+            attempts.push((MergedDocumentKind::Order, id, result.map(drop)));
+        }
+
+        let quotations = collect_pages(|p| {
+            let query = QueryParams::new().filter_by(filter.clone());
+            async move { self.client.quotations().search(query, Some(p)).await }
+        })
+        .await?;
+        for mut quotation in quotations.into_iter().filter(|q| belongs(&q.customer_id)) {
+            let Some(id) = quotation.id.clone() else {
+                continue;
+            };
+            quotation.customer_id = Some(primary_id.clone());
+            let result = self.client.quotations().update(&id, &quotation).await;
+            attempts.push((MergedDocumentKind::Quotation, id, result.map(drop)));
+        }
+
+        let invoices_kept = collect_pages(|p| {
+            let query = QueryParams::new().filter_by(filter.clone());
+            async move { self.client.invoices().search(query, Some(p)).await }
+        })
+        .await?
+        .into_iter()
+        .filter(|i| belongs(&i.customer_id))
+        .filter_map(|i| i.id)
+        .collect();
+
+        let mut moved = Vec::new();
+        let mut failures = Vec::new();
+        for (kind, id, result) in attempts {
+            let document = MergedDocument { kind, id };
+            match result {
+                Ok(()) => moved.push(document),
+                Err(error) => failures.push(MergeFailure { document, error }),
+            }
+        }
+
+        let today = self.client.clock().today();
+        append_note(
+            &mut primary.note,
+            format!(
+                "{}: merged with duplicate {}",
+                today,
+                describe(&duplicate, duplicate_id)
+            ),
+        );
+        append_note(
+            &mut duplicate.note,
+            format!("{}: merged into {}", today, describe(&primary, primary_id)),
+        );
+        let primary = self.update(primary_id, &primary).await?;
+        if failures.is_empty() {
+            duplicate.is_active = Some(false);
+        }
+        let duplicate = self.update(duplicate_id, &duplicate).await?;
+
+        Ok(CustomerMerge {
+            primary,
+            duplicate,
+            copied_fields,
+            moved,
+            failures,
+            invoices_kept,
+        })
+    }
+
     /// Stream all customers, automatically paginating through results.
     ///
     /// This method returns a Stream that fetches customers page by page,
@@ -228,4 +653,259 @@ impl<'a> CustomersEndpoint<'a> {
     ) -> impl futures::Stream<Item = Result<Customer>> + '_ {
         crate::paginated_stream!(page_size, |params| self.list(Some(params)))
     }
+
+    /// Stream all customers with a custom page size and prefetching.
+    ///
+    /// Requires the `stream` feature.
+    #[cfg(feature = "stream")]
+    pub fn list_stream_with(
+        &self,
+        options: crate::pagination::StreamOptions,
+    ) -> impl futures::Stream<Item = Result<Customer>> + '_ {
+        crate::pagination::paginated_stream_with(options, move |params| self.list(Some(params)))
+    }
+
+    /// Stream the customers matching `query`, following pages as the stream
+    /// is consumed.
+    ///
+    /// Requires the `stream` feature.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::TryStreamExt;
+    /// use spiris::{AccessToken, Client, QueryParams};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+    /// let query = QueryParams::new().filter("IsActive eq true");
+    /// let customers: Vec<_> = client.customers().stream(query).try_collect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "stream")]
+    pub fn stream(&self, query: QueryParams) -> impl futures::Stream<Item = Result<Customer>> + '_ {
+        self.stream_with(query, crate::pagination::StreamOptions::default())
+    }
+
+    /// Create many customers, a few at a time.
+    ///
+    /// The input is taken in chunks, and each chunk's customers are created
+    /// with up to [`BulkOptions::concurrency`] requests in flight, each still
+    /// through the client's rate limiter. A customer with the same customer
+    /// number or organisation number as an earlier one in the input, or as
+    /// a customer already in the API, is skipped. A customer that fails
+    /// doesn't stop the rest; every input customer gets an outcome in the
+    /// report.
+    ///
+    /// Fails only if the existing customers cannot be listed. Requires the
+    /// `stream` feature.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use spiris::{Client, Customer};
+    /// use spiris::endpoints::customers::BulkOptions;
+    /// # async fn example(client: &Client, rows: Vec<Customer>) -> spiris::Result<()> {
+    /// let report = client
+    ///     .customers()
+    ///     .bulk_create(rows, BulkOptions::new().concurrency(8))
+    ///     .await?;
+    /// println!("created {}", report.created().count());
+    /// for (index, error) in report.failures() {
+    ///     eprintln!("row {}: {}", index, error);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "stream")]
+    pub async fn bulk_create(
+        &self,
+        customers: impl IntoIterator<Item = Customer>,
+        options: BulkOptions,
+    ) -> Result<BulkReport> {
+        use futures::stream::{self, StreamExt};
+
+        let mut keys = BulkKeys::default();
+        if options.skip_existing {
+            for existing in self.list_all(None).await? {
+                keys.add_existing(&existing);
+            }
+        }
+
+        let mut items = Vec::new();
+        let mut input = customers.into_iter().enumerate();
+        loop {
+            let chunk: Vec<(usize, Customer)> =
+                input.by_ref().take(options.chunk_size.max(1)).collect();
+            if chunk.is_empty() {
+                break;
+            }
+
+            let mut pending = Vec::new();
+            for (index, customer) in chunk {
+                match keys.claim(index, &customer) {
+                    Some(outcome) => items.push(BulkItem { index, outcome }),
+                    None => pending.push((index, customer)),
+                }
+            }
+            let created: Vec<BulkItem> = stream::iter(pending)
+                .map(|(index, customer)| async move {
+                    let outcome = match self.create(&customer).await {
+                        Ok(created) => BulkOutcome::Created(Box::new(created)),
+                        Err(error) => BulkOutcome::Failed(error),
+                    };
+                    BulkItem { index, outcome }
+                })
+                .buffer_unordered(options.concurrency.max(1))
+                .collect()
+                .await;
+            items.extend(created);
+        }
+
+        items.sort_by_key(|item| item.index);
+        Ok(BulkReport { items })
+    }
+
+    /// Stream the customers matching `query` with a custom page size and
+    /// prefetching.
+    ///
+    /// Requires the `stream` feature.
+    #[cfg(feature = "stream")]
+    pub fn stream_with(
+        &self,
+        query: QueryParams,
+        options: crate::pagination::StreamOptions,
+    ) -> impl futures::Stream<Item = Result<Customer>> + '_ {
+        crate::pagination::paginated_stream_with(options, move |params| {
+            self.search(query.clone(), Some(params))
+        })
+    }
+}
+
+/// Copy the duplicate's contact fields onto the primary where the primary
+/// has none, returning the API names of the copied fields.
+fn copy_missing_fields(primary: &mut Customer, duplicate: &Customer) -> Vec<&'static str> {
+    fn fill<T: Clone>(target: &mut Option<T>, source: &Option<T>) -> bool {
+        if target.is_none() && source.is_some() {
+            *target = source.clone();
+            return true;
+        }
+        false
+    }
+    fn fill_text(target: &mut Option<String>, source: &Option<String>) -> bool {
+        if target.as_deref().is_some_and(|t| t.trim().is_empty()) {
+            *target = None;
+        }
+        fill(target, &source.clone().filter(|s| !s.trim().is_empty()))
+    }
+
+    let copied = [
+        (
+            "CorporateIdentityNumber",
+            fill_text(
+                &mut primary.corporate_identity_number,
+                &duplicate.corporate_identity_number,
+            ),
+        ),
+        ("Email", fill_text(&mut primary.email, &duplicate.email)),
+        ("Phone", fill_text(&mut primary.phone, &duplicate.phone)),
+        (
+            "MobilePhone",
+            fill_text(&mut primary.mobile_phone, &duplicate.mobile_phone),
+        ),
+        (
+            "Website",
+            fill_text(&mut primary.website, &duplicate.website),
+        ),
+        (
+            "InvoiceAddress",
+            fill(&mut primary.invoice_address, &duplicate.invoice_address),
+        ),
+        (
+            "DeliveryAddress",
+            fill(&mut primary.delivery_address, &duplicate.delivery_address),
+        ),
+        (
+            "DeliveryMethodId",
+            fill_text(
+                &mut primary.delivery_method_id,
+                &duplicate.delivery_method_id,
+            ),
+        ),
+        (
+            "DeliveryTermId",
+            fill_text(&mut primary.delivery_term_id, &duplicate.delivery_term_id),
+        ),
+        (
+            "PaymentTermsInDays",
+            fill(
+                &mut primary.payment_terms_in_days,
+                &duplicate.payment_terms_in_days,
+            ),
+        ),
+    ];
+    copied
+        .into_iter()
+        .filter(|(_, copied)| *copied)
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Add a line to a customer note.
+fn append_note(note: &mut Option<String>, line: String) {
+    *note = Some(match note.take().filter(|n| !n.trim().is_empty()) {
+        Some(existing) => format!("{}\n{}", existing.trim_end(), line),
+        None => line,
+    });
+}
+
+/// Customer number and name for a merge note, falling back to the ID.
+fn describe(customer: &Customer, id: &str) -> String {
+    match (&customer.customer_number, &customer.name) {
+        (Some(number), Some(name)) => format!("{} {}", number, name),
+        (Some(label), None) | (None, Some(label)) => label.clone(),
+        (None, None) => id.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Address;
+
+    #[test]
+    fn test_copy_missing_fields() {
+        let mut primary = Customer {
+            name: Some("Acme".to_string()),
+            email: Some(" ".to_string()),
+            phone: Some("08-123 45".to_string()),
+            ..Default::default()
+        };
+        let duplicate = Customer {
+            email: Some("billing@acme.example".to_string()),
+            phone: Some("070-000 00".to_string()),
+            invoice_address: Some(Address {
+                city: Some("Växjö".to_string()),
+                ..Default::default()
+            }),
+            website: Some(String::new()),
+            ..Default::default()
+        };
+
+        let copied = copy_missing_fields(&mut primary, &duplicate);
+
+        assert_eq!(copied, vec!["Email", "InvoiceAddress"]);
+        assert_eq!(primary.email.as_deref(), Some("billing@acme.example"));
+        assert_eq!(primary.phone.as_deref(), Some("08-123 45"));
+        assert!(primary.website.is_none());
+    }
+
+    #[test]
+    fn test_append_note() {
+        let mut note = None;
+        append_note(&mut note, "first".to_string());
+        append_note(&mut note, "second".to_string());
+        assert_eq!(note.as_deref(), Some("first\nsecond"));
+    }
 }
@@ -2,7 +2,7 @@
 
 use crate::client::Client;
 use crate::error::Result;
-use crate::types::{FiscalYear, PaginatedResponse, PaginationParams};
+use crate::types::{FiscalYear, Money, PaginatedResponse, PaginationParams};
 use serde::{Deserialize, Serialize};
 
 /// Opening balance entry.
@@ -12,7 +12,8 @@ pub struct OpeningBalance {
     /// Account number.
     pub account_number: String,
     /// Opening balance amount.
-    pub amount: f64,
+    #[serde(with = "crate::types::money_serde")]
+    pub amount: Money,
 }
 
 /// Fiscal years endpoint for managing accounting periods.
@@ -37,6 +38,8 @@ impl<'a> FiscalYearsEndpoint<'a> {
         }
     }
 
+    crate::__list_all_impl!("/fiscalyears", FiscalYear);
+
     /// Get a specific fiscal year by ID.
     pub async fn get(&self, id: &str) -> Result<FiscalYear> {
         let path = format!("/fiscalyears/{}", id);
@@ -45,6 +48,7 @@ impl<'a> FiscalYearsEndpoint<'a> {
 
     /// Create a new fiscal year.
     pub async fn create(&self, fiscal_year: &FiscalYear) -> Result<FiscalYear> {
+        self.client.validate_request(fiscal_year)?;
         self.client.post("/fiscalyears", fiscal_year).await
     }
 
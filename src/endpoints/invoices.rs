@@ -1,8 +1,30 @@
 //! Customer invoices API endpoint.
 
 use crate::client::Client;
-use crate::error::Result;
-use crate::types::{Invoice, InvoicePayment, PaginatedResponse, PaginationParams, QueryParams};
+use crate::error::{Error, Result};
+use crate::types::{
+    CreateInvoiceOptions, Invoice, InvoiceDelivery, InvoiceId, InvoicePatch, InvoicePayment,
+    InvoiceReminder, PaginatedResponse, PaginationParams, QueryParams, SendInvoiceOptions,
+};
+use bytes::Bytes;
+
+/// Outcome of [`InvoicesEndpoint::create_with`].
+#[derive(Debug)]
+pub struct CreatedInvoice {
+    /// The invoice as saved: updated with its OCR number, or as created if
+    /// that failed.
+    pub invoice: Invoice,
+    /// Why the OCR number could not be set on the invoice after it was
+    /// created. The invoice exists either way.
+    pub ocr_error: Option<Error>,
+}
+
+impl CreatedInvoice {
+    /// Whether every requested option was applied.
+    pub fn is_complete(&self) -> bool {
+        self.ocr_error.is_none()
+    }
+}
 
 /// Invoices endpoint for managing customer invoices.
 ///
@@ -63,6 +85,10 @@ impl<'a> InvoicesEndpoint<'a> {
         }
     }
 
+    crate::__list_all_impl!("/customerinvoices", Invoice);
+
+    crate::__sync_impl!("/customerinvoices", Invoice);
+
     /// Get a specific invoice by ID.
     ///
     /// # Arguments
@@ -72,14 +98,14 @@ impl<'a> InvoicesEndpoint<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use spiris::Client;
+    /// # use spiris::{Client, InvoiceId};
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// let invoice = client.invoices().get("invoice-id-123").await?;
+    /// let invoice = client.invoices().get(&InvoiceId::from("invoice-id-123")).await?;
     /// println!("Invoice #{:?}", invoice.invoice_number);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get(&self, id: &str) -> Result<Invoice> {
+    pub async fn get(&self, id: &InvoiceId) -> Result<Invoice> {
         let path = format!("/customerinvoices/{}", id);
         self.client.get(&path).await
     }
@@ -98,7 +124,7 @@ impl<'a> InvoicesEndpoint<'a> {
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
     /// let new_invoice = Invoice {
     ///     customer_id: Some("customer-id-123".to_string()),
-    ///     invoice_date: Some(Utc::now()),
+    ///     invoice_date: Some(Utc::now().date_naive()),
     ///     rows: vec![
     ///         InvoiceRow {
     ///             text: Some("Consulting services".to_string()),
@@ -114,9 +140,79 @@ impl<'a> InvoicesEndpoint<'a> {
     /// # }
     /// ```
     pub async fn create(&self, invoice: &Invoice) -> Result<Invoice> {
+        self.client.validate_request(invoice)?;
         self.client.post("/customerinvoices", invoice).await
     }
 
+    /// Create a new invoice with extra [`CreateInvoiceOptions`].
+    ///
+    /// With [`generate_ocr`](CreateInvoiceOptions::generate_ocr), an
+    /// invoice without an OCR number gets one generated from its invoice
+    /// number. If the API assigns the invoice number, the invoice is created
+    /// first and then updated with the OCR number, unless the API assigned
+    /// one too.
+    ///
+    /// An error is returned only if no invoice was created. If the invoice
+    /// was created but the OCR number could not be generated or saved, the
+    /// created invoice is returned with the reason in
+    /// [`ocr_error`](CreatedInvoice::ocr_error).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use spiris::{Client, CreateInvoiceOptions, Invoice};
+    /// # async fn example(client: &Client, invoice: Invoice) -> Result<(), Box<dyn std::error::Error>> {
+    /// let options = CreateInvoiceOptions::new().generate_ocr(true);
+    /// let created = client.invoices().create_with(&invoice, &options).await?;
+    /// if let Some(error) = &created.ocr_error {
+    ///     eprintln!("Invoice {:?} created without OCR: {}", created.invoice.id, error);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_with(
+        &self,
+        invoice: &Invoice,
+        options: &CreateInvoiceOptions,
+    ) -> Result<CreatedInvoice> {
+        let complete = |invoice| CreatedInvoice {
+            invoice,
+            ocr_error: None,
+        };
+        if !options.generate_ocr || invoice.ocr_number.is_some() {
+            return self.create(invoice).await.map(complete);
+        }
+        if let Some(number) = invoice.invoice_number.as_deref() {
+            let mut invoice = invoice.clone();
+            invoice.ocr_number = Some(crate::validation::ocr_for_invoice_number(number)?);
+            return self.create(&invoice).await.map(complete);
+        }
+
+        let created = self.create(invoice).await?;
+        if created.ocr_number.is_some() {
+            return Ok(complete(created));
+        }
+        let (Some(id), Some(number)) = (created.id.clone(), created.invoice_number.as_deref())
+        else {
+            return Ok(complete(created));
+        };
+        let updated = match crate::validation::ocr_for_invoice_number(number) {
+            Ok(ocr) => {
+                let mut with_ocr = created.clone();
+                with_ocr.ocr_number = Some(ocr);
+                self.update(&id, &with_ocr).await
+            }
+            Err(error) => Err(error),
+        };
+        Ok(match updated {
+            Ok(invoice) => complete(invoice),
+            Err(error) => CreatedInvoice {
+                invoice: created,
+                ocr_error: Some(error),
+            },
+        })
+    }
+
     /// Update an existing invoice.
     ///
     /// # Arguments
@@ -127,19 +223,48 @@ impl<'a> InvoicesEndpoint<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use spiris::Client;
+    /// # use spiris::{Client, InvoiceId};
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// let mut invoice = client.invoices().get("invoice-id-123").await?;
+    /// let id = InvoiceId::from("invoice-id-123");
+    /// let mut invoice = client.invoices().get(&id).await?;
     /// invoice.remarks = Some("Updated remarks".to_string());
-    /// let updated = client.invoices().update("invoice-id-123", &invoice).await?;
+    /// let updated = client.invoices().update(&id, &invoice).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn update(&self, id: &str, invoice: &Invoice) -> Result<Invoice> {
+    pub async fn update(&self, id: &InvoiceId, invoice: &Invoice) -> Result<Invoice> {
+        self.client.validate_request(invoice)?;
         let path = format!("/customerinvoices/{}", id);
         self.client.put(&path, invoice).await
     }
 
+    /// Change only the fields set or cleared in `patch`, leaving the rows
+    /// as they are.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use spiris::{Client, InvoiceId, InvoicePatch};
+    /// # use chrono::NaiveDate;
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let patch = InvoicePatch::new()
+    ///     .due_date(NaiveDate::from_ymd_opt(2025, 4, 30).unwrap())
+    ///     .clear_remarks();
+    /// client
+    ///     .invoices()
+    ///     .update_partial(&InvoiceId::from("invoice-id-123"), &patch)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update_partial(&self, id: &InvoiceId, patch: &InvoicePatch) -> Result<Invoice> {
+        let path = format!("/customerinvoices/{}", id);
+        self.client.patch(&path, patch).await
+    }
+
+    crate::__get_versioned_impl!("/customerinvoices", Invoice, InvoiceId);
+    crate::__update_if_match_impl!("/customerinvoices", Invoice, InvoiceId, validate);
+
     /// Delete an invoice.
     ///
     /// # Arguments
@@ -149,13 +274,13 @@ impl<'a> InvoicesEndpoint<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use spiris::Client;
+    /// # use spiris::{Client, InvoiceId};
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// client.invoices().delete("invoice-id-123").await?;
+    /// client.invoices().delete(&InvoiceId::from("invoice-id-123")).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn delete(&self, id: &str) -> Result<()> {
+    pub async fn delete(&self, id: &InvoiceId) -> Result<()> {
         let path = format!("/customerinvoices/{}", id);
         self.client.delete(&path).await
     }
@@ -191,6 +316,7 @@ impl<'a> InvoicesEndpoint<'a> {
             pagination: Option<PaginationParams>,
         }
 
+        let (query, pagination) = query.resolve_scope(pagination);
         let params = CombinedParams { query, pagination };
         self.client
             .get_with_params("/customerinvoices", &params)
@@ -203,12 +329,33 @@ impl<'a> InvoicesEndpoint<'a> {
     ///
     /// * `invoice_id` - The invoice ID
     /// * `payment` - The payment details
-    pub async fn register_payment(&self, invoice_id: &str, payment: &InvoicePayment) -> Result<()> {
+    pub async fn register_payment(
+        &self,
+        invoice_id: &InvoiceId,
+        payment: &InvoicePayment,
+    ) -> Result<()> {
         let path = format!("/customerinvoices/{}/payments", invoice_id);
         self.client.post::<(), _>(&path, payment).await?;
         Ok(())
     }
 
+    /// Get the PDF for an invoice.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use spiris::{Client, InvoiceId};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pdf = client.invoices().pdf(&InvoiceId::from("invoice-id-123")).await?;
+    /// println!("{} bytes", pdf.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn pdf(&self, invoice_id: &InvoiceId) -> Result<Bytes> {
+        let path = format!("/customerinvoices/{}/pdf", invoice_id);
+        self.client.get_body(&path).await
+    }
+
     /// Get the PDF for an invoice.
     ///
     /// # Arguments
@@ -217,10 +364,26 @@ impl<'a> InvoicesEndpoint<'a> {
     ///
     /// # Returns
     ///
-    /// The PDF as raw bytes.
-    pub async fn get_pdf(&self, invoice_id: &str) -> Result<Vec<u8>> {
-        let path = format!("/customerinvoices/{}/pdf", invoice_id);
-        self.client.get_bytes(&path).await
+    /// The PDF as raw bytes. [`pdf`](Self::pdf) returns them without a copy.
+    pub async fn get_pdf(&self, invoice_id: &InvoiceId) -> Result<Vec<u8>> {
+        self.pdf(invoice_id).await.map(|pdf| pdf.to_vec())
+    }
+
+    /// Download the PDF for an invoice to a file.
+    ///
+    /// The PDF is written to a temporary file next to `path`, which is then
+    /// renamed over it. A failed download or write leaves any existing file
+    /// untouched and no partial file behind. Failing to write the file is an
+    /// [`Error::Io`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn download_pdf_to(
+        &self,
+        invoice_id: &InvoiceId,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<()> {
+        let path = path.as_ref();
+        let pdf = self.pdf(invoice_id).await?;
+        Ok(write_atomically(path, &pdf).await?)
     }
 
     /// Send an invoice via e-invoice.
@@ -228,12 +391,58 @@ impl<'a> InvoicesEndpoint<'a> {
     /// # Arguments
     ///
     /// * `invoice_id` - The invoice ID to send electronically
-    pub async fn send_einvoice(&self, invoice_id: &str) -> Result<()> {
+    pub async fn send_einvoice(&self, invoice_id: &InvoiceId) -> Result<()> {
         let path = format!("/customerinvoices/{}/einvoice", invoice_id);
         self.client.post::<(), _>(&path, &()).await?;
         Ok(())
     }
 
+    /// Send an invoice to the customer by email or as an e-invoice.
+    ///
+    /// # Arguments
+    ///
+    /// * `invoice_id` - The invoice ID to send
+    /// * `options` - Channel, and for email the recipient, copy and text
+    ///
+    /// Email-only options are rejected for e-invoices, which always go to the
+    /// customer's registered e-invoice address.
+    pub async fn send(&self, invoice_id: &InvoiceId, options: &SendInvoiceOptions) -> Result<()> {
+        match options.delivery {
+            InvoiceDelivery::Email => {
+                let path = format!("/customerinvoices/{}/email", invoice_id);
+                self.client.post::<(), _>(&path, options).await?;
+                Ok(())
+            }
+            InvoiceDelivery::EInvoice if options.has_email_options() => Err(Error::InvalidRequest(
+                "recipient, copy and message options only apply to email delivery".to_string(),
+            )),
+            InvoiceDelivery::EInvoice => self.send_einvoice(invoice_id).await,
+        }
+    }
+
+    /// Send a payment reminder for an overdue invoice.
+    ///
+    /// # Arguments
+    ///
+    /// * `invoice_id` - The invoice ID
+    /// * `reminder` - Reminder level, fee and text
+    ///
+    /// See [`crate::dunning`] for selecting invoices to remind.
+    pub async fn send_reminder(
+        &self,
+        invoice_id: &InvoiceId,
+        reminder: &InvoiceReminder,
+    ) -> Result<InvoiceReminder> {
+        let path = format!("/customerinvoices/{}/reminders", invoice_id);
+        self.client.post(&path, reminder).await
+    }
+
+    /// List the reminders sent for an invoice.
+    pub async fn list_reminders(&self, invoice_id: &InvoiceId) -> Result<Vec<InvoiceReminder>> {
+        let path = format!("/customerinvoices/{}/reminders", invoice_id);
+        self.client.get(&path).await
+    }
+
     /// Stream all invoices, automatically paginating through results.
     ///
     /// Requires the `stream` feature.
@@ -252,4 +461,88 @@ impl<'a> InvoicesEndpoint<'a> {
     ) -> impl futures::Stream<Item = Result<Invoice>> + '_ {
         crate::paginated_stream!(page_size, |params| self.list(Some(params)))
     }
+
+    /// Stream all invoices with a custom page size and prefetching.
+    ///
+    /// Requires the `stream` feature.
+    #[cfg(feature = "stream")]
+    pub fn list_stream_with(
+        &self,
+        options: crate::pagination::StreamOptions,
+    ) -> impl futures::Stream<Item = Result<Invoice>> + '_ {
+        crate::pagination::paginated_stream_with(options, move |params| self.list(Some(params)))
+    }
+
+    /// Stream the invoices matching `query`, following pages as the stream
+    /// is consumed.
+    ///
+    /// Requires the `stream` feature.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::TryStreamExt;
+    /// use spiris::{AccessToken, Client, QueryParams};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+    /// let query = QueryParams::new().filter("IsSent eq true");
+    /// let invoices: Vec<_> = client.invoices().stream(query).try_collect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "stream")]
+    pub fn stream(&self, query: QueryParams) -> impl futures::Stream<Item = Result<Invoice>> + '_ {
+        self.stream_with(query, crate::pagination::StreamOptions::default())
+    }
+
+    /// Stream the invoices matching `query` with a custom page size and
+    /// prefetching.
+    ///
+    /// Requires the `stream` feature.
+    #[cfg(feature = "stream")]
+    pub fn stream_with(
+        &self,
+        query: QueryParams,
+        options: crate::pagination::StreamOptions,
+    ) -> impl futures::Stream<Item = Result<Invoice>> + '_ {
+        crate::pagination::paginated_stream_with(options, move |params| {
+            self.search(query.clone(), Some(params))
+        })
+    }
+}
+
+/// Write `contents` to a temporary file in the same directory as `path`,
+/// then rename it to `path`.
+#[cfg(not(target_arch = "wasm32"))]
+async fn write_atomically(path: &std::path::Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use tokio::io::AsyncWriteExt;
+
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+
+    let file_name = path.file_name().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "path has no file name")
+    })?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(format!(
+        ".{}-{}.tmp",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ));
+    let temp = path.with_file_name(temp_name);
+
+    let written = async {
+        let mut file = tokio::fs::File::create(&temp).await?;
+        file.write_all(contents).await?;
+        file.sync_all().await?;
+        drop(file);
+        tokio::fs::rename(&temp, path).await
+    }
+    .await;
+    if written.is_err() {
+        let _ = tokio::fs::remove_file(&temp).await;
+    }
+    written
 }
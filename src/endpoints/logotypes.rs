@@ -0,0 +1,45 @@
+//! Logotypes API endpoint.
+
+use crate::client::Client;
+use crate::error::Result;
+use crate::types::{Logotype, PaginatedResponse, PaginationParams};
+
+/// Logotypes endpoint for the company logos printed on documents.
+pub struct LogotypesEndpoint<'a> {
+    client: &'a Client,
+}
+
+impl<'a> LogotypesEndpoint<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self { client }
+    }
+
+    /// List logotypes with optional pagination.
+    pub async fn list(
+        &self,
+        params: Option<PaginationParams>,
+    ) -> Result<PaginatedResponse<Logotype>> {
+        if let Some(params) = params {
+            self.client.get_with_params("/logotypes", &params).await
+        } else {
+            self.client.get("/logotypes").await
+        }
+    }
+
+    crate::__list_all_impl!("/logotypes", Logotype);
+
+    /// Get a logotype's metadata.
+    pub async fn get(&self, id: &str) -> Result<Logotype> {
+        self.client.get(&format!("/logotypes/{}", id)).await
+    }
+
+    /// Download a logotype's image.
+    ///
+    /// See [`ImageCache`](crate::image_cache::ImageCache) (feature
+    /// `image-cache`) to avoid downloading it on every run.
+    pub async fn get_content(&self, id: &str) -> Result<Vec<u8>> {
+        self.client
+            .get_bytes(&format!("/logotypes/{}/content", id))
+            .await
+    }
+}
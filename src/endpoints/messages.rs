@@ -1,8 +1,15 @@
 //! Message threads API endpoint.
+//!
+//! Message threads also hold internal notes on customers and invoices, see
+//! [`notes`](MessagesEndpoint::notes) and [`add_note`](MessagesEndpoint::add_note).
 
 use crate::client::Client;
+use crate::endpoints::collect_pages;
 use crate::error::Result;
-use crate::types::{Message, MessageThread};
+use crate::query::Filter;
+use crate::types::{
+    Message, MessageThread, NoteTarget, PaginatedResponse, PaginationParams, QueryParams,
+};
 
 pub struct MessagesEndpoint<'a> {
     client: &'a Client,
@@ -13,10 +20,48 @@ impl<'a> MessagesEndpoint<'a> {
         Self { client }
     }
 
+    pub async fn list_threads(
+        &self,
+        params: Option<PaginationParams>,
+    ) -> Result<PaginatedResponse<MessageThread>> {
+        match params {
+            Some(params) => {
+                self.client
+                    .get_with_params("/messagethreads", &params)
+                    .await
+            }
+            None => self.client.get("/messagethreads").await,
+        }
+    }
+
+    /// Search message threads with custom query parameters.
+    pub async fn search_threads(
+        &self,
+        query: QueryParams,
+        pagination: Option<PaginationParams>,
+    ) -> Result<PaginatedResponse<MessageThread>> {
+        #[derive(serde::Serialize)]
+        struct CombinedParams {
+            #[serde(flatten)]
+            query: QueryParams,
+            #[serde(flatten)]
+            pagination: Option<PaginationParams>,
+        }
+
+        let params = CombinedParams { query, pagination };
+        self.client
+            .get_with_params("/messagethreads", &params)
+            .await
+    }
+
     pub async fn get_thread(&self, id: &str) -> Result<MessageThread> {
         self.client.get(&format!("/messagethreads/{}", id)).await
     }
 
+    pub async fn create_thread(&self, thread: &MessageThread) -> Result<MessageThread> {
+        self.client.post("/messagethreads", thread).await
+    }
+
     pub async fn update_thread(&self, id: &str, thread: &MessageThread) -> Result<MessageThread> {
         self.client
             .put(&format!("/messagethreads/{}", id), thread)
@@ -28,4 +73,52 @@ impl<'a> MessagesEndpoint<'a> {
             .post(&format!("/messagethreads/{}", thread_id), message)
             .await
     }
+
+    /// All note threads attached to a customer or invoice, oldest first.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use spiris::{Client, NoteTarget};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let target = NoteTarget::Customer("customer-id".to_string());
+    /// client
+    ///     .messages()
+    ///     .add_note(&target, "CRM", "Prefers invoices by e-invoice")
+    ///     .await?;
+    ///
+    /// for thread in client.messages().notes(&target).await? {
+    ///     for message in &thread.messages {
+    ///         println!("{:?}: {:?}", thread.subject, message.content);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn notes(&self, target: &NoteTarget) -> Result<Vec<MessageThread>> {
+        let filter = Filter::field("DocumentId")
+            .eq(target.document_id())
+            .and(Filter::field("DocumentType").eq(target.document_type()));
+        let mut threads = collect_pages(|p| {
+            let query = QueryParams::new().filter_by(filter.clone());
+            async move { self.search_threads(query, Some(p)).await }
+        })
+        .await?;
+
+        // Guard against the API ignoring the filter
+        threads.retain(|thread| thread.is_about(target));
+        threads.sort_by_key(|thread| thread.created_utc);
+        Ok(threads)
+    }
+
+    /// Attach a note to a customer or invoice as a new thread.
+    pub async fn add_note(
+        &self,
+        target: &NoteTarget,
+        subject: &str,
+        content: &str,
+    ) -> Result<MessageThread> {
+        self.create_thread(&MessageThread::note(target, subject, content))
+            .await
+    }
 }
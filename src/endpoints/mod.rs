@@ -22,11 +22,14 @@ pub mod delivery_terms;
 pub mod documents;
 pub mod fiscal_years;
 pub mod invoices;
+pub mod logotypes;
 pub mod messages;
 pub mod orders;
 pub mod projects;
 pub mod quotations;
+pub mod reference_data;
 pub mod supplier_invoice_drafts;
+pub mod supplier_invoice_payments;
 pub mod supplier_invoices;
 pub mod supplier_labels;
 pub mod supplier_ledger_items;
@@ -35,6 +38,7 @@ pub mod terms_of_payment;
 pub mod units;
 pub mod users;
 pub mod vat_codes;
+pub mod vat_reports;
 pub mod vouchers;
 
 pub use accounts::AccountsEndpoint;
@@ -59,11 +63,14 @@ pub use delivery_terms::DeliveryTermsEndpoint;
 pub use documents::DocumentsEndpoint;
 pub use fiscal_years::FiscalYearsEndpoint;
 pub use invoices::InvoicesEndpoint;
+pub use logotypes::LogotypesEndpoint;
 pub use messages::MessagesEndpoint;
 pub use orders::OrdersEndpoint;
 pub use projects::ProjectsEndpoint;
 pub use quotations::QuotationsEndpoint;
+pub use reference_data::{ReferenceData, ReferenceDataEndpoint};
 pub use supplier_invoice_drafts::SupplierInvoiceDraftsEndpoint;
+pub use supplier_invoice_payments::SupplierInvoicePaymentsEndpoint;
 pub use supplier_invoices::SupplierInvoicesEndpoint;
 pub use supplier_labels::SupplierLabelsEndpoint;
 pub use supplier_ledger_items::SupplierLedgerItemsEndpoint;
@@ -72,4 +79,242 @@ pub use terms_of_payment::TermsOfPaymentEndpoint;
 pub use units::UnitsEndpoint;
 pub use users::UsersEndpoint;
 pub use vat_codes::VatCodesEndpoint;
+pub use vat_reports::VatReportsEndpoint;
 pub use vouchers::VouchersEndpoint;
+
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::types::{ListAllOptions, ListScope, PaginatedResponse, PaginationParams, QueryParams};
+use serde::de::DeserializeOwned;
+use std::future::Future;
+
+/// Page size used when fetching every page of a list.
+pub(crate) const COLLECT_PAGE_SIZE: u32 = 500;
+
+/// Fetch every page of a list request into a single vector.
+///
+/// `fetch` is called with successive page numbers until the API reports
+/// that no further pages exist.
+pub(crate) async fn collect_pages<T, F, Fut>(fetch: F) -> Result<Vec<T>>
+where
+    F: FnMut(PaginationParams) -> Fut,
+    Fut: Future<Output = Result<PaginatedResponse<T>>>,
+{
+    let mut items = Vec::new();
+    for_each_page(COLLECT_PAGE_SIZE, fetch, |data, _| {
+        items.extend(data);
+        Ok(())
+    })
+    .await?;
+    Ok(items)
+}
+
+/// Whether another page follows one that returned `received` items.
+///
+/// An empty page ends the list even if the API claims there is more, so a
+/// wrong `HasNextPage` cannot loop forever.
+fn has_more(has_next_page: bool, received: usize) -> bool {
+    has_next_page && received > 0
+}
+
+/// Fetch pages of `page_size` from the first on, passing each page's items
+/// and whether more follow to `take`, until the last page or an error.
+async fn for_each_page<T, F, Fut>(
+    page_size: u32,
+    mut fetch: F,
+    mut take: impl FnMut(Vec<T>, bool) -> Result<()>,
+) -> Result<()>
+where
+    F: FnMut(PaginationParams) -> Fut,
+    Fut: Future<Output = Result<PaginatedResponse<T>>>,
+{
+    let mut page = 0;
+    loop {
+        let params = PaginationParams::new().page(page).pagesize(page_size);
+        let response = fetch(params).await?;
+        let more = has_more(response.meta.has_next_page, response.data.len());
+        take(response.data, more)?;
+        if !more {
+            return Ok(());
+        }
+        page += 1;
+    }
+}
+
+/// A list filter together with the page to fetch.
+#[derive(serde::Serialize)]
+struct PageQuery<'a> {
+    #[serde(flatten)]
+    query: &'a QueryParams,
+    #[serde(flatten)]
+    pagination: PaginationParams,
+}
+
+/// Fetch every page of `path` matching `query`, for the endpoints'
+/// `list_all_with`.
+pub(crate) async fn list_all<T: DeserializeOwned>(
+    client: &Client,
+    path: &str,
+    query: Option<QueryParams>,
+    options: ListAllOptions,
+) -> Result<Vec<T>> {
+    let (query, _) = query.unwrap_or_default().resolve_scope(None);
+    let query = &query;
+    let mut items = Vec::new();
+    let fetch = |pagination| async move {
+        let params = PageQuery { query, pagination };
+        client.get_with_params(path, &params).await
+    };
+    for_each_page(options.page_size, fetch, |data, more| {
+        items.extend(data);
+        match options.max_items {
+            Some(max_items) if items.len() > max_items || (more && items.len() >= max_items) => {
+                Err(Error::InvalidRequest(format!(
+                    "{} has more than {} items; narrow the query or raise max_items",
+                    path, max_items
+                )))
+            }
+            _ => Ok(()),
+        }
+    })
+    .await?;
+    Ok(items)
+}
+
+/// Fetch every page of `path` matching `query`, up to `parallelism` pages at
+/// a time, for the endpoints' `list_all_concurrent`.
+///
+/// The first page is read alone to learn the page count; the rest are
+/// requested concurrently and reassembled in page order.
+#[cfg(feature = "stream")]
+pub(crate) async fn list_all_concurrent<T: DeserializeOwned>(
+    client: &Client,
+    path: &str,
+    query: Option<QueryParams>,
+    parallelism: usize,
+) -> Result<Vec<T>> {
+    use futures::stream::{self, StreamExt, TryStreamExt};
+
+    let (query, _) = query.unwrap_or_default().resolve_scope(None);
+    let fetch = |page: u32| {
+        let query = &query;
+        async move {
+            let params = PageQuery {
+                query,
+                pagination: PaginationParams::new()
+                    .page(page)
+                    .pagesize(COLLECT_PAGE_SIZE),
+            };
+            client
+                .get_with_params::<PaginatedResponse<T>, _>(path, &params)
+                .await
+        }
+    };
+
+    let first = fetch(0).await?;
+    if !has_more(first.meta.has_next_page, first.data.len()) {
+        return Ok(first.data);
+    }
+    let total_pages = first.meta.total_pages.max(2);
+
+    let mut pages: Vec<(u32, Vec<T>)> = stream::iter(1..total_pages)
+        .map(|page| async move { Ok::<_, Error>((page, fetch(page).await?.data)) })
+        .buffer_unordered(parallelism.max(1))
+        .try_collect()
+        .await?;
+    pages.sort_unstable_by_key(|(page, _)| *page);
+
+    let mut items = first.data;
+    for (_, data) in pages {
+        items.extend(data);
+    }
+    Ok(items)
+}
+
+/// Stream every item of `path` matching `query`, deserializing each page's
+/// items as its body arrives, for the endpoints' `list_all_streaming`.
+#[cfg(feature = "stream")]
+pub(crate) fn list_all_streaming<'a, T: DeserializeOwned + 'a>(
+    client: &'a Client,
+    path: &'a str,
+    query: Option<QueryParams>,
+) -> impl futures::Stream<Item = Result<T>> + 'a {
+    use futures::StreamExt;
+
+    let (query, _) = query.unwrap_or_default().resolve_scope(None);
+    async_stream::try_stream! {
+        let mut page = 0;
+        loop {
+            let params = PageQuery {
+                query: &query,
+                pagination: PaginationParams::new()
+                    .page(page)
+                    .pagesize(COLLECT_PAGE_SIZE),
+            };
+            let response = client.get_response_with_params(path, &params).await?;
+            let mut body = response.bytes_stream();
+            let mut parser = crate::pagination::ListItems::new();
+            let mut received = 0;
+            while let Some(chunk) = body.next().await {
+                for item in parser.push(&chunk?) {
+                    received += 1;
+                    yield serde_json::from_slice::<T>(&item)?;
+                }
+            }
+            let meta = parser.finish()?;
+            if !has_more(meta.has_next_page, received) {
+                break;
+            }
+            page += 1;
+        }
+    }
+}
+
+/// Fetch every record of `path` modified at or after `since`, oldest
+/// change first, for the endpoints' `modified_since`.
+pub(crate) async fn modified_since<T: DeserializeOwned>(
+    client: &Client,
+    path: &str,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<Vec<T>> {
+    let mut query = QueryParams {
+        orderby: Some("ModifiedUtc asc".to_string()),
+        ..QueryParams::default()
+    };
+    if let Some(since) = since {
+        query = query.filter_by(crate::query::Filter::field("ModifiedUtc").ge(since));
+    }
+    list_all(client, path, Some(query), ListAllOptions::default()).await
+}
+
+/// Fetch what changed since `cursor` and advance it, for the endpoints'
+/// `sync`.
+pub(crate) async fn sync<T: DeserializeOwned + crate::types::Audited>(
+    client: &Client,
+    path: &str,
+    cursor: &mut crate::sync::SyncCursor,
+) -> Result<Vec<T>> {
+    let records = modified_since(client, path, cursor.high_water_mark).await?;
+    cursor.advance(&records);
+    Ok(records)
+}
+
+/// Pick the record a lookup should return from its matches.
+///
+/// Matches outside `scope` are dropped in case the API ignored the filter,
+/// and an active match wins over an inactive one sharing the same number.
+pub(crate) fn pick_in_scope<T>(
+    matches: Vec<T>,
+    scope: ListScope,
+    is_active: impl Fn(&T) -> Option<bool>,
+) -> Option<T> {
+    let mut matches: Vec<T> = matches
+        .into_iter()
+        .filter(|m| scope.includes(is_active(m)))
+        .collect();
+    let position = matches
+        .iter()
+        .position(|m| is_active(m) != Some(false))
+        .unwrap_or(0);
+    (position < matches.len()).then(|| matches.swap_remove(position))
+}
@@ -2,7 +2,7 @@
 
 use crate::client::Client;
 use crate::error::Result;
-use crate::types::{Order, PaginatedResponse, PaginationParams, QueryParams};
+use crate::types::{CustomerInvoiceDraft, Order, PaginatedResponse, PaginationParams, QueryParams};
 
 pub struct OrdersEndpoint<'a> {
     client: &'a Client,
@@ -21,6 +21,8 @@ impl<'a> OrdersEndpoint<'a> {
         }
     }
 
+    crate::__list_all_impl!("/orders", Order);
+
     pub async fn get(&self, id: &str) -> Result<Order> {
         self.client.get(&format!("/orders/{}", id)).await
     }
@@ -33,6 +35,9 @@ impl<'a> OrdersEndpoint<'a> {
         self.client.put(&format!("/orders/{}", id), order).await
     }
 
+    crate::__get_versioned_impl!("/orders", Order);
+    crate::__update_if_match_impl!("/orders", Order);
+
     pub async fn delete(&self, id: &str) -> Result<()> {
         self.client.delete(&format!("/orders/{}", id)).await
     }
@@ -49,8 +54,17 @@ impl<'a> OrdersEndpoint<'a> {
             #[serde(flatten)]
             pagination: Option<PaginationParams>,
         }
+
+        let (query, pagination) = query.resolve_scope(pagination);
         self.client
             .get_with_params("/orders", &CombinedParams { query, pagination })
             .await
     }
+
+    /// Convert an order into a customer invoice draft.
+    pub async fn convert_to_invoice_draft(&self, id: &str) -> Result<CustomerInvoiceDraft> {
+        self.client
+            .post(&format!("/orders/{}/convert", id), &serde_json::json!({}))
+            .await
+    }
 }
@@ -2,8 +2,24 @@
 
 use crate::client::Client;
 use crate::error::Result;
-use crate::types::{PaginatedResponse, PaginationParams, QueryParams, Quotation};
+use crate::types::{
+    CustomerInvoiceDraft, Order, PaginatedResponse, PaginationParams, QueryParams, Quotation,
+};
 
+/// Quotations endpoint for managing sales quotes and converting them into
+/// orders or invoice drafts.
+///
+/// # Example
+///
+/// ```no_run
+/// # use spiris::Client;
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let quote = client.quotes().accept("quote-id-123").await?;
+/// let order = client.quotes().convert_to_order("quote-id-123").await?;
+/// let draft = client.orders().convert_to_invoice_draft(order.id.as_deref().unwrap()).await?;
+/// # Ok(())
+/// # }
+/// ```
 pub struct QuotationsEndpoint<'a> {
     client: &'a Client,
 }
@@ -24,6 +40,8 @@ impl<'a> QuotationsEndpoint<'a> {
         }
     }
 
+    crate::__list_all_impl!("/quotations", Quotation);
+
     pub async fn get(&self, id: &str) -> Result<Quotation> {
         self.client.get(&format!("/quotations/{}", id)).await
     }
@@ -38,6 +56,9 @@ impl<'a> QuotationsEndpoint<'a> {
             .await
     }
 
+    crate::__get_versioned_impl!("/quotations", Quotation);
+    crate::__update_if_match_impl!("/quotations", Quotation);
+
     pub async fn delete(&self, id: &str) -> Result<()> {
         self.client.delete(&format!("/quotations/{}", id)).await
     }
@@ -54,8 +75,40 @@ impl<'a> QuotationsEndpoint<'a> {
             #[serde(flatten)]
             pagination: Option<PaginationParams>,
         }
+
+        let (query, pagination) = query.resolve_scope(pagination);
         self.client
             .get_with_params("/quotations", &CombinedParams { query, pagination })
             .await
     }
+
+    /// Mark a quotation as accepted by the customer.
+    pub async fn accept(&self, id: &str) -> Result<Quotation> {
+        self.client
+            .post(
+                &format!("/quotations/{}/accept", id),
+                &serde_json::json!({}),
+            )
+            .await
+    }
+
+    /// Convert a quotation into a sales order.
+    pub async fn convert_to_order(&self, id: &str) -> Result<Order> {
+        self.client
+            .post(
+                &format!("/quotations/{}/convert/order", id),
+                &serde_json::json!({}),
+            )
+            .await
+    }
+
+    /// Convert a quotation directly into a customer invoice draft.
+    pub async fn convert_to_invoice_draft(&self, id: &str) -> Result<CustomerInvoiceDraft> {
+        self.client
+            .post(
+                &format!("/quotations/{}/convert/invoicedraft", id),
+                &serde_json::json!({}),
+            )
+            .await
+    }
 }
@@ -0,0 +1,261 @@
+//! Cached reference data.
+//!
+//! Accounts, VAT codes, units, terms of payment, currencies and delivery terms
+//! rarely change but are needed to build almost every document. They are
+//! loaded together, cached on the [`Client`] for
+//! [`ClientConfig::reference_data_ttl_seconds`](crate::ClientConfig::reference_data_ttl_seconds),
+//! and shared between clones of the client.
+
+use crate::client::Client;
+use crate::endpoints::collect_pages;
+use crate::error::Result;
+use crate::types::{Account, Currency, DeliveryTerm, TermsOfPayment, Unit, VatCode};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Cache slot held by the client.
+pub(crate) type ReferenceDataCache = Mutex<Option<(DateTime<Utc>, Arc<ReferenceData>)>>;
+
+/// Reference data with lookups by id and by code.
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceData {
+    /// Chart of accounts for the current fiscal year.
+    pub accounts: Vec<Account>,
+    /// VAT codes.
+    pub vat_codes: Vec<VatCode>,
+    /// Units.
+    pub units: Vec<Unit>,
+    /// Terms of payment.
+    pub terms_of_payment: Vec<TermsOfPayment>,
+    /// Currencies.
+    pub currencies: Vec<Currency>,
+    /// Delivery terms.
+    pub delivery_terms: Vec<DeliveryTerm>,
+    index: HashMap<(Kind, String), usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Kind {
+    Account,
+    VatCodeId,
+    VatCode,
+    UnitId,
+    UnitCode,
+    TermsOfPaymentId,
+    Currency,
+    DeliveryTermId,
+    DeliveryTermCode,
+}
+
+impl ReferenceData {
+    /// Build the lookup maps over already loaded data.
+    pub fn new(
+        accounts: Vec<Account>,
+        vat_codes: Vec<VatCode>,
+        units: Vec<Unit>,
+        terms_of_payment: Vec<TermsOfPayment>,
+        currencies: Vec<Currency>,
+        delivery_terms: Vec<DeliveryTerm>,
+    ) -> Self {
+        let mut index = HashMap::new();
+        let mut add = |kind: Kind, key: &Option<String>, position: usize| {
+            if let Some(key) = key {
+                index.entry((kind, key.clone())).or_insert(position);
+            }
+        };
+
+        for (i, account) in accounts.iter().enumerate() {
+            add(Kind::Account, &account.account_number, i);
+        }
+        for (i, vat_code) in vat_codes.iter().enumerate() {
+            add(Kind::VatCodeId, &vat_code.id, i);
+            add(Kind::VatCode, &vat_code.code, i);
+        }
+        for (i, unit) in units.iter().enumerate() {
+            add(Kind::UnitId, &unit.id, i);
+            add(Kind::UnitCode, &unit.code, i);
+        }
+        for (i, terms) in terms_of_payment.iter().enumerate() {
+            add(Kind::TermsOfPaymentId, &terms.id, i);
+        }
+        for (i, currency) in currencies.iter().enumerate() {
+            add(Kind::Currency, &currency.code, i);
+        }
+        for (i, terms) in delivery_terms.iter().enumerate() {
+            add(Kind::DeliveryTermId, &terms.id, i);
+            add(Kind::DeliveryTermCode, &terms.code, i);
+        }
+
+        Self {
+            accounts,
+            vat_codes,
+            units,
+            terms_of_payment,
+            currencies,
+            delivery_terms,
+            index,
+        }
+    }
+
+    fn find<'a, T>(&self, items: &'a [T], kind: Kind, key: &str) -> Option<&'a T> {
+        self.index
+            .get(&(kind, key.to_string()))
+            .and_then(|&i| items.get(i))
+    }
+
+    /// Account by account number.
+    pub fn account(&self, account_number: &str) -> Option<&Account> {
+        self.find(&self.accounts, Kind::Account, account_number)
+    }
+
+    /// VAT code by id.
+    pub fn vat_code(&self, id: &str) -> Option<&VatCode> {
+        self.find(&self.vat_codes, Kind::VatCodeId, id)
+    }
+
+    /// VAT code by code.
+    pub fn vat_code_by_code(&self, code: &str) -> Option<&VatCode> {
+        self.find(&self.vat_codes, Kind::VatCode, code)
+    }
+
+    /// Unit by id.
+    pub fn unit(&self, id: &str) -> Option<&Unit> {
+        self.find(&self.units, Kind::UnitId, id)
+    }
+
+    /// Unit by code.
+    pub fn unit_by_code(&self, code: &str) -> Option<&Unit> {
+        self.find(&self.units, Kind::UnitCode, code)
+    }
+
+    /// Terms of payment by id.
+    pub fn terms_of_payment(&self, id: &str) -> Option<&TermsOfPayment> {
+        self.find(&self.terms_of_payment, Kind::TermsOfPaymentId, id)
+    }
+
+    /// Currency by ISO 4217 code.
+    pub fn currency(&self, code: &str) -> Option<&Currency> {
+        self.find(&self.currencies, Kind::Currency, code)
+    }
+
+    /// Delivery terms by id.
+    pub fn delivery_term(&self, id: &str) -> Option<&DeliveryTerm> {
+        self.find(&self.delivery_terms, Kind::DeliveryTermId, id)
+    }
+
+    /// Delivery terms by code.
+    pub fn delivery_term_by_code(&self, code: &str) -> Option<&DeliveryTerm> {
+        self.find(&self.delivery_terms, Kind::DeliveryTermCode, code)
+    }
+}
+
+/// Reference data endpoint.
+pub struct ReferenceDataEndpoint<'a> {
+    client: &'a Client,
+}
+
+impl<'a> ReferenceDataEndpoint<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self { client }
+    }
+
+    /// Get all reference data, loading it if the cache is empty or expired.
+    ///
+    /// Concurrent callers wait for a single load instead of each fetching.
+    pub async fn load_all(&self) -> Result<Arc<ReferenceData>> {
+        let (cache, ttl) = self.client.reference_data_cache();
+        let mut cache = cache.lock().await;
+        if let Some((loaded_at, data)) = cache.as_ref() {
+            if self.client.clock().elapsed_since(*loaded_at) < ttl {
+                return Ok(Arc::clone(data));
+            }
+        }
+
+        let data = Arc::new(self.fetch().await?);
+        *cache = Some((self.client.clock().now(), Arc::clone(&data)));
+        Ok(data)
+    }
+
+    /// Reload the reference data, ignoring the cache.
+    pub async fn refresh(&self) -> Result<Arc<ReferenceData>> {
+        self.invalidate().await;
+        self.load_all().await
+    }
+
+    /// Drop the cached reference data.
+    pub async fn invalidate(&self) {
+        *self.client.reference_data_cache().0.lock().await = None;
+    }
+
+    async fn fetch(&self) -> Result<ReferenceData> {
+        let client = self.client;
+        let (accounts, vat_codes, units, terms_of_payment, currencies, delivery_terms) = tokio::try_join!(
+            collect_pages(|p| async move { client.accounts().list(Some(p)).await }),
+            collect_pages(|p| async move { client.vat_codes().list(Some(p)).await }),
+            collect_pages(|p| async move { client.units().list(Some(p)).await }),
+            collect_pages(|p| async move { client.terms_of_payment().list(Some(p)).await }),
+            collect_pages(|p| async move { client.currencies().list(Some(p)).await }),
+            collect_pages(|p| async move { client.delivery_terms().list(Some(p)).await }),
+        )?;
+
+        Ok(ReferenceData::new(
+            accounts,
+            vat_codes,
+            units,
+            terms_of_payment,
+            currencies,
+            delivery_terms,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookups() {
+        let data = ReferenceData::new(
+            vec![Account {
+                account_number: Some("1930".to_string()),
+                name: Some("Företagskonto".to_string()),
+                ..Default::default()
+            }],
+            vec![VatCode {
+                id: Some("vat-25".to_string()),
+                code: Some("MP1".to_string()),
+                ..Default::default()
+            }],
+            vec![Unit {
+                id: Some("unit-1".to_string()),
+                code: Some("st".to_string()),
+                name: Some("Styck".to_string()),
+            }],
+            Vec::new(),
+            vec![Currency {
+                code: Some("EUR".to_string()),
+                name: Some("Euro".to_string()),
+            }],
+            Vec::new(),
+        );
+
+        assert_eq!(
+            data.account("1930").unwrap().name.as_deref(),
+            Some("Företagskonto")
+        );
+        assert_eq!(
+            data.vat_code_by_code("MP1").unwrap().id.as_deref(),
+            Some("vat-25")
+        );
+        assert!(data.vat_code("vat-25").is_some());
+        assert_eq!(
+            data.unit_by_code("st").unwrap().name.as_deref(),
+            Some("Styck")
+        );
+        assert!(data.currency("EUR").is_some());
+        assert!(data.currency("SEK").is_none());
+        assert!(data.delivery_term("dt-1").is_none());
+    }
+}
@@ -28,6 +28,8 @@ impl<'a> SupplierInvoiceDraftsEndpoint<'a> {
         }
     }
 
+    crate::__list_all_impl!("/supplierinvoicedrafts", SupplierInvoiceDraft);
+
     pub async fn get(&self, id: &str) -> Result<SupplierInvoiceDraft> {
         self.client
             .get(&format!("/supplierinvoicedrafts/{}", id))
@@ -48,6 +50,9 @@ impl<'a> SupplierInvoiceDraftsEndpoint<'a> {
             .await
     }
 
+    crate::__get_versioned_impl!("/supplierinvoicedrafts", SupplierInvoiceDraft);
+    crate::__update_if_match_impl!("/supplierinvoicedrafts", SupplierInvoiceDraft);
+
     pub async fn delete(&self, id: &str) -> Result<()> {
         self.client
             .delete(&format!("/supplierinvoicedrafts/{}", id))
@@ -72,6 +77,8 @@ impl<'a> SupplierInvoiceDraftsEndpoint<'a> {
             #[serde(flatten)]
             pagination: Option<PaginationParams>,
         }
+
+        let (query, pagination) = query.resolve_scope(pagination);
         self.client
             .get_with_params(
                 "/supplierinvoicedrafts",
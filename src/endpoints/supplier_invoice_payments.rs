@@ -0,0 +1,195 @@
+//! Supplier invoice payments API endpoint.
+
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::types::{Money, SupplierInvoice, SupplierInvoicePayment};
+
+/// Supplier invoice payments endpoint for settling accounts payable.
+///
+/// # Example
+///
+/// ```no_run
+/// # use spiris::{money, Client, AccessToken, SupplierInvoicePayment};
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+/// let invoice = client.supplier_invoices().get("invoice-id").await?;
+///
+/// // Pay part of a EUR invoice from the company's EUR account
+/// let payment = SupplierInvoicePayment::new(money!(500.0), chrono::Utc::now())
+///     .foreign_currency("EUR", money!(11.45))
+///     .bank_account("bank-account-id");
+///
+/// client.supplier_invoice_payments().register_for(&invoice, payment).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SupplierInvoicePaymentsEndpoint<'a> {
+    client: &'a Client,
+}
+
+impl<'a> SupplierInvoicePaymentsEndpoint<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self { client }
+    }
+
+    /// Register a payment on a supplier invoice.
+    ///
+    /// The amount must be positive; an exchange rate, if given, as well.
+    pub async fn register(
+        &self,
+        invoice_id: &str,
+        payment: &SupplierInvoicePayment,
+    ) -> Result<SupplierInvoicePayment> {
+        check_payment(payment)?;
+        let path = format!("/supplierinvoices/{}/payments", invoice_id);
+        self.client.post(&path, payment).await
+    }
+
+    /// Register a payment after checking it against the invoice.
+    ///
+    /// Rejects payments exceeding the remaining balance and payments in a
+    /// different currency than the invoice. The invoice currency is used
+    /// when the payment does not specify one.
+    pub async fn register_for(
+        &self,
+        invoice: &SupplierInvoice,
+        mut payment: SupplierInvoicePayment,
+    ) -> Result<SupplierInvoicePayment> {
+        let invoice_id = invoice
+            .id
+            .as_deref()
+            .ok_or_else(|| Error::InvalidRequest("Supplier invoice has no ID".into()))?;
+
+        check_payment(&payment)?;
+        check_against_invoice(invoice, &payment)?;
+
+        if payment.currency_code.is_none() {
+            payment.currency_code = invoice.currency_code.clone();
+        }
+        payment.supplier_invoice_id = Some(invoice_id.to_string());
+
+        self.register(invoice_id, &payment).await
+    }
+}
+
+fn check_payment(payment: &SupplierInvoicePayment) -> Result<()> {
+    match payment.amount {
+        Some(amount) if amount > Money::default() => {}
+        _ => {
+            return Err(Error::InvalidRequest(
+                "Payment amount must be greater than zero".into(),
+            ))
+        }
+    }
+    if payment
+        .currency_rate
+        .is_some_and(|rate| rate <= Money::default())
+    {
+        return Err(Error::InvalidRequest(
+            "Currency rate must be greater than zero".into(),
+        ));
+    }
+    Ok(())
+}
+
+fn check_against_invoice(
+    invoice: &SupplierInvoice,
+    payment: &SupplierInvoicePayment,
+) -> Result<()> {
+    if let (Some(invoice_currency), Some(payment_currency)) =
+        (&invoice.currency_code, &payment.currency_code)
+    {
+        if !invoice_currency.eq_ignore_ascii_case(payment_currency) {
+            return Err(Error::InvalidRequest(format!(
+                "Payment currency {} does not match invoice currency {}",
+                payment_currency, invoice_currency
+            )));
+        }
+    }
+
+    if invoice.is_paid == Some(true) {
+        return Err(Error::InvalidRequest(
+            "Supplier invoice is already paid".into(),
+        ));
+    }
+
+    let remaining = invoice
+        .remaining_amount
+        .or(invoice.total_amount_including_vat);
+    if let (Some(remaining), Some(amount)) = (remaining, payment.amount) {
+        if amount > remaining {
+            return Err(Error::InvalidRequest(format!(
+                "Payment amount {} exceeds remaining amount {}",
+                amount, remaining
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn money(value: &str) -> Money {
+        value.parse().unwrap()
+    }
+
+    fn invoice(currency: &str, remaining: &str) -> SupplierInvoice {
+        SupplierInvoice {
+            id: Some("si-1".to_string()),
+            currency_code: Some(currency.to_string()),
+            total_amount_including_vat: Some(money("1000")),
+            remaining_amount: Some(money(remaining)),
+            ..Default::default()
+        }
+    }
+
+    fn payment(amount: &str) -> SupplierInvoicePayment {
+        SupplierInvoicePayment::new(money(amount), chrono::Utc::now())
+    }
+
+    #[test]
+    fn test_check_payment_requires_positive_amount() {
+        assert!(check_payment(&payment("0")).is_err());
+        assert!(check_payment(&SupplierInvoicePayment::default()).is_err());
+        assert!(check_payment(&payment("10").foreign_currency("EUR", money("0"))).is_err());
+        assert!(check_payment(&payment("10")).is_ok());
+    }
+
+    #[test]
+    fn test_partial_payment_within_remaining() {
+        let invoice = invoice("SEK", "400");
+
+        assert!(check_against_invoice(&invoice, &payment("400")).is_ok());
+        assert!(check_against_invoice(&invoice, &payment("250")).is_ok());
+
+        let err = check_against_invoice(&invoice, &payment("400.01")).unwrap_err();
+        assert!(err.to_string().contains("exceeds remaining"));
+    }
+
+    #[test]
+    fn test_currency_must_match_invoice() {
+        let invoice = invoice("EUR", "100");
+
+        let eur = payment("100").foreign_currency("eur", money("11.5"));
+        assert!(check_against_invoice(&invoice, &eur).is_ok());
+
+        let usd = payment("100").foreign_currency("USD", money("10.2"));
+        assert!(check_against_invoice(&invoice, &usd).is_err());
+    }
+
+    #[test]
+    fn test_amount_in_domestic_currency() {
+        let p = payment("100").foreign_currency("EUR", money("11.5"));
+        assert_eq!(p.amount_in_domestic_currency(), Some(money("1150")));
+
+        let p = p.domestic_amount(money("1149.75"));
+        assert_eq!(p.amount_in_domestic_currency(), Some(money("1149.75")));
+
+        assert_eq!(
+            payment("100").amount_in_domestic_currency(),
+            Some(money("100"))
+        );
+    }
+}
@@ -30,6 +30,8 @@ impl<'a> SupplierInvoicesEndpoint<'a> {
         }
     }
 
+    crate::__list_all_impl!("/supplierinvoices", SupplierInvoice);
+
     /// Get a specific supplier invoice by ID.
     pub async fn get(&self, id: &str) -> Result<SupplierInvoice> {
         let path = format!("/supplierinvoices/{}", id);
@@ -38,15 +40,20 @@ impl<'a> SupplierInvoicesEndpoint<'a> {
 
     /// Create a new supplier invoice.
     pub async fn create(&self, invoice: &SupplierInvoice) -> Result<SupplierInvoice> {
+        self.client.validate_request(invoice)?;
         self.client.post("/supplierinvoices", invoice).await
     }
 
     /// Update an existing supplier invoice.
     pub async fn update(&self, id: &str, invoice: &SupplierInvoice) -> Result<SupplierInvoice> {
+        self.client.validate_request(invoice)?;
         let path = format!("/supplierinvoices/{}", id);
         self.client.put(&path, invoice).await
     }
 
+    crate::__get_versioned_impl!("/supplierinvoices", SupplierInvoice);
+    crate::__update_if_match_impl!("/supplierinvoices", SupplierInvoice, validate);
+
     /// Delete a supplier invoice.
     pub async fn delete(&self, id: &str) -> Result<()> {
         let path = format!("/supplierinvoices/{}", id);
@@ -74,6 +81,7 @@ impl<'a> SupplierInvoicesEndpoint<'a> {
             pagination: Option<PaginationParams>,
         }
 
+        let (query, pagination) = query.resolve_scope(pagination);
         let params = CombinedParams { query, pagination };
         self.client
             .get_with_params("/supplierinvoices", &params)
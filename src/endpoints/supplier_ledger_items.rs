@@ -26,6 +26,8 @@ impl<'a> SupplierLedgerItemsEndpoint<'a> {
         }
     }
 
+    crate::__list_all_impl!("/supplierledgeritems", SupplierLedgerItem);
+
     pub async fn get(&self, id: &str) -> Result<SupplierLedgerItem> {
         self.client
             .get(&format!("/supplierledgeritems/{}", id))
@@ -48,6 +50,8 @@ impl<'a> SupplierLedgerItemsEndpoint<'a> {
             #[serde(flatten)]
             pagination: Option<PaginationParams>,
         }
+
+        let (query, pagination) = query.resolve_scope(pagination);
         self.client
             .get_with_params(
                 "/supplierledgeritems",
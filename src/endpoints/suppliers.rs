@@ -2,7 +2,8 @@
 
 use crate::client::Client;
 use crate::error::Result;
-use crate::types::{PaginatedResponse, PaginationParams, QueryParams, Supplier};
+use crate::query::Filter;
+use crate::types::{ListScope, PaginatedResponse, PaginationParams, QueryParams, Supplier};
 
 /// Suppliers endpoint for managing supplier records.
 pub struct SuppliersEndpoint<'a> {
@@ -26,6 +27,8 @@ impl<'a> SuppliersEndpoint<'a> {
         }
     }
 
+    crate::__list_all_impl!("/suppliers", Supplier);
+
     /// Get a specific supplier by ID.
     pub async fn get(&self, id: &str) -> Result<Supplier> {
         let path = format!("/suppliers/{}", id);
@@ -33,16 +36,30 @@ impl<'a> SuppliersEndpoint<'a> {
     }
 
     /// Create a new supplier.
+    ///
+    /// Bankgiro and plusgiro numbers are checked and sent normalized; see
+    /// [`Bankgiro`](crate::validation::Bankgiro) and
+    /// [`Plusgiro`](crate::validation::Plusgiro).
     pub async fn create(&self, supplier: &Supplier) -> Result<Supplier> {
-        self.client.post("/suppliers", supplier).await
+        self.client.validate_request(supplier)?;
+        let supplier = crate::validation::normalize_supplier(supplier)?;
+        self.client.post("/suppliers", &*supplier).await
     }
 
     /// Update an existing supplier.
+    ///
+    /// Bankgiro and plusgiro numbers are checked and sent normalized, as
+    /// in [`create`](Self::create).
     pub async fn update(&self, id: &str, supplier: &Supplier) -> Result<Supplier> {
+        self.client.validate_request(supplier)?;
+        let supplier = crate::validation::normalize_supplier(supplier)?;
         let path = format!("/suppliers/{}", id);
-        self.client.put(&path, supplier).await
+        self.client.put(&path, &*supplier).await
     }
 
+    crate::__get_versioned_impl!("/suppliers", Supplier);
+    crate::__update_if_match_impl!("/suppliers", Supplier, validate);
+
     /// Delete a supplier.
     pub async fn delete(&self, id: &str) -> Result<()> {
         let path = format!("/suppliers/{}", id);
@@ -63,7 +80,23 @@ impl<'a> SuppliersEndpoint<'a> {
             pagination: Option<PaginationParams>,
         }
 
+        let (query, pagination) = query.resolve_scope(pagination);
         let params = CombinedParams { query, pagination };
         self.client.get_with_params("/suppliers", &params).await
     }
+
+    /// Find a supplier by supplier number.
+    ///
+    /// Deactivated suppliers keep their number, so use [`ListScope::ActiveOnly`]
+    /// unless an inactive supplier is acceptable. With [`ListScope::All`] an
+    /// active match is preferred over an inactive one.
+    pub async fn get_by_number(&self, number: &str, scope: ListScope) -> Result<Option<Supplier>> {
+        let query = QueryParams::new()
+            .filter_by(Filter::field("SupplierNumber").eq(number))
+            .scope(scope);
+        let matches = self.search(query, None).await?.data;
+        Ok(crate::endpoints::pick_in_scope(matches, scope, |m| {
+            m.is_active
+        }))
+    }
 }
@@ -1,9 +1,56 @@
 //! Users API endpoint.
 
+use crate::endpoints::collect_pages;
+use crate::error::Result;
 use crate::types::User;
+use std::collections::{HashMap, HashSet};
 
 crate::define_endpoint! {
     /// Users endpoint for accessing user information.
     UsersEndpoint, "/users", User,
     caps: [list, get]
 }
+
+impl UsersEndpoint<'_> {
+    /// Map user IDs to display names, for showing who created or changed a
+    /// record.
+    ///
+    /// Fetches the user list once. IDs of unknown users are left out of the
+    /// map.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use spiris::{Audited, Client};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let invoices = client.invoices().list(None).await?.data;
+    /// let ids = invoices.iter().flat_map(|i| [i.created_by_user_id(), i.modified_by_user_id()]);
+    /// let names = client.users().resolve_users(ids.flatten()).await?;
+    ///
+    /// for invoice in &invoices {
+    ///     let changed_by = invoice.modified_by_user_id().and_then(|id| names.get(id));
+    ///     println!("{:?} changed by {:?}", invoice.invoice_number, changed_by);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn resolve_users<I, S>(&self, ids: I) -> Result<HashMap<String, String>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let wanted: HashSet<String> = ids.into_iter().map(|id| id.as_ref().to_string()).collect();
+        if wanted.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let users = collect_pages(|p| async move { self.list(Some(p)).await }).await?;
+        Ok(users
+            .into_iter()
+            .filter_map(|user| {
+                let id = user.id.clone().filter(|id| wanted.contains(id))?;
+                Some((id, user.display_name()?))
+            })
+            .collect())
+    }
+}
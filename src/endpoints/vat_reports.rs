@@ -0,0 +1,9 @@
+//! VAT reports API endpoint.
+
+use crate::types::VatReport;
+
+crate::define_endpoint! {
+    /// VAT reports endpoint for reading VAT returns and their approval status.
+    VatReportsEndpoint, "/vatreports", VatReport,
+    caps: [list, get]
+}
@@ -1,8 +1,68 @@
 //! Vouchers API endpoint.
 
 use crate::client::Client;
-use crate::error::Result;
+use crate::endpoints::ReferenceData;
+use crate::error::{Error, Result, ValidationError};
+use crate::periods::PeriodLocks;
 use crate::types::{PaginatedResponse, PaginationParams, QueryParams, Voucher};
+use crate::validation::Validate;
+
+/// How [`VouchersEndpoint::create_batch`] handles a voucher that fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Atomicity {
+    /// Post every voucher that passes validation and report the rest.
+    BestEffort,
+    /// Post nothing if any voucher fails validation, and stop at the first
+    /// voucher the API rejects.
+    AbortOnFirstError,
+}
+
+/// A voucher posted by a batch.
+#[derive(Debug, Clone)]
+pub struct PostedVoucher {
+    /// Position of the voucher in the batch.
+    pub index: usize,
+    /// ID of the created voucher.
+    pub id: Option<String>,
+    /// Voucher number assigned by the API.
+    pub voucher_number: Option<String>,
+}
+
+/// A voucher in a batch that was not posted.
+#[derive(Debug)]
+pub struct VoucherBatchFailure {
+    /// Position of the voucher in the batch.
+    pub index: usize,
+    /// [`Error::Validation`] if the voucher failed the checks before
+    /// posting, otherwise the error from the API.
+    pub error: Error,
+}
+
+/// Outcome of [`VouchersEndpoint::create_batch`].
+#[derive(Debug, Default)]
+pub struct VoucherBatchResult {
+    /// Vouchers posted, in batch order.
+    pub posted: Vec<PostedVoucher>,
+    /// Vouchers that failed.
+    pub failures: Vec<VoucherBatchFailure>,
+    /// Positions of vouchers not attempted because the batch was aborted.
+    pub skipped: Vec<usize>,
+}
+
+impl VoucherBatchResult {
+    /// Whether every voucher was posted.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty() && self.skipped.is_empty()
+    }
+
+    /// Voucher numbers of the posted vouchers.
+    pub fn voucher_numbers(&self) -> Vec<&str> {
+        self.posted
+            .iter()
+            .filter_map(|posted| posted.voucher_number.as_deref())
+            .collect()
+    }
+}
 
 /// Vouchers endpoint for managing journal entries.
 pub struct VouchersEndpoint<'a> {
@@ -26,6 +86,8 @@ impl<'a> VouchersEndpoint<'a> {
         }
     }
 
+    crate::__list_all_impl!("/vouchers", Voucher);
+
     /// Get a specific voucher by ID.
     pub async fn get(&self, id: &str) -> Result<Voucher> {
         let path = format!("/vouchers/{}", id);
@@ -34,15 +96,102 @@ impl<'a> VouchersEndpoint<'a> {
 
     /// Create a new voucher.
     pub async fn create(&self, voucher: &Voucher) -> Result<Voucher> {
+        self.client.validate_request(voucher)?;
         self.client.post("/vouchers", voucher).await
     }
 
+    /// Create several vouchers, checking all of them first.
+    ///
+    /// Before anything is posted, every voucher is checked for balanced
+    /// rows, a date in an open period and accounts that exist in the chart
+    /// of accounts. Vouchers are then posted in order. The API has no
+    /// transactions, so with [`Atomicity::AbortOnFirstError`] a voucher the
+    /// API rejects after the checks stops the batch but leaves the vouchers
+    /// before it posted; they are listed in the result.
+    ///
+    /// Fails only if the fiscal years, lock date or accounts cannot be
+    /// loaded; problems with individual vouchers are reported in the
+    /// result.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use spiris::endpoints::vouchers::Atomicity;
+    /// # use spiris::{Client, Voucher};
+    /// # async fn example(client: &Client, payroll: Vec<Voucher>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let result = client
+    ///     .vouchers()
+    ///     .create_batch(&payroll, Atomicity::AbortOnFirstError)
+    ///     .await?;
+    ///
+    /// println!("Posted {}", result.voucher_numbers().join(", "));
+    /// for failure in &result.failures {
+    ///     println!("Voucher {} failed: {}", failure.index + 1, failure.error);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_batch(
+        &self,
+        vouchers: &[Voucher],
+        atomicity: Atomicity,
+    ) -> Result<VoucherBatchResult> {
+        let reference = self.client.reference_data().load_all().await?;
+        let locks = PeriodLocks::load(self.client).await?;
+
+        let mut result = VoucherBatchResult::default();
+        let mut valid = Vec::new();
+        for (index, voucher) in vouchers.iter().enumerate() {
+            let errors = batch_errors(voucher, &reference, &locks);
+            if errors.is_empty() {
+                valid.push(index);
+            } else {
+                result.failures.push(VoucherBatchFailure {
+                    index,
+                    error: Error::Validation(errors),
+                });
+            }
+        }
+
+        if atomicity == Atomicity::AbortOnFirstError && !result.failures.is_empty() {
+            result.skipped = valid;
+            return Ok(result);
+        }
+
+        let mut pending = valid.into_iter();
+        for index in pending.by_ref() {
+            match self
+                .client
+                .post::<Voucher, _>("/vouchers", &vouchers[index])
+                .await
+            {
+                Ok(created) => result.posted.push(PostedVoucher {
+                    index,
+                    id: created.id,
+                    voucher_number: created.voucher_number,
+                }),
+                Err(error) => {
+                    result.failures.push(VoucherBatchFailure { index, error });
+                    if atomicity == Atomicity::AbortOnFirstError {
+                        break;
+                    }
+                }
+            }
+        }
+        result.skipped = pending.collect();
+        Ok(result)
+    }
+
     /// Update an existing voucher.
     pub async fn update(&self, id: &str, voucher: &Voucher) -> Result<Voucher> {
+        self.client.validate_request(voucher)?;
         let path = format!("/vouchers/{}", id);
         self.client.put(&path, voucher).await
     }
 
+    crate::__get_versioned_impl!("/vouchers", Voucher);
+    crate::__update_if_match_impl!("/vouchers", Voucher, validate);
+
     /// Delete a voucher.
     pub async fn delete(&self, id: &str) -> Result<()> {
         let path = format!("/vouchers/{}", id);
@@ -63,7 +212,114 @@ impl<'a> VouchersEndpoint<'a> {
             pagination: Option<PaginationParams>,
         }
 
+        let (query, pagination) = query.resolve_scope(pagination);
         let params = CombinedParams { query, pagination };
         self.client.get_with_params("/vouchers", &params).await
     }
 }
+
+/// Problems that would make the API reject a voucher in a batch.
+fn batch_errors(
+    voucher: &Voucher,
+    reference: &ReferenceData,
+    locks: &PeriodLocks,
+) -> Vec<ValidationError> {
+    let mut errors = voucher.validation_errors();
+    let mut push = |field: String, message: String| {
+        errors.push(ValidationError { field, message });
+    };
+
+    if let Some(date) = voucher.voucher_date {
+        if let Err(Error::InvalidRequest(message)) = locks.check(date.date_naive()) {
+            push("VoucherDate".to_string(), message);
+        }
+    }
+    for (index, row) in voucher.rows.iter().enumerate() {
+        let Some(number) = row
+            .account_number
+            .as_deref()
+            .filter(|n| !n.trim().is_empty())
+        else {
+            continue;
+        };
+        let field = format!("Rows[{}].AccountNumber", index);
+        match reference.account(number) {
+            None => push(field, format!("account {} does not exist", number)),
+            Some(account) if account.is_active == Some(false) => {
+                push(field, format!("account {} is inactive", number))
+            }
+            Some(_) => {}
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Account, FiscalYear, VoucherRow};
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    fn row(account: &str, debit: f64, credit: f64) -> VoucherRow {
+        VoucherRow {
+            account_number: Some(account.to_string()),
+            debit_amount: Some(debit.to_string().parse().unwrap()),
+            credit_amount: Some(credit.to_string().parse().unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_batch_errors() {
+        let account = |number: &str, active: bool| Account {
+            account_number: Some(number.to_string()),
+            is_active: Some(active),
+            ..Default::default()
+        };
+        let reference = ReferenceData::new(
+            vec![
+                account("1930", true),
+                account("7010", true),
+                account("7011", false),
+            ],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+        );
+        let locks = PeriodLocks::new(
+            vec![FiscalYear {
+                start_date: Some(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()),
+                end_date: Some(Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap()),
+                ..Default::default()
+            }],
+            NaiveDate::from_ymd_opt(2025, 2, 28),
+        );
+
+        let voucher = Voucher {
+            voucher_date: Some(Utc.with_ymd_and_hms(2025, 3, 25, 0, 0, 0).unwrap()),
+            rows: vec![row("7010", 100.0, 0.0), row("1930", 0.0, 100.0)],
+            ..Default::default()
+        };
+        assert!(batch_errors(&voucher, &reference, &locks).is_empty());
+
+        let voucher = Voucher {
+            voucher_date: Some(Utc.with_ymd_and_hms(2025, 2, 25, 0, 0, 0).unwrap()),
+            rows: vec![row("7011", 100.0, 0.0), row("1910", 0.0, 100.0)],
+            ..Default::default()
+        };
+        let errors: Vec<String> = batch_errors(&voucher, &reference, &locks)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                "VoucherDate: 2025-02-25 is in a locked period (locked through 2025-02-28)",
+                "Rows[0].AccountNumber: account 7011 is inactive",
+                "Rows[1].AccountNumber: account 1910 does not exist",
+            ]
+        );
+    }
+}
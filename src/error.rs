@@ -1,7 +1,9 @@
 //! Error types for the Visma eAccounting API client.
 
+use crate::client::Language;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Result type for Visma eAccounting API operations.
@@ -13,13 +15,17 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// when a request fails. It provides structured access to error details including
 /// an error code, message, and any field-level validation errors.
 ///
+/// Validation errors are read from a `ValidationErrors` (or `Errors`) list of
+/// `{"Field": .., "Message": ..}` objects, or from a map of field name to
+/// messages.
+///
 /// # Example
 ///
 /// ```
 /// use spiris::error::{Error, ApiErrorResponse};
 ///
 /// fn handle_error(err: Error) {
-///     if let Error::ApiError { status_code, response, .. } = err {
+///     if let Error::ApiError { status_code, response, .. } = err.root() {
 ///         println!("Error {}: {}", status_code, response.message);
 ///         for validation_err in &response.validation_errors {
 ///             println!("  Field '{}': {}", validation_err.field, validation_err.message);
@@ -30,17 +36,33 @@ pub type Result<T> = std::result::Result<T, Error>;
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ApiErrorResponse {
-    /// Error code from the API (if provided).
-    #[serde(default)]
+    /// Error code from the API (if provided), e.g. `4000`.
+    #[serde(default, deserialize_with = "de::error_code")]
     pub error_code: Option<String>,
 
     /// Human-readable error message.
+    ///
+    /// Falls back to [`developer_message`](Self::developer_message) when the
+    /// body has no `Message`.
     #[serde(default)]
     pub message: String,
 
-    /// Field-level validation errors.
+    /// Technical description of the error, meant for developers.
+    #[serde(default, rename = "DeveloperErrorMessage")]
+    pub developer_message: Option<String>,
+
+    /// Id of the error in the API's logs; quote it to Visma support.
     #[serde(default)]
+    pub error_id: Option<String>,
+
+    /// Field-level validation errors.
+    #[serde(default, alias = "Errors", deserialize_with = "de::validation_errors")]
     pub validation_errors: Vec<ValidationError>,
+
+    /// Language of the messages, from the response's `Content-Language`
+    /// or the language the client asked for.
+    #[serde(skip)]
+    pub language: Option<Language>,
 }
 
 impl ApiErrorResponse {
@@ -49,9 +71,22 @@ impl ApiErrorResponse {
     /// This is used as a fallback when the API returns a non-JSON error body.
     pub fn from_raw(message: String) -> Self {
         Self {
-            error_code: None,
             message,
-            validation_errors: vec![],
+            ..Default::default()
+        }
+    }
+
+    /// Parse an error response body, falling back to the raw body as the
+    /// message if it is not a JSON error object.
+    pub fn parse(raw_body: &str) -> Self {
+        match serde_json::from_str::<ApiErrorResponse>(raw_body) {
+            Ok(mut response) => {
+                if response.message.is_empty() {
+                    response.message = response.developer_message.clone().unwrap_or_default();
+                }
+                response
+            }
+            Err(_) => Self::from_raw(raw_body.to_string()),
         }
     }
 
@@ -103,7 +138,152 @@ impl fmt::Display for ValidationError {
     }
 }
 
+mod de {
+    use super::ValidationError;
+    use serde::{Deserialize, Deserializer};
+    use std::collections::BTreeMap;
+
+    /// The API sends numeric codes; older endpoints send strings.
+    pub fn error_code<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<String>, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Code {
+            Number(i64),
+            Text(String),
+        }
+        Ok(
+            Option::<Code>::deserialize(deserializer)?.map(|code| match code {
+                Code::Number(n) => n.to_string(),
+                Code::Text(s) => s,
+            }),
+        )
+    }
+
+    /// Either a list of field errors or a map of field name to messages.
+    pub fn validation_errors<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<ValidationError>, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Messages {
+            One(String),
+            Many(Vec<String>),
+        }
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Errors {
+            List(Vec<ValidationError>),
+            ByField(BTreeMap<String, Messages>),
+        }
+        Ok(match Option::<Errors>::deserialize(deserializer)? {
+            None => Vec::new(),
+            Some(Errors::List(errors)) => errors,
+            Some(Errors::ByField(fields)) => fields
+                .into_iter()
+                .flat_map(|(field, messages)| {
+                    let messages = match messages {
+                        Messages::One(message) => vec![message],
+                        Messages::Many(messages) => messages,
+                    };
+                    messages.into_iter().map(move |message| ValidationError {
+                        field: field.clone(),
+                        message,
+                    })
+                })
+                .collect(),
+        })
+    }
+}
+
+fn join_validation_errors(errors: &[ValidationError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// The request an error came from.
+///
+/// Attached by the client to every error from an API call; see
+/// [`Error::context`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// HTTP method, such as `GET`.
+    pub method: &'static str,
+    /// Request path relative to the API base URL, without query string.
+    pub path: String,
+    /// Endpoint name, the first segment of the path (e.g. `customers`).
+    pub endpoint: String,
+    /// Id of the entity addressed, the path segment after the endpoint.
+    pub entity_id: Option<String>,
+    /// Requested page for paginated list calls.
+    pub page: Option<u32>,
+    /// Full URL of the request, including the query string.
+    pub url: Option<String>,
+    /// Id the API gave the request, from the response's `X-Correlation-Id`
+    /// or `X-Request-Id` header. Quote it when reporting a problem to Visma.
+    pub correlation_id: Option<String>,
+}
+
+impl ErrorContext {
+    /// Describe a request to `path`.
+    pub fn new(method: &'static str, path: &str) -> Self {
+        let path = path.split('?').next().unwrap_or_default();
+        let path = path.trim_start_matches('/').to_string();
+        let mut segments = path.split('/').filter(|s| !s.is_empty());
+        let endpoint = segments.next().unwrap_or_default().to_string();
+        let entity_id = segments.next().map(str::to_string);
+
+        Self {
+            method,
+            path,
+            endpoint,
+            entity_id,
+            page: None,
+            url: None,
+            correlation_id: None,
+        }
+    }
+
+    /// Set the requested page.
+    pub fn page(mut self, page: Option<u32>) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// Set the full request URL.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Set the API's correlation id for the request.
+    pub fn correlation_id(mut self, correlation_id: Option<String>) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} /{}", self.method, self.path)?;
+        if let Some(page) = self.page {
+            write!(f, ", page {}", page)?;
+        }
+        if let Some(id) = &self.correlation_id {
+            write!(f, ", correlation id {}", id)?;
+        }
+        Ok(())
+    }
+}
+
 /// Errors that can occur when using the Visma eAccounting API client.
+///
+/// Errors from API calls arrive wrapped in [`Error::WithContext`]; match on
+/// [`Error::root`] to inspect the underlying error.
 #[derive(Error, Debug)]
 pub enum Error {
     /// HTTP request failed.
@@ -114,6 +294,10 @@ pub enum Error {
     #[error("JSON parsing failed: {0}")]
     Json(#[from] serde_json::Error),
 
+    /// Reading or writing a local file failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     /// API returned an error response.
     ///
     /// This error includes the HTTP status code, a structured response
@@ -125,7 +309,7 @@ pub enum Error {
     /// use spiris::error::Error;
     ///
     /// fn check_error(err: &Error) {
-    ///     if let Error::ApiError { status_code, response, raw_body } = err {
+    ///     if let Error::ApiError { status_code, response, raw_body } = err.root() {
     ///         println!("Status: {}", status_code);
     ///         println!("Message: {}", response.message);
     ///         if response.has_validation_errors() {
@@ -141,7 +325,7 @@ pub enum Error {
         /// HTTP status code.
         status_code: u16,
         /// Parsed error response.
-        response: ApiErrorResponse,
+        response: Box<ApiErrorResponse>,
         /// Raw response body for debugging.
         raw_body: String,
     },
@@ -154,13 +338,33 @@ pub enum Error {
     #[error("Access token has expired")]
     TokenExpired,
 
+    /// The access token lacks scopes the client requires.
+    ///
+    /// See [`ClientConfig::required_scopes`](crate::ClientConfig::required_scopes).
+    #[error("Access token is missing required scope(s): {}", missing.join(", "))]
+    InsufficientScopes {
+        /// Required scopes that were not granted.
+        missing: Vec<String>,
+    },
+
     /// Invalid configuration.
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
 
     /// Rate limit exceeded.
-    #[error("Rate limit exceeded: {0}")]
-    RateLimitExceeded(String),
+    #[error("Rate limit exceeded: {message}")]
+    RateLimitExceeded {
+        /// Response body.
+        message: String,
+        /// How long the API asked to wait before trying again, from the
+        /// `Retry-After` header.
+        retry_after: Option<Duration>,
+    },
+
+    /// The entity changed on the server since it was fetched, so a
+    /// conditional update was rejected (HTTP 412).
+    #[error("Entity was changed by someone else: {0}")]
+    Conflict(String),
 
     /// Resource not found.
     #[error("Resource not found: {0}")]
@@ -170,6 +374,32 @@ pub enum Error {
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
 
+    /// Request body failed client-side validation.
+    ///
+    /// Lists every problem found; see [`crate::validation`].
+    #[error("Validation failed: {}", join_validation_errors(.0))]
+    Validation(Vec<ValidationError>),
+
+    /// Retrying was stopped by [`RetryConfig::max_elapsed_time`](crate::RetryConfig::max_elapsed_time).
+    #[error(
+        "Retry deadline of {deadline:?} exceeded after {attempts} attempt(s) in {elapsed:?}: {last_error}"
+    )]
+    RetryDeadlineExceeded {
+        /// The enforced deadline.
+        deadline: Duration,
+        /// Time spent on the call, including backoff.
+        elapsed: Duration,
+        /// Number of attempts made, including one abandoned at the deadline.
+        attempts: u32,
+        /// Error from the last completed attempt.
+        last_error: Box<Error>,
+    },
+
+    /// The call was cancelled through
+    /// [`RequestOptions::cancellation`](crate::RequestOptions::cancellation).
+    #[error("Request was cancelled")]
+    Cancelled,
+
     /// URL parsing error.
     #[error("URL parsing failed: {0}")]
     UrlParseError(#[from] url::ParseError),
@@ -177,6 +407,28 @@ pub enum Error {
     /// OAuth2 error.
     #[error("OAuth2 error: {0}")]
     OAuth2Error(String),
+
+    /// An error together with the request that caused it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spiris::error::{Error, ErrorContext};
+    ///
+    /// let err = Error::NotFound("Customer not found".to_string())
+    ///     .with_context(ErrorContext::new("GET", "/customers/abc-123"));
+    ///
+    /// assert_eq!(err.endpoint(), Some("customers"));
+    /// assert_eq!(err.entity_id(), Some("abc-123"));
+    /// assert!(matches!(err.root(), Error::NotFound(_)));
+    /// ```
+    #[error("{source} [{context}]")]
+    WithContext {
+        /// The request.
+        context: Box<ErrorContext>,
+        /// The underlying error.
+        source: Box<Error>,
+    },
 }
 
 impl Error {
@@ -185,42 +437,167 @@ impl Error {
     /// Attempts to parse the body as JSON. If parsing fails, uses the
     /// raw body as the error message.
     pub fn from_api_response(status_code: u16, raw_body: String) -> Self {
-        let response: ApiErrorResponse = serde_json::from_str(&raw_body)
-            .unwrap_or_else(|_| ApiErrorResponse::from_raw(raw_body.clone()));
+        Self::from_api_response_in(status_code, raw_body, None)
+    }
+
+    /// Like [`from_api_response`](Self::from_api_response), for a body whose
+    /// messages are in `language`.
+    pub fn from_api_response_in(
+        status_code: u16,
+        raw_body: String,
+        language: Option<Language>,
+    ) -> Self {
+        let mut response = ApiErrorResponse::parse(&raw_body);
+        response.language = language;
 
         Error::ApiError {
             status_code,
-            response,
+            response: Box::new(response),
             raw_body,
         }
     }
 
+    /// Attach the request that caused this error.
+    ///
+    /// An error that already has a context keeps it, only taking the page,
+    /// URL and correlation id from `context` if it lacks them.
+    pub fn with_context(self, context: ErrorContext) -> Self {
+        match self {
+            Error::WithContext {
+                context: mut existing,
+                source,
+            } => {
+                existing.page = existing.page.or(context.page);
+                existing.url = existing.url.or(context.url);
+                existing.correlation_id = existing.correlation_id.or(context.correlation_id);
+                Error::WithContext {
+                    context: existing,
+                    source,
+                }
+            }
+            source => Error::WithContext {
+                context: Box::new(context),
+                source: Box::new(source),
+            },
+        }
+    }
+
+    /// The error without its request context.
+    pub fn root(&self) -> &Error {
+        match self {
+            Error::WithContext { source, .. } => source.root(),
+            _ => self,
+        }
+    }
+
+    /// Consume the error, dropping its request context.
+    pub fn into_root(self) -> Error {
+        match self {
+            Error::WithContext { source, .. } => source.into_root(),
+            _ => self,
+        }
+    }
+
+    /// The request that caused this error, if known.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Error::WithContext { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Endpoint name of the failed request.
+    pub fn endpoint(&self) -> Option<&str> {
+        self.context().map(|c| c.endpoint.as_str())
+    }
+
+    /// HTTP method of the failed request.
+    pub fn method(&self) -> Option<&'static str> {
+        self.context().map(|c| c.method)
+    }
+
+    /// Id of the entity the failed request addressed.
+    pub fn entity_id(&self) -> Option<&str> {
+        self.context().and_then(|c| c.entity_id.as_deref())
+    }
+
+    /// Page requested by the failed list request.
+    pub fn page(&self) -> Option<u32> {
+        self.context().and_then(|c| c.page)
+    }
+
+    /// Full URL of the failed request.
+    pub fn url(&self) -> Option<&str> {
+        self.context().and_then(|c| c.url.as_deref())
+    }
+
+    /// The API's correlation id for the failed request.
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.context().and_then(|c| c.correlation_id.as_deref())
+    }
+
     /// Check if this error is retryable.
     ///
     /// Returns `true` for transient errors like rate limiting or server errors.
     pub fn is_retryable(&self) -> bool {
-        match self {
-            Error::RateLimitExceeded(_) => true,
+        match self.root() {
+            Error::RateLimitExceeded { .. } => true,
             Error::ApiError { status_code, .. } => *status_code >= 500,
             Error::Http(e) => e.is_timeout() || e.is_connect(),
             _ => false,
         }
     }
 
+    /// Whether the API rejected the request for exceeding its rate limit.
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self.root(), Error::RateLimitExceeded { .. })
+    }
+
+    /// Whether the request failed for lack of valid credentials: a 401 or
+    /// 403 response, an expired token, missing scopes or a failed OAuth2
+    /// exchange. Retrying will not help until the user signs in again.
+    pub fn is_auth_error(&self) -> bool {
+        matches!(
+            self.root(),
+            Error::AuthError(_)
+                | Error::TokenExpired
+                | Error::InsufficientScopes { .. }
+                | Error::OAuth2Error(_)
+        )
+    }
+
+    /// How long the API asked to wait before retrying a rate-limited request.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self.root() {
+            Error::RateLimitExceeded { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
     /// Get the HTTP status code if this is an API error.
     pub fn status_code(&self) -> Option<u16> {
-        match self {
+        match self.root() {
             Error::ApiError { status_code, .. } => Some(*status_code),
             _ => None,
         }
     }
 
-    /// Get the validation errors if this is an API error with validation failures.
+    /// The parsed error body if this is an API error.
+    pub fn api_response(&self) -> Option<&ApiErrorResponse> {
+        match self.root() {
+            Error::ApiError { response, .. } => Some(response),
+            _ => None,
+        }
+    }
+
+    /// Get the validation errors if this is an API error with validation failures
+    /// or a client-side validation error.
     pub fn validation_errors(&self) -> Option<&[ValidationError]> {
-        match self {
+        match self.root() {
             Error::ApiError { response, .. } if response.has_validation_errors() => {
                 Some(&response.validation_errors)
             }
+            Error::Validation(errors) => Some(errors),
             _ => None,
         }
     }
@@ -257,12 +634,43 @@ mod tests {
         assert_eq!(response.validation_errors[0].message, "Name is required");
     }
 
+    #[test]
+    fn test_api_error_response_parses_visma_error_body() {
+        let json = r#"{
+            "ErrorCode": 4000,
+            "DeveloperErrorMessage": "Customer number is already in use",
+            "ErrorId": "8f1c2a",
+            "ValidationErrors": {"CustomerNumber": ["Must be unique"], "Name": "Required"}
+        }"#;
+
+        let response = ApiErrorResponse::parse(json);
+        assert_eq!(response.error_code.as_deref(), Some("4000"));
+        assert_eq!(response.message, "Customer number is already in use");
+        assert_eq!(response.error_id.as_deref(), Some("8f1c2a"));
+        assert_eq!(
+            response
+                .validation_error_for("CustomerNumber")
+                .unwrap()
+                .message,
+            "Must be unique"
+        );
+        assert_eq!(
+            response.validation_error_for("Name").unwrap().message,
+            "Required"
+        );
+
+        let errors =
+            ApiErrorResponse::parse(r#"{"Errors": [{"Field": "Email", "Message": "Invalid"}]}"#);
+        assert_eq!(errors.validation_errors.len(), 1);
+    }
+
     #[test]
     fn test_api_error_response_has_validation_errors() {
         let response = ApiErrorResponse {
             error_code: None,
             message: "Test".to_string(),
             validation_errors: vec![],
+            ..Default::default()
         };
         assert!(!response.has_validation_errors());
 
@@ -273,6 +681,7 @@ mod tests {
                 field: "Field".to_string(),
                 message: "Error".to_string(),
             }],
+            ..Default::default()
         };
         assert!(response.has_validation_errors());
     }
@@ -292,6 +701,7 @@ mod tests {
                     message: "Invalid".to_string(),
                 },
             ],
+            ..Default::default()
         };
 
         assert_eq!(
@@ -311,6 +721,7 @@ mod tests {
             error_code: Some("ERR001".to_string()),
             message: "Something failed".to_string(),
             validation_errors: vec![],
+            ..Default::default()
         };
         assert_eq!(response.to_string(), "Something failed");
 
@@ -327,6 +738,7 @@ mod tests {
                     message: "Invalid".to_string(),
                 },
             ],
+            ..Default::default()
         };
         assert_eq!(
             response.to_string(),
@@ -376,7 +788,11 @@ mod tests {
 
     #[test]
     fn test_error_is_retryable() {
-        assert!(Error::RateLimitExceeded("test".to_string()).is_retryable());
+        assert!(Error::RateLimitExceeded {
+            message: "test".to_string(),
+            retry_after: None,
+        }
+        .is_retryable());
         assert!(Error::from_api_response(500, "Server error".to_string()).is_retryable());
         assert!(Error::from_api_response(502, "Bad gateway".to_string()).is_retryable());
         assert!(!Error::from_api_response(400, "Bad request".to_string()).is_retryable());
@@ -406,4 +822,85 @@ mod tests {
 
         assert!(Error::TokenExpired.validation_errors().is_none());
     }
+
+    #[test]
+    fn test_error_context_from_path() {
+        let context = ErrorContext::new("PUT", "/customerinvoicedrafts/d-1/convert?x=1");
+        assert_eq!(context.endpoint, "customerinvoicedrafts");
+        assert_eq!(context.entity_id.as_deref(), Some("d-1"));
+        assert_eq!(context.path, "customerinvoicedrafts/d-1/convert");
+
+        let context = ErrorContext::new("GET", "/customers").page(Some(3));
+        assert!(context.entity_id.is_none());
+        assert_eq!(context.to_string(), "GET /customers, page 3");
+    }
+
+    #[test]
+    fn test_error_with_context() {
+        let err = Error::from_api_response(503, "Unavailable".to_string())
+            .with_context(ErrorContext::new("GET", "/invoices").page(Some(2)))
+            .with_context(ErrorContext::new("POST", "/other"));
+
+        assert_eq!(err.method(), Some("GET"));
+        assert_eq!(err.endpoint(), Some("invoices"));
+        assert_eq!(err.page(), Some(2));
+        assert!(err.entity_id().is_none());
+        assert_eq!(err.status_code(), Some(503));
+        assert!(err.is_retryable());
+        assert_eq!(
+            err.to_string(),
+            "API error (503): Unavailable [GET /invoices, page 2]"
+        );
+        assert!(matches!(err.into_root(), Error::ApiError { .. }));
+
+        assert!(Error::TokenExpired.context().is_none());
+        assert!(Error::TokenExpired.endpoint().is_none());
+    }
+
+    #[test]
+    fn test_with_context_fills_in_missing_details() {
+        let err = Error::NotFound("gone".to_string())
+            .with_context(
+                ErrorContext::new("GET", "/customers")
+                    .url("https://api/v2/customers?page=2")
+                    .correlation_id(Some("c0ffee".to_string())),
+            )
+            .with_context(ErrorContext::new("GET", "/customers").page(Some(2)));
+
+        assert_eq!(err.page(), Some(2));
+        assert_eq!(err.url(), Some("https://api/v2/customers?page=2"));
+        assert_eq!(err.correlation_id(), Some("c0ffee"));
+        assert!(err
+            .to_string()
+            .ends_with("[GET /customers, page 2, correlation id c0ffee]"));
+    }
+
+    #[test]
+    fn test_error_classification() {
+        let rate_limited = Error::RateLimitExceeded {
+            message: "slow down".to_string(),
+            retry_after: None,
+        }
+        .with_context(ErrorContext::new("GET", "/customers"));
+        assert!(rate_limited.is_rate_limited());
+        assert!(rate_limited.is_retryable());
+        assert!(!rate_limited.is_auth_error());
+
+        for err in [
+            Error::AuthError("Forbidden".to_string()),
+            Error::TokenExpired,
+            Error::InsufficientScopes {
+                missing: vec!["ea:sales".to_string()],
+            },
+        ] {
+            assert!(err.is_auth_error());
+            assert!(!err.is_retryable());
+            assert!(!err.is_rate_limited());
+        }
+
+        let server = Error::from_api_response(503, "Unavailable".to_string());
+        assert!(server.is_retryable());
+        assert!(!server.is_rate_limited());
+        assert!(!server.is_auth_error());
+    }
 }
@@ -0,0 +1,675 @@
+//! CSV export and import of customers, articles and invoices.
+//!
+//! [`write`] turns records into CSV with one column per field, and [`read`]
+//! parses such a file back into records ready to pass to the endpoint's
+//! `create`. A line that can't be parsed doesn't stop the import; it is
+//! reported in [`CsvImport::errors`] with its line number.
+//!
+//! Invoices take one line per invoice row, with the invoice's own columns
+//! repeated. On import, consecutive lines with the same `InvoiceNumber` make
+//! up one invoice; the number only groups the lines and is not copied to the
+//! invoice, since the API assigns it.
+//!
+//! [`CsvOptions::excel_sv`] writes files that Excel opens correctly with
+//! Swedish settings: `;` between fields, decimal commas and a UTF-8 byte
+//! order mark.
+//!
+//! Requires the `csv` feature.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::export::csv::{self, CsvOptions};
+//! use spiris::Customer;
+//! # async fn example(client: &spiris::Client) -> spiris::Result<()> {
+//! let customers = client.customers().list_all(None).await?;
+//! let file = std::fs::File::create("kunder.csv").unwrap();
+//! csv::write(file, &customers, &CsvOptions::excel_sv())?;
+//!
+//! let file = std::fs::File::open("nya-kunder.csv").unwrap();
+//! let import = csv::read::<Customer>(file, &CsvOptions::excel_sv())?;
+//! for error in &import.errors {
+//!     eprintln!("line {}: {}", error.line, error.message);
+//! }
+//! for customer in &import.records {
+//!     client.customers().create(customer).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::types::{Address, Article, Customer, Invoice, InvoiceRow, Money};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::io;
+
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// How CSV files are written and read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// Field separator.
+    pub delimiter: u8,
+    /// Write amounts with a decimal comma, and read them that way.
+    pub decimal_comma: bool,
+    /// Start written files with a UTF-8 byte order mark. A mark at the start
+    /// of a file being read is always skipped.
+    pub bom: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            decimal_comma: false,
+            bom: false,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Comma separated, decimal points, no byte order mark.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Semicolon separated with decimal commas and a byte order mark, as
+    /// Excel with Swedish settings expects.
+    pub fn excel_sv() -> Self {
+        Self {
+            delimiter: b';',
+            decimal_comma: true,
+            bom: true,
+        }
+    }
+
+    /// Set the field separator.
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Use a decimal comma in amounts.
+    pub fn decimal_comma(mut self, decimal_comma: bool) -> Self {
+        self.decimal_comma = decimal_comma;
+        self
+    }
+
+    /// Write a UTF-8 byte order mark.
+    pub fn bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    fn amount(&self, value: Option<Money>) -> String {
+        let Some(value) = value else {
+            return String::new();
+        };
+        let text = value.to_string();
+        if self.decimal_comma {
+            text.replace('.', ",")
+        } else {
+            text
+        }
+    }
+}
+
+/// A record type with a CSV layout.
+pub trait CsvRecord: Sized {
+    /// Column headers, in order.
+    const HEADERS: &'static [&'static str];
+
+    /// Column whose value groups consecutive lines into one record, for
+    /// records spread over several lines.
+    const GROUP_BY: Option<&'static str> = None;
+
+    /// The lines for this record, each with a value per header.
+    fn to_lines(&self, options: &CsvOptions) -> Vec<Vec<String>>;
+
+    /// Build a record from its lines.
+    fn from_lines(lines: &[CsvLine<'_>]) -> std::result::Result<Self, String>;
+}
+
+/// One line of a CSV file being read, with values looked up by header.
+#[derive(Debug)]
+pub struct CsvLine<'a> {
+    headers: &'a HashMap<String, usize>,
+    record: &'a ::csv::StringRecord,
+    options: &'a CsvOptions,
+    /// Line number in the file, counting the header as line 1.
+    pub line: usize,
+}
+
+impl CsvLine<'_> {
+    /// The trimmed value in column `header`, `None` if empty or missing.
+    pub fn get(&self, header: &str) -> Option<&str> {
+        let index = *self.headers.get(header)?;
+        self.record
+            .get(index)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    }
+
+    /// The value in column `header` as a string.
+    pub fn string(&self, header: &str) -> Option<String> {
+        self.get(header).map(str::to_string)
+    }
+
+    /// The value in column `header` as an amount.
+    pub fn amount(&self, header: &str) -> std::result::Result<Option<Money>, String> {
+        let Some(value) = self.get(header) else {
+            return Ok(None);
+        };
+        let normalized: String = value
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| {
+                if c == ',' && self.options.decimal_comma {
+                    '.'
+                } else {
+                    c
+                }
+            })
+            .collect();
+        normalized
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("invalid amount '{}' in {}", value, header))
+    }
+
+    /// The value in column `header` parsed with [`str::parse`].
+    pub fn parse<T: std::str::FromStr>(
+        &self,
+        header: &str,
+    ) -> std::result::Result<Option<T>, String> {
+        self.get(header)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("invalid value '{}' in {}", value, header))
+            })
+            .transpose()
+    }
+
+    /// The value in column `header` as a `YYYY-MM-DD` date.
+    pub fn date(&self, header: &str) -> std::result::Result<Option<NaiveDate>, String> {
+        self.parse(header)
+    }
+
+    /// The value in column `header` as a boolean: `true`/`false`, `1`/`0`
+    /// or `ja`/`nej`.
+    pub fn bool(&self, header: &str) -> std::result::Result<Option<bool>, String> {
+        self.get(header)
+            .map(|value| match value.to_lowercase().as_str() {
+                "true" | "1" | "ja" | "yes" => Ok(true),
+                "false" | "0" | "nej" | "no" => Ok(false),
+                _ => Err(format!("invalid yes/no value '{}' in {}", value, header)),
+            })
+            .transpose()
+    }
+}
+
+/// A record [`read`] could not parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRowError {
+    /// Line number of the record's first line, counting the header as 1.
+    pub line: usize,
+    /// What was wrong.
+    pub message: String,
+}
+
+/// Records parsed by [`read`].
+#[derive(Debug, Clone)]
+pub struct CsvImport<T> {
+    /// Records that parsed, in file order.
+    pub records: Vec<T>,
+    /// Records that did not.
+    pub errors: Vec<CsvRowError>,
+}
+
+impl<T> CsvImport<T> {
+    /// Whether every record parsed.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Write `records` as CSV, with a header line.
+pub fn write<T: CsvRecord, W: io::Write>(
+    mut writer: W,
+    records: &[T],
+    options: &CsvOptions,
+) -> Result<()> {
+    if options.bom {
+        writer.write_all(BOM)?;
+    }
+    let mut csv = ::csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .from_writer(writer);
+    csv.write_record(T::HEADERS).map_err(csv_error)?;
+    for record in records {
+        for line in record.to_lines(options) {
+            csv.write_record(&line).map_err(csv_error)?;
+        }
+    }
+    Ok(csv.flush()?)
+}
+
+/// Write `records` as CSV into a byte vector.
+pub fn to_bytes<T: CsvRecord>(records: &[T], options: &CsvOptions) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    write(&mut out, records, options)?;
+    Ok(out)
+}
+
+/// Parse CSV with a header line into records.
+///
+/// Columns are matched by header, so their order doesn't matter and unknown
+/// columns are ignored. Fails only if the file can't be read; problems with
+/// individual records are collected in [`CsvImport::errors`].
+pub fn read<T: CsvRecord>(reader: impl io::Read, options: &CsvOptions) -> Result<CsvImport<T>> {
+    let mut reader = io::BufReader::new(reader);
+    let starts_with_bom = io::BufRead::fill_buf(&mut reader)?.starts_with(BOM);
+    if starts_with_bom {
+        io::BufRead::consume(&mut reader, BOM.len());
+    }
+
+    let mut csv = ::csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .flexible(true)
+        .from_reader(reader);
+    let headers: HashMap<String, usize> = csv
+        .headers()
+        .map_err(csv_error)?
+        .iter()
+        .enumerate()
+        .map(|(index, header)| (header.trim().to_string(), index))
+        .collect();
+
+    let mut rows = Vec::new();
+    for record in csv.records() {
+        let record = record.map_err(csv_error)?;
+        let line = record.position().map_or(0, |p| p.line() as usize);
+        if record.iter().all(|value| value.trim().is_empty()) {
+            continue;
+        }
+        rows.push((line, record));
+    }
+    let lines: Vec<CsvLine<'_>> = rows
+        .iter()
+        .map(|(line, record)| CsvLine {
+            headers: &headers,
+            record,
+            options,
+            line: *line,
+        })
+        .collect();
+
+    let mut import = CsvImport {
+        records: Vec::new(),
+        errors: Vec::new(),
+    };
+    for group in group_lines(&lines, T::GROUP_BY) {
+        match T::from_lines(group) {
+            Ok(record) => import.records.push(record),
+            Err(message) => import.errors.push(CsvRowError {
+                line: group[0].line,
+                message,
+            }),
+        }
+    }
+    Ok(import)
+}
+
+/// Split `lines` into runs sharing a value in `column`. Lines with no value
+/// there stand alone.
+fn group_lines<'a, 'b>(lines: &'b [CsvLine<'a>], column: Option<&str>) -> Vec<&'b [CsvLine<'a>]> {
+    let Some(column) = column else {
+        return lines.chunks(1).collect();
+    };
+    let mut groups = Vec::new();
+    let mut start = 0;
+    for index in 1..=lines.len() {
+        let key = lines[start].get(column);
+        if index == lines.len() || key.is_none() || lines[index].get(column) != key {
+            groups.push(&lines[start..index]);
+            start = index;
+        }
+    }
+    groups
+}
+
+fn csv_error(e: ::csv::Error) -> Error {
+    let message = e.to_string();
+    match e.into_kind() {
+        ::csv::ErrorKind::Io(e) => Error::Io(e),
+        _ => Error::InvalidRequest(format!("CSV error: {}", message)),
+    }
+}
+
+fn text(value: &Option<String>) -> String {
+    value.clone().unwrap_or_default()
+}
+
+fn display<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(T::to_string).unwrap_or_default()
+}
+
+fn address(line: &CsvLine<'_>, prefix: &str) -> Option<Address> {
+    let field = |name: &str| line.string(&format!("{}{}", prefix, name));
+    let address = Address {
+        address1: field("Address1"),
+        address2: field("Address2"),
+        postal_code: field("PostalCode"),
+        city: field("City"),
+        country_code: field("CountryCode"),
+    };
+    let empty = address.address1.is_none()
+        && address.address2.is_none()
+        && address.postal_code.is_none()
+        && address.city.is_none()
+        && address.country_code.is_none();
+    (!empty).then_some(address)
+}
+
+fn address_columns(address: &Option<Address>) -> [String; 5] {
+    let address = address.clone().unwrap_or_default();
+    [
+        text(&address.address1),
+        text(&address.address2),
+        text(&address.postal_code),
+        text(&address.city),
+        text(&address.country_code),
+    ]
+}
+
+impl CsvRecord for Customer {
+    const HEADERS: &'static [&'static str] = &[
+        "CustomerNumber",
+        "Name",
+        "CorporateIdentityNumber",
+        "VatNumber",
+        "Email",
+        "Phone",
+        "InvoiceAddress1",
+        "InvoiceAddress2",
+        "InvoicePostalCode",
+        "InvoiceCity",
+        "InvoiceCountryCode",
+        "PaymentTermsInDays",
+        "IsPrivatePerson",
+        "IsActive",
+    ];
+
+    fn to_lines(&self, _options: &CsvOptions) -> Vec<Vec<String>> {
+        let mut line = vec![
+            text(&self.customer_number),
+            text(&self.name),
+            text(&self.corporate_identity_number),
+            text(&self.vat_number),
+            text(&self.email),
+            text(&self.phone),
+        ];
+        line.extend(address_columns(&self.invoice_address));
+        line.extend([
+            display(&self.payment_terms_in_days),
+            display(&self.is_private_person),
+            display(&self.is_active),
+        ]);
+        vec![line]
+    }
+
+    fn from_lines(lines: &[CsvLine<'_>]) -> std::result::Result<Self, String> {
+        let line = &lines[0];
+        let customer = Customer {
+            customer_number: line.string("CustomerNumber"),
+            name: Some(line.string("Name").ok_or("Name is required")?),
+            corporate_identity_number: line.string("CorporateIdentityNumber"),
+            vat_number: line.string("VatNumber"),
+            email: line.string("Email"),
+            phone: line.string("Phone"),
+            invoice_address: address(line, "Invoice"),
+            payment_terms_in_days: line.parse("PaymentTermsInDays")?,
+            is_private_person: line.bool("IsPrivatePerson")?,
+            is_active: line.bool("IsActive")?,
+            ..Default::default()
+        };
+        Ok(customer)
+    }
+}
+
+impl CsvRecord for Article {
+    const HEADERS: &'static [&'static str] = &[
+        "ArticleNumber",
+        "Name",
+        "Unit",
+        "SalesPrice",
+        "PurchasePrice",
+        "VatRateId",
+        "IsActive",
+    ];
+
+    fn to_lines(&self, options: &CsvOptions) -> Vec<Vec<String>> {
+        vec![vec![
+            text(&self.article_number),
+            text(&self.name),
+            text(&self.unit),
+            options.amount(self.sales_price),
+            options.amount(self.purchase_price),
+            text(&self.vat_rate_id),
+            display(&self.is_active),
+        ]]
+    }
+
+    fn from_lines(lines: &[CsvLine<'_>]) -> std::result::Result<Self, String> {
+        let line = &lines[0];
+        Ok(Article {
+            article_number: line.string("ArticleNumber"),
+            name: Some(line.string("Name").ok_or("Name is required")?),
+            unit: line.string("Unit"),
+            sales_price: line.amount("SalesPrice")?,
+            purchase_price: line.amount("PurchasePrice")?,
+            vat_rate_id: line.string("VatRateId"),
+            is_active: line.bool("IsActive")?,
+            ..Default::default()
+        })
+    }
+}
+
+impl CsvRecord for Invoice {
+    const HEADERS: &'static [&'static str] = &[
+        "InvoiceNumber",
+        "CustomerId",
+        "InvoiceDate",
+        "DueDate",
+        "CurrencyCode",
+        "ArticleId",
+        "Text",
+        "Quantity",
+        "UnitPrice",
+        "DiscountPercentage",
+    ];
+
+    const GROUP_BY: Option<&'static str> = Some("InvoiceNumber");
+
+    fn to_lines(&self, options: &CsvOptions) -> Vec<Vec<String>> {
+        let invoice = [
+            text(&self.invoice_number),
+            display(&self.customer_id),
+            display(&self.invoice_date),
+            display(&self.due_date),
+            text(&self.currency_code),
+        ];
+        let row = |row: &InvoiceRow| {
+            let mut line = invoice.to_vec();
+            line.extend([
+                display(&row.article_id),
+                text(&row.text),
+                options.amount(row.quantity),
+                options.amount(row.unit_price),
+                options.amount(row.discount_percentage),
+            ]);
+            line
+        };
+        if self.rows.is_empty() {
+            vec![row(&InvoiceRow::default())]
+        } else {
+            self.rows.iter().map(row).collect()
+        }
+    }
+
+    fn from_lines(lines: &[CsvLine<'_>]) -> std::result::Result<Self, String> {
+        let first = &lines[0];
+        let mut invoice = Invoice {
+            customer_id: Some(
+                first
+                    .get("CustomerId")
+                    .ok_or("CustomerId is required")?
+                    .into(),
+            ),
+            invoice_date: first.date("InvoiceDate")?,
+            due_date: first.date("DueDate")?,
+            currency_code: first.string("CurrencyCode"),
+            ..Default::default()
+        };
+        for line in lines {
+            if line.get("ArticleId").is_none() && line.get("Text").is_none() {
+                continue;
+            }
+            invoice.rows.push(InvoiceRow {
+                article_id: line.get("ArticleId").map(Into::into),
+                text: line.string("Text"),
+                quantity: line
+                    .amount("Quantity")
+                    .map_err(|e| format!("line {}: {}", line.line, e))?,
+                unit_price: line
+                    .amount("UnitPrice")
+                    .map_err(|e| format!("line {}: {}", line.line, e))?,
+                discount_percentage: line
+                    .amount("DiscountPercentage")
+                    .map_err(|e| format!("line {}: {}", line.line, e))?,
+                ..Default::default()
+            });
+        }
+        if invoice.rows.is_empty() {
+            return Err("invoice has no rows".to_string());
+        }
+        Ok(invoice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::money;
+
+    #[test]
+    fn test_articles_round_trip_in_excel_format() {
+        let articles = vec![Article {
+            article_number: Some("A1".to_string()),
+            name: Some("Konsult; senior".to_string()),
+            sales_price: Some(money!(1250.50)),
+            is_active: Some(true),
+            ..Default::default()
+        }];
+
+        let bytes = to_bytes(&articles, &CsvOptions::excel_sv()).unwrap();
+        assert!(bytes.starts_with(BOM));
+        let text = String::from_utf8(bytes[BOM.len()..].to_vec()).unwrap();
+        assert_eq!(
+            text.lines().nth(1),
+            Some("A1;\"Konsult; senior\";;1250,50;;;true")
+        );
+
+        let import = read::<Article>(text.as_bytes(), &CsvOptions::excel_sv()).unwrap();
+        assert!(import.is_complete());
+        assert_eq!(import.records[0].name.as_deref(), Some("Konsult; senior"));
+        assert_eq!(import.records[0].sales_price, Some(money!(1250.50)));
+    }
+
+    #[test]
+    fn test_read_reports_bad_rows() {
+        let csv = "\u{feff}Name,CustomerNumber,PaymentTermsInDays,InvoiceCity\n\
+                   Acme AB,1001,30,Malmö\n\
+                   ,1002,30,\n\
+                   Beta AB,1003,thirty,\n";
+
+        let import = read::<Customer>(csv.as_bytes(), &CsvOptions::new()).unwrap();
+
+        assert_eq!(import.records.len(), 1);
+        assert_eq!(import.records[0].payment_terms_in_days, Some(30));
+        assert_eq!(
+            import.records[0]
+                .invoice_address
+                .as_ref()
+                .unwrap()
+                .city
+                .as_deref(),
+            Some("Malmö")
+        );
+        assert_eq!(
+            import.errors,
+            vec![
+                CsvRowError {
+                    line: 3,
+                    message: "Name is required".to_string()
+                },
+                CsvRowError {
+                    line: 4,
+                    message: "invalid value 'thirty' in PaymentTermsInDays".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invoice_lines_grouped_by_number() {
+        let csv = "InvoiceNumber,CustomerId,InvoiceDate,ArticleId,Text,Quantity,UnitPrice\n\
+                   1,c1,2025-03-01,a1,,2,100\n\
+                   1,c1,2025-03-01,,Resa,1,450\n\
+                   2,c2,2025-03-02,a2,,1,\n";
+
+        let import = read::<Invoice>(csv.as_bytes(), &CsvOptions::new()).unwrap();
+
+        assert!(import.is_complete());
+        assert_eq!(import.records.len(), 2);
+        assert_eq!(import.records[0].rows.len(), 2);
+        assert_eq!(import.records[0].invoice_number, None);
+        assert_eq!(import.records[0].rows[1].unit_price, Some(money!(450)));
+        assert_eq!(import.records[1].customer_id, Some("c2".into()));
+
+        let written = to_bytes(&import.records, &CsvOptions::new()).unwrap();
+        let lines = String::from_utf8(written).unwrap().lines().count();
+        assert_eq!(lines, 4);
+    }
+
+    #[test]
+    fn test_io_failures_are_io_errors() {
+        struct Broken;
+
+        impl io::Write for Broken {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        impl io::Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+        }
+
+        let articles = vec![Article::default()];
+        for options in [CsvOptions::new(), CsvOptions::excel_sv()] {
+            let err = write(Broken, &articles, &options).unwrap_err();
+            assert!(matches!(err, Error::Io(e) if e.kind() == io::ErrorKind::BrokenPipe));
+        }
+        let err = read::<Article>(Broken, &CsvOptions::new()).unwrap_err();
+        assert!(matches!(err, Error::Io(e) if e.kind() == io::ErrorKind::BrokenPipe));
+    }
+}
@@ -0,0 +1,5 @@
+//! Exports to file formats read by other accounting software.
+
+#[cfg(feature = "csv")]
+pub mod csv;
+pub mod sie;
@@ -0,0 +1,387 @@
+//! SIE 4 export of a fiscal year.
+//!
+//! SIE is the Swedish standard for moving bookkeeping between programs, and
+//! type 4 carries the chart of accounts, balances and every voucher.
+//! [`SieFile::fetch`] loads a fiscal year from the API and
+//! [`SieFile::to_bytes`] renders it in the CP437 (`PC8`) encoding the format
+//! requires.
+//!
+//! Balance sheet accounts (BAS classes 1-2) get `#IB` and `#UB` records with
+//! the balances before and at the end of the year; result accounts (classes
+//! 3-8) get a `#RES` record. Voucher numbers with a letter prefix such as
+//! `A12` are split into series `A` and number `12`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::export::sie::SieFile;
+//! # async fn example(client: &spiris::Client) -> spiris::Result<()> {
+//! let years = client.fiscal_years().list_all(None).await?;
+//! let sie = SieFile::fetch(client, &years[0]).await?;
+//! sie.save("bokslut-2025.se")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::endpoints::collect_pages;
+use crate::error::{Error, Result};
+use crate::query::Filter;
+use crate::types::{Account, AccountBalance, FiscalYear, Money, QueryParams, Voucher};
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A fiscal year's bookkeeping, ready to write as SIE 4.
+#[derive(Debug, Clone)]
+pub struct SieFile {
+    /// Name of the program writing the file, for `#PROGRAM`.
+    pub program: String,
+    /// Date the file was generated, for `#GEN`.
+    pub generated: NaiveDate,
+    /// Company name, for `#FNAMN`.
+    pub company_name: String,
+    /// Organisation number, for `#ORGNR`.
+    pub org_number: Option<String>,
+    /// First day of the fiscal year.
+    pub year_start: NaiveDate,
+    /// Last day of the fiscal year.
+    pub year_end: NaiveDate,
+    /// Chart of accounts.
+    pub accounts: Vec<Account>,
+    /// Balances on the day before the year starts.
+    pub opening: Vec<AccountBalance>,
+    /// Balances on the last day of the year.
+    pub closing: Vec<AccountBalance>,
+    /// Vouchers dated within the year.
+    pub vouchers: Vec<Voucher>,
+}
+
+impl SieFile {
+    /// Load the accounts, balances and vouchers of `fiscal_year`.
+    ///
+    /// Fails if the fiscal year has no ID, start date or end date.
+    pub async fn fetch(client: &Client, fiscal_year: &FiscalYear) -> Result<Self> {
+        let (Some(id), Some(start), Some(end)) = (
+            fiscal_year.id.as_deref(),
+            fiscal_year.start_date,
+            fiscal_year.end_date,
+        ) else {
+            return Err(Error::InvalidRequest(
+                "fiscal year needs an ID, start date and end date".to_string(),
+            ));
+        };
+        let (start, end) = (start.date_naive(), end.date_naive());
+
+        let settings = client.company_settings().get().await?;
+        let accounts =
+            collect_pages(
+                |p| async move { client.accounts().list_by_fiscal_year(id, Some(p)).await },
+            )
+            .await?;
+        let opening = crate::reports::balances(client, start.pred_opt().unwrap_or(start)).await?;
+        let closing = crate::reports::balances(client, end).await?;
+
+        let filter = Filter::field("VoucherDate")
+            .ge(start)
+            .and(Filter::field("VoucherDate").le(end));
+        let mut vouchers = collect_pages(|p| {
+            let query = QueryParams::new().filter_by(filter.clone());
+            async move { client.vouchers().search(query, Some(p)).await }
+        })
+        .await?;
+        vouchers.retain(|v| {
+            v.voucher_date
+                .is_some_and(|d| (start..=end).contains(&d.date_naive()))
+        });
+
+        Ok(Self {
+            program: "spiris".to_string(),
+            generated: client.clock().now().date_naive(),
+            company_name: settings.company_name.unwrap_or_default(),
+            org_number: settings.corporate_identity_number,
+            year_start: start,
+            year_end: end,
+            accounts,
+            opening,
+            closing,
+            vouchers,
+        })
+    }
+
+    /// The file as text, one record per line.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut line = |record: String| {
+            out.push_str(&record);
+            out.push_str("\r\n");
+        };
+
+        line("#FLAGGA 0".to_string());
+        line("#FORMAT PC8".to_string());
+        line("#SIETYP 4".to_string());
+        line(format!(
+            "#PROGRAM {} {}",
+            quote(&self.program),
+            quote(env!("CARGO_PKG_VERSION"))
+        ));
+        line(format!("#GEN {}", date(self.generated)));
+        line(format!("#FNAMN {}", quote(&self.company_name)));
+        if let Some(org_number) = &self.org_number {
+            line(format!("#ORGNR {}", quote(org_number)));
+        }
+        line(format!(
+            "#RAR 0 {} {}",
+            date(self.year_start),
+            date(self.year_end)
+        ));
+
+        let mut accounts: Vec<&Account> = self
+            .accounts
+            .iter()
+            .filter(|a| a.account_number.is_some())
+            .collect();
+        accounts.sort_by_key(|a| account_key(a.account_number.as_deref().unwrap_or_default()));
+        for account in accounts {
+            line(format!(
+                "#KONTO {} {}",
+                account.account_number.as_deref().unwrap_or_default(),
+                quote(account.name.as_deref().unwrap_or_default())
+            ));
+        }
+
+        let opening = balance_map(&self.opening);
+        let closing = balance_map(&self.closing);
+        let mut numbers: Vec<&(u32, &str)> = opening.keys().chain(closing.keys()).collect();
+        numbers.sort();
+        numbers.dedup();
+        let at = |balances: &BTreeMap<(u32, &str), Money>, key| {
+            balances.get(key).copied().unwrap_or_default()
+        };
+        for key in numbers.iter().filter(|(n, _)| is_balance_account(*n)) {
+            line(format!("#IB 0 {} {}", key.1, amount(at(&opening, *key))));
+        }
+        for key in numbers.iter().filter(|(n, _)| is_balance_account(*n)) {
+            line(format!("#UB 0 {} {}", key.1, amount(at(&closing, *key))));
+        }
+        for key in numbers.iter().filter(|(n, _)| !is_balance_account(*n)) {
+            line(format!("#RES 0 {} {}", key.1, amount(at(&closing, *key))));
+        }
+
+        for voucher in &self.vouchers {
+            let (series, number) = split_voucher_number(voucher.voucher_number.as_deref());
+            let Some(voucher_date) = voucher.voucher_date.map(|d| d.date_naive()) else {
+                continue;
+            };
+            line(format!(
+                "#VER {} {} {} {}",
+                quote(series),
+                quote(number),
+                date(voucher_date),
+                quote(voucher.voucher_text.as_deref().unwrap_or_default())
+            ));
+            line("{".to_string());
+            for row in &voucher.rows {
+                let Some(account) = row.account_number.as_deref() else {
+                    continue;
+                };
+                let value =
+                    row.debit_amount.unwrap_or_default() - row.credit_amount.unwrap_or_default();
+                let mut record = format!("   #TRANS {} {{}} {}", account, amount(value));
+                if let Some(text) = row.transaction_text.as_deref() {
+                    record.push_str(&format!(" \"\" {}", quote(text)));
+                }
+                line(record);
+            }
+            line("}".to_string());
+        }
+
+        out
+    }
+
+    /// The file encoded as CP437, as SIE readers expect.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode_cp437(&self.render())
+    }
+
+    /// Write the file to `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        Ok(std::fs::write(path, self.to_bytes())?)
+    }
+}
+
+/// Characters 0x80 to 0xFF of code page 437.
+const CP437_HIGH: &str = "ÇüéâäàåçêëèïîìÄÅÉæÆôöòûùÿÖÜ¢£¥₧ƒáíóúñÑªº¿⌐¬½¼¡«»\
+░▒▓│┤╡╢╖╕╣║╗╝╜╛┐└┴┬├─┼╞╟╚╔╩╦╠═╬╧╨╤╥╙╘╒╓╫╪┘┌█▄▌▐▀\
+αßΓπΣσµτΦΘΩδ∞φε∩≡±≥≤⌠⌡÷≈°∙·√ⁿ²■\u{a0}";
+
+/// Encode `text` as CP437. Characters the code page lacks become `?`.
+pub fn encode_cp437(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| {
+            if c.is_ascii() {
+                c as u8
+            } else {
+                CP437_HIGH
+                    .chars()
+                    .position(|high| high == c)
+                    .map_or(b'?', |index| 0x80 + index as u8)
+            }
+        })
+        .collect()
+}
+
+/// A quoted SIE string, with quotes escaped and line breaks removed.
+fn quote(text: &str) -> String {
+    let text: String = text
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn date(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+fn amount(value: Money) -> String {
+    format!("{:.2}", value)
+}
+
+/// Sort key putting account numbers in numeric order.
+fn account_key(number: &str) -> (u32, String) {
+    (number.parse().unwrap_or(u32::MAX), number.to_string())
+}
+
+fn is_balance_account(number: u32) -> bool {
+    (1000..3000).contains(&number)
+}
+
+/// Balances by account, in account order.
+fn balance_map(balances: &[AccountBalance]) -> BTreeMap<(u32, &str), Money> {
+    balances
+        .iter()
+        .filter_map(|b| {
+            let number = b.account_number.as_deref()?;
+            Some((
+                (number.parse().ok()?, number),
+                b.balance.unwrap_or_default(),
+            ))
+        })
+        .collect()
+}
+
+/// Split a voucher number such as `A12` into series and number.
+fn split_voucher_number(number: Option<&str>) -> (&str, &str) {
+    let number = number.unwrap_or_default().trim();
+    let digits = number
+        .find(|c: char| c.is_ascii_digit())
+        .unwrap_or(number.len());
+    (number[..digits].trim(), &number[digits..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::money;
+    use crate::types::VoucherRow;
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn balance(account: &str, amount: Money) -> AccountBalance {
+        AccountBalance {
+            account_number: Some(account.to_string()),
+            balance: Some(amount),
+            ..Default::default()
+        }
+    }
+
+    fn account(number: &str, name: &str) -> Account {
+        Account {
+            account_number: Some(number.to_string()),
+            name: Some(name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn file() -> SieFile {
+        SieFile {
+            program: "spiris".to_string(),
+            generated: day(2026, 1, 15),
+            company_name: "Åkerö \"Bygg\" AB".to_string(),
+            org_number: Some("556677-8899".to_string()),
+            year_start: day(2025, 1, 1),
+            year_end: day(2025, 12, 31),
+            accounts: vec![
+                account("3001", "Försäljning"),
+                account("1930", "Företagskonto"),
+            ],
+            opening: vec![balance("1930", money!(1000))],
+            closing: vec![balance("3001", money!(-400)), balance("1930", money!(1400))],
+            vouchers: vec![Voucher {
+                voucher_number: Some("A12".to_string()),
+                voucher_date: Some("2025-03-05T00:00:00Z".parse().unwrap()),
+                voucher_text: Some("Kontantförsäljning".to_string()),
+                rows: vec![
+                    VoucherRow {
+                        account_number: Some("1930".to_string()),
+                        debit_amount: Some(money!(400)),
+                        ..Default::default()
+                    },
+                    VoucherRow {
+                        account_number: Some("3001".to_string()),
+                        credit_amount: Some(money!(400)),
+                        transaction_text: Some("Kassa".to_string()),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn test_render_records() {
+        let text = file().render();
+        let lines: Vec<&str> = text.split("\r\n").collect();
+
+        assert_eq!(lines[0], "#FLAGGA 0");
+        assert!(lines.contains(&"#FNAMN \"Åkerö \\\"Bygg\\\" AB\""));
+        assert!(lines.contains(&"#RAR 0 20250101 20251231"));
+        let konto = lines.iter().position(|l| l.starts_with("#KONTO")).unwrap();
+        assert_eq!(lines[konto], "#KONTO 1930 \"Företagskonto\"");
+        assert!(lines.contains(&"#IB 0 1930 1000.00"));
+        assert!(lines.contains(&"#UB 0 1930 1400.00"));
+        assert!(lines.contains(&"#RES 0 3001 -400.00"));
+        assert!(lines.contains(&"#VER \"A\" \"12\" 20250305 \"Kontantförsäljning\""));
+        assert!(lines.contains(&"   #TRANS 1930 {} 400.00"));
+        assert!(lines.contains(&"   #TRANS 3001 {} -400.00 \"\" \"Kassa\""));
+    }
+
+    #[test]
+    fn test_encode_cp437() {
+        assert_eq!(encode_cp437("Åäö ÅÄÖ é"), b"\x8f\x84\x94 \x8f\x8e\x99 \x82");
+        assert_eq!(encode_cp437("€"), b"?");
+    }
+
+    #[test]
+    fn test_split_voucher_number() {
+        assert_eq!(split_voucher_number(Some("A12")), ("A", "12"));
+        assert_eq!(split_voucher_number(Some("57")), ("", "57"));
+        assert_eq!(split_voucher_number(None), ("", ""));
+    }
+
+    #[test]
+    fn test_save() {
+        let dir = std::env::temp_dir().join(format!("spiris-sie-{}", std::process::id()));
+        let path = dir.join("bokslut.se");
+        assert!(matches!(file().save(&path), Err(Error::Io(_))));
+
+        std::fs::create_dir_all(&dir).unwrap();
+        file().save(&path).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), file().to_bytes());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,476 @@
+//! In-memory fake of the API for end-to-end tests.
+//!
+//! [`FakeServer`] listens on a local port and keeps customers, articles and
+//! customer invoices in memory, so a test can create a customer and list it
+//! back, or sync against a server that numbers invoices, without sandbox
+//! credentials. It covers a subset of the API:
+//!
+//! - `GET`, `POST` on `/customers`, `/articles` and `/customerinvoices`
+//! - `GET`, `PUT`, `PATCH` and `DELETE` on `/{collection}/{id}`
+//! - `page` and `pagesize` paging with the API's `Meta` section
+//! - `filter` expressions of `Field eq value` clauses joined by `and`
+//!
+//! New records get an `Id`, `CreatedUtc` and `ModifiedUtc`. Customers and
+//! articles without a number get the next one; invoices are always numbered
+//! by the server and their `TotalAmount` is the sum of their rows. Creating
+//! a customer without a name or an invoice for an unknown customer fails
+//! with a validation error, as with the real API. Other paths answer 404.
+//!
+//! Requires the `fake` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use spiris::fake::FakeServer;
+//! use spiris::Customer;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let fake = FakeServer::start().await;
+//! let client = fake.client();
+//!
+//! let customer = Customer {
+//!     name: Some("Acme AB".to_string()),
+//!     ..Default::default()
+//! };
+//! let created = client.customers().create(&customer).await?;
+//!
+//! let page = client.customers().list(None).await?;
+//! assert_eq!(page.data[0].id, created.id);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::auth::AccessToken;
+use crate::client::{Client, ClientConfig};
+use crate::retry::RetryConfig;
+use crate::types::{Article, Customer, Invoice};
+use chrono::{SecondsFormat, Utc};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+use std::sync::{Arc, Mutex};
+use wiremock::http::Method;
+use wiremock::matchers::any;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// Page size when a list request sends none.
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+#[derive(Debug)]
+struct Collection {
+    path: &'static str,
+    number_field: &'static str,
+    kind: u32,
+    next_number: u32,
+    created: u32,
+    items: Vec<Value>,
+}
+
+impl Collection {
+    fn new(path: &'static str, number_field: &'static str, kind: u32, first: u32) -> Self {
+        Self {
+            path,
+            number_field,
+            kind,
+            next_number: first,
+            created: 0,
+            items: Vec::new(),
+        }
+    }
+
+    fn position(&self, id: &str) -> Option<usize> {
+        self.items.iter().position(|item| item["Id"] == id)
+    }
+
+    /// Store `record` with a new id, timestamps and, if needed, number.
+    fn insert(&mut self, mut record: Map<String, Value>, force_number: bool) -> Value {
+        self.created += 1;
+        let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+        record.insert(
+            "Id".to_string(),
+            json!(format!(
+                "{:08x}-0000-4000-8000-{:012}",
+                self.kind, self.created
+            )),
+        );
+        if force_number || is_blank(record.get(self.number_field)) {
+            record.insert(
+                self.number_field.to_string(),
+                json!(self.next_number.to_string()),
+            );
+            self.next_number += 1;
+        }
+        record.insert("CreatedUtc".to_string(), json!(now));
+        record.insert("ModifiedUtc".to_string(), json!(now));
+        let record = Value::Object(record);
+        self.items.push(record.clone());
+        record
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    customers: Collection,
+    articles: Collection,
+    invoices: Collection,
+}
+
+impl State {
+    fn collection(&mut self, path: &str) -> Option<&mut Collection> {
+        [&mut self.customers, &mut self.articles, &mut self.invoices]
+            .into_iter()
+            .find(|collection| collection.path == path)
+    }
+}
+
+/// A local HTTP server faking part of the API, with state kept in memory.
+///
+/// Stops when dropped.
+#[derive(Debug)]
+pub struct FakeServer {
+    server: MockServer,
+    state: Arc<Mutex<State>>,
+}
+
+impl FakeServer {
+    /// Start an empty server on a free local port.
+    pub async fn start() -> Self {
+        let state = Arc::new(Mutex::new(State {
+            customers: Collection::new("customers", "CustomerNumber", 1, 1),
+            articles: Collection::new("articles", "ArticleNumber", 2, 1),
+            invoices: Collection::new("customerinvoices", "InvoiceNumber", 3, 1),
+        }));
+        let server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(Responder {
+                state: Arc::clone(&state),
+            })
+            .mount(&server)
+            .await;
+        Self { server, state }
+    }
+
+    /// Base URL of the server.
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// A client for the server with a valid token and retries turned off.
+    pub fn client(&self) -> Client {
+        self.client_with_config(ClientConfig::new().retry_config(RetryConfig::new().max_retries(0)))
+    }
+
+    /// A client for the server built from `config`, whose base URL is
+    /// replaced.
+    pub fn client_with_config(&self, config: ClientConfig) -> Client {
+        Client::with_config(
+            AccessToken::new("fake-server-token".to_string(), 3600, None),
+            config.base_url(self.uri()),
+        )
+    }
+
+    /// Number the next invoice `number`.
+    pub fn set_next_invoice_number(&self, number: u32) {
+        self.state.lock().unwrap().invoices.next_number = number;
+    }
+
+    /// Store `customer` as if it had been created through the API.
+    pub fn insert_customer(&self, customer: &Customer) -> Customer {
+        self.insert(|state| &mut state.customers, customer, false)
+    }
+
+    /// Store `article` as if it had been created through the API.
+    pub fn insert_article(&self, article: &Article) -> Article {
+        self.insert(|state| &mut state.articles, article, false)
+    }
+
+    /// Store `invoice` as if it had been created through the API.
+    pub fn insert_invoice(&self, invoice: &Invoice) -> Invoice {
+        self.insert(|state| &mut state.invoices, invoice, true)
+    }
+
+    /// Customers on the server.
+    pub fn customers(&self) -> Vec<Customer> {
+        parse_all(&self.state.lock().unwrap().customers.items)
+    }
+
+    /// Articles on the server.
+    pub fn articles(&self) -> Vec<Article> {
+        parse_all(&self.state.lock().unwrap().articles.items)
+    }
+
+    /// Customer invoices on the server.
+    pub fn invoices(&self) -> Vec<Invoice> {
+        parse_all(&self.state.lock().unwrap().invoices.items)
+    }
+
+    /// Number of requests the server has received.
+    pub async fn request_count(&self) -> usize {
+        self.server
+            .received_requests()
+            .await
+            .map_or(0, |requests| requests.len())
+    }
+
+    fn insert<T>(
+        &self,
+        collection: impl FnOnce(&mut State) -> &mut Collection,
+        record: &T,
+        force_number: bool,
+    ) -> T
+    where
+        T: serde::Serialize + DeserializeOwned,
+    {
+        let Value::Object(mut record) = serde_json::to_value(record).unwrap_or_default() else {
+            unreachable!("API types serialize to JSON objects");
+        };
+        record.retain(|_, value| !value.is_null());
+        let mut state = self.state.lock().unwrap();
+        let stored = collection(&mut state).insert(record, force_number);
+        parse(stored)
+    }
+}
+
+fn parse<T: DeserializeOwned>(value: Value) -> T {
+    serde_json::from_value(value).expect("stored records match the API types")
+}
+
+fn parse_all<T: DeserializeOwned>(items: &[Value]) -> Vec<T> {
+    items.iter().cloned().map(parse).collect()
+}
+
+fn is_blank(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => true,
+        Some(Value::String(text)) => text.trim().is_empty(),
+        Some(_) => false,
+    }
+}
+
+fn error(status: u16, code: &str, message: String) -> ResponseTemplate {
+    ResponseTemplate::new(status).set_body_json(json!({ "ErrorCode": code, "Message": message }))
+}
+
+fn validation_error(field: &str, message: &str) -> ResponseTemplate {
+    ResponseTemplate::new(400).set_body_json(json!({
+        "ErrorCode": "VALIDATION_ERROR",
+        "Message": "Validation failed",
+        "ValidationErrors": [{ "Field": field, "Message": message }]
+    }))
+}
+
+fn not_found(what: &str) -> ResponseTemplate {
+    error(404, "NOT_FOUND", format!("{} was not found", what))
+}
+
+/// Whether `item` matches a filter of `Field eq value` clauses.
+fn matches_filter(item: &Value, filter: &str) -> Result<bool, String> {
+    for clause in filter.split(" and ") {
+        let (field, value) = clause
+            .trim()
+            .split_once(" eq ")
+            .ok_or_else(|| format!("unsupported filter clause: {}", clause.trim()))?;
+        let value = value.trim();
+        let expected = match value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+            Some(text) => json!(text.replace("''", "'")),
+            None => serde_json::from_str(value)
+                .map_err(|_| format!("unsupported filter value: {}", value))?,
+        };
+        let actual = match (field.trim(), &item[field.trim()]) {
+            // Records without the flag are active
+            ("IsActive", Value::Null) => json!(true),
+            (_, actual) => actual.clone(),
+        };
+        let equal = match expected.as_f64() {
+            Some(number) if !actual.is_null() => amount(&actual) == number,
+            _ => actual == expected,
+        };
+        if !equal {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// An amount sent as a JSON number or, by `rust_decimal`, as a string.
+fn amount(value: &Value) -> f64 {
+    match value {
+        Value::String(text) => text.parse().unwrap_or_default(),
+        value => value.as_f64().unwrap_or_default(),
+    }
+}
+
+fn row_total(row: &Value) -> f64 {
+    let price = amount(&row["UnitPrice"]);
+    let quantity = amount(&row["Quantity"]);
+    let discount = amount(&row["DiscountPercentage"]);
+    price * quantity * (1.0 - discount / 100.0)
+}
+
+fn with_totals(record: &mut Map<String, Value>) {
+    let Some(Value::Array(rows)) = record.get_mut("Rows") else {
+        return;
+    };
+    let mut total = 0.0;
+    for row in rows.iter_mut() {
+        let amount = row_total(row);
+        total += amount;
+        if let Value::Object(row) = row {
+            row.insert("TotalAmount".to_string(), json!(amount));
+        }
+    }
+    record.insert("TotalAmount".to_string(), json!(total));
+}
+
+struct Responder {
+    state: Arc<Mutex<State>>,
+}
+
+impl Respond for Responder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        if !request.headers.contains_key("authorization") {
+            return error(401, "UNAUTHORIZED", "Missing access token".to_string());
+        }
+        let segments: Vec<&str> = request
+            .url
+            .path_segments()
+            .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+        let mut state = self.state.lock().unwrap();
+        match segments.as_slice() {
+            [path] => list_or_create(&mut state, path, request),
+            [path, id] => item(&mut state, path, id, request),
+            _ => not_found(request.url.path()),
+        }
+    }
+}
+
+fn list_or_create(state: &mut State, path: &str, request: &Request) -> ResponseTemplate {
+    match request.method {
+        Method::GET => match state.collection(path) {
+            Some(collection) => list(collection, request),
+            None => not_found(request.url.path()),
+        },
+        Method::POST => {
+            let Ok(Value::Object(mut record)) = request.body_json::<Value>() else {
+                return validation_error("Body", "The request body must be a JSON object");
+            };
+            record.retain(|_, value| !value.is_null());
+            if path == "customers" && is_blank(record.get("Name")) {
+                return validation_error("Name", "Name is required");
+            }
+            if path == "customerinvoices" {
+                let customer = record.get("CustomerId").and_then(Value::as_str);
+                if customer
+                    .and_then(|id| state.customers.position(id))
+                    .is_none()
+                {
+                    return validation_error("CustomerId", "Customer does not exist");
+                }
+                with_totals(&mut record);
+            }
+            if path == "customers" || path == "articles" {
+                record.entry("IsActive").or_insert(json!(true));
+            }
+            match state.collection(path) {
+                Some(collection) => {
+                    let numbered = collection.path == "customerinvoices";
+                    let stored = collection.insert(record, numbered);
+                    ResponseTemplate::new(201).set_body_json(stored)
+                }
+                None => not_found(request.url.path()),
+            }
+        }
+        _ => not_found(request.url.path()),
+    }
+}
+
+fn list(collection: &Collection, request: &Request) -> ResponseTemplate {
+    let query = |name: &str| {
+        request
+            .url
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    let page: usize = query("page").and_then(|p| p.parse().ok()).unwrap_or(0);
+    let page_size: usize = query("pagesize")
+        .and_then(|p| p.parse().ok())
+        .filter(|&size| size > 0)
+        .unwrap_or(DEFAULT_PAGE_SIZE);
+
+    let mut matching = Vec::new();
+    for item in &collection.items {
+        match query("filter").map(|filter| matches_filter(item, &filter)) {
+            Some(Err(message)) => return error(400, "INVALID_FILTER", message),
+            Some(Ok(false)) => {}
+            _ => matching.push(item.clone()),
+        }
+    }
+    let total_count = matching.len();
+    let total_pages = total_count.div_ceil(page_size);
+    let data: Vec<Value> = matching
+        .into_iter()
+        .skip(page * page_size)
+        .take(page_size)
+        .collect();
+    ResponseTemplate::new(200).set_body_json(json!({
+        "Data": data,
+        "Meta": {
+            "CurrentPage": page,
+            "PageSize": page_size,
+            "TotalPages": total_pages,
+            "TotalCount": total_count,
+            "HasNextPage": page + 1 < total_pages,
+            "HasPreviousPage": page > 0
+        }
+    }))
+}
+
+fn item(state: &mut State, path: &str, id: &str, request: &Request) -> ResponseTemplate {
+    let Some(collection) = state.collection(path) else {
+        return not_found(request.url.path());
+    };
+    let Some(index) = collection.position(id) else {
+        return not_found(&format!("{} {}", path, id));
+    };
+    match request.method {
+        Method::GET => ResponseTemplate::new(200).set_body_json(&collection.items[index]),
+        Method::DELETE => {
+            collection.items.remove(index);
+            ResponseTemplate::new(204)
+        }
+        Method::PUT | Method::PATCH => {
+            let Ok(Value::Object(changes)) = request.body_json::<Value>() else {
+                return validation_error("Body", "The request body must be a JSON object");
+            };
+            let number_field = collection.number_field;
+            let Value::Object(record) = &mut collection.items[index] else {
+                unreachable!("records are JSON objects");
+            };
+            for (key, value) in changes {
+                // The server owns these fields
+                if matches!(key.as_str(), "Id" | "CreatedUtc" | "ModifiedUtc") {
+                    continue;
+                }
+                if key == number_field && path == "customerinvoices" {
+                    continue;
+                }
+                if value.is_null() && request.method == Method::PATCH {
+                    record.remove(&key);
+                } else if !value.is_null() {
+                    record.insert(key, value);
+                }
+            }
+            if path == "customerinvoices" {
+                with_totals(record);
+            }
+            record.insert(
+                "ModifiedUtc".to_string(),
+                json!(Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)),
+            );
+            ResponseTemplate::new(200).set_body_json(&collection.items[index])
+        }
+        _ => not_found(request.url.path()),
+    }
+}
@@ -0,0 +1,256 @@
+//! Field names of the API models.
+//!
+//! Each model has an enum of its fields, named as the API names them, so
+//! [`QueryParams::order_by`](crate::QueryParams::order_by) and
+//! [`QueryParams::select_fields`](crate::QueryParams::select_fields) take
+//! checked names instead of strings. The enums are generated from the model
+//! structs in [`types`](crate::types); a field added there belongs here too.
+//!
+//! # Example
+//!
+//! ```
+//! use spiris::fields::InvoiceField;
+//! use spiris::query::Direction;
+//! use spiris::QueryParams;
+//!
+//! let query = QueryParams::new()
+//!     .order_by(InvoiceField::InvoiceDate, Direction::Descending)
+//!     .select_fields([InvoiceField::Id, InvoiceField::InvoiceNumber]);
+//! assert_eq!(query.orderby.as_deref(), Some("InvoiceDate desc"));
+//! assert_eq!(query.select.as_deref(), Some("Id,InvoiceNumber"));
+//! ```
+
+use std::fmt;
+
+/// A field of an API model.
+pub trait Field: Copy {
+    /// Name of the field in the API.
+    fn api_name(self) -> &'static str;
+}
+
+macro_rules! api_fields {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident {
+            $($variant:ident => $api:literal,)*
+        }
+    )*) => {$(
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $(
+                #[doc = concat!("`", $api, "`")]
+                $variant,
+            )*
+        }
+
+        impl $name {
+            /// Every field, in the order the model declares them.
+            pub const ALL: &'static [$name] = &[$($name::$variant),*];
+        }
+
+        impl Field for $name {
+            fn api_name(self) -> &'static str {
+                match self {
+                    $($name::$variant => $api,)*
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.api_name())
+            }
+        }
+    )*};
+}
+
+api_fields! {
+    /// Fields of [`Customer`](crate::types::Customer), for sorting and selecting customers.
+    CustomerField {
+        Id => "Id",
+        CustomerNumber => "CustomerNumber",
+        CorporateIdentityNumber => "CorporateIdentityNumber",
+        Name => "Name",
+        Email => "Email",
+        Phone => "Phone",
+        MobilePhone => "MobilePhone",
+        Website => "Website",
+        InvoiceAddress => "InvoiceAddress",
+        DeliveryAddress => "DeliveryAddress",
+        DeliveryMethodId => "DeliveryMethodId",
+        DeliveryTermId => "DeliveryTermId",
+        PaymentTermsInDays => "PaymentTermsInDays",
+        IsActive => "IsActive",
+        IsPrivatePerson => "IsPrivatePerson",
+        VatNumber => "VatNumber",
+        Note => "Note",
+        CreatedUtc => "CreatedUtc",
+        ModifiedUtc => "ModifiedUtc",
+        CreatedByUserId => "CreatedByUserId",
+        ModifiedByUserId => "ModifiedByUserId",
+    }
+
+    /// Fields of [`Supplier`](crate::types::Supplier), for sorting and selecting suppliers.
+    SupplierField {
+        Id => "Id",
+        SupplierNumber => "SupplierNumber",
+        CorporateIdentityNumber => "CorporateIdentityNumber",
+        Name => "Name",
+        Email => "Email",
+        Phone => "Phone",
+        MobilePhone => "MobilePhone",
+        Website => "Website",
+        Address => "Address",
+        BankAccountNumber => "BankAccountNumber",
+        BankGiroNumber => "BankGiroNumber",
+        PlusGiroNumber => "PlusGiroNumber",
+        IsActive => "IsActive",
+        CreatedUtc => "CreatedUtc",
+        ModifiedUtc => "ModifiedUtc",
+        CreatedByUserId => "CreatedByUserId",
+        ModifiedByUserId => "ModifiedByUserId",
+    }
+
+    /// Fields of [`Article`](crate::types::Article), for sorting and selecting articles.
+    ArticleField {
+        Id => "Id",
+        ArticleNumber => "ArticleNumber",
+        Name => "Name",
+        Unit => "Unit",
+        SalesPrice => "SalesPrice",
+        PurchasePrice => "PurchasePrice",
+        IsActive => "IsActive",
+        VatRateId => "VatRateId",
+        CreatedUtc => "CreatedUtc",
+        ModifiedUtc => "ModifiedUtc",
+        CreatedByUserId => "CreatedByUserId",
+        ModifiedByUserId => "ModifiedByUserId",
+    }
+
+    /// Fields of [`Invoice`](crate::types::Invoice), for sorting and selecting customer invoices.
+    InvoiceField {
+        Id => "Id",
+        InvoiceNumber => "InvoiceNumber",
+        CustomerId => "CustomerId",
+        InvoiceDate => "InvoiceDate",
+        DueDate => "DueDate",
+        DeliveryDate => "DeliveryDate",
+        DeliveryMethodId => "DeliveryMethodId",
+        DeliveryTermId => "DeliveryTermId",
+        CurrencyCode => "CurrencyCode",
+        Rows => "Rows",
+        TotalAmount => "TotalAmount",
+        TotalVatAmount => "TotalVatAmount",
+        TotalAmountIncludingVat => "TotalAmountIncludingVat",
+        RemainingAmount => "RemainingAmount",
+        IsSent => "IsSent",
+        OcrNumber => "OcrNumber",
+        ReminderLevel => "ReminderLevel",
+        LastReminderDate => "LastReminderDate",
+        Remarks => "Remarks",
+        ReverseChargeOnConstructionServices => "ReverseChargeOnConstructionServices",
+        CustomerVatNumber => "CustomerVatNumber",
+        EuThirdParty => "EuThirdParty",
+        CreatedUtc => "CreatedUtc",
+        ModifiedUtc => "ModifiedUtc",
+        CreatedByUserId => "CreatedByUserId",
+        ModifiedByUserId => "ModifiedByUserId",
+    }
+
+    /// Fields of [`SupplierInvoice`](crate::types::SupplierInvoice), for sorting and selecting supplier invoices.
+    SupplierInvoiceField {
+        Id => "Id",
+        SupplierId => "SupplierId",
+        InvoiceNumber => "InvoiceNumber",
+        InvoiceDate => "InvoiceDate",
+        DueDate => "DueDate",
+        CurrencyCode => "CurrencyCode",
+        CurrencyRate => "CurrencyRate",
+        Rows => "Rows",
+        TotalAmount => "TotalAmount",
+        TotalVatAmount => "TotalVatAmount",
+        TotalAmountIncludingVat => "TotalAmountIncludingVat",
+        RemainingAmount => "RemainingAmount",
+        IsPaid => "IsPaid",
+        PaymentDate => "PaymentDate",
+        OcrNumber => "OcrNumber",
+        ApprovalStatus => "ApprovalStatus",
+        ApproverUserId => "ApproverUserId",
+        Attachments => "Attachments",
+        CreatedUtc => "CreatedUtc",
+        ModifiedUtc => "ModifiedUtc",
+        CreatedByUserId => "CreatedByUserId",
+        ModifiedByUserId => "ModifiedByUserId",
+    }
+
+    /// Fields of [`Voucher`](crate::types::Voucher), for sorting and selecting vouchers.
+    VoucherField {
+        Id => "Id",
+        VoucherNumber => "VoucherNumber",
+        VoucherDate => "VoucherDate",
+        VoucherType => "VoucherType",
+        VoucherText => "VoucherText",
+        Rows => "Rows",
+        CreatedUtc => "CreatedUtc",
+        ModifiedUtc => "ModifiedUtc",
+        CreatedByUserId => "CreatedByUserId",
+        ModifiedByUserId => "ModifiedByUserId",
+    }
+
+    /// Fields of [`Order`](crate::types::Order), for sorting and selecting orders.
+    OrderField {
+        Id => "Id",
+        OrderNumber => "OrderNumber",
+        CustomerId => "CustomerId",
+        OrderDate => "OrderDate",
+        DeliveryDate => "DeliveryDate",
+        DeliveryMethodId => "DeliveryMethodId",
+        DeliveryTermId => "DeliveryTermId",
+        CurrencyCode => "CurrencyCode",
+        Rows => "Rows",
+        TotalAmount => "TotalAmount",
+        TotalVatAmount => "TotalVatAmount",
+        Status => "Status",
+        YourReference => "YourReference",
+        OurReference => "OurReference",
+        CreatedUtc => "CreatedUtc",
+        ModifiedUtc => "ModifiedUtc",
+        CreatedByUserId => "CreatedByUserId",
+        ModifiedByUserId => "ModifiedByUserId",
+    }
+
+    /// Fields of [`Quotation`](crate::types::Quotation), for sorting and selecting quotations.
+    QuotationField {
+        Id => "Id",
+        QuotationNumber => "QuotationNumber",
+        CustomerId => "CustomerId",
+        QuotationDate => "QuotationDate",
+        ValidUntilDate => "ValidUntilDate",
+        CurrencyCode => "CurrencyCode",
+        Rows => "Rows",
+        TotalAmount => "TotalAmount",
+        TotalVatAmount => "TotalVatAmount",
+        Status => "Status",
+        CreatedUtc => "CreatedUtc",
+        ModifiedUtc => "ModifiedUtc",
+        CreatedByUserId => "CreatedByUserId",
+        ModifiedByUserId => "ModifiedByUserId",
+    }
+
+    /// Fields of [`Project`](crate::types::Project), for sorting and selecting projects.
+    ProjectField {
+        Id => "Id",
+        ProjectNumber => "ProjectNumber",
+        Name => "Name",
+        StartDate => "StartDate",
+        EndDate => "EndDate",
+        CustomerId => "CustomerId",
+        IsCompleted => "IsCompleted",
+        Notes => "Notes",
+        CreatedUtc => "CreatedUtc",
+        ModifiedUtc => "ModifiedUtc",
+        CreatedByUserId => "CreatedByUserId",
+        ModifiedByUserId => "ModifiedByUserId",
+    }
+}
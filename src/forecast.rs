@@ -0,0 +1,297 @@
+//! Cash flow forecasting from open invoices.
+//!
+//! The forecast places the remaining amount of every open customer invoice
+//! (inflow) and supplier invoice (outflow) in the week of its due date.
+//! Invoices that are already overdue are expected to be settled in the
+//! first week and are flagged as such.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::forecast;
+//! use spiris::{AccessToken, Client};
+//! use chrono::NaiveDate;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+//! let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+//! let cash_flow = forecast::weekly(&client, today, 13).await?;
+//!
+//! for week in &cash_flow.weeks {
+//!     println!("{}: {} in, {} out", week.week_start, week.inflow, week.outflow);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::endpoints::collect_pages;
+use crate::error::Result;
+use crate::types::{Invoice, Money, SupplierInvoice};
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+
+/// Direction of a cash flow item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowDirection {
+    /// Money coming in from a customer invoice.
+    Inflow,
+    /// Money going out for a supplier invoice.
+    Outflow,
+}
+
+/// A single expected payment.
+#[derive(Debug, Clone, Serialize)]
+pub struct CashFlowItem {
+    /// Due date of the invoice.
+    pub due_date: NaiveDate,
+    /// Whether money comes in or goes out.
+    pub direction: FlowDirection,
+    /// Remaining amount of the invoice.
+    #[serde(with = "crate::types::money_serde")]
+    pub amount: Money,
+    /// Invoice ID.
+    pub invoice_id: Option<String>,
+    /// Invoice number.
+    pub invoice_number: Option<String>,
+    /// Customer or supplier ID.
+    pub counterparty_id: Option<String>,
+    /// Whether the invoice was already overdue at the start of the forecast.
+    pub overdue: bool,
+}
+
+/// Expected inflow and outflow for one week.
+#[derive(Debug, Clone, Serialize)]
+pub struct WeekBucket {
+    /// Monday of the week.
+    pub week_start: NaiveDate,
+    /// Expected incoming payments.
+    #[serde(with = "crate::types::money_serde")]
+    pub inflow: Money,
+    /// Expected outgoing payments.
+    #[serde(with = "crate::types::money_serde")]
+    pub outflow: Money,
+}
+
+impl WeekBucket {
+    /// Inflow minus outflow.
+    pub fn net(&self) -> Money {
+        self.inflow - self.outflow
+    }
+}
+
+/// Weekly cash flow forecast.
+#[derive(Debug, Clone, Serialize)]
+pub struct CashFlowForecast {
+    /// Date the forecast was made at.
+    pub as_of: NaiveDate,
+    /// One bucket per week, starting with the week containing `as_of`.
+    pub weeks: Vec<WeekBucket>,
+    /// All items within the horizon, ordered by due date.
+    pub items: Vec<CashFlowItem>,
+}
+
+impl CashFlowForecast {
+    /// Build a forecast over `weeks` weeks from open invoices.
+    ///
+    /// Customer invoices use their remaining amount. Supplier invoices use
+    /// the remaining amount, or the total for unpaid invoices that do not
+    /// report one. Items due after the horizon or without a due date are
+    /// left out.
+    pub fn from_invoices(
+        as_of: NaiveDate,
+        weeks: u32,
+        invoices: &[Invoice],
+        supplier_invoices: &[SupplierInvoice],
+    ) -> Self {
+        let start = as_of - Duration::days(as_of.weekday().num_days_from_monday() as i64);
+        let end = start + Duration::weeks(weeks as i64);
+
+        let mut buckets: Vec<WeekBucket> = (0..weeks)
+            .map(|week| WeekBucket {
+                week_start: start + Duration::weeks(week as i64),
+                inflow: Money::default(),
+                outflow: Money::default(),
+            })
+            .collect();
+
+        let inflows = invoices.iter().filter_map(|invoice| {
+            Some(CashFlowItem {
+                due_date: invoice.due_date?,
+                direction: FlowDirection::Inflow,
+                amount: invoice.remaining_amount?,
+                invoice_id: invoice.id.clone().map(String::from),
+                invoice_number: invoice.invoice_number.clone(),
+                counterparty_id: invoice.customer_id.clone().map(String::from),
+                overdue: false,
+            })
+        });
+        let outflows = supplier_invoices.iter().filter_map(|invoice| {
+            let amount = match invoice.remaining_amount {
+                Some(remaining) => remaining,
+                None if invoice.is_paid != Some(true) => invoice.total_amount_including_vat?,
+                None => return None,
+            };
+            Some(CashFlowItem {
+                due_date: invoice.due_date?,
+                direction: FlowDirection::Outflow,
+                amount,
+                invoice_id: invoice.id.clone(),
+                invoice_number: invoice.invoice_number.clone(),
+                counterparty_id: invoice.supplier_id.clone(),
+                overdue: false,
+            })
+        });
+
+        let mut items = Vec::new();
+        for mut item in inflows.chain(outflows) {
+            if item.amount <= Money::default() || item.due_date >= end || buckets.is_empty() {
+                continue;
+            }
+            item.overdue = item.due_date < start;
+
+            let week = if item.overdue {
+                0
+            } else {
+                ((item.due_date - start).num_days() / 7) as usize
+            };
+            match item.direction {
+                FlowDirection::Inflow => buckets[week].inflow += item.amount,
+                FlowDirection::Outflow => buckets[week].outflow += item.amount,
+            }
+            items.push(item);
+        }
+        items.sort_by_key(|item| item.due_date);
+
+        Self {
+            as_of,
+            weeks: buckets,
+            items,
+        }
+    }
+
+    /// Total expected inflow over the horizon.
+    pub fn total_inflow(&self) -> Money {
+        self.weeks.iter().map(|w| w.inflow).sum()
+    }
+
+    /// Total expected outflow over the horizon.
+    pub fn total_outflow(&self) -> Money {
+        self.weeks.iter().map(|w| w.outflow).sum()
+    }
+
+    /// The `n` largest items regardless of direction, largest first.
+    pub fn largest_items(&self, n: usize) -> Vec<&CashFlowItem> {
+        let mut items: Vec<&CashFlowItem> = self.items.iter().collect();
+        items.sort_by(|a, b| {
+            b.amount
+                .partial_cmp(&a.amount)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        items.truncate(n);
+        items
+    }
+}
+
+/// Build a weekly cash flow forecast from the company's open invoices.
+pub async fn weekly(client: &Client, as_of: NaiveDate, weeks: u32) -> Result<CashFlowForecast> {
+    let invoices = collect_pages(|p| async move { client.invoices().list(Some(p)).await }).await?;
+    let supplier_invoices =
+        collect_pages(|p| async move { client.supplier_invoices().list(Some(p)).await }).await?;
+
+    Ok(CashFlowForecast::from_invoices(
+        as_of,
+        weeks,
+        &invoices,
+        &supplier_invoices,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn money(value: &str) -> Money {
+        value.parse().unwrap()
+    }
+
+    fn invoice(due: (i32, u32, u32), remaining: &str) -> Invoice {
+        Invoice {
+            id: Some(format!("inv-{}-{}", due.1, due.2).into()),
+            due_date: Some(NaiveDate::from_ymd_opt(due.0, due.1, due.2).unwrap()),
+            remaining_amount: Some(money(remaining)),
+            ..Default::default()
+        }
+    }
+
+    fn supplier_invoice(due: (i32, u32, u32), total: &str, paid: bool) -> SupplierInvoice {
+        SupplierInvoice {
+            due_date: Some(NaiveDate::from_ymd_opt(due.0, due.1, due.2).unwrap()),
+            total_amount_including_vat: Some(money(total)),
+            is_paid: Some(paid),
+            ..Default::default()
+        }
+    }
+
+    // Wednesday; the forecast starts on Monday 2025-03-10
+    fn as_of() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, 12).unwrap()
+    }
+
+    #[test]
+    fn test_weeks_start_on_monday() {
+        let forecast = CashFlowForecast::from_invoices(as_of(), 4, &[], &[]);
+
+        assert_eq!(forecast.weeks.len(), 4);
+        assert_eq!(
+            forecast.weeks[0].week_start,
+            NaiveDate::from_ymd_opt(2025, 3, 10).unwrap()
+        );
+        assert_eq!(
+            forecast.weeks[3].week_start,
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_items_are_bucketed_by_due_week() {
+        let invoices = vec![
+            invoice((2025, 3, 14), "100"),
+            invoice((2025, 3, 17), "200"),
+            invoice((2025, 3, 2), "50"),
+            invoice((2025, 5, 1), "999"),
+            invoice((2025, 3, 18), "0"),
+        ];
+        let supplier_invoices = vec![
+            supplier_invoice((2025, 3, 20), "80", false),
+            supplier_invoice((2025, 3, 20), "500", true),
+        ];
+
+        let forecast = CashFlowForecast::from_invoices(as_of(), 4, &invoices, &supplier_invoices);
+
+        assert_eq!(forecast.weeks[0].inflow, money("150"));
+        assert_eq!(forecast.weeks[1].inflow, money("200"));
+        assert_eq!(forecast.weeks[1].outflow, money("80"));
+        assert_eq!(forecast.weeks[1].net(), money("120"));
+        assert_eq!(forecast.total_inflow(), money("350"));
+        assert_eq!(forecast.total_outflow(), money("80"));
+
+        assert_eq!(forecast.items.len(), 4);
+        assert!(forecast.items[0].overdue);
+        assert_eq!(forecast.items[0].amount, money("50"));
+    }
+
+    #[test]
+    fn test_largest_items() {
+        let invoices = vec![invoice((2025, 3, 14), "100"), invoice((2025, 3, 17), "300")];
+        let supplier_invoices = vec![supplier_invoice((2025, 3, 20), "200", false)];
+
+        let forecast = CashFlowForecast::from_invoices(as_of(), 4, &invoices, &supplier_invoices);
+        let largest = forecast.largest_items(2);
+
+        assert_eq!(largest.len(), 2);
+        assert_eq!(largest[0].amount, money("300"));
+        assert_eq!(largest[1].direction, FlowDirection::Outflow);
+    }
+}
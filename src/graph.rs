@@ -0,0 +1,312 @@
+//! Creating interdependent records.
+//!
+//! Imports often create records that reference each other: a customer, then
+//! an invoice for it, then sending the invoice. A [`Graph`] names each step and
+//! the steps it depends on. Steps receive the outputs (usually ids) of their
+//! dependencies, independent branches run concurrently, and the
+//! [`GraphReport`] tells exactly which steps completed, which failed and which
+//! never started.
+//!
+//! After the first failure no new steps are started; steps already running
+//! are allowed to finish so the report reflects what was created.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::graph::Graph;
+//! use spiris::{AccessToken, Client, Customer, CustomerId, Error, Invoice, InvoiceId, InvoiceRow};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+//! let report = Graph::new()
+//!     .node("customer", &[], |client, _| async move {
+//!         let customer = Customer {
+//!             name: Some("Acme AB".to_string()),
+//!             ..Default::default()
+//!         };
+//!         let created = client.customers().create(&customer).await?;
+//!         let id = created.id.ok_or_else(|| Error::InvalidRequest("customer has no id".into()))?;
+//!         Ok(id.into_string())
+//!     })
+//!     .node("invoice", &["customer"], |client, ids| async move {
+//!         let invoice = Invoice {
+//!             customer_id: Some(CustomerId::from(&ids["customer"])),
+//!             rows: vec![InvoiceRow {
+//!                 text: Some("Onboarding".to_string()),
+//!                 ..Default::default()
+//!             }],
+//!             ..Default::default()
+//!         };
+//!         let created = client.invoices().create(&invoice).await?;
+//!         let id = created.id.ok_or_else(|| Error::InvalidRequest("invoice has no id".into()))?;
+//!         Ok(id.into_string())
+//!     })
+//!     .node("send", &["invoice"], |client, ids| async move {
+//!         client.invoices().send_einvoice(&InvoiceId::from(&ids["invoice"])).await?;
+//!         Ok(String::new())
+//!     })
+//!     .run(&client)
+//!     .await?;
+//!
+//! for failure in &report.failed {
+//!     println!("{} failed: {}", failure.node, failure.error);
+//! }
+//! println!("Not started: {:?}", report.skipped);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::error::{Error, Result};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use tokio::task::JoinSet;
+
+type StepFuture = Pin<Box<dyn Future<Output = Result<String>> + Send>>;
+type Step = Box<dyn FnOnce(Client, Outputs) -> StepFuture + Send>;
+
+struct Node {
+    name: String,
+    depends_on: Vec<String>,
+    step: Step,
+}
+
+/// Outputs of a step's dependencies, by step name.
+#[derive(Debug, Clone, Default)]
+pub struct Outputs(HashMap<String, String>);
+
+impl Outputs {
+    /// Output of the named dependency.
+    pub fn get(&self, node: &str) -> Option<&str> {
+        self.0.get(node).map(String::as_str)
+    }
+}
+
+impl std::ops::Index<&str> for Outputs {
+    type Output = str;
+
+    /// Output of the named dependency.
+    ///
+    /// Panics if `node` is not a dependency of the step.
+    fn index(&self, node: &str) -> &str {
+        self.get(node)
+            .unwrap_or_else(|| panic!("{} is not a dependency of this step", node))
+    }
+}
+
+/// A step that failed.
+#[derive(Debug)]
+pub struct NodeFailure {
+    /// Name of the step.
+    pub node: String,
+    /// Why it failed.
+    pub error: Error,
+}
+
+/// Outcome of running a [`Graph`].
+#[derive(Debug, Default)]
+pub struct GraphReport {
+    /// Completed steps and their outputs, in completion order.
+    pub completed: Vec<(String, String)>,
+    /// Failed steps.
+    pub failed: Vec<NodeFailure>,
+    /// Steps that were not started because an earlier step failed.
+    pub skipped: Vec<String>,
+}
+
+impl GraphReport {
+    /// Whether every step completed.
+    pub fn is_ok(&self) -> bool {
+        self.failed.is_empty() && self.skipped.is_empty()
+    }
+
+    /// Output of a completed step.
+    pub fn output(&self, node: &str) -> Option<&str> {
+        self.completed
+            .iter()
+            .find(|(name, _)| name == node)
+            .map(|(_, output)| output.as_str())
+    }
+}
+
+/// Steps with dependencies between them.
+#[derive(Default)]
+pub struct Graph {
+    nodes: Vec<Node>,
+    max_concurrency: Option<usize>,
+}
+
+impl Graph {
+    /// Create an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a step that runs once every step in `depends_on` has completed.
+    ///
+    /// The step gets a clone of the client and the outputs of its
+    /// dependencies, and returns its own output, usually the id of the record
+    /// it created.
+    pub fn node<F, Fut>(mut self, name: impl Into<String>, depends_on: &[&str], step: F) -> Self
+    where
+        F: FnOnce(Client, Outputs) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<String>> + Send + 'static,
+    {
+        self.nodes.push(Node {
+            name: name.into(),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            step: Box::new(move |client, outputs| Box::pin(step(client, outputs))),
+        });
+        self
+    }
+
+    /// Limit how many steps run at the same time.
+    pub fn max_concurrency(mut self, max: usize) -> Self {
+        self.max_concurrency = Some(max.max(1));
+        self
+    }
+
+    /// Check for duplicate names, unknown dependencies and cycles.
+    fn check(&self) -> Result<()> {
+        let mut names = HashSet::new();
+        for node in &self.nodes {
+            if !names.insert(node.name.as_str()) {
+                return Err(Error::InvalidRequest(format!(
+                    "duplicate graph node {}",
+                    node.name
+                )));
+            }
+        }
+        for node in &self.nodes {
+            if let Some(unknown) = node.depends_on.iter().find(|d| !names.contains(d.as_str())) {
+                return Err(Error::InvalidRequest(format!(
+                    "graph node {} depends on unknown node {}",
+                    node.name, unknown
+                )));
+            }
+        }
+
+        let mut resolved = HashSet::new();
+        while resolved.len() < self.nodes.len() {
+            let ready: Vec<&str> = self
+                .nodes
+                .iter()
+                .filter(|n| !resolved.contains(n.name.as_str()))
+                .filter(|n| n.depends_on.iter().all(|d| resolved.contains(d.as_str())))
+                .map(|n| n.name.as_str())
+                .collect();
+            if ready.is_empty() {
+                let mut cycle: Vec<&str> = self
+                    .nodes
+                    .iter()
+                    .map(|n| n.name.as_str())
+                    .filter(|n| !resolved.contains(n))
+                    .collect();
+                cycle.sort_unstable();
+                return Err(Error::InvalidRequest(format!(
+                    "graph has a dependency cycle among {}",
+                    cycle.join(", ")
+                )));
+            }
+            resolved.extend(ready);
+        }
+        Ok(())
+    }
+
+    /// Run the steps.
+    ///
+    /// Fails only if the graph itself is invalid; failed steps are reported
+    /// in the [`GraphReport`]. A panicking step is resumed on the caller.
+    pub async fn run(self, client: &Client) -> Result<GraphReport> {
+        self.check()?;
+
+        let limit = self.max_concurrency.unwrap_or(usize::MAX);
+        let mut pending = self.nodes;
+        let mut outputs: HashMap<String, String> = HashMap::new();
+        let mut report = GraphReport::default();
+        let mut running = JoinSet::new();
+
+        loop {
+            if report.failed.is_empty() {
+                while running.len() < limit {
+                    let Some(index) = pending
+                        .iter()
+                        .position(|n| n.depends_on.iter().all(|d| outputs.contains_key(d)))
+                    else {
+                        break;
+                    };
+                    let node = pending.remove(index);
+                    let inputs = Outputs(
+                        node.depends_on
+                            .iter()
+                            .map(|d| (d.clone(), outputs[d].clone()))
+                            .collect(),
+                    );
+                    let future = (node.step)(client.clone(), inputs);
+                    let name = node.name;
+                    running.spawn(async move { (name, future.await) });
+                }
+            }
+
+            let Some(joined) = running.join_next().await else {
+                break;
+            };
+            match joined {
+                Ok((name, Ok(output))) => {
+                    outputs.insert(name.clone(), output.clone());
+                    report.completed.push((name, output));
+                }
+                Ok((node, Err(error))) => report.failed.push(NodeFailure { node, error }),
+                Err(join_error) => std::panic::resume_unwind(join_error.into_panic()),
+            }
+        }
+
+        report.skipped = pending.into_iter().map(|n| n.name).collect();
+        Ok(report)
+    }
+}
+
+impl std::fmt::Debug for Graph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Graph")
+            .field(
+                "nodes",
+                &self
+                    .nodes
+                    .iter()
+                    .map(|n| (&n.name, &n.depends_on))
+                    .collect::<Vec<_>>(),
+            )
+            .field("max_concurrency", &self.max_concurrency)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(output: &'static str) -> impl FnOnce(Client, Outputs) -> StepFuture + Send + 'static {
+        move |_, _| Box::pin(async move { Ok(output.to_string()) })
+    }
+
+    #[test]
+    fn test_check_rejects_invalid_graphs() {
+        let unknown = Graph::new().node("invoice", &["customer"], step("i"));
+        let err = unknown.check().unwrap_err().to_string();
+        assert!(err.contains("unknown node customer"), "{}", err);
+
+        let duplicate = Graph::new()
+            .node("a", &[], step("a"))
+            .node("a", &[], step("a"));
+        assert!(duplicate.check().is_err());
+
+        let cycle = Graph::new()
+            .node("root", &[], step("r"))
+            .node("a", &["root", "b"], step("a"))
+            .node("b", &["a"], step("b"));
+        let err = cycle.check().unwrap_err().to_string();
+        assert!(err.contains("cycle among a, b"), "{}", err);
+    }
+}
@@ -0,0 +1,279 @@
+//! Conditional GET cache keyed by request URL and access token.
+//!
+//! With a cache set through
+//! [`ClientConfig::response_cache`](crate::ClientConfig::response_cache),
+//! the client keeps GET responses that carry an `ETag` or `Last-Modified`
+//! header. The next GET of the same URL sends `If-None-Match` /
+//! `If-Modified-Since`, and a 304 Not Modified answer is served from the
+//! cache, so mostly-static lists such as units, terms of payment and VAT
+//! codes are not downloaded again.
+//!
+//! [`MemoryCache`] keeps entries in memory for a fixed time. Implement
+//! [`ResponseCache`] to share entries between processes, e.g. in Redis;
+//! [`CachedResponse`] is serializable for that purpose.
+//!
+//! Entries are keyed by URL and a hash of the `Authorization` header, so
+//! clients for different companies can share a cache without seeing each
+//! other's responses. A new access token starts with empty entries.
+//!
+//! # Example
+//!
+//! ```
+//! use spiris::http_cache::MemoryCache;
+//! use spiris::ClientConfig;
+//! use std::time::Duration;
+//!
+//! let config = ClientConfig::new().response_cache(MemoryCache::new(Duration::from_secs(3600)));
+//! ```
+
+use crate::clock::{Clock, SystemClock};
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use reqwest::header::{
+    HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+    LAST_MODIFIED,
+};
+use reqwest::{Request, RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Future returned by [`ResponseCache`] methods.
+pub type CacheFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Storage for cached responses.
+pub trait ResponseCache: Send + Sync + fmt::Debug {
+    /// The entry stored under `key`, if it has not expired.
+    fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<CachedResponse>>;
+
+    /// Store `response` under `key`, replacing any earlier entry.
+    fn put<'a>(&'a self, key: &'a str, response: CachedResponse) -> CacheFuture<'a, ()>;
+}
+
+/// A response body with the validators to revalidate it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    /// `ETag` header of the response.
+    pub etag: Option<String>,
+    /// `Last-Modified` header of the response.
+    pub last_modified: Option<String>,
+    /// `Content-Type` header of the response.
+    pub content_type: Option<String>,
+    /// Response body.
+    pub body: Vec<u8>,
+    /// When the response was stored or last revalidated.
+    pub stored_at: DateTime<Utc>,
+}
+
+impl CachedResponse {
+    /// Ask the server to answer 304 if this entry is still current.
+    pub(crate) fn conditional(&self, request: RequestBuilder) -> RequestBuilder {
+        match (&self.etag, &self.last_modified) {
+            (Some(etag), _) => request.header(IF_NONE_MATCH, etag),
+            (None, Some(last_modified)) => request.header(IF_MODIFIED_SINCE, last_modified),
+            (None, None) => request,
+        }
+    }
+
+    /// The entry as a 200 OK response.
+    fn replay(&self) -> Response {
+        let mut response = http::Response::new(self.body.clone());
+        if let Some(value) = self
+            .content_type
+            .as_deref()
+            .and_then(|v| HeaderValue::from_str(v).ok())
+        {
+            response.headers_mut().insert(CONTENT_TYPE, value);
+        }
+        Response::from(response)
+    }
+}
+
+fn header(headers: &HeaderMap, name: reqwest::header::HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// The key `request` is cached under: a hash of its `Authorization`
+/// header followed by its URL.
+pub(crate) fn cache_key(request: &Request) -> String {
+    let mut hasher = DefaultHasher::new();
+    request
+        .headers()
+        .get(AUTHORIZATION)
+        .map(HeaderValue::as_bytes)
+        .hash(&mut hasher);
+    format!("{:016x} {}", hasher.finish(), request.url())
+}
+
+/// Store a cacheable response, or answer a 304 from `cached`.
+///
+/// Other responses are returned unchanged.
+pub(crate) async fn store_or_replay(
+    cache: &dyn ResponseCache,
+    key: &str,
+    cached: Option<CachedResponse>,
+    response: Response,
+    now: DateTime<Utc>,
+) -> Result<Response> {
+    match (response.status(), cached) {
+        (StatusCode::NOT_MODIFIED, Some(mut cached)) => {
+            if let Some(etag) = header(response.headers(), ETAG) {
+                cached.etag = Some(etag);
+            }
+            cached.stored_at = now;
+            let replay = cached.replay();
+            cache.put(key, cached).await;
+            Ok(replay)
+        }
+        (StatusCode::OK, _) => {
+            let etag = header(response.headers(), ETAG);
+            let last_modified = header(response.headers(), LAST_MODIFIED);
+            if etag.is_none() && last_modified.is_none() {
+                return Ok(response);
+            }
+            let entry = CachedResponse {
+                etag,
+                last_modified,
+                content_type: header(response.headers(), CONTENT_TYPE),
+                body: response.bytes().await?.to_vec(),
+                stored_at: now,
+            };
+            let replay = entry.replay();
+            cache.put(key, entry).await;
+            Ok(replay)
+        }
+        _ => Ok(response),
+    }
+}
+
+/// In-memory cache whose entries expire a fixed time after they were
+/// stored or last revalidated.
+#[derive(Debug)]
+pub struct MemoryCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, CachedResponse>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl MemoryCache {
+    /// Keep entries for `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Measure entry ages with `clock` instead of the system time.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Number of entries, including expired ones not yet looked up.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Whether the cache holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all entries.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+impl ResponseCache for MemoryCache {
+    fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<CachedResponse>> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = match entries.get(key) {
+            Some(entry) if self.clock.elapsed_since(entry.stored_at) < self.ttl => {
+                Some(entry.clone())
+            }
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        };
+        Box::pin(std::future::ready(entry))
+    }
+
+    fn put<'a>(&'a self, key: &'a str, response: CachedResponse) -> CacheFuture<'a, ()> {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), response);
+        Box::pin(std::future::ready(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    fn entry(stored_at: DateTime<Utc>) -> CachedResponse {
+        CachedResponse {
+            etag: Some("\"v1\"".to_string()),
+            last_modified: None,
+            content_type: Some("application/json".to_string()),
+            body: b"[]".to_vec(),
+            stored_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_expires_entries() {
+        let clock = MockClock::new(Utc::now());
+        let cache = MemoryCache::new(Duration::from_secs(60)).with_clock(Arc::new(clock.clone()));
+
+        cache.put("https://api/units", entry(clock.now())).await;
+        clock.advance(Duration::from_secs(59));
+        assert!(cache.get("https://api/units").await.is_some());
+
+        clock.advance(Duration::from_secs(1));
+        assert!(cache.get("https://api/units").await.is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_cache_key_depends_on_authorization() {
+        let client = reqwest::Client::new();
+        let request = |token: &str| {
+            client
+                .get("https://api/units?page=1")
+                .bearer_auth(token)
+                .build()
+                .unwrap()
+        };
+
+        assert_eq!(cache_key(&request("a")), cache_key(&request("a")));
+        assert_ne!(cache_key(&request("a")), cache_key(&request("b")));
+        assert!(cache_key(&request("a")).ends_with(" https://api/units?page=1"));
+    }
+
+    #[tokio::test]
+    async fn test_replay_keeps_body_and_content_type() {
+        let response = entry(Utc::now()).replay();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+        assert_eq!(response.text().await.unwrap(), "[]");
+    }
+}
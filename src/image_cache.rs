@@ -0,0 +1,175 @@
+//! Local cache for logos and other images that rarely change.
+//!
+//! Requires the `image-cache` feature.
+//!
+//! Images are stored once per distinct content, named by their SHA-256
+//! hash, under `blobs/` in the cache directory. A small file under `keys/`
+//! maps each resource (such as `logotypes/{id}`) to the hash of its
+//! content, so a cached resource is read from disk without a request and
+//! two resources with the same image share one file.
+//!
+//! ```text
+//! cache/
+//! ├── blobs/3a7bd3e2360a3d29eea436fcfb7e44c735d117c42d1c1835420b6b9942dd4f1b
+//! └── keys/<sha256 of "logotypes/abc-123">   -> 3a7bd3e2...
+//! ```
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::image_cache::ImageCache;
+//! use spiris::{AccessToken, Client};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+//! let cache = ImageCache::new("/var/cache/spiris");
+//!
+//! for logo in client.logotypes().list(None).await?.data {
+//!     let image = cache.logotype(&client, logo.id.as_deref().unwrap_or_default()).await?;
+//!     println!("{} bytes at {}", image.content.len(), image.path.display());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::error::{Error, Result};
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+/// An image read from the cache.
+#[derive(Debug, Clone)]
+pub struct CachedImage {
+    /// SHA-256 of the content, hex encoded.
+    pub hash: String,
+    /// File holding the content.
+    pub path: PathBuf,
+    /// The image.
+    pub content: Vec<u8>,
+}
+
+/// Content-addressed image cache in a local directory.
+#[derive(Debug, Clone)]
+pub struct ImageCache {
+    dir: PathBuf,
+}
+
+impl ImageCache {
+    /// Use `dir` for the cache. It is created when the first image is
+    /// stored.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// A company logotype, downloaded only if it is not cached.
+    pub async fn logotype(&self, client: &Client, id: &str) -> Result<CachedImage> {
+        self.get_or_fetch(&format!("logotypes/{}", id), || async move {
+            client.logotypes().get_content(id).await
+        })
+        .await
+    }
+
+    /// An attachment's content, downloaded only if it is not cached.
+    pub async fn attachment(&self, client: &Client, id: &str) -> Result<CachedImage> {
+        self.get_or_fetch(&format!("attachments/{}", id), || async move {
+            client.attachments().get_content(id).await
+        })
+        .await
+    }
+
+    /// The cached content for `key`, if any.
+    ///
+    /// Content that no longer matches its hash is treated as missing.
+    pub async fn get(&self, key: &str) -> Option<CachedImage> {
+        let hash = tokio::fs::read_to_string(self.key_path(key)).await.ok()?;
+        let hash = hash.trim();
+        let path = self.blob_path(hash);
+        let content = tokio::fs::read(&path).await.ok()?;
+        (hex_sha256(&content) == hash).then(|| CachedImage {
+            hash: hash.to_string(),
+            path,
+            content,
+        })
+    }
+
+    /// The cached content for `key`, calling `fetch` and storing the result
+    /// if there is none.
+    pub async fn get_or_fetch<F, Fut>(&self, key: &str, fetch: F) -> Result<CachedImage>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Vec<u8>>>,
+    {
+        if let Some(image) = self.get(key).await {
+            return Ok(image);
+        }
+        self.insert(key, fetch().await?).await
+    }
+
+    /// Store `content` for `key`, replacing what was cached for it.
+    pub async fn insert(&self, key: &str, content: Vec<u8>) -> Result<CachedImage> {
+        let hash = hex_sha256(&content);
+        let path = self.blob_path(&hash);
+        let stored = tokio::fs::read(&path)
+            .await
+            .is_ok_and(|existing| hex_sha256(&existing) == hash);
+        if !stored {
+            write_atomic(&path, &content).await?;
+        }
+        write_atomic(&self.key_path(key), hash.as_bytes()).await?;
+        Ok(CachedImage {
+            hash,
+            path,
+            content,
+        })
+    }
+
+    /// Forget `key`, so the next lookup downloads it again.
+    ///
+    /// The content stays on disk; other keys may share it.
+    pub async fn invalidate(&self, key: &str) -> Result<()> {
+        match tokio::fs::remove_file(self.key_path(key)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(io_error(&self.key_path(key), e))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn key_path(&self, key: &str) -> PathBuf {
+        self.dir.join("keys").join(hex_sha256(key.as_bytes()))
+    }
+
+    fn blob_path(&self, hash: &str) -> PathBuf {
+        self.dir.join("blobs").join(hash)
+    }
+}
+
+fn hex_sha256(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// Write through a temporary file, so readers never see a partial file.
+async fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| io_error(parent, e))?;
+    }
+    let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+    tokio::fs::write(&tmp, content)
+        .await
+        .map_err(|e| io_error(&tmp, e))?;
+    tokio::fs::rename(&tmp, path)
+        .await
+        .map_err(|e| io_error(path, e))
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> Error {
+    Error::InvalidRequest(format!("image cache {}: {}", path.display(), e))
+}
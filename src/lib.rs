@@ -96,7 +96,7 @@
 //! # let client = Client::new(token);
 //! let invoice = Invoice {
 //!     customer_id: Some("customer-id-here".to_string()),
-//!     invoice_date: Some(Utc::now()),
+//!     invoice_date: Some(Utc::now().date_naive()),
 //!     rows: vec![
 //!         InvoiceRow {
 //!             text: Some("Consulting services".to_string()),
@@ -139,41 +139,92 @@
 //! ```
 
 pub mod auth;
+#[cfg(feature = "bank-files")]
+pub mod autogiro;
+#[cfg(feature = "bank-files")]
+pub mod bank_files;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(all(feature = "blocking", target_arch = "wasm32"))]
+compile_error!("the `blocking` feature needs a tokio runtime and is not available on wasm32");
+pub mod budget;
+pub mod bundles;
 pub mod client;
+pub mod clock;
+pub mod closing;
+pub mod diff;
+pub mod disputes;
+pub mod dunning;
 pub mod endpoints;
 pub mod error;
+pub mod export;
+#[cfg(feature = "fake")]
+pub mod fake;
+pub mod fields;
+pub mod forecast;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod graph;
+pub mod http_cache;
+#[cfg(feature = "image-cache")]
+pub mod image_cache;
+#[cfg(all(feature = "image-cache", target_arch = "wasm32"))]
+compile_error!("the `image-cache` feature writes to disk and is not available on wasm32");
 #[macro_use]
 pub mod macros;
 pub mod middleware;
 #[cfg(feature = "stream")]
 pub mod pagination;
+#[cfg(feature = "peppol")]
+pub mod peppol;
+pub mod periods;
 pub mod query;
 #[cfg(feature = "rate-limit")]
 pub mod rate_limit;
+#[cfg(feature = "bank-files")]
+pub mod reconciliation;
+pub mod redact;
+pub mod reports;
 pub mod retry;
+pub mod sync;
+#[cfg(feature = "test-fixtures")]
+pub mod test_fixtures;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod types;
+pub mod validation;
+pub mod vat;
+#[cfg(feature = "vcr")]
+pub mod vcr;
+#[cfg(feature = "vies")]
+pub mod vies;
 #[cfg(feature = "webhooks")]
 pub mod webhooks;
 
 // Re-export commonly used types
-pub use auth::{AccessToken, OAuth2Config, OAuth2Handler};
-pub use client::{Client, ClientConfig};
-pub use error::{ApiErrorResponse, Error, Result, ValidationError};
+pub use auth::{AccessToken, Environment, OAuth2Config, OAuth2Handler};
+pub use client::{Client, ClientConfig, Language, RequestOptions};
+pub use error::{ApiErrorResponse, Error, ErrorContext, Result, ValidationError};
 #[cfg(feature = "rate-limit")]
 pub use rate_limit::RateLimitConfig;
-pub use retry::RetryConfig;
+pub use retry::{Jitter, RateLimitStatus, RetryConfig, RetryPolicy};
+pub use tokio_util::sync::CancellationToken;
 pub use types::{
     Account, AccountBalance, AccountType, Address, AllocationPeriod, Article, ArticleAccountCoding,
-    ArticleCreate, ArticleLabel, ArticleUpdate, Attachment, AttachmentLink, Bank, BankAccount,
-    CompanySettings, ConvertDraftOptions, CostCenter, CostCenterItem, Country, Currency, Customer,
-    CustomerCreate, CustomerInvoiceDraft, CustomerInvoiceDraftRow, CustomerLabel,
-    CustomerLedgerItem, CustomerUpdate, DeliveryMethod, DeliveryTerm, Document, FiscalYear,
-    ForeignPaymentCode, Invoice, InvoiceCreate, InvoicePayment, InvoiceRow, InvoiceRowCreate,
-    InvoiceUpdate, Message, MessageThread, Money, Order, OrderRow, PaginatedResponse,
-    PaginationParams, Project, QueryParams, Quotation, QuotationRow, ResponseMetadata, Supplier,
-    SupplierInvoice, SupplierInvoiceDraft, SupplierInvoiceRow, SupplierLabel, SupplierLedgerItem,
-    TermsOfPayment, Unit, User, VatCode, Voucher, VoucherRow,
+    ArticleCreate, ArticleId, ArticleLabel, ArticlePatch, ArticleUpdate, Attachment,
+    AttachmentLink, AttachmentUpload, Audited, Bank, BankAccount, CompanySettings,
+    ConvertDraftOptions, CostCenter, CostCenterItem, Country, CreateInvoiceOptions, Currency,
+    Customer, CustomerCreate, CustomerId, CustomerInvoiceDraft, CustomerInvoiceDraftRow,
+    CustomerLabel, CustomerLedgerItem, CustomerPatch, CustomerUpdate, DeliveryMethod, DeliveryTerm,
+    Document, FiscalYear, ForeignPaymentCode, Invoice, InvoiceCreate, InvoiceDelivery, InvoiceId,
+    InvoicePatch, InvoicePayment, InvoiceReminder, InvoiceRow, InvoiceRowCreate, InvoiceStatus,
+    InvoiceUpdate, ListAllOptions, ListScope, Logotype, Message, MessageThread, Money, NoteTarget,
+    Order, OrderRow, PaginatedResponse, PaginationParams, Patch, PaymentStatus, Project,
+    QueryParams, Quotation, QuotationRow, ReminderLevel, ResponseMetadata, SendInvoiceOptions,
+    SentStatus, Supplier, SupplierInvoice, SupplierInvoiceDraft, SupplierInvoicePayment,
+    SupplierInvoiceRow, SupplierLabel, SupplierLedgerItem, TermsOfPayment, Unit, User, VatCode,
+    VatReport, Versioned, Voucher, VoucherRow,
 };
+pub use validation::Validate;
 
 // Add endpoint accessors to the Client
 impl Client {
@@ -256,6 +307,11 @@ impl Client {
         endpoints::VatCodesEndpoint::new(self)
     }
 
+    /// Access the VAT reports endpoint.
+    pub fn vat_reports(&self) -> endpoints::VatReportsEndpoint<'_> {
+        endpoints::VatReportsEndpoint::new(self)
+    }
+
     /// Access the vouchers endpoint.
     pub fn vouchers(&self) -> endpoints::VouchersEndpoint<'_> {
         endpoints::VouchersEndpoint::new(self)
@@ -266,6 +322,11 @@ impl Client {
         endpoints::BankAccountsEndpoint::new(self)
     }
 
+    /// Access cached reference data (accounts, VAT codes, units, terms).
+    pub fn reference_data(&self) -> endpoints::ReferenceDataEndpoint<'_> {
+        endpoints::ReferenceDataEndpoint::new(self)
+    }
+
     /// Access the projects endpoint.
     pub fn projects(&self) -> endpoints::ProjectsEndpoint<'_> {
         endpoints::ProjectsEndpoint::new(self)
@@ -291,11 +352,21 @@ impl Client {
         endpoints::QuotationsEndpoint::new(self)
     }
 
+    /// Access the quotations endpoint (alias for [`Client::quotations`]).
+    pub fn quotes(&self) -> endpoints::QuotationsEndpoint<'_> {
+        endpoints::QuotationsEndpoint::new(self)
+    }
+
     /// Access the supplier invoice drafts endpoint.
     pub fn supplier_invoice_drafts(&self) -> endpoints::SupplierInvoiceDraftsEndpoint<'_> {
         endpoints::SupplierInvoiceDraftsEndpoint::new(self)
     }
 
+    /// Access the supplier invoice payments endpoint.
+    pub fn supplier_invoice_payments(&self) -> endpoints::SupplierInvoicePaymentsEndpoint<'_> {
+        endpoints::SupplierInvoicePaymentsEndpoint::new(self)
+    }
+
     /// Access the supplier ledger items endpoint.
     pub fn supplier_ledger_items(&self) -> endpoints::SupplierLedgerItemsEndpoint<'_> {
         endpoints::SupplierLedgerItemsEndpoint::new(self)
@@ -341,6 +412,11 @@ impl Client {
         endpoints::AttachmentsEndpoint::new(self)
     }
 
+    /// Access the logotypes endpoint.
+    pub fn logotypes(&self) -> endpoints::LogotypesEndpoint<'_> {
+        endpoints::LogotypesEndpoint::new(self)
+    }
+
     /// Access the documents endpoint.
     pub fn documents(&self) -> endpoints::DocumentsEndpoint<'_> {
         endpoints::DocumentsEndpoint::new(self)
@@ -423,6 +499,40 @@ mod tests {
         assert_eq!(params.select, Some("Id,Name".to_string()));
     }
 
+    #[test]
+    fn test_list_scope_filter() {
+        let params = PaginationParams::new()
+            .pagesize(50)
+            .scope(ListScope::InactiveOnly);
+        assert_eq!(
+            serde_json::to_value(params).unwrap(),
+            serde_json::json!({"pagesize": 50, "filter": "IsActive eq false"})
+        );
+
+        let params = PaginationParams::new().page(1);
+        assert_eq!(
+            serde_json::to_value(params).unwrap(),
+            serde_json::json!({"page": 1})
+        );
+    }
+
+    #[test]
+    fn test_resolve_scope() {
+        let (query, pagination) = QueryParams::new()
+            .filter("Name eq 'Acme'")
+            .resolve_scope(Some(PaginationParams::new().scope(ListScope::ActiveOnly)));
+        assert_eq!(
+            query.filter.as_deref(),
+            Some("(Name eq 'Acme') and (IsActive eq true)")
+        );
+        assert_eq!(pagination.unwrap().scope, ListScope::All);
+
+        let (query, _) = QueryParams::new()
+            .scope(ListScope::InactiveOnly)
+            .resolve_scope(None);
+        assert_eq!(query.filter.as_deref(), Some("IsActive eq false"));
+    }
+
     #[test]
     fn test_client_config_builder() {
         let config = ClientConfig::new()
@@ -10,10 +10,10 @@
 ///
 /// # Capabilities
 ///
-/// - `list` - List all items with pagination
-/// - `get` - Get a single item by ID
+/// - `list` - List items a page at a time, or every page with `list_all`
+/// - `get` - Get a single item by ID, or with its ETag via `get_versioned`
 /// - `create` - Create a new item
-/// - `update` - Update an existing item
+/// - `update` - Update an existing item, or only if unchanged via `update_if_match`
 /// - `delete` - Delete an item
 /// - `search` - Search with query parameters
 /// - `stream` - Paginated streaming (requires `stream` feature)
@@ -81,6 +81,8 @@ macro_rules! __endpoint_impl {
             }
         }
 
+        $crate::__list_all_impl!($path, $type);
+
         $crate::__endpoint_impl!($path, $type, $($rest),*);
     };
 
@@ -91,6 +93,8 @@ macro_rules! __endpoint_impl {
             self.client.get(&format!(concat!($path, "/{}"), id)).await
         }
 
+        $crate::__get_versioned_impl!($path, $type);
+
         $crate::__endpoint_impl!($path, $type, $($rest),*);
     };
 
@@ -111,6 +115,8 @@ macro_rules! __endpoint_impl {
             self.client.put(&format!(concat!($path, "/{}"), id), item).await
         }
 
+        $crate::__update_if_match_impl!($path, $type);
+
         $crate::__endpoint_impl!($path, $type, $($rest),*);
     };
 
@@ -140,6 +146,7 @@ macro_rules! __endpoint_impl {
                 pagination: Option<$crate::types::PaginationParams>,
             }
 
+            let (query, pagination) = query.resolve_scope(pagination);
             let params = CombinedParams { query, pagination };
             self.client.get_with_params($path, &params).await
         }
@@ -168,6 +175,17 @@ macro_rules! __endpoint_impl {
             $crate::paginated_stream!(page_size, |params| self.list(Some(params)))
         }
 
+        /// Stream all items with a custom page size and prefetching.
+        ///
+        /// Requires the `stream` feature.
+        #[cfg(feature = "stream")]
+        pub fn list_stream_with(
+            &self,
+            options: $crate::pagination::StreamOptions,
+        ) -> impl futures::Stream<Item = $crate::error::Result<$type>> + '_ {
+            $crate::pagination::paginated_stream_with(options, move |params| self.list(Some(params)))
+        }
+
         $crate::__endpoint_impl!($path, $type, $($rest),*);
     };
 
@@ -177,5 +195,174 @@ macro_rules! __endpoint_impl {
     };
 }
 
+/// Internal macro adding `list_all` and `list_all_with` to an endpoint.
+///
+/// Used by the `list` capability and by hand-written endpoints.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __list_all_impl {
+    ($path:literal, $type:ty) => {
+        /// Fetch every page, optionally filtered by `query`.
+        ///
+        /// Pages of 500 items are requested one after another, each through
+        /// the client's rate limiter. Use
+        /// [`list_all_with`](Self::list_all_with) to change the page size or
+        /// limit the number of items.
+        pub async fn list_all(
+            &self,
+            query: Option<$crate::types::QueryParams>,
+        ) -> $crate::error::Result<Vec<$type>> {
+            self.list_all_with(query, $crate::types::ListAllOptions::default())
+                .await
+        }
+
+        /// Fetch every page with a custom page size and item limit.
+        pub async fn list_all_with(
+            &self,
+            query: Option<$crate::types::QueryParams>,
+            options: $crate::types::ListAllOptions,
+        ) -> $crate::error::Result<Vec<$type>> {
+            $crate::endpoints::list_all(self.client, $path, query, options).await
+        }
+
+        /// Fetch every page, requesting up to `parallelism` pages at once.
+        ///
+        /// The first page is fetched alone to learn how many pages there
+        /// are; the rest run concurrently, each still through the client's
+        /// rate limiter. Items come back in page order. Much faster than
+        /// [`list_all`](Self::list_all) for large registers, but a listing
+        /// that changes while it is read can skip or repeat items at page
+        /// boundaries.
+        ///
+        /// Requires the `stream` feature.
+        #[cfg(feature = "stream")]
+        pub async fn list_all_concurrent(
+            &self,
+            query: Option<$crate::types::QueryParams>,
+            parallelism: usize,
+        ) -> $crate::error::Result<Vec<$type>> {
+            $crate::endpoints::list_all_concurrent(self.client, $path, query, parallelism).await
+        }
+
+        /// Stream every item, deserializing each one as the response body
+        /// arrives instead of after the whole page has been read.
+        ///
+        /// Pages of 500 items are requested one after another, and memory
+        /// stays flat however large the register is, which suits full
+        /// exports. Requires the `stream` feature.
+        #[cfg(feature = "stream")]
+        pub fn list_all_streaming(
+            &self,
+            query: Option<$crate::types::QueryParams>,
+        ) -> impl futures::Stream<Item = $crate::error::Result<$type>> + '_ {
+            $crate::endpoints::list_all_streaming(self.client, $path, query)
+        }
+    };
+}
+
+/// Internal macro adding `modified_since` and `sync` to an endpoint whose
+/// records carry `ModifiedUtc`.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __sync_impl {
+    ($path:literal, $type:ty) => {
+        /// Fetch every record modified at or after `since`, oldest change
+        /// first.
+        pub async fn modified_since(
+            &self,
+            since: chrono::DateTime<chrono::Utc>,
+        ) -> $crate::error::Result<Vec<$type>> {
+            $crate::endpoints::modified_since(self.client, $path, Some(since)).await
+        }
+
+        /// Fetch the records changed since `cursor` and move it past them.
+        ///
+        /// A new cursor fetches everything. See [`crate::sync`] for how
+        /// records at the cursor's exact time are handled.
+        pub async fn sync(
+            &self,
+            cursor: &mut $crate::sync::SyncCursor,
+        ) -> $crate::error::Result<Vec<$type>> {
+            $crate::endpoints::sync(self.client, $path, cursor).await
+        }
+    };
+}
+
+/// Internal macro adding `get_versioned` to an endpoint.
+///
+/// Used by the `get` capability and by hand-written endpoints, which pass
+/// their ID type when it is not `str`.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __get_versioned_impl {
+    ($path:literal, $type:ty) => {
+        $crate::__get_versioned_impl!($path, $type, str);
+    };
+
+    ($path:literal, $type:ty, $id:ty) => {
+        /// Get an item by ID together with its ETag, to update it with
+        /// [`update_if_match`](Self::update_if_match).
+        pub async fn get_versioned(
+            &self,
+            id: &$id,
+        ) -> $crate::error::Result<$crate::types::Versioned<$type>> {
+            self.client
+                .get_versioned(&format!(concat!($path, "/{}"), id))
+                .await
+        }
+    };
+}
+
+/// Internal macro adding `update_if_match` to an endpoint.
+///
+/// Used by the `update` capability and by hand-written endpoints; `validate`
+/// checks the item first, like their `update` does.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __update_if_match_impl {
+    ($path:literal, $type:ty) => {
+        $crate::__update_if_match_impl!($path, $type, str);
+    };
+
+    ($path:literal, $type:ty, validate) => {
+        $crate::__update_if_match_impl!($path, $type, str, validate);
+    };
+
+    ($path:literal, $type:ty, $id:ty) => {
+        /// Update an item only if it is unchanged on the server since it was
+        /// fetched with `get_versioned`.
+        ///
+        /// Fails with [`Error::Conflict`](crate::Error::Conflict) if it
+        /// changed. Without an ETag the update is unconditional.
+        pub async fn update_if_match(
+            &self,
+            id: &$id,
+            item: &$crate::types::Versioned<$type>,
+        ) -> $crate::error::Result<$crate::types::Versioned<$type>> {
+            self.client
+                .put_if_match(&format!(concat!($path, "/{}"), id), item)
+                .await
+        }
+    };
+
+    ($path:literal, $type:ty, $id:ty, validate) => {
+        /// Update an item only if it is unchanged on the server since it was
+        /// fetched with `get_versioned`.
+        ///
+        /// Fails with [`Error::Conflict`](crate::Error::Conflict) if it
+        /// changed. Without an ETag the update is unconditional.
+        pub async fn update_if_match(
+            &self,
+            id: &$id,
+            item: &$crate::types::Versioned<$type>,
+        ) -> $crate::error::Result<$crate::types::Versioned<$type>> {
+            self.client.validate_request(&item.value)?;
+            self.client
+                .put_if_match(&format!(concat!($path, "/{}"), id), item)
+                .await
+        }
+    };
+}
+
 // Macro compilation is tested via the actual endpoint implementations
 // in src/endpoints/. Those tests cover all macro variants.
@@ -27,7 +27,12 @@
 use crate::error::Result;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+// `std::time::Instant` panics in the browser
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
 
 /// Context provided to middleware before a request is sent.
 #[derive(Debug)]
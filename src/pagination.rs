@@ -42,8 +42,10 @@
 //! ```
 
 use crate::error::Result;
-use crate::types::{PaginatedResponse, PaginationParams};
-use futures::Stream;
+use crate::types::{PaginatedResponse, PaginationParams, ResponseMetadata};
+use futures::stream::FuturesOrdered;
+use futures::{FutureExt, Stream, StreamExt};
+use std::collections::VecDeque;
 use std::future::Future;
 
 /// Default page size for pagination streams.
@@ -137,10 +139,245 @@ where
     }
 }
 
+/// Page size and prefetching for pagination streams.
+///
+/// With `prefetch` set, up to that many following pages are requested while
+/// the items of the current page are being consumed, so a consumer that does
+/// little work per item no longer waits a full round trip at every page
+/// boundary. Prefetched requests make progress whenever the stream is polled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamOptions {
+    /// Number of items per page.
+    pub page_size: u32,
+    /// Maximum number of pages requested ahead of the one being consumed.
+    pub prefetch: usize,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self {
+            page_size: DEFAULT_PAGE_SIZE,
+            prefetch: 0,
+        }
+    }
+}
+
+impl StreamOptions {
+    /// Create options with the default page size and no prefetching.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the page size.
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Set how many pages to request ahead.
+    pub fn prefetch(mut self, pages: usize) -> Self {
+        self.prefetch = pages;
+        self
+    }
+}
+
+/// Creates a stream that paginates through API responses according to
+/// `options`, prefetching following pages if requested.
+///
+/// Unlike [`paginated_stream`], `fetch` may borrow, so endpoints can pass a
+/// closure over `self`. Pages are yielded in order; an error from a
+/// prefetched page is returned when that page is reached.
+pub fn paginated_stream_with<'a, T, F, Fut>(
+    options: StreamOptions,
+    fetch: F,
+) -> impl Stream<Item = Result<T>> + 'a
+where
+    T: 'a,
+    F: Fn(PaginationParams) -> Fut + 'a,
+    Fut: Future<Output = Result<PaginatedResponse<T>>> + 'a,
+{
+    let request = move |page: u32| {
+        fetch(
+            PaginationParams::new()
+                .page(page)
+                .pagesize(options.page_size),
+        )
+    };
+
+    async_stream::try_stream! {
+        let mut in_flight = FuturesOrdered::new();
+        let mut ready = VecDeque::new();
+        in_flight.push_back(request(0));
+        let mut current_page = 0u32;
+        let mut next_page = 1u32;
+
+        loop {
+            let response = match ready.pop_front() {
+                Some(response) => response,
+                None => match in_flight.next().await {
+                    Some(response) => response,
+                    None => break,
+                },
+            }?;
+
+            let has_next_page = response.meta.has_next_page;
+            if has_next_page {
+                // Only request pages known to exist; without a page count
+                // that is just the next one.
+                let end = response.meta.total_pages.max(current_page + 2);
+                while in_flight.len() + ready.len() < options.prefetch && next_page < end {
+                    in_flight.push_back(request(next_page));
+                    next_page += 1;
+                }
+            }
+
+            for item in response.data {
+                yield item;
+                if !in_flight.is_empty() {
+                    if let Some(Some(response)) = in_flight.next().now_or_never() {
+                        ready.push_back(response);
+                    }
+                }
+            }
+
+            if !has_next_page {
+                break;
+            }
+            if in_flight.is_empty() && ready.is_empty() {
+                in_flight.push_back(request(next_page));
+                next_page += 1;
+            }
+            current_page += 1;
+        }
+    }
+}
+
+/// Splits a list response body into its `Data` items as the bytes arrive.
+///
+/// Only the item being received is buffered, so a page of thousands of
+/// items is deserialized one at a time instead of after the whole body has
+/// been read. Everything outside the `Data` array is kept to read `Meta`
+/// from at the end.
+#[derive(Debug, Default)]
+pub(crate) struct ListItems {
+    /// Bytes of the item being received.
+    item: Vec<u8>,
+    /// The body with the `Data` array emptied.
+    envelope: Vec<u8>,
+    depth: u32,
+    in_string: bool,
+    escaped: bool,
+    in_data: bool,
+    /// The last string seen at the top level, i.e. the current key.
+    key: Vec<u8>,
+    string: Vec<u8>,
+}
+
+impl ListItems {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next chunk of the body, returning the items it completed.
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> Vec<Vec<u8>> {
+        let mut items = Vec::new();
+        for &byte in chunk {
+            if self.in_data {
+                self.push_data(byte, &mut items);
+            } else {
+                self.push_envelope(byte);
+            }
+        }
+        items
+    }
+
+    fn push_envelope(&mut self, byte: u8) {
+        self.envelope.push(byte);
+        if self.in_string {
+            match byte {
+                _ if self.escaped => self.escaped = false,
+                b'\\' => self.escaped = true,
+                b'"' => {
+                    self.in_string = false;
+                    if self.depth == 1 {
+                        self.key = std::mem::take(&mut self.string);
+                    }
+                    return;
+                }
+                _ => {}
+            }
+            if self.depth == 1 {
+                self.string.push(byte);
+            }
+            return;
+        }
+        match byte {
+            b'"' => {
+                self.in_string = true;
+                self.string.clear();
+            }
+            b'[' if self.depth == 1 && self.key == b"Data" => {
+                self.depth += 1;
+                self.in_data = true;
+            }
+            b'{' | b'[' => self.depth += 1,
+            b'}' | b']' => self.depth = self.depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    fn push_data(&mut self, byte: u8, items: &mut Vec<Vec<u8>>) {
+        if self.in_string {
+            match byte {
+                _ if self.escaped => self.escaped = false,
+                b'\\' => self.escaped = true,
+                b'"' => self.in_string = false,
+                _ => {}
+            }
+            self.item.push(byte);
+            return;
+        }
+        match byte {
+            b',' | b']' if self.depth == 2 => {
+                let mut item = std::mem::take(&mut self.item);
+                while item.last().is_some_and(u8::is_ascii_whitespace) {
+                    item.pop();
+                }
+                if !item.is_empty() {
+                    items.push(item);
+                }
+                if byte == b']' {
+                    self.envelope.push(b']');
+                    self.depth -= 1;
+                    self.in_data = false;
+                }
+                return;
+            }
+            b' ' | b'\t' | b'\r' | b'\n' if self.item.is_empty() => return,
+            b'"' => self.in_string = true,
+            b'{' | b'[' => self.depth += 1,
+            b'}' | b']' => self.depth -= 1,
+            _ => {}
+        }
+        self.item.push(byte);
+    }
+
+    /// The page's `Meta`, once the whole body has been pushed.
+    pub(crate) fn finish(self) -> Result<ResponseMetadata> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct Envelope {
+            meta: ResponseMetadata,
+        }
+
+        let envelope: Envelope = serde_json::from_slice(&self.envelope)?;
+        Ok(envelope.meta)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::ResponseMetadata;
     use futures::StreamExt;
 
     #[tokio::test]
@@ -250,4 +487,124 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert!(results[0].is_err());
     }
+
+    fn numbered_page(page: u32, total_pages: u32) -> Result<PaginatedResponse<u32>> {
+        Ok(PaginatedResponse {
+            data: vec![page * 10, page * 10 + 1],
+            meta: ResponseMetadata {
+                current_page: page,
+                page_size: 2,
+                total_pages,
+                total_count: total_pages * 2,
+                has_next_page: page + 1 < total_pages,
+                has_previous_page: page > 0,
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_paginated_stream_with_prefetch() {
+        use std::sync::Mutex;
+
+        let requested = Mutex::new(Vec::new());
+        let options = StreamOptions::new().page_size(2).prefetch(2);
+        let stream = paginated_stream_with(options, |params| {
+            let page = params.page.unwrap();
+            requested.lock().unwrap().push(page);
+            async move { numbered_page(page, 4) }
+        });
+        futures::pin_mut!(stream);
+
+        assert_eq!(stream.next().await.unwrap().unwrap(), 0);
+        assert_eq!(*requested.lock().unwrap(), vec![0, 1, 2]);
+
+        let rest: Vec<u32> = stream.map(|r| r.unwrap()).collect().await;
+        assert_eq!(rest, vec![1, 10, 11, 20, 21, 30, 31]);
+        // Never requests past the last page
+        assert_eq!(*requested.lock().unwrap(), vec![0, 1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_paginated_stream_with_prefetch_error_in_order() {
+        use crate::error::Error;
+
+        let options = StreamOptions::new().page_size(2).prefetch(3);
+        let stream = paginated_stream_with(options, |params| async move {
+            match params.page.unwrap() {
+                1 => Err(Error::NotFound("page 1".to_string())),
+                n => numbered_page(n, 3),
+            }
+        });
+
+        let results: Vec<Result<u32>> = stream.collect().await;
+        assert_eq!(results.len(), 3);
+        assert_eq!(*results[1].as_ref().unwrap(), 1);
+        assert!(results[2].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_paginated_stream_with_without_prefetch_is_sequential() {
+        use std::sync::Mutex;
+
+        let requested = Mutex::new(Vec::new());
+        let stream = paginated_stream_with(StreamOptions::new().page_size(2), |params| {
+            let page = params.page.unwrap();
+            requested.lock().unwrap().push(page);
+            async move { numbered_page(page, 3) }
+        });
+        futures::pin_mut!(stream);
+
+        stream.next().await.unwrap().unwrap();
+        stream.next().await.unwrap().unwrap();
+        assert_eq!(*requested.lock().unwrap(), vec![0]);
+        assert_eq!(stream.count().await, 4);
+    }
+
+    fn split_items(body: &str, chunk_size: usize) -> (Vec<String>, ResponseMetadata) {
+        let mut parser = ListItems::new();
+        let mut items = Vec::new();
+        for chunk in body.as_bytes().chunks(chunk_size) {
+            items.extend(
+                parser
+                    .push(chunk)
+                    .into_iter()
+                    .map(|item| String::from_utf8(item).unwrap()),
+            );
+        }
+        (items, parser.finish().unwrap())
+    }
+
+    #[test]
+    fn test_list_items_split_across_chunks() {
+        let body = r#"{"Meta": {"CurrentPage": 0, "PageSize": 2, "TotalPages": 3,
+            "TotalCount": 5, "HasNextPage": true, "HasPreviousPage": false},
+            "Data": [ {"Id": "a", "Name": "Data, \"quoted\" ]}", "Rows": [1, [2]]},
+            {"Id": "b"} ]}"#;
+
+        for chunk_size in [1, 3, 7, body.len()] {
+            let (items, meta) = split_items(body, chunk_size);
+            assert_eq!(
+                items,
+                vec![
+                    r#"{"Id": "a", "Name": "Data, \"quoted\" ]}", "Rows": [1, [2]]}"#,
+                    r#"{"Id": "b"}"#,
+                ]
+            );
+            assert_eq!(meta.total_count, 5);
+            assert!(meta.has_next_page);
+        }
+    }
+
+    #[test]
+    fn test_list_items_empty_and_truncated() {
+        let meta = r#""Meta": {"CurrentPage": 0, "PageSize": 50, "TotalPages": 0,
+            "TotalCount": 0, "HasNextPage": false, "HasPreviousPage": false}"#;
+        let (items, _) = split_items(&format!(r#"{{"Data": [], {}}}"#, meta), 4);
+        assert!(items.is_empty());
+
+        let mut parser = ListItems::new();
+        let items = parser.push(br#"{"Data": [{"Id": "a"}, {"Id": "#);
+        assert_eq!(items.len(), 1);
+        assert!(parser.finish().is_err());
+    }
 }
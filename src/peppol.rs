@@ -0,0 +1,697 @@
+//! Peppol BIS Billing 3 e-invoices.
+//!
+//! [`PeppolInvoice`] renders a customer invoice as UBL 2.1 XML following
+//! Peppol BIS Billing 3 and EN 16931, so the invoice can also be delivered
+//! through an access point of your own. The seller comes from the company
+//! settings and the buyer from the customer record.
+//!
+//! Invoice rows only carry a VAT rate ID, so the percentage for each ID is
+//! registered with [`PeppolInvoice::vat_rate`]; rows with an unregistered
+//! ID use [`PeppolInvoice::default_vat_rate`], 25% unless changed. Line,
+//! VAT and invoice totals are computed from the rows so that they add up as
+//! the EN 16931 rules require.
+//!
+//! Requires the `peppol` feature.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::peppol::PeppolInvoice;
+//! use spiris::{money, InvoiceId};
+//! # async fn example(client: &spiris::Client) -> Result<(), Box<dyn std::error::Error>> {
+//! let invoice = client.invoices().get(&InvoiceId::from("invoice-id")).await?;
+//! let customer = client
+//!     .customers()
+//!     .get(invoice.customer_id.as_ref().unwrap())
+//!     .await?;
+//! let company = client.company_settings().get().await?;
+//!
+//! let xml = PeppolInvoice::new(&invoice, &company, &customer)
+//!     .vat_rate("vat-rate-12", money!(12))
+//!     .payee_account("5050-1055")
+//!     .to_xml()?;
+//! std::fs::write("invoice.xml", xml)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::dunning::round_cents;
+use crate::error::{Error, Result, ValidationError};
+use crate::types::{Address, CompanySettings, Customer, Invoice, InvoiceRow, Money};
+use quick_xml::events::{BytesDecl, BytesText, Event};
+use quick_xml::Writer;
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+
+const CUSTOMIZATION_ID: &str =
+    "urn:cen.eu:en16931:2017#compliant#urn:fdc:peppol.eu:2017:poacc:billing:3.0";
+const PROFILE_ID: &str = "urn:fdc:peppol.eu:2017:poacc:billing:01:1.0";
+
+/// Electronic address scheme for Swedish organisation numbers.
+const SCHEME_SE_ORGNR: &str = "0007";
+
+/// VAT category of an invoice line (UNCL5305).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VatCategory {
+    /// Standard rated.
+    Standard,
+    /// Exempt from VAT.
+    Exempt,
+    /// Reverse charge: the buyer accounts for the VAT.
+    ReverseCharge,
+    /// Intra-community supply of goods or services.
+    IntraCommunity,
+}
+
+impl VatCategory {
+    /// The UNCL5305 code.
+    pub fn code(self) -> &'static str {
+        match self {
+            VatCategory::Standard => "S",
+            VatCategory::Exempt => "E",
+            VatCategory::ReverseCharge => "AE",
+            VatCategory::IntraCommunity => "K",
+        }
+    }
+
+    /// Exemption reason required by EN 16931 for categories without VAT.
+    fn exemption_reason(self) -> Option<&'static str> {
+        match self {
+            VatCategory::Standard => None,
+            VatCategory::Exempt => Some("Exempt from VAT"),
+            VatCategory::ReverseCharge => Some("Reverse charge"),
+            VatCategory::IntraCommunity => Some("Intra-community supply"),
+        }
+    }
+}
+
+/// A customer invoice ready to render as Peppol BIS Billing 3 XML.
+#[derive(Debug, Clone)]
+pub struct PeppolInvoice<'a> {
+    invoice: &'a Invoice,
+    seller: &'a CompanySettings,
+    buyer: &'a Customer,
+    vat_rates: HashMap<String, Money>,
+    default_vat_rate: Money,
+    buyer_reference: Option<String>,
+    buyer_endpoint: Option<(String, String)>,
+    payee_account: Option<String>,
+}
+
+/// A rendered line with its computed amounts.
+struct Line<'r> {
+    row: &'r InvoiceRow,
+    quantity: Money,
+    net: Money,
+    category: VatCategory,
+    percent: Money,
+}
+
+impl<'a> PeppolInvoice<'a> {
+    /// Prepare `invoice`, sold by the company in `seller` to `buyer`.
+    pub fn new(invoice: &'a Invoice, seller: &'a CompanySettings, buyer: &'a Customer) -> Self {
+        Self {
+            invoice,
+            seller,
+            buyer,
+            vat_rates: HashMap::new(),
+            default_vat_rate: crate::money!(25),
+            buyer_reference: None,
+            buyer_endpoint: None,
+            payee_account: None,
+        }
+    }
+
+    /// Percentage for rows with the VAT rate ID `vat_rate_id`.
+    pub fn vat_rate(mut self, vat_rate_id: impl Into<String>, percent: Money) -> Self {
+        self.vat_rates.insert(vat_rate_id.into(), percent);
+        self
+    }
+
+    /// Percentage for rows whose VAT rate ID isn't registered.
+    pub fn default_vat_rate(mut self, percent: Money) -> Self {
+        self.default_vat_rate = percent;
+        self
+    }
+
+    /// Reference the buyer asked for, such as a cost centre or contact.
+    /// Defaults to the customer number, since Peppol requires one.
+    pub fn buyer_reference(mut self, reference: impl Into<String>) -> Self {
+        self.buyer_reference = Some(reference.into());
+        self
+    }
+
+    /// Buyer's Peppol address, as an EAS scheme and identifier. Defaults to
+    /// the customer's organisation number under scheme `0007`.
+    pub fn buyer_endpoint(mut self, scheme: impl Into<String>, id: impl Into<String>) -> Self {
+        self.buyer_endpoint = Some((scheme.into(), id.into()));
+        self
+    }
+
+    /// Account the buyer pays to, such as a bankgiro or IBAN. Adds payment
+    /// instructions with the invoice's OCR number as payment reference.
+    pub fn payee_account(mut self, account: impl Into<String>) -> Self {
+        self.payee_account = Some(account.into());
+        self
+    }
+
+    /// Every field missing for a valid e-invoice; empty if none are.
+    pub fn validation_errors(&self) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        let mut require = |field: &str, present: bool| {
+            if !present {
+                errors.push(ValidationError {
+                    field: field.to_string(),
+                    message: "is required".to_string(),
+                });
+            }
+        };
+        require("InvoiceNumber", self.invoice.invoice_number.is_some());
+        require("InvoiceDate", self.invoice.invoice_date.is_some());
+        require("Rows", self.lines().next().is_some());
+        require("Seller.CompanyName", self.seller.company_name.is_some());
+        require(
+            "Seller.CorporateIdentityNumber",
+            self.seller.corporate_identity_number.is_some(),
+        );
+        require("Buyer.Name", self.buyer.name.is_some());
+        require(
+            "Buyer.CorporateIdentityNumber",
+            self.buyer_endpoint.is_some() || self.buyer.corporate_identity_number.is_some(),
+        );
+        errors
+    }
+
+    /// Render the invoice as UBL XML.
+    ///
+    /// Fails with [`Error::Validation`] listing every missing field.
+    pub fn to_xml(&self) -> Result<String> {
+        let errors = self.validation_errors();
+        if !errors.is_empty() {
+            return Err(Error::Validation(errors));
+        }
+
+        let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+        self.write(&mut writer)?;
+        let xml = String::from_utf8(writer.into_inner())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(xml)
+    }
+
+    fn currency(&self) -> &str {
+        self.invoice
+            .currency_code
+            .as_deref()
+            .or(self.seller.currency_code.as_deref())
+            .unwrap_or("SEK")
+    }
+
+    /// Rows with an amount, skipping text-only rows.
+    fn lines(&self) -> impl Iterator<Item = Line<'_>> {
+        let reverse_charge = self
+            .invoice
+            .reverse_charge_on_construction_services
+            .unwrap_or(false);
+        let intra_community = self.invoice.eu_third_party.unwrap_or(false);
+        self.invoice
+            .rows
+            .iter()
+            .filter(|row| row.unit_price.is_some())
+            .map(move |row| {
+                let quantity = row.quantity.unwrap_or(crate::money!(1));
+                let gross = quantity * row.unit_price.unwrap_or_default();
+                let discount = row.discount_percentage.unwrap_or_default();
+                let net = round_cents(gross - gross * discount / crate::money!(100));
+                let category = if reverse_charge {
+                    VatCategory::ReverseCharge
+                } else if intra_community {
+                    VatCategory::IntraCommunity
+                } else if row.is_vat_free.unwrap_or(false) {
+                    VatCategory::Exempt
+                } else {
+                    VatCategory::Standard
+                };
+                let percent = match category {
+                    VatCategory::Standard => row
+                        .vat_rate_id
+                        .as_ref()
+                        .and_then(|id| self.vat_rates.get(id))
+                        .copied()
+                        .unwrap_or(self.default_vat_rate),
+                    _ => Money::default(),
+                };
+                Line {
+                    row,
+                    quantity,
+                    net,
+                    category,
+                    percent,
+                }
+            })
+    }
+
+    fn write(&self, w: &mut Writer<Vec<u8>>) -> io::Result<()> {
+        let currency = self.currency();
+        let lines: Vec<Line<'_>> = self.lines().collect();
+
+        w.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+        w.create_element("Invoice")
+            .with_attribute((
+                "xmlns",
+                "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
+            ))
+            .with_attribute((
+                "xmlns:cac",
+                "urn:oasis:names:specification:ubl:schema:xsd:CommonAggregateComponents-2",
+            ))
+            .with_attribute((
+                "xmlns:cbc",
+                "urn:oasis:names:specification:ubl:schema:xsd:CommonBasicComponents-2",
+            ))
+            .write_inner_content(|w| {
+                text(w, "cbc:CustomizationID", CUSTOMIZATION_ID)?;
+                text(w, "cbc:ProfileID", PROFILE_ID)?;
+                text(
+                    w,
+                    "cbc:ID",
+                    self.invoice.invoice_number.as_deref().unwrap_or_default(),
+                )?;
+                if let Some(date) = self.invoice.invoice_date {
+                    text(w, "cbc:IssueDate", &date.to_string())?;
+                }
+                if let Some(date) = self.invoice.due_date {
+                    text(w, "cbc:DueDate", &date.to_string())?;
+                }
+                text(w, "cbc:InvoiceTypeCode", "380")?;
+                if let Some(remarks) = self.invoice.remarks.as_deref() {
+                    text(w, "cbc:Note", remarks)?;
+                }
+                text(w, "cbc:DocumentCurrencyCode", currency)?;
+                let buyer_reference = self
+                    .buyer_reference
+                    .as_deref()
+                    .or(self.buyer.customer_number.as_deref())
+                    .or(self.invoice.invoice_number.as_deref())
+                    .unwrap_or_default();
+                text(w, "cbc:BuyerReference", buyer_reference)?;
+
+                self.write_seller(w)?;
+                self.write_buyer(w)?;
+                if let Some(delivery) = self.invoice.delivery_date {
+                    w.create_element("cac:Delivery").write_inner_content(|w| {
+                        text(w, "cbc:ActualDeliveryDate", &delivery.to_string())
+                    })?;
+                }
+                if let Some(account) = &self.payee_account {
+                    w.create_element("cac:PaymentMeans")
+                        .write_inner_content(|w| {
+                            text(w, "cbc:PaymentMeansCode", "30")?;
+                            if let Some(ocr) = self.invoice.ocr_number.as_deref() {
+                                text(w, "cbc:PaymentID", ocr)?;
+                            }
+                            w.create_element("cac:PayeeFinancialAccount")
+                                .write_inner_content(|w| text(w, "cbc:ID", account))?;
+                            Ok(())
+                        })?;
+                }
+
+                let tax = self.write_tax_total(w, &lines)?;
+                let net: Money = lines.iter().map(|l| l.net).sum();
+                w.create_element("cac:LegalMonetaryTotal")
+                    .write_inner_content(|w| {
+                        amount(w, "cbc:LineExtensionAmount", net, currency)?;
+                        amount(w, "cbc:TaxExclusiveAmount", net, currency)?;
+                        amount(w, "cbc:TaxInclusiveAmount", net + tax, currency)?;
+                        amount(w, "cbc:PayableAmount", net + tax, currency)?;
+                        Ok(())
+                    })?;
+
+                for (index, line) in lines.iter().enumerate() {
+                    self.write_line(w, index + 1, line)?;
+                }
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    fn write_seller(&self, w: &mut Writer<Vec<u8>>) -> io::Result<()> {
+        let seller = self.seller;
+        let org_number = digits(seller.corporate_identity_number.as_deref());
+        let country = seller.country_code.as_deref().unwrap_or("SE");
+        w.create_element("cac:AccountingSupplierParty")
+            .write_inner_content(|w| {
+                w.create_element("cac:Party").write_inner_content(|w| {
+                    w.create_element("cbc:EndpointID")
+                        .with_attribute(("schemeID", SCHEME_SE_ORGNR))
+                        .write_text_content(BytesText::new(&org_number))?;
+                    postal_address(w, seller.address.as_ref(), country)?;
+                    if country == "SE" {
+                        // Swedish VAT numbers are the organisation number
+                        // with SE in front and 01 after.
+                        let vat_number = format!("SE{}01", org_number);
+                        w.create_element("cac:PartyTaxScheme")
+                            .write_inner_content(|w| {
+                                text(w, "cbc:CompanyID", &vat_number)?;
+                                tax_scheme(w)
+                            })?;
+                    }
+                    w.create_element("cac:PartyLegalEntity")
+                        .write_inner_content(|w| {
+                            text(
+                                w,
+                                "cbc:RegistrationName",
+                                seller.company_name.as_deref().unwrap_or_default(),
+                            )?;
+                            text(w, "cbc:CompanyID", &org_number)
+                        })?;
+                    if let Some(email) = seller.email.as_deref() {
+                        w.create_element("cac:Contact")
+                            .write_inner_content(|w| text(w, "cbc:ElectronicMail", email))?;
+                    }
+                    Ok(())
+                })?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    fn write_buyer(&self, w: &mut Writer<Vec<u8>>) -> io::Result<()> {
+        let buyer = self.buyer;
+        let org_number = digits(buyer.corporate_identity_number.as_deref());
+        let (scheme, endpoint) = match &self.buyer_endpoint {
+            Some((scheme, id)) => (scheme.as_str(), id.clone()),
+            None => (SCHEME_SE_ORGNR, org_number.clone()),
+        };
+        let address = buyer.invoice_address.as_ref();
+        let country = address
+            .and_then(|a| a.country_code.as_deref())
+            .unwrap_or("SE");
+        let vat_number = buyer
+            .vat_number
+            .as_deref()
+            .or(self.invoice.customer_vat_number.as_deref());
+        w.create_element("cac:AccountingCustomerParty")
+            .write_inner_content(|w| {
+                w.create_element("cac:Party").write_inner_content(|w| {
+                    w.create_element("cbc:EndpointID")
+                        .with_attribute(("schemeID", scheme))
+                        .write_text_content(BytesText::new(&endpoint))?;
+                    postal_address(w, address, country)?;
+                    if let Some(vat_number) = vat_number {
+                        w.create_element("cac:PartyTaxScheme")
+                            .write_inner_content(|w| {
+                                text(w, "cbc:CompanyID", vat_number)?;
+                                tax_scheme(w)
+                            })?;
+                    }
+                    w.create_element("cac:PartyLegalEntity")
+                        .write_inner_content(|w| {
+                            text(
+                                w,
+                                "cbc:RegistrationName",
+                                buyer.name.as_deref().unwrap_or_default(),
+                            )?;
+                            if !org_number.is_empty() {
+                                text(w, "cbc:CompanyID", &org_number)?;
+                            }
+                            Ok(())
+                        })?;
+                    Ok(())
+                })?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    /// Write the VAT breakdown and return the total VAT.
+    fn write_tax_total(&self, w: &mut Writer<Vec<u8>>, lines: &[Line<'_>]) -> io::Result<Money> {
+        let currency = self.currency();
+        let mut subtotals: BTreeMap<(VatCategory, String), (Money, Money)> = BTreeMap::new();
+        for line in lines {
+            let entry = subtotals
+                .entry((line.category, line.percent.to_string()))
+                .or_insert((Money::default(), line.percent));
+            entry.0 += line.net;
+        }
+        let subtotals: Vec<(VatCategory, Money, Money, Money)> = subtotals
+            .into_iter()
+            .map(|((category, _), (taxable, percent))| {
+                let tax = round_cents(taxable * percent / crate::money!(100));
+                (category, percent, taxable, tax)
+            })
+            .collect();
+        let total: Money = subtotals.iter().map(|(_, _, _, tax)| *tax).sum();
+
+        w.create_element("cac:TaxTotal").write_inner_content(|w| {
+            amount(w, "cbc:TaxAmount", total, currency)?;
+            for (category, percent, taxable, tax) in &subtotals {
+                w.create_element("cac:TaxSubtotal")
+                    .write_inner_content(|w| {
+                        amount(w, "cbc:TaxableAmount", *taxable, currency)?;
+                        amount(w, "cbc:TaxAmount", *tax, currency)?;
+                        tax_category(w, "cac:TaxCategory", *category, *percent, true)
+                    })?;
+            }
+            Ok(())
+        })?;
+        Ok(total)
+    }
+
+    fn write_line(
+        &self,
+        w: &mut Writer<Vec<u8>>,
+        number: usize,
+        line: &Line<'_>,
+    ) -> io::Result<()> {
+        let currency = self.currency();
+        let row = line.row;
+        w.create_element("cac:InvoiceLine")
+            .write_inner_content(|w| {
+                text(w, "cbc:ID", &number.to_string())?;
+                w.create_element("cbc:InvoicedQuantity")
+                    .with_attribute(("unitCode", "C62"))
+                    .write_text_content(BytesText::new(&line.quantity.to_string()))?;
+                amount(w, "cbc:LineExtensionAmount", line.net, currency)?;
+                w.create_element("cac:Item").write_inner_content(|w| {
+                    let name = row
+                        .text
+                        .as_deref()
+                        .or(row.article_id.as_ref().map(|id| id.as_str()))
+                        .unwrap_or("-");
+                    text(w, "cbc:Name", name)?;
+                    if let Some(article) = &row.article_id {
+                        w.create_element("cac:SellersItemIdentification")
+                            .write_inner_content(|w| text(w, "cbc:ID", article.as_str()))?;
+                    }
+                    tax_category(
+                        w,
+                        "cac:ClassifiedTaxCategory",
+                        line.category,
+                        line.percent,
+                        false,
+                    )
+                })?;
+                w.create_element("cac:Price").write_inner_content(|w| {
+                    let price = row.unit_price.unwrap_or_default();
+                    let discount = row.discount_percentage.unwrap_or_default();
+                    let net_price = price - price * discount / crate::money!(100);
+                    amount(w, "cbc:PriceAmount", net_price, currency)
+                })?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+}
+
+fn text(w: &mut Writer<Vec<u8>>, name: &str, value: &str) -> io::Result<()> {
+    w.create_element(name)
+        .write_text_content(BytesText::new(value))?;
+    Ok(())
+}
+
+fn amount(w: &mut Writer<Vec<u8>>, name: &str, value: Money, currency: &str) -> io::Result<()> {
+    w.create_element(name)
+        .with_attribute(("currencyID", currency))
+        .write_text_content(BytesText::new(&format!("{:.2}", value)))?;
+    Ok(())
+}
+
+fn tax_scheme(w: &mut Writer<Vec<u8>>) -> io::Result<()> {
+    w.create_element("cac:TaxScheme")
+        .write_inner_content(|w| text(w, "cbc:ID", "VAT"))?;
+    Ok(())
+}
+
+fn tax_category(
+    w: &mut Writer<Vec<u8>>,
+    element: &str,
+    category: VatCategory,
+    percent: Money,
+    with_reason: bool,
+) -> io::Result<()> {
+    w.create_element(element).write_inner_content(|w| {
+        text(w, "cbc:ID", category.code())?;
+        text(w, "cbc:Percent", &format!("{:.2}", percent))?;
+        if let Some(reason) = category.exemption_reason().filter(|_| with_reason) {
+            text(w, "cbc:TaxExemptionReason", reason)?;
+        }
+        tax_scheme(w)
+    })?;
+    Ok(())
+}
+
+fn postal_address(
+    w: &mut Writer<Vec<u8>>,
+    address: Option<&Address>,
+    country: &str,
+) -> io::Result<()> {
+    w.create_element("cac:PostalAddress")
+        .write_inner_content(|w| {
+            if let Some(address) = address {
+                if let Some(street) = address.address1.as_deref() {
+                    text(w, "cbc:StreetName", street)?;
+                }
+                if let Some(street) = address.address2.as_deref() {
+                    text(w, "cbc:AdditionalStreetName", street)?;
+                }
+                if let Some(city) = address.city.as_deref() {
+                    text(w, "cbc:CityName", city)?;
+                }
+                if let Some(postal_code) = address.postal_code.as_deref() {
+                    text(w, "cbc:PostalZone", postal_code)?;
+                }
+            }
+            w.create_element("cac:Country")
+                .write_inner_content(|w| text(w, "cbc:IdentificationCode", country))?;
+            Ok(())
+        })?;
+    Ok(())
+}
+
+/// The digits of an organisation number.
+fn digits(value: Option<&str>) -> String {
+    value
+        .unwrap_or_default()
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::money;
+    use chrono::NaiveDate;
+
+    fn company() -> CompanySettings {
+        CompanySettings {
+            company_name: Some("Konsult & Co AB".to_string()),
+            corporate_identity_number: Some("556677-8899".to_string()),
+            country_code: Some("SE".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn customer() -> Customer {
+        Customer {
+            customer_number: Some("1001".to_string()),
+            name: Some("Acme AB".to_string()),
+            corporate_identity_number: Some("559900-1122".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn invoice() -> Invoice {
+        Invoice {
+            invoice_number: Some("10042".to_string()),
+            invoice_date: NaiveDate::from_ymd_opt(2025, 3, 1),
+            due_date: NaiveDate::from_ymd_opt(2025, 3, 31),
+            ocr_number: Some("1004257".to_string()),
+            rows: vec![
+                InvoiceRow {
+                    text: Some("Konsulttimmar".to_string()),
+                    quantity: Some(money!(10)),
+                    unit_price: Some(money!(950)),
+                    discount_percentage: Some(money!(10)),
+                    ..Default::default()
+                },
+                InvoiceRow {
+                    text: Some("Böcker".to_string()),
+                    quantity: Some(money!(2)),
+                    unit_price: Some(money!(100)),
+                    vat_rate_id: Some("vat-6".to_string()),
+                    ..Default::default()
+                },
+                InvoiceRow {
+                    text: Some("Tack för ordern!".to_string()),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_to_xml_computes_totals_per_rate() {
+        let invoice = invoice();
+        let (company, customer) = (company(), customer());
+        let xml = PeppolInvoice::new(&invoice, &company, &customer)
+            .vat_rate("vat-6", money!(6))
+            .payee_account("5050-1055")
+            .to_xml()
+            .unwrap();
+
+        assert!(xml.contains("<cbc:ID>10042</cbc:ID>"));
+        assert!(xml.contains("<cbc:BuyerReference>1001</cbc:BuyerReference>"));
+        assert!(xml.contains(r#"<cbc:EndpointID schemeID="0007">5566778899</cbc:EndpointID>"#));
+        assert!(xml.contains("<cbc:CompanyID>SE556677889901</cbc:CompanyID>"));
+        assert!(xml.contains("<cbc:RegistrationName>Konsult &amp; Co AB</cbc:RegistrationName>"));
+        assert!(xml.contains("<cbc:PaymentID>1004257</cbc:PaymentID>"));
+        // 10 * 950 - 10% = 8550 at 25%, 200 at 6%
+        assert!(xml.contains(r#"<cbc:TaxAmount currencyID="SEK">2149.50</cbc:TaxAmount>"#));
+        assert!(xml.contains(
+            r#"<cbc:LineExtensionAmount currencyID="SEK">8750.00</cbc:LineExtensionAmount>"#
+        ));
+        assert!(xml.contains(r#"<cbc:PayableAmount currencyID="SEK">10899.50</cbc:PayableAmount>"#));
+        assert!(xml.contains(r#"<cbc:PriceAmount currencyID="SEK">855.00</cbc:PriceAmount>"#));
+        assert_eq!(xml.matches("<cac:InvoiceLine>").count(), 2);
+    }
+
+    #[test]
+    fn test_reverse_charge_lines_have_no_vat() {
+        let invoice = Invoice {
+            reverse_charge_on_construction_services: Some(true),
+            ..invoice()
+        };
+        let (company, customer) = (company(), customer());
+        let xml = PeppolInvoice::new(&invoice, &company, &customer)
+            .to_xml()
+            .unwrap();
+
+        assert!(xml.contains("<cbc:ID>AE</cbc:ID>"));
+        assert!(xml.contains("<cbc:TaxExemptionReason>Reverse charge</cbc:TaxExemptionReason>"));
+        assert!(xml.contains(r#"<cbc:TaxAmount currencyID="SEK">0.00</cbc:TaxAmount>"#));
+    }
+
+    #[test]
+    fn test_to_xml_lists_missing_fields() {
+        let invoice = Invoice::default();
+        let customer = Customer::default();
+        let result = PeppolInvoice::new(&invoice, &company(), &customer).to_xml();
+
+        let Err(Error::Validation(errors)) = result else {
+            panic!("expected validation errors");
+        };
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "InvoiceNumber",
+                "InvoiceDate",
+                "Rows",
+                "Buyer.Name",
+                "Buyer.CorporateIdentityNumber"
+            ]
+        );
+    }
+}
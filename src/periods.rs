@@ -0,0 +1,237 @@
+//! Fiscal years, accounting periods and period locks.
+//!
+//! The API rejects anything booked in a locked fiscal year or on or before the
+//! company's lock date. [`PeriodLocks`] loads both once so dates can be checked
+//! before a request is sent.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::periods::PeriodLocks;
+//! use spiris::{AccessToken, Client};
+//! use chrono::NaiveDate;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+//! let locks = PeriodLocks::load(&client).await?;
+//!
+//! for fiscal_year in &locks.fiscal_years {
+//!     for period in locks.periods(fiscal_year) {
+//!         println!("{} - {} locked: {}", period.start, period.end, period.is_locked);
+//!     }
+//! }
+//!
+//! let payment_date = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+//! locks.check(payment_date)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::endpoints::collect_pages;
+use crate::error::{Error, Result};
+use crate::types::FiscalYear;
+use chrono::{Datelike, Months, NaiveDate};
+
+/// A monthly accounting period within a fiscal year.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Period {
+    /// First day of the period.
+    pub start: NaiveDate,
+    /// Last day of the period.
+    pub end: NaiveDate,
+    /// Whether the period is locked.
+    pub is_locked: bool,
+}
+
+/// Lock status of the company's fiscal years and periods.
+#[derive(Debug, Clone, Default)]
+pub struct PeriodLocks {
+    /// Fiscal years ordered by start date.
+    pub fiscal_years: Vec<FiscalYear>,
+    /// Bookkeeping is locked up to and including this date.
+    pub locked_through: Option<NaiveDate>,
+}
+
+impl PeriodLocks {
+    /// Create from fiscal years and the company lock date.
+    pub fn new(mut fiscal_years: Vec<FiscalYear>, locked_through: Option<NaiveDate>) -> Self {
+        fiscal_years.sort_by_key(|year| year.start_date);
+        Self {
+            fiscal_years,
+            locked_through,
+        }
+    }
+
+    /// Load the fiscal years and the lock date from the company settings.
+    pub async fn load(client: &Client) -> Result<Self> {
+        let fiscal_years =
+            collect_pages(|p| async move { client.fiscal_years().list(Some(p)).await }).await?;
+        let settings = client.company_settings().get().await?;
+        Ok(Self::new(
+            fiscal_years,
+            settings.locked_through_date.map(|d| d.date_naive()),
+        ))
+    }
+
+    /// The fiscal year containing `date`.
+    pub fn fiscal_year(&self, date: NaiveDate) -> Option<&FiscalYear> {
+        self.fiscal_years
+            .iter()
+            .find(|year| match (year.start_date, year.end_date) {
+                (Some(start), Some(end)) => (start.date_naive()..=end.date_naive()).contains(&date),
+                _ => false,
+            })
+    }
+
+    /// Whether `date` falls in a locked period.
+    pub fn is_locked(&self, date: NaiveDate) -> bool {
+        self.locked_through.is_some_and(|through| date <= through)
+            || self
+                .fiscal_year(date)
+                .is_some_and(|year| year.is_locked == Some(true))
+    }
+
+    /// Check that something dated `date` can be booked.
+    ///
+    /// Fails with [`Error::InvalidRequest`] if the date is locked or outside
+    /// every fiscal year.
+    pub fn check(&self, date: NaiveDate) -> Result<()> {
+        let Some(year) = self.fiscal_year(date) else {
+            return Err(Error::InvalidRequest(format!(
+                "{} is not within any fiscal year",
+                date
+            )));
+        };
+        if year.is_locked == Some(true) {
+            return Err(Error::InvalidRequest(format!(
+                "{} is in a locked fiscal year",
+                date
+            )));
+        }
+        if let Some(through) = self.locked_through.filter(|through| date <= *through) {
+            return Err(Error::InvalidRequest(format!(
+                "{} is in a locked period (locked through {})",
+                date, through
+            )));
+        }
+        Ok(())
+    }
+
+    /// Monthly periods of a fiscal year with their lock status.
+    ///
+    /// A fiscal year that does not start on the first of a month gets a
+    /// shorter first period.
+    pub fn periods(&self, fiscal_year: &FiscalYear) -> Vec<Period> {
+        let (Some(start), Some(end)) = (fiscal_year.start_date, fiscal_year.end_date) else {
+            return Vec::new();
+        };
+        let (start, end) = (start.date_naive(), end.date_naive());
+        let year_locked = fiscal_year.is_locked == Some(true);
+
+        let mut periods = Vec::new();
+        let mut period_start = start;
+        while period_start <= end {
+            let next_month =
+                (period_start.with_day(1).unwrap() + Months::new(1)).min(end.succ_opt().unwrap());
+            let period_end = next_month.pred_opt().unwrap();
+            periods.push(Period {
+                start: period_start,
+                end: period_end,
+                is_locked: year_locked
+                    || self
+                        .locked_through
+                        .is_some_and(|through| period_end <= through),
+            });
+            period_start = next_month;
+        }
+        periods
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn fiscal_year(
+        id: &str,
+        start: (i32, u32, u32),
+        end: (i32, u32, u32),
+        locked: bool,
+    ) -> FiscalYear {
+        FiscalYear {
+            id: Some(id.to_string()),
+            start_date: Some(
+                Utc.with_ymd_and_hms(start.0, start.1, start.2, 0, 0, 0)
+                    .unwrap(),
+            ),
+            end_date: Some(Utc.with_ymd_and_hms(end.0, end.1, end.2, 0, 0, 0).unwrap()),
+            is_locked: Some(locked),
+            ..Default::default()
+        }
+    }
+
+    fn locks() -> PeriodLocks {
+        PeriodLocks::new(
+            vec![
+                fiscal_year("2025", (2025, 1, 1), (2025, 12, 31), false),
+                fiscal_year("2024", (2024, 1, 1), (2024, 12, 31), true),
+            ],
+            Some(date(2025, 2, 28)),
+        )
+    }
+
+    #[test]
+    fn test_is_locked() {
+        let locks = locks();
+
+        assert_eq!(locks.fiscal_years[0].id.as_deref(), Some("2024"));
+        assert!(locks.is_locked(date(2024, 6, 15)));
+        assert!(locks.is_locked(date(2025, 2, 28)));
+        assert!(!locks.is_locked(date(2025, 3, 1)));
+    }
+
+    #[test]
+    fn test_check() {
+        let locks = locks();
+
+        assert!(locks.check(date(2025, 3, 1)).is_ok());
+
+        let err = locks.check(date(2025, 2, 10)).unwrap_err().to_string();
+        assert!(err.contains("locked through 2025-02-28"), "{}", err);
+        let err = locks.check(date(2024, 6, 15)).unwrap_err().to_string();
+        assert!(err.contains("locked fiscal year"), "{}", err);
+        let err = locks.check(date(2026, 1, 5)).unwrap_err().to_string();
+        assert!(err.contains("not within any fiscal year"), "{}", err);
+    }
+
+    #[test]
+    fn test_periods() {
+        let locks = locks();
+        let periods = locks.periods(&locks.fiscal_years[1]);
+
+        assert_eq!(periods.len(), 12);
+        assert_eq!(periods[1].start, date(2025, 2, 1));
+        assert_eq!(periods[1].end, date(2025, 2, 28));
+        assert!(periods[1].is_locked);
+        assert!(!periods[2].is_locked);
+        assert_eq!(periods[11].end, date(2025, 12, 31));
+    }
+
+    #[test]
+    fn test_periods_of_broken_fiscal_year() {
+        let year = fiscal_year("2024/25", (2024, 5, 15), (2025, 4, 30), false);
+        let periods = PeriodLocks::default().periods(&year);
+
+        assert_eq!(periods.len(), 12);
+        assert_eq!(periods[0].start, date(2024, 5, 15));
+        assert_eq!(periods[0].end, date(2024, 5, 31));
+        assert_eq!(periods[11].start, date(2025, 4, 1));
+        assert!(periods.iter().all(|p| !p.is_locked));
+    }
+}
@@ -22,6 +22,7 @@
 //! assert_eq!(filter.to_string(), "Name eq 'O''Brien & Co'");
 //! ```
 
+use crate::types::{ArticleId, CustomerId, InvoiceId, InvoiceStatus, PaymentStatus, SentStatus};
 use std::fmt;
 
 /// A filter expression for OData queries.
@@ -351,6 +352,42 @@ impl FilterValue for &String {
     }
 }
 
+impl FilterValue for &CustomerId {
+    fn to_odata(&self) -> String {
+        self.as_str().to_odata()
+    }
+}
+
+impl FilterValue for &InvoiceId {
+    fn to_odata(&self) -> String {
+        self.as_str().to_odata()
+    }
+}
+
+impl FilterValue for &ArticleId {
+    fn to_odata(&self) -> String {
+        self.as_str().to_odata()
+    }
+}
+
+impl FilterValue for &InvoiceStatus {
+    fn to_odata(&self) -> String {
+        self.as_str().to_odata()
+    }
+}
+
+impl FilterValue for &SentStatus {
+    fn to_odata(&self) -> String {
+        self.as_str().to_odata()
+    }
+}
+
+impl FilterValue for &PaymentStatus {
+    fn to_odata(&self) -> String {
+        self.as_str().to_odata()
+    }
+}
+
 impl FilterValue for i32 {
     fn to_odata(&self) -> String {
         self.to_string()
@@ -397,10 +434,31 @@ impl FilterValue for f64 {
     }
 }
 
-impl FilterValue for chrono::DateTime<chrono::Utc> {
+#[cfg(feature = "decimal")]
+impl FilterValue for rust_decimal::Decimal {
+    fn to_odata(&self) -> String {
+        self.normalize().to_string()
+    }
+}
+
+/// Times in other zones are converted to UTC, since the `Z` suffix says the
+/// value is UTC.
+impl<Tz: chrono::TimeZone> FilterValue for chrono::DateTime<Tz> {
     fn to_odata(&self) -> String {
         // OData date-time format
-        self.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+        self.with_timezone(&chrono::Utc)
+            .format("%Y-%m-%dT%H:%M:%SZ")
+            .to_string()
+    }
+}
+
+/// `None` compares with `null`.
+impl<T: FilterValue> FilterValue for Option<T> {
+    fn to_odata(&self) -> String {
+        match self {
+            Some(value) => value.to_odata(),
+            None => "null".to_string(),
+        }
     }
 }
 
@@ -410,6 +468,23 @@ impl FilterValue for chrono::NaiveDate {
     }
 }
 
+/// Sort direction for [`QueryParams::order_by`](crate::QueryParams::order_by).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Direction::Ascending => "asc",
+            Direction::Descending => "desc",
+        })
+    }
+}
+
 /// Escape a string for use in OData expressions.
 /// Single quotes are escaped by doubling them.
 fn escape_string(s: &str) -> String {
@@ -554,6 +629,44 @@ mod tests {
         assert_eq!(filter.to_string(), "Amount gt 1000.5");
     }
 
+    #[test]
+    fn test_dates() {
+        use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
+
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let filter = Filter::field("InvoiceDate").ge(date);
+        assert_eq!(filter.to_string(), "InvoiceDate ge 2025-03-01");
+
+        let utc = Utc.with_ymd_and_hms(2025, 3, 1, 8, 30, 0).unwrap();
+        let filter = Filter::field("ModifiedUtc").gt(utc);
+        assert_eq!(filter.to_string(), "ModifiedUtc gt 2025-03-01T08:30:00Z");
+
+        // 09:30 in Stockholm (UTC+1) is 08:30 UTC
+        let stockholm = FixedOffset::east_opt(3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 3, 1, 9, 30, 0)
+            .unwrap();
+        let filter = Filter::field("ModifiedUtc").gt(stockholm);
+        assert_eq!(filter.to_string(), "ModifiedUtc gt 2025-03-01T08:30:00Z");
+    }
+
+    #[test]
+    fn test_option_value() {
+        let filter = Filter::field("ProjectId").eq(None::<&str>);
+        assert_eq!(filter.to_string(), "ProjectId eq null");
+
+        let filter = Filter::field("ProjectId").eq(Some("P'1"));
+        assert_eq!(filter.to_string(), "ProjectId eq 'P''1'");
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_value() {
+        let amount: rust_decimal::Decimal = "1500.50".parse().unwrap();
+        let filter = Filter::field("TotalAmount").ge(amount);
+        assert_eq!(filter.to_string(), "TotalAmount ge 1500.5");
+    }
+
     #[test]
     fn test_into_string() {
         let filter = Filter::field("IsActive").eq(true);
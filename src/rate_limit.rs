@@ -3,6 +3,10 @@
 //! This module provides rate limiting to prevent exceeding API quotas.
 //! The Spiris API allows 600 requests per minute per endpoint.
 //!
+//! The limiter is a token bucket shared by all clones of a
+//! [`Client`](crate::Client), and every attempt of a retried request takes a
+//! token, so concurrent tasks of a bulk job stay under the limit together.
+//!
 //! # Feature Flag
 //!
 //! This module is only available when the `rate-limit` feature is enabled:
@@ -20,7 +24,7 @@
 //!
 //! let token = AccessToken::new("token".to_string(), 3600, None);
 //! let config = ClientConfig::new()
-//!     .rate_limit(RateLimitConfig::default());
+//!     .rate_limit_config(RateLimitConfig::new(600).per_second(20));
 //! let client = Client::with_config(token, config);
 //!
 //! // All requests will now be rate-limited
@@ -42,6 +46,9 @@ pub struct RateLimitConfig {
 
     /// Allow burst of requests up to this limit.
     pub burst_size: u32,
+
+    /// Maximum requests per second, on top of the per-minute limit.
+    pub requests_per_second: Option<u32>,
 }
 
 impl Default for RateLimitConfig {
@@ -49,6 +56,7 @@ impl Default for RateLimitConfig {
         Self {
             requests_per_minute: crate::client::RATE_LIMIT_PER_MINUTE,
             burst_size: 10,
+            requests_per_second: None,
         }
     }
 }
@@ -59,6 +67,7 @@ impl RateLimitConfig {
         Self {
             requests_per_minute,
             burst_size: 10,
+            requests_per_second: None,
         }
     }
 
@@ -67,11 +76,18 @@ impl RateLimitConfig {
         self.burst_size = burst;
         self
     }
+
+    /// Also limit requests per second, to spread a minute's quota out.
+    pub fn per_second(mut self, requests_per_second: u32) -> Self {
+        self.requests_per_second = Some(requests_per_second);
+        self
+    }
 }
 
 /// Internal rate limiter using the governor crate.
 pub(crate) struct ApiRateLimiter {
     limiter: Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>,
+    per_second: Option<Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>>,
 }
 
 impl ApiRateLimiter {
@@ -82,8 +98,14 @@ impl ApiRateLimiter {
         )
         .allow_burst(NonZeroU32::new(config.burst_size).unwrap_or(NonZeroU32::new(1).unwrap()));
 
+        let per_second = config
+            .requests_per_second
+            .and_then(NonZeroU32::new)
+            .map(|n| Arc::new(RateLimiter::direct(Quota::per_second(n))));
+
         Self {
             limiter: Arc::new(RateLimiter::direct(quota)),
+            per_second,
         }
     }
 
@@ -91,6 +113,9 @@ impl ApiRateLimiter {
     ///
     /// This method blocks (asynchronously) until the rate limit allows a new request.
     pub async fn acquire(&self) {
+        if let Some(per_second) = &self.per_second {
+            per_second.until_ready().await;
+        }
         self.limiter.until_ready().await;
     }
 
@@ -99,6 +124,11 @@ impl ApiRateLimiter {
     /// Returns `true` if a request can be made immediately, `false` otherwise.
     #[allow(dead_code)]
     pub fn try_acquire(&self) -> bool {
+        if let Some(per_second) = &self.per_second {
+            if per_second.check().is_err() {
+                return false;
+            }
+        }
         self.limiter.check().is_ok()
     }
 }
@@ -107,6 +137,7 @@ impl Clone for ApiRateLimiter {
     fn clone(&self) -> Self {
         Self {
             limiter: Arc::clone(&self.limiter),
+            per_second: self.per_second.clone(),
         }
     }
 }
@@ -140,6 +171,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_per_second_limit_applies_within_burst() {
+        let config = RateLimitConfig::new(600).burst_size(10).per_second(3);
+        assert_eq!(config.requests_per_second, Some(3));
+        let limiter = ApiRateLimiter::new(&config);
+
+        for _ in 0..3 {
+            assert!(limiter.try_acquire());
+        }
+        assert!(!limiter.try_acquire());
+    }
+
     #[tokio::test]
     async fn test_rate_limiter_acquire() {
         let config = RateLimitConfig::new(600).burst_size(2);
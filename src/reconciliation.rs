@@ -0,0 +1,474 @@
+//! Matching bank transactions against open invoices.
+//!
+//! Incoming payments are matched against open customer invoices and outgoing
+//! payments against open supplier invoices. Each suggestion carries a
+//! confidence between 0 and 1 built from the evidence found:
+//!
+//! | Evidence                                         | Weight |
+//! |--------------------------------------------------|--------|
+//! | Reference equals the OCR or invoice number       | 0.6    |
+//! | Invoice number mentioned in the message          | 0.2    |
+//! | Amount equals the remaining amount               | 0.3    |
+//!
+//! An amount match alone is shared between all invoices with the same
+//! remaining amount, so ambiguous amounts produce no suggestion.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::{bank_files, reconciliation};
+//! use spiris::{AccessToken, Client};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+//! let transactions = bank_files::parse(&std::fs::read_to_string("camt054.xml")?)?;
+//! let items = reconciliation::open_items(&client).await?;
+//!
+//! for suggestion in reconciliation::suggest_matches(&transactions, &items) {
+//!     if suggestion.confidence >= 0.9 {
+//!         let transaction = &transactions[suggestion.transaction];
+//!         let item = &items[suggestion.item];
+//!         reconciliation::register_match(&client, transaction, item).await?;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::bank_files::BankTransaction;
+use crate::client::Client;
+use crate::endpoints::collect_pages;
+use crate::error::{Error, Result};
+use crate::types::{
+    Invoice, InvoiceId, InvoicePayment, Money, SupplierInvoice, SupplierInvoicePayment,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+
+/// Suggestions below this confidence are dropped.
+pub const MIN_CONFIDENCE: f64 = 0.25;
+
+const REFERENCE_WEIGHT: f64 = 0.6;
+const MESSAGE_WEIGHT: f64 = 0.2;
+const AMOUNT_WEIGHT: f64 = 0.3;
+
+/// Kind of open ledger item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenItemKind {
+    /// Unpaid customer invoice, settled by an incoming payment.
+    CustomerInvoice,
+    /// Unpaid supplier invoice, settled by an outgoing payment.
+    SupplierInvoice,
+}
+
+/// An invoice with a remaining balance.
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenItem {
+    /// Customer or supplier invoice.
+    pub kind: OpenItemKind,
+    /// Invoice ID.
+    pub invoice_id: String,
+    /// Invoice number.
+    pub invoice_number: Option<String>,
+    /// OCR payment reference.
+    pub ocr_number: Option<String>,
+    /// Customer or supplier ID.
+    pub counterparty_id: Option<String>,
+    /// Due date of the invoice.
+    pub due_date: Option<NaiveDate>,
+    /// Remaining amount to be paid.
+    #[serde(with = "crate::types::money_serde")]
+    pub remaining: Money,
+    /// Currency code of the invoice.
+    pub currency_code: Option<String>,
+}
+
+impl OpenItem {
+    /// Open item for a customer invoice, if it has a remaining balance.
+    pub fn from_invoice(invoice: &Invoice) -> Option<Self> {
+        let remaining = invoice.remaining_amount?;
+        if remaining <= Money::default() {
+            return None;
+        }
+        Some(Self {
+            kind: OpenItemKind::CustomerInvoice,
+            invoice_id: invoice.id.clone()?.into_string(),
+            invoice_number: invoice.invoice_number.clone(),
+            ocr_number: invoice.ocr_number.clone(),
+            counterparty_id: invoice.customer_id.clone().map(String::from),
+            due_date: invoice.due_date,
+            remaining,
+            currency_code: invoice.currency_code.clone(),
+        })
+    }
+
+    /// Open item for a supplier invoice, if it is unpaid.
+    ///
+    /// Uses the total for unpaid invoices that do not report a remaining
+    /// amount.
+    pub fn from_supplier_invoice(invoice: &SupplierInvoice) -> Option<Self> {
+        let remaining = match invoice.remaining_amount {
+            Some(remaining) => remaining,
+            None if invoice.is_paid != Some(true) => invoice.total_amount_including_vat?,
+            None => return None,
+        };
+        if remaining <= Money::default() {
+            return None;
+        }
+        Some(Self {
+            kind: OpenItemKind::SupplierInvoice,
+            invoice_id: invoice.id.clone()?,
+            invoice_number: invoice.invoice_number.clone(),
+            ocr_number: invoice.ocr_number.clone(),
+            counterparty_id: invoice.supplier_id.clone(),
+            due_date: invoice.due_date,
+            remaining,
+            currency_code: invoice.currency_code.clone(),
+        })
+    }
+
+    fn settled_by(&self, transaction: &BankTransaction) -> bool {
+        let direction = match self.kind {
+            OpenItemKind::CustomerInvoice => transaction.is_incoming(),
+            OpenItemKind::SupplierInvoice => transaction.amount < Money::default(),
+        };
+        let currency = self
+            .currency_code
+            .as_deref()
+            .is_none_or(|code| code.eq_ignore_ascii_case(&transaction.currency));
+        direction && currency
+    }
+}
+
+/// Evidence behind a match suggestion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchReason {
+    /// The payment reference equals the OCR or invoice number.
+    Reference,
+    /// The invoice number appears in the payment message.
+    InvoiceNumberInMessage,
+    /// The amount equals the remaining amount.
+    Amount,
+}
+
+/// A suggested pairing of a bank transaction and an open item.
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchSuggestion {
+    /// Index into the transactions.
+    pub transaction: usize,
+    /// Index into the open items.
+    pub item: usize,
+    /// Confidence between 0 and 1.
+    pub confidence: f64,
+    /// Evidence found for the match.
+    pub reasons: Vec<MatchReason>,
+}
+
+/// Suggest matches between bank transactions and open items.
+///
+/// Every transaction and every item appears in at most one suggestion; the
+/// most confident pairs win. Suggestions are ordered by transaction index.
+pub fn suggest_matches(
+    transactions: &[BankTransaction],
+    items: &[OpenItem],
+) -> Vec<MatchSuggestion> {
+    let mut candidates = Vec::new();
+    for (t, transaction) in transactions.iter().enumerate() {
+        let amount = abs(transaction.amount);
+        let same_amount = items
+            .iter()
+            .filter(|item| item.settled_by(transaction) && item.remaining == amount)
+            .count();
+
+        for (i, item) in items.iter().enumerate() {
+            if !item.settled_by(transaction) {
+                continue;
+            }
+
+            let mut reasons = Vec::new();
+            let mut confidence = 0.0;
+            if reference_matches(transaction, item) {
+                reasons.push(MatchReason::Reference);
+                confidence += REFERENCE_WEIGHT;
+            } else if message_mentions(transaction, item) {
+                reasons.push(MatchReason::InvoiceNumberInMessage);
+                confidence += MESSAGE_WEIGHT;
+            }
+            if item.remaining == amount {
+                reasons.push(MatchReason::Amount);
+                confidence += if reasons.len() == 1 {
+                    AMOUNT_WEIGHT / same_amount as f64
+                } else {
+                    AMOUNT_WEIGHT
+                };
+            }
+
+            let confidence = f64::min(confidence, 1.0);
+            if confidence >= MIN_CONFIDENCE {
+                candidates.push(MatchSuggestion {
+                    transaction: t,
+                    item: i,
+                    confidence,
+                    reasons,
+                });
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    let mut used_transactions = vec![false; transactions.len()];
+    let mut used_items = vec![false; items.len()];
+    let mut suggestions: Vec<MatchSuggestion> = candidates
+        .into_iter()
+        .filter(|c| {
+            if used_transactions[c.transaction] || used_items[c.item] {
+                return false;
+            }
+            used_transactions[c.transaction] = true;
+            used_items[c.item] = true;
+            true
+        })
+        .collect();
+    suggestions.sort_by_key(|s| s.transaction);
+    suggestions
+}
+
+fn normalize(reference: &str) -> String {
+    reference
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .trim_start_matches('0')
+        .to_ascii_uppercase()
+}
+
+fn reference_matches(transaction: &BankTransaction, item: &OpenItem) -> bool {
+    let Some(reference) = transaction.reference.as_deref().map(normalize) else {
+        return false;
+    };
+    !reference.is_empty()
+        && [&item.ocr_number, &item.invoice_number]
+            .into_iter()
+            .flatten()
+            .any(|candidate| normalize(candidate) == reference)
+}
+
+fn message_mentions(transaction: &BankTransaction, item: &OpenItem) -> bool {
+    let (Some(message), Some(number)) = (&transaction.message, &item.invoice_number) else {
+        return false;
+    };
+    message
+        .split(|c: char| !c.is_alphanumeric() && c != '-')
+        .any(|word| word == number)
+}
+
+fn abs(amount: Money) -> Money {
+    if amount < Money::default() {
+        -amount
+    } else {
+        amount
+    }
+}
+
+/// Load all open customer and supplier invoices.
+pub async fn open_items(client: &Client) -> Result<Vec<OpenItem>> {
+    let invoices = collect_pages(|p| async move { client.invoices().list(Some(p)).await }).await?;
+    let supplier_invoices =
+        collect_pages(|p| async move { client.supplier_invoices().list(Some(p)).await }).await?;
+
+    Ok(invoices
+        .iter()
+        .filter_map(OpenItem::from_invoice)
+        .chain(
+            supplier_invoices
+                .iter()
+                .filter_map(OpenItem::from_supplier_invoice),
+        )
+        .collect())
+}
+
+/// Register the transaction as a payment on the open item.
+///
+/// The payment is dated on the booking date and carries the bank's
+/// reference. Transactions exceeding the remaining amount are rejected.
+pub async fn register_match(
+    client: &Client,
+    transaction: &BankTransaction,
+    item: &OpenItem,
+) -> Result<()> {
+    if !item.settled_by(transaction) {
+        return Err(Error::InvalidRequest(format!(
+            "Transaction of {} {} cannot settle invoice {}",
+            transaction.amount, transaction.currency, item.invoice_id
+        )));
+    }
+    let amount = abs(transaction.amount);
+    if amount > item.remaining {
+        return Err(Error::InvalidRequest(format!(
+            "Payment amount {} exceeds remaining amount {}",
+            amount, item.remaining
+        )));
+    }
+
+    let payment_date = booking_time(transaction.booking_date);
+    let reference = transaction
+        .bank_reference
+        .clone()
+        .or_else(|| transaction.reference.clone());
+
+    match item.kind {
+        OpenItemKind::CustomerInvoice => {
+            let payment = InvoicePayment {
+                amount: Some(amount),
+                payment_date: Some(payment_date),
+                payment_reference_number: reference,
+                ..Default::default()
+            };
+            client
+                .invoices()
+                .register_payment(&InvoiceId::new(item.invoice_id.as_str()), &payment)
+                .await
+        }
+        OpenItemKind::SupplierInvoice => {
+            let mut payment = SupplierInvoicePayment::new(amount, payment_date);
+            payment.supplier_invoice_id = Some(item.invoice_id.clone());
+            payment.payment_reference_number = reference;
+            client
+                .supplier_invoice_payments()
+                .register(&item.invoice_id, &payment)
+                .await?;
+            Ok(())
+        }
+    }
+}
+
+fn booking_time(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(chrono::NaiveTime::MIN).and_utc()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn money(value: &str) -> Money {
+        value.parse().unwrap()
+    }
+
+    fn transaction(
+        amount: &str,
+        reference: Option<&str>,
+        message: Option<&str>,
+    ) -> BankTransaction {
+        BankTransaction {
+            booking_date: NaiveDate::from_ymd_opt(2025, 3, 14).unwrap(),
+            amount: money(amount),
+            currency: "SEK".to_string(),
+            reference: reference.map(String::from),
+            message: message.map(String::from),
+            counterparty: None,
+            bank_reference: None,
+        }
+    }
+
+    fn item(kind: OpenItemKind, number: &str, ocr: Option<&str>, remaining: &str) -> OpenItem {
+        OpenItem {
+            kind,
+            invoice_id: format!("id-{}", number),
+            invoice_number: Some(number.to_string()),
+            ocr_number: ocr.map(String::from),
+            counterparty_id: None,
+            due_date: None,
+            remaining: money(remaining),
+            currency_code: Some("SEK".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_reference_and_amount_match() {
+        let transactions = vec![transaction("1250", Some("0010014"), None)];
+        let items = vec![
+            item(OpenItemKind::CustomerInvoice, "1001", Some("10014"), "1250"),
+            item(OpenItemKind::CustomerInvoice, "1002", None, "1250"),
+        ];
+
+        let suggestions = suggest_matches(&transactions, &items);
+
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].item, 0);
+        assert!((suggestions[0].confidence - 0.9).abs() < 1e-9);
+        assert_eq!(
+            suggestions[0].reasons,
+            [MatchReason::Reference, MatchReason::Amount]
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_amount_is_not_suggested() {
+        let transactions = vec![transaction("500", None, None)];
+        let items = vec![
+            item(OpenItemKind::CustomerInvoice, "1001", None, "500"),
+            item(OpenItemKind::CustomerInvoice, "1002", None, "500"),
+        ];
+        assert!(suggest_matches(&transactions, &items).is_empty());
+
+        let suggestions = suggest_matches(&transactions, &items[..1]);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].reasons, [MatchReason::Amount]);
+    }
+
+    #[test]
+    fn test_direction_and_currency_must_match() {
+        let items = vec![
+            item(OpenItemKind::SupplierInvoice, "F-77", None, "320.50"),
+            item(OpenItemKind::CustomerInvoice, "1003", None, "320.50"),
+        ];
+
+        let outgoing = vec![transaction("-320.50", None, Some("Invoice F-77"))];
+        let suggestions = suggest_matches(&outgoing, &items);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].item, 0);
+        assert_eq!(
+            suggestions[0].reasons,
+            [MatchReason::InvoiceNumberInMessage, MatchReason::Amount]
+        );
+
+        let mut eur = transaction("320.50", None, None);
+        eur.currency = "EUR".to_string();
+        assert!(suggest_matches(&[eur], &items).is_empty());
+    }
+
+    #[test]
+    fn test_items_are_used_once() {
+        let transactions = vec![
+            transaction("100", Some("1001"), None),
+            transaction("100", Some("1001"), None),
+        ];
+        let items = vec![item(OpenItemKind::CustomerInvoice, "1001", None, "100")];
+
+        let suggestions = suggest_matches(&transactions, &items);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].transaction, 0);
+    }
+
+    #[test]
+    fn test_open_items_skip_settled_invoices() {
+        let paid = Invoice {
+            id: Some("inv-1".into()),
+            remaining_amount: Some(Money::default()),
+            ..Default::default()
+        };
+        assert!(OpenItem::from_invoice(&paid).is_none());
+
+        let unpaid = SupplierInvoice {
+            id: Some("si-1".to_string()),
+            total_amount_including_vat: Some(money("800")),
+            is_paid: Some(false),
+            ..Default::default()
+        };
+        let item = OpenItem::from_supplier_invoice(&unpaid).unwrap();
+        assert_eq!(item.remaining, money("800"));
+        assert_eq!(item.kind, OpenItemKind::SupplierInvoice);
+    }
+}
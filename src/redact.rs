@@ -0,0 +1,232 @@
+//! Masking personal data in logged request and response bodies.
+//!
+//! The client runs request bodies and error responses through the
+//! [`Redactor`] in [`ClientConfig::redactor`](crate::ClientConfig::redactor)
+//! before handing them to tracing or middleware, so body logging can stay
+//! enabled in production. Errors returned to the caller are not redacted.
+//!
+//! Values of the configured JSON fields are replaced wherever the field
+//! appears, also in JSON embedded in an error message. Fields are matched by
+//! name, case-insensitively; personal data in free text is not detected.
+//!
+//! # Example
+//!
+//! ```
+//! use spiris::redact::Redactor;
+//!
+//! let redactor = Redactor::default().field("YourReference");
+//! let body = r#"{"Name": "Anna", "EmailAddress": "anna@example.com", "YourReference": "Anna"}"#;
+//!
+//! assert_eq!(
+//!     redactor.redact(body),
+//!     r#"{"Name": "Anna", "EmailAddress": "***", "YourReference": "***"}"#
+//! );
+//! ```
+
+/// Fields masked by [`Redactor::default`]: personal and corporate identity
+/// numbers, contact details and bank details.
+pub const DEFAULT_REDACTED_FIELDS: &[&str] = &[
+    "CorporateIdentityNumber",
+    "PersonalIdentityNumber",
+    "Email",
+    "EmailAddress",
+    "Phone",
+    "MobilePhone",
+    "BankAccountNumber",
+    "BankGiroNumber",
+    "PlusGiroNumber",
+    "Iban",
+    "Bic",
+];
+
+/// Masks the values of configured fields in JSON text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redactor {
+    fields: Vec<String>,
+    mask: String,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self {
+            fields: Vec::new(),
+            mask: "***".to_string(),
+        }
+        .fields(DEFAULT_REDACTED_FIELDS.iter().copied())
+    }
+}
+
+impl Redactor {
+    /// A redactor that masks nothing.
+    pub fn none() -> Self {
+        Self {
+            fields: Vec::new(),
+            ..Self::default()
+        }
+    }
+
+    /// Also mask `field`.
+    pub fn field(mut self, field: impl Into<String>) -> Self {
+        let field = field.into().to_ascii_lowercase();
+        if !self.fields.contains(&field) {
+            self.fields.push(field);
+        }
+        self
+    }
+
+    /// Also mask each of `fields`.
+    pub fn fields<I, S>(self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        fields.into_iter().fold(self, Self::field)
+    }
+
+    /// Set the replacement for masked values (default `***`).
+    pub fn mask(mut self, mask: impl Into<String>) -> Self {
+        self.mask = mask.into();
+        self
+    }
+
+    /// Whether values of `field` are masked.
+    pub fn is_redacted(&self, field: &str) -> bool {
+        self.fields.iter().any(|f| f.eq_ignore_ascii_case(field))
+    }
+
+    /// Mask the values of configured fields in `text`.
+    ///
+    /// String, number and boolean values are replaced by the mask as a JSON
+    /// string, so valid JSON stays valid. `null` values are kept. For object
+    /// and array values, only configured fields inside them are masked.
+    pub fn redact(&self, text: &str) -> String {
+        if self.fields.is_empty() {
+            return text.to_string();
+        }
+
+        let bytes = text.as_bytes();
+        let mut out = String::with_capacity(text.len());
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if bytes[i] != b'"' {
+                let next = text[i..].find('"').map_or(bytes.len(), |n| i + n);
+                out.push_str(&text[i..next]);
+                i = next;
+                continue;
+            }
+
+            let Some(end) = string_end(bytes, i) else {
+                out.push_str(&text[i..]);
+                break;
+            };
+            out.push_str(&text[i..=end]);
+            let key = &text[i + 1..end];
+            i = end + 1;
+
+            let colon = skip_whitespace(bytes, i);
+            if colon >= bytes.len() || bytes[colon] != b':' || !self.is_redacted(key) {
+                continue;
+            }
+            let value = skip_whitespace(bytes, colon + 1);
+            if let Some(value_end) = scalar_end(bytes, value) {
+                out.push_str(&text[i..value]);
+                out.push('"');
+                out.push_str(&self.mask);
+                out.push('"');
+                i = value_end;
+            }
+        }
+        out
+    }
+}
+
+/// Index of the quote closing the string starting at `start`.
+fn string_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Some(i),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+        i += 1;
+    }
+    i
+}
+
+/// End of a maskable scalar value starting at `start`.
+fn scalar_end(bytes: &[u8], start: usize) -> Option<usize> {
+    match bytes.get(start)? {
+        b'"' => string_end(bytes, start).map(|end| end + 1),
+        b'{' | b'[' => None,
+        _ if bytes[start..].starts_with(b"null") => None,
+        _ => {
+            let len = bytes[start..]
+                .iter()
+                .position(|b| matches!(b, b',' | b'}' | b']') || b.is_ascii_whitespace())
+                .unwrap_or(bytes.len() - start);
+            (len > 0).then_some(start + len)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_nested_and_keeps_json_valid() {
+        let body = r#"{
+            "Name": "Anna Svensson",
+            "CorporateIdentityNumber": "19800101-1234",
+            "Phone": 46701234567,
+            "InvoiceAddress": {"EMAIL": "anna@example.com", "City": "Malmö"},
+            "Contacts": [{"Email": "x\"y@example.com"}, {"Email": null}],
+            "BankAccount": {"Iban": "SE45 5000 0000 0583 9825 7466"}
+        }"#;
+
+        let redacted = Redactor::default().redact(body);
+        let value: serde_json::Value = serde_json::from_str(&redacted).unwrap();
+
+        assert_eq!(value["Name"], "Anna Svensson");
+        assert_eq!(value["CorporateIdentityNumber"], "***");
+        assert_eq!(value["Phone"], "***");
+        assert_eq!(value["InvoiceAddress"]["EMAIL"], "***");
+        assert_eq!(value["InvoiceAddress"]["City"], "Malmö");
+        assert_eq!(value["Contacts"][0]["Email"], "***");
+        assert!(value["Contacts"][1]["Email"].is_null());
+        assert_eq!(value["BankAccount"]["Iban"], "***");
+    }
+
+    #[test]
+    fn test_redacts_json_embedded_in_text() {
+        let text = r#"Resource not found: {"Message": "No customer", "Email":"a@b.se"}"#;
+        assert_eq!(
+            Redactor::default().mask("[redacted]").redact(text),
+            r#"Resource not found: {"Message": "No customer", "Email":"[redacted]"}"#
+        );
+    }
+
+    #[test]
+    fn test_values_and_unconfigured_fields_are_kept() {
+        let redactor = Redactor::none().field("Secret");
+        let text = r#"{"Note": "Email", "Secret": true, "Other": "Secret"}"#;
+
+        assert_eq!(
+            redactor.redact(text),
+            r#"{"Note": "Email", "Secret": "***", "Other": "Secret"}"#
+        );
+        assert_eq!(Redactor::none().redact(text), text);
+        assert_eq!(
+            redactor.redact(r#"{"Secret": "unterminated"#),
+            r#"{"Secret": "unterminated"#
+        );
+    }
+}
@@ -0,0 +1,951 @@
+//! Financial report builders.
+//!
+//! Each report has a pure constructor that works on data already fetched
+//! (useful for tests and offline processing) and an async function that
+//! fetches what it needs through the [`Client`]:
+//!
+//! - [`ar_ageing`] / [`AgeingReport::from_ledger`] - accounts receivable ageing
+//! - [`profit_and_loss`] / [`ProfitAndLoss::from_balances`] - result per BAS account class
+//! - [`vat_summary`] / [`VatSummary::from_balances`] - output and input VAT for a period
+//! - [`sales_by_item`] / [`SalesByItem::from_invoices`] - invoiced quantity and amount per article or bundle
+//!
+//! All reports are serializable so they can be rendered as JSON or CSV.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::reports;
+//! use spiris::{AccessToken, Client};
+//! use chrono::NaiveDate;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+//! let as_of = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+//! let ageing = reports::ar_ageing(&client, as_of).await?;
+//!
+//! for row in &ageing.rows {
+//!     println!("{}: {} overdue", row.customer_id, row.buckets.overdue());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::bundles::{row_amount, BundleCatalog, CollapsedRow};
+use crate::client::Client;
+use crate::endpoints::collect_pages;
+use crate::error::Result;
+use crate::types::{
+    AccountBalance, ArticleId, Customer, CustomerId, CustomerLedgerItem, FiscalYear, Invoice, Money,
+};
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+// =============================================================================
+// Accounts receivable ageing
+// =============================================================================
+
+/// Ageing bucket based on days past the due date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgeingBucket {
+    /// Not yet due.
+    NotDue,
+    /// 0-30 days past due.
+    Days0To30,
+    /// 31-60 days past due.
+    Days31To60,
+    /// 61-90 days past due.
+    Days61To90,
+    /// More than 90 days past due.
+    Over90,
+}
+
+impl AgeingBucket {
+    /// All buckets in ascending age.
+    pub const ALL: [AgeingBucket; 5] = [
+        AgeingBucket::NotDue,
+        AgeingBucket::Days0To30,
+        AgeingBucket::Days31To60,
+        AgeingBucket::Days61To90,
+        AgeingBucket::Over90,
+    ];
+
+    /// Bucket for a number of days past due (negative when not yet due).
+    pub fn for_days_overdue(days: i64) -> Self {
+        match days {
+            d if d < 0 => AgeingBucket::NotDue,
+            0..=30 => AgeingBucket::Days0To30,
+            31..=60 => AgeingBucket::Days31To60,
+            61..=90 => AgeingBucket::Days61To90,
+            _ => AgeingBucket::Over90,
+        }
+    }
+
+    /// Short column label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            AgeingBucket::NotDue => "Not due",
+            AgeingBucket::Days0To30 => "0-30",
+            AgeingBucket::Days31To60 => "31-60",
+            AgeingBucket::Days61To90 => "61-90",
+            AgeingBucket::Over90 => "90+",
+        }
+    }
+}
+
+/// Amounts per ageing bucket.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BucketAmounts {
+    /// Not yet due.
+    #[serde(with = "crate::types::money_serde")]
+    pub not_due: Money,
+    /// 0-30 days past due.
+    #[serde(with = "crate::types::money_serde")]
+    pub days_0_30: Money,
+    /// 31-60 days past due.
+    #[serde(with = "crate::types::money_serde")]
+    pub days_31_60: Money,
+    /// 61-90 days past due.
+    #[serde(with = "crate::types::money_serde")]
+    pub days_61_90: Money,
+    /// More than 90 days past due.
+    #[serde(with = "crate::types::money_serde")]
+    pub over_90: Money,
+}
+
+impl BucketAmounts {
+    /// Amount in a bucket.
+    pub fn get(&self, bucket: AgeingBucket) -> Money {
+        match bucket {
+            AgeingBucket::NotDue => self.not_due,
+            AgeingBucket::Days0To30 => self.days_0_30,
+            AgeingBucket::Days31To60 => self.days_31_60,
+            AgeingBucket::Days61To90 => self.days_61_90,
+            AgeingBucket::Over90 => self.over_90,
+        }
+    }
+
+    fn add(&mut self, bucket: AgeingBucket, amount: Money) {
+        let slot = match bucket {
+            AgeingBucket::NotDue => &mut self.not_due,
+            AgeingBucket::Days0To30 => &mut self.days_0_30,
+            AgeingBucket::Days31To60 => &mut self.days_31_60,
+            AgeingBucket::Days61To90 => &mut self.days_61_90,
+            AgeingBucket::Over90 => &mut self.over_90,
+        };
+        *slot += amount;
+    }
+
+    /// Sum of all buckets.
+    pub fn total(&self) -> Money {
+        self.not_due + self.overdue()
+    }
+
+    /// Sum of all overdue buckets.
+    pub fn overdue(&self) -> Money {
+        self.days_0_30 + self.days_31_60 + self.days_61_90 + self.over_90
+    }
+}
+
+/// Ageing of a single customer's open invoices.
+#[derive(Debug, Clone, Serialize)]
+pub struct AgeingRow {
+    /// Customer ID.
+    pub customer_id: CustomerId,
+    /// Customer name, when known.
+    pub customer_name: Option<String>,
+    /// Open amounts per bucket.
+    pub buckets: BucketAmounts,
+    /// Number of open invoices.
+    pub invoice_count: usize,
+    /// Open amount on held invoices (disputed, on hold or pending direct
+    /// debit), included in `buckets`.
+    #[serde(with = "crate::types::money_serde")]
+    pub on_hold: Money,
+    /// Number of held invoices.
+    pub on_hold_count: usize,
+}
+
+/// Accounts receivable ageing report.
+#[derive(Debug, Clone, Serialize)]
+pub struct AgeingReport {
+    /// Date the ageing is calculated at.
+    pub as_of: NaiveDate,
+    /// One row per customer with open invoices, ordered by customer ID.
+    pub rows: Vec<AgeingRow>,
+    /// Totals over all customers.
+    pub totals: BucketAmounts,
+    /// Open amount on held invoices over all customers.
+    #[serde(with = "crate::types::money_serde")]
+    pub on_hold: Money,
+}
+
+impl AgeingReport {
+    /// Build an ageing report from invoices.
+    ///
+    /// Ages each invoice's current remaining amount, so payments made after
+    /// `as_of` are not added back; use [`from_ledger`](Self::from_ledger) for
+    /// a report at a past date. Invoices dated after `as_of` and invoices
+    /// without a remaining amount or due date are ignored. Held invoices (see
+    /// [`disputes`](crate::disputes)) are aged like the others and also summed
+    /// in `on_hold`.
+    pub fn from_invoices(as_of: NaiveDate, invoices: &[Invoice]) -> Self {
+        Self::from_open(
+            as_of,
+            invoices
+                .iter()
+                .filter(|invoice| invoice.invoice_date.is_none_or(|date| date <= as_of))
+                .filter_map(|invoice| Some((invoice, invoice.remaining_amount?))),
+        )
+    }
+
+    /// Build an ageing report from customer ledger items.
+    ///
+    /// The open amount of an invoice at `as_of` is the sum of its ledger
+    /// items dated on or before that day: the invoice itself plus payments and
+    /// credits against it. Payments are dated by their payment date, other
+    /// items by the invoice date. `invoices` supplies the due dates and hold
+    /// status; items for invoices not in it, and invoices dated after `as_of`,
+    /// are ignored.
+    pub fn from_ledger(
+        as_of: NaiveDate,
+        invoices: &[Invoice],
+        items: &[CustomerLedgerItem],
+    ) -> Self {
+        let by_id: HashMap<&str, &Invoice> = invoices
+            .iter()
+            .filter(|invoice| invoice.invoice_date.is_none_or(|date| date <= as_of))
+            .filter_map(|invoice| Some((invoice.id.as_deref()?, invoice)))
+            .collect();
+
+        let mut open: BTreeMap<&str, Money> = BTreeMap::new();
+        for item in items {
+            let (Some(invoice_id), Some(amount)) =
+                (item.customer_invoice_id.as_deref(), item.amount)
+            else {
+                continue;
+            };
+            let Some((invoice_id, invoice)) = by_id.get_key_value(invoice_id) else {
+                continue;
+            };
+            let date = item
+                .payment_date
+                .map(|paid| paid.date_naive())
+                .or(invoice.invoice_date);
+            if date.is_some_and(|date| date > as_of) {
+                continue;
+            }
+            *open.entry(invoice_id).or_default() += amount;
+        }
+
+        Self::from_open(
+            as_of,
+            open.into_iter()
+                .map(|(invoice_id, amount)| (by_id[invoice_id], amount)),
+        )
+    }
+
+    fn from_open<'a>(as_of: NaiveDate, open: impl Iterator<Item = (&'a Invoice, Money)>) -> Self {
+        let mut rows: BTreeMap<CustomerId, AgeingRow> = BTreeMap::new();
+        let mut totals = BucketAmounts::default();
+        let mut on_hold = Money::default();
+
+        for (invoice, remaining) in open {
+            let Some(due) = invoice.due_date else {
+                continue;
+            };
+            if remaining == Money::default() {
+                continue;
+            }
+
+            let days = (as_of - due).num_days();
+            let bucket = AgeingBucket::for_days_overdue(days);
+            let customer_id = invoice.customer_id.clone().unwrap_or_default();
+
+            let row = rows
+                .entry(customer_id.clone())
+                .or_insert_with(|| AgeingRow {
+                    customer_id,
+                    customer_name: None,
+                    buckets: BucketAmounts::default(),
+                    invoice_count: 0,
+                    on_hold: Money::default(),
+                    on_hold_count: 0,
+                });
+            row.buckets.add(bucket, remaining);
+            row.invoice_count += 1;
+            totals.add(bucket, remaining);
+            if invoice.is_held() {
+                row.on_hold += remaining;
+                row.on_hold_count += 1;
+                on_hold += remaining;
+            }
+        }
+
+        Self {
+            as_of,
+            rows: rows.into_values().collect(),
+            totals,
+            on_hold,
+        }
+    }
+
+    /// Fill in customer names from a customer list.
+    pub fn with_customer_names(mut self, customers: &[Customer]) -> Self {
+        let names: HashMap<&str, &str> = customers
+            .iter()
+            .filter_map(|c| Some((c.id.as_deref()?, c.name.as_deref()?)))
+            .collect();
+        for row in &mut self.rows {
+            row.customer_name = names.get(row.customer_id.as_str()).map(|n| n.to_string());
+        }
+        self
+    }
+}
+
+/// Build an accounts receivable ageing report as of a date.
+///
+/// Open amounts come from the customer ledger, so the report shows what
+/// was outstanding at `as_of` even if it has been paid since. See
+/// [`AgeingReport::from_ledger`].
+pub async fn ar_ageing(client: &Client, as_of: NaiveDate) -> Result<AgeingReport> {
+    let items =
+        collect_pages(|p| async move { client.customer_ledger_items().list(Some(p)).await })
+            .await?;
+    let invoices = collect_pages(|p| async move { client.invoices().list(Some(p)).await }).await?;
+    let customers =
+        collect_pages(|p| async move { client.customers().list(Some(p)).await }).await?;
+
+    Ok(AgeingReport::from_ledger(as_of, &invoices, &items).with_customer_names(&customers))
+}
+
+// =============================================================================
+// Sales by item
+// =============================================================================
+
+/// What a [`SalesLine`] counts.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(tag = "kind", content = "id", rename_all = "snake_case")]
+pub enum SalesItem {
+    /// An article, by ID.
+    Article(ArticleId),
+    /// A bundle, by number.
+    Bundle(String),
+}
+
+/// Invoiced quantity and amount of one item.
+#[derive(Debug, Clone, Serialize)]
+pub struct SalesLine {
+    /// The article or bundle.
+    pub item: SalesItem,
+    /// Quantity invoiced.
+    #[serde(with = "crate::types::money_serde")]
+    pub quantity: Money,
+    /// Amount invoiced, excluding VAT.
+    #[serde(with = "crate::types::money_serde")]
+    pub amount: Money,
+    /// Number of invoices the item appears on.
+    pub invoice_count: usize,
+}
+
+/// Sales per article or bundle for a period.
+#[derive(Debug, Clone, Serialize)]
+pub struct SalesByItem {
+    /// First day of the period.
+    pub period_start: NaiveDate,
+    /// Last day of the period.
+    pub period_end: NaiveDate,
+    /// One line per item sold, ordered by item.
+    pub lines: Vec<SalesLine>,
+    /// Total amount, excluding VAT.
+    #[serde(with = "crate::types::money_serde")]
+    pub total: Money,
+}
+
+impl SalesByItem {
+    /// Build the report from invoices dated within `period_start..=period_end`.
+    ///
+    /// With a bundle catalog, rows expanded from its bundles are counted as
+    /// the bundle rather than as the underlying articles. Text rows are
+    /// ignored.
+    pub fn from_invoices(
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+        invoices: &[Invoice],
+        bundles: Option<&BundleCatalog>,
+    ) -> Self {
+        let no_bundles = BundleCatalog::new();
+        let catalog = bundles.unwrap_or(&no_bundles);
+        let mut lines: BTreeMap<SalesItem, SalesLine> = BTreeMap::new();
+
+        for invoice in invoices {
+            let Some(date) = invoice.invoice_date else {
+                continue;
+            };
+            if date < period_start || date > period_end {
+                continue;
+            }
+
+            let mut seen = Vec::new();
+            for row in catalog.collapse(&invoice.rows) {
+                let (item, quantity, amount) = match row {
+                    CollapsedRow::Bundle {
+                        bundle,
+                        quantity,
+                        amount,
+                    } => (SalesItem::Bundle(bundle.number.clone()), quantity, amount),
+                    CollapsedRow::Row(row) => {
+                        let Some(article_id) = &row.article_id else {
+                            continue;
+                        };
+                        (
+                            SalesItem::Article(article_id.clone()),
+                            row.quantity.unwrap_or_default(),
+                            row_amount(row),
+                        )
+                    }
+                };
+
+                let line = lines.entry(item.clone()).or_insert_with(|| SalesLine {
+                    item: item.clone(),
+                    quantity: Money::default(),
+                    amount: Money::default(),
+                    invoice_count: 0,
+                });
+                line.quantity += quantity;
+                line.amount += amount;
+                if !seen.contains(&item) {
+                    line.invoice_count += 1;
+                    seen.push(item);
+                }
+            }
+        }
+
+        let lines: Vec<SalesLine> = lines.into_values().collect();
+        let total = lines.iter().map(|l| l.amount).sum();
+        Self {
+            period_start,
+            period_end,
+            lines,
+            total,
+        }
+    }
+}
+
+/// Build a sales by item report for `start..=end`, optionally counting
+/// bundles as single items.
+pub async fn sales_by_item(
+    client: &Client,
+    start: NaiveDate,
+    end: NaiveDate,
+    bundles: Option<&BundleCatalog>,
+) -> Result<SalesByItem> {
+    let invoices = collect_pages(|p| async move { client.invoices().list(Some(p)).await }).await?;
+
+    Ok(SalesByItem::from_invoices(start, end, &invoices, bundles))
+}
+
+// =============================================================================
+// Account based reports
+// =============================================================================
+
+/// Movement on a single account during a period.
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountLine<S> {
+    /// Account number.
+    pub account_number: String,
+    /// Account name.
+    pub name: Option<String>,
+    /// Report section the account belongs to.
+    pub section: S,
+    /// Amount for the period, signed as presented in the report.
+    #[serde(with = "crate::types::money_serde")]
+    pub amount: Money,
+}
+
+/// Change in balance per account between two balance snapshots.
+fn movements(
+    opening: &[AccountBalance],
+    closing: &[AccountBalance],
+) -> Vec<(u32, AccountBalance, Money)> {
+    let opening: HashMap<&str, Money> = opening
+        .iter()
+        .filter_map(|b| Some((b.account_number.as_deref()?, b.balance.unwrap_or_default())))
+        .collect();
+
+    let mut result: Vec<_> = closing
+        .iter()
+        .filter_map(|b| {
+            let number_str = b.account_number.as_deref()?;
+            let number = number_str.parse::<u32>().ok()?;
+            let start = opening.get(number_str).copied().unwrap_or_default();
+            let movement = b.balance.unwrap_or_default() - start;
+            Some((number, b.clone(), movement))
+        })
+        .filter(|(_, _, movement)| *movement != Money::default())
+        .collect();
+    result.sort_by_key(|(number, _, _)| *number);
+    result
+}
+
+/// Balances of every account at the end of `date`.
+pub(crate) async fn balances(client: &Client, date: NaiveDate) -> Result<Vec<AccountBalance>> {
+    let date = date.format("%Y-%m-%d").to_string();
+    collect_pages(|p| {
+        let date = date.clone();
+        async move { client.accounts().get_balances_with_params(&date, p).await }
+    })
+    .await
+}
+
+/// Section of the profit and loss statement, following the BAS account classes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PnlSection {
+    /// Class 3: net sales and other operating income.
+    Revenue,
+    /// Class 4: goods, materials and subcontracting.
+    CostOfGoods,
+    /// Classes 5-6: other external expenses.
+    OtherExternalExpenses,
+    /// Class 7: personnel costs and depreciation.
+    Personnel,
+    /// Class 8: financial items, appropriations and tax.
+    FinancialItems,
+}
+
+impl PnlSection {
+    /// Section for a BAS account number, `None` for balance sheet accounts.
+    pub fn for_account(account_number: u32) -> Option<Self> {
+        match account_number / 1000 {
+            3 => Some(PnlSection::Revenue),
+            4 => Some(PnlSection::CostOfGoods),
+            5 | 6 => Some(PnlSection::OtherExternalExpenses),
+            7 => Some(PnlSection::Personnel),
+            8 => Some(PnlSection::FinancialItems),
+            _ => None,
+        }
+    }
+}
+
+/// Profit and loss statement for a period.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfitAndLoss {
+    /// First day of the period.
+    pub period_start: NaiveDate,
+    /// Last day of the period.
+    pub period_end: NaiveDate,
+    /// Result accounts with movement; income positive, costs negative.
+    pub lines: Vec<AccountLine<PnlSection>>,
+    /// Net result for the period.
+    #[serde(with = "crate::types::money_serde")]
+    pub net_result: Money,
+}
+
+impl ProfitAndLoss {
+    /// Build the statement from opening and closing account balances.
+    ///
+    /// `opening` should be the balances at the day before the period starts,
+    /// or empty when the period starts a fiscal year.
+    pub fn from_balances(
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+        opening: &[AccountBalance],
+        closing: &[AccountBalance],
+    ) -> Self {
+        let lines: Vec<_> = movements(opening, closing)
+            .into_iter()
+            .filter_map(|(number, balance, movement)| {
+                Some(AccountLine {
+                    section: PnlSection::for_account(number)?,
+                    account_number: number.to_string(),
+                    name: balance.name,
+                    amount: -movement,
+                })
+            })
+            .collect();
+        let net_result = lines.iter().map(|l| l.amount).sum();
+
+        Self {
+            period_start,
+            period_end,
+            lines,
+            net_result,
+        }
+    }
+
+    /// Total for a section.
+    pub fn section_total(&self, section: PnlSection) -> Money {
+        self.lines
+            .iter()
+            .filter(|l| l.section == section)
+            .map(|l| l.amount)
+            .sum()
+    }
+}
+
+/// Build a profit and loss statement for `start..=end`.
+pub async fn profit_and_loss(
+    client: &Client,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<ProfitAndLoss> {
+    let fiscal_years =
+        collect_pages(|p| async move { client.fiscal_years().list(Some(p)).await }).await?;
+
+    // Result accounts restart at each fiscal year, so there is no opening
+    // balance to subtract when the period starts a fiscal year.
+    let opening = if starts_fiscal_year(&fiscal_years, start) {
+        Vec::new()
+    } else {
+        balances(client, start.pred_opt().unwrap_or(start)).await?
+    };
+    let closing = balances(client, end).await?;
+
+    Ok(ProfitAndLoss::from_balances(start, end, &opening, &closing))
+}
+
+fn starts_fiscal_year(fiscal_years: &[FiscalYear], date: NaiveDate) -> bool {
+    fiscal_years
+        .iter()
+        .any(|fy| fy.start_date.is_some_and(|d| d.date_naive() == date))
+}
+
+/// Kind of VAT account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VatKind {
+    /// Output VAT (BAS 2610-2639), owed on sales.
+    Output,
+    /// Input VAT (BAS 2640-2649), deductible on purchases.
+    Input,
+}
+
+impl VatKind {
+    /// Kind for a BAS account number, `None` for non-VAT accounts.
+    pub fn for_account(account_number: u32) -> Option<Self> {
+        match account_number {
+            2610..=2639 => Some(VatKind::Output),
+            2640..=2649 => Some(VatKind::Input),
+            _ => None,
+        }
+    }
+}
+
+/// VAT summary for a period.
+#[derive(Debug, Clone, Serialize)]
+pub struct VatSummary {
+    /// First day of the period.
+    pub period_start: NaiveDate,
+    /// Last day of the period.
+    pub period_end: NaiveDate,
+    /// VAT accounts with movement, amounts as positive VAT.
+    pub lines: Vec<AccountLine<VatKind>>,
+    /// Total output VAT.
+    #[serde(with = "crate::types::money_serde")]
+    pub output_vat: Money,
+    /// Total input VAT.
+    #[serde(with = "crate::types::money_serde")]
+    pub input_vat: Money,
+    /// VAT to pay (negative means a refund).
+    #[serde(with = "crate::types::money_serde")]
+    pub net_payable: Money,
+}
+
+impl VatSummary {
+    /// Build the summary from opening and closing account balances.
+    pub fn from_balances(
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+        opening: &[AccountBalance],
+        closing: &[AccountBalance],
+    ) -> Self {
+        let lines: Vec<_> = movements(opening, closing)
+            .into_iter()
+            .filter_map(|(number, balance, movement)| {
+                let kind = VatKind::for_account(number)?;
+                Some(AccountLine {
+                    account_number: number.to_string(),
+                    name: balance.name,
+                    section: kind,
+                    // Output VAT is a credit balance, input VAT a debit balance.
+                    amount: match kind {
+                        VatKind::Output => -movement,
+                        VatKind::Input => movement,
+                    },
+                })
+            })
+            .collect();
+
+        let total = |kind: VatKind| -> Money {
+            lines
+                .iter()
+                .filter(|l| l.section == kind)
+                .map(|l| l.amount)
+                .sum()
+        };
+        let output_vat = total(VatKind::Output);
+        let input_vat = total(VatKind::Input);
+
+        Self {
+            period_start,
+            period_end,
+            lines,
+            output_vat,
+            input_vat,
+            net_payable: output_vat - input_vat,
+        }
+    }
+}
+
+/// Build a VAT summary for `start..=end`.
+pub async fn vat_summary(client: &Client, start: NaiveDate, end: NaiveDate) -> Result<VatSummary> {
+    let opening = balances(client, start.pred_opt().unwrap_or(start)).await?;
+    let closing = balances(client, end).await?;
+
+    Ok(VatSummary::from_balances(start, end, &opening, &closing))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundles::Bundle;
+    use crate::money;
+    use crate::types::InvoiceRow;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn invoice(customer: &str, due: NaiveDate, remaining: Money) -> Invoice {
+        Invoice {
+            customer_id: Some(customer.into()),
+            due_date: Some(due),
+            remaining_amount: Some(remaining),
+            ..Default::default()
+        }
+    }
+
+    fn balance(account: &str, amount: Money) -> AccountBalance {
+        AccountBalance {
+            account_number: Some(account.to_string()),
+            name: Some(format!("Account {}", account)),
+            balance: Some(amount),
+        }
+    }
+
+    #[test]
+    fn test_bucket_for_days_overdue() {
+        assert_eq!(AgeingBucket::for_days_overdue(-5), AgeingBucket::NotDue);
+        assert_eq!(AgeingBucket::for_days_overdue(0), AgeingBucket::Days0To30);
+        assert_eq!(AgeingBucket::for_days_overdue(30), AgeingBucket::Days0To30);
+        assert_eq!(AgeingBucket::for_days_overdue(31), AgeingBucket::Days31To60);
+        assert_eq!(AgeingBucket::for_days_overdue(90), AgeingBucket::Days61To90);
+        assert_eq!(AgeingBucket::for_days_overdue(91), AgeingBucket::Over90);
+    }
+
+    #[test]
+    fn test_ageing_report_groups_by_customer() {
+        let as_of = date(2025, 3, 31);
+        let invoices = vec![
+            invoice("c1", date(2025, 4, 10), money!(100)),
+            invoice("c1", date(2025, 3, 15), money!(200)),
+            invoice("c2", date(2024, 12, 1), money!(300)),
+            invoice("c2", date(2025, 1, 1), money!(0)),
+            Invoice {
+                remarks: Some("[DISPUTED] Wrong price".to_string()),
+                ..invoice("c2", date(2025, 2, 1), money!(50))
+            },
+        ];
+
+        let report =
+            AgeingReport::from_invoices(as_of, &invoices).with_customer_names(&[Customer {
+                id: Some("c1".into()),
+                name: Some("Acme".to_string()),
+                ..Default::default()
+            }]);
+
+        assert_eq!(report.rows.len(), 2);
+        assert_eq!(report.rows[0].customer_name.as_deref(), Some("Acme"));
+        assert_eq!(report.rows[0].buckets.not_due, money!(100));
+        assert_eq!(report.rows[0].buckets.days_0_30, money!(200));
+        assert_eq!(report.rows[0].invoice_count, 2);
+        assert_eq!(report.rows[1].buckets.over_90, money!(300));
+        assert_eq!(report.rows[1].invoice_count, 2);
+        assert_eq!(report.rows[1].on_hold, money!(50));
+        assert_eq!(report.rows[1].on_hold_count, 1);
+        assert_eq!(report.rows[0].on_hold_count, 0);
+        assert_eq!(report.totals.total(), money!(650));
+        assert_eq!(report.totals.overdue(), money!(550));
+        assert_eq!(report.on_hold, money!(50));
+    }
+
+    fn ledger_invoice(id: &str, invoiced: NaiveDate, due: NaiveDate) -> Invoice {
+        Invoice {
+            id: Some(id.into()),
+            invoice_date: Some(invoiced),
+            ..invoice("c1", due, money!(0))
+        }
+    }
+
+    fn ledger_item(invoice_id: &str, amount: Money, paid: Option<NaiveDate>) -> CustomerLedgerItem {
+        CustomerLedgerItem {
+            customer_invoice_id: Some(invoice_id.into()),
+            amount: Some(amount),
+            payment_date: paid.map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ledger_ageing_bucket_boundaries() {
+        let as_of = date(2025, 6, 30);
+        let cases = [
+            (30, AgeingBucket::Days0To30),
+            (31, AgeingBucket::Days31To60),
+            (60, AgeingBucket::Days31To60),
+            (61, AgeingBucket::Days61To90),
+            (90, AgeingBucket::Days61To90),
+            (91, AgeingBucket::Over90),
+        ];
+
+        for (days, bucket) in cases {
+            let due = as_of - chrono::Duration::days(days);
+            let invoices = [ledger_invoice("i1", due - chrono::Duration::days(30), due)];
+            let items = [ledger_item("i1", money!(100), None)];
+
+            let report = AgeingReport::from_ledger(as_of, &invoices, &items);
+
+            assert_eq!(report.totals.get(bucket), money!(100), "{} days", days);
+            assert_eq!(report.totals.total(), money!(100), "{} days", days);
+        }
+    }
+
+    #[test]
+    fn test_ledger_ageing_uses_amounts_open_at_date() {
+        let as_of = date(2025, 3, 31);
+        let invoices = [
+            ledger_invoice("paid-before", date(2025, 1, 1), date(2025, 1, 31)),
+            ledger_invoice("paid-after", date(2025, 2, 1), date(2025, 3, 3)),
+            ledger_invoice("part-paid", date(2025, 3, 1), date(2025, 3, 31)),
+            ledger_invoice("future", date(2025, 4, 2), date(2025, 5, 2)),
+        ];
+        let items = [
+            ledger_item("paid-before", money!(500), None),
+            ledger_item("paid-before", money!(-500), Some(date(2025, 2, 15))),
+            ledger_item("paid-after", money!(300), None),
+            ledger_item("paid-after", money!(-300), Some(date(2025, 4, 10))),
+            ledger_item("part-paid", money!(200), None),
+            ledger_item("part-paid", money!(-50), Some(date(2025, 3, 31))),
+            ledger_item("future", money!(900), None),
+            ledger_item("unknown", money!(70), None),
+        ];
+
+        let report = AgeingReport::from_ledger(as_of, &invoices, &items);
+
+        assert_eq!(report.rows.len(), 1);
+        assert_eq!(report.rows[0].invoice_count, 2);
+        assert_eq!(report.totals.days_0_30, money!(450));
+        assert_eq!(report.totals.total(), money!(450));
+    }
+
+    #[test]
+    fn test_ageing_from_invoices_skips_invoices_dated_after() {
+        let as_of = date(2025, 3, 31);
+        let invoices = [
+            invoice("c1", date(2025, 3, 15), money!(200)),
+            Invoice {
+                invoice_date: Some(date(2025, 4, 1)),
+                ..invoice("c1", date(2025, 5, 1), money!(400))
+            },
+        ];
+
+        let report = AgeingReport::from_invoices(as_of, &invoices);
+
+        assert_eq!(report.totals.total(), money!(200));
+    }
+
+    #[test]
+    fn test_sales_by_item_counts_bundles_once() {
+        let bundle = Bundle::new("PKG-1", "Starter")
+            .priced_component("a1", money!(2), money!(50))
+            .priced_component("a2", money!(1), money!(300));
+        let catalog = BundleCatalog::new().bundle(bundle.clone());
+        let sold = |day: u32| Invoice {
+            invoice_date: Some(NaiveDate::from_ymd_opt(2025, 5, day).unwrap()),
+            rows: vec![InvoiceRow {
+                article_id: Some("a1".into()),
+                quantity: Some(money!(1)),
+                unit_price: Some(money!(60)),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let invoices = vec![
+            sold(2).add_bundle(&bundle, money!(2)),
+            sold(20),
+            sold(31).add_bundle(&bundle, money!(1)),
+        ];
+        let (start, end) = (date(2025, 5, 1), date(2025, 5, 30));
+
+        let flat = SalesByItem::from_invoices(start, end, &invoices, None);
+        assert_eq!(flat.lines.len(), 2);
+        assert_eq!(flat.lines[0].item, SalesItem::Article("a1".into()));
+        assert_eq!(flat.lines[0].quantity, money!(6));
+        assert_eq!(flat.lines[0].invoice_count, 2);
+        assert_eq!(flat.total, money!(920));
+
+        let grouped = SalesByItem::from_invoices(start, end, &invoices, Some(&catalog));
+        assert_eq!(grouped.lines.len(), 2);
+        assert_eq!(grouped.lines[0].quantity, money!(2));
+        assert_eq!(
+            grouped.lines[1].item,
+            SalesItem::Bundle("PKG-1".to_string())
+        );
+        assert_eq!(grouped.lines[1].quantity, money!(2));
+        assert_eq!(grouped.lines[1].amount, money!(800));
+        assert_eq!(grouped.total, flat.total);
+    }
+
+    #[test]
+    fn test_profit_and_loss_from_balances() {
+        let opening = vec![balance("3001", money!(-1000)), balance("5010", money!(200))];
+        let closing = vec![
+            balance("1930", money!(5000)),
+            balance("3001", money!(-4000)),
+            balance("5010", money!(700)),
+            balance("7010", money!(1500)),
+        ];
+
+        let pnl =
+            ProfitAndLoss::from_balances(date(2025, 2, 1), date(2025, 2, 28), &opening, &closing);
+
+        assert_eq!(pnl.lines.len(), 3);
+        assert_eq!(pnl.section_total(PnlSection::Revenue), money!(3000));
+        assert_eq!(
+            pnl.section_total(PnlSection::OtherExternalExpenses),
+            money!(-500)
+        );
+        assert_eq!(pnl.section_total(PnlSection::Personnel), money!(-1500));
+        assert_eq!(pnl.net_result, money!(1000));
+    }
+
+    #[test]
+    fn test_vat_summary_from_balances() {
+        let opening = vec![balance("2611", money!(-100)), balance("2641", money!(40))];
+        let closing = vec![
+            balance("2611", money!(-600)),
+            balance("2641", money!(240)),
+            balance("3001", money!(-2000)),
+        ];
+
+        let vat =
+            VatSummary::from_balances(date(2025, 1, 1), date(2025, 3, 31), &opening, &closing);
+
+        assert_eq!(vat.lines.len(), 2);
+        assert_eq!(vat.output_vat, money!(500));
+        assert_eq!(vat.input_vat, money!(200));
+        assert_eq!(vat.net_payable, money!(300));
+    }
+}
@@ -1,13 +1,68 @@
 //! Retry logic with exponential backoff for API requests.
+//!
+//! Backoff can be randomised with [`Jitter`] so that many workers failing at
+//! once don't retry in lockstep, and [`RetryPolicy`] overrides per status
+//! code which errors are retried.
+//!
+//! A 429 Too Many Requests response is retried after the delay in its
+//! `Retry-After` header instead of the backoff interval. The quota the API
+//! reports in `X-RateLimit-*` headers is available from
+//! [`Client::rate_limit_status`](crate::Client::rate_limit_status).
 
+use crate::clock::{Clock, SystemClock};
 use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::collections::BTreeMap;
 use std::time::Duration;
-use tokio::time::sleep;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::timeout;
+
+/// How the backoff interval is randomised.
+///
+/// With `Full` or `Equal`, each wait is drawn from a range below the
+/// exponential interval, which spreads out retries from clients that failed
+/// at the same moment. A `Retry-After` delay asked for by the API is never
+/// randomised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Jitter {
+    /// Wait exactly the backoff interval.
+    #[default]
+    None,
+    /// Wait a random time between zero and the interval.
+    Full,
+    /// Wait half the interval plus a random time up to the other half.
+    Equal,
+}
+
+impl Jitter {
+    /// Randomise `interval` according to this strategy.
+    pub fn apply(self, interval: Duration) -> Duration {
+        match self {
+            Jitter::None => interval,
+            Jitter::Full => interval.mul_f64(fastrand::f64()),
+            Jitter::Equal => {
+                let half = interval / 2;
+                half + half.mul_f64(fastrand::f64())
+            }
+        }
+    }
+}
+
+/// Whether errors with a given status code are retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryPolicy {
+    /// Always retry, even a status that normally isn't.
+    Retry,
+    /// Never retry, even a status that normally is.
+    Never,
+}
 
 /// Configuration for retry behavior.
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
-    /// Maximum number of retry attempts.
+    /// Maximum number of retries after the first attempt, so the client
+    /// sends a request at most `1 + max_retries` times.
     pub max_retries: u32,
 
     /// Initial backoff duration.
@@ -19,8 +74,17 @@ pub struct RetryConfig {
     /// Multiplier for exponential backoff.
     pub multiplier: f64,
 
-    /// Maximum elapsed time before giving up.
+    /// Maximum wall-clock time for a call including all retries.
+    ///
+    /// No retry is started, and a retry in flight is abandoned, once this is
+    /// exceeded. `None` bounds retries by `max_retries` only.
     pub max_elapsed_time: Option<Duration>,
+
+    /// How the backoff interval is randomised.
+    pub jitter: Jitter,
+
+    /// Per status code overrides of [`is_retryable_error`].
+    pub status_policies: BTreeMap<u16, RetryPolicy>,
 }
 
 impl Default for RetryConfig {
@@ -31,6 +95,8 @@ impl Default for RetryConfig {
             max_interval: Duration::from_secs(30),
             multiplier: 2.0,
             max_elapsed_time: Some(Duration::from_secs(120)),
+            jitter: Jitter::None,
+            status_policies: BTreeMap::new(),
         }
     }
 }
@@ -58,48 +124,292 @@ impl RetryConfig {
         self.max_interval = interval;
         self
     }
+
+    /// Set the backoff multiplier.
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Set the maximum wall-clock time for a call including all retries.
+    ///
+    /// Pass `None` to bound retries by `max_retries` only.
+    pub fn max_elapsed_time(mut self, max_elapsed_time: Option<Duration>) -> Self {
+        self.max_elapsed_time = max_elapsed_time;
+        self
+    }
+
+    /// Set how the backoff interval is randomised.
+    pub fn jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Decide whether errors with `status_code` are retried, overriding
+    /// the default of retrying 429 and 5xx responses.
+    ///
+    /// For example `.status_policy(409, RetryPolicy::Never)` stops a
+    /// conflict from being retried, and `.status_policy(503, RetryPolicy::Never)`
+    /// fails fast during maintenance.
+    pub fn status_policy(mut self, status_code: u16, policy: RetryPolicy) -> Self {
+        self.status_policies.insert(status_code, policy);
+        self
+    }
+
+    /// Whether `error` should be retried under this configuration.
+    ///
+    /// A [`status_policy`](Self::status_policy) for the error's status code
+    /// wins; otherwise this is [`is_retryable_error`].
+    pub fn should_retry(&self, error: &Error) -> bool {
+        let policy = response_status(error).and_then(|status| self.status_policies.get(&status));
+        match policy {
+            Some(RetryPolicy::Retry) => true,
+            Some(RetryPolicy::Never) => false,
+            None => is_retryable_error(error),
+        }
+    }
 }
 
 /// Retry a request operation with exponential backoff.
 ///
 /// This function will retry the operation if it fails with a retryable error
-/// (network errors, rate limits, server errors).
-pub async fn retry_request<T, F, Fut>(config: &RetryConfig, mut operation: F) -> Result<T>
+/// (network errors, rate limits, server errors), as decided by
+/// [`RetryConfig::should_retry`]. Once
+/// [`RetryConfig::max_elapsed_time`] would be exceeded it fails with
+/// [`Error::RetryDeadlineExceeded`].
+///
+/// Unlike the client, it calls `operation` at most `max_retries` times in
+/// total, the first call included.
+pub async fn retry_request<T, F, Fut>(config: &RetryConfig, operation: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    retry_request_with_clock(config, &SystemClock, operation).await
+}
+
+/// Retry like [`retry_request`], reading the time and sleeping through
+/// `clock`.
+///
+/// With a [`MockClock`](crate::clock::MockClock) the backoff completes
+/// without waiting and the deadline is measured in mock time.
+pub async fn retry_request_with_clock<T, F, Fut>(
+    config: &RetryConfig,
+    clock: &dyn Clock,
+    mut operation: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let started = clock.now();
+    let first = operation().await;
+    // Here `max_retries` bounds the attempts, the first one included
+    let retries = config.max_retries.saturating_sub(1);
+    retry_request_after(config, clock, started, first, retries, operation).await
+}
+
+/// Continue retrying after a first attempt made by the caller.
+///
+/// `started` is when the first attempt began; the deadline counts from there.
+/// At most `retries` attempts are made after the first.
+pub(crate) async fn retry_request_after<T, F, Fut>(
+    config: &RetryConfig,
+    clock: &dyn Clock,
+    started: DateTime<Utc>,
+    first: Result<T>,
+    retries: u32,
+    mut operation: F,
+) -> Result<T>
 where
     F: FnMut() -> Fut,
     Fut: std::future::Future<Output = Result<T>>,
 {
     let mut current_interval = config.initial_interval;
     let mut attempts = 0;
+    let mut result = first;
 
     loop {
-        match operation().await {
+        let err = match result {
             Ok(value) => return Ok(value),
-            Err(err) => {
-                attempts += 1;
+            Err(err) => err,
+        };
+        attempts += 1;
+
+        // If not retryable or out of retries, return error
+        if !config.should_retry(&err) || attempts > retries {
+            return Err(err);
+        }
+
+        // Wait as long as the API asked to, or back off
+        let retry_after = err.retry_after();
+        let delay = retry_after.unwrap_or_else(|| config.jitter.apply(current_interval));
+
+        // Give up rather than sleep past the deadline
+        if let Some(deadline) = config.max_elapsed_time {
+            if clock.elapsed_since(started) + delay >= deadline {
+                return Err(deadline_exceeded(deadline, clock, started, attempts, err));
+            }
+        }
+
+        // Wait before retrying
+        clock.sleep(delay).await;
+
+        // Calculate next backoff interval
+        if retry_after.is_none() {
+            current_interval =
+                Duration::from_secs_f64(current_interval.as_secs_f64() * config.multiplier)
+                    .min(config.max_interval);
+        }
 
-                // If not retryable or out of retries, return error
-                if !is_retryable_error(&err) || attempts >= config.max_retries {
-                    return Err(err);
+        // Abandon an attempt still running at the deadline
+        result = match config.max_elapsed_time {
+            Some(deadline) => {
+                let remaining = deadline.saturating_sub(clock.elapsed_since(started));
+                match timeout(remaining, operation()).await {
+                    Ok(result) => result,
+                    // The abandoned attempt counts, its error is unknown
+                    Err(_) => {
+                        return Err(deadline_exceeded(
+                            deadline,
+                            clock,
+                            started,
+                            attempts + 1,
+                            err,
+                        ))
+                    }
                 }
+            }
+            None => operation().await,
+        };
+    }
+}
+
+/// Run `future` for at most `duration`, timed by the browser.
+#[cfg(target_arch = "wasm32")]
+async fn timeout<F: std::future::Future>(
+    duration: Duration,
+    future: F,
+) -> std::result::Result<F::Output, ()> {
+    tokio::select! {
+        output = future => Ok(output),
+        _ = gloo_timers::future::sleep(duration) => Err(()),
+    }
+}
+
+fn deadline_exceeded(
+    deadline: Duration,
+    clock: &dyn Clock,
+    started: DateTime<Utc>,
+    attempts: u32,
+    last: Error,
+) -> Error {
+    Error::RetryDeadlineExceeded {
+        deadline,
+        elapsed: clock.elapsed_since(started),
+        attempts,
+        last_error: Box::new(last),
+    }
+}
+
+/// Request quota reported by the API in rate limit headers.
+///
+/// Read from `X-RateLimit-Limit`, `X-RateLimit-Remaining` and
+/// `X-RateLimit-Reset`, or the same headers without the `X-` prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    /// Requests allowed in the current window.
+    pub limit: Option<u64>,
+    /// Requests left in the current window.
+    pub remaining: Option<u64>,
+    /// When the current window ends.
+    pub reset: Option<DateTime<Utc>>,
+    /// When the response carrying these values arrived.
+    pub observed_at: DateTime<Utc>,
+}
+
+impl RateLimitStatus {
+    /// Read the quota from response headers received at `now`.
+    ///
+    /// Returns `None` if the response has none of the headers.
+    pub fn from_headers(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Self> {
+        let limit = rate_limit_header(headers, "ratelimit-limit");
+        let remaining = rate_limit_header(headers, "ratelimit-remaining");
+        let reset =
+            rate_limit_header(headers, "ratelimit-reset").map(|reset| reset_time(reset, now));
+        if limit.is_none() && remaining.is_none() && reset.is_none() {
+            return None;
+        }
+        Some(Self {
+            limit,
+            remaining,
+            reset,
+            observed_at: now,
+        })
+    }
+}
 
-                // Wait before retrying
-                sleep(current_interval).await;
+/// Values above this are Unix timestamps rather than seconds from now.
+const RESET_EPOCH_THRESHOLD: u64 = 1_000_000_000;
 
-                // Calculate next backoff interval
-                current_interval =
-                    Duration::from_secs_f64(current_interval.as_secs_f64() * config.multiplier)
-                        .min(config.max_interval);
+fn reset_time(reset: u64, now: DateTime<Utc>) -> DateTime<Utc> {
+    if reset >= RESET_EPOCH_THRESHOLD {
+        DateTime::from_timestamp(reset as i64, 0).unwrap_or(now)
+    } else {
+        now + chrono::Duration::seconds(reset as i64)
+    }
+}
+
+fn rate_limit_header(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers
+        .get(format!("x-{}", name))
+        .or_else(|| headers.get(name))
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+}
+
+/// How long a 429 response received at `now` asks the client to wait.
+///
+/// Reads `Retry-After` as seconds or as an HTTP date, falling back to the
+/// rate limit reset time. A time already passed gives a zero wait.
+pub fn retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let until = |at: DateTime<Utc>| (at - now).to_std().unwrap_or(Duration::ZERO);
+    match headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+    {
+        Some(value) => {
+            let value = value.trim();
+            match value.parse::<u64>() {
+                Ok(seconds) => Some(Duration::from_secs(seconds)),
+                Err(_) => DateTime::parse_from_rfc2822(value)
+                    .ok()
+                    .map(|at| until(at.with_timezone(&Utc))),
             }
         }
+        None => RateLimitStatus::from_headers(headers, now)?
+            .reset
+            .map(until),
+    }
+}
+
+/// The HTTP status an error was made from, if any.
+fn response_status(error: &Error) -> Option<u16> {
+    match error.root() {
+        Error::ApiError { status_code, .. } => Some(*status_code),
+        Error::RateLimitExceeded { .. } => Some(429),
+        Error::NotFound(_) => Some(404),
+        Error::Conflict(_) => Some(412),
+        Error::Http(err) => err.status().map(|status| status.as_u16()),
+        _ => None,
     }
 }
 
 /// Determine if an error is retryable.
 pub fn is_retryable_error(error: &Error) -> bool {
-    match error {
-        Error::Http(_) => true,              // Network errors are retryable
-        Error::RateLimitExceeded(_) => true, // Rate limits are retryable
+    match error.root() {
+        Error::Http(_) => true,                  // Network errors are retryable
+        Error::RateLimitExceeded { .. } => true, // Rate limits are retryable
         Error::ApiError { status_code, .. } => {
             // Retry on server errors (5xx) but not client errors (4xx)
             *status_code >= 500
@@ -112,6 +422,16 @@ pub fn is_retryable_error(error: &Error) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::MockClock;
+    use std::time::Instant;
+    use tokio::time::sleep;
+
+    fn rate_limited(retry_after: Option<Duration>) -> Error {
+        Error::RateLimitExceeded {
+            message: "slow down".to_string(),
+            retry_after,
+        }
+    }
 
     #[test]
     fn test_retry_config_defaults() {
@@ -134,19 +454,270 @@ mod tests {
     fn test_is_retryable_error() {
         use crate::error::ApiErrorResponse;
 
-        assert!(is_retryable_error(&Error::RateLimitExceeded(
-            "test".to_string()
-        )));
+        assert!(is_retryable_error(&rate_limited(None)));
         assert!(is_retryable_error(&Error::ApiError {
             status_code: 500,
-            response: ApiErrorResponse::from_raw("Server error".to_string()),
+            response: Box::new(ApiErrorResponse::from_raw("Server error".to_string())),
             raw_body: "Server error".to_string(),
         }));
         assert!(!is_retryable_error(&Error::ApiError {
             status_code: 400,
-            response: ApiErrorResponse::from_raw("Bad request".to_string()),
+            response: Box::new(ApiErrorResponse::from_raw("Bad request".to_string())),
             raw_body: "Bad request".to_string(),
         }));
         assert!(!is_retryable_error(&Error::TokenExpired));
     }
+
+    fn api_error(status_code: u16) -> Error {
+        use crate::error::ApiErrorResponse;
+
+        Error::ApiError {
+            status_code,
+            response: Box::new(ApiErrorResponse::from_raw(String::new())),
+            raw_body: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_jitter_stays_within_interval() {
+        let interval = Duration::from_secs(8);
+        assert_eq!(Jitter::None.apply(interval), interval);
+        for _ in 0..100 {
+            assert!(Jitter::Full.apply(interval) <= interval);
+            let equal = Jitter::Equal.apply(interval);
+            assert!(equal >= Duration::from_secs(4) && equal <= interval);
+        }
+    }
+
+    #[test]
+    fn test_status_policy_overrides_default() {
+        let config = RetryConfig::new()
+            .status_policy(409, RetryPolicy::Retry)
+            .status_policy(503, RetryPolicy::Never)
+            .status_policy(429, RetryPolicy::Never);
+
+        assert!(config.should_retry(&api_error(409)));
+        assert!(!config.should_retry(&api_error(503)));
+        assert!(!config.should_retry(&rate_limited(None)));
+        assert!(config.should_retry(&api_error(500)));
+        assert!(!config.should_retry(&api_error(400)));
+    }
+
+    #[tokio::test]
+    async fn test_never_policy_stops_retries() {
+        let config = RetryConfig::new()
+            .max_retries(5)
+            .status_policy(502, RetryPolicy::Never);
+        let clock = MockClock::new(Utc::now());
+
+        let calls = std::sync::atomic::AtomicU32::new(0);
+        let result: Result<()> = retry_request_with_clock(&config, &clock, || {
+            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            async { Err(api_error(502)) }
+        })
+        .await;
+
+        assert_eq!(result.unwrap_err().status_code(), Some(502));
+        assert_eq!(calls.into_inner(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_jittered_backoff_never_exceeds_interval() {
+        let config = RetryConfig::new()
+            .max_retries(4)
+            .initial_interval(Duration::from_secs(10))
+            .jitter(Jitter::Full)
+            .max_elapsed_time(None);
+        let start = Utc::now();
+        let clock = MockClock::new(start);
+
+        let result: Result<()> =
+            retry_request_with_clock(&config, &clock, || async { Err(rate_limited(None)) }).await;
+
+        // Without jitter this would wait 10s + 20s + 40s
+        assert!(result.is_err());
+        assert!(clock.elapsed_since(start) <= Duration::from_secs(70));
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_before_sleeping_past_deadline() {
+        let config = RetryConfig::new()
+            .max_retries(10)
+            .initial_interval(Duration::from_millis(20))
+            .max_elapsed_time(Some(Duration::from_millis(50)));
+
+        let started = Instant::now();
+        let result: Result<()> = retry_request(&config, || async { Err(rate_limited(None)) }).await;
+
+        assert!(started.elapsed() < Duration::from_millis(50));
+        match result {
+            Err(Error::RetryDeadlineExceeded {
+                deadline,
+                attempts,
+                last_error,
+                ..
+            }) => {
+                assert_eq!(deadline, Duration::from_millis(50));
+                // 20ms + 40ms of backoff would pass the deadline.
+                assert_eq!(attempts, 2);
+                assert!(matches!(*last_error, Error::RateLimitExceeded { .. }));
+            }
+            other => panic!("expected deadline error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_abandons_attempt_at_deadline() {
+        let config = RetryConfig::new()
+            .initial_interval(Duration::from_millis(10))
+            .max_elapsed_time(Some(Duration::from_millis(60)));
+
+        let calls = std::sync::atomic::AtomicU32::new(0);
+        let result: Result<()> = retry_request(&config, || {
+            let call = calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            async move {
+                if call > 0 {
+                    sleep(Duration::from_secs(5)).await;
+                }
+                Err(rate_limited(None))
+            }
+        })
+        .await;
+
+        let err = result.unwrap_err();
+        // The first attempt failed, the second was abandoned.
+        assert!(matches!(
+            err,
+            Error::RetryDeadlineExceeded { attempts: 2, .. }
+        ));
+        assert!(err.to_string().contains("60ms"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_backoff_uses_clock() {
+        let config = RetryConfig::new()
+            .max_retries(10)
+            .initial_interval(Duration::from_secs(10))
+            .max_elapsed_time(Some(Duration::from_secs(60)));
+        let start = Utc::now();
+        let clock = MockClock::new(start);
+
+        let started = Instant::now();
+        let result: Result<()> =
+            retry_request_with_clock(&config, &clock, || async { Err(rate_limited(None)) }).await;
+
+        // 10s + 20s of backoff is slept in mock time; another 40s would
+        // pass the deadline.
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(clock.elapsed_since(start), Duration::from_secs(30));
+        match result {
+            Err(Error::RetryDeadlineExceeded {
+                attempts, elapsed, ..
+            }) => {
+                assert_eq!(attempts, 3);
+                assert_eq!(elapsed, Duration::from_secs(30));
+            }
+            other => panic!("expected deadline error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_waits_for_retry_after_instead_of_backoff() {
+        let config = RetryConfig::new()
+            .max_retries(3)
+            .initial_interval(Duration::from_millis(500))
+            .max_elapsed_time(Some(Duration::from_secs(60)));
+        let start = Utc::now();
+        let clock = MockClock::new(start);
+
+        let calls = std::sync::atomic::AtomicU32::new(0);
+        let result = retry_request_with_clock(&config, &clock, || {
+            let call = calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            async move {
+                if call < 2 {
+                    Err(rate_limited(Some(Duration::from_secs(7))))
+                } else {
+                    Ok(call)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(clock.elapsed_since(start), Duration::from_secs(14));
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_when_retry_after_passes_deadline() {
+        let config = RetryConfig::new().max_elapsed_time(Some(Duration::from_secs(60)));
+        let clock = MockClock::new(Utc::now());
+
+        let result: Result<()> = retry_request_with_clock(&config, &clock, || async {
+            Err(rate_limited(Some(Duration::from_secs(600))))
+        })
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::RetryDeadlineExceeded { attempts: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_retry_after_header() {
+        let now = DateTime::parse_from_rfc3339("2025-03-14T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let headers = |pairs: &[(&'static str, &str)]| {
+            let mut map = HeaderMap::new();
+            for (name, value) in pairs {
+                map.insert(*name, value.parse().unwrap());
+            }
+            map
+        };
+
+        assert_eq!(
+            retry_after(&headers(&[("retry-after", "30")]), now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            retry_after(
+                &headers(&[("retry-after", "Fri, 14 Mar 2025 12:01:30 GMT")]),
+                now
+            ),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            retry_after(
+                &headers(&[("retry-after", "Fri, 14 Mar 2025 11:00:00 GMT")]),
+                now
+            ),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            retry_after(&headers(&[("x-ratelimit-reset", "12")]), now),
+            Some(Duration::from_secs(12))
+        );
+        assert_eq!(retry_after(&headers(&[("retry-after", "soon")]), now), None);
+        assert_eq!(retry_after(&HeaderMap::new(), now), None);
+    }
+
+    #[test]
+    fn test_rate_limit_status_from_headers() {
+        let now = DateTime::parse_from_rfc3339("2025-03-14T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", "600".parse().unwrap());
+        headers.insert("x-ratelimit-remaining", "42".parse().unwrap());
+        headers.insert("ratelimit-reset", "1741953660".parse().unwrap());
+
+        let status = RateLimitStatus::from_headers(&headers, now).unwrap();
+        assert_eq!(status.limit, Some(600));
+        assert_eq!(status.remaining, Some(42));
+        assert_eq!(status.reset, Some(now + chrono::Duration::seconds(60)));
+        assert_eq!(status.observed_at, now);
+
+        assert!(RateLimitStatus::from_headers(&HeaderMap::new(), now).is_none());
+    }
 }
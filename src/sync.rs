@@ -0,0 +1,495 @@
+//! Incremental sync based on modification times.
+//!
+//! A [`SyncCursor`] remembers the latest `ModifiedUtc` seen for a register.
+//! Passing it to an endpoint's `sync` fetches only the records modified since
+//! then and moves the cursor forward, so a nightly job downloads the day's
+//! changes instead of the whole company. Save the cursor between runs with
+//! [`SyncCursor::save`] or store it with serde.
+//!
+//! Records modified at exactly the cursor's time are fetched again on the
+//! next run, since the API compares whole seconds and a record may have
+//! changed within the same second after the previous sync. Apply synced
+//! records as upserts so that seeing one twice does no harm.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::sync::SyncCursor;
+//! # async fn example(client: &spiris::Client) -> spiris::Result<()> {
+//! let mut cursor = SyncCursor::load("state/customers.json")?;
+//! for customer in client.customers().sync(&mut cursor).await? {
+//!     println!("changed: {:?}", customer.name);
+//! }
+//! cursor.save("state/customers.json")?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Pushing local data
+//!
+//! The other direction, keeping the API in line with a local system such as
+//! an ERP, starts from a [`LocalSnapshot`] of the records that should exist.
+//! [`plan`] fetches the API's records and works out what to create, update
+//! and, with [`SyncOptions::delete_missing`], delete, matching records by
+//! their [`Syncable::sync_key`]. [`SyncPlan::apply`] then sends the changes,
+//! or with [`SyncOptions::dry_run`] only reports what it would send.
+//!
+//! ```no_run
+//! use spiris::sync::{self, SyncOptions};
+//! use spiris::Customer;
+//! # async fn example(client: &spiris::Client, erp_customers: Vec<Customer>) -> spiris::Result<()> {
+//! let options = SyncOptions::new().delete_missing(true).dry_run(true);
+//! let plan = sync::plan(client, &erp_customers, &options).await?;
+//! let report = plan.apply(client, &options).await;
+//! println!(
+//!     "would create {}, update {}, delete {}",
+//!     report.created.len(),
+//!     report.updated.len(),
+//!     report.deleted.len()
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::diff::{PlannedCreate, PlannedUpdate};
+use crate::error::{Error, Result};
+use crate::types::{Article, ArticleId, Audited, Customer, CustomerId, Supplier};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::future::Future;
+use std::path::Path;
+
+/// The modification time an incremental sync has reached.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncCursor {
+    /// Latest `ModifiedUtc` of the records synced so far; `None` before the
+    /// first sync, which fetches everything.
+    pub high_water_mark: Option<DateTime<Utc>>,
+}
+
+impl SyncCursor {
+    /// A cursor that has not synced anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A cursor that starts syncing from `since`.
+    pub fn starting_at(since: DateTime<Utc>) -> Self {
+        Self {
+            high_water_mark: Some(since),
+        }
+    }
+
+    /// Move the high-water mark past the modification times of `records`.
+    ///
+    /// The mark never moves backwards, and records without a modification
+    /// time are ignored.
+    pub fn advance<'a, T: Audited + 'a>(&mut self, records: impl IntoIterator<Item = &'a T>) {
+        let latest = records.into_iter().filter_map(Audited::modified_utc).max();
+        self.high_water_mark = self.high_water_mark.max(latest);
+    }
+
+    /// Read a cursor saved with [`save`](Self::save), or start a new one if
+    /// `path` doesn't exist.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(Error::InvalidConfig(format!(
+                "failed to read sync cursor {}: {}",
+                path.display(),
+                e
+            ))),
+        }
+    }
+
+    /// Write the cursor to `path` as JSON, creating its directory if needed.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self)?;
+        let write = || -> std::io::Result<()> {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, json + "\n")
+        };
+        write().map_err(|e| {
+            Error::InvalidRequest(format!(
+                "failed to write sync cursor {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+}
+
+/// A record type that [`plan`] can match and [`SyncPlan::apply`] can write.
+pub trait Syncable: Serialize + DeserializeOwned + Clone + Send + Sync {
+    /// ID of a record in the API.
+    type Id: Clone + fmt::Debug + Serialize + Send + Sync;
+
+    /// Key matching a local record with its API counterpart, such as the
+    /// customer number. Local records without a key are always created.
+    fn sync_key(&self) -> Option<String>;
+
+    /// ID of the record in the API.
+    fn sync_id(&self) -> Option<Self::Id>;
+
+    /// Fetch every record of this type from the API.
+    fn fetch_all(client: &Client) -> impl Future<Output = Result<Vec<Self>>> + Send;
+
+    /// Create `record` in the API.
+    fn create(client: &Client, record: &Self) -> impl Future<Output = Result<Self>> + Send;
+
+    /// Replace the record `id` with `record`.
+    fn update(
+        client: &Client,
+        id: &Self::Id,
+        record: &Self,
+    ) -> impl Future<Output = Result<Self>> + Send;
+
+    /// Delete the record `id`.
+    fn delete(client: &Client, id: &Self::Id) -> impl Future<Output = Result<()>> + Send;
+}
+
+macro_rules! impl_syncable {
+    ($type:ty, $id:ty, $key:ident, $endpoint:ident) => {
+        impl Syncable for $type {
+            type Id = $id;
+
+            fn sync_key(&self) -> Option<String> {
+                self.$key.clone()
+            }
+
+            fn sync_id(&self) -> Option<$id> {
+                self.id.clone()
+            }
+
+            async fn fetch_all(client: &Client) -> Result<Vec<Self>> {
+                client.$endpoint().list_all(None).await
+            }
+
+            async fn create(client: &Client, record: &Self) -> Result<Self> {
+                client.$endpoint().create(record).await
+            }
+
+            async fn update(client: &Client, id: &$id, record: &Self) -> Result<Self> {
+                client.$endpoint().update(id, record).await
+            }
+
+            async fn delete(client: &Client, id: &$id) -> Result<()> {
+                client.$endpoint().delete(id).await
+            }
+        }
+    };
+}
+
+impl_syncable!(Customer, CustomerId, customer_number, customers);
+impl_syncable!(Article, ArticleId, article_number, articles);
+impl_syncable!(Supplier, String, supplier_number, suppliers);
+
+/// The records that should exist in the API, as held by the local system.
+pub trait LocalSnapshot<T> {
+    /// Every local record.
+    fn records(&self) -> Result<Vec<T>>;
+}
+
+impl<T: Clone> LocalSnapshot<T> for [T] {
+    fn records(&self) -> Result<Vec<T>> {
+        Ok(self.to_vec())
+    }
+}
+
+impl<T: Clone> LocalSnapshot<T> for Vec<T> {
+    fn records(&self) -> Result<Vec<T>> {
+        Ok(self.clone())
+    }
+}
+
+/// How [`plan`] and [`SyncPlan::apply`] behave.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncOptions {
+    /// Delete API records whose key is missing locally.
+    pub delete_missing: bool,
+    /// Report the changes without sending them.
+    pub dry_run: bool,
+}
+
+impl SyncOptions {
+    /// Options that create and update but never delete.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also delete API records whose key no local record has. Records
+    /// without a key are never deleted.
+    pub fn delete_missing(mut self, delete_missing: bool) -> Self {
+        self.delete_missing = delete_missing;
+        self
+    }
+
+    /// Only report what [`SyncPlan::apply`] would send.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+}
+
+/// An API record with no local counterpart.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedDelete<I> {
+    /// Key of the record.
+    pub key: String,
+    /// ID of the record in the API.
+    pub id: I,
+}
+
+/// Changes bringing the API in line with a [`LocalSnapshot`].
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncPlan<T: Syncable> {
+    /// Records to create.
+    pub creates: Vec<PlannedCreate<T>>,
+    /// Records to update, with the fields that change.
+    pub updates: Vec<PlannedUpdate<T, T::Id>>,
+    /// Records to delete.
+    pub deletes: Vec<PlannedDelete<T::Id>>,
+    /// Number of local records that already match.
+    pub unchanged: usize,
+}
+
+/// Fetch the API's records and plan the changes turning them into `local`.
+pub async fn plan<T: Syncable>(
+    client: &Client,
+    local: &(impl LocalSnapshot<T> + ?Sized),
+    options: &SyncOptions,
+) -> Result<SyncPlan<T>> {
+    let desired = local.records()?;
+    let current = T::fetch_all(client).await?;
+    SyncPlan::between(&desired, &current, options)
+}
+
+impl<T: Syncable> SyncPlan<T> {
+    /// Plan the changes turning the API records `current` into `desired`.
+    ///
+    /// Creates and updates are planned as by [`crate::diff::plan`], so only
+    /// fields set locally are compared. Fails if two local records share a
+    /// key.
+    pub fn between(desired: &[T], current: &[T], options: &SyncOptions) -> Result<Self> {
+        let changes = crate::diff::plan(desired, current, T::sync_key, T::sync_id)?;
+
+        let mut deletes = Vec::new();
+        if options.delete_missing {
+            let local: HashSet<String> = desired.iter().filter_map(T::sync_key).collect();
+            for record in current {
+                let Some(key) = record.sync_key().filter(|key| !local.contains(key)) else {
+                    continue;
+                };
+                let id = record.sync_id().ok_or_else(|| {
+                    Error::InvalidRequest(format!("record {} has no ID in the API", key))
+                })?;
+                deletes.push(PlannedDelete { key, id });
+            }
+        }
+
+        Ok(Self {
+            creates: changes.creates,
+            updates: changes.updates,
+            deletes,
+            unchanged: changes.unchanged,
+        })
+    }
+
+    /// Whether the plan changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.creates.is_empty() && self.updates.is_empty() && self.deletes.is_empty()
+    }
+
+    /// Send the planned changes: creates, then updates, then deletes.
+    ///
+    /// A failed change is recorded in the report and the rest still run.
+    /// With [`SyncOptions::dry_run`] nothing is sent and the report lists
+    /// the records as they would be sent.
+    pub async fn apply(&self, client: &Client, options: &SyncOptions) -> SyncReport<T> {
+        let mut report = SyncReport {
+            dry_run: options.dry_run,
+            created: Vec::new(),
+            updated: Vec::new(),
+            deleted: Vec::new(),
+            unchanged: self.unchanged,
+            failures: Vec::new(),
+        };
+
+        if options.dry_run {
+            report.created = self.creates.iter().map(|c| c.record.clone()).collect();
+            report.updated = self.updates.iter().map(|u| u.record.clone()).collect();
+            report.deleted = self.deletes.iter().map(|d| d.key.clone()).collect();
+            return report;
+        }
+
+        for create in &self.creates {
+            match T::create(client, &create.record).await {
+                Ok(record) => report.created.push(record),
+                Err(error) => report.failures.push(SyncFailure {
+                    action: SyncAction::Create,
+                    key: create.key.clone(),
+                    error,
+                }),
+            }
+        }
+        for update in &self.updates {
+            match T::update(client, &update.id, &update.record).await {
+                Ok(record) => report.updated.push(record),
+                Err(error) => report.failures.push(SyncFailure {
+                    action: SyncAction::Update,
+                    key: Some(update.key.clone()),
+                    error,
+                }),
+            }
+        }
+        for delete in &self.deletes {
+            match T::delete(client, &delete.id).await {
+                Ok(()) => report.deleted.push(delete.key.clone()),
+                Err(error) => report.failures.push(SyncFailure {
+                    action: SyncAction::Delete,
+                    key: Some(delete.key.clone()),
+                    error,
+                }),
+            }
+        }
+
+        report
+    }
+}
+
+/// Kind of change in a [`SyncFailure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncAction {
+    /// Creating a record.
+    Create,
+    /// Updating a record.
+    Update,
+    /// Deleting a record.
+    Delete,
+}
+
+/// A change the API refused.
+#[derive(Debug)]
+pub struct SyncFailure {
+    /// What was attempted.
+    pub action: SyncAction,
+    /// Key of the record, if it has one.
+    pub key: Option<String>,
+    /// The error from the API.
+    pub error: Error,
+}
+
+/// Outcome of [`SyncPlan::apply`].
+#[derive(Debug)]
+pub struct SyncReport<T> {
+    /// Whether this was a dry run and nothing was sent.
+    pub dry_run: bool,
+    /// Records created, as returned by the API.
+    pub created: Vec<T>,
+    /// Records updated, as returned by the API.
+    pub updated: Vec<T>,
+    /// Keys of the records deleted.
+    pub deleted: Vec<String>,
+    /// Number of local records that already matched.
+    pub unchanged: usize,
+    /// Changes the API refused.
+    pub failures: Vec<SyncFailure>,
+}
+
+impl<T> SyncReport<T> {
+    /// Whether every planned change went through.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Customer;
+
+    fn modified(at: &str) -> Customer {
+        Customer {
+            modified_utc: Some(at.parse().unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_advance_keeps_latest_time() {
+        let mut cursor = SyncCursor::new();
+        cursor.advance(&[
+            modified("2025-03-01T10:00:00Z"),
+            Customer::default(),
+            modified("2025-03-02T08:30:00Z"),
+        ]);
+        assert_eq!(
+            cursor.high_water_mark,
+            Some("2025-03-02T08:30:00Z".parse().unwrap())
+        );
+
+        cursor.advance(&[modified("2025-01-01T00:00:00Z")]);
+        cursor.advance(&Vec::<Customer>::new());
+        assert_eq!(
+            cursor.high_water_mark,
+            Some("2025-03-02T08:30:00Z".parse().unwrap())
+        );
+    }
+
+    fn numbered(number: &str, id: Option<&str>, name: &str) -> Customer {
+        Customer {
+            id: id.map(Into::into),
+            customer_number: Some(number.to_string()),
+            name: Some(name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_plan_deletes_only_when_asked() {
+        let current = vec![
+            numbered("1001", Some("c1"), "Acme AB"),
+            numbered("1002", Some("c2"), "Beta AB"),
+            Customer {
+                id: Some("c3".into()),
+                ..Default::default()
+            },
+        ];
+        let desired = vec![
+            numbered("1001", None, "Acme Sverige AB"),
+            numbered("1003", None, "Gamma AB"),
+        ];
+
+        let plan = SyncPlan::between(&desired, &current, &SyncOptions::new()).unwrap();
+        assert_eq!(plan.creates.len(), 1);
+        assert_eq!(plan.updates.len(), 1);
+        assert!(plan.deletes.is_empty());
+
+        let options = SyncOptions::new().delete_missing(true);
+        let plan = SyncPlan::between(&desired, &current, &options).unwrap();
+        assert_eq!(plan.deletes.len(), 1);
+        assert_eq!(plan.deletes[0].key, "1002");
+        assert_eq!(plan.deletes[0].id, CustomerId::from("c2"));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path =
+            std::env::temp_dir().join(format!("spiris-sync-{}/customers.json", std::process::id()));
+        assert_eq!(SyncCursor::load(&path).unwrap(), SyncCursor::new());
+
+        let cursor = SyncCursor::starting_at("2025-03-02T08:30:00Z".parse().unwrap());
+        cursor.save(&path).unwrap();
+        assert_eq!(SyncCursor::load(&path).unwrap(), cursor);
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
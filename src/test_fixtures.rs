@@ -0,0 +1,308 @@
+//! Wiremock fixtures for integration tests.
+//!
+//! Downstream apps can point a [`Client`] at a [`wiremock::MockServer`] and
+//! mount realistic API responses with one call per endpoint instead of
+//! writing their own JSON. Fixture data is deterministic: customer, article
+//! and invoice `n` always have the same id, number and amounts, so tests can
+//! assert on them. The `*_json` functions give the raw API JSON for custom
+//! mocks.
+//!
+//! Requires the `test-fixtures` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use spiris::test_fixtures::{self, mock_customers_list};
+//! use wiremock::MockServer;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let server = MockServer::start().await;
+//! mock_customers_list(&server, 3).await;
+//!
+//! let client = test_fixtures::client(&server);
+//! let page = client.customers().list(None).await?;
+//! assert_eq!(page.data.len(), 3);
+//! assert_eq!(page.data[0].id, Some(test_fixtures::customer_id(1)));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::auth::AccessToken;
+use crate::client::{Client, ClientConfig};
+use crate::retry::RetryConfig;
+use crate::types::{Article, ArticleId, Customer, CustomerId, Invoice, InvoiceId};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const COMPANIES: [(&str, &str, &str); 8] = [
+    ("Acme AB", "Storgatan 1", "Stockholm"),
+    ("Nordisk Handel AB", "Kungsgatan 12", "Göteborg"),
+    ("Fjällbygg AB", "Bergsvägen 4", "Östersund"),
+    ("Sundsvall Konsult HB", "Esplanaden 8", "Sundsvall"),
+    ("Göta Elektriska AB", "Industrivägen 22", "Jönköping"),
+    ("Lindqvist Design", "Södra Förstadsgatan 3", "Malmö"),
+    ("Västkustens Fisk AB", "Hamnplan 2", "Uddevalla"),
+    ("Norrsken Mekaniska AB", "Verkstadsgatan 9", "Luleå"),
+];
+
+const ARTICLES: [(&str, &str, u32); 5] = [
+    ("Konsulttimme", "h", 1150),
+    ("Programvarulicens", "st", 4900),
+    ("Support per månad", "mån", 990),
+    ("Installation", "st", 2500),
+    ("Utbildning heldag", "dag", 8900),
+];
+
+const INVOICE_STATUSES: [(&str, &str, &str); 4] = [
+    ("Draft", "NotSent", "Unpaid"),
+    ("Booked", "Sent", "Unpaid"),
+    ("Booked", "Sent", "PartiallyPaid"),
+    ("Booked", "Sent", "Paid"),
+];
+
+fn fixture_id(kind: u32, n: u32) -> String {
+    format!("{:08x}-0000-4000-8000-{:012}", kind, n)
+}
+
+fn pick<T: Copy, const N: usize>(items: &[T; N], n: u32) -> T {
+    items[n.saturating_sub(1) as usize % N]
+}
+
+/// Id of fixture customer `n`.
+pub fn customer_id(n: u32) -> CustomerId {
+    fixture_id(1, n).into()
+}
+
+/// Id of fixture article `n`.
+pub fn article_id(n: u32) -> ArticleId {
+    fixture_id(2, n).into()
+}
+
+/// Id of fixture invoice `n`.
+pub fn invoice_id(n: u32) -> InvoiceId {
+    fixture_id(3, n).into()
+}
+
+/// API JSON for customer `n`.
+pub fn customer_json(n: u32) -> Value {
+    let (name, street, city) = pick(&COMPANIES, n);
+    json!({
+        "Id": customer_id(n),
+        "CustomerNumber": (1000 + n).to_string(),
+        "CorporateIdentityNumber": format!("556{:03}-{:04}", n % 1000, 1000 + n % 9000),
+        "Name": name,
+        "Email": format!("faktura{}@example.se", n),
+        "Phone": format!("+46 8 {:03} {:02} {:02}", 100 + n % 900, n % 100, (n * 7) % 100),
+        "InvoiceAddress": {
+            "Address1": street,
+            "PostalCode": format!("{} {:02}", 100 + n % 900, n % 100),
+            "City": city,
+            "CountryCode": "SE"
+        },
+        "PaymentTermsInDays": 30,
+        "IsActive": true,
+        "IsPrivatePerson": false,
+        "CreatedUtc": "2024-01-15T10:30:00Z",
+        "ModifiedUtc": "2024-06-20T14:45:00Z"
+    })
+}
+
+/// API JSON for article `n`.
+pub fn article_json(n: u32) -> Value {
+    let (name, unit, price) = pick(&ARTICLES, n);
+    json!({
+        "Id": article_id(n),
+        "ArticleNumber": format!("ART-{:03}", n),
+        "Name": name,
+        "Unit": unit,
+        "SalesPrice": price,
+        "PurchasePrice": price / 2,
+        "IsActive": true,
+        "VatRateId": "vat-25",
+        "CreatedUtc": "2024-01-10T08:00:00Z",
+        "ModifiedUtc": "2024-03-15T14:30:00Z"
+    })
+}
+
+/// API JSON for invoice `n`, billed to customer `n` for article `n`.
+///
+/// Invoices cycle through draft, unpaid, partly paid and paid.
+pub fn invoice_json(n: u32) -> Value {
+    let (name, _, price) = pick(&ARTICLES, n);
+    let (status, sent_status, payment_status) = pick(&INVOICE_STATUSES, n);
+    let quantity = n % 5 + 1;
+    let total = price * quantity;
+    let vat = total / 4;
+    let remaining = match payment_status {
+        "Paid" => 0,
+        "PartiallyPaid" => (total + vat) / 2,
+        _ => total + vat,
+    };
+    let day = n % 28 + 1;
+    json!({
+        "Id": invoice_id(n),
+        "InvoiceNumber": (2000 + n).to_string(),
+        "CustomerId": customer_id(n),
+        "InvoiceDate": format!("2024-06-{:02}", day),
+        "DueDate": format!("2024-07-{:02}", day),
+        "CurrencyCode": "SEK",
+        "Rows": [{
+            "Id": format!("{}-row-1", invoice_id(n)),
+            "ArticleId": article_id(n),
+            "Text": name,
+            "UnitPrice": price,
+            "Quantity": quantity,
+            "VatRateId": "vat-25",
+            "TotalAmount": total
+        }],
+        "TotalAmount": total,
+        "TotalVatAmount": vat,
+        "TotalAmountIncludingVat": total + vat,
+        "RemainingAmount": remaining,
+        "IsSent": sent_status == "Sent",
+        "Status": status,
+        "SentStatus": sent_status,
+        "PaymentStatus": payment_status,
+        "OcrNumber": format!("{}{:06}", 2000 + n, n),
+        "CreatedUtc": format!("2024-06-{:02}T09:00:00Z", day),
+        "ModifiedUtc": format!("2024-06-{:02}T09:00:00Z", day)
+    })
+}
+
+/// Wrap `items` in a single-page list response.
+pub fn paginated_json(items: Vec<Value>) -> Value {
+    let count = items.len();
+    json!({
+        "Data": items,
+        "Meta": {
+            "CurrentPage": 1,
+            "PageSize": count.max(1),
+            "TotalPages": 1,
+            "TotalCount": count,
+            "HasNextPage": false,
+            "HasPreviousPage": false
+        }
+    })
+}
+
+/// API error body for `status`, as sent with a failed request.
+pub fn error_json(status: u16) -> Value {
+    let (code, message) = match status {
+        400 => ("VALIDATION_ERROR", "Validation failed"),
+        401 => ("UNAUTHORIZED", "The access token is invalid or has expired"),
+        403 => ("FORBIDDEN", "The token lacks the scope for this resource"),
+        404 => ("NOT_FOUND", "The requested resource was not found"),
+        409 => ("CONFLICT", "The resource was changed by another request"),
+        429 => ("RATE_LIMIT_EXCEEDED", "Too many requests"),
+        _ => ("INTERNAL_ERROR", "An unexpected error occurred"),
+    };
+    json!({ "ErrorCode": code, "Message": message })
+}
+
+/// A client for `server` with a valid token and retries turned off.
+pub fn client(server: &MockServer) -> Client {
+    Client::with_config(
+        AccessToken::new("test-fixtures-token".to_string(), 3600, None),
+        ClientConfig::new()
+            .base_url(server.uri())
+            .retry_config(RetryConfig::new().max_retries(0)),
+    )
+}
+
+fn parse<T: DeserializeOwned>(value: Value) -> T {
+    serde_json::from_value(value).expect("fixture JSON matches the API types")
+}
+
+/// Answer `GET path` with `items` as a single page.
+pub async fn mock_list(server: &MockServer, list_path: &str, items: Vec<Value>) {
+    Mock::given(method("GET"))
+        .and(path(list_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(paginated_json(items)))
+        .mount(server)
+        .await;
+}
+
+/// Answer `GET path` with `body`.
+pub async fn mock_get(server: &MockServer, item_path: &str, body: Value) {
+    Mock::given(method("GET"))
+        .and(path(item_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(server)
+        .await;
+}
+
+/// Answer `http_method path` with `status` and an API error body.
+pub async fn mock_error(server: &MockServer, http_method: &str, error_path: &str, status: u16) {
+    Mock::given(method(http_method))
+        .and(path(error_path))
+        .respond_with(ResponseTemplate::new(status).set_body_json(error_json(status)))
+        .mount(server)
+        .await;
+}
+
+/// Serve customers `1..=count` from `GET /customers`.
+pub async fn mock_customers_list(server: &MockServer, count: u32) -> Vec<Customer> {
+    let items: Vec<Value> = (1..=count).map(customer_json).collect();
+    mock_list(server, "/customers", items.clone()).await;
+    items.into_iter().map(parse).collect()
+}
+
+/// Serve customer `n` from `GET /customers/{id}`.
+pub async fn mock_customer(server: &MockServer, n: u32) -> Customer {
+    let body = customer_json(n);
+    mock_get(
+        server,
+        &format!("/customers/{}", customer_id(n)),
+        body.clone(),
+    )
+    .await;
+    parse(body)
+}
+
+/// Serve articles `1..=count` from `GET /articles`.
+pub async fn mock_articles_list(server: &MockServer, count: u32) -> Vec<Article> {
+    let items: Vec<Value> = (1..=count).map(article_json).collect();
+    mock_list(server, "/articles", items.clone()).await;
+    items.into_iter().map(parse).collect()
+}
+
+/// Serve article `n` from `GET /articles/{id}`.
+pub async fn mock_article(server: &MockServer, n: u32) -> Article {
+    let body = article_json(n);
+    mock_get(
+        server,
+        &format!("/articles/{}", article_id(n)),
+        body.clone(),
+    )
+    .await;
+    parse(body)
+}
+
+/// Serve invoices `1..=count` from `GET /customerinvoices`.
+pub async fn mock_invoices_list(server: &MockServer, count: u32) -> Vec<Invoice> {
+    let items: Vec<Value> = (1..=count).map(invoice_json).collect();
+    mock_list(server, "/customerinvoices", items.clone()).await;
+    items.into_iter().map(parse).collect()
+}
+
+/// Serve invoice `n` from `GET /customerinvoices/{id}`.
+pub async fn mock_invoice(server: &MockServer, n: u32) -> Invoice {
+    let body = invoice_json(n);
+    let item_path = format!("/customerinvoices/{}", invoice_id(n));
+    mock_get(server, &item_path, body.clone()).await;
+    parse(body)
+}
+
+/// Serve a minimal PDF for invoice `n` from `GET /customerinvoices/{id}/pdf`.
+pub async fn mock_invoice_pdf(server: &MockServer, n: u32) -> Vec<u8> {
+    let pdf = format!("%PDF-1.4\n% Invoice {}\n%%EOF\n", 2000 + n).into_bytes();
+    Mock::given(method("GET"))
+        .and(path(format!("/customerinvoices/{}/pdf", invoice_id(n))))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(pdf.clone(), "application/pdf"))
+        .mount(server)
+        .await;
+    pdf
+}
@@ -0,0 +1,339 @@
+//! Test data for sandbox companies.
+//!
+//! [`seed_sandbox`] fills a sandbox company with customers, articles and
+//! invoices in every status (draft, unpaid, overdue, partly paid and paid)
+//! with dates spread over a period, so downstream apps can be tested against
+//! realistic data. Seeding is deterministic: the same [`SeedPlan`] creates
+//! the same records.
+//!
+//! Requires the `testing` feature.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::testing::{seed_sandbox, SeedPlan};
+//! use spiris::client::SANDBOX_BASE_URL;
+//! use spiris::{AccessToken, Client, ClientConfig};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let token = AccessToken::new("sandbox-token".to_string(), 3600, None);
+//! let client = Client::with_config(token, ClientConfig::new().base_url(SANDBOX_BASE_URL));
+//!
+//! let plan = SeedPlan::new().customers(20).articles(10).invoices(100);
+//! let seeded = seed_sandbox(&client, &plan).await?;
+//! println!("Created {} invoices", seeded.invoices.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::{Client, DEFAULT_BASE_URL};
+use crate::error::{Error, Result};
+use crate::types::{
+    Article, Customer, CustomerInvoiceDraft, CustomerInvoiceDraftRow, Invoice, InvoicePayment,
+    InvoiceRow, Money,
+};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+
+/// Status of a seeded invoice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedStatus {
+    /// Saved as a customer invoice draft.
+    Draft,
+    /// Booked and not yet due.
+    Unpaid,
+    /// Booked and past its due date.
+    Overdue,
+    /// Past due with half of the amount paid.
+    PartlyPaid,
+    /// Paid in full.
+    Paid,
+}
+
+impl SeedStatus {
+    const ALL: [SeedStatus; 5] = [
+        SeedStatus::Draft,
+        SeedStatus::Unpaid,
+        SeedStatus::Overdue,
+        SeedStatus::PartlyPaid,
+        SeedStatus::Paid,
+    ];
+}
+
+/// What [`seed_sandbox`] creates.
+#[derive(Debug, Clone)]
+pub struct SeedPlan {
+    /// Number of customers. Every tenth customer is created inactive.
+    pub customers: usize,
+    /// Number of articles.
+    pub articles: usize,
+    /// Number of invoices, cycling through every [`SeedStatus`].
+    pub invoices: usize,
+    /// Latest invoice date; overdue invoices are overdue relative to this.
+    pub as_of: NaiveDate,
+    /// Invoice dates are spread over this many days before `as_of`.
+    pub period_days: u32,
+    /// Days from invoice date to due date.
+    pub payment_terms_days: u32,
+    /// Prefix of customer and article numbers, so seeded records can be told
+    /// apart from others.
+    pub number_prefix: String,
+}
+
+impl Default for SeedPlan {
+    fn default() -> Self {
+        Self {
+            customers: 10,
+            articles: 5,
+            invoices: 25,
+            as_of: Utc::now().date_naive(),
+            period_days: 180,
+            payment_terms_days: 30,
+            number_prefix: "SEED".to_string(),
+        }
+    }
+}
+
+impl SeedPlan {
+    /// Create a plan with the default sizes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of customers.
+    pub fn customers(mut self, count: usize) -> Self {
+        self.customers = count;
+        self
+    }
+
+    /// Set the number of articles.
+    pub fn articles(mut self, count: usize) -> Self {
+        self.articles = count;
+        self
+    }
+
+    /// Set the number of invoices.
+    pub fn invoices(mut self, count: usize) -> Self {
+        self.invoices = count;
+        self
+    }
+
+    /// Set the latest invoice date.
+    pub fn as_of(mut self, date: NaiveDate) -> Self {
+        self.as_of = date;
+        self
+    }
+
+    /// Set the number of days invoice dates are spread over.
+    pub fn period_days(mut self, days: u32) -> Self {
+        self.period_days = days;
+        self
+    }
+
+    /// Set the prefix of customer and article numbers.
+    pub fn number_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.number_prefix = prefix.into();
+        self
+    }
+
+    /// Status and invoice date of the invoice at `index`.
+    fn invoice_slot(&self, index: usize) -> (SeedStatus, NaiveDate) {
+        let status = SeedStatus::ALL[index % SeedStatus::ALL.len()];
+        let terms = i64::from(self.payment_terms_days);
+        let period = i64::from(self.period_days.max(1));
+        let offset = (index as i64 * 7) % period;
+
+        let days_back = match status {
+            // Not yet due
+            SeedStatus::Unpaid => offset % terms.max(1),
+            // Due date before `as_of`
+            SeedStatus::Overdue | SeedStatus::PartlyPaid => {
+                terms + 1 + offset % (period - terms).max(1)
+            }
+            SeedStatus::Draft | SeedStatus::Paid => offset,
+        };
+        (status, self.as_of - Duration::days(days_back))
+    }
+
+    fn check(&self) -> Result<()> {
+        if self.invoices > 0 && (self.customers == 0 || self.articles == 0) {
+            return Err(Error::InvalidRequest(
+                "seeding invoices needs at least one customer and one article".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Records created by [`seed_sandbox`].
+#[derive(Debug, Clone, Default)]
+pub struct SeedReport {
+    /// Created customers.
+    pub customers: Vec<Customer>,
+    /// Created articles.
+    pub articles: Vec<Article>,
+    /// Created invoice drafts.
+    pub drafts: Vec<CustomerInvoiceDraft>,
+    /// Created invoices with their status.
+    pub invoices: Vec<(SeedStatus, Invoice)>,
+}
+
+fn at_noon(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::from_hms_opt(12, 0, 0).unwrap())
+        .and_utc()
+}
+
+fn created_id<I: Clone>(id: &Option<I>, what: &str) -> Result<I> {
+    id.clone()
+        .ok_or_else(|| Error::InvalidRequest(format!("created {} has no id", what)))
+}
+
+/// Populate a sandbox company according to `plan`.
+///
+/// Refuses to run against the production API. Records are created one at a
+/// time; on failure the error is returned and records created so far remain.
+pub async fn seed_sandbox(client: &Client, plan: &SeedPlan) -> Result<SeedReport> {
+    if client.base_url().trim_end_matches('/') == DEFAULT_BASE_URL.trim_end_matches('/') {
+        return Err(Error::InvalidConfig(
+            "refusing to seed the production API; use a sandbox base URL".to_string(),
+        ));
+    }
+    plan.check()?;
+
+    let mut report = SeedReport::default();
+
+    for i in 1..=plan.customers {
+        let customer = Customer {
+            customer_number: Some(format!("{}{:04}", plan.number_prefix, i)),
+            name: Some(format!("Seed Customer {}", i)),
+            email: Some(format!("customer{}@example.com", i)),
+            is_active: Some(i % 10 != 0),
+            payment_terms_in_days: Some(plan.payment_terms_days),
+            ..Default::default()
+        };
+        report
+            .customers
+            .push(client.customers().create(&customer).await?);
+    }
+
+    for i in 1..=plan.articles {
+        let article = Article {
+            article_number: Some(format!("{}-A{:03}", plan.number_prefix, i)),
+            name: Some(format!("Seed Article {}", i)),
+            unit: Some("st".to_string()),
+            sales_price: Some(Money::from(100 + 25 * (i % 8) as i32)),
+            is_active: Some(true),
+            ..Default::default()
+        };
+        report
+            .articles
+            .push(client.articles().create(&article).await?);
+    }
+
+    let active_customers: Vec<&Customer> = report
+        .customers
+        .iter()
+        .filter(|c| c.is_active != Some(false))
+        .collect();
+    let mut invoices = Vec::new();
+    let mut drafts = Vec::new();
+
+    for i in 0..plan.invoices {
+        let customer_id = created_id(&active_customers[i % active_customers.len()].id, "customer")?;
+        let article = &report.articles[i % report.articles.len()];
+        let article_id = created_id(&article.id, "article")?;
+        let quantity = Money::from(1 + (i % 5) as i32);
+        let (status, date) = plan.invoice_slot(i);
+        let due = date + Duration::days(i64::from(plan.payment_terms_days));
+
+        if status == SeedStatus::Draft {
+            let draft = CustomerInvoiceDraft {
+                customer_id: Some(customer_id),
+                invoice_date: Some(date),
+                due_date: Some(due),
+                rows: vec![CustomerInvoiceDraftRow {
+                    article_id: Some(article_id),
+                    unit_price: article.sales_price,
+                    quantity: Some(quantity),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            };
+            drafts.push(client.customer_invoice_drafts().create(&draft).await?);
+            continue;
+        }
+
+        let invoice = Invoice {
+            customer_id: Some(customer_id),
+            invoice_date: Some(date),
+            due_date: Some(due),
+            rows: vec![InvoiceRow {
+                article_id: Some(article_id),
+                unit_price: article.sales_price,
+                quantity: Some(quantity),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let created = client.invoices().create(&invoice).await?;
+
+        let total = created
+            .total_amount_including_vat
+            .or(created.total_amount)
+            .unwrap_or_default();
+        let paid = match status {
+            SeedStatus::Paid => Some(total),
+            SeedStatus::PartlyPaid => Some(total / Money::from(2)),
+            _ => None,
+        };
+        if let Some(amount) = paid.filter(|a| *a > Money::default()) {
+            let payment = InvoicePayment {
+                amount: Some(amount),
+                payment_date: Some(at_noon(due.min(plan.as_of))),
+                ..Default::default()
+            };
+            let id = created_id(&created.id, "invoice")?;
+            client.invoices().register_payment(&id, &payment).await?;
+        }
+        invoices.push((status, created));
+    }
+
+    report.drafts = drafts;
+    report.invoices = invoices;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invoice_slots_span_statuses_and_dates() {
+        let plan = SeedPlan::new()
+            .as_of(NaiveDate::from_ymd_opt(2025, 6, 30).unwrap())
+            .period_days(120);
+        let terms = Duration::days(30);
+
+        for index in 0..50 {
+            let (status, date) = plan.invoice_slot(index);
+            assert!(date <= plan.as_of);
+            assert!(date > plan.as_of - Duration::days(121), "{}", date);
+            match status {
+                SeedStatus::Unpaid => assert!(date + terms >= plan.as_of),
+                SeedStatus::Overdue | SeedStatus::PartlyPaid => {
+                    assert!(date + terms < plan.as_of)
+                }
+                _ => {}
+            }
+        }
+
+        let statuses: Vec<SeedStatus> = (0..5).map(|i| plan.invoice_slot(i).0).collect();
+        assert_eq!(statuses, SeedStatus::ALL);
+    }
+
+    #[test]
+    fn test_plan_needs_customers_and_articles_for_invoices() {
+        assert!(SeedPlan::new().articles(0).check().is_err());
+        assert!(SeedPlan::new().customers(0).check().is_err());
+        assert!(SeedPlan::new().invoices(0).articles(0).check().is_ok());
+    }
+}
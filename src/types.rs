@@ -1,37 +1,29 @@
 //! Common types and data models for the Visma eAccounting API.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Type alias for monetary values.
 ///
-/// When the `decimal` feature is enabled, this is `rust_decimal::Decimal` for precise
-/// financial calculations. Otherwise, it defaults to `f64` for simplicity.
+/// With the default `decimal` feature this is `rust_decimal::Decimal`, so
+/// prices, amounts and VAT add up to the öre like the API's totals. It
+/// (de)serializes as a JSON number, as the API sends it.
 ///
-/// # Example with decimal feature
-///
-/// ```toml
-/// [dependencies]
-/// spiris = { version = "0.1", features = ["decimal"] }
 /// ```
+/// use spiris::{money, Money};
 ///
-/// ```ignore
-/// use spiris::Money;
-/// use rust_decimal_macros::dec;
-///
-/// let price: Money = dec!(100.50);
-/// let quantity: Money = dec!(3);
-/// let total = price * quantity;  // Precise calculation
-/// assert_eq!(total, dec!(301.50));
+/// let price: Money = money!(100.10);
+/// let total = price * money!(3);
+/// assert_eq!(total, money!(300.30));
 /// ```
 #[cfg(feature = "decimal")]
 pub type Money = rust_decimal::Decimal;
 
 /// Type alias for monetary values.
 ///
-/// This is the default `f64` representation. For precise financial calculations,
-/// enable the `decimal` feature to use `rust_decimal::Decimal` instead.
+/// Without the `decimal` feature (`default-features = false`), amounts are
+/// `f64` and sums may be off by rounding errors.
 #[cfg(not(feature = "decimal"))]
 pub type Money = f64;
 
@@ -49,17 +41,336 @@ pub type Money = f64;
 /// ```
 #[macro_export]
 macro_rules! money {
-    ($val:expr) => {{
+    ($val:expr) => {
+        $crate::types::__money_from_literal(stringify!($val))
+    };
+}
+
+/// Parse a [`money!`] literal. The representation depends on this crate's
+/// `decimal` feature, not the calling crate's.
+#[doc(hidden)]
+pub fn __money_from_literal(literal: &str) -> Money {
+    let literal: String = literal.chars().filter(|c| !c.is_whitespace()).collect();
+    literal.parse().expect("Invalid money literal")
+}
+
+/// Calendar dates such as invoice and due dates.
+///
+/// The API sends them as midnight timestamps (`2025-03-14T00:00:00`, with or
+/// without an offset). The date is taken as written, never converted between
+/// time zones, and sent back as `2025-03-14`.
+mod api_date {
+    use chrono::NaiveDate;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        date: &Option<NaiveDate>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match date {
+            Some(date) => serializer.collect_str(&date.format("%Y-%m-%d")),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<NaiveDate>, D::Error> {
+        let Some(value) = Option::<String>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+        let date = value.get(..10).unwrap_or(&value);
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(Some)
+            .map_err(|_| de::Error::custom(format!("invalid date '{}'", value)))
+    }
+}
+
+/// Amounts, written as JSON numbers whichever type [`Money`] is.
+///
+/// `Decimal` serializes as a string by default, which would change what is
+/// sent to the API. Reading accepts numbers and strings.
+pub(crate) mod money_serde {
+    use super::Money;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Money, serializer: S) -> Result<S::Ok, S::Error> {
         #[cfg(feature = "decimal")]
         {
-            use std::str::FromStr;
-            rust_decimal::Decimal::from_str(stringify!($val)).expect("Invalid decimal literal")
+            rust_decimal::serde::float::serialize(value, serializer)
         }
         #[cfg(not(feature = "decimal"))]
         {
-            $val as f64
+            serializer.serialize_f64(*value)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Money, D::Error> {
+        <Money as Deserialize>::deserialize(deserializer)
+    }
+
+    /// The same for `Option<Money>`.
+    pub mod option {
+        use super::Money;
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        struct AsNumber<'a>(&'a Money);
+
+        impl Serialize for AsNumber<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                super::serialize(self.0, serializer)
+            }
+        }
+
+        pub fn serialize<S: Serializer>(
+            value: &Option<Money>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(value) => serializer.serialize_some(&AsNumber(value)),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Money>, D::Error> {
+            Option::<Money>::deserialize(deserializer)
+        }
+    }
+}
+
+macro_rules! entity_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Wrap an ID received from the API or stored elsewhere.
+            pub fn new(id: impl Into<String>) -> Self {
+                Self(id.into())
+            }
+
+            /// The ID as a string.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// The ID as an owned string.
+            pub fn into_string(self) -> String {
+                self.0
+            }
+        }
+
+        impl std::ops::Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::borrow::Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(id: String) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(id: &str) -> Self {
+                Self(id.to_string())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
         }
-    }};
+    };
+}
+
+entity_id! {
+    /// ID (GUID) of a [`Customer`].
+    ///
+    /// The ID types keep a customer ID from being passed where an invoice
+    /// or article ID is expected:
+    ///
+    /// ```compile_fail
+    /// # async fn example(client: &spiris::Client, id: spiris::CustomerId) {
+    /// client.articles().get(&id).await;
+    /// # }
+    /// ```
+    ///
+    /// IDs from the API arrive typed; wrap others with `CustomerId::from`.
+    CustomerId
+}
+
+entity_id! {
+    /// ID (GUID) of a customer [`Invoice`].
+    InvoiceId
+}
+
+entity_id! {
+    /// ID (GUID) of an [`Article`].
+    ArticleId
+}
+
+/// Status value as sent by the API: normally a string, but a number is
+/// accepted so that a changed representation ends up in `Unknown` instead of
+/// failing the whole response.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawStatus {
+    Text(String),
+    Number(i64),
+}
+
+impl From<RawStatus> for String {
+    fn from(raw: RawStatus) -> Self {
+        match raw {
+            RawStatus::Text(text) => text,
+            RawStatus::Number(number) => number.to_string(),
+        }
+    }
+}
+
+macro_rules! api_status {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $value:literal,)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)+
+            /// A value not known to this version of the crate.
+            Unknown(String),
+        }
+
+        impl $name {
+            /// The value as sent by the API.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)+
+                    Self::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => Self::$variant,)+
+                    other => Self::Unknown(other.to_string()),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::from(RawStatus::deserialize(deserializer)?);
+                Ok(Self::from(value.as_str()))
+            }
+        }
+    };
+}
+
+api_status! {
+    /// Bookkeeping status of a customer [`Invoice`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spiris::InvoiceStatus;
+    ///
+    /// assert_eq!(InvoiceStatus::from("Booked"), InvoiceStatus::Booked);
+    /// assert_eq!(
+    ///     InvoiceStatus::from("Archived"),
+    ///     InvoiceStatus::Unknown("Archived".to_string())
+    /// );
+    /// ```
+    InvoiceStatus {
+        /// Not yet booked.
+        Draft = "Draft",
+        /// Booked in the ledger.
+        Booked = "Booked",
+        /// Fully credited by a credit invoice.
+        Credited = "Credited",
+        /// Cancelled.
+        Cancelled = "Cancelled",
+    }
+}
+
+api_status! {
+    /// Whether a customer [`Invoice`] has reached the customer.
+    SentStatus {
+        /// Not sent.
+        NotSent = "NotSent",
+        /// Sent by email, e-invoice or post.
+        Sent = "Sent",
+        /// Sending failed, e.g. the e-invoice was rejected.
+        Failed = "Failed",
+    }
+}
+
+api_status! {
+    /// How much of a customer [`Invoice`] has been paid.
+    PaymentStatus {
+        /// Nothing paid.
+        Unpaid = "Unpaid",
+        /// Some, but not all, of the amount paid.
+        PartiallyPaid = "PartiallyPaid",
+        /// Fully paid.
+        Paid = "Paid",
+    }
 }
 
 /// Pagination parameters for list requests.
@@ -72,6 +383,10 @@ pub struct PaginationParams {
     /// Page size (default: 50, max: 500).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pagesize: Option<u32>,
+
+    /// Which records to include by their `IsActive` flag.
+    #[serde(rename = "filter", skip_serializing_if = "ListScope::is_all")]
+    pub scope: ListScope,
 }
 
 impl PaginationParams {
@@ -91,6 +406,106 @@ impl PaginationParams {
         self.pagesize = Some(pagesize);
         self
     }
+
+    /// Only list active or inactive records.
+    ///
+    /// Applies to resources with an `IsActive` flag, such as customers,
+    /// suppliers, articles and accounts.
+    pub fn scope(mut self, scope: ListScope) -> Self {
+        self.scope = scope;
+        self
+    }
+}
+
+/// Page size and item limit for an endpoint's `list_all_with`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListAllOptions {
+    /// Number of items per page request (max: 500).
+    pub page_size: u32,
+
+    /// Fail rather than fetch more than this many items.
+    pub max_items: Option<usize>,
+}
+
+impl Default for ListAllOptions {
+    fn default() -> Self {
+        Self {
+            page_size: 500,
+            max_items: None,
+        }
+    }
+}
+
+impl ListAllOptions {
+    /// Create options with pages of 500 items and no item limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the page size.
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Fail with [`Error::InvalidRequest`](crate::Error::InvalidRequest)
+    /// instead of fetching more than `max_items` items, so an unexpectedly
+    /// broad query can't load the whole company into memory.
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+}
+
+/// Which records a list or lookup includes, by their `IsActive` flag.
+///
+/// Deactivated records stay in the API and often share a number or name with
+/// their replacement, so lookups that should hit a single record use
+/// [`ListScope::ActiveOnly`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListScope {
+    /// Active records only.
+    ActiveOnly,
+    /// Active and inactive records (the API default).
+    #[default]
+    All,
+    /// Inactive records only.
+    InactiveOnly,
+}
+
+impl ListScope {
+    /// The filter selecting this scope, if any.
+    pub fn filter(self) -> Option<crate::query::Filter> {
+        match self {
+            ListScope::ActiveOnly => Some(crate::query::Filter::field("IsActive").eq(true)),
+            ListScope::All => None,
+            ListScope::InactiveOnly => Some(crate::query::Filter::field("IsActive").eq(false)),
+        }
+    }
+
+    /// Whether a record with this `IsActive` value is in scope.
+    ///
+    /// Records without the flag are treated as active.
+    pub fn includes(self, is_active: Option<bool>) -> bool {
+        match self {
+            ListScope::ActiveOnly => is_active != Some(false),
+            ListScope::All => true,
+            ListScope::InactiveOnly => is_active == Some(false),
+        }
+    }
+
+    fn is_all(&self) -> bool {
+        *self == ListScope::All
+    }
+}
+
+impl Serialize for ListScope {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.filter() {
+            Some(filter) => serializer.serialize_str(filter.as_str()),
+            None => serializer.serialize_none(),
+        }
+    }
 }
 
 /// Response wrapper for paginated list requests.
@@ -104,6 +519,84 @@ pub struct PaginatedResponse<T> {
     pub meta: ResponseMetadata,
 }
 
+impl<T> PaginatedResponse<T> {
+    /// Total number of items across all pages.
+    pub fn total_count(&self) -> u32 {
+        self.meta.total_count
+    }
+
+    /// Total number of pages.
+    pub fn total_pages(&self) -> u32 {
+        self.meta.total_pages
+    }
+
+    /// Whether there is a page after this one.
+    pub fn has_next_page(&self) -> bool {
+        self.meta.has_next_page
+    }
+
+    /// Parameters requesting the page after this one, with the same page
+    /// size, or `None` on the last page.
+    ///
+    /// The response doesn't say which [`ListScope`] was requested, so set it
+    /// again with [`PaginationParams::scope`] if the first request had one.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use spiris::{AccessToken, Client, PaginationParams};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+    /// let mut params = Some(PaginationParams::new().pagesize(100));
+    /// while let Some(current) = params {
+    ///     let page = client.customers().list(Some(current)).await?;
+    ///     println!("{} of {} customers", page.data.len(), page.total_count());
+    ///     params = page.next_params();
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn next_params(&self) -> Option<PaginationParams> {
+        if !self.meta.has_next_page || self.data.is_empty() {
+            return None;
+        }
+        Some(
+            PaginationParams::new()
+                .page(self.meta.current_page + 1)
+                .pagesize(self.meta.page_size),
+        )
+    }
+}
+
+/// An entity together with the `ETag` the API returned for it.
+///
+/// Fetch with an endpoint's `get_versioned` and pass back to
+/// `update_if_match`, which fails with
+/// [`Error::Conflict`](crate::Error::Conflict) if someone else changed the
+/// entity in between. Derefs to the entity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Versioned<T> {
+    /// The entity.
+    pub value: T,
+    /// Version of the entity on the server; `None` if the API sent none.
+    pub etag: Option<String>,
+}
+
+impl<T> std::ops::Deref for Versioned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> std::ops::DerefMut for Versioned<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
 /// Metadata included in API responses.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -133,7 +626,7 @@ pub struct ResponseMetadata {
 pub struct Customer {
     /// Unique identifier.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub id: Option<String>,
+    pub id: Option<CustomerId>,
 
     /// Customer number.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -171,6 +664,14 @@ pub struct Customer {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub delivery_address: Option<Address>,
 
+    /// Delivery method ID (see [`DeliveryMethod`]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_method_id: Option<String>,
+
+    /// Delivery terms ID (see [`DeliveryTerm`]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_term_id: Option<String>,
+
     /// Payment terms in days.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payment_terms_in_days: Option<u32>,
@@ -183,6 +684,14 @@ pub struct Customer {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_private_person: Option<bool>,
 
+    /// VAT registration number, e.g. `DE123456789`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vat_number: Option<String>,
+
+    /// Free-text note.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+
     /// When the customer was created.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_utc: Option<DateTime<Utc>>,
@@ -190,6 +699,14 @@ pub struct Customer {
     /// When the customer was last modified.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub modified_utc: Option<DateTime<Utc>>,
+
+    /// ID of the user who created the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_user_id: Option<String>,
+
+    /// ID of the user who last modified the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_by_user_id: Option<String>,
 }
 
 /// Address information.
@@ -223,7 +740,7 @@ pub struct Address {
 pub struct Invoice {
     /// Unique identifier.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub id: Option<String>,
+    pub id: Option<InvoiceId>,
 
     /// Invoice number.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -231,19 +748,27 @@ pub struct Invoice {
 
     /// Customer ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub customer_id: Option<String>,
+    pub customer_id: Option<CustomerId>,
 
     /// Invoice date.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub invoice_date: Option<DateTime<Utc>>,
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub invoice_date: Option<NaiveDate>,
 
     /// Due date.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub due_date: Option<DateTime<Utc>>,
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
 
     /// Delivery date.
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub delivery_date: Option<NaiveDate>,
+
+    /// Delivery method ID (see [`DeliveryMethod`]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_method_id: Option<String>,
+
+    /// Delivery terms ID (see [`DeliveryTerm`]).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub delivery_date: Option<DateTime<Utc>>,
+    pub delivery_term_id: Option<String>,
 
     /// Currency code (ISO 4217).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -253,25 +778,88 @@ pub struct Invoice {
     pub rows: Vec<InvoiceRow>,
 
     /// Total amount excluding VAT.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_amount: Option<Money>,
 
     /// Total VAT amount.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_vat_amount: Option<Money>,
 
     /// Total amount including VAT.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_amount_including_vat: Option<Money>,
 
+    /// Amount still to be paid.
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub remaining_amount: Option<Money>,
+
     /// Whether the invoice is sent.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_sent: Option<bool>,
 
+    /// Bookkeeping status.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<InvoiceStatus>,
+
+    /// Delivery status of the invoice to the customer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_status: Option<SentStatus>,
+
+    /// Payment status.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_status: Option<PaymentStatus>,
+
+    /// OCR payment reference.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ocr_number: Option<String>,
+
+    /// Highest reminder level sent for the invoice.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reminder_level: Option<ReminderLevel>,
+
+    /// When the last reminder was sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_reminder_date: Option<DateTime<Utc>>,
+
     /// Remarks/notes.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub remarks: Option<String>,
 
+    /// Whether reverse charge on construction services (omvänd byggmoms) applies.
+    ///
+    /// Use [`crate::vat::ReverseChargeConstruction`] to set this together with
+    /// the buyer's VAT number, zero-VAT rows and the mandated invoice text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverse_charge_on_construction_services: Option<bool>,
+
+    /// The buyer's VAT registration number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_vat_number: Option<String>,
+
+    /// Whether the invoice is the middle sale in an EU triangular trade
+    /// (trepartshandel).
+    ///
+    /// Use [`crate::vat::IntraEuSale`] to set this together with the buyer's
+    /// VAT number and VAT free rows.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eu_third_party: Option<bool>,
+
     /// When the invoice was created.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_utc: Option<DateTime<Utc>>,
@@ -279,6 +867,14 @@ pub struct Invoice {
     /// When the invoice was last modified.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub modified_utc: Option<DateTime<Utc>>,
+
+    /// ID of the user who created the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_user_id: Option<String>,
+
+    /// ID of the user who last modified the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_by_user_id: Option<String>,
 }
 
 /// Invoice row/line item.
@@ -291,30 +887,50 @@ pub struct InvoiceRow {
 
     /// Article/product ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub article_id: Option<String>,
+    pub article_id: Option<ArticleId>,
 
     /// Description/text.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
 
     /// Unit price.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unit_price: Option<Money>,
 
     /// Quantity.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub quantity: Option<Money>,
 
     /// Discount percentage (0-100).
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub discount_percentage: Option<Money>,
 
     /// VAT rate ID.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub vat_rate_id: Option<String>,
 
-    /// Total amount for this row.
+    /// Whether the row is exempt from VAT (e.g. reverse charge).
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_vat_free: Option<bool>,
+
+    /// Total amount for this row.
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_amount: Option<Money>,
 }
 
@@ -324,7 +940,7 @@ pub struct InvoiceRow {
 pub struct Article {
     /// Unique identifier.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub id: Option<String>,
+    pub id: Option<ArticleId>,
 
     /// Article number.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -339,11 +955,19 @@ pub struct Article {
     pub unit: Option<String>,
 
     /// Sales price.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub sales_price: Option<Money>,
 
     /// Purchase price.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub purchase_price: Option<Money>,
 
     /// Whether the article is active.
@@ -361,6 +985,14 @@ pub struct Article {
     /// When the article was last modified.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub modified_utc: Option<DateTime<Utc>>,
+
+    /// ID of the user who created the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_user_id: Option<String>,
+
+    /// ID of the user who last modified the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_by_user_id: Option<String>,
 }
 
 // =============================================================================
@@ -377,19 +1009,19 @@ pub struct CustomerInvoiceDraft {
 
     /// Customer ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub customer_id: Option<String>,
+    pub customer_id: Option<CustomerId>,
 
     /// Invoice date.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub invoice_date: Option<DateTime<Utc>>,
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub invoice_date: Option<NaiveDate>,
 
     /// Due date.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub due_date: Option<DateTime<Utc>>,
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
 
     /// Delivery date.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub delivery_date: Option<DateTime<Utc>>,
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub delivery_date: Option<NaiveDate>,
 
     /// Currency code (ISO 4217).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -400,15 +1032,27 @@ pub struct CustomerInvoiceDraft {
     pub rows: Vec<CustomerInvoiceDraftRow>,
 
     /// Total amount excluding VAT.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_amount: Option<Money>,
 
     /// Total VAT amount.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_vat_amount: Option<Money>,
 
     /// Total amount including VAT.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_amount_including_vat: Option<Money>,
 
     /// Remarks/notes.
@@ -430,6 +1074,14 @@ pub struct CustomerInvoiceDraft {
     /// When the draft was last modified.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub modified_utc: Option<DateTime<Utc>>,
+
+    /// ID of the user who created the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_user_id: Option<String>,
+
+    /// ID of the user who last modified the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_by_user_id: Option<String>,
 }
 
 /// Customer invoice draft row/line item.
@@ -442,22 +1094,34 @@ pub struct CustomerInvoiceDraftRow {
 
     /// Article/product ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub article_id: Option<String>,
+    pub article_id: Option<ArticleId>,
 
     /// Description/text.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
 
     /// Unit price.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unit_price: Option<Money>,
 
     /// Quantity.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub quantity: Option<Money>,
 
     /// Discount percentage (0-100).
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub discount_percentage: Option<Money>,
 
     /// VAT rate ID.
@@ -465,7 +1129,11 @@ pub struct CustomerInvoiceDraftRow {
     pub vat_rate_id: Option<String>,
 
     /// Total amount for this row.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_amount: Option<Money>,
 }
 
@@ -492,14 +1160,18 @@ pub struct CustomerLedgerItem {
 
     /// Customer ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub customer_id: Option<String>,
+    pub customer_id: Option<CustomerId>,
 
     /// Customer invoice ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub customer_invoice_id: Option<String>,
+    pub customer_invoice_id: Option<InvoiceId>,
 
     /// Amount in currency.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub currency_amount: Option<Money>,
 
     /// Currency code.
@@ -507,7 +1179,11 @@ pub struct CustomerLedgerItem {
     pub currency_code: Option<String>,
 
     /// Amount in domestic currency.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub amount: Option<Money>,
 
     /// Payment date.
@@ -561,7 +1237,11 @@ pub struct CustomerLabel {
 #[serde(rename_all = "PascalCase")]
 pub struct InvoicePayment {
     /// Payment amount.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub amount: Option<Money>,
 
     /// Payment date.
@@ -577,8 +1257,327 @@ pub struct InvoicePayment {
     pub payment_reference_number: Option<String>,
 
     /// Currency rate (exchange rate).
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub currency_rate: Option<Money>,
+}
+
+/// Reminder level of an overdue invoice.
+///
+/// Levels count from 1, one per reminder sent. The API reports level 0,
+/// [`ReminderLevel::NONE`], for an invoice that has not been reminded.
+///
+/// # Example
+///
+/// ```
+/// use spiris::ReminderLevel;
+///
+/// let level = ReminderLevel::NONE.next();
+/// assert_eq!(level, ReminderLevel::FIRST);
+/// assert_eq!(level.next().get(), 2);
+/// ```
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct ReminderLevel(u8);
+
+impl ReminderLevel {
+    /// No reminder sent.
+    pub const NONE: Self = Self(0);
+    /// The first reminder.
+    pub const FIRST: Self = Self(1);
+
+    /// Level `level`, where 1 is the first reminder.
+    pub const fn new(level: u8) -> Self {
+        Self(level)
+    }
+
+    /// The level as a number.
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+
+    /// The level after this one.
+    pub const fn next(self) -> Self {
+        Self(self.0.saturating_add(1))
+    }
+
+    /// Whether no reminder has been sent.
+    pub const fn is_none(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl std::fmt::Display for ReminderLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Payment reminder for an overdue customer invoice.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct InvoiceReminder {
+    /// Unique identifier.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Customer invoice the reminder belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_invoice_id: Option<InvoiceId>,
+
+    /// Reminder level, starting at 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<ReminderLevel>,
+
+    /// Reminder fee added to the invoice.
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee: Option<Money>,
+
+    /// Text printed on the reminder.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    /// When the reminder was sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reminder_date: Option<DateTime<Utc>>,
+}
+
+/// Options for [`InvoicesEndpoint::create_with`](crate::endpoints::invoices::InvoicesEndpoint::create_with).
+///
+/// # Example
+///
+/// ```
+/// use spiris::CreateInvoiceOptions;
+///
+/// let options = CreateInvoiceOptions::new().generate_ocr(true);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CreateInvoiceOptions {
+    /// Set an OCR reference generated from the invoice number if the
+    /// invoice has none. See [`OcrReference`](crate::validation::OcrReference).
+    pub generate_ocr: bool,
+}
+
+impl CreateInvoiceOptions {
+    /// Create options that change nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generate an OCR reference from the invoice number.
+    pub fn generate_ocr(mut self, generate: bool) -> Self {
+        self.generate_ocr = generate;
+        self
+    }
+}
+
+/// Channel used by [`InvoicesEndpoint::send`](crate::endpoints::invoices::InvoicesEndpoint::send).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InvoiceDelivery {
+    /// Email the invoice PDF.
+    #[default]
+    Email,
+    /// Send as an e-invoice to the customer's e-invoice address.
+    EInvoice,
+}
+
+/// Options for sending a customer invoice.
+///
+/// # Example
+///
+/// ```
+/// use spiris::SendInvoiceOptions;
+///
+/// let options = SendInvoiceOptions::email()
+///     .recipient("ap@customer.example")
+///     .copy_to_self(true);
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SendInvoiceOptions {
+    /// Channel to send through.
+    #[serde(skip)]
+    pub delivery: InvoiceDelivery,
+
+    /// Email address to send to instead of the customer's invoice email.
+    #[serde(rename = "EmailAddress", skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+
+    /// Send a copy to the company's own email address.
+    #[serde(rename = "SendCopyToSender", skip_serializing_if = "Option::is_none")]
+    pub copy_to_self: Option<bool>,
+
+    /// Email subject.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+
+    /// Email message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl SendInvoiceOptions {
+    /// Send by email to the customer's invoice email address.
+    pub fn email() -> Self {
+        Self::default()
+    }
+
+    /// Send as an e-invoice.
+    pub fn einvoice() -> Self {
+        Self {
+            delivery: InvoiceDelivery::EInvoice,
+            ..Default::default()
+        }
+    }
+
+    /// Email a different address than the customer's.
+    pub fn recipient(mut self, email: impl Into<String>) -> Self {
+        self.recipient = Some(email.into());
+        self
+    }
+
+    /// Send a copy to the company's own email address.
+    pub fn copy_to_self(mut self, copy: bool) -> Self {
+        self.copy_to_self = Some(copy);
+        self
+    }
+
+    /// Set the email subject.
+    pub fn subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = Some(subject.into());
+        self
+    }
+
+    /// Set the email message.
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Whether any email-only option is set.
+    pub(crate) fn has_email_options(&self) -> bool {
+        self.recipient.is_some()
+            || self.copy_to_self.is_some()
+            || self.subject.is_some()
+            || self.message.is_some()
+    }
+}
+
+/// Payment registered on a supplier invoice.
+///
+/// `amount` is in the invoice currency. For foreign currency invoices set
+/// the exchange rate with [`foreign_currency`](Self::foreign_currency); the
+/// amount in the domestic currency is then derived from it unless given
+/// explicitly. An amount below the remaining balance registers a partial
+/// payment.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SupplierInvoicePayment {
+    /// Unique identifier.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Supplier invoice the payment belongs to.
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub supplier_invoice_id: Option<String>,
+
+    /// Payment date.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_date: Option<DateTime<Utc>>,
+
+    /// Paid amount in the invoice currency.
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub amount: Option<Money>,
+
+    /// Paid amount in the domestic currency.
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub amount_in_domestic_currency: Option<Money>,
+
+    /// Currency code (ISO 4217) of the payment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency_code: Option<String>,
+
+    /// Exchange rate used for the payment.
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub currency_rate: Option<Money>,
+
+    /// Bank account the payment was made from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_account_id: Option<String>,
+
+    /// Payment reference number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_reference_number: Option<String>,
+}
+
+impl SupplierInvoicePayment {
+    /// Create a payment of `amount` (in the invoice currency) on a date.
+    pub fn new(amount: impl Into<Money>, payment_date: DateTime<Utc>) -> Self {
+        Self {
+            amount: Some(amount.into()),
+            payment_date: Some(payment_date),
+            ..Default::default()
+        }
+    }
+
+    /// Set the bank account the payment was made from.
+    pub fn bank_account(mut self, id: impl Into<String>) -> Self {
+        self.bank_account_id = Some(id.into());
+        self
+    }
+
+    /// Set the payment reference number.
+    pub fn reference(mut self, reference: impl Into<String>) -> Self {
+        self.payment_reference_number = Some(reference.into());
+        self
+    }
+
+    /// Mark the payment as made in a foreign currency at the given rate.
+    pub fn foreign_currency(
+        mut self,
+        currency_code: impl Into<String>,
+        rate: impl Into<Money>,
+    ) -> Self {
+        self.currency_code = Some(currency_code.into());
+        self.currency_rate = Some(rate.into());
+        self
+    }
+
+    /// Set the amount in the domestic currency explicitly, e.g. from a bank statement.
+    pub fn domestic_amount(mut self, amount: impl Into<Money>) -> Self {
+        self.amount_in_domestic_currency = Some(amount.into());
+        self
+    }
+
+    /// The amount in the domestic currency, derived from the rate if not set.
+    pub fn amount_in_domestic_currency(&self) -> Option<Money> {
+        self.amount_in_domestic_currency
+            .or_else(|| match self.currency_rate {
+                Some(rate) => self.amount.map(|amount| amount * rate),
+                None => self.amount,
+            })
+    }
 }
 
 // =============================================================================
@@ -648,6 +1647,14 @@ pub struct Supplier {
     /// When the supplier was last modified.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub modified_utc: Option<DateTime<Utc>>,
+
+    /// ID of the user who created the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_user_id: Option<String>,
+
+    /// ID of the user who last modified the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_by_user_id: Option<String>,
 }
 
 /// Supplier invoice.
@@ -667,19 +1674,23 @@ pub struct SupplierInvoice {
     pub invoice_number: Option<String>,
 
     /// Invoice date.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub invoice_date: Option<DateTime<Utc>>,
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub invoice_date: Option<NaiveDate>,
 
     /// Due date.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub due_date: Option<DateTime<Utc>>,
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
 
     /// Currency code (ISO 4217).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub currency_code: Option<String>,
 
     /// Currency rate.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub currency_rate: Option<Money>,
 
     /// Invoice rows/line items.
@@ -687,17 +1698,37 @@ pub struct SupplierInvoice {
     pub rows: Vec<SupplierInvoiceRow>,
 
     /// Total amount excluding VAT.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_amount: Option<Money>,
 
     /// Total VAT amount.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_vat_amount: Option<Money>,
 
     /// Total amount including VAT.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_amount_including_vat: Option<Money>,
 
+    /// Amount still to be paid.
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub remaining_amount: Option<Money>,
+
     /// Whether the invoice is paid.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_paid: Option<bool>,
@@ -710,6 +1741,18 @@ pub struct SupplierInvoice {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ocr_number: Option<String>,
 
+    /// Approval status (see [`crate::endpoints::approvals`]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_status: Option<i32>,
+
+    /// User the invoice is waiting on for approval.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approver_user_id: Option<String>,
+
+    /// IDs of attached documents, e.g. the scanned invoice.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<String>,
+
     /// When the invoice was created.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_utc: Option<DateTime<Utc>>,
@@ -717,6 +1760,14 @@ pub struct SupplierInvoice {
     /// When the invoice was last modified.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub modified_utc: Option<DateTime<Utc>>,
+
+    /// ID of the user who created the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_user_id: Option<String>,
+
+    /// ID of the user who last modified the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_by_user_id: Option<String>,
 }
 
 /// Supplier invoice row/line item.
@@ -736,11 +1787,19 @@ pub struct SupplierInvoiceRow {
     pub text: Option<String>,
 
     /// Amount.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub amount: Option<Money>,
 
     /// VAT amount.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub vat_amount: Option<Money>,
 
     /// VAT rate ID.
@@ -789,7 +1848,11 @@ pub struct Account {
     pub is_active: Option<bool>,
 
     /// Opening balance.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub opening_balance: Option<Money>,
 }
 
@@ -806,7 +1869,11 @@ pub struct AccountBalance {
     pub name: Option<String>,
 
     /// Balance amount.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub balance: Option<Money>,
 }
 
@@ -839,7 +1906,7 @@ pub struct FiscalYear {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub end_date: Option<DateTime<Utc>>,
 
-    /// Whether this is the current fiscal year.
+    /// Whether the fiscal year is locked for bookkeeping.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_locked: Option<bool>,
 
@@ -865,10 +1932,59 @@ pub struct VatCode {
     pub description: Option<String>,
 
     /// VAT rate percentage.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub vat_rate: Option<Money>,
 }
 
+/// VAT report (momsdeklaration) for a VAT period.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct VatReport {
+    /// Unique identifier.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Name of the report.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// First day of the VAT period.
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<NaiveDate>,
+
+    /// Last day of the VAT period.
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<NaiveDate>,
+
+    /// Approval status (0 = None, 1 = Approved, 2 = Rejected, 3 = Ready for
+    /// approval).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_approval_status: Option<i32>,
+
+    /// Whether the report was withdrawn after it was sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_regretted: Option<bool>,
+
+    /// Creation timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_utc: Option<DateTime<Utc>>,
+
+    /// Last modification timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_utc: Option<DateTime<Utc>>,
+}
+
+impl VatReport {
+    /// Whether the report has been approved and not withdrawn.
+    pub fn is_approved(&self) -> bool {
+        self.document_approval_status == Some(1) && self.is_regretted != Some(true)
+    }
+}
+
 /// Voucher (journal entry).
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -904,6 +2020,14 @@ pub struct Voucher {
     /// When the voucher was last modified.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub modified_utc: Option<DateTime<Utc>>,
+
+    /// ID of the user who created the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_user_id: Option<String>,
+
+    /// ID of the user who last modified the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_by_user_id: Option<String>,
 }
 
 /// Voucher row (journal entry line).
@@ -915,11 +2039,19 @@ pub struct VoucherRow {
     pub account_number: Option<String>,
 
     /// Debit amount.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub debit_amount: Option<Money>,
 
     /// Credit amount.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub credit_amount: Option<Money>,
 
     /// Transaction text.
@@ -963,6 +2095,14 @@ pub struct BankAccount {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bic: Option<String>,
 
+    /// Bankgiro number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_giro_number: Option<String>,
+
+    /// Plusgiro number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plus_giro_number: Option<String>,
+
     /// Ledger account number.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ledger_account_number: Option<String>,
@@ -980,6 +2120,31 @@ pub struct BankAccount {
     pub is_active: Option<bool>,
 }
 
+impl BankAccount {
+    /// Whether `number` is this account's bankgiro, plusgiro, IBAN or account
+    /// number. Spaces and dashes are ignored.
+    pub fn has_number(&self, number: &str) -> bool {
+        let normalize = |value: &str| {
+            value
+                .chars()
+                .filter(|c| !c.is_whitespace() && *c != '-')
+                .collect::<String>()
+                .to_ascii_uppercase()
+        };
+        let wanted = normalize(number);
+        !wanted.is_empty()
+            && [
+                &self.bank_giro_number,
+                &self.plus_giro_number,
+                &self.iban,
+                &self.account_number,
+            ]
+            .into_iter()
+            .flatten()
+            .any(|value| normalize(value) == wanted)
+    }
+}
+
 // =============================================================================
 // Projects & Cost Centers
 // =============================================================================
@@ -999,7 +2164,7 @@ pub struct Project {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub end_date: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub customer_id: Option<String>,
+    pub customer_id: Option<CustomerId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_completed: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1008,6 +2173,10 @@ pub struct Project {
     pub created_utc: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub modified_utc: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_by_user_id: Option<String>,
 }
 
 /// Cost center for allocating expenses.
@@ -1065,18 +2234,30 @@ pub struct Order {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub order_number: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub customer_id: Option<String>,
+    pub customer_id: Option<CustomerId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub order_date: Option<DateTime<Utc>>,
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub delivery_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_method_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub delivery_date: Option<DateTime<Utc>>,
+    pub delivery_term_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub currency_code: Option<String>,
     #[serde(default)]
     pub rows: Vec<OrderRow>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_amount: Option<Money>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_vat_amount: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<i32>,
@@ -1088,6 +2269,10 @@ pub struct Order {
     pub created_utc: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub modified_utc: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_by_user_id: Option<String>,
 }
 
 /// Order row/line item.
@@ -1097,16 +2282,32 @@ pub struct OrderRow {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub article_id: Option<String>,
+    pub article_id: Option<ArticleId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unit_price: Option<Money>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub quantity: Option<Money>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub discount_percentage: Option<Money>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub delivered_quantity: Option<Money>,
 }
 
@@ -1119,7 +2320,7 @@ pub struct Quotation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub quotation_number: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub customer_id: Option<String>,
+    pub customer_id: Option<CustomerId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub quotation_date: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1128,9 +2329,17 @@ pub struct Quotation {
     pub currency_code: Option<String>,
     #[serde(default)]
     pub rows: Vec<QuotationRow>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_amount: Option<Money>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_vat_amount: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<i32>,
@@ -1138,6 +2347,10 @@ pub struct Quotation {
     pub created_utc: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub modified_utc: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_by_user_id: Option<String>,
 }
 
 /// Quotation row/line item.
@@ -1147,14 +2360,26 @@ pub struct QuotationRow {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub article_id: Option<String>,
+    pub article_id: Option<ArticleId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unit_price: Option<Money>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub quantity: Option<Money>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub discount_percentage: Option<Money>,
 }
 
@@ -1172,20 +2397,28 @@ pub struct SupplierInvoiceDraft {
     pub supplier_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub invoice_number: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub invoice_date: Option<DateTime<Utc>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub due_date: Option<DateTime<Utc>>,
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub invoice_date: Option<NaiveDate>,
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub currency_code: Option<String>,
     #[serde(default)]
     pub rows: Vec<SupplierInvoiceRow>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total_amount: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_utc: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub modified_utc: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_by_user_id: Option<String>,
 }
 
 /// Supplier ledger item.
@@ -1198,11 +2431,19 @@ pub struct SupplierLedgerItem {
     pub supplier_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub supplier_invoice_id: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub currency_amount: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub currency_code: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub amount: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payment_date: Option<DateTime<Utc>>,
@@ -1330,6 +2571,60 @@ pub struct Attachment {
     pub created_utc: Option<DateTime<Utc>>,
 }
 
+/// Company logotype shown on invoices and other documents.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Logotype {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temporary_url: Option<String>,
+}
+
+/// A file to upload as an attachment.
+///
+/// The API takes the file content base64 encoded in a JSON body.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AttachmentUpload {
+    /// File name shown in the attachment list.
+    pub file_name: String,
+    /// MIME type, such as `application/pdf` or `image/jpeg`.
+    pub content_type: String,
+    /// Base64 encoded file content.
+    pub data: String,
+    /// Comment shown with the attachment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+impl AttachmentUpload {
+    /// Create an upload from raw file content.
+    pub fn new(
+        file_name: impl Into<String>,
+        content_type: impl Into<String>,
+        content: &[u8],
+    ) -> Self {
+        use base64::Engine;
+        Self {
+            file_name: file_name.into(),
+            content_type: content_type.into(),
+            data: base64::engine::general_purpose::STANDARD.encode(content),
+            comment: None,
+        }
+    }
+
+    /// Set the comment.
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+}
+
 /// Link between attachment and document.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -1378,6 +2673,9 @@ pub struct CompanySettings {
     pub currency_code: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub country_code: Option<String>,
+    /// Bookkeeping is locked up to and including this date.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked_through_date: Option<DateTime<Utc>>,
 }
 
 /// Country.
@@ -1418,6 +2716,73 @@ pub struct User {
     pub is_active: Option<bool>,
 }
 
+impl User {
+    /// Full name, or the email address when the user has no name.
+    pub fn display_name(&self) -> Option<String> {
+        let name = [self.first_name.as_deref(), self.last_name.as_deref()]
+            .into_iter()
+            .flatten()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if name.is_empty() {
+            self.email.clone()
+        } else {
+            Some(name)
+        }
+    }
+}
+
+/// Records that track when and by whom they were created and last modified.
+///
+/// User IDs can be turned into names with
+/// [`UsersEndpoint::resolve_users`](crate::endpoints::UsersEndpoint::resolve_users).
+pub trait Audited {
+    /// When the record was created.
+    fn created_utc(&self) -> Option<DateTime<Utc>>;
+    /// When the record was last modified.
+    fn modified_utc(&self) -> Option<DateTime<Utc>>;
+    /// ID of the user who created the record.
+    fn created_by_user_id(&self) -> Option<&str>;
+    /// ID of the user who last modified the record.
+    fn modified_by_user_id(&self) -> Option<&str>;
+}
+
+macro_rules! impl_audited {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Audited for $ty {
+                fn created_utc(&self) -> Option<DateTime<Utc>> {
+                    self.created_utc
+                }
+                fn modified_utc(&self) -> Option<DateTime<Utc>> {
+                    self.modified_utc
+                }
+                fn created_by_user_id(&self) -> Option<&str> {
+                    self.created_by_user_id.as_deref()
+                }
+                fn modified_by_user_id(&self) -> Option<&str> {
+                    self.modified_by_user_id.as_deref()
+                }
+            }
+        )*
+    };
+}
+
+impl_audited!(
+    Customer,
+    Invoice,
+    Article,
+    CustomerInvoiceDraft,
+    Supplier,
+    SupplierInvoice,
+    Voucher,
+    Project,
+    Order,
+    Quotation,
+    SupplierInvoiceDraft,
+);
+
 /// Bank reference data.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -1476,6 +2841,58 @@ pub struct Message {
     pub created_utc: Option<DateTime<Utc>>,
 }
 
+/// Record an internal note is attached to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NoteTarget {
+    /// A customer, by ID.
+    Customer(String),
+    /// A customer invoice, by ID.
+    Invoice(String),
+}
+
+impl NoteTarget {
+    /// Document type code of the target in message threads.
+    pub fn document_type(&self) -> i32 {
+        match self {
+            NoteTarget::Customer(_) => 1,
+            NoteTarget::Invoice(_) => 2,
+        }
+    }
+
+    /// ID of the target record.
+    pub fn document_id(&self) -> &str {
+        match self {
+            NoteTarget::Customer(id) | NoteTarget::Invoice(id) => id,
+        }
+    }
+}
+
+impl MessageThread {
+    /// A new thread holding one note on `target`.
+    pub fn note(
+        target: &NoteTarget,
+        subject: impl Into<String>,
+        content: impl Into<String>,
+    ) -> Self {
+        Self {
+            subject: Some(subject.into()),
+            document_type: Some(target.document_type()),
+            document_id: Some(target.document_id().to_string()),
+            messages: vec![Message {
+                content: Some(content.into()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    /// Whether the thread is attached to `target`.
+    pub fn is_about(&self, target: &NoteTarget) -> bool {
+        self.document_type == Some(target.document_type())
+            && self.document_id.as_deref() == Some(target.document_id())
+    }
+}
+
 /// Generic query parameters for filtering and selecting fields.
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct QueryParams {
@@ -1487,9 +2904,18 @@ pub struct QueryParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub select: Option<String>,
 
+    /// Sort order, as `Field direction` pairs separated by commas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orderby: Option<String>,
+
     /// Additional custom parameters.
     #[serde(flatten)]
     pub extra: HashMap<String, String>,
+
+    /// Which records to include by their `IsActive` flag, combined with
+    /// [`filter`](Self::filter) when the query is sent.
+    #[serde(skip)]
+    pub scope: ListScope,
 }
 
 impl QueryParams {
@@ -1522,16 +2948,98 @@ impl QueryParams {
     }
 
     /// Set fields to select.
+    ///
+    /// Prefer [`select_fields`](Self::select_fields), which checks the names.
     pub fn select(mut self, select: impl Into<String>) -> Self {
         self.select = Some(select.into());
         self
     }
 
+    /// Set fields to select from the model's field enum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spiris::fields::CustomerField;
+    /// use spiris::QueryParams;
+    ///
+    /// let params = QueryParams::new().select_fields([CustomerField::Id, CustomerField::Name]);
+    /// assert_eq!(params.select.as_deref(), Some("Id,Name"));
+    /// ```
+    pub fn select_fields<F: crate::fields::Field>(
+        mut self,
+        fields: impl IntoIterator<Item = F>,
+    ) -> Self {
+        let names: Vec<&str> = fields.into_iter().map(|f| f.api_name()).collect();
+        self.select = Some(names.join(","));
+        self
+    }
+
+    /// Sort by `field`; each call adds a key after those already set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spiris::fields::InvoiceField;
+    /// use spiris::query::Direction;
+    /// use spiris::QueryParams;
+    ///
+    /// let params = QueryParams::new()
+    ///     .order_by(InvoiceField::DueDate, Direction::Ascending)
+    ///     .order_by(InvoiceField::InvoiceDate, Direction::Descending);
+    /// assert_eq!(
+    ///     params.orderby.as_deref(),
+    ///     Some("DueDate asc,InvoiceDate desc")
+    /// );
+    /// ```
+    pub fn order_by(
+        mut self,
+        field: impl crate::fields::Field,
+        direction: crate::query::Direction,
+    ) -> Self {
+        let key = format!("{} {}", field.api_name(), direction);
+        self.orderby = Some(match self.orderby.take() {
+            Some(existing) => format!("{},{}", existing, key),
+            None => key,
+        });
+        self
+    }
+
     /// Add a custom parameter.
     pub fn param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.extra.insert(key.into(), value.into());
         self
     }
+
+    /// Only match active or inactive records.
+    pub fn scope(mut self, scope: ListScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Fold the scopes of the query and the pagination into the filter.
+    ///
+    /// Both would otherwise be sent as separate `filter` parameters.
+    pub(crate) fn resolve_scope(
+        mut self,
+        pagination: Option<PaginationParams>,
+    ) -> (Self, Option<PaginationParams>) {
+        let pagination = pagination.map(|mut p| {
+            if self.scope == ListScope::All {
+                self.scope = p.scope;
+            }
+            p.scope = ListScope::All;
+            p
+        });
+        if let Some(scope) = self.scope.filter() {
+            self.filter = Some(match self.filter.take() {
+                Some(filter) => crate::query::Filter::raw(filter).and(scope).into(),
+                None => scope.into(),
+            });
+        }
+        self.scope = ListScope::All;
+        (self, pagination)
+    }
 }
 
 // =============================================================================
@@ -1596,6 +3104,14 @@ pub struct CustomerCreate {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub delivery_address: Option<Address>,
 
+    /// Delivery method ID (see [`DeliveryMethod`]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_method_id: Option<String>,
+
+    /// Delivery terms ID (see [`DeliveryTerm`]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_term_id: Option<String>,
+
     /// Payment terms in days.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payment_terms_in_days: Option<u32>,
@@ -1622,6 +3138,8 @@ impl CustomerCreate {
             website: None,
             invoice_address: None,
             delivery_address: None,
+            delivery_method_id: None,
+            delivery_term_id: None,
             payment_terms_in_days: None,
             is_active: None,
             is_private_person: None,
@@ -1676,6 +3194,18 @@ impl CustomerCreate {
         self
     }
 
+    /// Set the delivery method ID.
+    pub fn delivery_method_id(mut self, value: String) -> Self {
+        self.delivery_method_id = Some(value);
+        self
+    }
+
+    /// Set the delivery terms ID.
+    pub fn delivery_term_id(mut self, value: String) -> Self {
+        self.delivery_term_id = Some(value);
+        self
+    }
+
     /// Set the payment terms in days.
     pub fn payment_terms_in_days(mut self, value: u32) -> Self {
         self.payment_terms_in_days = Some(value);
@@ -1748,6 +3278,14 @@ pub struct CustomerUpdate {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub delivery_address: Option<Address>,
 
+    /// Delivery method ID (see [`DeliveryMethod`]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_method_id: Option<String>,
+
+    /// Delivery terms ID (see [`DeliveryTerm`]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_term_id: Option<String>,
+
     /// Payment terms in days.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payment_terms_in_days: Option<u32>,
@@ -1821,6 +3359,18 @@ impl CustomerUpdate {
         self
     }
 
+    /// Set the delivery method ID.
+    pub fn delivery_method_id(mut self, value: String) -> Self {
+        self.delivery_method_id = Some(value);
+        self
+    }
+
+    /// Set the delivery terms ID.
+    pub fn delivery_term_id(mut self, value: String) -> Self {
+        self.delivery_term_id = Some(value);
+        self
+    }
+
     /// Set the payment terms in days.
     pub fn payment_terms_in_days(mut self, value: u32) -> Self {
         self.payment_terms_in_days = Some(value);
@@ -1866,11 +3416,19 @@ pub struct ArticleCreate {
     pub unit: Option<String>,
 
     /// Sales price.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub sales_price: Option<Money>,
 
     /// Purchase price.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub purchase_price: Option<Money>,
 
     /// Whether the article is active.
@@ -1952,11 +3510,19 @@ pub struct ArticleUpdate {
     pub unit: Option<String>,
 
     /// Sales price.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub sales_price: Option<Money>,
 
     /// Purchase price.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub purchase_price: Option<Money>,
 
     /// Whether the article is active.
@@ -2023,22 +3589,34 @@ impl ArticleUpdate {
 pub struct InvoiceRowCreate {
     /// Article/product ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub article_id: Option<String>,
+    pub article_id: Option<ArticleId>,
 
     /// Description/text.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
 
     /// Unit price.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unit_price: Option<Money>,
 
     /// Quantity.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub quantity: Option<Money>,
 
     /// Discount percentage (0-100).
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "money_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub discount_percentage: Option<Money>,
 
     /// VAT rate ID.
@@ -2053,7 +3631,7 @@ impl InvoiceRowCreate {
     }
 
     /// Create an invoice row with an article reference.
-    pub fn with_article(article_id: String, quantity: Money) -> Self {
+    pub fn with_article(article_id: ArticleId, quantity: Money) -> Self {
         Self {
             article_id: Some(article_id),
             quantity: Some(quantity),
@@ -2072,7 +3650,7 @@ impl InvoiceRowCreate {
     }
 
     /// Set the article ID.
-    pub fn article_id(mut self, value: String) -> Self {
+    pub fn article_id(mut self, value: ArticleId) -> Self {
         self.article_id = Some(value);
         self
     }
@@ -2115,7 +3693,7 @@ impl InvoiceRowCreate {
 /// ```
 /// use spiris::{InvoiceCreate, InvoiceRowCreate, money};
 ///
-/// let invoice = InvoiceCreate::new("customer-id-123".to_string())
+/// let invoice = InvoiceCreate::new("customer-id-123".into())
 ///     .row(InvoiceRowCreate::with_text(
 ///         "Consulting".to_string(),
 ///         money!(1500.0),
@@ -2127,19 +3705,27 @@ impl InvoiceRowCreate {
 #[serde(rename_all = "PascalCase")]
 pub struct InvoiceCreate {
     /// Customer ID (required).
-    pub customer_id: String,
+    pub customer_id: CustomerId,
 
     /// Invoice date.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub invoice_date: Option<DateTime<Utc>>,
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub invoice_date: Option<NaiveDate>,
 
     /// Due date.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub due_date: Option<DateTime<Utc>>,
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
 
     /// Delivery date.
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub delivery_date: Option<NaiveDate>,
+
+    /// Delivery method ID (see [`DeliveryMethod`]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_method_id: Option<String>,
+
+    /// Delivery terms ID (see [`DeliveryTerm`]).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub delivery_date: Option<DateTime<Utc>>,
+    pub delivery_term_id: Option<String>,
 
     /// Currency code (ISO 4217).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -2155,12 +3741,14 @@ pub struct InvoiceCreate {
 
 impl InvoiceCreate {
     /// Create a new invoice for a customer.
-    pub fn new(customer_id: String) -> Self {
+    pub fn new(customer_id: CustomerId) -> Self {
         Self {
             customer_id,
             invoice_date: None,
             due_date: None,
             delivery_date: None,
+            delivery_method_id: None,
+            delivery_term_id: None,
             currency_code: None,
             rows: Vec::new(),
             remarks: None,
@@ -2168,23 +3756,35 @@ impl InvoiceCreate {
     }
 
     /// Set the invoice date.
-    pub fn invoice_date(mut self, value: DateTime<Utc>) -> Self {
+    pub fn invoice_date(mut self, value: NaiveDate) -> Self {
         self.invoice_date = Some(value);
         self
     }
 
     /// Set the due date.
-    pub fn due_date(mut self, value: DateTime<Utc>) -> Self {
+    pub fn due_date(mut self, value: NaiveDate) -> Self {
         self.due_date = Some(value);
         self
     }
 
     /// Set the delivery date.
-    pub fn delivery_date(mut self, value: DateTime<Utc>) -> Self {
+    pub fn delivery_date(mut self, value: NaiveDate) -> Self {
         self.delivery_date = Some(value);
         self
     }
 
+    /// Set the delivery method ID.
+    pub fn delivery_method_id(mut self, value: String) -> Self {
+        self.delivery_method_id = Some(value);
+        self
+    }
+
+    /// Set the delivery terms ID.
+    pub fn delivery_term_id(mut self, value: String) -> Self {
+        self.delivery_term_id = Some(value);
+        self
+    }
+
     /// Set the currency code.
     pub fn currency_code(mut self, value: String) -> Self {
         self.currency_code = Some(value);
@@ -2218,19 +3818,27 @@ impl InvoiceCreate {
 pub struct InvoiceUpdate {
     /// Customer ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub customer_id: Option<String>,
+    pub customer_id: Option<CustomerId>,
 
     /// Invoice date.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub invoice_date: Option<DateTime<Utc>>,
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub invoice_date: Option<NaiveDate>,
 
     /// Due date.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub due_date: Option<DateTime<Utc>>,
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
 
     /// Delivery date.
+    #[serde(default, with = "api_date", skip_serializing_if = "Option::is_none")]
+    pub delivery_date: Option<NaiveDate>,
+
+    /// Delivery method ID (see [`DeliveryMethod`]).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub delivery_date: Option<DateTime<Utc>>,
+    pub delivery_method_id: Option<String>,
+
+    /// Delivery terms ID (see [`DeliveryTerm`]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_term_id: Option<String>,
 
     /// Currency code.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -2252,29 +3860,41 @@ impl InvoiceUpdate {
     }
 
     /// Set the customer ID.
-    pub fn customer_id(mut self, value: String) -> Self {
+    pub fn customer_id(mut self, value: CustomerId) -> Self {
         self.customer_id = Some(value);
         self
     }
 
     /// Set the invoice date.
-    pub fn invoice_date(mut self, value: DateTime<Utc>) -> Self {
+    pub fn invoice_date(mut self, value: NaiveDate) -> Self {
         self.invoice_date = Some(value);
         self
     }
 
     /// Set the due date.
-    pub fn due_date(mut self, value: DateTime<Utc>) -> Self {
+    pub fn due_date(mut self, value: NaiveDate) -> Self {
         self.due_date = Some(value);
         self
     }
 
     /// Set the delivery date.
-    pub fn delivery_date(mut self, value: DateTime<Utc>) -> Self {
+    pub fn delivery_date(mut self, value: NaiveDate) -> Self {
         self.delivery_date = Some(value);
         self
     }
 
+    /// Set the delivery method ID.
+    pub fn delivery_method_id(mut self, value: String) -> Self {
+        self.delivery_method_id = Some(value);
+        self
+    }
+
+    /// Set the delivery terms ID.
+    pub fn delivery_term_id(mut self, value: String) -> Self {
+        self.delivery_term_id = Some(value);
+        self
+    }
+
     /// Set the currency code.
     pub fn currency_code(mut self, value: String) -> Self {
         self.currency_code = Some(value);
@@ -2293,3 +3913,190 @@ impl InvoiceUpdate {
         self
     }
 }
+
+// =============================================================================
+// Partial Update (PATCH) Types
+// =============================================================================
+
+/// A field in a partial update: left unchanged, set to a value, or cleared.
+///
+/// Unchanged fields are left out of the request body and cleared fields are
+/// sent as `null`, so only the fields named in the patch are touched.
+///
+/// # Example
+///
+/// ```
+/// use spiris::{CustomerPatch, Patch};
+///
+/// let patch = CustomerPatch::new()
+///     .email("billing@acme.se")
+///     .clear_website();
+///
+/// assert_eq!(patch.email, Patch::Set("billing@acme.se".to_string()));
+/// assert_eq!(patch.website, Patch::Clear);
+/// assert_eq!(patch.phone, Patch::Unchanged);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Patch<T> {
+    /// Keep the current value.
+    #[default]
+    Unchanged,
+    /// Replace the value.
+    Set(T),
+    /// Remove the value.
+    Clear,
+}
+
+impl<T> Patch<T> {
+    /// Whether the field is left as it is.
+    pub fn is_unchanged(&self) -> bool {
+        matches!(self, Patch::Unchanged)
+    }
+}
+
+impl<T> From<T> for Patch<T> {
+    fn from(value: T) -> Self {
+        Patch::Set(value)
+    }
+}
+
+impl<T: Serialize> Serialize for Patch<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Patch::Set(value) => value.serialize(serializer),
+            Patch::Unchanged | Patch::Clear => serializer.serialize_none(),
+        }
+    }
+}
+
+macro_rules! patch_type {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($(#[$field_meta:meta])* $field:ident $(/ $clear:ident)?: $ty:ty,)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Default, Serialize)]
+        #[serde(rename_all = "PascalCase")]
+        pub struct $name {
+            $(
+                $(#[$field_meta])*
+                #[serde(skip_serializing_if = "Patch::is_unchanged")]
+                pub $field: Patch<$ty>,
+            )+
+        }
+
+        impl $name {
+            /// Create a patch that changes nothing.
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Whether the patch leaves every field unchanged.
+            pub fn is_empty(&self) -> bool {
+                $(self.$field.is_unchanged())&&+
+            }
+
+            $(
+                #[doc = concat!("Set `", stringify!($field), "`.")]
+                pub fn $field(mut self, value: impl Into<$ty>) -> Self {
+                    self.$field = Patch::Set(value.into());
+                    self
+                }
+
+                $(
+                    #[doc = concat!("Clear `", stringify!($field), "`.")]
+                    pub fn $clear(mut self) -> Self {
+                        self.$field = Patch::Clear;
+                        self
+                    }
+                )?
+            )+
+        }
+    };
+}
+
+patch_type! {
+    /// Partial update of a customer, sent with
+    /// [`CustomersEndpoint::update_partial`](crate::endpoints::customers::CustomersEndpoint::update_partial).
+    ///
+    /// Unlike [`CustomerUpdate`], fields can be cleared as well as set.
+    CustomerPatch {
+        /// Customer name.
+        name: String,
+        /// Customer number.
+        customer_number: String,
+        /// Corporate identity number (organization number).
+        corporate_identity_number / clear_corporate_identity_number: String,
+        /// Email address.
+        email / clear_email: String,
+        /// Phone number.
+        phone / clear_phone: String,
+        /// Mobile phone number.
+        mobile_phone / clear_mobile_phone: String,
+        /// Website URL.
+        website / clear_website: String,
+        /// Invoice address.
+        invoice_address / clear_invoice_address: Address,
+        /// Delivery address.
+        delivery_address / clear_delivery_address: Address,
+        /// Delivery method ID (see [`DeliveryMethod`]).
+        delivery_method_id / clear_delivery_method_id: String,
+        /// Delivery terms ID (see [`DeliveryTerm`]).
+        delivery_term_id / clear_delivery_term_id: String,
+        /// Payment terms in days.
+        payment_terms_in_days / clear_payment_terms_in_days: u32,
+        /// Whether the customer is active.
+        is_active: bool,
+        /// Whether the customer is private (person).
+        is_private_person: bool,
+    }
+}
+
+patch_type! {
+    /// Partial update of an article, sent with
+    /// [`ArticlesEndpoint::update_partial`](crate::endpoints::articles::ArticlesEndpoint::update_partial).
+    ArticlePatch {
+        /// Article name.
+        name: String,
+        /// Article number.
+        article_number: String,
+        /// Unit label.
+        unit / clear_unit: String,
+        /// Sales price.
+        sales_price / clear_sales_price: Money,
+        /// Purchase price.
+        purchase_price / clear_purchase_price: Money,
+        /// Whether the article is active.
+        is_active: bool,
+        /// VAT rate ID.
+        vat_rate_id / clear_vat_rate_id: String,
+    }
+}
+
+patch_type! {
+    /// Partial update of an invoice, sent with
+    /// [`InvoicesEndpoint::update_partial`](crate::endpoints::invoices::InvoicesEndpoint::update_partial).
+    ///
+    /// Rows can't be patched; replace them with
+    /// [`InvoicesEndpoint::update`](crate::endpoints::invoices::InvoicesEndpoint::update).
+    InvoicePatch {
+        /// Customer ID.
+        customer_id: CustomerId,
+        /// Invoice date.
+        invoice_date: NaiveDate,
+        /// Due date.
+        due_date: NaiveDate,
+        /// Delivery date.
+        delivery_date / clear_delivery_date: NaiveDate,
+        /// Delivery method ID (see [`DeliveryMethod`]).
+        delivery_method_id / clear_delivery_method_id: String,
+        /// Delivery terms ID (see [`DeliveryTerm`]).
+        delivery_term_id / clear_delivery_term_id: String,
+        /// Currency code.
+        currency_code: String,
+        /// Remarks/notes.
+        remarks / clear_remarks: String,
+    }
+}
@@ -0,0 +1,1513 @@
+//! Client-side validation of request bodies.
+//!
+//! The API reports one problem per request for many mistakes, so fixing a
+//! payload by trial and error takes several round trips. [`Validate`] checks
+//! required fields, field lengths, email addresses, amounts, enum values,
+//! references and row consistency locally and returns every problem at once.
+//!
+//! Validation is opt-in: call [`Validate::validate`] directly, or enable
+//! [`ClientConfig::validate_requests`](crate::ClientConfig::validate_requests)
+//! to run it before every create and update of a validated type.
+//!
+//! Swedish organisation and personal numbers are checked with
+//! [`IdentityNumber`], and payment details with [`Bankgiro`], [`Plusgiro`],
+//! [`Iban`] and [`Bic`], and OCR payment references with [`OcrReference`].
+//! Creating a customer, and creating or updating a
+//! supplier or bank account, always checks and normalizes these numbers,
+//! whether or not request validation is enabled.
+//!
+//! # Example
+//!
+//! ```
+//! use spiris::validation::Validate;
+//! use spiris::{Customer, Error};
+//!
+//! let customer = Customer {
+//!     customer_number: Some("C-000000000000001".to_string()),
+//!     ..Default::default()
+//! };
+//!
+//! if let Err(Error::Validation(errors)) = customer.validate() {
+//!     for error in &errors {
+//!         println!("{}", error); // "Name: is required", ...
+//!     }
+//! }
+//! ```
+
+use crate::error::{Error, Result, ValidationError};
+use crate::types::{
+    Address, Article, BankAccount, Customer, FiscalYear, Invoice, Money, Supplier, SupplierInvoice,
+    Voucher,
+};
+use chrono::{Datelike, NaiveDate, Utc};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+/// A request body that can be checked before it is sent.
+pub trait Validate {
+    /// Every problem found; empty if the value is valid.
+    fn validation_errors(&self) -> Vec<ValidationError>;
+
+    /// Check the value, failing with [`Error::Validation`] listing every
+    /// problem.
+    fn validate(&self) -> Result<()> {
+        let errors = self.validation_errors();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Validation(errors))
+        }
+    }
+}
+
+/// Collects problems using the API's PascalCase field names.
+#[derive(Debug, Default)]
+pub(crate) struct Validator {
+    prefix: String,
+    errors: Vec<ValidationError>,
+}
+
+impl Validator {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&mut self, field: &str, message: impl Into<String>) {
+        self.errors.push(ValidationError {
+            field: format!("{}{}", self.prefix, field),
+            message: message.into(),
+        });
+    }
+
+    /// Run checks for a nested object, prefixing its field names.
+    fn nested(&mut self, prefix: &str, check: impl FnOnce(&mut Self)) {
+        let inner = format!("{}{}.", self.prefix, prefix);
+        let outer = std::mem::replace(&mut self.prefix, inner);
+        check(self);
+        self.prefix = outer;
+    }
+
+    /// The field must be set and not blank.
+    fn required(&mut self, field: &str, value: &Option<impl AsRef<str>>) {
+        if value.as_ref().is_none_or(|v| v.as_ref().trim().is_empty()) {
+            self.push(field, "is required");
+        }
+    }
+
+    /// The field must be set.
+    fn required_value<T>(&mut self, field: &str, value: &Option<T>) {
+        if value.is_none() {
+            self.push(field, "is required");
+        }
+    }
+
+    /// The field, if set, must not exceed `max` characters.
+    fn max_len(&mut self, field: &str, value: &Option<String>, max: usize) {
+        if let Some(len) = value.as_ref().map(|v| v.chars().count()) {
+            if len > max {
+                self.push(
+                    field,
+                    format!("must be at most {} characters, got {}", max, len),
+                );
+            }
+        }
+    }
+
+    /// The field, if set, must have exactly `len` characters.
+    fn exact_len(&mut self, field: &str, value: &Option<String>, len: usize) {
+        if let Some(actual) = value.as_ref().map(|v| v.chars().count()) {
+            if actual != len {
+                self.push(field, format!("must be {} characters, got {}", len, actual));
+            }
+        }
+    }
+
+    /// The field, if set and not blank, must look like an email address.
+    ///
+    /// Only the shape is checked: one `@` with a local part before it and a
+    /// dotted domain after it, and no spaces. Skipped if the field already
+    /// has a problem, such as being too long.
+    fn email(&mut self, field: &str, value: &Option<String>) {
+        let Some(email) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) else {
+            return;
+        };
+        let path = format!("{}{}", self.prefix, field);
+        if self.errors.iter().any(|e| e.field == path) {
+            return;
+        }
+        let valid = match email.split_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && !domain.contains('@')
+                    && domain.split('.').count() > 1
+                    && domain.split('.').all(|label| !label.is_empty())
+                    && !email.contains(char::is_whitespace)
+            }
+            None => false,
+        };
+        if !valid {
+            self.push(field, format!("'{}' is not a valid email address", email));
+        }
+    }
+
+    /// The amount, if set, must not be negative.
+    fn non_negative(&mut self, field: &str, value: Option<Money>) {
+        if value.is_some_and(|v| v < Money::default()) {
+            self.push(field, "must not be negative");
+        }
+    }
+
+    /// The amount, if set, must be greater than zero.
+    fn positive(&mut self, field: &str, value: Option<Money>) {
+        if value.is_some_and(|v| v <= Money::default()) {
+            self.push(field, "must be greater than zero");
+        }
+    }
+
+    /// A reference to another entity, if set, must not be blank.
+    fn reference(&mut self, field: &str, value: &Option<impl AsRef<str>>) {
+        if value.as_ref().is_some_and(|v| v.as_ref().trim().is_empty()) {
+            self.push(field, "must not be empty when set");
+        }
+    }
+
+    /// The field, if set, must be one of `allowed`.
+    fn one_of(&mut self, field: &str, value: Option<i32>, allowed: &[i32]) {
+        if let Some(value) = value.filter(|v| !allowed.contains(v)) {
+            self.push(
+                field,
+                format!("must be one of {:?}, got {}", allowed, value),
+            );
+        }
+    }
+
+    fn address(&mut self, field: &str, address: &Option<Address>) {
+        if let Some(address) = address {
+            self.nested(field, |v| {
+                v.max_len("Address1", &address.address1, 50);
+                v.max_len("Address2", &address.address2, 50);
+                v.max_len("PostalCode", &address.postal_code, 10);
+                v.max_len("City", &address.city, 50);
+                v.exact_len("CountryCode", &address.country_code, 2);
+            });
+        }
+    }
+
+    /// A Swedish customer's identity number, if set, must pass the
+    /// checksum, and a private person must not have an organisation number.
+    /// Customers count as Swedish only with an invoice address in `SE`.
+    /// Returns the normalized number.
+    fn identity_number(&mut self, field: &str, customer: &Customer) -> Option<String> {
+        let value = customer
+            .corporate_identity_number
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())?;
+        let country = customer
+            .invoice_address
+            .as_ref()
+            .and_then(|a| a.country_code.as_deref());
+        if !country.is_some_and(|c| c.eq_ignore_ascii_case("SE")) {
+            return None;
+        }
+        match IdentityNumber::parse(value) {
+            Ok(number) => {
+                if customer.is_private_person == Some(true)
+                    && number.kind() == IdentityNumberKind::Organisation
+                {
+                    self.push(
+                        field,
+                        format!(
+                            "'{}' is an organisation number, not a personal number",
+                            value
+                        ),
+                    );
+                }
+                Some(number.to_string())
+            }
+            Err(e) => {
+                self.push(field, format!("'{}' {}", value, e));
+                None
+            }
+        }
+    }
+
+    /// The field, if set and not blank, must parse with `parse`. Returns
+    /// the normalized value.
+    fn parsed<T: fmt::Display, E: fmt::Display>(
+        &mut self,
+        field: &str,
+        value: &Option<String>,
+        parse: impl FnOnce(&str) -> std::result::Result<T, E>,
+    ) -> Option<String> {
+        let value = value.as_deref().map(str::trim).filter(|v| !v.is_empty())?;
+        match parse(value) {
+            Ok(parsed) => Some(parsed.to_string()),
+            Err(e) => {
+                self.push(field, format!("'{}' {}", value, e));
+                None
+            }
+        }
+    }
+
+    /// Fail with [`Error::Validation`] if any problem was found.
+    fn check(self) -> Result<()> {
+        let errors = self.finish();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Validation(errors))
+        }
+    }
+
+    fn finish(self) -> Vec<ValidationError> {
+        self.errors
+    }
+}
+
+impl Validate for Customer {
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        let mut v = Validator::new();
+        v.required("Name", &self.name);
+        v.max_len("Name", &self.name, 50);
+        v.max_len("CustomerNumber", &self.customer_number, 16);
+        v.max_len(
+            "CorporateIdentityNumber",
+            &self.corporate_identity_number,
+            20,
+        );
+        v.identity_number("CorporateIdentityNumber", self);
+        v.max_len("Email", &self.email, 255);
+        v.email("Email", &self.email);
+        v.max_len("Phone", &self.phone, 50);
+        v.max_len("MobilePhone", &self.mobile_phone, 50);
+        v.max_len("Website", &self.website, 255);
+        v.address("InvoiceAddress", &self.invoice_address);
+        v.address("DeliveryAddress", &self.delivery_address);
+        v.reference("DeliveryMethodId", &self.delivery_method_id);
+        v.reference("DeliveryTermId", &self.delivery_term_id);
+        v.finish()
+    }
+}
+
+impl Validate for Supplier {
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        let mut v = Validator::new();
+        v.required("Name", &self.name);
+        v.max_len("Name", &self.name, 50);
+        v.max_len("SupplierNumber", &self.supplier_number, 16);
+        v.max_len(
+            "CorporateIdentityNumber",
+            &self.corporate_identity_number,
+            20,
+        );
+        v.max_len("Email", &self.email, 255);
+        v.email("Email", &self.email);
+        v.max_len("Phone", &self.phone, 50);
+        v.max_len("MobilePhone", &self.mobile_phone, 50);
+        v.max_len("Website", &self.website, 255);
+        v.address("Address", &self.address);
+        v.parsed("BankGiroNumber", &self.bank_giro_number, Bankgiro::parse);
+        v.parsed("PlusGiroNumber", &self.plus_giro_number, Plusgiro::parse);
+        v.finish()
+    }
+}
+
+impl Validate for BankAccount {
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        let mut v = Validator::new();
+        v.parsed("Iban", &self.iban, Iban::parse);
+        v.parsed("Bic", &self.bic, Bic::parse);
+        v.parsed("BankGiroNumber", &self.bank_giro_number, Bankgiro::parse);
+        v.parsed("PlusGiroNumber", &self.plus_giro_number, Plusgiro::parse);
+        v.exact_len("CurrencyCode", &self.currency_code, 3);
+        v.finish()
+    }
+}
+
+impl Validate for Article {
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        let mut v = Validator::new();
+        v.required("Name", &self.name);
+        v.max_len("Name", &self.name, 50);
+        v.max_len("ArticleNumber", &self.article_number, 40);
+        v.non_negative("SalesPrice", self.sales_price);
+        v.non_negative("PurchasePrice", self.purchase_price);
+        v.reference("VatRateId", &self.vat_rate_id);
+        v.finish()
+    }
+}
+
+impl Validate for Invoice {
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        let mut v = Validator::new();
+        v.required("CustomerId", &self.customer_id);
+        v.exact_len("CurrencyCode", &self.currency_code, 3);
+        v.reference("DeliveryMethodId", &self.delivery_method_id);
+        v.reference("DeliveryTermId", &self.delivery_term_id);
+        if self.rows.is_empty() {
+            v.push("Rows", "at least one row is required");
+        }
+        // Rows left to the default VAT for a foreign EU buyer are almost
+        // always a missed EU sale; explicitly taxed rows are accepted
+        let eu_buyer = self
+            .customer_vat_number
+            .as_deref()
+            .is_some_and(crate::vat::is_foreign_eu_vat_number);
+        for (index, row) in self.rows.iter().enumerate() {
+            v.nested(&format!("Rows[{}]", index), |v| {
+                if row.article_id.is_none() && row.text.is_none() {
+                    v.push("ArticleId", "an article or a text is required");
+                }
+                // A row without an article is a text row, which the API
+                // rejects if it is priced
+                if row.article_id.is_none() && (row.unit_price.is_some() || row.quantity.is_some())
+                {
+                    v.push("ArticleId", "is required on a row with a price or quantity");
+                }
+                v.reference("ArticleId", &row.article_id);
+                // Credits use negative quantities, so only the price is checked
+                v.non_negative("UnitPrice", row.unit_price);
+                if row
+                    .discount_percentage
+                    .is_some_and(|d| d < Money::default() || d > Money::from(100))
+                {
+                    v.push("DiscountPercentage", "must be between 0 and 100");
+                }
+                v.reference("VatRateId", &row.vat_rate_id);
+                if eu_buyer && row.is_vat_free.is_none() {
+                    v.push(
+                        "IsVatFree",
+                        "must be set for a buyer with an EU VAT number, see vat::IntraEuSale",
+                    );
+                }
+            });
+        }
+        v.finish()
+    }
+}
+
+impl Validate for SupplierInvoice {
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        let mut v = Validator::new();
+        v.required("SupplierId", &self.supplier_id);
+        v.exact_len("CurrencyCode", &self.currency_code, 3);
+        v.positive("CurrencyRate", self.currency_rate);
+        v.one_of(
+            "ApprovalStatus",
+            self.approval_status,
+            &[
+                crate::endpoints::approvals::APPROVAL_STATUS_NONE,
+                crate::endpoints::approvals::APPROVAL_STATUS_PENDING,
+                crate::endpoints::approvals::APPROVAL_STATUS_APPROVED,
+                crate::endpoints::approvals::APPROVAL_STATUS_REJECTED,
+            ],
+        );
+        for (index, row) in self.rows.iter().enumerate() {
+            v.nested(&format!("Rows[{}]", index), |v| {
+                v.required("AccountNumber", &row.account_number);
+                v.reference("VatRateId", &row.vat_rate_id);
+                v.reference("CostCenterItemId", &row.cost_center_item_id);
+            });
+        }
+        v.finish()
+    }
+}
+
+impl Validate for Voucher {
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        let mut v = Validator::new();
+        v.required_value("VoucherDate", &self.voucher_date);
+        v.max_len("VoucherText", &self.voucher_text, 1000);
+        if self.rows.len() < 2 {
+            v.push("Rows", "at least two rows are required");
+        }
+
+        let zero = Money::default();
+        let (mut debit, mut credit) = (zero, zero);
+        for (index, row) in self.rows.iter().enumerate() {
+            v.nested(&format!("Rows[{}]", index), |v| {
+                v.required("AccountNumber", &row.account_number);
+                v.reference("CostCenterItemId", &row.cost_center_item_id);
+                v.reference("ProjectId", &row.project_id);
+                let row_debit = row.debit_amount.unwrap_or_default();
+                let row_credit = row.credit_amount.unwrap_or_default();
+                if row_debit < zero || row_credit < zero {
+                    v.push("DebitAmount", "amounts must not be negative");
+                } else if row_debit > zero && row_credit > zero {
+                    v.push("DebitAmount", "a row has either a debit or a credit amount");
+                } else if row_debit == zero && row_credit == zero {
+                    v.push("DebitAmount", "a debit or a credit amount is required");
+                }
+                debit += row_debit;
+                credit += row_credit;
+            });
+        }
+        if (debit - credit).abs() >= crate::money!(0.005) {
+            v.push(
+                "Rows",
+                format!("debit {:.2} and credit {:.2} do not balance", debit, credit),
+            );
+        }
+        v.finish()
+    }
+}
+
+impl Validate for FiscalYear {
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        let mut v = Validator::new();
+        v.required_value("StartDate", &self.start_date);
+        v.required_value("EndDate", &self.end_date);
+        if let (Some(start), Some(end)) = (self.start_date, self.end_date) {
+            if end <= start {
+                v.push("EndDate", "must be after StartDate");
+            }
+        }
+        v.one_of("BookkeepingMethod", self.bookkeeping_method, &[1, 2]);
+        v.finish()
+    }
+}
+
+/// Check a Swedish customer's identity number and normalize it to
+/// `NNNNNN-NNNN`, before the customer is created.
+///
+/// Customers without an invoice address in Sweden are left as they are.
+pub(crate) fn normalize_customer(customer: &Customer) -> Result<Cow<'_, Customer>> {
+    let mut v = Validator::new();
+    let number = v.identity_number("CorporateIdentityNumber", customer);
+    v.check()?;
+    let mut customer = Cow::Borrowed(customer);
+    if let Some(number) = changed(number, &customer.corporate_identity_number) {
+        customer.to_mut().corporate_identity_number = Some(number);
+    }
+    Ok(customer)
+}
+
+/// Check a supplier's bankgiro and plusgiro numbers and normalize them,
+/// before the supplier is created or updated.
+pub(crate) fn normalize_supplier(supplier: &Supplier) -> Result<Cow<'_, Supplier>> {
+    let mut v = Validator::new();
+    let bankgiro = v.parsed(
+        "BankGiroNumber",
+        &supplier.bank_giro_number,
+        Bankgiro::parse,
+    );
+    let plusgiro = v.parsed(
+        "PlusGiroNumber",
+        &supplier.plus_giro_number,
+        Plusgiro::parse,
+    );
+    v.check()?;
+    let mut supplier = Cow::Borrowed(supplier);
+    if let Some(bankgiro) = changed(bankgiro, &supplier.bank_giro_number) {
+        supplier.to_mut().bank_giro_number = Some(bankgiro);
+    }
+    if let Some(plusgiro) = changed(plusgiro, &supplier.plus_giro_number) {
+        supplier.to_mut().plus_giro_number = Some(plusgiro);
+    }
+    Ok(supplier)
+}
+
+/// Check a bank account's IBAN, BIC, bankgiro and plusgiro numbers and
+/// normalize them, before the account is created or updated.
+pub(crate) fn normalize_bank_account(account: &BankAccount) -> Result<Cow<'_, BankAccount>> {
+    let mut v = Validator::new();
+    let iban = v.parsed("Iban", &account.iban, Iban::parse);
+    let bic = v.parsed("Bic", &account.bic, Bic::parse);
+    let bankgiro = v.parsed("BankGiroNumber", &account.bank_giro_number, Bankgiro::parse);
+    let plusgiro = v.parsed("PlusGiroNumber", &account.plus_giro_number, Plusgiro::parse);
+    v.check()?;
+    let mut account = Cow::Borrowed(account);
+    if let Some(iban) = changed(iban, &account.iban) {
+        account.to_mut().iban = Some(iban);
+    }
+    if let Some(bic) = changed(bic, &account.bic) {
+        account.to_mut().bic = Some(bic);
+    }
+    if let Some(bankgiro) = changed(bankgiro, &account.bank_giro_number) {
+        account.to_mut().bank_giro_number = Some(bankgiro);
+    }
+    if let Some(plusgiro) = changed(plusgiro, &account.plus_giro_number) {
+        account.to_mut().plus_giro_number = Some(plusgiro);
+    }
+    Ok(account)
+}
+
+/// The normalized value, if it differs from the current one.
+fn changed(normalized: Option<String>, current: &Option<String>) -> Option<String> {
+    normalized.filter(|n| current.as_ref() != Some(n))
+}
+
+/// What a Swedish identity number identifies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdentityNumberKind {
+    /// An organisationsnummer of a legal entity.
+    Organisation,
+    /// A personnummer.
+    Personal,
+    /// A samordningsnummer, given to people who aren't registered in
+    /// Sweden. The day of birth has 60 added to it.
+    Coordination,
+}
+
+/// Why a Swedish identity number was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentityNumberError {
+    /// Not 10 or 12 digits, or contains something other than digits and
+    /// a `-` or `+` separator.
+    Format,
+    /// A personal number whose birth date doesn't exist.
+    Date,
+    /// The check digit doesn't match.
+    Checksum,
+}
+
+impl fmt::Display for IdentityNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdentityNumberError::Format => {
+                write!(f, "is not a 10 or 12 digit organisation or personal number")
+            }
+            IdentityNumberError::Date => write!(f, "does not contain a valid date of birth"),
+            IdentityNumberError::Checksum => write!(f, "has an invalid check digit"),
+        }
+    }
+}
+
+impl std::error::Error for IdentityNumberError {}
+
+/// A Swedish organisationsnummer, personnummer or samordningsnummer with a
+/// valid check digit.
+///
+/// Parsing accepts 10 digits, or 12 with the century (or the `16` prefix
+/// of organisation numbers), with or without a separator. It displays in
+/// the normalized 10 digit form `NNNNNN-NNNN`, with a `+` separator for
+/// people aged 100 or over. Without the century that is taken from the
+/// separator given, with it from the year of birth.
+///
+/// ```
+/// use spiris::validation::{IdentityNumber, IdentityNumberKind};
+///
+/// let number: IdentityNumber = "165566778899".parse().unwrap();
+/// assert_eq!(number.kind(), IdentityNumberKind::Organisation);
+/// assert_eq!(number.to_string(), "556677-8899");
+///
+/// assert!("556677-8898".parse::<IdentityNumber>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdentityNumber {
+    digits: [u8; 10],
+    centenarian: bool,
+    kind: IdentityNumberKind,
+}
+
+impl IdentityNumber {
+    /// Parse and check a number.
+    pub fn parse(value: &str) -> std::result::Result<Self, IdentityNumberError> {
+        let value = value.trim();
+        let separators = value.chars().filter(|c| matches!(c, '-' | '+')).count();
+        if separators > 1
+            || value
+                .chars()
+                .any(|c| !c.is_ascii_digit() && c != '-' && c != '+')
+        {
+            return Err(IdentityNumberError::Format);
+        }
+        let all: Vec<u8> = value
+            .bytes()
+            .filter(u8::is_ascii_digit)
+            .map(|b| b - b'0')
+            .collect();
+        let (prefix, digits) = match all.len() {
+            10 => (None, &all[..]),
+            12 => (Some(all[0] * 10 + all[1]), &all[2..]),
+            _ => return Err(IdentityNumberError::Format),
+        };
+        let digits: [u8; 10] = digits.try_into().map_err(|_| IdentityNumberError::Format)?;
+
+        let month = u32::from(digits[2] * 10 + digits[3]);
+        // Year of birth, when the century is given
+        let mut born = None;
+        let kind = if month >= 20 {
+            IdentityNumberKind::Organisation
+        } else {
+            let day = u32::from(digits[4] * 10 + digits[5]);
+            let (kind, day) = if day > 60 {
+                (IdentityNumberKind::Coordination, day - 60)
+            } else {
+                (IdentityNumberKind::Personal, day)
+            };
+            let year = match prefix {
+                Some(century) => i32::from(century) * 100,
+                // A leap year, so 29 February is accepted without a century
+                None => 2000,
+            } + i32::from(digits[0] * 10 + digits[1]);
+            if NaiveDate::from_ymd_opt(year, month, day).is_none() {
+                return Err(IdentityNumberError::Date);
+            }
+            born = prefix.map(|_| year);
+            kind
+        };
+        let valid_prefix = match (prefix, kind) {
+            (None, _) | (Some(16), IdentityNumberKind::Organisation) => true,
+            (Some(century), IdentityNumberKind::Personal | IdentityNumberKind::Coordination) => {
+                (18..=20).contains(&century)
+            }
+            _ => false,
+        };
+        if !valid_prefix {
+            return Err(IdentityNumberError::Format);
+        }
+        if !luhn(&digits) {
+            return Err(IdentityNumberError::Checksum);
+        }
+        // The separator becomes `+` in the year the person turns 100
+        let centenarian = match born {
+            Some(year) => Utc::now().year() - year >= 100,
+            None => value.contains('+'),
+        };
+        Ok(Self {
+            digits,
+            centenarian,
+            kind,
+        })
+    }
+
+    /// What the number identifies.
+    pub fn kind(&self) -> IdentityNumberKind {
+        self.kind
+    }
+}
+
+impl FromStr for IdentityNumber {
+    type Err = IdentityNumberError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for IdentityNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digit = |d: &u8| char::from(b'0' + d);
+        let separator = if self.centenarian { '+' } else { '-' };
+        let head: String = self.digits[..6].iter().map(digit).collect();
+        let tail: String = self.digits[6..].iter().map(digit).collect();
+        write!(f, "{}{}{}", head, separator, tail)
+    }
+}
+
+/// The Luhn (mod 10) check used by Swedish identity numbers, with every
+/// other digit doubled starting from the first.
+pub(crate) fn luhn(digits: &[u8]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            let d = u32::from(d);
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+/// Why a bankgiro, plusgiro, IBAN or BIC was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentNumberError {
+    /// Wrong length, or characters that don't belong in the number.
+    Format,
+    /// An IBAN for a country whose IBANs have another length.
+    Length,
+    /// The check digits don't match.
+    Checksum,
+}
+
+impl fmt::Display for PaymentNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaymentNumberError::Format => write!(f, "has the wrong length or characters"),
+            PaymentNumberError::Length => write!(f, "has the wrong length for its country"),
+            PaymentNumberError::Checksum => write!(f, "has invalid check digits"),
+        }
+    }
+}
+
+impl std::error::Error for PaymentNumberError {}
+
+/// Shared impls of the payment number newtypes, which hold their
+/// normalized form.
+macro_rules! payment_number {
+    ($type:ident) => {
+        impl $type {
+            /// The normalized number.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl FromStr for $type {
+            type Err = PaymentNumberError;
+
+            fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+                Self::parse(s)
+            }
+        }
+
+        impl TryFrom<String> for $type {
+            type Error = PaymentNumberError;
+
+            fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+                Self::parse(&value)
+            }
+        }
+
+        impl From<$type> for String {
+            fn from(value: $type) -> Self {
+                value.0
+            }
+        }
+
+        impl AsRef<str> for $type {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $type {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+    };
+}
+
+/// Digits of a Swedish giro number, ignoring spaces and dashes.
+fn giro_digits(value: &str) -> std::result::Result<Vec<u8>, PaymentNumberError> {
+    let value = value.trim();
+    if value.is_empty()
+        || value
+            .chars()
+            .any(|c| !c.is_ascii_digit() && c != '-' && c != ' ')
+    {
+        return Err(PaymentNumberError::Format);
+    }
+    Ok(value
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|b| b - b'0')
+        .collect())
+}
+
+/// A bankgiro number with a valid check digit, normalized to `NNN-NNNN` or
+/// `NNNN-NNNN`.
+///
+/// ```
+/// use spiris::validation::Bankgiro;
+///
+/// assert_eq!(Bankgiro::parse("50501055").unwrap().as_str(), "5050-1055");
+/// assert!(Bankgiro::parse("5050-1056").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Bankgiro(String);
+
+impl Bankgiro {
+    /// Parse and check a number, with or without the dash.
+    pub fn parse(value: &str) -> std::result::Result<Self, PaymentNumberError> {
+        let digits = giro_digits(value)?;
+        if !(7..=8).contains(&digits.len()) {
+            return Err(PaymentNumberError::Format);
+        }
+        if !luhn(&digits) {
+            return Err(PaymentNumberError::Checksum);
+        }
+        let text: String = digits.iter().map(|d| char::from(b'0' + d)).collect();
+        let (head, tail) = text.split_at(text.len() - 4);
+        Ok(Self(format!("{}-{}", head, tail)))
+    }
+}
+
+payment_number!(Bankgiro);
+
+/// A plusgiro number with a valid check digit, normalized to its digits
+/// with a dash before the check digit, such as `90050-6`.
+///
+/// ```
+/// use spiris::validation::Plusgiro;
+///
+/// assert_eq!(Plusgiro::parse("9 00 50-6").unwrap().as_str(), "90050-6");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Plusgiro(String);
+
+impl Plusgiro {
+    /// Parse and check a number, with or without spaces and the dash.
+    pub fn parse(value: &str) -> std::result::Result<Self, PaymentNumberError> {
+        let digits = giro_digits(value)?;
+        if !(2..=8).contains(&digits.len()) {
+            return Err(PaymentNumberError::Format);
+        }
+        if !luhn(&digits) {
+            return Err(PaymentNumberError::Checksum);
+        }
+        let text: String = digits.iter().map(|d| char::from(b'0' + d)).collect();
+        let (head, check) = text.split_at(text.len() - 1);
+        Ok(Self(format!("{}-{}", head, check)))
+    }
+}
+
+payment_number!(Plusgiro);
+
+/// IBAN lengths of countries payments commonly go to. IBANs from other
+/// countries are only checked against the general limits.
+const IBAN_LENGTHS: &[(&str, usize)] = &[
+    ("AT", 20),
+    ("BE", 16),
+    ("CH", 21),
+    ("DE", 22),
+    ("DK", 18),
+    ("EE", 20),
+    ("ES", 24),
+    ("FI", 18),
+    ("FR", 27),
+    ("GB", 22),
+    ("IE", 22),
+    ("IS", 26),
+    ("IT", 27),
+    ("LT", 20),
+    ("LV", 21),
+    ("NL", 18),
+    ("NO", 15),
+    ("PL", 28),
+    ("PT", 25),
+    ("SE", 24),
+];
+
+/// An IBAN with valid check digits, normalized to the electronic format:
+/// upper case without spaces.
+///
+/// ```
+/// use spiris::validation::Iban;
+///
+/// let iban = Iban::parse("se45 5000 0000 0583 9825 7466").unwrap();
+/// assert_eq!(iban.as_str(), "SE4550000000058398257466");
+/// assert_eq!(iban.country_code(), "SE");
+/// assert_eq!(iban.grouped(), "SE45 5000 0000 0583 9825 7466");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Iban(String);
+
+impl Iban {
+    /// Parse and check an IBAN, with or without spaces.
+    pub fn parse(value: &str) -> std::result::Result<Self, PaymentNumberError> {
+        let iban: String = value
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase();
+        let bytes = iban.as_bytes();
+        if !(15..=34).contains(&bytes.len())
+            || !bytes[..2].iter().all(u8::is_ascii_uppercase)
+            || !bytes[2..4].iter().all(u8::is_ascii_digit)
+            || !bytes.iter().all(u8::is_ascii_alphanumeric)
+        {
+            return Err(PaymentNumberError::Format);
+        }
+        if let Some((_, len)) = IBAN_LENGTHS.iter().find(|(c, _)| *c == &iban[..2]) {
+            if bytes.len() != *len {
+                return Err(PaymentNumberError::Length);
+            }
+        }
+        // ISO 13616: move the first four characters to the end, turn
+        // letters into 10-35 and check the number is 1 mod 97
+        let remainder = bytes[4..].iter().chain(&bytes[..4]).fold(0u32, |acc, &b| {
+            let value = u32::from(if b.is_ascii_digit() {
+                b - b'0'
+            } else {
+                b - b'A' + 10
+            });
+            if value >= 10 {
+                (acc * 100 + value) % 97
+            } else {
+                (acc * 10 + value) % 97
+            }
+        });
+        if remainder != 1 {
+            return Err(PaymentNumberError::Checksum);
+        }
+        Ok(Self(iban))
+    }
+
+    /// The two-letter country code.
+    pub fn country_code(&self) -> &str {
+        &self.0[..2]
+    }
+
+    /// The IBAN in groups of four, as printed on paper.
+    pub fn grouped(&self) -> String {
+        self.0
+            .as_bytes()
+            .chunks(4)
+            .map(|chunk| String::from_utf8_lossy(chunk))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+payment_number!(Iban);
+
+/// A BIC (SWIFT code), normalized to upper case.
+///
+/// A BIC has no check digits, so only its shape is checked: a four-letter
+/// bank code, a two-letter country code, a two-character location and an
+/// optional three-character branch.
+///
+/// ```
+/// use spiris::validation::Bic;
+///
+/// assert_eq!(Bic::parse("esessess").unwrap().country_code(), "SE");
+/// assert!(Bic::parse("ESES1SESS").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Bic(String);
+
+impl Bic {
+    /// Parse and check a BIC.
+    pub fn parse(value: &str) -> std::result::Result<Self, PaymentNumberError> {
+        let bic = value.trim().to_ascii_uppercase();
+        let bytes = bic.as_bytes();
+        if !(bytes.len() == 8 || bytes.len() == 11)
+            || !bytes[..6].iter().all(u8::is_ascii_uppercase)
+            || !bytes[6..].iter().all(u8::is_ascii_alphanumeric)
+        {
+            return Err(PaymentNumberError::Format);
+        }
+        Ok(Self(bic))
+    }
+
+    /// The two-letter country code.
+    pub fn country_code(&self) -> &str {
+        &self.0[4..6]
+    }
+}
+
+payment_number!(Bic);
+
+/// A Swedish OCR payment reference with a length digit and a Luhn check
+/// digit, as Bankgirot's hardest OCR control expects.
+///
+/// The second to last digit is the length of the whole reference, modulo
+/// 10, and the last digit is the Luhn check digit over everything before
+/// it. [`generate`](Self::generate) builds one from an invoice number.
+///
+/// ```
+/// use spiris::validation::OcrReference;
+///
+/// let ocr = OcrReference::generate("10042").unwrap();
+/// assert_eq!(ocr.as_str(), "1004274");
+/// assert!(OcrReference::parse("1004274").is_ok());
+/// assert!(OcrReference::parse("1004273").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct OcrReference(String);
+
+impl OcrReference {
+    /// Longest reference Bankgirot accepts.
+    pub const MAX_LEN: usize = 25;
+
+    /// Build a reference from the digits of `base`, such as an invoice
+    /// number, by adding the length and check digits.
+    ///
+    /// Other characters in `base` are ignored; it must contain between 1
+    /// and 23 digits.
+    pub fn generate(base: &str) -> std::result::Result<Self, PaymentNumberError> {
+        let mut digits: Vec<u8> = base
+            .bytes()
+            .filter(u8::is_ascii_digit)
+            .map(|b| b - b'0')
+            .collect();
+        if digits.is_empty() || digits.len() + 2 > Self::MAX_LEN {
+            return Err(PaymentNumberError::Format);
+        }
+        digits.push(((digits.len() + 2) % 10) as u8);
+        digits.push(0);
+        let last = digits.len() - 1;
+        // The check digit that makes the Luhn sum a multiple of 10
+        while !luhn(&digits) {
+            digits[last] += 1;
+        }
+        Ok(Self(digits.iter().map(|d| char::from(b'0' + d)).collect()))
+    }
+
+    /// Parse a reference and check its length and check digits. Spaces are
+    /// ignored.
+    pub fn parse(value: &str) -> std::result::Result<Self, PaymentNumberError> {
+        let ocr: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+        if !(2..=Self::MAX_LEN).contains(&ocr.len()) || !ocr.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(PaymentNumberError::Format);
+        }
+        let digits: Vec<u8> = ocr.bytes().map(|b| b - b'0').collect();
+        if usize::from(digits[digits.len() - 2]) != digits.len() % 10 || !luhn(&digits) {
+            return Err(PaymentNumberError::Checksum);
+        }
+        Ok(Self(ocr))
+    }
+}
+
+payment_number!(OcrReference);
+
+/// The OCR reference for `invoice_number`, as an [`Error::Validation`] on
+/// `InvoiceNumber` if it has no usable digits.
+pub(crate) fn ocr_for_invoice_number(invoice_number: &str) -> Result<String> {
+    OcrReference::generate(invoice_number)
+        .map(String::from)
+        .map_err(|e| {
+            Error::Validation(vec![ValidationError {
+                field: "InvoiceNumber".to_string(),
+                message: format!("'{}' {} for an OCR reference", invoice_number, e),
+            }])
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{InvoiceRow, SupplierInvoiceRow, VoucherRow};
+
+    fn fields(errors: &[ValidationError]) -> Vec<&str> {
+        errors.iter().map(|e| e.field.as_str()).collect()
+    }
+
+    #[test]
+    fn test_customer_reports_all_problems() {
+        let customer = Customer {
+            name: Some("  ".to_string()),
+            customer_number: Some("C".repeat(17)),
+            invoice_address: Some(Address {
+                country_code: Some("SWE".to_string()),
+                ..Default::default()
+            }),
+            delivery_method_id: Some(String::new()),
+            ..Default::default()
+        };
+
+        let errors = customer.validation_errors();
+        assert_eq!(
+            fields(&errors),
+            [
+                "Name",
+                "CustomerNumber",
+                "InvoiceAddress.CountryCode",
+                "DeliveryMethodId"
+            ]
+        );
+        assert_eq!(errors[1].message, "must be at most 16 characters, got 17");
+
+        match customer.validate() {
+            Err(Error::Validation(errors)) => assert_eq!(errors.len(), 4),
+            other => panic!("expected validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_valid_customer() {
+        let customer = Customer {
+            name: Some("Acme AB".to_string()),
+            ..Default::default()
+        };
+        assert!(customer.validate().is_ok());
+    }
+
+    #[test]
+    fn test_invoice_rows() {
+        let invoice = Invoice {
+            customer_id: Some("cust-1".into()),
+            currency_code: Some("SEK".to_string()),
+            rows: vec![
+                InvoiceRow {
+                    text: Some("Consulting".to_string()),
+                    ..Default::default()
+                },
+                InvoiceRow::default(),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(fields(&invoice.validation_errors()), ["Rows[1].ArticleId"]);
+
+        let empty = Invoice::default();
+        assert_eq!(fields(&empty.validation_errors()), ["CustomerId", "Rows"]);
+    }
+
+    #[test]
+    fn test_invoice_row_amounts_and_consistency() {
+        let invoice = Invoice {
+            customer_id: Some("cust-1".into()),
+            rows: vec![
+                InvoiceRow {
+                    article_id: Some("art-1".into()),
+                    unit_price: Some(crate::money!(-100.0)),
+                    quantity: Some(crate::money!(-2.0)),
+                    discount_percentage: Some(crate::money!(120.0)),
+                    ..Default::default()
+                },
+                InvoiceRow {
+                    text: Some("Consulting".to_string()),
+                    unit_price: Some(crate::money!(950.0)),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            fields(&invoice.validation_errors()),
+            [
+                "Rows[0].UnitPrice",
+                "Rows[0].DiscountPercentage",
+                "Rows[1].ArticleId"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_email_and_prices() {
+        let email = |email: &str| {
+            Customer {
+                name: Some("Acme AB".to_string()),
+                email: Some(email.to_string()),
+                ..Default::default()
+            }
+            .validation_errors()
+        };
+        assert!(email("info@acme.se").is_empty());
+        assert!(email("").is_empty());
+        for invalid in [
+            "info",
+            "@acme.se",
+            "info@acme",
+            "info@acme.",
+            "in fo@acme.se",
+            "a@b@c.se",
+        ] {
+            assert_eq!(fields(&email(invalid)), ["Email"], "{}", invalid);
+        }
+
+        let article = Article {
+            name: Some("Widget".to_string()),
+            sales_price: Some(crate::money!(-1.0)),
+            purchase_price: Some(Money::default()),
+            ..Default::default()
+        };
+        assert_eq!(fields(&article.validation_errors()), ["SalesPrice"]);
+
+        let invoice = SupplierInvoice {
+            supplier_id: Some("sup-1".to_string()),
+            currency_rate: Some(Money::default()),
+            ..Default::default()
+        };
+        assert_eq!(fields(&invoice.validation_errors()), ["CurrencyRate"]);
+    }
+
+    #[test]
+    fn test_invoice_to_eu_buyer_needs_vat_treatment() {
+        let row = |is_vat_free: Option<bool>| InvoiceRow {
+            text: Some("Consulting".to_string()),
+            is_vat_free,
+            ..Default::default()
+        };
+        let mut invoice = Invoice {
+            customer_id: Some("cust-1".into()),
+            customer_vat_number: Some("DE123456789".to_string()),
+            rows: vec![row(None), row(Some(false)), row(Some(true))],
+            ..Default::default()
+        };
+        assert_eq!(fields(&invoice.validation_errors()), ["Rows[0].IsVatFree"]);
+
+        invoice.customer_vat_number = Some("SE556677889901".to_string());
+        assert!(invoice.validation_errors().is_empty());
+    }
+
+    #[test]
+    fn test_voucher_balance() {
+        let row = |account: &str, debit: Money, credit: Money| VoucherRow {
+            account_number: Some(account.to_string()),
+            debit_amount: Some(debit),
+            credit_amount: Some(credit),
+            ..Default::default()
+        };
+        let mut voucher = Voucher {
+            voucher_date: Some(chrono::Utc::now()),
+            rows: vec![
+                row("7010", crate::money!(25000.0), Money::default()),
+                row("2710", Money::default(), crate::money!(7500.0)),
+                row("1930", Money::default(), crate::money!(17500.0)),
+            ],
+            ..Default::default()
+        };
+        assert!(voucher.validate().is_ok());
+
+        voucher.rows[2].credit_amount = Some(crate::money!(17000.0));
+        voucher
+            .rows
+            .push(row("1930", Money::default(), Money::default()));
+        let errors = voucher.validation_errors();
+        assert_eq!(fields(&errors), ["Rows[3].DebitAmount", "Rows"]);
+        assert_eq!(
+            errors[1].message,
+            "debit 25000.00 and credit 24500.00 do not balance"
+        );
+    }
+
+    #[test]
+    fn test_enum_values() {
+        let invoice = SupplierInvoice {
+            supplier_id: Some("sup-1".to_string()),
+            approval_status: Some(7),
+            rows: vec![SupplierInvoiceRow::default()],
+            ..Default::default()
+        };
+        assert_eq!(
+            fields(&invoice.validation_errors()),
+            ["ApprovalStatus", "Rows[0].AccountNumber"]
+        );
+
+        let fiscal_year = FiscalYear {
+            bookkeeping_method: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(
+            fields(&fiscal_year.validation_errors()),
+            ["StartDate", "EndDate", "BookkeepingMethod"]
+        );
+    }
+
+    #[test]
+    fn test_identity_numbers() {
+        for (input, normalized, kind) in [
+            (
+                "556677-8899",
+                "556677-8899",
+                IdentityNumberKind::Organisation,
+            ),
+            (
+                "16556677-8899",
+                "556677-8899",
+                IdentityNumberKind::Organisation,
+            ),
+            ("811218-9876", "811218-9876", IdentityNumberKind::Personal),
+            ("198112189876", "811218-9876", IdentityNumberKind::Personal),
+            ("121212+1212", "121212+1212", IdentityNumberKind::Personal),
+            ("191212121212", "121212+1212", IdentityNumberKind::Personal),
+            ("19121212-1212", "121212+1212", IdentityNumberKind::Personal),
+            ("201212121212", "121212-1212", IdentityNumberKind::Personal),
+            (
+                "701063-2391",
+                "701063-2391",
+                IdentityNumberKind::Coordination,
+            ),
+        ] {
+            let number = IdentityNumber::parse(input).unwrap();
+            assert_eq!(number.to_string(), normalized, "{}", input);
+            assert_eq!(number.kind(), kind, "{}", input);
+        }
+
+        for (input, error) in [
+            ("556677-8898", IdentityNumberError::Checksum),
+            ("55667788", IdentityNumberError::Format),
+            ("5566-778-899", IdentityNumberError::Format),
+            ("195566778899", IdentityNumberError::Format),
+            ("811318-9876", IdentityNumberError::Date),
+            ("19000229-1234", IdentityNumberError::Date),
+        ] {
+            assert_eq!(IdentityNumber::parse(input), Err(error), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_customer_identity_number() {
+        let customer = |number: &str, private: bool, country: &str| Customer {
+            name: Some("Acme AB".to_string()),
+            corporate_identity_number: Some(number.to_string()),
+            is_private_person: Some(private),
+            invoice_address: Some(Address {
+                country_code: Some(country.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let unformatted = customer("5566778899", false, "SE");
+        let normalized = normalize_customer(&unformatted).unwrap();
+        assert_eq!(
+            normalized.corporate_identity_number.as_deref(),
+            Some("556677-8899")
+        );
+        assert!(matches!(
+            normalize_customer(&customer("556677-8899", false, "SE")).unwrap(),
+            Cow::Borrowed(_)
+        ));
+
+        assert_eq!(
+            fields(&customer("556677-8898", false, "SE").validation_errors()),
+            ["CorporateIdentityNumber"]
+        );
+        assert_eq!(
+            fields(&customer("556677-8899", true, "SE").validation_errors()),
+            ["CorporateIdentityNumber"]
+        );
+        assert!(customer("HRB 12345", false, "DE").validate().is_ok());
+        let without_country = Customer {
+            invoice_address: None,
+            ..customer("HRB 12345", false, "SE")
+        };
+        assert!(without_country.validate().is_ok());
+        assert!(matches!(
+            normalize_customer(&without_country).unwrap(),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!(
+            normalize_customer(&customer("556677-8898", false, "SE")),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_payment_numbers() {
+        assert_eq!(
+            Bankgiro::parse("505-1055").map(String::from),
+            Err(PaymentNumberError::Checksum)
+        );
+        assert_eq!(
+            Bankgiro::parse("450-5112 5").map(String::from),
+            Ok("4505-1125".to_string())
+        );
+        assert_eq!(
+            Bankgiro::parse("123456").map(String::from),
+            Err(PaymentNumberError::Format)
+        );
+        assert_eq!(
+            Plusgiro::parse("4774840").map(String::from),
+            Ok("477484-0".to_string())
+        );
+        assert_eq!(
+            Plusgiro::parse("4774841").map(String::from),
+            Err(PaymentNumberError::Checksum)
+        );
+
+        assert_eq!(
+            Iban::parse("DE89 3704 0044 0532 0130 00").map(String::from),
+            Ok("DE89370400440532013000".to_string())
+        );
+        assert_eq!(
+            Iban::parse("GB29NWBK60161331926818"),
+            Err(PaymentNumberError::Checksum)
+        );
+        assert_eq!(
+            Iban::parse("SE455000000005839825746"),
+            Err(PaymentNumberError::Length)
+        );
+        assert_eq!(
+            Iban::parse("5E4550000000058398257466"),
+            Err(PaymentNumberError::Format)
+        );
+
+        assert_eq!(
+            Bic::parse("handsess").map(String::from),
+            Ok("HANDSESS".to_string())
+        );
+        assert_eq!(
+            Bic::parse("NDEASESSXXX").map(String::from),
+            Ok("NDEASESSXXX".to_string())
+        );
+        assert_eq!(Bic::parse("NDEASES"), Err(PaymentNumberError::Format));
+
+        let iban: Iban = serde_json::from_str(r#""se45 5000 0000 0583 9825 7466""#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&iban).unwrap(),
+            r#""SE4550000000058398257466""#
+        );
+        assert!(serde_json::from_str::<Bankgiro>(r#""5050-1056""#).is_err());
+    }
+
+    #[test]
+    fn test_supplier_and_bank_account_payment_numbers() {
+        let supplier = Supplier {
+            name: Some("Leverantören AB".to_string()),
+            bank_giro_number: Some("50501055".to_string()),
+            plus_giro_number: Some("90050-6".to_string()),
+            ..Default::default()
+        };
+        let normalized = normalize_supplier(&supplier).unwrap();
+        assert_eq!(normalized.bank_giro_number.as_deref(), Some("5050-1055"));
+        assert_eq!(normalized.plus_giro_number.as_deref(), Some("90050-6"));
+
+        let account = BankAccount {
+            iban: Some("SE45 5000 0000 0583 9825 7467".to_string()),
+            bic: Some("ESSESESS".to_string()),
+            bank_giro_number: Some("5050-1055".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(fields(&account.validation_errors()), ["Iban"]);
+        assert!(matches!(
+            normalize_bank_account(&account),
+            Err(Error::Validation(errors)) if fields(&errors) == ["Iban"]
+        ));
+
+        let account = BankAccount {
+            iban: Some("SE4550000000058398257466".to_string()),
+            ..account
+        };
+        assert!(matches!(
+            normalize_bank_account(&account).unwrap(),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_ocr_references() {
+        assert_eq!(
+            OcrReference::generate("1").map(String::from),
+            Ok("133".to_string())
+        );
+        assert_eq!(
+            OcrReference::generate("F-2025-0042").map(String::from),
+            Ok("2025004207".to_string())
+        );
+        assert_eq!(
+            OcrReference::generate("F-"),
+            Err(PaymentNumberError::Format)
+        );
+        assert_eq!(
+            OcrReference::generate(&"1".repeat(24)),
+            Err(PaymentNumberError::Format)
+        );
+
+        for base in ["7", "10042", "123456789", "9999999999999999999"] {
+            let ocr = OcrReference::generate(base).unwrap();
+            assert_eq!(OcrReference::parse(ocr.as_str()), Ok(ocr));
+        }
+        // Correct Luhn digit but wrong length digit
+        assert_eq!(
+            OcrReference::parse("1004282"),
+            Err(PaymentNumberError::Checksum)
+        );
+        assert_eq!(
+            OcrReference::parse("10O4274"),
+            Err(PaymentNumberError::Format)
+        );
+    }
+}
@@ -0,0 +1,629 @@
+//! VAT helpers for special invoicing rules.
+//!
+//! Swedish construction services sold between VAT-registered companies are
+//! subject to reverse charge (*omvänd betalningsskyldighet*, commonly called
+//! "omvänd byggmoms"). The seller invoices without VAT, the buyer accounts for
+//! it, and the invoice must state the buyer's VAT number together with a
+//! reference to the reverse charge rules.
+//!
+//! Sales to VAT-registered buyers in other EU countries work the same way
+//! and are handled by [`IntraEuSale`]. VAT numbers are only checked for
+//! format here; the VIES client (`vies` feature) confirms that they are
+//! registered. With
+//! [`ClientConfig::validate_requests`](crate::ClientConfig::validate_requests)
+//! on, an invoice to a foreign EU VAT number fails validation if a row
+//! leaves its VAT unset, but rows that explicitly charge Swedish VAT are
+//! sent as they are.
+//!
+//! # Example
+//!
+//! ```
+//! use spiris::vat::{ReverseChargeConstruction, REVERSE_CHARGE_CONSTRUCTION_TEXT};
+//! use spiris::{Invoice, InvoiceRow};
+//!
+//! let mut invoice = Invoice {
+//!     customer_id: Some("customer-123".into()),
+//!     rows: vec![InvoiceRow {
+//!         text: Some("Installation work".to_string()),
+//!         ..Default::default()
+//!     }],
+//!     ..Default::default()
+//! };
+//!
+//! ReverseChargeConstruction::new("SE556677889901").apply(&mut invoice)?;
+//!
+//! assert_eq!(invoice.reverse_charge_on_construction_services, Some(true));
+//! assert_eq!(invoice.rows[0].is_vat_free, Some(true));
+//! assert!(invoice
+//!     .remarks
+//!     .as_deref()
+//!     .unwrap()
+//!     .contains(REVERSE_CHARGE_CONSTRUCTION_TEXT));
+//! # Ok::<(), spiris::Error>(())
+//! ```
+
+use crate::error::{Error, Result};
+use crate::types::{Customer, Invoice};
+use crate::validation::IdentityNumber;
+
+/// Text that must appear on invoices subject to reverse charge on construction services.
+pub const REVERSE_CHARGE_CONSTRUCTION_TEXT: &str = "Omvänd betalningsskyldighet för byggtjänster";
+
+/// Country prefixes used in EU VAT registration numbers.
+///
+/// Greece uses `EL` rather than its ISO code, and `XI` covers Northern Ireland.
+const EU_VAT_PREFIXES: &[&str] = &[
+    "AT", "BE", "BG", "CY", "CZ", "DE", "DK", "EE", "EL", "ES", "FI", "FR", "HR", "HU", "IE", "IT",
+    "LT", "LU", "LV", "MT", "NL", "PL", "PT", "RO", "SE", "SI", "SK", "XI",
+];
+
+/// Normalize and check the format of an EU VAT registration number.
+///
+/// Whitespace, dashes and dots are stripped and the country prefix is
+/// upper-cased. Swedish numbers must consist of `SE`, a ten digit
+/// organisation number and the suffix `01`.
+///
+/// This only checks the format; whether the number is actually registered
+/// has to be verified against the tax authority.
+///
+/// # Example
+///
+/// ```
+/// use spiris::vat::normalize_vat_number;
+///
+/// assert_eq!(normalize_vat_number("se 556677-8899 01").unwrap(), "SE556677889901");
+/// assert!(normalize_vat_number("5566778899").is_err());
+/// ```
+pub fn normalize_vat_number(value: &str) -> Result<String> {
+    let normalized: String = value
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-' && *c != '.')
+        .collect::<String>()
+        .to_uppercase();
+
+    let invalid =
+        |reason: &str| Error::InvalidRequest(format!("Invalid VAT number '{}': {}", value, reason));
+
+    if normalized.len() < 4 || !normalized.is_char_boundary(2) {
+        return Err(invalid("too short"));
+    }
+
+    let (prefix, number) = normalized.split_at(2);
+    if !EU_VAT_PREFIXES.contains(&prefix) {
+        return Err(invalid("missing or unknown country prefix"));
+    }
+    if number.len() > 12 || !number.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(invalid("unexpected characters or length"));
+    }
+    if prefix == "SE"
+        && (number.len() != 12
+            || !number.ends_with("01")
+            || !number.chars().all(|c| c.is_ascii_digit()))
+    {
+        return Err(invalid("Swedish VAT numbers are SE + 10 digits + 01"));
+    }
+
+    Ok(normalized)
+}
+
+/// Reverse charge on construction services (omvänd byggmoms).
+///
+/// Applying it to an invoice sets the reverse charge flag and the buyer's
+/// validated VAT number, marks rows as VAT free and adds the mandated text
+/// to the invoice remarks.
+///
+/// The scheme is domestic, so the buyer must have a Swedish VAT number; an
+/// organisation number is turned into one. Invoices already set up as an
+/// [`IntraEuSale`] are refused.
+///
+/// Rows explicitly marked with `is_vat_free: Some(false)` are normal VAT rows.
+/// Mixing those with reverse charge rows is refused unless
+/// [`allow_mixed_rows`](Self::allow_mixed_rows) is enabled, e.g. when the
+/// invoice also covers goods or services outside the construction sector.
+#[derive(Debug, Clone)]
+pub struct ReverseChargeConstruction {
+    buyer_vat_number: String,
+    allow_mixed_rows: bool,
+}
+
+impl ReverseChargeConstruction {
+    /// Create reverse charge settings for a buyer's Swedish VAT number or
+    /// organisation number.
+    pub fn new(buyer_vat_number: impl Into<String>) -> Self {
+        Self {
+            buyer_vat_number: buyer_vat_number.into(),
+            allow_mixed_rows: false,
+        }
+    }
+
+    /// Allow normal VAT rows alongside reverse charge rows.
+    pub fn allow_mixed_rows(mut self, allow: bool) -> Self {
+        self.allow_mixed_rows = allow;
+        self
+    }
+
+    /// Check and apply reverse charge to an invoice.
+    ///
+    /// The invoice is left untouched if validation fails.
+    pub fn apply(&self, invoice: &mut Invoice) -> Result<()> {
+        let vat_number = swedish_vat_number(&self.buyer_vat_number)?;
+        let foreign_buyer = invoice
+            .customer_vat_number
+            .as_deref()
+            .is_some_and(is_foreign_eu_vat_number);
+        if invoice.eu_third_party == Some(true) || foreign_buyer {
+            return Err(Error::InvalidRequest(
+                "Invoice is already an intra-community supply".into(),
+            ));
+        }
+
+        let normal_rows: Vec<usize> = invoice
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.is_vat_free == Some(false))
+            .map(|(index, _)| index)
+            .collect();
+
+        if !normal_rows.is_empty() && !self.allow_mixed_rows {
+            return Err(Error::InvalidRequest(format!(
+                "Reverse charge invoice contains normal VAT rows at positions {:?}",
+                normal_rows
+            )));
+        }
+        if !invoice.rows.is_empty() && normal_rows.len() == invoice.rows.len() {
+            return Err(Error::InvalidRequest(
+                "Reverse charge invoice has no VAT free rows".into(),
+            ));
+        }
+
+        for row in &mut invoice.rows {
+            if row.is_vat_free.is_none() {
+                row.is_vat_free = Some(true);
+            }
+        }
+
+        invoice.reverse_charge_on_construction_services = Some(true);
+        invoice.customer_vat_number = Some(vat_number);
+        append_remark(invoice, REVERSE_CHARGE_CONSTRUCTION_TEXT);
+
+        Ok(())
+    }
+}
+
+/// The Swedish VAT number of `value`, a VAT number or an organisation number.
+fn swedish_vat_number(value: &str) -> Result<String> {
+    if let Ok(number) = IdentityNumber::parse(value) {
+        return Ok(format!(
+            "SE{}01",
+            number.to_string().replace(['-', '+'], "")
+        ));
+    }
+    let vat_number = normalize_vat_number(value)?;
+    if !vat_number.starts_with("SE") {
+        return Err(Error::InvalidRequest(format!(
+            "Reverse charge on construction services requires a Swedish VAT number, got '{}'",
+            value
+        )));
+    }
+    Ok(vat_number)
+}
+
+/// Kind of sale to a VAT-registered buyer in another EU country.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EuSaleKind {
+    /// Goods shipped to the buyer's country.
+    Goods,
+    /// Services taxed where the buyer is established (main rule).
+    Services,
+    /// Goods sold as the middle party in a triangular trade (trepartshandel).
+    Triangulation,
+}
+
+impl EuSaleKind {
+    /// Box of the Swedish VAT return (momsdeklaration) the sale is reported in.
+    pub fn vat_return_box(self) -> u8 {
+        match self {
+            EuSaleKind::Goods => 35,
+            EuSaleKind::Services => 39,
+            EuSaleKind::Triangulation => 38,
+        }
+    }
+
+    /// Text that must appear on the invoice.
+    pub fn invoice_text(self) -> &'static str {
+        match self {
+            EuSaleKind::Goods => {
+                "Intra-community supply of goods exempt from VAT, Article 138 Council Directive 2006/112/EC"
+            }
+            EuSaleKind::Services => {
+                "Reverse charge, Article 196 Council Directive 2006/112/EC"
+            }
+            EuSaleKind::Triangulation => {
+                "Triangulation, Article 141 Council Directive 2006/112/EC: VAT is accounted for by the customer"
+            }
+        }
+    }
+}
+
+/// A sale to a VAT-registered buyer in another EU country.
+///
+/// Applying it to an invoice sets the buyer's validated VAT number, marks all
+/// rows as VAT free, flags triangular trade and adds the text required for
+/// the kind of sale to the invoice remarks. Swedish VAT numbers are refused,
+/// since those buyers are charged Swedish VAT.
+///
+/// The number is only checked for format here; use the VIES client (`vies`
+/// feature) to confirm that it is registered.
+///
+/// # Example
+///
+/// ```
+/// use spiris::vat::{EuSaleKind, IntraEuSale};
+/// use spiris::{Invoice, InvoiceRow};
+///
+/// let mut invoice = Invoice {
+///     customer_id: Some("customer-123".into()),
+///     rows: vec![InvoiceRow::default()],
+///     ..Default::default()
+/// };
+///
+/// IntraEuSale::new(EuSaleKind::Services, "DE 123456789").apply(&mut invoice)?;
+///
+/// assert_eq!(invoice.customer_vat_number.as_deref(), Some("DE123456789"));
+/// assert_eq!(invoice.rows[0].is_vat_free, Some(true));
+/// # Ok::<(), spiris::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct IntraEuSale {
+    kind: EuSaleKind,
+    buyer_vat_number: String,
+}
+
+impl IntraEuSale {
+    /// Create an EU sale to the holder of `buyer_vat_number`.
+    pub fn new(kind: EuSaleKind, buyer_vat_number: impl Into<String>) -> Self {
+        Self {
+            kind,
+            buyer_vat_number: buyer_vat_number.into(),
+        }
+    }
+
+    /// Create an EU sale to a customer, using the VAT number on the customer.
+    pub fn for_customer(kind: EuSaleKind, customer: &Customer) -> Result<Self> {
+        let vat_number = customer.vat_number.as_deref().ok_or_else(|| {
+            Error::InvalidRequest(format!(
+                "Customer {} has no VAT number",
+                customer.name.as_deref().unwrap_or("without name")
+            ))
+        })?;
+        Ok(Self::new(kind, vat_number))
+    }
+
+    /// The kind of sale.
+    pub fn kind(&self) -> EuSaleKind {
+        self.kind
+    }
+
+    /// Check and apply the EU sale to an invoice.
+    ///
+    /// The invoice is left untouched if validation fails.
+    pub fn apply(&self, invoice: &mut Invoice) -> Result<()> {
+        let vat_number = self.check(invoice)?;
+
+        for row in &mut invoice.rows {
+            row.is_vat_free = Some(true);
+        }
+        invoice.customer_vat_number = Some(vat_number);
+        invoice.eu_third_party = Some(self.kind == EuSaleKind::Triangulation);
+
+        append_remark(invoice, self.kind.invoice_text());
+
+        Ok(())
+    }
+
+    /// Check that the sale can be applied to `invoice`, returning the
+    /// normalized buyer VAT number.
+    pub(crate) fn check(&self, invoice: &Invoice) -> Result<String> {
+        let vat_number = normalize_vat_number(&self.buyer_vat_number)?;
+        if vat_number.starts_with("SE") {
+            return Err(Error::InvalidRequest(
+                "Domestic buyers cannot be invoiced as intra-community supplies".into(),
+            ));
+        }
+        // Northern Ireland follows EU rules for goods only
+        if vat_number.starts_with("XI") && self.kind == EuSaleKind::Services {
+            return Err(Error::InvalidRequest(
+                "Services to Northern Ireland (XI) are not intra-community supplies".into(),
+            ));
+        }
+        if invoice.reverse_charge_on_construction_services == Some(true) {
+            return Err(Error::InvalidRequest(
+                "Invoice already uses reverse charge on construction services".into(),
+            ));
+        }
+        Ok(vat_number)
+    }
+}
+
+/// Add `text` on a line of its own at the end of the invoice remarks, unless
+/// the remarks already contain it.
+pub(crate) fn append_remark(invoice: &mut Invoice, text: &str) {
+    invoice.remarks = Some(match invoice.remarks.take() {
+        Some(remarks) if remarks.contains(text) => remarks,
+        Some(remarks) if !remarks.trim().is_empty() => format!("{}\n{}", remarks, text),
+        _ => text.to_string(),
+    });
+}
+
+/// Whether `vat_number` belongs to a buyer registered in another EU country.
+pub(crate) fn is_foreign_eu_vat_number(vat_number: &str) -> bool {
+    normalize_vat_number(vat_number).is_ok_and(|n| !n.starts_with("SE"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::InvoiceRow;
+
+    fn invoice_with_rows(rows: Vec<InvoiceRow>) -> Invoice {
+        Invoice {
+            customer_id: Some("cust-1".into()),
+            rows,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_normalize_vat_number() {
+        assert_eq!(
+            normalize_vat_number("SE556677889901").unwrap(),
+            "SE556677889901"
+        );
+        assert_eq!(
+            normalize_vat_number("se 5566.7788-9901").unwrap(),
+            "SE556677889901"
+        );
+        assert_eq!(normalize_vat_number("DE123456789").unwrap(), "DE123456789");
+    }
+
+    #[test]
+    fn test_normalize_vat_number_rejects_invalid() {
+        assert!(normalize_vat_number("").is_err());
+        assert!(normalize_vat_number("5566778899").is_err());
+        assert!(normalize_vat_number("US123456789").is_err());
+        assert!(normalize_vat_number("SE5566778899").is_err());
+        assert!(normalize_vat_number("SE556677889902").is_err());
+        assert!(normalize_vat_number("DE1234_56789").is_err());
+    }
+
+    #[test]
+    fn test_append_remark() {
+        let mut invoice = Invoice::default();
+        append_remark(&mut invoice, "Reverse charge");
+        assert_eq!(invoice.remarks.as_deref(), Some("Reverse charge"));
+
+        invoice.remarks = Some("Project Alpha".to_string());
+        append_remark(&mut invoice, "Reverse charge");
+        append_remark(&mut invoice, "Reverse charge");
+        assert_eq!(
+            invoice.remarks.as_deref(),
+            Some("Project Alpha\nReverse charge")
+        );
+    }
+
+    #[test]
+    fn test_apply_marks_rows_and_adds_text() {
+        let mut invoice = invoice_with_rows(vec![InvoiceRow::default(), InvoiceRow::default()]);
+        invoice.remarks = Some("Project Alpha".to_string());
+
+        ReverseChargeConstruction::new("SE 556677889901")
+            .apply(&mut invoice)
+            .unwrap();
+
+        assert_eq!(invoice.reverse_charge_on_construction_services, Some(true));
+        assert_eq!(
+            invoice.customer_vat_number.as_deref(),
+            Some("SE556677889901")
+        );
+        assert!(invoice.rows.iter().all(|r| r.is_vat_free == Some(true)));
+        assert_eq!(
+            invoice.remarks.as_deref(),
+            Some("Project Alpha\nOmvänd betalningsskyldighet för byggtjänster")
+        );
+    }
+
+    #[test]
+    fn test_apply_is_idempotent() {
+        let mut invoice = invoice_with_rows(vec![InvoiceRow::default()]);
+        let reverse_charge = ReverseChargeConstruction::new("SE556677889901");
+
+        reverse_charge.apply(&mut invoice).unwrap();
+        reverse_charge.apply(&mut invoice).unwrap();
+
+        assert_eq!(
+            invoice.remarks.as_deref(),
+            Some(REVERSE_CHARGE_CONSTRUCTION_TEXT)
+        );
+    }
+
+    #[test]
+    fn test_apply_rejects_mixed_rows() {
+        let normal = InvoiceRow {
+            is_vat_free: Some(false),
+            ..Default::default()
+        };
+        let mut invoice = invoice_with_rows(vec![InvoiceRow::default(), normal]);
+
+        let err = ReverseChargeConstruction::new("SE556677889901")
+            .apply(&mut invoice)
+            .unwrap_err();
+
+        assert!(err.to_string().contains("[1]"));
+        assert!(invoice.reverse_charge_on_construction_services.is_none());
+        assert!(invoice.rows[0].is_vat_free.is_none());
+    }
+
+    #[test]
+    fn test_apply_allows_mixed_rows_when_enabled() {
+        let normal = InvoiceRow {
+            is_vat_free: Some(false),
+            ..Default::default()
+        };
+        let mut invoice = invoice_with_rows(vec![InvoiceRow::default(), normal]);
+
+        ReverseChargeConstruction::new("SE556677889901")
+            .allow_mixed_rows(true)
+            .apply(&mut invoice)
+            .unwrap();
+
+        assert_eq!(invoice.rows[0].is_vat_free, Some(true));
+        assert_eq!(invoice.rows[1].is_vat_free, Some(false));
+    }
+
+    #[test]
+    fn test_apply_requires_reverse_charge_rows() {
+        let normal = InvoiceRow {
+            is_vat_free: Some(false),
+            ..Default::default()
+        };
+        let mut invoice = invoice_with_rows(vec![normal]);
+
+        let result = ReverseChargeConstruction::new("SE556677889901")
+            .allow_mixed_rows(true)
+            .apply(&mut invoice);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_apply_rejects_invalid_vat_number() {
+        let mut invoice = invoice_with_rows(vec![InvoiceRow::default()]);
+
+        let result = ReverseChargeConstruction::new("not-a-vat-number").apply(&mut invoice);
+
+        assert!(result.is_err());
+        assert!(invoice.customer_vat_number.is_none());
+    }
+
+    #[test]
+    fn test_apply_requires_swedish_buyer() {
+        let mut invoice = invoice_with_rows(vec![InvoiceRow::default()]);
+
+        let err = ReverseChargeConstruction::new("DE123456789")
+            .apply(&mut invoice)
+            .unwrap_err();
+
+        assert!(err.to_string().contains("Swedish"), "{}", err);
+        assert!(invoice.reverse_charge_on_construction_services.is_none());
+
+        ReverseChargeConstruction::new("556677-8899")
+            .apply(&mut invoice)
+            .unwrap();
+        assert_eq!(
+            invoice.customer_vat_number.as_deref(),
+            Some("SE556677889901")
+        );
+    }
+
+    #[test]
+    fn test_apply_refuses_intra_eu_invoice() {
+        let mut invoice = invoice_with_rows(vec![InvoiceRow::default()]);
+        IntraEuSale::new(EuSaleKind::Goods, "DE123456789")
+            .apply(&mut invoice)
+            .unwrap();
+
+        let result = ReverseChargeConstruction::new("SE556677889901").apply(&mut invoice);
+
+        assert!(result.is_err());
+        assert!(invoice.reverse_charge_on_construction_services.is_none());
+        assert_eq!(invoice.customer_vat_number.as_deref(), Some("DE123456789"));
+
+        let mut triangulation = invoice_with_rows(vec![InvoiceRow::default()]);
+        triangulation.eu_third_party = Some(true);
+        assert!(ReverseChargeConstruction::new("SE556677889901")
+            .apply(&mut triangulation)
+            .is_err());
+    }
+
+    #[test]
+    fn test_intra_eu_sale() {
+        let mut invoice = invoice_with_rows(vec![InvoiceRow {
+            is_vat_free: Some(false),
+            ..Default::default()
+        }]);
+
+        IntraEuSale::new(EuSaleKind::Goods, "de123456789")
+            .apply(&mut invoice)
+            .unwrap();
+
+        assert_eq!(invoice.customer_vat_number.as_deref(), Some("DE123456789"));
+        assert_eq!(invoice.rows[0].is_vat_free, Some(true));
+        assert_eq!(invoice.eu_third_party, Some(false));
+        assert_eq!(
+            invoice.remarks.as_deref(),
+            Some(EuSaleKind::Goods.invoice_text())
+        );
+        assert_eq!(EuSaleKind::Goods.vat_return_box(), 35);
+    }
+
+    #[test]
+    fn test_intra_eu_triangulation_sets_flag() {
+        let mut invoice = invoice_with_rows(vec![InvoiceRow::default()]);
+
+        IntraEuSale::new(EuSaleKind::Triangulation, "FI12345678")
+            .apply(&mut invoice)
+            .unwrap();
+
+        assert_eq!(invoice.eu_third_party, Some(true));
+        let json = serde_json::to_value(&invoice).unwrap();
+        assert_eq!(json["EuThirdParty"], true);
+    }
+
+    #[test]
+    fn test_intra_eu_sale_rejects_domestic_and_conflicting_buyers() {
+        let mut invoice = invoice_with_rows(vec![InvoiceRow::default()]);
+
+        assert!(IntraEuSale::new(EuSaleKind::Goods, "SE556677889901")
+            .apply(&mut invoice)
+            .is_err());
+        assert!(IntraEuSale::new(EuSaleKind::Services, "XI123456789")
+            .apply(&mut invoice)
+            .is_err());
+        assert!(IntraEuSale::new(EuSaleKind::Goods, "XI123456789")
+            .apply(&mut invoice.clone())
+            .is_ok());
+
+        invoice.reverse_charge_on_construction_services = Some(true);
+        assert!(IntraEuSale::new(EuSaleKind::Goods, "DE123456789")
+            .apply(&mut invoice)
+            .is_err());
+        assert!(invoice.customer_vat_number.is_none());
+    }
+
+    #[test]
+    fn test_intra_eu_sale_for_customer() {
+        let customer = Customer {
+            name: Some("Beispiel GmbH".to_string()),
+            vat_number: Some("DE123456789".to_string()),
+            ..Default::default()
+        };
+        let sale = IntraEuSale::for_customer(EuSaleKind::Services, &customer).unwrap();
+        assert_eq!(sale.kind(), EuSaleKind::Services);
+
+        assert!(IntraEuSale::for_customer(EuSaleKind::Services, &Customer::default()).is_err());
+    }
+
+    #[test]
+    fn test_invoice_serializes_reverse_charge_fields() {
+        let mut invoice = invoice_with_rows(vec![InvoiceRow::default()]);
+        ReverseChargeConstruction::new("SE556677889901")
+            .apply(&mut invoice)
+            .unwrap();
+
+        let json = serde_json::to_value(&invoice).unwrap();
+        assert_eq!(json["ReverseChargeOnConstructionServices"], true);
+        assert_eq!(json["CustomerVatNumber"], "SE556677889901");
+        assert_eq!(json["Rows"][0]["IsVatFree"], true);
+    }
+}
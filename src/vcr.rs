@@ -0,0 +1,321 @@
+//! Record and replay of HTTP interactions ("cassettes").
+//!
+//! A [`Cassette`] set through [`ClientConfig::cassette`](crate::ClientConfig::cassette)
+//! sees every HTTP attempt the client makes, including retries. When
+//! recording it sends the request and keeps the method, URL, body and the
+//! full response; [`Cassette::save`] writes them to a JSON file. When
+//! replaying it answers each request from the file without touching the
+//! network, so tests captured against the real API run offline in CI.
+//!
+//! The `Authorization` header is never written. Request and response bodies
+//! go through the cassette's [`Redactor`], which by default masks OAuth
+//! tokens and secrets; add personal data fields with
+//! [`Cassette::redactor`] before committing cassettes.
+//!
+//! Requests are matched on method and URL. Repeated requests get the
+//! recorded responses in order, so a replayed retry sees the same failures
+//! as the recorded one.
+//!
+//! Requires the `vcr` feature.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::vcr::Cassette;
+//! use spiris::{AccessToken, Client, ClientConfig};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! // Records on the first run, replays once the file exists
+//! let cassette = Cassette::open("tests/cassettes/list_customers.json")?;
+//! let token = std::env::var("SPIRIS_ACCESS_TOKEN").unwrap_or_default();
+//! let client = Client::with_config(
+//!     AccessToken::new(token, 3600, None),
+//!     ClientConfig::new().cassette(cassette.clone()),
+//! );
+//!
+//! let customers = client.customers().list(None).await?;
+//! cassette.save()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::redact::Redactor;
+use base64::Engine;
+use reqwest::{RequestBuilder, Response, ResponseBuilderExt};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+/// Fields masked by a new cassette's redactor.
+pub const DEFAULT_CASSETTE_REDACTED_FIELDS: &[&str] = &[
+    "access_token",
+    "refresh_token",
+    "id_token",
+    "client_secret",
+    "AccessToken",
+    "RefreshToken",
+    "ClientSecret",
+];
+
+// Headers about the original body encoding and length, which redaction changes
+const DROPPED_HEADERS: &[&str] = &["content-length", "content-encoding", "transfer-encoding"];
+
+/// Whether a cassette sends requests or answers them from its file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VcrMode {
+    /// Send requests and keep the interactions.
+    Record,
+    /// Answer requests from recorded interactions.
+    Replay,
+}
+
+/// A recorded request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// HTTP method.
+    pub method: String,
+    /// Full URL, including the query.
+    pub url: String,
+    /// Redacted request body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// A recorded response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Response headers.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Redacted body, or base64 if [`body_base64`](Self::body_base64) is set.
+    #[serde(default)]
+    pub body: String,
+    /// The body is binary, such as a PDF, and stored as base64.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub body_base64: bool,
+}
+
+/// One request and the response it got.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// The request.
+    pub request: RecordedRequest,
+    /// The response.
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug)]
+struct State {
+    interactions: Vec<Interaction>,
+    replayed: Vec<bool>,
+    redactor: Redactor,
+}
+
+/// Recorded HTTP interactions, kept in a JSON file.
+///
+/// Clones share the same interactions, so a test can keep one to save and
+/// hand another to the client.
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: VcrMode,
+    state: Arc<Mutex<State>>,
+}
+
+impl Cassette {
+    fn new(path: PathBuf, mode: VcrMode, interactions: Vec<Interaction>) -> Self {
+        let replayed = vec![false; interactions.len()];
+        Self {
+            path,
+            mode,
+            state: Arc::new(Mutex::new(State {
+                interactions,
+                replayed,
+                redactor: Redactor::none().fields(DEFAULT_CASSETTE_REDACTED_FIELDS.iter().copied()),
+            })),
+        }
+    }
+
+    /// Record a new cassette, to be written to `path` by [`save`](Self::save).
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self::new(path.into(), VcrMode::Record, Vec::new())
+    }
+
+    /// Replay the cassette at `path`.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            Error::InvalidConfig(format!("failed to read cassette {}: {}", path.display(), e))
+        })?;
+        let file: CassetteFile = serde_json::from_str(&content)?;
+        Ok(Self::new(path, VcrMode::Replay, file.interactions))
+    }
+
+    /// Replay the cassette at `path` if it exists, otherwise record it.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if path.exists() {
+            Self::replay(path)
+        } else {
+            Ok(Self::record(path))
+        }
+    }
+
+    /// Mask bodies with `redactor` instead of the default, which only masks
+    /// tokens and secrets.
+    pub fn redactor(self, redactor: Redactor) -> Self {
+        self.state.lock().unwrap().redactor = redactor;
+        self
+    }
+
+    /// Whether the cassette records or replays.
+    pub fn mode(&self) -> VcrMode {
+        self.mode
+    }
+
+    /// The cassette file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The interactions recorded or loaded so far.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.state.lock().unwrap().interactions.clone()
+    }
+
+    /// Write the recorded interactions to the cassette file, creating its
+    /// directory if needed. Does nothing when replaying.
+    pub fn save(&self) -> Result<()> {
+        if self.mode == VcrMode::Replay {
+            return Ok(());
+        }
+        let file = CassetteFile {
+            interactions: self.interactions(),
+        };
+        let json = serde_json::to_string_pretty(&file)?;
+        let write = || -> std::io::Result<()> {
+            if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&self.path, json + "\n")
+        };
+        write().map_err(|e| {
+            Error::InvalidRequest(format!(
+                "failed to write cassette {}: {}",
+                self.path.display(),
+                e
+            ))
+        })
+    }
+
+    /// Send `request`, or answer it from the cassette when replaying.
+    pub(crate) async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let (client, request) = request.build_split();
+        let request = request?;
+        let method = request.method().to_string();
+        let url = request.url().clone();
+
+        if self.mode == VcrMode::Replay {
+            return self.next_response(&method, &url);
+        }
+
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned());
+        let response = client.execute(request).await?;
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter(|(name, _)| !DROPPED_HEADERS.contains(&name.as_str()))
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let bytes = response.bytes().await?;
+
+        let mut state = self.state.lock().unwrap();
+        let (body_text, body_base64) = match std::str::from_utf8(&bytes) {
+            Ok(text) => (state.redactor.redact(text), false),
+            Err(_) => (
+                base64::engine::general_purpose::STANDARD.encode(&bytes),
+                true,
+            ),
+        };
+        let interaction = Interaction {
+            request: RecordedRequest {
+                method,
+                url: url.to_string(),
+                body: body.map(|body| state.redactor.redact(&body)),
+            },
+            response: RecordedResponse {
+                status,
+                headers,
+                body: body_text,
+                body_base64,
+            },
+        };
+        let response = interaction.response.to_response(&url);
+        state.interactions.push(interaction);
+        state.replayed.push(true);
+        response
+    }
+
+    /// The first recorded response to `method url` not yet replayed.
+    fn next_response(&self, method: &str, url: &Url) -> Result<Response> {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        let index = state
+            .interactions
+            .iter()
+            .zip(&state.replayed)
+            .position(|(interaction, replayed)| {
+                !replayed
+                    && interaction.request.method == method
+                    && interaction.request.url == url.as_str()
+            })
+            .ok_or_else(|| {
+                Error::InvalidRequest(format!(
+                    "cassette {} has no recorded response left for {} {}",
+                    self.path.display(),
+                    method,
+                    url
+                ))
+            })?;
+        state.replayed[index] = true;
+        state.interactions[index].response.to_response(url)
+    }
+}
+
+impl RecordedResponse {
+    fn to_response(&self, url: &Url) -> Result<Response> {
+        let body = if self.body_base64 {
+            base64::engine::general_purpose::STANDARD
+                .decode(&self.body)
+                .map_err(|e| Error::InvalidConfig(format!("invalid cassette body: {}", e)))?
+        } else {
+            self.body.clone().into_bytes()
+        };
+        let response = self
+            .headers
+            .iter()
+            .fold(
+                http::Response::builder()
+                    .status(self.status)
+                    .url(url.clone()),
+                |builder, (name, value)| builder.header(name, value),
+            )
+            .body(body)
+            .map_err(|e| Error::InvalidConfig(format!("invalid cassette response: {}", e)))?;
+        Ok(Response::from(response))
+    }
+}
@@ -0,0 +1,369 @@
+//! EU VAT number validation against the European Commission's VIES service.
+//!
+//! Intra-community B2B sales may be invoiced without VAT only when the buyer
+//! holds a valid VAT registration in another member state. [`ViesClient`]
+//! checks the number with VIES and [`ViesClient::apply_intra_eu_zero_vat`]
+//! applies an [`IntraEuSale`] to the invoice only once the check succeeds.
+//! The returned
+//! [`ViesCheck`] is the validation receipt and should be stored alongside the
+//! invoice for audit purposes.
+//!
+//! Valid numbers are cached for a day, so checking the same customer before
+//! each of their invoices costs one VIES lookup. Invalid numbers are cached
+//! for an hour, and lookups the member state could not answer not at all.
+//! Set the lifetimes with [`ViesClient::cache_ttl`] and
+//! [`ViesClient::invalid_cache_ttl`]. A cached [`ViesCheck`] keeps the date
+//! and consultation number of the lookup that was actually made.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use spiris::vat::EuSaleKind;
+//! use spiris::vies::ViesClient;
+//! use spiris::Invoice;
+//!
+//! # async fn example(mut invoice: Invoice) -> Result<(), Box<dyn std::error::Error>> {
+//! let vies = ViesClient::new().requester("SE556677889901");
+//!
+//! let receipt = vies
+//!     .apply_intra_eu_zero_vat(&mut invoice, EuSaleKind::Goods, "DE123456789")
+//!     .await?;
+//! println!("Validated by VIES, consultation number {:?}", receipt.request_identifier);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{ApiErrorResponse, Error, Result, ValidationError};
+use crate::types::{Customer, Invoice};
+use crate::vat::{normalize_vat_number, EuSaleKind, IntraEuSale};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+// `std::time::Instant` panics in the browser
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+#[cfg(feature = "tracing")]
+use tracing::{info, warn};
+
+/// Default VIES REST API base URL.
+pub const DEFAULT_VIES_URL: &str = "https://ec.europa.eu/taxation_customs/vies/rest-api/";
+
+/// How long VIES answers are cached by default.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How long a VIES answer that the number is invalid is cached by default.
+pub const DEFAULT_INVALID_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// `userError` values VIES sends when the member state's service could not
+/// answer; the lookup may succeed later.
+const UNAVAILABLE_USER_ERRORS: &[&str] = &[
+    "MS_UNAVAILABLE",
+    "SERVICE_UNAVAILABLE",
+    "TIMEOUT",
+    "MS_MAX_CONCURRENT_REQ",
+    "GLOBAL_MAX_CONCURRENT_REQ",
+];
+
+/// Result of a VIES VAT number check.
+///
+/// This doubles as the audit receipt: `request_date` and
+/// `request_identifier` (the consultation number, only issued when a
+/// requester VAT number is supplied) prove that the check was made.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ViesCheck {
+    /// Member state code of the checked number.
+    pub country_code: String,
+
+    /// The checked number without country prefix.
+    pub vat_number: String,
+
+    /// Whether the number is registered for intra-community trade.
+    pub valid: bool,
+
+    /// When VIES performed the check.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_date: Option<String>,
+
+    /// Consultation number issued by VIES.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_identifier: Option<String>,
+
+    /// Registered trader name, if disclosed by the member state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Registered trader address, if disclosed by the member state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+
+    /// VIES outcome code, `VALID` or `INVALID` for a checked number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_error: Option<String>,
+}
+
+impl ViesCheck {
+    /// The full VAT number including country prefix.
+    pub fn full_vat_number(&self) -> String {
+        format!("{}{}", self.country_code, self.vat_number)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CheckRequest<'a> {
+    country_code: &'a str,
+    vat_number: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requester_member_state_code: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requester_number: Option<&'a str>,
+}
+
+/// Client for the VIES VAT number validation service.
+///
+/// Clones share the answer cache.
+#[derive(Debug, Clone)]
+pub struct ViesClient {
+    http_client: reqwest::Client,
+    base_url: String,
+    requester: Option<String>,
+    cache_ttl: Duration,
+    invalid_cache_ttl: Duration,
+    cache: Arc<Mutex<HashMap<String, (Instant, ViesCheck)>>>,
+}
+
+impl Default for ViesClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViesClient {
+    /// Create a client for the public VIES service.
+    pub fn new() -> Self {
+        let http_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            http_client,
+            base_url: DEFAULT_VIES_URL.to_string(),
+            requester: None,
+            cache_ttl: DEFAULT_CACHE_TTL,
+            invalid_cache_ttl: DEFAULT_INVALID_CACHE_TTL,
+            cache: Arc::default(),
+        }
+    }
+
+    /// Override the VIES base URL (useful for testing).
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Set your own VAT number so VIES issues a consultation number.
+    pub fn requester(mut self, vat_number: impl Into<String>) -> Self {
+        self.requester = Some(vat_number.into());
+        self
+    }
+
+    /// How long answers are reused before VIES is asked again. Defaults to
+    /// [`DEFAULT_CACHE_TTL`]; `Duration::ZERO` turns caching off.
+    ///
+    /// Answers that the number is invalid are kept for at most
+    /// [`invalid_cache_ttl`](Self::invalid_cache_ttl).
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// How long an answer that the number is invalid is reused. Defaults to
+    /// [`DEFAULT_INVALID_CACHE_TTL`].
+    pub fn invalid_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.invalid_cache_ttl = ttl;
+        self
+    }
+
+    /// Forget all cached answers.
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// Check a VAT number against VIES.
+    ///
+    /// The number is normalized first; malformed numbers are rejected without
+    /// contacting the service. An unregistered number is not an error, check
+    /// [`ViesCheck::valid`]. When VIES cannot tell, for instance because the
+    /// member state's service is down, the check fails with
+    /// [`Error::ApiError`]: status 503 for an outage, which
+    /// [`Error::is_retryable`] accepts, and 400 otherwise, with the VIES
+    /// `userError` as the error code. Answers, valid or not, are cached;
+    /// failed lookups are not.
+    pub async fn check(&self, vat_number: &str) -> Result<ViesCheck> {
+        let normalized = normalize_vat_number(vat_number)?;
+        if let Some(check) = self.cached(&normalized) {
+            return Ok(check);
+        }
+        let check = self.lookup(&normalized).await?;
+        if !self.ttl_for(&check).is_zero() {
+            self.cache
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(normalized, (Instant::now(), check.clone()));
+        }
+        Ok(check)
+    }
+
+    /// Check the VAT number of `customer` against VIES.
+    ///
+    /// Fails with [`Error::Validation`] if the customer has no VAT number.
+    pub async fn check_customer(&self, customer: &Customer) -> Result<ViesCheck> {
+        self.check(customer_vat_number(customer)?).await
+    }
+
+    /// Validate the customer's VAT number and zero-rate an intra-community
+    /// invoice to them, as [`apply_intra_eu_zero_vat`](Self::apply_intra_eu_zero_vat)
+    /// does. Call it before creating the invoice.
+    pub async fn apply_for_customer(
+        &self,
+        invoice: &mut Invoice,
+        kind: EuSaleKind,
+        customer: &Customer,
+    ) -> Result<ViesCheck> {
+        self.apply_intra_eu_zero_vat(invoice, kind, customer_vat_number(customer)?)
+            .await
+    }
+
+    fn cached(&self, vat_number: &str) -> Option<ViesCheck> {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        match cache.get(vat_number) {
+            Some((at, check)) if at.elapsed() < self.ttl_for(check) => Some(check.clone()),
+            Some(_) => {
+                cache.remove(vat_number);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn ttl_for(&self, check: &ViesCheck) -> Duration {
+        if check.valid {
+            self.cache_ttl
+        } else {
+            self.cache_ttl.min(self.invalid_cache_ttl)
+        }
+    }
+
+    async fn lookup(&self, normalized: &str) -> Result<ViesCheck> {
+        let (country_code, number) = normalized.split_at(2);
+
+        let requester = self
+            .requester
+            .as_deref()
+            .map(normalize_vat_number)
+            .transpose()?;
+        let (requester_member_state_code, requester_number) = match &requester {
+            Some(r) => (Some(&r[..2]), Some(&r[2..])),
+            None => (None, None),
+        };
+
+        let request = CheckRequest {
+            country_code,
+            vat_number: number,
+            requester_member_state_code,
+            requester_number,
+        };
+
+        let url = format!("{}/check-vat-number", self.base_url.trim_end_matches('/'));
+        let response = self.http_client.post(&url).json(&request).send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+
+        if !status.is_success() {
+            return Err(Error::from_api_response(status.as_u16(), body));
+        }
+
+        let check: ViesCheck = serde_json::from_str(&body)?;
+        match check.user_error.as_deref() {
+            None | Some("VALID") | Some("INVALID") => Ok(check),
+            Some(user_error) => {
+                #[cfg(feature = "tracing")]
+                warn!(vat_number = %normalized, %user_error, "VIES could not check VAT number");
+
+                let status_code = if UNAVAILABLE_USER_ERRORS.contains(&user_error) {
+                    503
+                } else {
+                    400
+                };
+                let response = ApiErrorResponse {
+                    error_code: Some(user_error.to_string()),
+                    message: format!("VIES could not check {}: {}", normalized, user_error),
+                    ..Default::default()
+                };
+                Err(Error::ApiError {
+                    status_code,
+                    response: Box::new(response),
+                    raw_body: body,
+                })
+            }
+        }
+    }
+
+    /// Validate the buyer's VAT number and zero-rate an intra-community invoice.
+    ///
+    /// The sale is first checked as [`IntraEuSale::apply`] checks it, so
+    /// Swedish buyers, services to Northern Ireland and invoices using
+    /// reverse charge on construction services are refused without asking
+    /// VIES. The invoice is only modified when VIES confirms the number, and
+    /// then exactly as [`IntraEuSale::apply`] does.
+    pub async fn apply_intra_eu_zero_vat(
+        &self,
+        invoice: &mut Invoice,
+        kind: EuSaleKind,
+        buyer_vat_number: &str,
+    ) -> Result<ViesCheck> {
+        let sale = IntraEuSale::new(kind, buyer_vat_number);
+        let normalized = sale.check(invoice)?;
+
+        let receipt = self.check(&normalized).await?;
+        if !receipt.valid {
+            #[cfg(feature = "tracing")]
+            warn!(vat_number = %normalized, "VIES rejected buyer VAT number");
+
+            return Err(Error::InvalidRequest(format!(
+                "VAT number {} is not registered in VIES",
+                normalized
+            )));
+        }
+
+        #[cfg(feature = "tracing")]
+        info!(
+            vat_number = %normalized,
+            request_date = ?receipt.request_date,
+            request_identifier = ?receipt.request_identifier,
+            "VIES validated buyer VAT number"
+        );
+
+        sale.apply(invoice)?;
+        Ok(receipt)
+    }
+}
+
+fn customer_vat_number(customer: &Customer) -> Result<&str> {
+    customer
+        .vat_number
+        .as_deref()
+        .filter(|v| !v.trim().is_empty())
+        .ok_or_else(|| {
+            Error::Validation(vec![ValidationError {
+                field: "VatNumber".to_string(),
+                message: "is required to check the customer in VIES".to_string(),
+            }])
+        })
+}
@@ -11,26 +11,34 @@
 //! - HMAC signature verification
 //! - Event type handling
 //! - Typed event payloads
+//! - A ready-made axum router ([`axum_router`], `webhooks-axum` feature)
 //!
 //! # Example
 //!
 //! ```
-//! use spiris::webhooks::{WebhookHandler, WebhookEvent, WebhookConfig};
+//! use spiris::webhooks::{self, Action, Event};
+//! # fn main() -> spiris::Result<()> {
+//! # let helper = webhooks::WebhookTestHelper::new("your_webhook_secret");
+//! # let body = br#"{"id": "evt_1", "eventType": "invoice.paid", "timestamp": 1700000000, "resourceId": "inv_1"}"#;
+//! # let mut headers = http::HeaderMap::new();
+//! # headers.insert("X-Webhook-Signature", helper.sign(body).parse().unwrap());
 //!
-//! // Create a webhook handler with a signing secret
-//! let config = WebhookConfig::new("your_webhook_secret");
-//! let handler = WebhookHandler::new(config);
-//!
-//! // In your webhook endpoint handler:
-//! // let event = handler.verify_and_parse(payload, signature)?;
-//! // match event.event_type.as_str() {
-//! //     "invoice.created" => { /* handle invoice created */ }
-//! //     "customer.updated" => { /* handle customer updated */ }
-//! //     _ => { /* unknown event */ }
-//! // }
+//! // In your webhook endpoint, with the request's headers and raw body:
+//! webhooks::verify_signature("your_webhook_secret", &headers, body)?;
+//! match serde_json::from_slice::<Event>(body)? {
+//!     Event::Invoice(event) if event.action == Action::Paid => {
+//!         println!("invoice {:?} paid at {}", event.id, event.occurred_at);
+//!     }
+//!     Event::Customer(event) => println!("customer {:?} {}", event.id, event.action),
+//!     other => println!("ignoring {}", other.event_type()),
+//! }
+//! # Ok(())
+//! # }
 //! ```
 
 use crate::error::{Error, Result};
+use crate::types::{ArticleId, CustomerId, InvoiceId};
+use chrono::{DateTime, Utc};
 use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
@@ -74,7 +82,9 @@ impl WebhookConfig {
 }
 
 /// A webhook event received from the API.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// [`Event`] gives the same payload typed by entity and action.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WebhookEvent {
     /// Unique identifier for this event.
@@ -161,8 +171,11 @@ impl WebhookHandler {
 
         mac.update(payload);
 
-        // Decode the provided signature (expected to be hex-encoded)
-        let provided_sig = hex::decode(signature.trim())
+        // Decode the provided signature (expected to be hex-encoded, with
+        // or without a `sha256=` prefix)
+        let signature = signature.trim();
+        let signature = signature.strip_prefix("sha256=").unwrap_or(signature);
+        let provided_sig = hex::decode(signature)
             .map_err(|_| Error::AuthError("Invalid signature format".to_string()))?;
 
         mac.verify_slice(&provided_sig)
@@ -171,6 +184,26 @@ impl WebhookHandler {
         Ok(())
     }
 
+    /// Verify the signature sent in the
+    /// [`signature_header`](WebhookConfig::signature_header) of a request.
+    pub fn verify_headers(&self, headers: &http::HeaderMap, payload: &[u8]) -> Result<()> {
+        let signature = headers
+            .get(self.config.signature_header.as_str())
+            .ok_or_else(|| {
+                Error::AuthError(format!("Missing {} header", self.config.signature_header))
+            })?
+            .to_str()
+            .map_err(|_| Error::AuthError("Invalid signature format".to_string()))?;
+        self.verify_signature(payload, signature)
+    }
+
+    /// Parse the webhook payload into a typed [`Event`] without signature
+    /// verification.
+    pub fn parse_event(&self, payload: &[u8]) -> Result<Event> {
+        serde_json::from_slice(payload)
+            .map_err(|e| Error::InvalidRequest(format!("Failed to parse webhook payload: {}", e)))
+    }
+
     /// Parse the webhook payload without signature verification.
     ///
     /// Use this only if you've already verified the signature through other means.
@@ -185,6 +218,159 @@ impl WebhookHandler {
     }
 }
 
+/// Verify the signature of a webhook request signed with `secret`.
+///
+/// Reads the hex HMAC-SHA256 of `body` from the `X-Webhook-Signature`
+/// header; use [`WebhookHandler::verify_headers`] for another header.
+pub fn verify_signature(secret: &str, headers: &http::HeaderMap, body: &[u8]) -> Result<()> {
+    WebhookHandler::new(WebhookConfig::new(secret)).verify_headers(headers, body)
+}
+
+/// What happened to the entity an [`Event`] is about.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Action {
+    /// The entity was created.
+    Created,
+    /// The entity was changed.
+    Updated,
+    /// The entity was deleted.
+    Deleted,
+    /// An invoice was sent to the customer.
+    Sent,
+    /// An invoice was paid in full.
+    Paid,
+    /// A payment was received.
+    Received,
+    /// An action not known to this version of the crate.
+    Unknown(String),
+}
+
+impl Action {
+    fn parse(action: &str) -> Self {
+        match action {
+            "created" => Action::Created,
+            "updated" => Action::Updated,
+            "deleted" => Action::Deleted,
+            "sent" => Action::Sent,
+            "paid" => Action::Paid,
+            "received" => Action::Received,
+            other => Action::Unknown(other.to_string()),
+        }
+    }
+
+    /// The action as it appears in the event type, e.g. `"created"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Action::Created => "created",
+            Action::Updated => "updated",
+            Action::Deleted => "deleted",
+            Action::Sent => "sent",
+            Action::Paid => "paid",
+            Action::Received => "received",
+            Action::Unknown(action) => action,
+        }
+    }
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An event about a single entity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityEvent<Id> {
+    /// Unique identifier of the event, for deduplicating redeliveries.
+    pub event_id: String,
+    /// What happened to the entity.
+    pub action: Action,
+    /// The entity's ID, if the payload has one.
+    pub id: Option<Id>,
+    /// When the event occurred.
+    pub occurred_at: DateTime<Utc>,
+    /// The raw payload data.
+    pub data: serde_json::Value,
+}
+
+/// A webhook callback payload, typed by the entity it is about.
+///
+/// Deserializes from the same JSON as [`WebhookEvent`]; events for an
+/// entity type this crate doesn't know end up in `Unknown`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "WebhookEvent")]
+#[non_exhaustive]
+pub enum Event {
+    /// A customer invoice event.
+    Invoice(EntityEvent<InvoiceId>),
+    /// A customer event.
+    Customer(EntityEvent<CustomerId>),
+    /// An article event.
+    Article(EntityEvent<ArticleId>),
+    /// A supplier event.
+    Supplier(EntityEvent<String>),
+    /// A payment event.
+    Payment(EntityEvent<String>),
+    /// An event for an entity type not known to this version of the crate.
+    Unknown(WebhookEvent),
+}
+
+impl Event {
+    /// The event type, e.g. `"invoice.created"`.
+    pub fn event_type(&self) -> String {
+        match self {
+            Event::Invoice(event) => format!("invoice.{}", event.action),
+            Event::Customer(event) => format!("customer.{}", event.action),
+            Event::Article(event) => format!("article.{}", event.action),
+            Event::Supplier(event) => format!("supplier.{}", event.action),
+            Event::Payment(event) => format!("payment.{}", event.action),
+            Event::Unknown(event) => event.event_type.clone(),
+        }
+    }
+
+    /// Unique identifier of the event.
+    pub fn event_id(&self) -> &str {
+        match self {
+            Event::Invoice(event) => &event.event_id,
+            Event::Customer(event) => &event.event_id,
+            Event::Article(event) => &event.event_id,
+            Event::Supplier(event) | Event::Payment(event) => &event.event_id,
+            Event::Unknown(event) => &event.id,
+        }
+    }
+}
+
+impl TryFrom<WebhookEvent> for Event {
+    type Error = String;
+
+    fn try_from(event: WebhookEvent) -> std::result::Result<Self, String> {
+        fn typed<Id>(
+            event: WebhookEvent,
+            id: impl Fn(String) -> Id,
+        ) -> std::result::Result<EntityEvent<Id>, String> {
+            let occurred_at = DateTime::from_timestamp(event.timestamp, 0)
+                .ok_or_else(|| format!("invalid webhook timestamp {}", event.timestamp))?;
+            Ok(EntityEvent {
+                action: Action::parse(event.action().unwrap_or_default()),
+                event_id: event.id,
+                id: event.resource_id.map(id),
+                occurred_at,
+                data: event.data,
+            })
+        }
+
+        Ok(match event.category() {
+            Some("invoice") => Event::Invoice(typed(event, InvoiceId::new)?),
+            Some("customer") => Event::Customer(typed(event, CustomerId::new)?),
+            Some("article") => Event::Article(typed(event, ArticleId::new)?),
+            Some("supplier") => Event::Supplier(typed(event, |id| id)?),
+            Some("payment") => Event::Payment(typed(event, |id| id)?),
+            _ => Event::Unknown(event),
+        })
+    }
+}
+
 /// Common webhook event types.
 pub mod event_types {
     // Invoice events
@@ -325,6 +511,107 @@ impl WebhookTestHelper {
     }
 }
 
+#[cfg(feature = "webhooks-axum")]
+pub use receiver::{axum_router, VerifiedEvent};
+
+/// Receiving webhooks with axum.
+#[cfg(feature = "webhooks-axum")]
+mod receiver {
+    use super::{Event, WebhookHandler};
+    use axum::body::Bytes;
+    use axum::extract::{FromRef, FromRequest, Request};
+    use axum::http::StatusCode;
+    use axum::response::{IntoResponse, Response};
+    use axum::routing::post;
+    use axum::Router;
+    use std::future::Future;
+
+    /// Extractor for a webhook delivery whose signature has been checked.
+    ///
+    /// Reads the [`WebhookHandler`] from the router state. A missing or
+    /// wrong signature is rejected with 401 Unauthorized, and a payload that
+    /// isn't an event with 400 Bad Request.
+    ///
+    /// ```no_run
+    /// use axum::routing::post;
+    /// use axum::Router;
+    /// use spiris::webhooks::{VerifiedEvent, WebhookConfig, WebhookHandler};
+    ///
+    /// async fn receive(VerifiedEvent(event): VerifiedEvent) {
+    ///     println!("{} {}", event.event_id(), event.event_type());
+    /// }
+    ///
+    /// let handler = WebhookHandler::new(WebhookConfig::new("secret"));
+    /// let app: Router = Router::new()
+    ///     .route("/webhooks", post(receive))
+    ///     .with_state(handler);
+    /// ```
+    #[derive(Debug, Clone)]
+    pub struct VerifiedEvent(pub Event);
+
+    impl<S> FromRequest<S> for VerifiedEvent
+    where
+        WebhookHandler: FromRef<S>,
+        S: Send + Sync,
+    {
+        type Rejection = Response;
+
+        async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+            let handler = WebhookHandler::from_ref(state);
+            let headers = request.headers().clone();
+            let body = Bytes::from_request(request, state)
+                .await
+                .map_err(IntoResponse::into_response)?;
+
+            handler
+                .verify_headers(&headers, &body)
+                .map_err(|e| (StatusCode::UNAUTHORIZED, e.to_string()).into_response())?;
+            let event = handler
+                .parse_event(&body)
+                .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()).into_response())?;
+            Ok(VerifiedEvent(event))
+        }
+    }
+
+    /// A router receiving webhook deliveries with `POST /`.
+    ///
+    /// Each delivery is verified with `handler`, parsed into an [`Event`]
+    /// and passed to `on_event`; once that finishes the delivery is
+    /// acknowledged with 200 OK. Nest the router wherever the webhook URL
+    /// points:
+    ///
+    /// ```no_run
+    /// use spiris::webhooks::{self, Event, WebhookConfig, WebhookHandler};
+    ///
+    /// # async fn example() -> std::io::Result<()> {
+    /// let handler = WebhookHandler::new(WebhookConfig::new("secret"));
+    /// let app = axum::Router::new().nest(
+    ///     "/webhooks",
+    ///     webhooks::axum_router(handler, |event: Event| async move {
+    ///         println!("{}", event.event_type());
+    ///     }),
+    /// );
+    /// let listener = tokio::net::TcpListener::bind("0.0.0.0:8090").await?;
+    /// axum::serve(listener, app).await
+    /// # }
+    /// ```
+    pub fn axum_router<F, Fut>(handler: WebhookHandler, on_event: F) -> Router
+    where
+        F: Fn(Event) -> Fut + Clone + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Router::new()
+            .route(
+                "/",
+                post(move |VerifiedEvent(event): VerifiedEvent| async move {
+                    on_event(event).await;
+                    StatusCode::OK
+                }),
+            )
+            .with_state(handler)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -497,4 +784,69 @@ mod tests {
         assert_eq!(event_types::CUSTOMER_UPDATED, "customer.updated");
         assert_eq!(event_types::PAYMENT_RECEIVED, "payment.received");
     }
+
+    #[test]
+    fn test_parse_typed_event() {
+        let payload = br#"{
+            "id": "evt_123",
+            "eventType": "invoice.paid",
+            "timestamp": 1700000000,
+            "resourceType": "invoice",
+            "resourceId": "inv_456",
+            "data": {"amount": 1000}
+        }"#;
+
+        let event = test_handler().parse_event(payload).unwrap();
+        let Event::Invoice(invoice) = &event else {
+            panic!("expected an invoice event, got {:?}", event);
+        };
+        assert_eq!(invoice.action, Action::Paid);
+        assert_eq!(invoice.id, Some(InvoiceId::new("inv_456")));
+        assert_eq!(invoice.occurred_at.timestamp(), 1700000000);
+        assert_eq!(invoice.data["amount"], 1000);
+        assert_eq!(event.event_id(), "evt_123");
+        assert_eq!(event.event_type(), "invoice.paid");
+    }
+
+    #[test]
+    fn test_unknown_entity_and_action() {
+        let event: Event = serde_json::from_value(serde_json::json!({
+            "id": "evt_1",
+            "eventType": "customer.merged",
+            "timestamp": 0
+        }))
+        .unwrap();
+        assert!(matches!(
+            event,
+            Event::Customer(EntityEvent { action: Action::Unknown(ref action), id: None, .. })
+                if action == "merged"
+        ));
+
+        let event: Event = serde_json::from_value(serde_json::json!({
+            "id": "evt_2",
+            "eventType": "voucher.created",
+            "timestamp": 0
+        }))
+        .unwrap();
+        assert!(matches!(event, Event::Unknown(ref raw) if raw.event_type == "voucher.created"));
+        assert_eq!(event.event_type(), "voucher.created");
+    }
+
+    #[test]
+    fn test_verify_signature_from_headers() {
+        let helper = WebhookTestHelper::new("test_secret_key");
+        let payload = br#"{"id": "evt_1", "eventType": "article.updated", "timestamp": 0}"#;
+        let mut headers = http::HeaderMap::new();
+
+        let missing = verify_signature("test_secret_key", &headers, payload).unwrap_err();
+        assert!(missing.to_string().contains("X-Webhook-Signature"));
+
+        headers.insert(
+            "x-webhook-signature",
+            format!("sha256={}", helper.sign(payload)).parse().unwrap(),
+        );
+        verify_signature("test_secret_key", &headers, payload).unwrap();
+        assert!(verify_signature("other_secret", &headers, payload).is_err());
+        assert!(verify_signature("test_secret_key", &headers, b"tampered").is_err());
+    }
 }
@@ -1,11 +1,9 @@
-// Skip these tests when decimal feature is enabled (uses f64 literals)
-#![cfg(not(feature = "decimal"))]
 //! Integration tests for the Accounts endpoint.
 
 mod mock_server;
 
 use mock_server::MockApi;
-use spiris::Account;
+use spiris::{money, Account};
 
 #[tokio::test]
 async fn test_list_accounts() {
@@ -166,7 +164,7 @@ async fn test_get_account_balances() {
     mock.assert();
     assert_eq!(result.data.len(), 2);
     assert_eq!(result.data[0].account_number, Some("1910".to_string()));
-    assert_eq!(result.data[0].balance, Some(50000.0));
+    assert_eq!(result.data[0].balance, Some(money!(50000.0)));
 }
 
 #[tokio::test]
@@ -0,0 +1,138 @@
+//! Integration tests for the Approvals endpoint.
+
+mod mock_server;
+
+use mock_server::MockApi;
+use spiris::endpoints::approvals::ApprovalAction;
+
+const SUPPLIER_INVOICES: &str = r#"{
+    "Data": [
+        {"Id": "si-1", "SupplierId": "sup-1", "DueDate": "2025-04-01T00:00:00Z", "ApprovalStatus": 1, "ApproverUserId": "user-1", "Attachments": ["att-1"]},
+        {"Id": "si-2", "SupplierId": "sup-2", "DueDate": "2025-03-15T00:00:00Z", "ApprovalStatus": 1, "ApproverUserId": "user-2"},
+        {"Id": "si-3", "SupplierId": "sup-1", "DueDate": "2025-03-01T00:00:00Z", "ApprovalStatus": 2, "ApproverUserId": "user-1"},
+        {"Id": "si-4", "SupplierId": "sup-3", "DueDate": "2025-03-10T00:00:00Z"}
+    ],
+    "Meta": {
+        "CurrentPage": 0, "PageSize": 500, "TotalPages": 1,
+        "TotalCount": 4, "HasNextPage": false, "HasPreviousPage": false
+    }
+}"#;
+
+#[tokio::test]
+async fn test_pending_supplier_invoices() {
+    let mut api = MockApi::new().await;
+    let mock = api.mock_get_any_query("/supplierinvoices", SUPPLIER_INVOICES);
+
+    let pending = api
+        .client
+        .approvals()
+        .pending_supplier_invoices(None)
+        .await
+        .unwrap();
+
+    mock.assert();
+    let ids: Vec<_> = pending.iter().map(|i| i.id.as_deref().unwrap()).collect();
+    assert_eq!(ids, ["si-2", "si-1"]);
+    assert_eq!(pending[1].attachments, ["att-1"]);
+}
+
+#[tokio::test]
+async fn test_pending_supplier_invoices_for_user() {
+    let mut api = MockApi::new().await;
+    api.mock_get_any_query("/supplierinvoices", SUPPLIER_INVOICES);
+
+    let pending = api
+        .client
+        .approvals()
+        .pending_supplier_invoices(Some("user-1"))
+        .await
+        .unwrap();
+
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id.as_deref(), Some("si-1"));
+}
+
+#[tokio::test]
+async fn test_reject_supplier_invoice_with_comment() {
+    let mut api = MockApi::new().await;
+
+    let mock = api
+        .server
+        .mock("PUT", "/approval/supplierinvoice/si-1")
+        .match_body(mockito::Matcher::Json(serde_json::json!({
+            "IsApproved": false,
+            "Comment": "Wrong cost center"
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body("null")
+        .create();
+
+    api.client
+        .approvals()
+        .approve_supplier_invoice("si-1", &ApprovalAction::reject("Wrong cost center"))
+        .await
+        .unwrap();
+
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_reject_without_comment_is_not_sent() {
+    let api = MockApi::new().await;
+
+    let result = api
+        .client
+        .approvals()
+        .approve_supplier_invoice("si-1", &ApprovalAction::reject(""))
+        .await;
+
+    assert!(matches!(result, Err(spiris::Error::InvalidRequest(_))));
+}
+
+#[tokio::test]
+async fn test_request_supplier_invoice_approval() {
+    let mut api = MockApi::new().await;
+    api.mock_get(
+        "/supplierinvoices/si-4",
+        r#"{"Id": "si-4", "SupplierId": "sup-3", "ApprovalStatus": 3, "ApproverUserId": "user-1"}"#,
+    );
+    let update = api
+        .server
+        .mock("PUT", "/supplierinvoices/si-4")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "ApprovalStatus": 1,
+            "ApproverUserId": "user-2"
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "si-4", "ApprovalStatus": 1, "ApproverUserId": "user-2"}"#)
+        .create();
+
+    let invoice = api
+        .client
+        .approvals()
+        .request_supplier_invoice_approval("si-4", "user-2")
+        .await
+        .unwrap();
+
+    update.assert();
+    assert_eq!(invoice.approver_user_id.as_deref(), Some("user-2"));
+}
+
+#[tokio::test]
+async fn test_request_approval_of_approved_invoice() {
+    let mut api = MockApi::new().await;
+    api.mock_get(
+        "/supplierinvoices/si-3",
+        r#"{"Id": "si-3", "ApprovalStatus": 2, "ApproverUserId": "user-1"}"#,
+    );
+
+    let result = api
+        .client
+        .approvals()
+        .request_supplier_invoice_approval("si-3", "user-2")
+        .await;
+
+    assert!(matches!(result, Err(spiris::Error::InvalidRequest(_))));
+}
@@ -1,11 +1,9 @@
-// Skip these tests when decimal feature is enabled (uses f64 literals)
-#![cfg(not(feature = "decimal"))]
 //! Integration tests for the Articles endpoint.
 
 mod mock_server;
 
 use mock_server::MockApi;
-use spiris::{Article, PaginationParams, QueryParams};
+use spiris::{money, Article, PaginationParams, QueryParams};
 
 #[tokio::test]
 async fn test_list_articles() {
@@ -43,9 +41,9 @@ async fn test_list_articles() {
 
     mock.assert();
     assert_eq!(result.data.len(), 2);
-    assert_eq!(result.data[0].id, Some("art-001".to_string()));
+    assert_eq!(result.data[0].id, Some("art-001".into()));
     assert_eq!(result.data[0].name, Some("Widget A".to_string()));
-    assert_eq!(result.data[0].sales_price, Some(199.99));
+    assert_eq!(result.data[0].sales_price, Some(money!(199.99)));
 }
 
 #[tokio::test]
@@ -65,13 +63,13 @@ async fn test_get_article() {
 
     let mock = api.mock_get("/articles/art-123", response_body);
 
-    let result = api.client.articles().get("art-123").await.unwrap();
+    let result = api.client.articles().get(&"art-123".into()).await.unwrap();
 
     mock.assert();
-    assert_eq!(result.id, Some("art-123".to_string()));
+    assert_eq!(result.id, Some("art-123".into()));
     assert_eq!(result.name, Some("Consulting Hour".to_string()));
     assert_eq!(result.unit, Some("hour".to_string()));
-    assert_eq!(result.sales_price, Some(1500.0));
+    assert_eq!(result.sales_price, Some(money!(1500.0)));
 }
 
 #[tokio::test]
@@ -90,7 +88,7 @@ async fn test_create_article() {
 
     let new_article = Article {
         name: Some("New Product".to_string()),
-        sales_price: Some(500.0),
+        sales_price: Some(money!(500.0)),
         is_active: Some(true),
         ..Default::default()
     };
@@ -98,7 +96,7 @@ async fn test_create_article() {
     let result = api.client.articles().create(&new_article).await.unwrap();
 
     mock.assert();
-    assert_eq!(result.id, Some("art-new-001".to_string()));
+    assert_eq!(result.id, Some("art-new-001".into()));
     assert_eq!(result.article_number, Some("NEW-001".to_string()));
 }
 
@@ -117,22 +115,22 @@ async fn test_update_article() {
     let mock = api.mock_put("/articles/art-123", response_body);
 
     let updated_article = Article {
-        id: Some("art-123".to_string()),
+        id: Some("art-123".into()),
         name: Some("Updated Product Name".to_string()),
-        sales_price: Some(599.99),
+        sales_price: Some(money!(599.99)),
         ..Default::default()
     };
 
     let result = api
         .client
         .articles()
-        .update("art-123", &updated_article)
+        .update(&"art-123".into(), &updated_article)
         .await
         .unwrap();
 
     mock.assert();
     assert_eq!(result.name, Some("Updated Product Name".to_string()));
-    assert_eq!(result.sales_price, Some(599.99));
+    assert_eq!(result.sales_price, Some(money!(599.99)));
 }
 
 #[tokio::test]
@@ -141,7 +139,7 @@ async fn test_delete_article() {
 
     let mock = api.mock_delete("/articles/art-123");
 
-    let result = api.client.articles().delete("art-123").await;
+    let result = api.client.articles().delete(&"art-123".into()).await;
 
     mock.assert();
     assert!(result.is_ok());
@@ -0,0 +1,111 @@
+//! Integration tests for the attachments endpoint.
+
+mod mock_server;
+
+use mock_server::MockApi;
+use mockito::Matcher;
+use spiris::AttachmentUpload;
+
+#[tokio::test]
+async fn test_upload_attachment() {
+    let mut api = MockApi::new().await;
+    let mock = api
+        .server
+        .mock("POST", "/attachments")
+        .match_body(Matcher::Json(serde_json::json!({
+            "FileName": "kvitto.pdf",
+            "ContentType": "application/pdf",
+            "Data": "JVBERi0xLjQ=",
+            "Comment": "Lunch"
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "att-1", "FileName": "kvitto.pdf", "ContentType": "application/pdf"}"#)
+        .create();
+
+    let upload =
+        AttachmentUpload::new("kvitto.pdf", "application/pdf", b"%PDF-1.4").comment("Lunch");
+    let attachment = api.client.attachments().upload(&upload).await.unwrap();
+
+    mock.assert();
+    assert_eq!(attachment.id.as_deref(), Some("att-1"));
+}
+
+#[tokio::test]
+async fn test_upload_file() {
+    let mut api = MockApi::new().await;
+    let mock = api
+        .server
+        .mock("POST", "/attachments")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "FileName": "receipt.png",
+            "ContentType": "image/png",
+            "Data": "iVBORw=="
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "att-2"}"#)
+        .create();
+
+    let path = std::env::temp_dir().join(format!("spiris-attachments-{}", std::process::id()));
+    std::fs::create_dir_all(&path).unwrap();
+    let file = path.join("receipt.png");
+    std::fs::write(&file, [0x89, b'P', b'N', b'G']).unwrap();
+
+    let attachment = api.client.attachments().upload_file(&file).await.unwrap();
+    std::fs::remove_dir_all(&path).unwrap();
+
+    mock.assert();
+    assert_eq!(attachment.id.as_deref(), Some("att-2"));
+
+    let err = api
+        .client
+        .attachments()
+        .upload_file("notes.txt")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("unsupported attachment type"));
+}
+
+#[tokio::test]
+async fn test_download_attachment() {
+    let mut api = MockApi::new().await;
+    api.mock_get_bytes("/attachments/att-1/content", b"%PDF-1.4");
+
+    let content = api.client.attachments().get_content("att-1").await.unwrap();
+    assert_eq!(content, b"%PDF-1.4");
+}
+
+#[tokio::test]
+async fn test_link_attachment() {
+    let mut api = MockApi::new().await;
+    let supplier_invoice = api
+        .server
+        .mock("POST", "/attachmentlinks")
+        .match_body(Matcher::Json(serde_json::json!({
+            "AttachmentId": "att-1", "DocumentId": "si-1", "DocumentType": 1
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"AttachmentId": "att-1", "DocumentId": "si-1", "DocumentType": 1}"#)
+        .create();
+    let voucher = api
+        .server
+        .mock("POST", "/attachmentlinks")
+        .match_body(Matcher::PartialJson(serde_json::json!({"DocumentType": 3})))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"AttachmentId": "att-1", "DocumentId": "v-1", "DocumentType": 3}"#)
+        .create();
+
+    let attachments = api.client.attachments();
+    attachments
+        .link_to_supplier_invoice("att-1", "si-1")
+        .await
+        .unwrap();
+    let link = attachments.link_to_voucher("att-1", "v-1").await.unwrap();
+
+    supplier_invoice.assert();
+    voucher.assert();
+    assert_eq!(link.document_id.as_deref(), Some("v-1"));
+}
@@ -5,7 +5,7 @@
 //! - Includes challenge in authorization URL
 //! - Sends verifier during token exchange
 
-use spiris::auth::{OAuth2Config, OAuth2Handler};
+use spiris::auth::{Environment, OAuth2Config, OAuth2Handler};
 
 // =============================================================================
 // PKCE Challenge Generation Tests
@@ -146,6 +146,23 @@ fn test_authorize_url_contains_scopes() {
     );
 }
 
+#[test]
+fn test_authorize_url_uses_configured_scopes() {
+    let config = OAuth2Config::new(
+        "test_client_id".to_string(),
+        "test_client_secret".to_string(),
+        "http://localhost:8080/callback".to_string(),
+    )
+    .scopes(["ea:api", "ea:purchase"]);
+
+    let handler = OAuth2Handler::new(config).unwrap();
+    let (auth_url, _, _) = handler.authorize_url();
+
+    assert!(auth_url.contains("scope=ea%3Aapi+ea%3Apurchase"));
+    assert!(!auth_url.contains("ea%3Asales"));
+    assert!(!auth_url.contains("offline_access"));
+}
+
 // =============================================================================
 // CSRF Token Tests
 // =============================================================================
@@ -236,6 +253,37 @@ fn test_oauth2_config_default_urls() {
     );
 }
 
+#[test]
+fn test_oauth2_config_sandbox_environment() {
+    let config = OAuth2Config::new(
+        "test_client_id".to_string(),
+        "test_client_secret".to_string(),
+        "http://localhost:8080/callback".to_string(),
+    )
+    .environment(Environment::Sandbox);
+
+    assert_eq!(
+        config.auth_url,
+        "https://identity-sandbox.test.vismaonline.com/connect/authorize"
+    );
+    assert_eq!(
+        config.token_url,
+        "https://identity-sandbox.test.vismaonline.com/connect/token"
+    );
+    assert_eq!(config.scopes, ["ea:api", "ea:sales", "offline_access"]);
+
+    let handler = OAuth2Handler::new(config).unwrap();
+    let (auth_url, _, _) = handler.authorize_url();
+    assert!(
+        auth_url.starts_with("https://identity-sandbox.test.vismaonline.com/connect/authorize?")
+    );
+
+    let production = OAuth2Config::default()
+        .environment(Environment::Sandbox)
+        .environment(Environment::Production);
+    assert_eq!(production.token_url, OAuth2Config::default().token_url);
+}
+
 #[test]
 fn test_oauth2_config_new() {
     let config = OAuth2Config::new(
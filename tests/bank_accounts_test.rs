@@ -0,0 +1,72 @@
+//! Integration tests for the bank accounts endpoint.
+
+mod mock_server;
+
+use mock_server::MockApi;
+
+const BANK_ACCOUNTS: &str = r#"{
+    "Data": [
+        {"Id": "ba-1", "Name": "Företagskonto", "AccountNumber": "1234-5678901", "BankGiroNumber": "123-4567",
+         "CurrencyCode": "SEK", "IsDefault": true, "IsActive": true},
+        {"Id": "ba-2", "Name": "EUR-konto", "Iban": "SE45 5000 0000 0583 9825 7466", "CurrencyCode": "EUR",
+         "IsDefault": false, "IsActive": true},
+        {"Id": "ba-3", "Name": "Gamla kontot", "PlusGiroNumber": "12345-6", "CurrencyCode": "SEK",
+         "IsDefault": false, "IsActive": false}
+    ],
+    "Meta": {
+        "CurrentPage": 0, "PageSize": 500, "TotalPages": 1,
+        "TotalCount": 3, "HasNextPage": false, "HasPreviousPage": false
+    }
+}"#;
+
+#[tokio::test]
+async fn test_bank_account_for_currency() {
+    let mut api = MockApi::new().await;
+    api.mock_get_any_query("/bankaccounts", BANK_ACCOUNTS);
+
+    let sek = api
+        .client
+        .bank_accounts()
+        .for_currency("SEK")
+        .await
+        .unwrap();
+    assert_eq!(sek.unwrap().id.as_deref(), Some("ba-1"));
+
+    let eur = api
+        .client
+        .bank_accounts()
+        .for_currency("EUR")
+        .await
+        .unwrap();
+    assert_eq!(eur.unwrap().id.as_deref(), Some("ba-2"));
+
+    let usd = api
+        .client
+        .bank_accounts()
+        .for_currency("USD")
+        .await
+        .unwrap();
+    assert!(usd.is_none());
+}
+
+#[tokio::test]
+async fn test_find_bank_account_by_number() {
+    let mut api = MockApi::new().await;
+    api.mock_get_any_query("/bankaccounts", BANK_ACCOUNTS);
+    let accounts = api.client.bank_accounts();
+
+    let by_bankgiro = accounts.find_by_number("1234567").await.unwrap();
+    assert_eq!(
+        by_bankgiro.unwrap().bank_giro_number.as_deref(),
+        Some("123-4567")
+    );
+
+    let by_iban = accounts
+        .find_by_number("SE4550000000058398257466")
+        .await
+        .unwrap();
+    assert_eq!(by_iban.unwrap().id.as_deref(), Some("ba-2"));
+
+    // Inactive accounts are not offered for payments
+    assert!(accounts.find_by_number("12345-6").await.unwrap().is_none());
+}
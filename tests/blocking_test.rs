@@ -0,0 +1,74 @@
+#![cfg(feature = "blocking")]
+//! Integration tests for the blocking client.
+
+use mockito::{Matcher, Server};
+use spiris::blocking::Client;
+use spiris::{AccessToken, ClientConfig, CustomerId, CustomerPatch, Error, RetryConfig};
+
+fn client(server: &Server) -> Client {
+    let config = ClientConfig::new()
+        .base_url(server.url())
+        .retry_config(RetryConfig::new().max_retries(0));
+    Client::with_config(
+        AccessToken::new("test_token".to_string(), 3600, None),
+        config,
+    )
+}
+
+#[test]
+fn test_blocking_get_and_patch_customer() {
+    let mut server = Server::new();
+    let get = server
+        .mock("GET", "/customers/cust-1")
+        .match_header("Authorization", "Bearer test_token")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "cust-1", "Name": "Acme AB"}"#)
+        .create();
+    let patch = server
+        .mock("PATCH", "/customers/cust-1")
+        .match_body(Matcher::Json(
+            serde_json::json!({"Email": "billing@acme.se"}),
+        ))
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "cust-1", "Name": "Acme AB", "Email": "billing@acme.se"}"#)
+        .create();
+
+    let client = client(&server);
+    let id = CustomerId::from("cust-1");
+    let customer = client.customers().get(&id).unwrap();
+    let updated = client
+        .customers()
+        .update_partial(&id, &CustomerPatch::new().email("billing@acme.se"))
+        .unwrap();
+
+    get.assert();
+    patch.assert();
+    assert_eq!(customer.name.as_deref(), Some("Acme AB"));
+    assert_eq!(updated.email.as_deref(), Some("billing@acme.se"));
+}
+
+#[test]
+fn test_blocking_errors_and_block_on() {
+    let mut server = Server::new();
+    server
+        .mock("GET", "/articles/missing")
+        .with_status(404)
+        .with_body("Not found")
+        .create();
+    server
+        .mock("GET", "/units")
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"Meta": {"CurrentPage": 0, "PageSize": 50, "TotalPages": 1, "TotalCount": 1, "HasNextPage": false, "HasPreviousPage": false}, "Data": [{"Id": "u-1", "Name": "Hour"}]}"#,
+        )
+        .create();
+
+    let client = client(&server);
+    let err = client.articles().get(&"missing".into()).unwrap_err();
+    assert!(matches!(err.into_root(), Error::NotFound(_)));
+
+    let units = client
+        .block_on(client.as_async().units().list(None))
+        .unwrap();
+    assert_eq!(units.data[0].name.as_deref(), Some("Hour"));
+}
@@ -11,9 +11,11 @@
 mod mock_server;
 
 use mock_server::MockApi;
-use spiris::client::{ClientConfig, DEFAULT_BASE_URL, RATE_LIMIT_PER_MINUTE};
+use spiris::client::{ClientConfig, DEFAULT_BASE_URL, RATE_LIMIT_PER_MINUTE, SANDBOX_BASE_URL};
 use spiris::retry::RetryConfig;
-use spiris::{AccessToken, Client, Error};
+use spiris::{
+    AccessToken, CancellationToken, Client, Environment, Error, Language, RequestOptions,
+};
 use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
@@ -79,6 +81,15 @@ fn test_client_config_base_url_builder() {
     assert_eq!(config.base_url, "https://custom.api.com/v1/");
 }
 
+#[test]
+fn test_client_config_environment_builder() {
+    let config = ClientConfig::new().environment(Environment::Sandbox);
+    assert_eq!(config.base_url, SANDBOX_BASE_URL);
+
+    let config = config.environment(Environment::Production);
+    assert_eq!(config.base_url, DEFAULT_BASE_URL);
+}
+
 #[test]
 fn test_client_config_timeout_builder() {
     let config = ClientConfig::new().timeout_seconds(60);
@@ -425,6 +436,77 @@ async fn test_client_makes_request_with_accept_header() {
     mock.assert();
 }
 
+#[tokio::test]
+async fn test_client_sends_accept_language() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/customers/c1")
+        .match_header("accept-language", "sv-SE")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "c1"}"#)
+        .create();
+
+    let client = Client::with_config(
+        AccessToken::new("token".to_string(), 3600, None),
+        ClientConfig::new()
+            .base_url(server.url())
+            .language(Language::Swedish),
+    );
+    client.customers().get(&"c1".into()).await.unwrap();
+
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_api_error_records_response_language() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/customers/c1")
+        .with_status(422)
+        .with_header("content-type", "application/json")
+        .with_header("content-language", "en")
+        .with_body(r#"{"Message": "The customer is inactive"}"#)
+        .create();
+    let _fallback = server
+        .mock("GET", "/customers/c2")
+        .with_status(422)
+        .with_body(r#"{"Message": "Kunden är inaktiv"}"#)
+        .create();
+
+    let client = Client::with_config(
+        AccessToken::new("token".to_string(), 3600, None),
+        ClientConfig::new()
+            .base_url(server.url())
+            .language(Language::Swedish)
+            .retry_config(RetryConfig::new().max_retries(0)),
+    );
+
+    let language = |err: Error| match err.into_root() {
+        Error::ApiError { response, .. } => response.language,
+        other => panic!("expected an API error, got {:?}", other),
+    };
+    let err = client.customers().get(&"c1".into()).await.unwrap_err();
+    assert_eq!(language(err), Some(Language::English));
+    let err = client.customers().get(&"c2".into()).await.unwrap_err();
+    assert_eq!(language(err), Some(Language::Swedish));
+}
+
+#[test]
+fn test_language_from_tag() {
+    assert_eq!(Language::from_tag("sv"), Some(Language::Swedish));
+    assert_eq!(
+        Language::from_tag("en-US,en;q=0.9"),
+        Some(Language::English)
+    );
+    assert_eq!(Language::from_tag("SV_se"), Some(Language::Swedish));
+    assert_eq!(Language::from_tag("fi-FI"), None);
+    assert_eq!(
+        Language::from_tag(Language::English.tag()),
+        Some(Language::English)
+    );
+}
+
 #[tokio::test]
 async fn test_client_request_fails_with_expired_token() {
     let server = mockito::Server::new_async().await;
@@ -434,7 +516,11 @@ async fn test_client_request_fails_with_expired_token() {
 
     tokio::time::sleep(Duration::from_millis(50)).await;
 
-    let result = client.customers().list(None).await;
+    let result = client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     assert!(matches!(result, Err(Error::TokenExpired)));
 }
@@ -462,7 +548,11 @@ async fn test_client_request_succeeds_after_token_update() {
     tokio::time::sleep(Duration::from_millis(50)).await;
 
     // Request should fail with expired token
-    let result1 = client.customers().list(None).await;
+    let result1 = client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
     assert!(matches!(result1, Err(Error::TokenExpired)));
 
     // Update to valid token
@@ -493,7 +583,7 @@ async fn test_client_get_method() {
         .with_body(&response)
         .create();
 
-    let result = api.client.customers().get("cust-1").await;
+    let result = api.client.customers().get(&"cust-1".into()).await;
     assert!(result.is_ok());
 
     mock.assert();
@@ -535,7 +625,11 @@ async fn test_client_put_method() {
         .with_body(&response)
         .create();
 
-    let result = api.client.customers().update("cust-1", &customer).await;
+    let result = api
+        .client
+        .customers()
+        .update(&"cust-1".into(), &customer)
+        .await;
     assert!(result.is_ok());
 
     mock.assert();
@@ -551,7 +645,7 @@ async fn test_client_delete_method() {
         .with_status(204)
         .create();
 
-    let result = api.client.customers().delete("cust-1").await;
+    let result = api.client.customers().delete(&"cust-1".into()).await;
     assert!(result.is_ok());
 
     mock.assert();
@@ -567,7 +661,12 @@ async fn test_client_handles_401_unauthorized() {
 
     let _mock = api.mock_error("GET", "/customers", 401, r#"{"Message": "Unauthorized"}"#);
 
-    let result = api.client.customers().list(None).await;
+    let result = api
+        .client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     assert!(matches!(result, Err(Error::AuthError(_))));
 }
@@ -578,7 +677,12 @@ async fn test_client_handles_403_forbidden() {
 
     let _mock = api.mock_error("GET", "/customers", 403, r#"{"Message": "Forbidden"}"#);
 
-    let result = api.client.customers().list(None).await;
+    let result = api
+        .client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     assert!(matches!(result, Err(Error::AuthError(_))));
 }
@@ -594,7 +698,12 @@ async fn test_client_handles_404_not_found() {
         r#"{"Message": "Not found"}"#,
     );
 
-    let result = api.client.customers().get("nonexistent").await;
+    let result = api
+        .client
+        .customers()
+        .get(&"nonexistent".into())
+        .await
+        .map_err(Error::into_root);
 
     assert!(matches!(result, Err(Error::NotFound(_))));
 }
@@ -610,9 +719,14 @@ async fn test_client_handles_429_rate_limit() {
         r#"{"Message": "Too many requests"}"#,
     );
 
-    let result = api.client.customers().list(None).await;
+    let result = api
+        .client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
-    assert!(matches!(result, Err(Error::RateLimitExceeded(_))));
+    assert!(matches!(result, Err(Error::RateLimitExceeded { .. })));
 }
 
 #[tokio::test]
@@ -622,9 +736,20 @@ async fn test_client_handles_400_bad_request() {
     let _mock = api.mock_error("POST", "/customers", 400, r#"{"Message": "Invalid data"}"#);
 
     let customer = mock_server::fixtures::customer(1);
-    let result = api.client.customers().create(&customer).await;
+    let result = api
+        .client
+        .customers()
+        .create(&customer)
+        .await
+        .map_err(Error::into_root);
 
-    assert!(matches!(result, Err(Error::InvalidRequest(_))));
+    assert!(matches!(
+        result,
+        Err(Error::ApiError {
+            status_code: 400,
+            ..
+        })
+    ));
 }
 
 #[tokio::test]
@@ -638,7 +763,12 @@ async fn test_client_handles_500_server_error() {
         r#"{"Message": "Internal server error"}"#,
     );
 
-    let result = api.client.customers().list(None).await;
+    let result = api
+        .client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     assert!(matches!(
         result,
@@ -691,7 +821,7 @@ async fn test_client_builds_correct_url_for_get_by_id() {
         .with_body(&response)
         .create();
 
-    let result = api.client.customers().get("test-id-123").await;
+    let result = api.client.customers().get(&"test-id-123".into()).await;
     assert!(result.is_ok());
 
     mock.assert();
@@ -739,8 +869,60 @@ async fn test_put_sends_content_type_json() {
         .with_body(&response)
         .create();
 
-    let result = api.client.customers().update("cust-1", &customer).await;
+    let result = api
+        .client
+        .customers()
+        .update(&"cust-1".into(), &customer)
+        .await;
     assert!(result.is_ok());
 
     mock.assert();
 }
+
+// =============================================================================
+// Per-Call Options
+// =============================================================================
+
+#[tokio::test]
+async fn test_with_options_timeout_applies_to_that_handle_only() {
+    let mut api = MockApi::new().await;
+    let customer = serde_json::to_string(&mock_server::fixtures::customer(1)).unwrap();
+    let _mock = api.mock_slow_response("/customers/cust-001", 300, &customer);
+
+    let hurried = api
+        .client
+        .with_options(RequestOptions::new().timeout(Duration::from_millis(50)));
+    let result = hurried.customers().get(&"cust-001".into()).await;
+    assert!(
+        matches!(result.map_err(Error::into_root), Err(Error::Http(ref e)) if e.is_timeout()),
+        "expected a timeout"
+    );
+
+    assert!(api.client.customers().get(&"cust-001".into()).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_with_options_cancellation_aborts_call() {
+    let mut api = MockApi::new().await;
+    let customer = serde_json::to_string(&mock_server::fixtures::customer(1)).unwrap();
+    let _mock = api.mock_slow_response("/customers/cust-001", 2000, &customer);
+
+    let cancel = CancellationToken::new();
+    let client = api
+        .client
+        .with_options(RequestOptions::new().cancellation(cancel.clone()));
+    let trigger = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        trigger.cancel();
+    });
+
+    let started = std::time::Instant::now();
+    let result = client.customers().get(&"cust-001".into()).await;
+
+    assert!(matches!(
+        result.map_err(Error::into_root),
+        Err(Error::Cancelled)
+    ));
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
@@ -45,9 +45,9 @@ async fn test_client_token_update() {
         .with_body(r#"{"Id": "cust-1", "Name": "Customer 1"}"#)
         .create();
 
-    let result1 = api.client.customers().get("cust-1").await.unwrap();
+    let result1 = api.client.customers().get(&"cust-1".into()).await.unwrap();
     mock1.assert();
-    assert_eq!(result1.id, Some("cust-1".to_string()));
+    assert_eq!(result1.id, Some("cust-1".into()));
 
     // Update the token
     let new_token = AccessToken::new("new_token".to_string(), 3600, None);
@@ -63,9 +63,9 @@ async fn test_client_token_update() {
         .with_body(r#"{"Id": "cust-2", "Name": "Customer 2"}"#)
         .create();
 
-    let result2 = api.client.customers().get("cust-2").await.unwrap();
+    let result2 = api.client.customers().get(&"cust-2".into()).await.unwrap();
     mock2.assert();
-    assert_eq!(result2.id, Some("cust-2".to_string()));
+    assert_eq!(result2.id, Some("cust-2".into()));
 }
 
 #[tokio::test]
@@ -176,10 +176,14 @@ async fn test_client_clone() {
     );
 
     // Use the cloned client
-    let result = cloned_client.customers().get("cust-1").await.unwrap();
+    let result = cloned_client
+        .customers()
+        .get(&"cust-1".into())
+        .await
+        .unwrap();
 
     mock.assert();
-    assert_eq!(result.id, Some("cust-1".to_string()));
+    assert_eq!(result.id, Some("cust-1".into()));
 }
 
 #[tokio::test]
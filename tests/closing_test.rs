@@ -0,0 +1,126 @@
+//! Integration tests for the month-end closing checklist.
+
+mod mock_server;
+
+use chrono::NaiveDate;
+use mock_server::{meta_json, MockApi};
+use spiris::closing::{Check, CheckStatus, Checklist};
+
+fn page(data: &str) -> String {
+    format!(r#"{{"Data": {}, {}}}"#, data, meta_json(0, 500, 1, 1))
+}
+
+fn period() -> (NaiveDate, NaiveDate) {
+    (
+        NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+        NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_closing_checklist_reports_problems() {
+    let mut api = MockApi::new().await;
+
+    let _customer_drafts = api.mock_get_any_query(
+        "/customerinvoicedrafts",
+        &page(r#"[{"Id": "cd-1", "InvoiceDate": "2025-01-20T00:00:00Z"}, {"Id": "cd-2", "InvoiceDate": "2025-02-03T00:00:00Z"}]"#),
+    );
+    let _supplier_drafts = api.mock_get_any_query("/supplierinvoicedrafts", &page("[]"));
+    let _invoices = api.mock_get_any_query(
+        "/customerinvoices",
+        &page(
+            r#"[
+                {"Id": "inv-1", "InvoiceNumber": "1001", "DueDate": "2025-01-10T00:00:00Z", "RemainingAmount": 500.0, "Rows": []},
+                {"Id": "inv-2", "InvoiceNumber": "1002", "DueDate": "2025-01-12T00:00:00Z", "RemainingAmount": 0.0, "Rows": []}
+            ]"#,
+        ),
+    );
+    let _vat_reports = api.mock_get_any_query(
+        "/vatreports",
+        &page(
+            r#"[
+                {"Id": "vr-1", "Name": "Moms 2024-12", "StartDate": "2024-12-01T00:00:00", "EndDate": "2024-12-31T00:00:00", "DocumentApprovalStatus": 3},
+                {"Id": "vr-2", "Name": "Moms 2025-01", "StartDate": "2025-01-01T00:00:00", "EndDate": "2025-01-31T00:00:00", "DocumentApprovalStatus": 3}
+            ]"#,
+        ),
+    );
+    let _vouchers = api.mock_get_any_query(
+        "/vouchers",
+        &page(
+            r#"[
+                {"Id": "v-1", "VoucherNumber": "A1", "VoucherDate": "2025-01-15T00:00:00Z",
+                 "Rows": [{"AccountNumber": "1930", "DebitAmount": 100.0}, {"AccountNumber": "3001", "CreditAmount": 100.0}]},
+                {"Id": "v-2", "VoucherNumber": "A2", "VoucherDate": "2025-01-16T00:00:00Z",
+                 "Rows": [{"AccountNumber": "1930", "DebitAmount": 100.0}, {"AccountNumber": "3001", "CreditAmount": 90.0}]}
+            ]"#,
+        ),
+    );
+
+    let (start, end) = period();
+    let report = Checklist::new().run(&api.client, start, end).await.unwrap();
+
+    assert!(!report.is_passed());
+
+    let drafts = report.result(Check::UnbookedDrafts).unwrap();
+    assert_eq!(drafts.status, CheckStatus::Failed);
+    assert_eq!(drafts.items, vec!["customer draft cd-1"]);
+
+    let overdue = report.result(Check::OverdueInvoices).unwrap();
+    assert_eq!(overdue.items, vec!["invoice 1001"]);
+
+    let vat = report.result(Check::VatReportStatus).unwrap();
+    assert_eq!(vat.items, vec!["VAT report Moms 2025-01"]);
+
+    let vouchers = report.result(Check::UnbalancedVouchers).unwrap();
+    assert_eq!(vouchers.items, vec!["voucher A2"]);
+}
+
+#[tokio::test]
+async fn test_closing_checklist_passes_clean_period() {
+    let mut api = MockApi::new().await;
+
+    let _customer_drafts = api.mock_get_any_query("/customerinvoicedrafts", &page("[]"));
+    let _supplier_drafts = api.mock_get_any_query("/supplierinvoicedrafts", &page("[]"));
+    let _invoices = api.mock_get_any_query("/customerinvoices", &page("[]"));
+    let _vat_reports = api.mock_get_any_query(
+        "/vatreports",
+        &page(r#"[{"Id": "vr-1", "EndDate": "2025-01-31T00:00:00", "DocumentApprovalStatus": 1}]"#),
+    );
+    let _vouchers = api.mock_get_any_query(
+        "/vouchers",
+        &page(
+            r#"[{"Id": "v-1", "VoucherDate": "2025-01-15T00:00:00Z",
+                 "Rows": [{"DebitAmount": 250.0}, {"CreditAmount": 250.0}]}]"#,
+        ),
+    );
+
+    let (start, end) = period();
+    let report = Checklist::new().run(&api.client, start, end).await.unwrap();
+
+    assert!(report.is_passed());
+    assert_eq!(report.results.len(), 4);
+}
+
+#[tokio::test]
+async fn test_closing_checklist_fails_on_withdrawn_vat_report() {
+    let mut api = MockApi::new().await;
+
+    let _vat_reports = api.mock_get_any_query(
+        "/vatreports",
+        &page(
+            r#"[{"Id": "vr-1", "EndDate": "2025-01-31T00:00:00", "DocumentApprovalStatus": 1, "IsRegretted": true}]"#,
+        ),
+    );
+
+    let (start, end) = period();
+    let report = Checklist::with_checks([Check::VatReportStatus])
+        .run(&api.client, start, end)
+        .await
+        .unwrap();
+
+    assert!(!report.is_passed());
+    assert_eq!(
+        report.result(Check::VatReportStatus).unwrap().items,
+        vec!["VAT report vr-1"]
+    );
+}
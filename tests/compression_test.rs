@@ -0,0 +1,68 @@
+//! Integration tests for compressed responses.
+
+#![cfg(feature = "compression")]
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use mockito::{Matcher, Server};
+use spiris::{AccessToken, Client, ClientConfig, RetryConfig};
+use std::io::Write;
+
+fn client(server: &Server, config: ClientConfig) -> Client {
+    Client::with_config(
+        AccessToken::new("test_token".to_string(), 3600, None),
+        config
+            .base_url(server.url())
+            .retry_config(RetryConfig::new().max_retries(0)),
+    )
+}
+
+fn gzip(body: &str) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(body.as_bytes()).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[tokio::test]
+async fn test_gzip_response_is_decompressed() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/customers/cust-1")
+        .match_header("accept-encoding", Matcher::Regex("gzip".into()))
+        .match_header("accept-encoding", Matcher::Regex("br".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("content-encoding", "gzip")
+        .with_body(gzip(r#"{"Id": "cust-1", "Name": "Acme AB"}"#))
+        .create_async()
+        .await;
+
+    let customer = client(&server, ClientConfig::new())
+        .customers()
+        .get(&"cust-1".into())
+        .await
+        .unwrap();
+
+    assert_eq!(customer.name.as_deref(), Some("Acme AB"));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_compression_can_be_turned_off() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/customers/cust-1")
+        .match_header("accept-encoding", Matcher::Missing)
+        .with_status(200)
+        .with_body(r#"{"Id": "cust-1"}"#)
+        .create_async()
+        .await;
+
+    client(&server, ClientConfig::new().compression(false))
+        .customers()
+        .get(&"cust-1".into())
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+}
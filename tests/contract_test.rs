@@ -1,5 +1,3 @@
-// Skip these tests when decimal feature is enabled (uses f64 literals)
-#![cfg(not(feature = "decimal"))]
 //! Contract tests for API response schema validation.
 //!
 //! These tests verify that:
@@ -11,7 +9,7 @@
 mod mock_server;
 
 use mock_server::MockApi;
-use spiris::{Customer, PaginationParams};
+use spiris::{money, Customer, PaginationParams};
 
 // =============================================================================
 // Customer Response Contract Tests
@@ -32,7 +30,12 @@ async fn test_customer_response_has_required_fields() {
 
     let _mock = api.mock_get("/customers/cust-123", json);
 
-    let customer = api.client.customers().get("cust-123").await.unwrap();
+    let customer = api
+        .client
+        .customers()
+        .get(&"cust-123".into())
+        .await
+        .unwrap();
 
     assert!(customer.id.is_some());
     assert!(customer.customer_number.is_some());
@@ -57,7 +60,12 @@ async fn test_customer_response_optional_fields() {
 
     let _mock = api.mock_get("/customers/cust-123", json);
 
-    let customer = api.client.customers().get("cust-123").await.unwrap();
+    let customer = api
+        .client
+        .customers()
+        .get(&"cust-123".into())
+        .await
+        .unwrap();
 
     assert_eq!(customer.email, Some("test@example.com".to_string()));
     assert_eq!(customer.phone, Some("+46701234567".to_string()));
@@ -90,7 +98,12 @@ async fn test_customer_with_address() {
 
     let _mock = api.mock_get("/customers/cust-123", json);
 
-    let customer = api.client.customers().get("cust-123").await.unwrap();
+    let customer = api
+        .client
+        .customers()
+        .get(&"cust-123".into())
+        .await
+        .unwrap();
 
     assert!(customer.invoice_address.is_some());
     let invoice_addr = customer.invoice_address.unwrap();
@@ -101,6 +114,30 @@ async fn test_customer_with_address() {
     assert!(customer.delivery_address.is_some());
 }
 
+#[tokio::test]
+async fn test_customer_delivery_references() {
+    let mut api = MockApi::new().await;
+
+    let json = r#"{
+        "Id": "cust-123",
+        "Name": "Customer With Delivery Terms",
+        "DeliveryMethodId": "dm-1",
+        "DeliveryTermId": "dt-2"
+    }"#;
+
+    let _mock = api.mock_get("/customers/cust-123", json);
+
+    let customer = api
+        .client
+        .customers()
+        .get(&"cust-123".into())
+        .await
+        .unwrap();
+
+    assert_eq!(customer.delivery_method_id, Some("dm-1".to_string()));
+    assert_eq!(customer.delivery_term_id, Some("dt-2".to_string()));
+}
+
 // =============================================================================
 // Invoice Response Contract Tests
 // =============================================================================
@@ -123,7 +160,7 @@ async fn test_invoice_response_structure() {
 
     let _mock = api.mock_get("/customerinvoices/inv-123", json);
 
-    let invoice = api.client.invoices().get("inv-123").await.unwrap();
+    let invoice = api.client.invoices().get(&"inv-123".into()).await.unwrap();
 
     assert!(invoice.id.is_some());
     assert!(invoice.invoice_number.is_some());
@@ -163,18 +200,18 @@ async fn test_invoice_with_rows() {
 
     let _mock = api.mock_get("/customerinvoices/inv-123", json);
 
-    let invoice = api.client.invoices().get("inv-123").await.unwrap();
+    let invoice = api.client.invoices().get(&"inv-123".into()).await.unwrap();
 
     assert_eq!(invoice.rows.len(), 2);
 
     let row1 = &invoice.rows[0];
-    assert_eq!(row1.article_id, Some("art-001".to_string()));
-    assert_eq!(row1.quantity, Some(2.0));
-    assert_eq!(row1.unit_price, Some(100.00));
-    assert_eq!(row1.total_amount, Some(200.00));
+    assert_eq!(row1.article_id, Some("art-001".into()));
+    assert_eq!(row1.quantity, Some(money!(2.0)));
+    assert_eq!(row1.unit_price, Some(money!(100.00)));
+    assert_eq!(row1.total_amount, Some(money!(200.00)));
 
     let row2 = &invoice.rows[1];
-    assert_eq!(row2.quantity, Some(1.0));
+    assert_eq!(row2.quantity, Some(money!(1.0)));
 }
 
 #[tokio::test]
@@ -191,9 +228,9 @@ async fn test_invoice_monetary_values_precision() {
 
     let _mock = api.mock_get("/customerinvoices/inv-123", json);
 
-    let invoice = api.client.invoices().get("inv-123").await.unwrap();
+    let invoice = api.client.invoices().get(&"inv-123".into()).await.unwrap();
 
-    assert_eq!(invoice.total_amount, Some(999.99));
+    assert_eq!(invoice.total_amount, Some(money!(999.99)));
     // Note: f64 may have precision issues
     assert!(invoice.total_vat_amount.is_some());
     assert!(invoice.total_amount_including_vat.is_some());
@@ -218,7 +255,7 @@ async fn test_article_response_structure() {
 
     let _mock = api.mock_get("/articles/art-123", json);
 
-    let article = api.client.articles().get("art-123").await.unwrap();
+    let article = api.client.articles().get(&"art-123".into()).await.unwrap();
 
     assert!(article.id.is_some());
     assert!(article.article_number.is_some());
@@ -243,7 +280,7 @@ async fn test_article_with_vat_rate() {
 
     let _mock = api.mock_get("/articles/art-123", json);
 
-    let article = api.client.articles().get("art-123").await.unwrap();
+    let article = api.client.articles().get(&"art-123".into()).await.unwrap();
 
     assert_eq!(article.vat_rate_id, Some("vat-25".to_string()));
     assert_eq!(article.unit, Some("pcs".to_string()));
@@ -385,10 +422,10 @@ async fn test_not_found_error_response_format() {
 
     let _mock = api.mock_error("GET", "/customers/nonexistent", 404, json);
 
-    let result = api.client.customers().get("nonexistent").await;
+    let result = api.client.customers().get(&"nonexistent".into()).await;
 
     assert!(result.is_err());
-    match result.unwrap_err() {
+    match result.unwrap_err().into_root() {
         spiris::Error::NotFound(msg) => {
             assert!(msg.contains("not found") || msg.contains("NOT_FOUND"));
         }
@@ -412,8 +449,8 @@ async fn test_rate_limit_error_response_format() {
     let result = api.client.customers().list(None).await;
 
     assert!(result.is_err());
-    match result.unwrap_err() {
-        spiris::Error::RateLimitExceeded(_) => {}
+    match result.unwrap_err().into_root() {
+        spiris::Error::RateLimitExceeded { .. } => {}
         other => panic!("Expected RateLimitExceeded error, got {:?}", other),
     }
 }
@@ -433,7 +470,7 @@ async fn test_server_error_response_format() {
     let result = api.client.customers().list(None).await;
 
     assert!(result.is_err());
-    match result.unwrap_err() {
+    match result.unwrap_err().into_root() {
         spiris::Error::ApiError { status_code, .. } => {
             assert_eq!(status_code, 500);
         }
@@ -458,7 +495,12 @@ async fn test_boolean_fields_parse_correctly() {
 
     let _mock = api.mock_get("/customers/cust-123", json);
 
-    let customer = api.client.customers().get("cust-123").await.unwrap();
+    let customer = api
+        .client
+        .customers()
+        .get(&"cust-123".into())
+        .await
+        .unwrap();
 
     assert_eq!(customer.is_active, Some(true));
     assert_eq!(customer.is_private_person, Some(false));
@@ -502,7 +544,7 @@ async fn test_date_fields_parse_as_datetime() {
 
     let _mock = api.mock_get("/customerinvoices/inv-123", json);
 
-    let invoice = api.client.invoices().get("inv-123").await.unwrap();
+    let invoice = api.client.invoices().get(&"inv-123".into()).await.unwrap();
 
     // Dates are parsed as DateTime<Utc>
     assert!(invoice.invoice_date.is_some());
@@ -522,7 +564,12 @@ async fn test_datetime_fields_parse_correctly() {
 
     let _mock = api.mock_get("/customers/cust-123", json);
 
-    let customer = api.client.customers().get("cust-123").await.unwrap();
+    let customer = api
+        .client
+        .customers()
+        .get(&"cust-123".into())
+        .await
+        .unwrap();
 
     assert!(customer.created_utc.is_some());
     assert!(customer.modified_utc.is_some());
@@ -546,7 +593,12 @@ async fn test_null_fields_become_none() {
 
     let _mock = api.mock_get("/customers/cust-123", json);
 
-    let customer = api.client.customers().get("cust-123").await.unwrap();
+    let customer = api
+        .client
+        .customers()
+        .get(&"cust-123".into())
+        .await
+        .unwrap();
 
     assert!(customer.email.is_none());
     assert!(customer.phone.is_none());
@@ -565,7 +617,12 @@ async fn test_missing_fields_become_none() {
 
     let _mock = api.mock_get("/customers/cust-123", json);
 
-    let customer = api.client.customers().get("cust-123").await.unwrap();
+    let customer = api
+        .client
+        .customers()
+        .get(&"cust-123".into())
+        .await
+        .unwrap();
 
     // All optional fields should be None
     assert!(customer.email.is_none());
@@ -586,7 +643,12 @@ async fn test_empty_string_vs_null() {
 
     let _mock = api.mock_get("/customers/cust-123", json);
 
-    let customer = api.client.customers().get("cust-123").await.unwrap();
+    let customer = api
+        .client
+        .customers()
+        .get(&"cust-123".into())
+        .await
+        .unwrap();
 
     // Empty string is Some(""), null is None
     assert_eq!(customer.email, Some("".to_string()));
@@ -643,12 +705,45 @@ async fn test_update_request_omits_none_fields() {
         ..Default::default()
     };
 
-    let result = api.client.customers().update("cust-123", &customer).await;
+    let result = api
+        .client
+        .customers()
+        .update(&"cust-123".into(), &customer)
+        .await;
     assert!(result.is_ok());
 
     mock.assert();
 }
 
+#[tokio::test]
+async fn test_order_request_includes_delivery_references() {
+    let mut api = MockApi::new().await;
+
+    let mock = api
+        .server
+        .mock("POST", "/orders")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "DeliveryMethodId": "dm-1",
+            "DeliveryTermId": "dt-2"
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "ord-1", "DeliveryMethodId": "dm-1", "DeliveryTermId": "dt-2"}"#)
+        .create();
+
+    let order = spiris::Order {
+        customer_id: Some("cust-123".into()),
+        delivery_method_id: Some("dm-1".to_string()),
+        delivery_term_id: Some("dt-2".to_string()),
+        ..Default::default()
+    };
+
+    let created = api.client.orders().create(&order).await.unwrap();
+    assert_eq!(created.delivery_term_id, Some("dt-2".to_string()));
+
+    mock.assert();
+}
+
 // =============================================================================
 // Complex Nested Structure Tests
 // =============================================================================
@@ -665,9 +760,9 @@ async fn test_invoice_with_nested_address() {
 
     let _mock = api.mock_get("/customerinvoices/inv-123", json);
 
-    let invoice = api.client.invoices().get("inv-123").await.unwrap();
+    let invoice = api.client.invoices().get(&"inv-123".into()).await.unwrap();
 
-    assert_eq!(invoice.customer_id, Some("cust-456".to_string()));
+    assert_eq!(invoice.customer_id, Some("cust-456".into()));
 }
 
 #[tokio::test]
@@ -763,7 +858,7 @@ async fn test_voucher_response_structure() {
 
     let row1 = &voucher.rows[0];
     assert_eq!(row1.account_number, Some("1910".to_string()));
-    assert_eq!(row1.debit_amount, Some(1000.00));
+    assert_eq!(row1.debit_amount, Some(money!(1000.00)));
 }
 
 // =============================================================================
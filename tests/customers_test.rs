@@ -3,7 +3,7 @@
 mod mock_server;
 
 use mock_server::MockApi;
-use spiris::{Customer, PaginationParams, QueryParams};
+use spiris::{Customer, CustomerPatch, ListScope, PaginationParams, QueryParams};
 
 #[tokio::test]
 async fn test_list_customers() {
@@ -40,7 +40,7 @@ async fn test_list_customers() {
 
     mock.assert();
     assert_eq!(result.data.len(), 2);
-    assert_eq!(result.data[0].id, Some("cust-001".to_string()));
+    assert_eq!(result.data[0].id, Some("cust-001".into()));
     assert_eq!(result.data[0].name, Some("Acme Corp".to_string()));
     assert_eq!(result.data[1].customer_number, Some("1002".to_string()));
     assert_eq!(result.meta.total_count, 2);
@@ -95,10 +95,15 @@ async fn test_get_customer() {
 
     let mock = api.mock_get("/customers/cust-123", response_body);
 
-    let result = api.client.customers().get("cust-123").await.unwrap();
+    let result = api
+        .client
+        .customers()
+        .get(&"cust-123".into())
+        .await
+        .unwrap();
 
     mock.assert();
-    assert_eq!(result.id, Some("cust-123".to_string()));
+    assert_eq!(result.id, Some("cust-123".into()));
     assert_eq!(result.name, Some("Test Customer AB".to_string()));
     assert_eq!(result.email, Some("test@customer.se".to_string()));
     assert_eq!(result.payment_terms_in_days, Some(30));
@@ -128,7 +133,7 @@ async fn test_create_customer() {
     let result = api.client.customers().create(&new_customer).await.unwrap();
 
     mock.assert();
-    assert_eq!(result.id, Some("cust-new-001".to_string()));
+    assert_eq!(result.id, Some("cust-new-001".into()));
     assert_eq!(result.customer_number, Some("3001".to_string()));
     assert_eq!(result.name, Some("New Customer".to_string()));
 }
@@ -148,7 +153,7 @@ async fn test_update_customer() {
     let mock = api.mock_put("/customers/cust-123", response_body);
 
     let updated_customer = Customer {
-        id: Some("cust-123".to_string()),
+        id: Some("cust-123".into()),
         name: Some("Updated Customer Name".to_string()),
         email: Some("updated@email.com".to_string()),
         ..Default::default()
@@ -157,7 +162,7 @@ async fn test_update_customer() {
     let result = api
         .client
         .customers()
-        .update("cust-123", &updated_customer)
+        .update(&"cust-123".into(), &updated_customer)
         .await
         .unwrap();
 
@@ -165,13 +170,36 @@ async fn test_update_customer() {
     assert_eq!(result.name, Some("Updated Customer Name".to_string()));
 }
 
+#[tokio::test]
+async fn test_update_customer_partial() {
+    let mut api = MockApi::new().await;
+
+    let mock = api.mock_patch_with_body(
+        "/customers/cust-123",
+        r#"{"Email": "billing@acme.se", "Phone": null}"#,
+        r#"{"Id": "cust-123", "Name": "Acme AB", "Email": "billing@acme.se"}"#,
+    );
+
+    let patch = CustomerPatch::new().email("billing@acme.se").clear_phone();
+    let result = api
+        .client
+        .customers()
+        .update_partial(&"cust-123".into(), &patch)
+        .await
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(result.email.as_deref(), Some("billing@acme.se"));
+    assert_eq!(result.phone, None);
+}
+
 #[tokio::test]
 async fn test_delete_customer() {
     let mut api = MockApi::new().await;
 
     let mock = api.mock_delete("/customers/cust-123");
 
-    let result = api.client.customers().delete("cust-123").await;
+    let result = api.client.customers().delete(&"cust-123".into()).await;
 
     mock.assert();
     assert!(result.is_ok());
@@ -232,3 +260,338 @@ async fn test_search_customers_with_select() {
     mock.assert();
     assert_eq!(result.data.len(), 2);
 }
+
+const DUPLICATE_NUMBER: &str = r#"{
+    "Data": [
+        {"Id": "cust-old", "CustomerNumber": "1001", "Name": "Acme (old)", "IsActive": false},
+        {"Id": "cust-new", "CustomerNumber": "1001", "Name": "Acme", "IsActive": true}
+    ],
+    "Meta": {
+        "CurrentPage": 0, "PageSize": 50, "TotalPages": 1,
+        "TotalCount": 2, "HasNextPage": false, "HasPreviousPage": false
+    }
+}"#;
+
+#[tokio::test]
+async fn test_get_customer_by_number_active_only() {
+    let mut api = MockApi::new().await;
+    let mock = api.mock_get_with_query(
+        "/customers",
+        vec![(
+            "filter",
+            "(CustomerNumber eq '1001') and (IsActive eq true)",
+        )],
+        DUPLICATE_NUMBER,
+    );
+
+    let customer = api
+        .client
+        .customers()
+        .get_by_number("1001", ListScope::ActiveOnly)
+        .await
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(customer.unwrap().id.as_deref(), Some("cust-new"));
+}
+
+#[tokio::test]
+async fn test_get_customer_by_number_inactive() {
+    let mut api = MockApi::new().await;
+    api.mock_get_with_query(
+        "/customers",
+        vec![("filter", "CustomerNumber eq '1001'")],
+        DUPLICATE_NUMBER,
+    );
+    api.mock_get_with_query(
+        "/customers",
+        vec![(
+            "filter",
+            "(CustomerNumber eq '1001') and (IsActive eq false)",
+        )],
+        DUPLICATE_NUMBER,
+    );
+
+    let customers = api.client.customers();
+    let any = customers
+        .get_by_number("1001", ListScope::All)
+        .await
+        .unwrap();
+    assert_eq!(any.unwrap().id.as_deref(), Some("cust-new"));
+
+    let inactive = customers
+        .get_by_number("1001", ListScope::InactiveOnly)
+        .await
+        .unwrap();
+    assert_eq!(inactive.unwrap().id.as_deref(), Some("cust-old"));
+}
+
+#[tokio::test]
+async fn test_list_customers_with_scope() {
+    let mut api = MockApi::new().await;
+    let mock = api.mock_get_with_query(
+        "/customers",
+        vec![("pagesize", "100"), ("filter", "IsActive eq true")],
+        DUPLICATE_NUMBER,
+    );
+
+    let params = PaginationParams::new()
+        .pagesize(100)
+        .scope(ListScope::ActiveOnly);
+    api.client.customers().list(Some(params)).await.unwrap();
+
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_merge_customers() {
+    use mockito::Matcher;
+    use spiris::endpoints::customers::MergedDocumentKind;
+
+    let mut api = MockApi::new().await;
+    let empty = mock_server::paginated_response("[]", 0, 0);
+
+    api.mock_get(
+        "/customers/cust-primary",
+        r#"{"Id": "cust-primary", "CustomerNumber": "1001", "Name": "Acme AB", "Phone": "08-123 45", "IsActive": true}"#,
+    );
+    api.mock_get(
+        "/customers/cust-dup",
+        r#"{"Id": "cust-dup", "CustomerNumber": "1042", "Name": "ACME AB", "Email": "billing@acme.example", "Phone": "070-000 00", "IsActive": true}"#,
+    );
+    api.mock_get_any_query(
+        "/customerinvoicedrafts",
+        &mock_server::paginated_response(r#"[{"Id": "draft-1", "CustomerId": "cust-dup"}]"#, 0, 1),
+    );
+    api.mock_get_any_query(
+        "/orders",
+        &mock_server::paginated_response(r#"[{"Id": "order-1", "CustomerId": "cust-dup"}]"#, 0, 1),
+    );
+    api.mock_get_any_query("/quotations", &empty);
+    api.mock_get_any_query(
+        "/customerinvoices",
+        &mock_server::paginated_response(
+            r#"[{"Id": "inv-1", "CustomerId": "cust-dup", "Rows": []}]"#,
+            0,
+            1,
+        ),
+    );
+
+    let draft = api
+        .server
+        .mock("PUT", "/customerinvoicedrafts/draft-1")
+        .match_body(Matcher::PartialJson(
+            serde_json::json!({"CustomerId": "cust-primary"}),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "draft-1", "CustomerId": "cust-primary"}"#)
+        .create();
+    api.mock_error(
+        "PUT",
+        "/orders/order-1",
+        400,
+        r#"{"Message": "Invoiced orders cannot be changed"}"#,
+    );
+    let primary = api
+        .server
+        .mock("PUT", "/customers/cust-primary")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "Email": "billing@acme.example",
+            "Phone": "08-123 45",
+        })))
+        .match_body(Matcher::Regex(
+            r#""Note":"\d{4}-\d{2}-\d{2}: merged with duplicate 1042 ACME AB""#.to_string(),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "cust-primary", "Name": "Acme AB", "Email": "billing@acme.example"}"#)
+        .create();
+    let duplicate = api
+        .server
+        .mock("PUT", "/customers/cust-dup")
+        .match_body(Matcher::PartialJson(serde_json::json!({"IsActive": true})))
+        .match_body(Matcher::Regex("merged into 1001 Acme AB".to_string()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "cust-dup", "Name": "ACME AB", "IsActive": true}"#)
+        .create();
+
+    let merge = api
+        .client
+        .customers()
+        .merge(&"cust-primary".into(), &"cust-dup".into())
+        .await
+        .unwrap();
+
+    draft.assert();
+    primary.assert();
+    duplicate.assert();
+    assert_eq!(merge.copied_fields, vec!["Email"]);
+    assert_eq!(merge.moved.len(), 1);
+    assert_eq!(merge.moved[0].kind, MergedDocumentKind::InvoiceDraft);
+    assert!(!merge.is_complete());
+    assert_eq!(merge.failures[0].document.id, "order-1");
+    assert_eq!(merge.invoices_kept, vec!["inv-1"]);
+    // The order is still on the duplicate, so it stays active
+    assert_eq!(merge.duplicate.is_active, Some(true));
+}
+
+#[tokio::test]
+async fn test_merge_customers_deactivates_duplicate_when_complete() {
+    use mockito::Matcher;
+
+    let mut api = MockApi::new().await;
+    let empty = mock_server::paginated_response("[]", 0, 0);
+
+    api.mock_get(
+        "/customers/cust-primary",
+        r#"{"Id": "cust-primary", "CustomerNumber": "1001", "Name": "Acme AB", "IsActive": true}"#,
+    );
+    api.mock_get(
+        "/customers/cust-dup",
+        r#"{"Id": "cust-dup", "CustomerNumber": "1042", "Name": "ACME AB", "IsActive": true}"#,
+    );
+    for path in [
+        "/customerinvoicedrafts",
+        "/orders",
+        "/quotations",
+        "/customerinvoices",
+    ] {
+        api.mock_get_any_query(path, &empty);
+    }
+    api.mock_put(
+        "/customers/cust-primary",
+        r#"{"Id": "cust-primary", "Name": "Acme AB"}"#,
+    );
+    let duplicate = api
+        .server
+        .mock("PUT", "/customers/cust-dup")
+        .match_body(Matcher::PartialJson(serde_json::json!({"IsActive": false})))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "cust-dup", "Name": "ACME AB", "IsActive": false}"#)
+        .create();
+
+    let merge = api
+        .client
+        .customers()
+        .merge(&"cust-primary".into(), &"cust-dup".into())
+        .await
+        .unwrap();
+
+    duplicate.assert();
+    assert!(merge.is_complete());
+    assert_eq!(merge.duplicate.is_active, Some(false));
+}
+
+#[tokio::test]
+async fn test_merge_customer_into_itself() {
+    let api = MockApi::new().await;
+
+    let result = api
+        .client
+        .customers()
+        .merge(&"cust-001".into(), &"cust-001".into())
+        .await;
+
+    assert!(matches!(result, Err(spiris::Error::InvalidRequest(_))));
+}
+
+#[tokio::test]
+async fn test_sync_customers_advances_cursor() {
+    use spiris::sync::SyncCursor;
+
+    let mut api = MockApi::new().await;
+    let mock = api.mock_get_with_query(
+        "/customers",
+        vec![
+            ("filter", "ModifiedUtc ge 2025-03-01T00:00:00Z"),
+            ("orderby", "ModifiedUtc asc"),
+        ],
+        r#"{
+            "Data": [
+                {"Id": "cust-001", "Name": "Acme Corp", "ModifiedUtc": "2025-03-01T09:15:00Z"},
+                {"Id": "cust-002", "Name": "Beta Inc", "ModifiedUtc": "2025-03-02T16:40:00Z"}
+            ],
+            "Meta": {
+                "CurrentPage": 0,
+                "PageSize": 50,
+                "TotalPages": 1,
+                "TotalCount": 2, "HasNextPage": false, "HasPreviousPage": false
+            }
+        }"#,
+    );
+
+    let mut cursor = SyncCursor::starting_at("2025-03-01T00:00:00Z".parse().unwrap());
+    let changed = api.client.customers().sync(&mut cursor).await.unwrap();
+
+    mock.assert();
+    assert_eq!(changed.len(), 2);
+    assert_eq!(
+        cursor.high_water_mark,
+        Some("2025-03-02T16:40:00Z".parse().unwrap())
+    );
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_bulk_create_skips_duplicates() {
+    use spiris::endpoints::customers::{BulkOptions, BulkOutcome};
+
+    let mut api = MockApi::new().await;
+    api.mock_get_any_query(
+        "/customers",
+        &mock_server::paginated_response(
+            r#"[{"Id": "cust-001", "CustomerNumber": "1001", "Name": "Acme Corp"}]"#,
+            0,
+            1,
+        ),
+    );
+    let create = api
+        .server
+        .mock("POST", "/customers")
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "cust-new", "Name": "Created"}"#)
+        .expect(2)
+        .create();
+
+    let customer = |number: &str, org: &str, name: &str| Customer {
+        customer_number: (!number.is_empty()).then(|| number.to_string()),
+        corporate_identity_number: (!org.is_empty()).then(|| org.to_string()),
+        name: Some(name.to_string()),
+        ..Default::default()
+    };
+    let input = vec![
+        customer("1001", "", "Acme again"),
+        customer("1002", "556677-8899", "Beta AB"),
+        customer("", "5566778899", "Beta AB (copy)"),
+        customer("1003", "", "Gamma AB"),
+        customer("1002", "", "Beta AB (second copy)"),
+    ];
+
+    let report = api
+        .client
+        .customers()
+        .bulk_create(input, BulkOptions::new().chunk_size(2).concurrency(2))
+        .await
+        .unwrap();
+
+    create.assert();
+    assert!(report.is_complete());
+    assert_eq!(report.items.len(), 5);
+    assert!(matches!(
+        &report.items[0].outcome,
+        BulkOutcome::AlreadyExists(Some(id)) if id.as_str() == "cust-001"
+    ));
+    assert!(matches!(
+        report.items[2].outcome,
+        BulkOutcome::DuplicateOf(1)
+    ));
+    assert!(matches!(
+        report.items[4].outcome,
+        BulkOutcome::DuplicateOf(1)
+    ));
+    assert_eq!(report.created().count(), 2);
+}
@@ -61,17 +61,32 @@ fn test_invoice_with_decimal() {
 #[test]
 fn test_decimal_serialization() {
     let row = InvoiceRow {
-        unit_price: Some(money!(100.00)),
-        quantity: Some(money!(5.0)),
+        unit_price: Some(money!(19.99)),
+        quantity: Some(money!(2)),
         ..Default::default()
     };
 
-    // Serialize to JSON
-    let json = serde_json::to_string(&row).unwrap();
+    // Amounts go on the wire as JSON numbers, not strings
+    let json = serde_json::to_value(&row).unwrap();
 
-    // The JSON should contain the numeric values
-    assert!(json.contains("100"));
-    assert!(json.contains("5"));
+    assert_eq!(json["UnitPrice"], serde_json::json!(19.99));
+    assert_eq!(json["Quantity"], serde_json::json!(2.0));
+    assert!(json["UnitPrice"].is_number());
+    assert!(json["Quantity"].is_number());
+}
+
+#[test]
+fn test_decimal_serialization_round_trips() {
+    let invoice = Invoice {
+        total_amount: Some(money!(12345.67)),
+        ..Default::default()
+    };
+
+    let json = serde_json::to_string(&invoice).unwrap();
+    assert!(json.contains(r#""TotalAmount":12345.67"#));
+
+    let back: Invoice = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.total_amount, Some(money!(12345.67)));
 }
 
 #[test]
@@ -0,0 +1,130 @@
+//! Integration tests for dunning runs.
+
+mod mock_server;
+
+use chrono::NaiveDate;
+use mock_server::MockApi;
+use spiris::dunning::{self, DunningPolicy, InterestPolicy};
+use spiris::ReminderLevel;
+
+const INVOICES: &str = r#"{
+    "Data": [
+        {"Id": "inv-1", "InvoiceNumber": "1001", "Rows": [], "DueDate": "2025-03-01T00:00:00Z", "RemainingAmount": 1200.0},
+        {"Id": "inv-2", "InvoiceNumber": "1002", "Rows": [], "DueDate": "2025-01-10T00:00:00Z", "RemainingAmount": 800.0,
+         "ReminderLevel": 1, "LastReminderDate": "2025-02-01T00:00:00Z"},
+        {"Id": "inv-3", "InvoiceNumber": "1003", "Rows": [], "DueDate": "2025-03-30T00:00:00Z", "RemainingAmount": 500.0}
+    ],
+    "Meta": {
+        "CurrentPage": 0, "PageSize": 500, "TotalPages": 1,
+        "TotalCount": 3, "HasNextPage": false, "HasPreviousPage": false
+    }
+}"#;
+
+fn as_of() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, 31).unwrap()
+}
+
+#[tokio::test]
+async fn test_preview_dunning_candidates() {
+    let mut api = MockApi::new().await;
+    api.mock_get_any_query("/customerinvoices", INVOICES);
+
+    let candidates = dunning::preview(&api.client, &DunningPolicy::default(), as_of())
+        .await
+        .unwrap();
+
+    let levels: Vec<_> = candidates
+        .iter()
+        .map(|c| (c.invoice_id.as_str(), c.level))
+        .collect();
+    assert_eq!(
+        levels,
+        [
+            ("inv-2", ReminderLevel::new(2)),
+            ("inv-1", ReminderLevel::FIRST)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_run_reports_per_invoice_results() {
+    let mut api = MockApi::new().await;
+    api.mock_get_any_query("/customerinvoices", INVOICES);
+
+    let second_reminder = api
+        .server
+        .mock("POST", "/customerinvoices/inv-2/reminders")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "CustomerInvoiceId": "inv-2",
+            "Level": 2,
+            "ReminderDate": "2025-03-31T00:00:00Z"
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "rem-1", "Level": 2}"#)
+        .create();
+    let failing = api.mock_error(
+        "POST",
+        "/customerinvoices/inv-1/reminders",
+        400,
+        r#"{"Message": "Customer has no address"}"#,
+    );
+
+    let candidates = dunning::preview(&api.client, &DunningPolicy::default(), as_of())
+        .await
+        .unwrap();
+    let results = dunning::run(&api.client, &candidates, as_of()).await;
+
+    second_reminder.assert();
+    failing.assert();
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(results[1]
+        .error
+        .as_deref()
+        .unwrap()
+        .contains("Customer has no address"));
+}
+
+#[tokio::test]
+async fn test_create_interest_invoices() {
+    let mut api = MockApi::new().await;
+    api.mock_get_any_query(
+        "/customerinvoices",
+        &INVOICES.replace(
+            r#""Id": "inv-2","#,
+            r#""Id": "inv-2", "CustomerId": "cust-2","#,
+        ),
+    );
+
+    let create = api
+        .server
+        .mock("POST", "/customerinvoices")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "CustomerId": "cust-2",
+            "InvoiceDate": "2025-03-31",
+            "Rows": [{
+                "Text": "Interest on invoice 1002, 80 days at 12%",
+                "IsVatFree": true
+            }]
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "inv-9", "InvoiceNumber": "1009", "Rows": []}"#)
+        .expect(1)
+        .create();
+
+    let policy = InterestPolicy::new(spiris::money!(12));
+    let candidates = dunning::preview_interest(&api.client, &policy, as_of())
+        .await
+        .unwrap();
+    // inv-1 and inv-3 have no customer to invoice
+    assert_eq!(candidates.len(), 1);
+
+    let results =
+        dunning::create_interest_invoices(&api.client, &policy, &candidates, as_of()).await;
+
+    create.assert();
+    assert!(results[0].is_ok());
+    assert_eq!(results[0].interest_invoice_id.as_deref(), Some("inv-9"));
+}
@@ -1,5 +1,3 @@
-// Skip these tests when decimal feature is enabled (uses f64 literals)
-#![cfg(not(feature = "decimal"))]
 //! Comprehensive CRUD operation tests for all endpoints.
 //!
 //! These tests verify that each endpoint correctly implements:
@@ -13,7 +11,7 @@
 mod mock_server;
 
 use mock_server::{fixtures, meta_json, MockApi};
-use spiris::{Article, Customer, Invoice, InvoiceRow, PaginationParams};
+use spiris::{money, Article, Customer, Error, Invoice, InvoiceRow, PaginationParams, Versioned};
 
 // =============================================================================
 // Customer Endpoint CRUD Tests
@@ -71,11 +69,11 @@ async fn test_customer_get() {
 
     let _mock = api.mock_get("/customers/cust-001", &response);
 
-    let result = api.client.customers().get("cust-001").await;
+    let result = api.client.customers().get(&"cust-001".into()).await;
 
     assert!(result.is_ok());
     let customer = result.unwrap();
-    assert_eq!(customer.id, Some("cust-001".to_string()));
+    assert_eq!(customer.id, Some("cust-001".into()));
     assert_eq!(customer.name, Some("Test Customer 1".to_string()));
 }
 
@@ -91,7 +89,7 @@ async fn test_customer_create() {
     };
 
     let created_customer = Customer {
-        id: Some("cust-new".to_string()),
+        id: Some("cust-new".into()),
         name: Some("New Customer".to_string()),
         email: Some("new@example.com".to_string()),
         is_active: Some(true),
@@ -121,7 +119,7 @@ async fn test_customer_update() {
     let mut api = MockApi::new().await;
 
     let updated_customer = Customer {
-        id: Some("cust-001".to_string()),
+        id: Some("cust-001".into()),
         name: Some("Updated Name".to_string()),
         email: Some("updated@example.com".to_string()),
         is_active: Some(true),
@@ -141,7 +139,7 @@ async fn test_customer_update() {
     let result = api
         .client
         .customers()
-        .update("cust-001", &updated_customer)
+        .update(&"cust-001".into(), &updated_customer)
         .await;
 
     assert!(result.is_ok());
@@ -159,7 +157,7 @@ async fn test_customer_delete() {
         .with_status(204)
         .create();
 
-    let result = api.client.customers().delete("cust-001").await;
+    let result = api.client.customers().delete(&"cust-001".into()).await;
 
     assert!(result.is_ok());
 }
@@ -191,6 +189,82 @@ async fn test_customer_search() {
     assert_eq!(response.data.len(), 1);
 }
 
+#[tokio::test]
+async fn test_customer_update_if_match_sends_etag() {
+    let mut api = MockApi::new().await;
+    let customer = serde_json::to_string(&fixtures::customer(1)).unwrap();
+
+    let _get = api
+        .server
+        .mock("GET", "/customers/cust-001")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("ETag", "\"7\"")
+        .with_body(&customer)
+        .create_async()
+        .await;
+    let put = api
+        .server
+        .mock("PUT", "/customers/cust-001")
+        .match_header("If-Match", "\"7\"")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "Name": "Renamed AB"
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("ETag", "\"8\"")
+        .with_body(&customer)
+        .create_async()
+        .await;
+
+    let mut fetched = api
+        .client
+        .customers()
+        .get_versioned(&"cust-001".into())
+        .await
+        .unwrap();
+    assert_eq!(fetched.etag.as_deref(), Some("\"7\""));
+    fetched.name = Some("Renamed AB".to_string());
+
+    let updated = api
+        .client
+        .customers()
+        .update_if_match(&"cust-001".into(), &fetched)
+        .await
+        .unwrap();
+
+    put.assert_async().await;
+    assert_eq!(updated.etag.as_deref(), Some("\"8\""));
+}
+
+#[tokio::test]
+async fn test_customer_update_if_match_conflict() {
+    let mut api = MockApi::new().await;
+
+    let _put = api
+        .server
+        .mock("PUT", "/customers/cust-001")
+        .match_header("If-Match", "\"7\"")
+        .with_status(412)
+        .with_body(r#"{"Message": "The entity has been modified"}"#)
+        .create_async()
+        .await;
+
+    let stale = Versioned {
+        value: fixtures::customer(1),
+        etag: Some("\"7\"".to_string()),
+    };
+    let result = api
+        .client
+        .customers()
+        .update_if_match(&"cust-001".into(), &stale)
+        .await;
+
+    let err = result.unwrap_err();
+    assert!(matches!(err.root(), Error::Conflict(_)), "{:?}", err);
+    assert!(!err.is_retryable());
+}
+
 // =============================================================================
 // Invoice Endpoint CRUD Tests
 // =============================================================================
@@ -225,11 +299,11 @@ async fn test_invoice_get() {
 
     let _mock = api.mock_get("/customerinvoices/inv-001", &response);
 
-    let result = api.client.invoices().get("inv-001").await;
+    let result = api.client.invoices().get(&"inv-001".into()).await;
 
     assert!(result.is_ok());
     let invoice = result.unwrap();
-    assert_eq!(invoice.id, Some("inv-001".to_string()));
+    assert_eq!(invoice.id, Some("inv-001".into()));
 }
 
 #[tokio::test]
@@ -237,21 +311,21 @@ async fn test_invoice_create() {
     let mut api = MockApi::new().await;
 
     let new_invoice = Invoice {
-        customer_id: Some("cust-001".to_string()),
+        customer_id: Some("cust-001".into()),
         rows: vec![InvoiceRow {
-            article_id: Some("art-001".to_string()),
-            quantity: Some(2.0),
-            unit_price: Some(100.0),
+            article_id: Some("art-001".into()),
+            quantity: Some(money!(2.0)),
+            unit_price: Some(money!(100.0)),
             ..Default::default()
         }],
         ..Default::default()
     };
 
     let created_invoice = Invoice {
-        id: Some("inv-new".to_string()),
+        id: Some("inv-new".into()),
         invoice_number: Some("1001".to_string()),
-        customer_id: Some("cust-001".to_string()),
-        total_amount: Some(200.0),
+        customer_id: Some("cust-001".into()),
+        total_amount: Some(money!(200.0)),
         rows: vec![],
         ..Default::default()
     };
@@ -278,8 +352,8 @@ async fn test_invoice_update() {
     let mut api = MockApi::new().await;
 
     let updated_invoice = Invoice {
-        id: Some("inv-001".to_string()),
-        customer_id: Some("cust-001".to_string()),
+        id: Some("inv-001".into()),
+        customer_id: Some("cust-001".into()),
         rows: vec![],
         ..Default::default()
     };
@@ -297,7 +371,7 @@ async fn test_invoice_update() {
     let result = api
         .client
         .invoices()
-        .update("inv-001", &updated_invoice)
+        .update(&"inv-001".into(), &updated_invoice)
         .await;
 
     assert!(result.is_ok());
@@ -313,7 +387,7 @@ async fn test_invoice_delete() {
         .with_status(204)
         .create();
 
-    let result = api.client.invoices().delete("inv-001").await;
+    let result = api.client.invoices().delete(&"inv-001".into()).await;
 
     assert!(result.is_ok());
 }
@@ -349,11 +423,11 @@ async fn test_article_get() {
 
     let _mock = api.mock_get("/articles/art-001", &response);
 
-    let result = api.client.articles().get("art-001").await;
+    let result = api.client.articles().get(&"art-001".into()).await;
 
     assert!(result.is_ok());
     let article = result.unwrap();
-    assert_eq!(article.id, Some("art-001".to_string()));
+    assert_eq!(article.id, Some("art-001".into()));
 }
 
 #[tokio::test]
@@ -363,16 +437,16 @@ async fn test_article_create() {
     let new_article = Article {
         name: Some("New Article".to_string()),
         article_number: Some("ART-NEW".to_string()),
-        sales_price: Some(99.99),
+        sales_price: Some(money!(99.99)),
         is_active: Some(true),
         ..Default::default()
     };
 
     let created_article = Article {
-        id: Some("art-new".to_string()),
+        id: Some("art-new".into()),
         name: Some("New Article".to_string()),
         article_number: Some("ART-NEW".to_string()),
-        sales_price: Some(99.99),
+        sales_price: Some(money!(99.99)),
         is_active: Some(true),
         ..Default::default()
     };
@@ -399,9 +473,9 @@ async fn test_article_update() {
     let mut api = MockApi::new().await;
 
     let updated_article = Article {
-        id: Some("art-001".to_string()),
+        id: Some("art-001".into()),
         name: Some("Updated Article".to_string()),
-        sales_price: Some(149.99),
+        sales_price: Some(money!(149.99)),
         is_active: Some(true),
         ..Default::default()
     };
@@ -419,7 +493,7 @@ async fn test_article_update() {
     let result = api
         .client
         .articles()
-        .update("art-001", &updated_article)
+        .update(&"art-001".into(), &updated_article)
         .await;
 
     assert!(result.is_ok());
@@ -435,7 +509,7 @@ async fn test_article_delete() {
         .with_status(204)
         .create();
 
-    let result = api.client.articles().delete("art-001").await;
+    let result = api.client.articles().delete(&"art-001".into()).await;
 
     assert!(result.is_ok());
 }
@@ -506,7 +580,7 @@ async fn test_customer_get_not_found() {
         r#"{"Message": "Customer not found"}"#,
     );
 
-    let result = api.client.customers().get("nonexistent").await;
+    let result = api.client.customers().get(&"nonexistent".into()).await;
 
     assert!(result.is_err());
 }
@@ -522,7 +596,7 @@ async fn test_invoice_get_not_found() {
         r#"{"Message": "Invoice not found"}"#,
     );
 
-    let result = api.client.invoices().get("nonexistent").await;
+    let result = api.client.invoices().get(&"nonexistent".into()).await;
 
     assert!(result.is_err());
 }
@@ -538,7 +612,7 @@ async fn test_article_get_not_found() {
         r#"{"Message": "Article not found"}"#,
     );
 
-    let result = api.client.articles().get("nonexistent").await;
+    let result = api.client.articles().get(&"nonexistent".into()).await;
 
     assert!(result.is_err());
 }
@@ -600,10 +674,14 @@ async fn test_customer_update_conflict() {
     );
 
     let customer = Customer {
-        id: Some("cust-001".to_string()),
+        id: Some("cust-001".into()),
         ..Default::default()
     };
-    let result = api.client.customers().update("cust-001", &customer).await;
+    let result = api
+        .client
+        .customers()
+        .update(&"cust-001".into(), &customer)
+        .await;
 
     assert!(result.is_err());
 }
@@ -623,7 +701,7 @@ async fn test_customer_delete_not_found() {
         r#"{"Message": "Customer not found"}"#,
     );
 
-    let result = api.client.customers().delete("nonexistent").await;
+    let result = api.client.customers().delete(&"nonexistent".into()).await;
 
     assert!(result.is_err());
 }
@@ -637,7 +715,7 @@ async fn test_customer_list_large_page() {
     let mut api = MockApi::new().await;
 
     // Generate 50 customers
-    let customers: Vec<Customer> = (1..=50).map(|i| fixtures::customer(i)).collect();
+    let customers: Vec<Customer> = (1..=50).map(fixtures::customer).collect();
     let data = serde_json::to_string(&customers).unwrap();
     let meta = meta_json(0, 50, 10, 500);
     let response = format!(r#"{{"Data": {}, {}}}"#, data, meta);
@@ -662,7 +740,7 @@ async fn test_customer_get_with_special_id() {
     let mut api = MockApi::new().await;
 
     let customer = Customer {
-        id: Some("cust-abc-123-xyz".to_string()),
+        id: Some("cust-abc-123-xyz".into()),
         name: Some("Special Customer".to_string()),
         ..Default::default()
     };
@@ -670,7 +748,7 @@ async fn test_customer_get_with_special_id() {
 
     let _mock = api.mock_get("/customers/cust-abc-123-xyz", &response);
 
-    let result = api.client.customers().get("cust-abc-123-xyz").await;
+    let result = api.client.customers().get(&"cust-abc-123-xyz".into()).await;
 
     assert!(result.is_ok());
 }
@@ -8,7 +8,7 @@ use spiris::{AccessToken, ApiErrorResponse, Client, ClientConfig, Customer, Erro
 fn api_error(status_code: u16, message: &str) -> Error {
     Error::ApiError {
         status_code,
-        response: ApiErrorResponse::from_raw(message.to_string()),
+        response: Box::new(ApiErrorResponse::from_raw(message.to_string())),
         raw_body: message.to_string(),
     }
 }
@@ -24,7 +24,12 @@ async fn test_not_found_error() {
         r#"{"Message": "Customer not found"}"#,
     );
 
-    let result = api.client.customers().get("nonexistent").await;
+    let result = api
+        .client
+        .customers()
+        .get(&"nonexistent".into())
+        .await
+        .map_err(Error::into_root);
 
     mock.assert();
     assert!(matches!(result, Err(Error::NotFound(_))));
@@ -44,7 +49,12 @@ async fn test_unauthorized_error() {
         .with_body(r#"{"Message": "Invalid token"}"#)
         .create();
 
-    let result = api.client.customers().list(None).await;
+    let result = api
+        .client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     mock.assert();
     assert!(matches!(result, Err(Error::AuthError(_))));
@@ -61,7 +71,12 @@ async fn test_forbidden_error() {
         .with_body(r#"{"Message": "Access denied"}"#)
         .create();
 
-    let result = api.client.customers().list(None).await;
+    let result = api
+        .client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     mock.assert();
     assert!(matches!(result, Err(Error::AuthError(_))));
@@ -78,11 +93,16 @@ async fn test_rate_limit_error() {
         r#"{"Message": "Rate limit exceeded. Try again later."}"#,
     );
 
-    let result = api.client.customers().list(None).await;
+    let result = api
+        .client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     mock.assert();
-    assert!(matches!(result, Err(Error::RateLimitExceeded(_))));
-    if let Err(Error::RateLimitExceeded(msg)) = result {
+    assert!(matches!(result, Err(Error::RateLimitExceeded { .. })));
+    if let Err(Error::RateLimitExceeded { message: msg, .. }) = result {
         assert!(msg.contains("Rate limit"));
     }
 }
@@ -99,13 +119,17 @@ async fn test_bad_request_error() {
     );
 
     let invalid_customer = Customer::default();
-    let result = api.client.customers().create(&invalid_customer).await;
+    let result = api
+        .client
+        .customers()
+        .create(&invalid_customer)
+        .await
+        .map_err(Error::into_root);
 
     mock.assert();
-    assert!(matches!(result, Err(Error::InvalidRequest(_))));
-    if let Err(Error::InvalidRequest(msg)) = result {
-        assert!(msg.contains("Name is required"));
-    }
+    let err = result.unwrap_err();
+    assert_eq!(err.status_code(), Some(400));
+    assert!(err.to_string().contains("Name is required"));
 }
 
 #[tokio::test]
@@ -119,7 +143,12 @@ async fn test_server_error() {
         r#"{"Message": "Internal server error"}"#,
     );
 
-    let result = api.client.customers().list(None).await;
+    let result = api
+        .client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     mock.assert();
     assert!(matches!(
@@ -142,7 +171,12 @@ async fn test_service_unavailable_error() {
         r#"{"Message": "Service temporarily unavailable"}"#,
     );
 
-    let result = api.client.customers().list(None).await;
+    let result = api
+        .client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     mock.assert();
     assert!(matches!(
@@ -166,7 +200,11 @@ async fn test_expired_token_error() {
     let config = ClientConfig::new().base_url(server.url());
     let client = Client::with_config(expired_token, config);
 
-    let result = client.customers().list(None).await;
+    let result = client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     assert!(matches!(result, Err(Error::TokenExpired)));
 }
@@ -182,7 +220,12 @@ async fn test_delete_not_found_error() {
         r#"{"Message": "Customer not found"}"#,
     );
 
-    let result = api.client.customers().delete("nonexistent").await;
+    let result = api
+        .client
+        .customers()
+        .delete(&"nonexistent".into())
+        .await
+        .map_err(Error::into_root);
 
     mock.assert();
     assert!(matches!(result, Err(Error::NotFound(_))));
@@ -200,11 +243,16 @@ async fn test_update_conflict_error() {
     );
 
     let customer = Customer {
-        id: Some("cust-123".to_string()),
+        id: Some("cust-123".into()),
         name: Some("Updated".to_string()),
         ..Default::default()
     };
-    let result = api.client.customers().update("cust-123", &customer).await;
+    let result = api
+        .client
+        .customers()
+        .update(&"cust-123".into(), &customer)
+        .await
+        .map_err(Error::into_root);
 
     mock.assert();
     assert!(matches!(
@@ -235,13 +283,29 @@ async fn test_validation_error_details() {
         phone: Some("not-a-phone".to_string()),
         ..Default::default()
     };
-    let result = api.client.customers().create(&invalid_customer).await;
+    let result = api
+        .client
+        .customers()
+        .create(&invalid_customer)
+        .await
+        .map_err(Error::into_root);
 
     mock.assert();
-    assert!(matches!(result, Err(Error::InvalidRequest(_))));
-    if let Err(Error::InvalidRequest(msg)) = result {
-        assert!(msg.contains("Validation failed"));
-    }
+    let err = result.unwrap_err();
+    assert_eq!(err.status_code(), Some(400));
+    let fields: Vec<_> = err
+        .validation_errors()
+        .unwrap()
+        .iter()
+        .map(|e| (e.field.as_str(), e.message.as_str()))
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            ("Email", "Invalid email format"),
+            ("Phone", "Invalid phone number")
+        ]
+    );
 }
 
 #[tokio::test]
@@ -250,7 +314,12 @@ async fn test_empty_response_body_error() {
 
     let mock = api.mock_error("GET", "/customers/empty", 404, "");
 
-    let result = api.client.customers().get("empty").await;
+    let result = api
+        .client
+        .customers()
+        .get(&"empty".into())
+        .await
+        .map_err(Error::into_root);
 
     mock.assert();
     assert!(matches!(result, Err(Error::NotFound(_))));
@@ -266,7 +335,12 @@ async fn test_502_bad_gateway() {
 
     let _mock = api.mock_error("GET", "/customers", 502, r#"{"Message": "Bad Gateway"}"#);
 
-    let result = api.client.customers().list(None).await;
+    let result = api
+        .client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     assert!(matches!(
         result,
@@ -288,7 +362,12 @@ async fn test_504_gateway_timeout() {
         r#"{"Message": "Gateway timeout"}"#,
     );
 
-    let result = api.client.customers().list(None).await;
+    let result = api
+        .client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     assert!(matches!(
         result,
@@ -328,7 +407,10 @@ async fn test_422_unprocessable_entity() {
 fn test_rate_limit_error_is_retryable() {
     use spiris::retry::is_retryable_error;
 
-    let error = Error::RateLimitExceeded("Rate limit exceeded".to_string());
+    let error = Error::RateLimitExceeded {
+        message: "Rate limit exceeded".to_string(),
+        retry_after: None,
+    };
     assert!(
         is_retryable_error(&error),
         "Rate limit errors should be retryable"
@@ -415,7 +497,10 @@ fn test_not_found_display() {
 
 #[test]
 fn test_rate_limit_display() {
-    let error = Error::RateLimitExceeded("Too many requests".to_string());
+    let error = Error::RateLimitExceeded {
+        message: "Too many requests".to_string(),
+        retry_after: None,
+    };
 
     let display = format!("{}", error);
     assert!(
@@ -515,7 +600,12 @@ async fn test_invoice_not_found() {
         r#"{"Message": "Invoice not found"}"#,
     );
 
-    let result = api.client.invoices().get("inv-nonexistent").await;
+    let result = api
+        .client
+        .invoices()
+        .get(&"inv-nonexistent".into())
+        .await
+        .map_err(Error::into_root);
 
     assert!(matches!(
         result,
@@ -538,7 +628,12 @@ async fn test_article_not_found() {
         r#"{"Message": "Article not found"}"#,
     );
 
-    let result = api.client.articles().get("art-nonexistent").await;
+    let result = api
+        .client
+        .articles()
+        .get(&"art-nonexistent".into())
+        .await
+        .map_err(Error::into_root);
 
     assert!(matches!(
         result,
@@ -550,6 +645,85 @@ async fn test_article_not_found() {
     ));
 }
 
+#[tokio::test]
+async fn test_errors_carry_request_context() {
+    let mut api = MockApi::new().await;
+
+    api.mock_error(
+        "PUT",
+        "/customers/cust-404",
+        404,
+        r#"{"Message": "Customer not found"}"#,
+    );
+    let err = api
+        .client
+        .customers()
+        .update(&"cust-404".into(), &Customer::default())
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.method(), Some("PUT"));
+    assert_eq!(err.endpoint(), Some("customers"));
+    assert_eq!(err.entity_id(), Some("cust-404"));
+    assert!(err.page().is_none());
+    assert!(matches!(err.root(), Error::NotFound(_)));
+    assert!(err.to_string().ends_with("[PUT /customers/cust-404]"));
+
+    api.server
+        .mock("GET", "/customerinvoices")
+        .match_query(mockito::Matcher::UrlEncoded("page".into(), "4".into()))
+        .with_status(400)
+        .with_body("Invalid page")
+        .create();
+    let err = api
+        .client
+        .invoices()
+        .list(Some(spiris::PaginationParams::new().page(4)))
+        .await
+        .unwrap_err();
+
+    let context = err.context().unwrap();
+    assert_eq!(context.method, "GET");
+    assert_eq!(context.endpoint, "customerinvoices");
+    assert_eq!(context.page, Some(4));
+    assert!(context.entity_id.is_none());
+}
+
+#[tokio::test]
+async fn test_errors_carry_url_and_correlation_id() {
+    let mut api = MockApi::new().await;
+
+    api.server
+        .mock("GET", "/customers")
+        .match_query(mockito::Matcher::UrlEncoded("page".into(), "2".into()))
+        .with_status(429)
+        .with_header("x-correlation-id", "5f2b9c7e")
+        .with_body("Too many requests")
+        .create();
+    let err = api
+        .client
+        .customers()
+        .list(Some(spiris::PaginationParams::new().page(2)))
+        .await
+        .unwrap_err();
+
+    assert!(err.is_rate_limited());
+    assert!(err.is_retryable());
+    assert!(!err.is_auth_error());
+    assert_eq!(err.correlation_id(), Some("5f2b9c7e"));
+    assert_eq!(err.page(), Some(2));
+    let url = err.url().unwrap();
+    assert!(url.starts_with(&api.server.url()));
+    assert!(url.ends_with("/customers?page=2"), "{}", url);
+    assert!(err.to_string().contains("correlation id 5f2b9c7e"));
+
+    api.mock_unauthorized("/articles");
+    let err = api.client.articles().list(None).await.unwrap_err();
+    assert!(err.is_auth_error());
+    assert!(!err.is_retryable());
+    assert!(err.correlation_id().is_none());
+}
+
 // =============================================================================
 // Error Recovery Tests
 // =============================================================================
@@ -572,11 +746,11 @@ async fn test_success_after_error() {
     );
 
     // First call fails
-    let result1 = api.client.customers().get("cust-001").await;
+    let result1 = api.client.customers().get(&"cust-001".into()).await;
     assert!(result1.is_err());
 
     // Second call succeeds
-    let result2 = api.client.customers().get("cust-001").await;
+    let result2 = api.client.customers().get(&"cust-001".into()).await;
     assert!(result2.is_ok());
 }
 
@@ -596,17 +770,32 @@ async fn test_different_errors_in_sequence() {
     );
 
     // Each call returns different error
-    let result1 = api.client.customers().get("cust-001").await;
+    let result1 = api
+        .client
+        .customers()
+        .get(&"cust-001".into())
+        .await
+        .map_err(Error::into_root);
     if let Err(Error::ApiError { status_code, .. }) = result1 {
         assert_eq!(status_code, 500);
     }
 
-    let result2 = api.client.customers().get("cust-001").await;
+    let result2 = api
+        .client
+        .customers()
+        .get(&"cust-001".into())
+        .await
+        .map_err(Error::into_root);
     if let Err(Error::ApiError { status_code, .. }) = result2 {
         assert_eq!(status_code, 503);
     }
 
-    let result3 = api.client.customers().get("cust-001").await;
+    let result3 = api
+        .client
+        .customers()
+        .get(&"cust-001".into())
+        .await
+        .map_err(Error::into_root);
     if let Err(Error::ApiError { status_code, .. }) = result3 {
         assert_eq!(status_code, 502);
     }
@@ -628,6 +817,8 @@ async fn test_concurrent_errors() {
     let customers = api.client.customers();
     let articles = api.client.articles();
     let (result1, result2) = tokio::join!(customers.list(None), articles.list(None));
+    let result1 = result1.map_err(Error::into_root);
+    let result2 = result2.map_err(Error::into_root);
 
     assert!(result1.is_err());
     assert!(result2.is_err());
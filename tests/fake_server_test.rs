@@ -0,0 +1,212 @@
+//! Integration tests for the in-memory fake server.
+
+#![cfg(feature = "fake")]
+
+use spiris::fake::FakeServer;
+use spiris::{
+    Article, Customer, CustomerPatch, Error, Invoice, InvoiceRow, ListScope, PaginationParams,
+    QueryParams,
+};
+
+fn customer(name: &str) -> Customer {
+    Customer {
+        name: Some(name.to_string()),
+        ..Default::default()
+    }
+}
+
+fn amount(value: f64) -> spiris::Money {
+    serde_json::from_value(serde_json::json!(value)).unwrap()
+}
+
+#[tokio::test]
+async fn test_create_customer_then_list_it_back() {
+    let fake = FakeServer::start().await;
+    let client = fake.client();
+
+    let created = client
+        .customers()
+        .create(&customer("Acme AB"))
+        .await
+        .unwrap();
+    assert!(created.id.is_some());
+    assert_eq!(created.customer_number.as_deref(), Some("1"));
+    assert_eq!(created.is_active, Some(true));
+    assert!(created.created_utc.is_some());
+
+    let fetched = client
+        .customers()
+        .get(created.id.as_ref().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(fetched.name.as_deref(), Some("Acme AB"));
+
+    let page = client.customers().list(None).await.unwrap();
+    assert_eq!(page.data.len(), 1);
+    assert_eq!(page.data[0].id, created.id);
+    assert_eq!(fake.customers().len(), 1);
+}
+
+#[tokio::test]
+async fn test_pagination() {
+    let fake = FakeServer::start().await;
+    for n in 0..7 {
+        fake.insert_customer(&customer(&format!("Kund {}", n)));
+    }
+    let client = fake.client();
+
+    let page = client
+        .customers()
+        .list(Some(PaginationParams::new().page(1).pagesize(3)))
+        .await
+        .unwrap();
+    assert_eq!(page.data.len(), 3);
+    assert_eq!(page.data[0].name.as_deref(), Some("Kund 3"));
+    assert_eq!(page.total_count(), 7);
+    assert_eq!(page.total_pages(), 3);
+    assert!(page.has_next_page());
+
+    let all = client.customers().list_all(None).await.unwrap();
+    assert_eq!(all.len(), 7);
+}
+
+#[tokio::test]
+async fn test_filters() {
+    let fake = FakeServer::start().await;
+    fake.insert_customer(&customer("Acme AB"));
+    fake.insert_customer(&Customer {
+        is_active: Some(false),
+        ..customer("Gamla Acme AB")
+    });
+    let client = fake.client();
+
+    let active = client
+        .customers()
+        .list(Some(PaginationParams::new().scope(ListScope::ActiveOnly)))
+        .await
+        .unwrap();
+    assert_eq!(active.data.len(), 1);
+    assert_eq!(active.data[0].name.as_deref(), Some("Acme AB"));
+
+    let by_name = client
+        .customers()
+        .search(QueryParams::new().filter("Name eq 'Gamla Acme AB'"), None)
+        .await
+        .unwrap();
+    assert_eq!(by_name.data.len(), 1);
+    assert_eq!(by_name.data[0].is_active, Some(false));
+}
+
+#[tokio::test]
+async fn test_invoice_numbering_and_totals() {
+    let fake = FakeServer::start().await;
+    fake.set_next_invoice_number(1001);
+    let buyer = fake.insert_customer(&customer("Acme AB"));
+    let client = fake.client();
+
+    let invoice = Invoice {
+        customer_id: buyer.id.clone(),
+        invoice_number: Some("999".to_string()),
+        rows: vec![
+            InvoiceRow {
+                text: Some("Konsulttimme".to_string()),
+                unit_price: Some(amount(1000.0)),
+                quantity: Some(amount(3.0)),
+                ..Default::default()
+            },
+            InvoiceRow {
+                text: Some("Licens".to_string()),
+                unit_price: Some(amount(500.0)),
+                quantity: Some(amount(1.0)),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let first = client.invoices().create(&invoice).await.unwrap();
+    let second = client.invoices().create(&invoice).await.unwrap();
+    assert_eq!(first.invoice_number.as_deref(), Some("1001"));
+    assert_eq!(second.invoice_number.as_deref(), Some("1002"));
+    assert_eq!(first.total_amount, Some(amount(3500.0)));
+    assert_eq!(first.rows[0].total_amount, Some(amount(3000.0)));
+}
+
+#[tokio::test]
+async fn test_validation_errors() {
+    let fake = FakeServer::start().await;
+    let client = fake.client();
+
+    let error = client
+        .customers()
+        .create(&Customer::default())
+        .await
+        .unwrap_err();
+    assert_eq!(error.status_code(), Some(400));
+
+    let orphan = Invoice {
+        customer_id: Some("no-such-customer".into()),
+        ..Default::default()
+    };
+    let error = client.invoices().create(&orphan).await.unwrap_err();
+    assert_eq!(error.status_code(), Some(400));
+    assert!(fake.invoices().is_empty());
+}
+
+#[tokio::test]
+async fn test_update_patch_and_delete() {
+    let fake = FakeServer::start().await;
+    let client = fake.client();
+    let created = client
+        .customers()
+        .create(&Customer {
+            email: Some("info@acme.se".to_string()),
+            ..customer("Acme AB")
+        })
+        .await
+        .unwrap();
+    let id = created.id.clone().unwrap();
+
+    let renamed = client
+        .customers()
+        .update(&id, &customer("Acme Sverige AB"))
+        .await
+        .unwrap();
+    assert_eq!(renamed.name.as_deref(), Some("Acme Sverige AB"));
+    assert_eq!(renamed.customer_number, created.customer_number);
+
+    let patched = client
+        .customers()
+        .update_partial(&id, &CustomerPatch::new().clear_email())
+        .await
+        .unwrap();
+    assert_eq!(patched.email, None);
+    assert_eq!(patched.name.as_deref(), Some("Acme Sverige AB"));
+
+    client.customers().delete(&id).await.unwrap();
+    let error = client.customers().get(&id).await.unwrap_err();
+    assert!(matches!(error.root(), Error::NotFound(_)));
+}
+
+#[tokio::test]
+async fn test_articles_are_numbered() {
+    let fake = FakeServer::start().await;
+    let client = fake.client();
+    let article = Article {
+        name: Some("Support".to_string()),
+        ..Default::default()
+    };
+
+    let first = client.articles().create(&article).await.unwrap();
+    let numbered = client
+        .articles()
+        .create(&Article {
+            article_number: Some("ART-9".to_string()),
+            ..article.clone()
+        })
+        .await
+        .unwrap();
+    assert_eq!(first.article_number.as_deref(), Some("1"));
+    assert_eq!(numbered.article_number.as_deref(), Some("ART-9"));
+    assert_eq!(fake.articles().len(), 2);
+    assert_eq!(fake.request_count().await, 2);
+}
@@ -0,0 +1,121 @@
+//! Integration tests for dependent creates with `Graph`.
+
+mod mock_server;
+
+use mock_server::MockApi;
+use mockito::Matcher;
+use spiris::graph::Graph;
+use spiris::{Customer, CustomerId, Error, Invoice, InvoiceId, Supplier};
+
+fn id_of(id: Option<impl Into<String>>) -> spiris::error::Result<String> {
+    id.map(Into::into)
+        .ok_or_else(|| Error::InvalidRequest("no id in response".into()))
+}
+
+fn onboarding() -> Graph {
+    Graph::new()
+        .node("customer", &[], |client, _| async move {
+            let customer = Customer {
+                name: Some("Acme AB".to_string()),
+                ..Default::default()
+            };
+            id_of(client.customers().create(&customer).await?.id)
+        })
+        .node("supplier", &[], |client, _| async move {
+            let supplier = Supplier {
+                name: Some("Parts AB".to_string()),
+                ..Default::default()
+            };
+            id_of(client.suppliers().create(&supplier).await?.id)
+        })
+        .node("invoice", &["customer"], |client, ids| async move {
+            let invoice = Invoice {
+                customer_id: Some(CustomerId::from(&ids["customer"])),
+                ..Default::default()
+            };
+            id_of(client.invoices().create(&invoice).await?.id)
+        })
+        .node("send", &["invoice"], |client, ids| async move {
+            client
+                .invoices()
+                .send_einvoice(&InvoiceId::from(&ids["invoice"]))
+                .await?;
+            Ok(String::new())
+        })
+}
+
+#[tokio::test]
+async fn test_graph_passes_ids_to_dependents() {
+    let mut api = MockApi::new().await;
+    api.mock_post("/customers", r#"{"Id": "cust-1", "Name": "Acme AB"}"#);
+    api.mock_post("/suppliers", r#"{"Id": "sup-1", "Name": "Parts AB"}"#);
+    let invoice = api
+        .server
+        .mock("POST", "/customerinvoices")
+        .match_body(Matcher::PartialJson(
+            serde_json::json!({"CustomerId": "cust-1"}),
+        ))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "inv-1", "CustomerId": "cust-1", "Rows": []}"#)
+        .create();
+    let send = api
+        .server
+        .mock("POST", "/customerinvoices/inv-1/einvoice")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body("null")
+        .create();
+
+    let report = onboarding().run(&api.client).await.unwrap();
+
+    invoice.assert();
+    send.assert();
+    assert!(report.is_ok(), "{:?}", report);
+    assert_eq!(report.output("invoice"), Some("inv-1"));
+    assert_eq!(report.output("supplier"), Some("sup-1"));
+    assert_eq!(report.completed.last().unwrap().0, "send");
+}
+
+#[tokio::test]
+async fn test_graph_reports_failed_and_skipped_nodes() {
+    let mut api = MockApi::new().await;
+    api.mock_post("/customers", r#"{"Id": "cust-1", "Name": "Acme AB"}"#);
+    api.mock_post("/suppliers", r#"{"Id": "sup-1", "Name": "Parts AB"}"#);
+    api.mock_error(
+        "POST",
+        "/customerinvoices",
+        400,
+        r#"{"Message": "Rows are required"}"#,
+    );
+    let send = api
+        .server
+        .mock("POST", Matcher::Regex("einvoice".to_string()))
+        .expect(0)
+        .create();
+
+    let report = onboarding().run(&api.client).await.unwrap();
+
+    send.assert();
+    assert!(!report.is_ok());
+    assert_eq!(report.output("customer"), Some("cust-1"));
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].node, "invoice");
+    assert!(matches!(
+        report.failed[0].error.root(),
+        Error::ApiError { status_code: 400, response, .. } if response.message == "Rows are required"
+    ));
+    assert_eq!(report.skipped, ["send"]);
+}
+
+#[tokio::test]
+async fn test_graph_rejects_cycles_before_running() {
+    let api = MockApi::new().await;
+    let result = Graph::new()
+        .node("a", &["b"], |_, _| async { Ok(String::new()) })
+        .node("b", &["a"], |_, _| async { Ok(String::new()) })
+        .run(&api.client)
+        .await;
+
+    assert!(matches!(result, Err(Error::InvalidRequest(_))));
+}
@@ -0,0 +1,79 @@
+//! Integration tests for the image cache.
+
+#![cfg(feature = "image-cache")]
+
+mod mock_server;
+
+use mock_server::MockApi;
+use spiris::image_cache::ImageCache;
+
+const PNG: &[u8] = &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+fn cache_dir(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("spiris-{}-{}", name, std::process::id()))
+}
+
+#[tokio::test]
+async fn test_logotype_is_downloaded_once() {
+    let mut api = MockApi::new().await;
+    let logo = api
+        .server
+        .mock("GET", "/logotypes/logo-1/content")
+        .with_status(200)
+        .with_header("content-type", "image/png")
+        .with_body(PNG)
+        .expect(1)
+        .create();
+    let attachment = api
+        .server
+        .mock("GET", "/attachments/att-1/content")
+        .with_status(200)
+        .with_body(PNG)
+        .expect(1)
+        .create();
+
+    let dir = cache_dir("image-cache");
+    let cache = ImageCache::new(&dir);
+
+    let first = cache.logotype(&api.client, "logo-1").await.unwrap();
+    let second = cache.logotype(&api.client, "logo-1").await.unwrap();
+    assert_eq!(first.content, PNG);
+    assert_eq!(second.path, first.path);
+
+    // The same image under another resource shares the stored file
+    let same = cache.attachment(&api.client, "att-1").await.unwrap();
+    assert_eq!(same.path, first.path);
+    assert_eq!(std::fs::read_dir(dir.join("blobs")).unwrap().count(), 1);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+    logo.assert();
+    attachment.assert();
+}
+
+#[tokio::test]
+async fn test_invalidated_or_corrupt_images_are_downloaded_again() {
+    let mut api = MockApi::new().await;
+    let logo = api
+        .server
+        .mock("GET", "/logotypes/logo-1/content")
+        .with_status(200)
+        .with_body(PNG)
+        .expect(3)
+        .create();
+
+    let dir = cache_dir("image-cache-stale");
+    let cache = ImageCache::new(&dir);
+
+    let image = cache.logotype(&api.client, "logo-1").await.unwrap();
+    cache.invalidate("logotypes/logo-1").await.unwrap();
+    assert!(cache.get("logotypes/logo-1").await.is_none());
+    cache.logotype(&api.client, "logo-1").await.unwrap();
+
+    std::fs::write(&image.path, b"truncated").unwrap();
+    assert!(cache.get("logotypes/logo-1").await.is_none());
+    let image = cache.logotype(&api.client, "logo-1").await.unwrap();
+    assert_eq!(std::fs::read(&image.path).unwrap(), PNG);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+    logo.assert();
+}
@@ -1,11 +1,15 @@
-// Skip these tests when decimal feature is enabled (uses f64 literals)
-#![cfg(not(feature = "decimal"))]
 //! Integration tests for the Invoices endpoint.
 
 mod mock_server;
 
+use chrono::NaiveDate;
 use mock_server::MockApi;
-use spiris::{Invoice, InvoicePayment, InvoiceRow, PaginationParams};
+use mockito::Matcher;
+use spiris::disputes;
+use spiris::{
+    money, CreateInvoiceOptions, Error, Invoice, InvoicePatch, InvoicePayment, InvoiceRow,
+    PaginationParams, SendInvoiceOptions,
+};
 
 #[tokio::test]
 async fn test_list_invoices() {
@@ -48,9 +52,9 @@ async fn test_list_invoices() {
 
     mock.assert();
     assert_eq!(result.data.len(), 2);
-    assert_eq!(result.data[0].id, Some("inv-001".to_string()));
+    assert_eq!(result.data[0].id, Some("inv-001".into()));
     assert_eq!(result.data[0].invoice_number, Some("1001".to_string()));
-    assert_eq!(result.data[0].total_amount, Some(10000.0));
+    assert_eq!(result.data[0].total_amount, Some(money!(10000.0)));
 }
 
 #[tokio::test]
@@ -80,12 +84,12 @@ async fn test_get_invoice() {
 
     let mock = api.mock_get("/customerinvoices/inv-123", response_body);
 
-    let result = api.client.invoices().get("inv-123").await.unwrap();
+    let result = api.client.invoices().get(&"inv-123".into()).await.unwrap();
 
     mock.assert();
-    assert_eq!(result.id, Some("inv-123".to_string()));
+    assert_eq!(result.id, Some("inv-123".into()));
     assert_eq!(result.invoice_number, Some("2001".to_string()));
-    assert_eq!(result.total_amount_including_vat, Some(18750.0));
+    assert_eq!(result.total_amount_including_vat, Some(money!(18750.0)));
     assert_eq!(result.rows.len(), 1);
     assert_eq!(result.rows[0].text, Some("Consulting services".to_string()));
 }
@@ -117,12 +121,12 @@ async fn test_create_invoice() {
     let mock = api.mock_post("/customerinvoices", response_body);
 
     let new_invoice = Invoice {
-        customer_id: Some("cust-001".to_string()),
+        customer_id: Some("cust-001".into()),
         currency_code: Some("SEK".to_string()),
         rows: vec![InvoiceRow {
             text: Some("Product A".to_string()),
-            unit_price: Some(200.0),
-            quantity: Some(10.0),
+            unit_price: Some(money!(200.0)),
+            quantity: Some(money!(10.0)),
             ..Default::default()
         }],
         ..Default::default()
@@ -131,9 +135,9 @@ async fn test_create_invoice() {
     let result = api.client.invoices().create(&new_invoice).await.unwrap();
 
     mock.assert();
-    assert_eq!(result.id, Some("inv-new-001".to_string()));
+    assert_eq!(result.id, Some("inv-new-001".into()));
     assert_eq!(result.invoice_number, Some("3001".to_string()));
-    assert_eq!(result.total_amount, Some(2000.0));
+    assert_eq!(result.total_amount, Some(money!(2000.0)));
 }
 
 #[tokio::test]
@@ -153,7 +157,7 @@ async fn test_update_invoice() {
     let mock = api.mock_put("/customerinvoices/inv-123", response_body);
 
     let updated_invoice = Invoice {
-        id: Some("inv-123".to_string()),
+        id: Some("inv-123".into()),
         remarks: Some("Updated remarks".to_string()),
         ..Default::default()
     };
@@ -161,7 +165,7 @@ async fn test_update_invoice() {
     let result = api
         .client
         .invoices()
-        .update("inv-123", &updated_invoice)
+        .update(&"inv-123".into(), &updated_invoice)
         .await
         .unwrap();
 
@@ -169,13 +173,36 @@ async fn test_update_invoice() {
     assert_eq!(result.remarks, Some("Updated remarks".to_string()));
 }
 
+#[tokio::test]
+async fn test_update_invoice_partial() {
+    let mut api = MockApi::new().await;
+
+    let mock = api.mock_patch_with_body(
+        "/customerinvoices/inv-123",
+        r#"{"DueDate": "2025-04-30", "Remarks": null}"#,
+        r#"{"Id": "inv-123", "DueDate": "2025-04-30T00:00:00", "Rows": []}"#,
+    );
+
+    let due = NaiveDate::from_ymd_opt(2025, 4, 30).unwrap();
+    let patch = InvoicePatch::new().due_date(due).clear_remarks();
+    let result = api
+        .client
+        .invoices()
+        .update_partial(&"inv-123".into(), &patch)
+        .await
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(result.due_date, Some(due));
+}
+
 #[tokio::test]
 async fn test_delete_invoice() {
     let mut api = MockApi::new().await;
 
     let mock = api.mock_delete("/customerinvoices/inv-123");
 
-    let result = api.client.invoices().delete("inv-123").await;
+    let result = api.client.invoices().delete(&"inv-123".into()).await;
 
     mock.assert();
     assert!(result.is_ok());
@@ -196,7 +223,7 @@ async fn test_register_invoice_payment() {
         .create();
 
     let payment = InvoicePayment {
-        amount: Some(10000.0),
+        amount: Some(money!(10000.0)),
         payment_date: None,
         bank_account_id: Some("bank-001".to_string()),
         ..Default::default()
@@ -205,7 +232,7 @@ async fn test_register_invoice_payment() {
     let result = api
         .client
         .invoices()
-        .register_payment("inv-123", &payment)
+        .register_payment(&"inv-123".into(), &payment)
         .await;
 
     mock.assert();
@@ -219,12 +246,152 @@ async fn test_get_invoice_pdf() {
     let pdf_content = b"%PDF-1.4 fake pdf content";
     let mock = api.mock_get_bytes("/customerinvoices/inv-123/pdf", pdf_content);
 
-    let result = api.client.invoices().get_pdf("inv-123").await.unwrap();
+    let result = api
+        .client
+        .invoices()
+        .get_pdf(&"inv-123".into())
+        .await
+        .unwrap();
 
     mock.assert();
     assert_eq!(result, pdf_content.to_vec());
 }
 
+#[tokio::test]
+async fn test_invoice_pdf_bytes() {
+    let mut api = MockApi::new().await;
+
+    let pdf_content = b"%PDF-1.4 fake pdf content";
+    let mock = api.mock_get_bytes("/customerinvoices/inv-123/pdf", pdf_content);
+
+    let pdf = api.client.invoices().pdf(&"inv-123".into()).await.unwrap();
+
+    mock.assert();
+    assert_eq!(&pdf[..], pdf_content);
+}
+
+#[tokio::test]
+async fn test_download_pdf_to_file() {
+    let mut api = MockApi::new().await;
+
+    let pdf_content = b"%PDF-1.4 fake pdf content";
+    let mock = api.mock_get_bytes("/customerinvoices/inv-123/pdf", pdf_content);
+    let path = std::env::temp_dir().join(format!("spiris-invoice-{}.pdf", std::process::id()));
+
+    api.client
+        .invoices()
+        .download_pdf_to(&"inv-123".into(), &path)
+        .await
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(std::fs::read(&path).unwrap(), pdf_content);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_download_pdf_to_keeps_existing_file_on_error() {
+    let mut api = MockApi::new().await;
+
+    let _mock = api.mock_error("GET", "/customerinvoices/inv-123/pdf", 500, "Unavailable");
+    let dir = std::env::temp_dir().join(format!("spiris-pdf-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("invoice.pdf");
+    std::fs::write(&path, b"previous").unwrap();
+
+    let result = api
+        .client
+        .invoices()
+        .download_pdf_to(&"inv-123".into(), &path)
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(std::fs::read(&path).unwrap(), b"previous");
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_download_pdf_to_reports_write_failure_as_io_error() {
+    let mut api = MockApi::new().await;
+
+    let _mock = api.mock_get_bytes("/customerinvoices/inv-123/pdf", b"%PDF-1.4");
+    let path = std::env::temp_dir()
+        .join(format!("spiris-missing-{}", std::process::id()))
+        .join("invoice.pdf");
+
+    let err = api
+        .client
+        .invoices()
+        .download_pdf_to(&"inv-123".into(), &path)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(&err, Error::Io(e) if e.kind() == std::io::ErrorKind::NotFound),
+        "{:?}",
+        err
+    );
+    assert!(!err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_send_invoice_by_email() {
+    let mut api = MockApi::new().await;
+
+    let mock = api
+        .server
+        .mock("POST", "/customerinvoices/inv-123/email")
+        .match_body(Matcher::Json(serde_json::json!({
+            "EmailAddress": "ap@customer.example",
+            "SendCopyToSender": true
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body("null")
+        .create();
+
+    let options = SendInvoiceOptions::email()
+        .recipient("ap@customer.example")
+        .copy_to_self(true);
+    api.client
+        .invoices()
+        .send(&"inv-123".into(), &options)
+        .await
+        .unwrap();
+
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_send_invoice_as_einvoice() {
+    let mut api = MockApi::new().await;
+
+    let mock = api
+        .server
+        .mock("POST", "/customerinvoices/inv-123/einvoice")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body("null")
+        .create();
+
+    let invoices = api.client.invoices();
+    invoices
+        .send(&"inv-123".into(), &SendInvoiceOptions::einvoice())
+        .await
+        .unwrap();
+    let err = invoices
+        .send(
+            &"inv-123".into(),
+            &SendInvoiceOptions::einvoice().copy_to_self(true),
+        )
+        .await
+        .unwrap_err();
+
+    mock.assert();
+    assert!(matches!(err, Error::InvalidRequest(_)));
+}
+
 #[tokio::test]
 async fn test_send_einvoice() {
     let mut api = MockApi::new().await;
@@ -239,7 +406,7 @@ async fn test_send_einvoice() {
         .with_body("null")
         .create();
 
-    let result = api.client.invoices().send_einvoice("inv-123").await;
+    let result = api.client.invoices().send_einvoice(&"inv-123".into()).await;
 
     mock.assert();
     assert!(result.is_ok());
@@ -276,3 +443,123 @@ async fn test_list_invoices_with_pagination() {
     assert_eq!(result.meta.page_size, 25);
     assert_eq!(result.meta.total_count, 100);
 }
+
+#[tokio::test]
+async fn test_place_and_release_hold() {
+    let mut api = MockApi::new().await;
+
+    let get_mock = api.mock_get(
+        "/customerinvoices/inv-123",
+        r#"{"Id": "inv-123", "Remarks": "Order 55", "Rows": []}"#,
+    );
+    let put_mock = api
+        .server
+        .mock("PUT", "/customerinvoices/inv-123")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "Remarks": "[DISPUTED] Damaged goods\nOrder 55"
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"Id": "inv-123", "Remarks": "[DISPUTED] Damaged goods\nOrder 55", "Rows": []}"#,
+        )
+        .expect(1)
+        .create();
+
+    let hold = disputes::InvoiceHold::disputed("Damaged goods");
+    let invoice = disputes::place_hold(&api.client, &"inv-123".into(), &hold)
+        .await
+        .unwrap();
+
+    assert_eq!(invoice.hold(), Some(hold));
+
+    // The mocked invoice has no hold, so releasing sends no update
+    let released = disputes::release_hold(&api.client, &"inv-123".into())
+        .await
+        .unwrap();
+    assert!(!released.is_held());
+    get_mock.expect(2).assert();
+    put_mock.assert();
+}
+
+#[tokio::test]
+async fn test_create_with_generated_ocr() {
+    let mut api = MockApi::new().await;
+    let create = api
+        .server
+        .mock("POST", "/customerinvoices")
+        .match_body(Matcher::PartialJson(
+            serde_json::json!({"CustomerId": "cust-001"}),
+        ))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"Id": "inv-new", "InvoiceNumber": "10042", "CustomerId": "cust-001", "Rows": []}"#,
+        )
+        .create();
+    let update = api
+        .server
+        .mock("PUT", "/customerinvoices/inv-new")
+        .match_body(Matcher::PartialJson(
+            serde_json::json!({"OcrNumber": "1004274"}),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"Id": "inv-new", "InvoiceNumber": "10042", "OcrNumber": "1004274", "Rows": []}"#,
+        )
+        .create();
+
+    let invoice = Invoice {
+        customer_id: Some("cust-001".into()),
+        rows: vec![InvoiceRow {
+            text: Some("Consulting".to_string()),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let created = api
+        .client
+        .invoices()
+        .create_with(&invoice, &CreateInvoiceOptions::new().generate_ocr(true))
+        .await
+        .unwrap();
+
+    create.assert();
+    update.assert();
+    assert!(created.is_complete());
+    assert_eq!(created.invoice.ocr_number.as_deref(), Some("1004274"));
+}
+
+#[tokio::test]
+async fn test_create_with_returns_created_invoice_when_ocr_update_fails() {
+    let mut api = MockApi::new().await;
+    let _create = api
+        .server
+        .mock("POST", "/customerinvoices")
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "inv-new", "InvoiceNumber": "10042", "Rows": []}"#)
+        .create();
+    let _update = api.mock_error(
+        "PUT",
+        "/customerinvoices/inv-new",
+        400,
+        r#"{"Message": "Invoice is locked"}"#,
+    );
+
+    let created = api
+        .client
+        .invoices()
+        .create_with(
+            &Invoice::default(),
+            &CreateInvoiceOptions::new().generate_ocr(true),
+        )
+        .await
+        .unwrap();
+
+    assert!(!created.is_complete());
+    assert_eq!(created.invoice.id.as_deref(), Some("inv-new"));
+    assert!(created.invoice.ocr_number.is_none());
+    assert_eq!(created.ocr_error.unwrap().status_code(), Some(400));
+}
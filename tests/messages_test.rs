@@ -0,0 +1,85 @@
+//! Integration tests for the Messages endpoint and notes.
+
+mod mock_server;
+
+use mock_server::MockApi;
+use mockito::Matcher;
+use spiris::NoteTarget;
+
+#[tokio::test]
+async fn test_add_note_to_customer() {
+    let mut api = MockApi::new().await;
+
+    let mock = api
+        .server
+        .mock("POST", "/messagethreads")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "Subject": "CRM",
+            "DocumentType": 1,
+            "DocumentId": "cust-1",
+            "Messages": [{"Content": "Prefers e-invoices"}]
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"Id": "thread-1", "Subject": "CRM", "DocumentType": 1, "DocumentId": "cust-1"}"#,
+        )
+        .create();
+
+    let target = NoteTarget::Customer("cust-1".to_string());
+    let thread = api
+        .client
+        .messages()
+        .add_note(&target, "CRM", "Prefers e-invoices")
+        .await
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(thread.id.as_deref(), Some("thread-1"));
+    assert!(thread.is_about(&target));
+}
+
+#[tokio::test]
+async fn test_notes_for_invoice() {
+    let mut api = MockApi::new().await;
+
+    let mock = api
+        .server
+        .mock("GET", "/messagethreads")
+        .match_query(Matcher::UrlEncoded(
+            "filter".to_string(),
+            "(DocumentId eq 'inv-1') and (DocumentType eq 2)".to_string(),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+            "Data": [
+                {"Id": "t2", "DocumentType": 2, "DocumentId": "inv-1", "CreatedUtc": "2025-03-02T00:00:00Z",
+                 "Messages": [{"Content": "Customer called"}]},
+                {"Id": "t1", "DocumentType": 2, "DocumentId": "inv-1", "CreatedUtc": "2025-03-01T00:00:00Z"},
+                {"Id": "t3", "DocumentType": 1, "DocumentId": "inv-1"}
+            ],
+            "Meta": {
+                "CurrentPage": 0, "PageSize": 500, "TotalPages": 1,
+                "TotalCount": 3, "HasNextPage": false, "HasPreviousPage": false
+            }
+        }"#,
+        )
+        .create();
+
+    let notes = api
+        .client
+        .messages()
+        .notes(&NoteTarget::Invoice("inv-1".to_string()))
+        .await
+        .unwrap();
+
+    mock.assert();
+    let ids: Vec<_> = notes.iter().filter_map(|t| t.id.as_deref()).collect();
+    assert_eq!(ids, ["t1", "t2"]);
+    assert_eq!(
+        notes[1].messages[0].content.as_deref(),
+        Some("Customer called")
+    );
+}
@@ -301,6 +301,18 @@ impl MockApi {
             .create()
     }
 
+    /// Mock a GET request regardless of its query string (pagination, filters).
+    pub fn mock_get_any_query(&mut self, path: &str, response_body: &str) -> Mock {
+        self.server
+            .mock("GET", path)
+            .match_query(Matcher::Any)
+            .match_header("Authorization", "Bearer test_token")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(response_body)
+            .create()
+    }
+
     pub fn mock_get_with_query(
         &mut self,
         path: &str,
@@ -376,6 +388,23 @@ impl MockApi {
             .create()
     }
 
+    pub fn mock_patch_with_body(
+        &mut self,
+        path: &str,
+        request_body: &str,
+        response_body: &str,
+    ) -> Mock {
+        self.server
+            .mock("PATCH", path)
+            .match_header("Authorization", "Bearer test_token")
+            .match_header("Content-Type", "application/json")
+            .match_body(Matcher::Json(serde_json::from_str(request_body).unwrap()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(response_body)
+            .create()
+    }
+
     pub fn mock_delete(&mut self, path: &str) -> Mock {
         self.server
             .mock("DELETE", path)
@@ -883,7 +912,7 @@ pub mod fixtures {
 
     pub fn customer(id: u32) -> Customer {
         Customer {
-            id: Some(format!("cust-{:03}", id)),
+            id: Some(format!("cust-{:03}", id).into()),
             customer_number: Some(format!("{}", 1000 + id)),
             name: Some(format!("Test Customer {}", id)),
             email: Some(format!("customer{}@test.com", id)),
@@ -906,7 +935,7 @@ pub mod fixtures {
 
     pub fn article(id: u32) -> Article {
         Article {
-            id: Some(format!("art-{:03}", id)),
+            id: Some(format!("art-{:03}", id).into()),
             article_number: Some(format!("ART-{}", id)),
             name: Some(format!("Test Article {}", id)),
             sales_price: Some(super::money(100.0 * id as f64)),
@@ -917,9 +946,9 @@ pub mod fixtures {
 
     pub fn invoice(id: u32, customer_id: &str) -> Invoice {
         Invoice {
-            id: Some(format!("inv-{:03}", id)),
+            id: Some(format!("inv-{:03}", id).into()),
             invoice_number: Some(format!("{}", 2000 + id)),
-            customer_id: Some(customer_id.to_string()),
+            customer_id: Some(customer_id.into()),
             total_amount: Some(super::money(1000.0 * id as f64)),
             rows: vec![invoice_row(1)],
             ..Default::default()
@@ -992,12 +1021,12 @@ mod tests {
     #[test]
     fn test_fixtures() {
         let customer = fixtures::customer(1);
-        assert_eq!(customer.id, Some("cust-001".to_string()));
+        assert_eq!(customer.id, Some("cust-001".into()));
 
         let customers = fixtures::customers(3);
         assert_eq!(customers.len(), 3);
 
         let invoice = fixtures::invoice(1, "cust-001");
-        assert_eq!(invoice.customer_id, Some("cust-001".to_string()));
+        assert_eq!(invoice.customer_id, Some("cust-001".into()));
     }
 }
@@ -1,53 +1,48 @@
 //! Tests for monetary value handling in the API client.
 //!
-//! Currently, the library uses f64 for monetary values.
-//! These tests document the current behavior and potential precision issues.
-//!
-//! Note: When the `decimal` feature is enabled, these tests are skipped
-//! and dedicated decimal tests in decimal_test.rs are run instead.
-
-// These tests are specific to f64 behavior
-#![cfg(not(feature = "decimal"))]
+//! Amounts are [`Money`]: `Decimal` with the default `decimal` feature and
+//! `f64` without it. These tests run against whichever is enabled; the
+//! f64 precision tests document why `Decimal` is the default.
 
 mod mock_server;
 
 use mock_server::MockApi;
-use spiris::{Article, Invoice, InvoiceRow};
+use spiris::{money, Article, Invoice, InvoiceRow, Money};
 
 // =============================================================================
-// Current f64 Behavior Tests
+// Money Field Tests
 // =============================================================================
 
 #[test]
-fn test_invoice_total_amount_f64() {
+fn test_invoice_total_amount() {
     let invoice = Invoice {
-        total_amount: Some(1234.56),
+        total_amount: Some(money!(1234.56)),
         ..Default::default()
     };
 
-    assert_eq!(invoice.total_amount, Some(1234.56));
+    assert_eq!(invoice.total_amount, Some(money!(1234.56)));
 }
 
 #[test]
-fn test_invoice_row_unit_price_f64() {
+fn test_invoice_row_unit_price() {
     let row = InvoiceRow {
-        unit_price: Some(99.99),
-        quantity: Some(2.0),
+        unit_price: Some(money!(99.99)),
+        quantity: Some(money!(2.0)),
         ..Default::default()
     };
 
-    assert_eq!(row.unit_price, Some(99.99));
-    assert_eq!(row.quantity, Some(2.0));
+    assert_eq!(row.unit_price, Some(money!(99.99)));
+    assert_eq!(row.quantity, Some(money!(2.0)));
 }
 
 #[test]
-fn test_article_sales_price_f64() {
+fn test_article_sales_price() {
     let article = Article {
-        sales_price: Some(49.95),
+        sales_price: Some(money!(49.95)),
         ..Default::default()
     };
 
-    assert_eq!(article.sales_price, Some(49.95));
+    assert_eq!(article.sales_price, Some(money!(49.95)));
 }
 
 // =============================================================================
@@ -83,47 +78,47 @@ fn test_f64_multiplication_precision() {
 }
 
 #[test]
-fn test_f64_large_amount() {
+fn test_large_amount() {
     // Large amounts (millions) should work without issues
     let invoice = Invoice {
-        total_amount: Some(1_000_000.00),
+        total_amount: Some(money!(1000000.00)),
         ..Default::default()
     };
 
-    assert_eq!(invoice.total_amount, Some(1_000_000.00));
+    assert_eq!(invoice.total_amount, Some(money!(1000000.00)));
 }
 
 #[test]
-fn test_f64_small_amount() {
+fn test_small_amount() {
     // Small amounts (fractions of cents)
     let row = InvoiceRow {
-        unit_price: Some(0.001),
-        quantity: Some(1000.0),
+        unit_price: Some(money!(0.001)),
+        quantity: Some(money!(1000.0)),
         ..Default::default()
     };
 
-    assert_eq!(row.unit_price, Some(0.001));
+    assert_eq!(row.unit_price, Some(money!(0.001)));
 }
 
 #[test]
-fn test_f64_zero_amount() {
+fn test_zero_amount() {
     let invoice = Invoice {
-        total_amount: Some(0.0),
+        total_amount: Some(money!(0.0)),
         ..Default::default()
     };
 
-    assert_eq!(invoice.total_amount, Some(0.0));
+    assert_eq!(invoice.total_amount, Some(money!(0.0)));
 }
 
 #[test]
-fn test_f64_negative_amount() {
+fn test_negative_amount() {
     // Credits or refunds might be negative
     let row = InvoiceRow {
-        total_amount: Some(-100.00),
+        total_amount: Some(money!(-100.00)),
         ..Default::default()
     };
 
-    assert_eq!(row.total_amount, Some(-100.00));
+    assert_eq!(row.total_amount, Some(money!(-100.00)));
 }
 
 // =============================================================================
@@ -133,8 +128,8 @@ fn test_f64_negative_amount() {
 #[test]
 fn test_invoice_amount_serialization() {
     let invoice = Invoice {
-        id: Some("inv-001".to_string()),
-        total_amount: Some(1234.56),
+        id: Some("inv-001".into()),
+        total_amount: Some(money!(1234.56)),
         ..Default::default()
     };
 
@@ -150,7 +145,7 @@ fn test_invoice_amount_deserialization() {
 
     let invoice: Invoice = serde_json::from_str(json).unwrap();
 
-    assert_eq!(invoice.total_amount, Some(1234.56));
+    assert_eq!(invoice.total_amount, Some(money!(1234.56)));
 }
 
 #[test]
@@ -160,7 +155,7 @@ fn test_amount_deserialization_from_integer() {
 
     let invoice: Invoice = serde_json::from_str(json).unwrap();
 
-    assert_eq!(invoice.total_amount, Some(1000.0));
+    assert_eq!(invoice.total_amount, Some(money!(1000.0)));
 }
 
 #[test]
@@ -201,13 +196,13 @@ async fn test_invoice_with_amount_from_api() {
 
     let _mock = api.mock_get("/customerinvoices/inv-001", invoice_json);
 
-    let result = api.client.invoices().get("inv-001").await;
+    let result = api.client.invoices().get(&"inv-001".into()).await;
     assert!(result.is_ok());
 
     let invoice = result.unwrap();
-    assert_eq!(invoice.total_amount, Some(999.99));
-    assert_eq!(invoice.total_vat_amount, Some(200.00));
-    assert_eq!(invoice.total_amount_including_vat, Some(1199.99));
+    assert_eq!(invoice.total_amount, Some(money!(999.99)));
+    assert_eq!(invoice.total_vat_amount, Some(money!(200.00)));
+    assert_eq!(invoice.total_amount_including_vat, Some(money!(1199.99)));
 }
 
 #[tokio::test]
@@ -225,12 +220,12 @@ async fn test_article_with_prices_from_api() {
 
     let _mock = api.mock_get("/articles/art-001", article_json);
 
-    let result = api.client.articles().get("art-001").await;
+    let result = api.client.articles().get(&"art-001".into()).await;
     assert!(result.is_ok());
 
     let article = result.unwrap();
-    assert_eq!(article.sales_price, Some(149.99));
-    assert_eq!(article.purchase_price, Some(75.50));
+    assert_eq!(article.sales_price, Some(money!(149.99)));
+    assert_eq!(article.purchase_price, Some(money!(75.50)));
 }
 
 #[tokio::test]
@@ -260,35 +255,35 @@ async fn test_invoice_list_with_amounts() {
 
     let invoices = result.unwrap();
     assert_eq!(invoices.data.len(), 3);
-    assert_eq!(invoices.data[0].total_amount, Some(100.00));
-    assert_eq!(invoices.data[1].total_amount, Some(200.50));
-    assert_eq!(invoices.data[2].total_amount, Some(300.99));
+    assert_eq!(invoices.data[0].total_amount, Some(money!(100.00)));
+    assert_eq!(invoices.data[1].total_amount, Some(money!(200.50)));
+    assert_eq!(invoices.data[2].total_amount, Some(money!(300.99)));
 }
 
 // =============================================================================
-// Amount Calculation Tests (Current f64 Behavior)
+// Amount Calculation Tests
 // =============================================================================
 
 #[test]
 fn test_calculate_line_total() {
     let row = InvoiceRow {
-        unit_price: Some(25.00),
-        quantity: Some(4.0),
+        unit_price: Some(money!(25.00)),
+        quantity: Some(money!(4.0)),
         ..Default::default()
     };
 
     if let (Some(price), Some(qty)) = (row.unit_price, row.quantity) {
         let calculated_total = price * qty;
-        assert_eq!(calculated_total, 100.00);
+        assert_eq!(calculated_total, money!(100.00));
     }
 }
 
 #[test]
 fn test_calculate_with_discount() {
     let row = InvoiceRow {
-        unit_price: Some(100.00),
-        quantity: Some(1.0),
-        discount_percentage: Some(10.0),
+        unit_price: Some(money!(100.00)),
+        quantity: Some(money!(1.0)),
+        discount_percentage: Some(money!(10.0)),
         ..Default::default()
     };
 
@@ -296,33 +291,33 @@ fn test_calculate_with_discount() {
         (row.unit_price, row.quantity, row.discount_percentage)
     {
         let subtotal = price * qty;
-        let discount_amount = subtotal * (discount / 100.0);
+        let discount_amount = subtotal * (discount / money!(100));
         let total = subtotal - discount_amount;
 
-        assert_eq!(total, 90.00);
+        assert_eq!(total, money!(90.00));
     }
 }
 
 #[test]
 fn test_sum_invoice_rows() {
-    let rows = vec![
+    let rows = [
         InvoiceRow {
-            total_amount: Some(100.00),
+            total_amount: Some(money!(100.00)),
             ..Default::default()
         },
         InvoiceRow {
-            total_amount: Some(200.00),
+            total_amount: Some(money!(200.00)),
             ..Default::default()
         },
         InvoiceRow {
-            total_amount: Some(50.50),
+            total_amount: Some(money!(50.50)),
             ..Default::default()
         },
     ];
 
-    let total: f64 = rows.iter().filter_map(|r| r.total_amount).sum();
+    let total: Money = rows.iter().filter_map(|r| r.total_amount).sum();
 
-    assert!((total - 350.50).abs() < 0.0001);
+    assert_eq!(total, money!(350.50));
 }
 
 // =============================================================================
@@ -336,10 +331,10 @@ fn test_amount_with_many_decimal_places() {
 
     let invoice: Invoice = serde_json::from_str(json).unwrap();
 
-    // f64 preserves the precision
+    // Both representations keep the precision
     assert!(invoice.total_amount.is_some());
     let amount = invoice.total_amount.unwrap();
-    assert!((amount - 123.456789).abs() < 0.0000001);
+    assert!((amount - money!(123.456789)).abs() < money!(0.0000001));
 }
 
 #[test]
@@ -349,7 +344,7 @@ fn test_amount_scientific_notation() {
 
     let invoice: Invoice = serde_json::from_str(json).unwrap();
 
-    assert_eq!(invoice.total_amount, Some(1_500_000.0));
+    assert_eq!(invoice.total_amount, Some(money!(1500000.0)));
 }
 
 // =============================================================================
@@ -359,7 +354,7 @@ fn test_amount_scientific_notation() {
 #[test]
 fn test_very_large_amount() {
     let invoice = Invoice {
-        total_amount: Some(999_999_999.99),
+        total_amount: Some(money!(999999999.99)),
         ..Default::default()
     };
 
@@ -372,55 +367,10 @@ fn test_very_large_amount() {
 #[test]
 fn test_very_small_positive_amount() {
     let row = InvoiceRow {
-        unit_price: Some(0.01), // 1 cent
-        quantity: Some(1.0),
+        unit_price: Some(money!(0.01)), // 1 cent
+        quantity: Some(money!(1.0)),
         ..Default::default()
     };
 
-    assert_eq!(row.unit_price, Some(0.01));
+    assert_eq!(row.unit_price, Some(money!(0.01)));
 }
-
-// =============================================================================
-// Future Decimal Type Tests (Documentation)
-// =============================================================================
-//
-// Once rust_decimal is implemented, these tests should be enabled:
-//
-// #[test]
-// fn test_decimal_precise_calculation() {
-//     use rust_decimal::Decimal;
-//     use std::str::FromStr;
-//
-//     let price = Decimal::from_str("19.99").unwrap();
-//     let qty = Decimal::from_str("3").unwrap();
-//     let total = price * qty;
-//
-//     assert_eq!(total, Decimal::from_str("59.97").unwrap());
-//     // No floating-point precision issues!
-// }
-//
-// #[test]
-// fn test_decimal_serialization() {
-//     let row = InvoiceRow {
-//         unit_price: Some(Decimal::from_str("99.99").unwrap()),
-//         quantity: Some(Decimal::from_str("2.5").unwrap()),
-//         ..Default::default()
-//     };
-//
-//     let json = serde_json::to_string(&row).unwrap();
-//     // Should serialize as string or precise number
-//     assert!(json.contains("99.99"));
-// }
-//
-// #[test]
-// fn test_decimal_addition_precise() {
-//     use rust_decimal::Decimal;
-//     use std::str::FromStr;
-//
-//     let a = Decimal::from_str("0.1").unwrap();
-//     let b = Decimal::from_str("0.2").unwrap();
-//     let sum = a + b;
-//
-//     // Unlike f64, this is exactly 0.3
-//     assert_eq!(sum, Decimal::from_str("0.3").unwrap());
-// }
@@ -1,11 +1,9 @@
-// Skip these tests when decimal feature is enabled (uses f64 literals)
-#![cfg(not(feature = "decimal"))]
 //! Integration tests for the Orders endpoint.
 
 mod mock_server;
 
 use mock_server::MockApi;
-use spiris::{Order, OrderRow};
+use spiris::{money, Order, OrderRow};
 
 #[tokio::test]
 async fn test_list_orders() {
@@ -96,12 +94,12 @@ async fn test_create_order() {
     let mock = api.mock_post("/orders", response_body);
 
     let new_order = Order {
-        customer_id: Some("cust-001".to_string()),
+        customer_id: Some("cust-001".into()),
         currency_code: Some("SEK".to_string()),
         rows: vec![OrderRow {
             text: Some("Product".to_string()),
-            unit_price: Some(200.0),
-            quantity: Some(10.0),
+            unit_price: Some(money!(200.0)),
+            quantity: Some(money!(10.0)),
             ..Default::default()
         }],
         ..Default::default()
@@ -139,7 +137,7 @@ async fn test_update_order() {
         .unwrap();
 
     mock.assert();
-    assert_eq!(result.total_amount, Some(20000.0));
+    assert_eq!(result.total_amount, Some(money!(20000.0)));
 }
 
 #[tokio::test]
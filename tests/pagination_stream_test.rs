@@ -196,8 +196,49 @@ async fn test_manual_pagination_all_pages() {
     }
 
     assert_eq!(all_customers.len(), 5);
-    assert_eq!(all_customers[0].id, Some("cust-001".to_string()));
-    assert_eq!(all_customers[4].id, Some("cust-005".to_string()));
+    assert_eq!(all_customers[0].id, Some("cust-001".into()));
+    assert_eq!(all_customers[4].id, Some("cust-005".into()));
+}
+
+#[tokio::test]
+async fn test_next_params_walks_all_pages() {
+    let mut api = MockApi::new().await;
+    let pages = [
+        fixtures::customers(2),
+        vec![fixtures::customer(3), fixtures::customer(4)],
+        vec![fixtures::customer(5)],
+    ];
+    let mut mocks = Vec::new();
+    for (page, customers) in pages.iter().enumerate() {
+        let data = serde_json::to_string(customers).unwrap();
+        let body = format!(
+            r#"{{"Data": {}, {}}}"#,
+            data,
+            meta_json(page as u32, 2, 3, 5)
+        );
+        let page = page.to_string();
+        mocks.push(api.mock_get_with_query(
+            "/customers",
+            vec![("page", page.as_str()), ("pagesize", "2")],
+            &body,
+        ));
+    }
+
+    let mut all_customers = Vec::new();
+    let mut params = Some(PaginationParams::new().page(0).pagesize(2));
+    while let Some(current) = params {
+        let result = api.client.customers().list(Some(current)).await.unwrap();
+        assert_eq!(result.total_count(), 5);
+        assert_eq!(result.total_pages(), 3);
+        params = result.next_params();
+        assert_eq!(params.is_some(), result.has_next_page());
+        all_customers.extend(result.data);
+    }
+
+    assert_eq!(all_customers.len(), 5);
+    for mock in mocks {
+        mock.assert();
+    }
 }
 
 // =============================================================================
@@ -376,3 +417,432 @@ fn test_pagination_params_chaining() {
 //     page1_mock.assert();
 //     page2_mock.assert_not_called();
 // }
+
+// =============================================================================
+// Stream Prefetch Tests
+// =============================================================================
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_stream_with_prefetch_yields_pages_in_order() {
+    use futures::TryStreamExt;
+    use mockito::Matcher;
+    use spiris::pagination::StreamOptions;
+
+    let mut api = MockApi::new().await;
+    let pages = [
+        vec![fixtures::customer(1), fixtures::customer(2)],
+        vec![fixtures::customer(3), fixtures::customer(4)],
+        vec![fixtures::customer(5)],
+    ];
+    let mut mocks = Vec::new();
+    for (page, customers) in pages.iter().enumerate() {
+        let data = serde_json::to_string(customers).unwrap();
+        let body = format!(
+            r#"{{"Data": {}, {}}}"#,
+            data,
+            meta_json(page as u32, 2, 3, 5)
+        );
+        mocks.push(
+            api.server
+                .mock("GET", "/customers")
+                .match_query(Matcher::AllOf(vec![
+                    Matcher::UrlEncoded("page".into(), page.to_string()),
+                    Matcher::UrlEncoded("pagesize".into(), "2".into()),
+                ]))
+                .with_header("content-type", "application/json")
+                .with_body(body)
+                .expect(1)
+                .create(),
+        );
+    }
+
+    let customers: Vec<_> = api
+        .client
+        .customers()
+        .list_stream_with(StreamOptions::new().page_size(2).prefetch(2))
+        .try_collect()
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = customers.iter().filter_map(|c| c.id.as_deref()).collect();
+    assert_eq!(
+        ids,
+        vec!["cust-001", "cust-002", "cust-003", "cust-004", "cust-005"]
+    );
+    for mock in mocks {
+        mock.assert();
+    }
+}
+
+// =============================================================================
+// Filtered Stream Tests
+// =============================================================================
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_stream_with_query_follows_pages() {
+    use futures::TryStreamExt;
+    use mockito::Matcher;
+    use spiris::pagination::StreamOptions;
+    use spiris::QueryParams;
+
+    let mut api = MockApi::new().await;
+    let pages = [
+        vec![fixtures::customer(1), fixtures::customer(2)],
+        vec![fixtures::customer(3)],
+    ];
+    let mut mocks = Vec::new();
+    for (page, customers) in pages.iter().enumerate() {
+        let data = serde_json::to_string(customers).unwrap();
+        let body = format!(
+            r#"{{"Data": {}, {}}}"#,
+            data,
+            meta_json(page as u32, 2, 2, 3)
+        );
+        mocks.push(
+            api.server
+                .mock("GET", "/customers")
+                .match_query(Matcher::AllOf(vec![
+                    Matcher::UrlEncoded("filter".into(), "IsActive eq true".into()),
+                    Matcher::UrlEncoded("page".into(), page.to_string()),
+                    Matcher::UrlEncoded("pagesize".into(), "2".into()),
+                ]))
+                .with_header("content-type", "application/json")
+                .with_body(body)
+                .expect(1)
+                .create(),
+        );
+    }
+
+    let query = QueryParams::new().filter("IsActive eq true");
+    let customers: Vec<_> = api
+        .client
+        .customers()
+        .stream_with(query, StreamOptions::new().page_size(2))
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(customers.len(), 3);
+    for mock in mocks {
+        mock.assert();
+    }
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_invoice_stream_stops_on_error() {
+    use futures::StreamExt;
+    use spiris::QueryParams;
+
+    let mut api = MockApi::new().await;
+    let _mock = api
+        .server
+        .mock("GET", "/customerinvoices")
+        .match_query(mockito::Matcher::Any)
+        .with_status(400)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"ErrorCode": 4000, "DeveloperErrorMessage": "Invalid filter"}"#)
+        .create();
+
+    let results: Vec<_> = api
+        .client
+        .invoices()
+        .stream(QueryParams::new().filter("Nonsense"))
+        .collect()
+        .await;
+
+    assert_eq!(results.len(), 1);
+    assert!(results[0].is_err());
+}
+
+// =============================================================================
+// list_all Tests
+// =============================================================================
+
+fn mock_pages(
+    api: &mut MockApi,
+    path: &str,
+    pages: &[String],
+    filter: Option<&str>,
+) -> Vec<mockito::Mock> {
+    use mockito::Matcher;
+
+    pages
+        .iter()
+        .enumerate()
+        .map(|(page, data)| {
+            let body = format!(
+                r#"{{"Data": {}, {}}}"#,
+                data,
+                meta_json(page as u32, 2, pages.len() as u32, 5)
+            );
+            let mut query = vec![
+                Matcher::UrlEncoded("page".into(), page.to_string()),
+                Matcher::UrlEncoded("pagesize".into(), "2".into()),
+            ];
+            if let Some(filter) = filter {
+                query.push(Matcher::UrlEncoded("filter".into(), filter.into()));
+            }
+            api.server
+                .mock("GET", path)
+                .match_query(Matcher::AllOf(query))
+                .with_header("content-type", "application/json")
+                .with_body(body)
+                .expect(1)
+                .create()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_list_all_follows_pages_with_query_and_scope() {
+    use spiris::{ListAllOptions, ListScope, QueryParams};
+
+    let mut api = MockApi::new().await;
+    let pages = [
+        serde_json::to_string(&[fixtures::customer(1), fixtures::customer(2)]).unwrap(),
+        serde_json::to_string(&[fixtures::customer(3), fixtures::customer(4)]).unwrap(),
+        serde_json::to_string(&[fixtures::customer(5)]).unwrap(),
+    ];
+    let mocks = mock_pages(
+        &mut api,
+        "/customers",
+        &pages,
+        Some("(contains(Name, 'AB')) and (IsActive eq true)"),
+    );
+
+    let query = QueryParams::new()
+        .filter("contains(Name, 'AB')")
+        .scope(ListScope::ActiveOnly);
+    let customers = api
+        .client
+        .customers()
+        .list_all_with(Some(query), ListAllOptions::new().page_size(2))
+        .await
+        .unwrap();
+
+    assert_eq!(customers.len(), 5);
+    assert_eq!(customers[4].id.as_deref(), Some("cust-005"));
+    for mock in mocks {
+        mock.assert();
+    }
+}
+
+#[tokio::test]
+async fn test_list_all_stops_at_max_items() {
+    use spiris::{Error, ListAllOptions};
+
+    let mut api = MockApi::new().await;
+    let pages = [
+        serde_json::to_string(&[fixtures::customer(1), fixtures::customer(2)]).unwrap(),
+        serde_json::to_string(&[fixtures::customer(3), fixtures::customer(4)]).unwrap(),
+        serde_json::to_string(&[fixtures::customer(5)]).unwrap(),
+    ];
+    let mut mocks = mock_pages(&mut api, "/customers", &pages, None);
+    let last_page = mocks.pop().unwrap();
+
+    let result = api
+        .client
+        .customers()
+        .list_all_with(None, ListAllOptions::new().page_size(2).max_items(4))
+        .await;
+
+    assert!(matches!(
+        result.map_err(Error::into_root),
+        Err(Error::InvalidRequest(message)) if message.contains("more than 4 items")
+    ));
+    for mock in mocks {
+        mock.assert();
+    }
+    // The page past the limit is never requested
+    assert!(!last_page.matched());
+}
+
+#[tokio::test]
+async fn test_list_all_on_macro_endpoint() {
+    let mut api = MockApi::new().await;
+    let _mock = api
+        .server
+        .mock("GET", "/units")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "pagesize".into(),
+            "500".into(),
+        ))
+        .with_header("content-type", "application/json")
+        .with_body(format!(
+            r#"{{"Data": [{{"Id": "u1", "Name": "Hour"}}], {}}}"#,
+            meta_json(0, 500, 1, 1)
+        ))
+        .create();
+
+    let units = api.client.units().list_all(None).await.unwrap();
+
+    assert_eq!(units.len(), 1);
+    assert_eq!(units[0].name.as_deref(), Some("Hour"));
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_list_all_concurrent_keeps_page_order() {
+    use mockito::Matcher;
+    use spiris::{ListScope, QueryParams};
+
+    let mut api = MockApi::new().await;
+    let mocks: Vec<_> = (0..4u32)
+        .map(|page| {
+            let customers = [
+                fixtures::customer(page * 2 + 1),
+                fixtures::customer(page * 2 + 2),
+            ];
+            let body = format!(
+                r#"{{"Data": {}, {}}}"#,
+                serde_json::to_string(&customers).unwrap(),
+                meta_json(page, 500, 4, 8)
+            );
+            api.server
+                .mock("GET", "/customers")
+                .match_query(Matcher::AllOf(vec![
+                    Matcher::UrlEncoded("page".into(), page.to_string()),
+                    Matcher::UrlEncoded("pagesize".into(), "500".into()),
+                    Matcher::UrlEncoded("filter".into(), "IsActive eq true".into()),
+                ]))
+                .with_header("content-type", "application/json")
+                .with_body(body)
+                .expect(1)
+                .create()
+        })
+        .collect();
+
+    let customers = api
+        .client
+        .customers()
+        .list_all_concurrent(Some(QueryParams::new().scope(ListScope::ActiveOnly)), 2)
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = customers.iter().filter_map(|c| c.id.as_deref()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            "cust-001", "cust-002", "cust-003", "cust-004", "cust-005", "cust-006", "cust-007",
+            "cust-008"
+        ]
+    );
+    for mock in mocks {
+        mock.assert();
+    }
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_list_all_concurrent_fails_on_page_error() {
+    use mockito::Matcher;
+
+    let mut api = MockApi::new().await;
+    let _first = api
+        .server
+        .mock("GET", "/customers")
+        .match_query(Matcher::UrlEncoded("page".into(), "0".into()))
+        .with_header("content-type", "application/json")
+        .with_body(format!(
+            r#"{{"Data": {}, {}}}"#,
+            fixtures::customers_json(2),
+            meta_json(0, 500, 3, 6)
+        ))
+        .create();
+    let _rest = api
+        .server
+        .mock("GET", "/customers")
+        .match_query(Matcher::Regex("page=[12]".into()))
+        .with_status(404)
+        .create();
+
+    let result = api.client.customers().list_all_concurrent(None, 4).await;
+
+    assert!(matches!(
+        result.unwrap_err().root(),
+        spiris::Error::NotFound(_)
+    ));
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_list_all_streaming_reads_items_from_chunked_pages() {
+    use futures::TryStreamExt;
+    use mockito::Matcher;
+
+    let mut api = MockApi::new().await;
+    let mocks: Vec<_> = (0..2u32)
+        .map(|page| {
+            let customers = [
+                fixtures::customer(page * 2 + 1),
+                fixtures::customer(page * 2 + 2),
+            ];
+            let body = format!(
+                r#"{{"Data": {}, {}}}"#,
+                serde_json::to_string(&customers).unwrap(),
+                meta_json(page, 500, 2, 4)
+            );
+            api.server
+                .mock("GET", "/customers")
+                .match_query(Matcher::AllOf(vec![
+                    Matcher::UrlEncoded("page".into(), page.to_string()),
+                    Matcher::UrlEncoded("pagesize".into(), "500".into()),
+                ]))
+                .with_header("content-type", "application/json")
+                .with_chunked_body(move |writer| {
+                    for chunk in body.as_bytes().chunks(16) {
+                        writer.write_all(chunk)?;
+                    }
+                    Ok(())
+                })
+                .expect(1)
+                .create()
+        })
+        .collect();
+
+    let customers: Vec<_> = api
+        .client
+        .customers()
+        .list_all_streaming(None)
+        .try_collect()
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = customers.iter().filter_map(|c| c.id.as_deref()).collect();
+    assert_eq!(ids, vec!["cust-001", "cust-002", "cust-003", "cust-004"]);
+    for mock in mocks {
+        mock.assert();
+    }
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_list_all_streaming_yields_items_before_bad_one() {
+    use futures::StreamExt;
+
+    let mut api = MockApi::new().await;
+    let _mock = api.mock_get_any_query(
+        "/customers",
+        &format!(
+            r#"{{"Data": [{{"Id": "cust-001"}}, {{"Id": 42}}], {}}}"#,
+            meta_json(0, 500, 1, 2)
+        ),
+    );
+
+    let results: Vec<_> = api
+        .client
+        .customers()
+        .list_all_streaming(None)
+        .collect()
+        .await;
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap().id.as_deref(), Some("cust-001"));
+    assert!(matches!(
+        results[1].as_ref().unwrap_err().root(),
+        spiris::Error::Json(_)
+    ));
+}
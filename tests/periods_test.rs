@@ -0,0 +1,45 @@
+//! Integration tests for fiscal year and period locks.
+
+mod mock_server;
+
+use chrono::NaiveDate;
+use mock_server::MockApi;
+use spiris::periods::PeriodLocks;
+
+const FISCAL_YEARS: &str = r#"{
+    "Data": [
+        {"Id": "fy-2025", "StartDate": "2025-01-01T00:00:00Z", "EndDate": "2025-12-31T00:00:00Z", "IsLocked": false},
+        {"Id": "fy-2024", "StartDate": "2024-01-01T00:00:00Z", "EndDate": "2024-12-31T00:00:00Z", "IsLocked": true}
+    ],
+    "Meta": {
+        "CurrentPage": 0, "PageSize": 500, "TotalPages": 1,
+        "TotalCount": 2, "HasNextPage": false, "HasPreviousPage": false
+    }
+}"#;
+
+#[tokio::test]
+async fn test_load_period_locks() {
+    let mut api = MockApi::new().await;
+    api.mock_get_any_query("/fiscalyears", FISCAL_YEARS);
+    api.mock_get(
+        "/companysettings",
+        r#"{"CompanyName": "Testbolaget AB", "LockedThroughDate": "2025-03-31T00:00:00Z"}"#,
+    );
+
+    let locks = PeriodLocks::load(&api.client).await.unwrap();
+
+    assert_eq!(locks.fiscal_years[0].id.as_deref(), Some("fy-2024"));
+    assert_eq!(locks.locked_through, NaiveDate::from_ymd_opt(2025, 3, 31));
+
+    let periods = locks.periods(&locks.fiscal_years[1]);
+    assert_eq!(periods.iter().filter(|p| p.is_locked).count(), 3);
+
+    let date = NaiveDate::from_ymd_opt(2025, 3, 15).unwrap();
+    assert!(matches!(
+        locks.check(date),
+        Err(spiris::Error::InvalidRequest(_))
+    ));
+    assert!(locks
+        .check(NaiveDate::from_ymd_opt(2025, 4, 1).unwrap())
+        .is_ok());
+}
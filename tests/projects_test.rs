@@ -64,7 +64,7 @@ async fn test_get_project() {
     mock.assert();
     assert_eq!(result.id, Some("proj-123".to_string()));
     assert_eq!(result.name, Some("Enterprise Integration".to_string()));
-    assert_eq!(result.customer_id, Some("cust-456".to_string()));
+    assert_eq!(result.customer_id, Some("cust-456".into()));
 }
 
 #[tokio::test]
@@ -10,7 +10,9 @@
 mod mock_server;
 
 use mock_server::{fixtures, meta_json, MockApi};
-use spiris::{query::Filter, PaginationParams, QueryParams};
+use spiris::fields::CustomerField;
+use spiris::query::{Direction, Filter};
+use spiris::{PaginationParams, PaymentStatus, QueryParams};
 
 // =============================================================================
 // QueryParams Builder Tests
@@ -95,6 +97,13 @@ fn test_query_params_filter_by_string() {
     assert_eq!(params.filter, Some("Name eq 'Acme Corp'".to_string()));
 }
 
+#[test]
+fn test_query_params_filter_by_status() {
+    let params =
+        QueryParams::new().filter_by(Filter::field("PaymentStatus").ne(&PaymentStatus::Paid));
+    assert_eq!(params.filter, Some("PaymentStatus ne 'Paid'".to_string()));
+}
+
 #[test]
 fn test_query_params_filter_by_combined() {
     let filter = Filter::field("IsActive")
@@ -375,6 +384,38 @@ async fn test_invoice_list_pagination() {
     assert!(response.meta.has_previous_page);
 }
 
+#[tokio::test]
+async fn test_customer_search_sends_typed_orderby_and_select() {
+    let mut api = MockApi::new().await;
+
+    let customers = vec![fixtures::customer(1)];
+    let data = serde_json::to_string(&customers).unwrap();
+    let meta = meta_json(0, 50, 1, 1);
+    let response = format!(r#"{{"Data": {}, {}}}"#, data, meta);
+
+    let mock = api
+        .server
+        .mock("GET", "/customers")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("orderby".into(), "Name asc,CreatedUtc desc".into()),
+            mockito::Matcher::UrlEncoded("select".into(), "Id,Name".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(&response)
+        .create_async()
+        .await;
+
+    let query = QueryParams::new()
+        .order_by(CustomerField::Name, Direction::Ascending)
+        .order_by(CustomerField::CreatedUtc, Direction::Descending)
+        .select_fields([CustomerField::Id, CustomerField::Name]);
+    let result = api.client.customers().search(query, None).await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(result.data.len(), 1);
+}
+
 // =============================================================================
 // Edge Cases
 // =============================================================================
@@ -0,0 +1,116 @@
+//! Integration tests for the Quotations endpoint and the quote-to-invoice flow.
+
+mod mock_server;
+
+use mock_server::MockApi;
+use spiris::{Quotation, QuotationRow};
+
+#[tokio::test]
+async fn test_list_quotes() {
+    let mut api = MockApi::new().await;
+
+    let response_body = r#"{
+        "Data": [
+            {"Id": "q-001", "QuotationNumber": "501", "CustomerId": "cust-001", "Status": 1},
+            {"Id": "q-002", "QuotationNumber": "502", "CustomerId": "cust-002", "Status": 2}
+        ],
+        "Meta": {
+            "CurrentPage": 0, "PageSize": 50, "TotalPages": 1,
+            "TotalCount": 2, "HasNextPage": false, "HasPreviousPage": false
+        }
+    }"#;
+
+    let mock = api.mock_get("/quotations", response_body);
+
+    let result = api.client.quotes().list(None).await.unwrap();
+
+    mock.assert();
+    assert_eq!(result.data.len(), 2);
+    assert_eq!(result.data[1].quotation_number, Some("502".to_string()));
+}
+
+#[tokio::test]
+async fn test_create_quote() {
+    let mut api = MockApi::new().await;
+
+    let mock = api.mock_post(
+        "/quotations",
+        r#"{"Id": "q-100", "QuotationNumber": "600", "CustomerId": "cust-001", "Rows": [{"Text": "Design work"}]}"#,
+    );
+
+    let quote = Quotation {
+        customer_id: Some("cust-001".into()),
+        rows: vec![QuotationRow {
+            text: Some("Design work".to_string()),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let created = api.client.quotes().create(&quote).await.unwrap();
+
+    mock.assert();
+    assert_eq!(created.id, Some("q-100".to_string()));
+    assert_eq!(created.rows.len(), 1);
+}
+
+#[tokio::test]
+async fn test_accept_quote() {
+    let mut api = MockApi::new().await;
+
+    let mock = api.mock_post(
+        "/quotations/q-001/accept",
+        r#"{"Id": "q-001", "Status": 2}"#,
+    );
+
+    let quote = api.client.quotes().accept("q-001").await.unwrap();
+
+    mock.assert();
+    assert_eq!(quote.status, Some(2));
+}
+
+#[tokio::test]
+async fn test_convert_quote_to_order_then_invoice_draft() {
+    let mut api = MockApi::new().await;
+
+    let quote_mock = api.mock_post(
+        "/quotations/q-001/convert/order",
+        r#"{"Id": "ord-001", "OrderNumber": "1001", "CustomerId": "cust-001"}"#,
+    );
+    let order_mock = api.mock_post(
+        "/orders/ord-001/convert",
+        r#"{"Id": "draft-001", "CustomerId": "cust-001"}"#,
+    );
+
+    let order = api.client.quotes().convert_to_order("q-001").await.unwrap();
+    let draft = api
+        .client
+        .orders()
+        .convert_to_invoice_draft(order.id.as_deref().unwrap())
+        .await
+        .unwrap();
+
+    quote_mock.assert();
+    order_mock.assert();
+    assert_eq!(draft.id, Some("draft-001".to_string()));
+    assert_eq!(draft.customer_id, Some("cust-001".into()));
+}
+
+#[tokio::test]
+async fn test_convert_quote_to_invoice_draft() {
+    let mut api = MockApi::new().await;
+
+    let mock = api.mock_post(
+        "/quotations/q-001/convert/invoicedraft",
+        r#"{"Id": "draft-002", "CustomerId": "cust-001"}"#,
+    );
+
+    let draft = api
+        .client
+        .quotations()
+        .convert_to_invoice_draft("q-001")
+        .await
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(draft.id, Some("draft-002".to_string()));
+}
@@ -6,8 +6,8 @@
 //! - Implements backoff strategies
 //! - Provides appropriate error information
 //!
-//! Note: The library currently doesn't implement automatic rate limit handling.
-//! These tests document the expected behavior and verify error propagation.
+//! `MockApi` disables retries, so most of these tests see the 429 itself;
+//! `test_retries_after_retry_after_and_records_quota` covers the retry.
 
 mod mock_server;
 
@@ -17,7 +17,7 @@ use spiris::{ApiErrorResponse, Error};
 fn api_error(status_code: u16, message: &str) -> Error {
     Error::ApiError {
         status_code,
-        response: ApiErrorResponse::from_raw(message.to_string()),
+        response: Box::new(ApiErrorResponse::from_raw(message.to_string())),
         raw_body: message.to_string(),
     }
 }
@@ -32,12 +32,17 @@ async fn test_rate_limit_returns_429_error() {
 
     let _mock = api.mock_rate_limit("/customers", 60);
 
-    let result = api.client.customers().list(None).await;
+    let result = api
+        .client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     assert!(result.is_err(), "Should fail on rate limit");
 
     match result {
-        Err(Error::RateLimitExceeded(_)) => {
+        Err(Error::RateLimitExceeded { .. }) => {
             // Expected - dedicated rate limit error
         }
         Err(Error::ApiError {
@@ -66,11 +71,16 @@ async fn test_rate_limit_on_customer_create() {
         ..Default::default()
     };
 
-    let result = api.client.customers().create(&customer).await;
+    let result = api
+        .client
+        .customers()
+        .create(&customer)
+        .await
+        .map_err(Error::into_root);
 
     assert!(result.is_err(), "Should fail on rate limit");
     match result {
-        Err(Error::RateLimitExceeded(_))
+        Err(Error::RateLimitExceeded { .. })
         | Err(Error::ApiError {
             status_code: 429, ..
         }) => {}
@@ -100,7 +110,7 @@ async fn test_rate_limit_on_article_get() {
         r#"{"Message": "Too Many Requests"}"#,
     );
 
-    let result = api.client.articles().get("art-001").await;
+    let result = api.client.articles().get(&"art-001".into()).await;
 
     assert!(result.is_err(), "Should fail on rate limit");
 }
@@ -147,6 +157,47 @@ async fn test_rate_limit_long_retry_after() {
     assert!(result.is_err());
 }
 
+#[tokio::test]
+async fn test_retries_after_retry_after_and_records_quota() {
+    use mock_server::MockResponse;
+    use spiris::clock::{Clock, MockClock};
+    use spiris::{AccessToken, Client, ClientConfig, RetryConfig};
+    use std::time::Duration;
+
+    let mut api = MockApi::new().await;
+    let customer = mock_server::fixtures::customer(1);
+    let customer_json = serde_json::to_string(&customer).unwrap();
+    let _mocks = api.mock_get_sequence(
+        "/customers/cust-001",
+        vec![
+            MockResponse::rate_limit(20).with_header("X-RateLimit-Remaining", "0"),
+            MockResponse::ok(&customer_json)
+                .with_header("X-RateLimit-Limit", "600")
+                .with_header("X-RateLimit-Remaining", "599"),
+        ],
+    );
+
+    let start = chrono::Utc::now();
+    let clock = MockClock::new(start);
+    let config = ClientConfig::new()
+        .base_url(api.server.url())
+        .clock(clock.clone())
+        .retry_config(RetryConfig::new().initial_interval(Duration::from_millis(500)));
+    let client = Client::with_config(
+        AccessToken::new("test_token".to_string(), 3600, None),
+        config,
+    );
+
+    let fetched = client.customers().get(&"cust-001".into()).await.unwrap();
+
+    assert_eq!(fetched.id, customer.id);
+    // Waited the 20s the API asked for, not the 500ms backoff.
+    assert_eq!(clock.elapsed_since(start), Duration::from_secs(20));
+    let status = client.rate_limit_status().unwrap();
+    assert_eq!(status.limit, Some(600));
+    assert_eq!(status.remaining, Some(599));
+}
+
 // =============================================================================
 // Multiple Endpoint Rate Limiting Tests
 // =============================================================================
@@ -208,10 +259,15 @@ async fn test_rate_limit_error_contains_message() {
         r#"{"Message": "API rate limit exceeded. Please slow down."}"#,
     );
 
-    let result = api.client.customers().list(None).await;
+    let result = api
+        .client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     match result {
-        Err(Error::RateLimitExceeded(msg)) => {
+        Err(Error::RateLimitExceeded { message: msg, .. }) => {
             assert!(
                 msg.contains("rate") || msg.contains("Rate") || msg.contains("limit"),
                 "Error message should mention rate limiting: {}",
@@ -255,11 +311,11 @@ async fn test_recovery_after_rate_limit_sequence() {
     );
 
     // First call - rate limited
-    let result1 = api.client.customers().get("cust-001").await;
+    let result1 = api.client.customers().get(&"cust-001".into()).await;
     assert!(result1.is_err(), "First call should be rate limited");
 
     // Second call - success
-    let result2 = api.client.customers().get("cust-001").await;
+    let result2 = api.client.customers().get(&"cust-001".into()).await;
     assert!(result2.is_ok(), "Second call should succeed");
 }
 
@@ -285,12 +341,12 @@ async fn test_multiple_rate_limits_then_success() {
 
     // First three calls fail
     for i in 1..=3 {
-        let result = api.client.customers().get("cust-001").await;
+        let result = api.client.customers().get(&"cust-001".into()).await;
         assert!(result.is_err(), "Call {} should be rate limited", i);
     }
 
     // Fourth call succeeds
-    let result = api.client.customers().get("cust-001").await;
+    let result = api.client.customers().get(&"cust-001".into()).await;
     assert!(result.is_ok(), "Fourth call should succeed");
 }
 
@@ -302,7 +358,10 @@ async fn test_multiple_rate_limits_then_success() {
 fn test_rate_limit_error_is_retryable() {
     use spiris::retry::is_retryable_error;
 
-    let error = Error::RateLimitExceeded("Rate limit exceeded".to_string());
+    let error = Error::RateLimitExceeded {
+        message: "Rate limit exceeded".to_string(),
+        retry_after: None,
+    };
     assert!(
         is_retryable_error(&error),
         "Rate limit errors should be retryable"
@@ -358,6 +417,47 @@ async fn test_rate_limit_behavior_documentation() {
     // - Verify success after rate limit clears
 }
 
+// =============================================================================
+// Client-Side Rate Limiter Tests
+// =============================================================================
+
+#[cfg(feature = "rate-limit")]
+#[tokio::test]
+async fn test_client_rate_limiter_is_shared_by_clones() {
+    use spiris::{AccessToken, Client, ClientConfig, RateLimitConfig, RetryConfig};
+    use std::time::{Duration, Instant};
+
+    let mut api = MockApi::new().await;
+    let customer = serde_json::to_string(&mock_server::fixtures::customer(1)).unwrap();
+    let _mock = api
+        .server
+        .mock("GET", "/customers/cust-001")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(&customer)
+        .expect(3)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new()
+        .base_url(api.server.url())
+        .retry_config(RetryConfig::new().max_retries(0))
+        .rate_limit_config(RateLimitConfig::new(600).per_second(2));
+    let client = Client::with_config(
+        AccessToken::new("test_token".to_string(), 3600, None),
+        config,
+    );
+    let clone = client.clone();
+
+    let started = Instant::now();
+    client.customers().get(&"cust-001".into()).await.unwrap();
+    clone.customers().get(&"cust-001".into()).await.unwrap();
+    // Both tokens of the second are spent, across the two clones.
+    client.customers().get(&"cust-001".into()).await.unwrap();
+
+    assert!(started.elapsed() >= Duration::from_millis(400));
+}
+
 // =============================================================================
 // Edge Cases
 // =============================================================================
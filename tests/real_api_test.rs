@@ -5,17 +5,27 @@
 //!
 //! To run these tests:
 //! 1. Set the SPIRIS_ACCESS_TOKEN environment variable with a valid token
-//! 2. Run: cargo test --test real_api_test -- --ignored
+//! 2. Optionally set SPIRIS_ENVIRONMENT=sandbox to call the sandbox API, and
+//!    SPIRIS_API_VERSION (e.g. `v3`) to try another API version
+//! 3. Run: cargo test --test real_api_test -- --ignored
 //!
 //! WARNING: These tests may create/modify real data. Use a test account!
 
-use spiris::{AccessToken, Client, Customer, PaginationParams};
+use spiris::{AccessToken, Client, ClientConfig, Customer, Environment, PaginationParams};
 use std::env;
 
 fn get_client() -> Option<Client> {
     let token_str = env::var("SPIRIS_ACCESS_TOKEN").ok()?;
     let token = AccessToken::new(token_str, 3600, None);
-    Some(Client::new(token))
+
+    let mut config = ClientConfig::new();
+    if env::var("SPIRIS_ENVIRONMENT").is_ok_and(|e| e.eq_ignore_ascii_case("sandbox")) {
+        config = config.environment(Environment::Sandbox);
+    }
+    if let Ok(version) = env::var("SPIRIS_API_VERSION") {
+        config = config.api_version(version);
+    }
+    Some(Client::with_config(token, config))
 }
 
 // =============================================================================
@@ -219,7 +229,11 @@ async fn test_real_api_get_company_settings() {
 async fn test_real_api_get_nonexistent_customer() {
     let client = get_client().expect("SPIRIS_ACCESS_TOKEN not set");
 
-    let result = client.customers().get("nonexistent-id-12345").await;
+    let result = client
+        .customers()
+        .get(&"nonexistent-id-12345".into())
+        .await
+        .map_err(spiris::Error::into_root);
 
     match result {
         Ok(_) => {
@@ -241,7 +255,11 @@ async fn test_real_api_expired_token() {
     let token = AccessToken::new("invalid_token_12345".to_string(), 3600, None);
     let client = Client::new(token);
 
-    let result = client.customers().list(None).await;
+    let result = client
+        .customers()
+        .list(None)
+        .await
+        .map_err(spiris::Error::into_root);
 
     match result {
         Ok(_) => {
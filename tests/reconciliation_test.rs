@@ -0,0 +1,130 @@
+//! Integration tests for bank file reconciliation.
+
+#![cfg(feature = "bank-files")]
+
+mod mock_server;
+
+use mock_server::{money, MockApi};
+use spiris::{bank_files, reconciliation};
+
+const CAMT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.054.001.02">
+  <BkToCstmrDbtCdtNtfctn>
+    <Ntfctn>
+      <Ntry>
+        <Amt Ccy="SEK">1250.00</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd>
+        <BookgDt><Dt>2025-03-14</Dt></BookgDt>
+        <NtryDtls>
+          <TxDtls>
+            <Refs><AcctSvcrRef>TX-1</AcctSvcrRef></Refs>
+            <RmtInf><Strd><CdtrRefInf><Ref>10014</Ref></CdtrRefInf></Strd></RmtInf>
+          </TxDtls>
+        </NtryDtls>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="SEK">800.00</Amt>
+        <CdtDbtInd>DBIT</CdtDbtInd>
+        <BookgDt><Dt>2025-03-14</Dt></BookgDt>
+        <AcctSvcrRef>TX-2</AcctSvcrRef>
+      </Ntry>
+    </Ntfctn>
+  </BkToCstmrDbtCdtNtfctn>
+</Document>"#;
+
+const INVOICES: &str = r#"{
+    "Data": [
+        {"Id": "inv-1", "InvoiceNumber": "1001", "Rows": [], "OcrNumber": "10014", "RemainingAmount": 1250.0, "CurrencyCode": "SEK"},
+        {"Id": "inv-2", "InvoiceNumber": "1002", "Rows": [], "RemainingAmount": 0.0}
+    ],
+    "Meta": {
+        "CurrentPage": 0, "PageSize": 500, "TotalPages": 1,
+        "TotalCount": 2, "HasNextPage": false, "HasPreviousPage": false
+    }
+}"#;
+
+const SUPPLIER_INVOICES: &str = r#"{
+    "Data": [
+        {"Id": "si-1", "InvoiceNumber": "F-77", "Rows": [], "TotalAmountIncludingVat": 800.0, "IsPaid": false}
+    ],
+    "Meta": {
+        "CurrentPage": 0, "PageSize": 500, "TotalPages": 1,
+        "TotalCount": 1, "HasNextPage": false, "HasPreviousPage": false
+    }
+}"#;
+
+#[tokio::test]
+async fn test_reconcile_camt054_against_open_items() {
+    let mut api = MockApi::new().await;
+    api.mock_get_any_query("/customerinvoices", INVOICES);
+    api.mock_get_any_query("/supplierinvoices", SUPPLIER_INVOICES);
+
+    let transactions = bank_files::parse(CAMT).unwrap();
+    let items = reconciliation::open_items(&api.client).await.unwrap();
+    assert_eq!(items.len(), 2);
+
+    let suggestions = reconciliation::suggest_matches(&transactions, &items);
+    assert_eq!(suggestions.len(), 2);
+    assert_eq!(items[suggestions[0].item].invoice_id, "inv-1");
+    assert!(suggestions[0].confidence > suggestions[1].confidence);
+    assert_eq!(items[suggestions[1].item].invoice_id, "si-1");
+}
+
+#[tokio::test]
+async fn test_register_match_posts_payments() {
+    let mut api = MockApi::new().await;
+    api.mock_get_any_query("/customerinvoices", INVOICES);
+    api.mock_get_any_query("/supplierinvoices", SUPPLIER_INVOICES);
+
+    let customer_payment = api
+        .server
+        .mock("POST", "/customerinvoices/inv-1/payments")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "PaymentDate": "2025-03-14T00:00:00Z",
+            "PaymentReferenceNumber": "TX-1"
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body("null")
+        .create();
+    let supplier_payment = api
+        .server
+        .mock("POST", "/supplierinvoices/si-1/payments")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "SupplierInvoiceId": "si-1",
+            "PaymentReferenceNumber": "TX-2"
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "pay-1"}"#)
+        .create();
+
+    let transactions = bank_files::parse(CAMT).unwrap();
+    let items = reconciliation::open_items(&api.client).await.unwrap();
+    for suggestion in reconciliation::suggest_matches(&transactions, &items) {
+        reconciliation::register_match(
+            &api.client,
+            &transactions[suggestion.transaction],
+            &items[suggestion.item],
+        )
+        .await
+        .unwrap();
+    }
+
+    customer_payment.assert();
+    supplier_payment.assert();
+}
+
+#[tokio::test]
+async fn test_register_match_rejects_overpayment() {
+    let mut api = MockApi::new().await;
+    api.mock_get_any_query("/customerinvoices", INVOICES);
+    api.mock_get_any_query("/supplierinvoices", SUPPLIER_INVOICES);
+
+    let items = reconciliation::open_items(&api.client).await.unwrap();
+    let mut transaction = bank_files::parse(CAMT).unwrap().remove(0);
+    transaction.amount = money(1300.0);
+
+    let result = reconciliation::register_match(&api.client, &transaction, &items[0]).await;
+    assert!(matches!(result, Err(spiris::Error::InvalidRequest(_))));
+}
@@ -0,0 +1,96 @@
+//! Integration tests for redaction of logged bodies and errors.
+
+use mockito::{Matcher, Server};
+use spiris::middleware::{Middleware, RequestContext, ResponseContext};
+use spiris::redact::Redactor;
+use spiris::{AccessToken, Client, ClientConfig, Customer, RetryConfig};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<String>>>);
+
+impl Middleware for Capture {
+    fn on_request(&self, ctx: &mut RequestContext) -> spiris::Result<()> {
+        if let Some(body) = &ctx.body {
+            self.0.lock().unwrap().push(body.clone());
+        }
+        Ok(())
+    }
+
+    fn on_response(&self, ctx: &ResponseContext) {
+        if let Some(error) = &ctx.error {
+            self.0.lock().unwrap().push(error.clone());
+        }
+    }
+}
+
+fn client(server: &Server, capture: &Capture, redactor: Redactor) -> Client {
+    let config = ClientConfig::new()
+        .base_url(server.url())
+        .retry_config(RetryConfig::new().max_retries(0))
+        .middleware(capture.clone())
+        .redactor(redactor);
+    Client::with_config(
+        AccessToken::new("test_token".to_string(), 3600, None),
+        config,
+    )
+}
+
+#[tokio::test]
+async fn test_request_body_is_redacted_for_middleware_only() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/customers")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "Email": "anna@example.com"
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "cust-1"}"#)
+        .create();
+
+    let capture = Capture::default();
+    let customer = Customer {
+        name: Some("Anna Svensson".to_string()),
+        email: Some("anna@example.com".to_string()),
+        ..Default::default()
+    };
+    client(&server, &capture, Redactor::default())
+        .customers()
+        .create(&customer)
+        .await
+        .unwrap();
+
+    mock.assert();
+    let logged = capture.0.lock().unwrap();
+    assert_eq!(logged.len(), 1);
+    assert!(logged[0].contains(r#""Email":"***""#), "{}", logged[0]);
+    assert!(logged[0].contains("Anna Svensson"));
+    assert!(!logged[0].contains("anna@example.com"));
+}
+
+#[tokio::test]
+async fn test_error_body_is_redacted_for_middleware_only() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("GET", "/customers/cust-1")
+        .with_status(404)
+        .with_body(r#"{"Message": "Not found", "BankGiroNumber": "5050-1055"}"#)
+        .create();
+
+    let capture = Capture::default();
+    let err = client(&server, &capture, Redactor::none().field("BankGiroNumber"))
+        .customers()
+        .get(&"cust-1".into())
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("5050-1055"));
+    let logged = capture.0.lock().unwrap();
+    assert_eq!(logged.len(), 1);
+    assert!(
+        logged[0].contains(r#""BankGiroNumber": "***""#),
+        "{}",
+        logged[0]
+    );
+}
@@ -0,0 +1,141 @@
+//! Integration tests for the cached reference data bundle.
+
+mod mock_server;
+
+use mock_server::MockApi;
+use mockito::{Matcher, Mock};
+
+fn page(data: &str) -> String {
+    format!(
+        r#"{{
+    "Data": [{}],
+    "Meta": {{
+        "CurrentPage": 0, "PageSize": 500, "TotalPages": 1,
+        "TotalCount": 1, "HasNextPage": false, "HasPreviousPage": false
+    }}
+}}"#,
+        data
+    )
+}
+
+fn mock_list(api: &mut MockApi, path: &str, data: &str, hits: usize) -> Mock {
+    api.server
+        .mock("GET", path)
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(page(data))
+        .expect(hits)
+        .create()
+}
+
+fn mock_reference_data(api: &mut MockApi, hits: usize) -> Vec<Mock> {
+    vec![
+        mock_list(
+            api,
+            "/accounts",
+            r#"{"AccountNumber": "1930", "Name": "Företagskonto", "IsActive": true}"#,
+            hits,
+        ),
+        mock_list(
+            api,
+            "/vatcodes",
+            r#"{"Id": "vat-25", "Code": "MP1", "Description": "25 %", "VatRate": 0.25}"#,
+            hits,
+        ),
+        mock_list(
+            api,
+            "/units",
+            r#"{"Id": "unit-1", "Code": "st", "Name": "Styck"}"#,
+            hits,
+        ),
+        mock_list(
+            api,
+            "/termsofpayments",
+            r#"{"Id": "top-30", "Name": "30 dagar", "NumberOfDays": 30}"#,
+            hits,
+        ),
+        mock_list(
+            api,
+            "/currencies",
+            r#"{"Code": "EUR", "Name": "Euro"}"#,
+            hits,
+        ),
+        mock_list(
+            api,
+            "/deliveryterms",
+            r#"{"Id": "dt-1", "Code": "FCA", "Name": "Free carrier"}"#,
+            hits,
+        ),
+    ]
+}
+
+#[tokio::test]
+async fn test_load_all_is_cached() {
+    let mut api = MockApi::new().await;
+    let mocks = mock_reference_data(&mut api, 1);
+
+    let data = api.client.reference_data().load_all().await.unwrap();
+    assert_eq!(
+        data.account("1930").unwrap().name.as_deref(),
+        Some("Företagskonto")
+    );
+    assert_eq!(
+        data.vat_code_by_code("MP1").unwrap().id.as_deref(),
+        Some("vat-25")
+    );
+    assert_eq!(
+        data.unit_by_code("st").unwrap().id.as_deref(),
+        Some("unit-1")
+    );
+    assert_eq!(
+        data.terms_of_payment("top-30").unwrap().number_of_days,
+        Some(30)
+    );
+    assert_eq!(data.currency("EUR").unwrap().name.as_deref(), Some("Euro"));
+    assert!(data.delivery_term_by_code("FCA").is_some());
+
+    // Clones of the client share the cache.
+    let again = api
+        .client
+        .clone()
+        .reference_data()
+        .load_all()
+        .await
+        .unwrap();
+    assert!(std::sync::Arc::ptr_eq(&data, &again));
+
+    for mock in mocks {
+        mock.assert();
+    }
+}
+
+#[tokio::test]
+async fn test_refresh_reloads() {
+    let mut api = MockApi::new().await;
+    let mocks = mock_reference_data(&mut api, 2);
+
+    let first = api.client.reference_data().load_all().await.unwrap();
+    let second = api.client.reference_data().refresh().await.unwrap();
+    assert!(!std::sync::Arc::ptr_eq(&first, &second));
+
+    for mock in mocks {
+        mock.assert();
+    }
+}
+
+#[tokio::test]
+async fn test_load_all_fails_if_any_list_fails() {
+    let mut api = MockApi::new().await;
+    let _accounts = mock_list(&mut api, "/accounts", "", 1);
+    let _units = api
+        .server
+        .mock("GET", "/units")
+        .match_query(Matcher::Any)
+        .with_status(500)
+        .with_body("Internal error")
+        .create();
+
+    let result = api.client.reference_data().load_all().await;
+    assert!(result.is_err());
+}
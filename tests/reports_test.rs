@@ -0,0 +1,119 @@
+//! Integration tests for the report builders.
+
+mod mock_server;
+
+use chrono::NaiveDate;
+use mock_server::{meta_json, money, MockApi};
+use spiris::budget::{self, AccountRange, Budget};
+use spiris::reports::{self, PnlSection};
+
+fn page(data: &str) -> String {
+    format!(r#"{{"Data": {}, {}}}"#, data, meta_json(0, 500, 1, 1))
+}
+
+#[tokio::test]
+async fn test_ar_ageing_uses_ledger_and_customer_names() {
+    let mut api = MockApi::new().await;
+
+    let _items = api.mock_get_any_query(
+        "/customerledgeritems",
+        &page(
+            r#"[
+                {"Id": "l1", "CustomerId": "c1", "CustomerInvoiceId": "i1", "Amount": 1000.0},
+                {"Id": "l2", "CustomerId": "c1", "CustomerInvoiceId": "i2", "Amount": 250.0},
+                {"Id": "l3", "CustomerId": "c1", "CustomerInvoiceId": "i2", "Amount": -250.0, "PaymentDate": "2025-04-15T00:00:00Z"},
+                {"Id": "l4", "CustomerId": "c2", "CustomerInvoiceId": "i3", "Amount": 400.0},
+                {"Id": "l5", "CustomerId": "c2", "CustomerInvoiceId": "i3", "Amount": -400.0, "PaymentDate": "2025-01-20T00:00:00Z"}
+            ]"#,
+        ),
+    );
+    let _invoices = api.mock_get_any_query(
+        "/customerinvoices",
+        &page(
+            r#"[
+                {"Id": "i1", "CustomerId": "c1", "InvoiceDate": "2025-02-01T00:00:00Z", "DueDate": "2025-03-01T00:00:00Z", "RemainingAmount": 1000.0, "Rows": []},
+                {"Id": "i2", "CustomerId": "c1", "InvoiceDate": "2024-10-01T00:00:00Z", "DueDate": "2024-11-01T00:00:00Z", "RemainingAmount": 0.0, "Rows": []},
+                {"Id": "i3", "CustomerId": "c2", "InvoiceDate": "2024-12-01T00:00:00Z", "DueDate": "2025-01-01T00:00:00Z", "RemainingAmount": 0.0, "Rows": []}
+            ]"#,
+        ),
+    );
+    let _customers = api.mock_get_any_query(
+        "/customers",
+        &page(r#"[{"Id": "c1", "Name": "Acme AB"}, {"Id": "c2", "Name": "Beta AB"}]"#),
+    );
+
+    let as_of = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+    let report = reports::ar_ageing(&api.client, as_of).await.unwrap();
+
+    assert_eq!(report.rows.len(), 1);
+    assert_eq!(report.rows[0].customer_name.as_deref(), Some("Acme AB"));
+    assert_eq!(report.rows[0].buckets.days_0_30, money(1000.0));
+    // Paid after the report date, so still open at it
+    assert_eq!(report.rows[0].buckets.over_90, money(250.0));
+}
+
+#[tokio::test]
+async fn test_profit_and_loss_at_fiscal_year_start_uses_no_opening_balance() {
+    let mut api = MockApi::new().await;
+
+    let _years = api.mock_get_any_query(
+        "/fiscalyears",
+        &page(r#"[{"Id": "fy1", "StartDate": "2025-01-01T00:00:00Z", "EndDate": "2025-12-31T00:00:00Z"}]"#),
+    );
+    let closing = api.mock_get_any_query(
+        "/accountbalances/2025-01-31",
+        &page(r#"[{"AccountNumber": "3001", "Balance": -5000.0}, {"AccountNumber": "6110", "Balance": 1200.0}]"#),
+    );
+
+    let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+    let pnl = reports::profit_and_loss(&api.client, start, end)
+        .await
+        .unwrap();
+
+    closing.assert();
+    assert_eq!(pnl.section_total(PnlSection::Revenue), money(5000.0));
+    assert_eq!(pnl.net_result, money(3800.0));
+}
+
+#[tokio::test]
+async fn test_budget_vs_actual_uses_profit_and_loss() {
+    let mut api = MockApi::new().await;
+
+    let _years = api.mock_get_any_query(
+        "/fiscalyears",
+        &page(r#"[{"Id": "fy1", "StartDate": "2025-01-01T00:00:00Z", "EndDate": "2025-12-31T00:00:00Z"}]"#),
+    );
+    let _closing = api.mock_get_any_query(
+        "/accountbalances/2025-01-31",
+        &page(r#"[{"AccountNumber": "3001", "Balance": -5000.0}, {"AccountNumber": "6110", "Balance": 1200.0}]"#),
+    );
+
+    let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+    let budget = Budget::new("2025")
+        .month(AccountRange::new(3000, 3999).unwrap(), start, money(6000.0))
+        .month(AccountRange::account(6110), start, money(-1000.0));
+
+    let report = budget::budget_vs_actual(&api.client, &budget, start, end)
+        .await
+        .unwrap();
+
+    assert_eq!(report.lines[0].variance, money(-1000.0));
+    assert_eq!(report.lines[1].variance, money(-200.0));
+    assert_eq!(report.total_budget, money(5000.0));
+    assert_eq!(report.total_actual, money(3800.0));
+}
+
+#[tokio::test]
+async fn test_budget_vs_actual_rejects_partial_months() {
+    let api = MockApi::new().await;
+
+    let budget = Budget::new("2025");
+    let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2025, 1, 20).unwrap();
+
+    assert!(budget::budget_vs_actual(&api.client, &budget, start, end)
+        .await
+        .is_err());
+}
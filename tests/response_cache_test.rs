@@ -0,0 +1,138 @@
+//! Integration tests for the conditional GET response cache.
+
+mod mock_server;
+
+use mock_server::meta_json;
+use mockito::{Matcher, Server};
+use spiris::http_cache::MemoryCache;
+use spiris::{AccessToken, Client, ClientConfig, RetryConfig};
+use std::time::Duration;
+
+fn units_page() -> String {
+    format!(
+        r#"{{"Data": [{{"Id": "unit-1", "Code": "h", "Name": "Hour"}}], {}}}"#,
+        meta_json(0, 50, 1, 1)
+    )
+}
+
+fn client(server: &Server) -> Client {
+    let config = ClientConfig::new()
+        .base_url(server.url())
+        .retry_config(RetryConfig::new().max_retries(0))
+        .response_cache(MemoryCache::new(Duration::from_secs(3600)));
+    Client::with_config(
+        AccessToken::new("test_token".to_string(), 3600, None),
+        config,
+    )
+}
+
+#[tokio::test]
+async fn test_not_modified_is_served_from_cache() {
+    let mut server = Server::new_async().await;
+    let first = server
+        .mock("GET", "/units")
+        .match_header("If-None-Match", Matcher::Missing)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("ETag", "\"v1\"")
+        .with_body(units_page())
+        .expect(1)
+        .create_async()
+        .await;
+    let revalidated = server
+        .mock("GET", "/units")
+        .match_header("If-None-Match", "\"v1\"")
+        .with_status(304)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = client(&server);
+    for _ in 0..3 {
+        let units = client.units().list(None).await.unwrap();
+        assert_eq!(units.data[0].name.as_deref(), Some("Hour"));
+    }
+
+    first.assert_async().await;
+    revalidated.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_last_modified_and_query_are_part_of_the_entry() {
+    let mut server = Server::new_async().await;
+    let _first = server
+        .mock("GET", "/vatcodes")
+        .match_query(Matcher::UrlEncoded("page".into(), "1".into()))
+        .match_header("If-Modified-Since", Matcher::Missing)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("Last-Modified", "Fri, 14 Mar 2025 12:00:00 GMT")
+        .with_body(format!(r#"{{"Data": [], {}}}"#, meta_json(1, 50, 2, 0)))
+        .expect(1)
+        .create_async()
+        .await;
+    let other_page = server
+        .mock("GET", "/vatcodes")
+        .match_query(Matcher::UrlEncoded("page".into(), "2".into()))
+        .match_header("If-Modified-Since", Matcher::Missing)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(format!(r#"{{"Data": [], {}}}"#, meta_json(2, 50, 2, 0)))
+        .expect(1)
+        .create_async()
+        .await;
+    let revalidated = server
+        .mock("GET", "/vatcodes")
+        .match_query(Matcher::UrlEncoded("page".into(), "1".into()))
+        .match_header("If-Modified-Since", "Fri, 14 Mar 2025 12:00:00 GMT")
+        .with_status(304)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = client(&server);
+    let page = |n| Some(spiris::PaginationParams::new().page(n));
+    client.vat_codes().list(page(1)).await.unwrap();
+    client.vat_codes().list(page(2)).await.unwrap();
+    let cached = client.vat_codes().list(page(1)).await.unwrap();
+
+    assert_eq!(cached.meta.current_page, 1);
+    other_page.assert_async().await;
+    revalidated.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_entries_are_not_shared_between_tokens() {
+    let mut server = Server::new_async().await;
+    let first = server
+        .mock("GET", "/units")
+        .match_header("Authorization", "Bearer test_token")
+        .match_header("If-None-Match", Matcher::Missing)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("ETag", "\"v1\"")
+        .with_body(units_page())
+        .expect(1)
+        .create_async()
+        .await;
+    let other_company = server
+        .mock("GET", "/units")
+        .match_header("Authorization", "Bearer other_token")
+        .match_header("If-None-Match", Matcher::Missing)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(format!(r#"{{"Data": [], {}}}"#, meta_json(0, 50, 1, 0)))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = client(&server);
+    client.units().list(None).await.unwrap();
+    let other = client.clone();
+    other.set_access_token(AccessToken::new("other_token".to_string(), 3600, None));
+    let units = other.units().list(None).await.unwrap();
+
+    assert!(units.data.is_empty());
+    first.assert_async().await;
+    other_company.assert_async().await;
+}
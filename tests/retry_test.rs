@@ -10,13 +10,16 @@
 mod mock_server;
 
 use mock_server::{MockApi, MockResponse};
-use spiris::{ApiErrorResponse, Error, RetryConfig};
+use spiris::clock::{Clock, MockClock};
+use spiris::{
+    AccessToken, ApiErrorResponse, Client, ClientConfig, Error, Jitter, RetryConfig, RetryPolicy,
+};
 use std::time::Duration;
 
 fn api_error(status_code: u16, message: &str) -> Error {
     Error::ApiError {
         status_code,
-        response: ApiErrorResponse::from_raw(message.to_string()),
+        response: Box::new(ApiErrorResponse::from_raw(message.to_string())),
         raw_body: message.to_string(),
     }
 }
@@ -55,7 +58,10 @@ fn test_retry_config_builder() {
 fn test_rate_limit_is_retryable() {
     use spiris::retry::is_retryable_error;
 
-    let error = Error::RateLimitExceeded("Rate limit exceeded".to_string());
+    let error = Error::RateLimitExceeded {
+        message: "Rate limit exceeded".to_string(),
+        retry_after: None,
+    };
     assert!(is_retryable_error(&error), "Rate limit should be retryable");
 }
 
@@ -286,15 +292,15 @@ async fn test_mock_server_sequence_setup() {
     );
 
     // First call - 500 error
-    let result1 = api.client.customers().get("cust-001").await;
+    let result1 = api.client.customers().get(&"cust-001".into()).await;
     assert!(result1.is_err(), "First call should fail with 500");
 
     // Second call - 502 error
-    let result2 = api.client.customers().get("cust-001").await;
+    let result2 = api.client.customers().get(&"cust-001".into()).await;
     assert!(result2.is_err(), "Second call should fail with 502");
 
     // Third call - success
-    let result3 = api.client.customers().get("cust-001").await;
+    let result3 = api.client.customers().get(&"cust-001".into()).await;
     assert!(result3.is_ok(), "Third call should succeed");
 }
 
@@ -305,11 +311,16 @@ async fn test_mock_server_rate_limit_response() {
     // Mock rate limit response
     let _mock = api.mock_rate_limit("/customers", 60);
 
-    let result = api.client.customers().list(None).await;
+    let result = api
+        .client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     // Should get rate limit error
     match result {
-        Err(Error::RateLimitExceeded(_)) => {}
+        Err(Error::RateLimitExceeded { .. }) => {}
         Err(Error::ApiError {
             status_code: 429, ..
         }) => {}
@@ -328,6 +339,83 @@ async fn test_mock_server_connection_reset() {
     assert!(result.is_err(), "Should fail on connection reset");
 }
 
+#[tokio::test]
+async fn test_client_stops_retrying_at_deadline() {
+    let start = chrono::Utc::now();
+    let clock = MockClock::new(start);
+    let config = RetryConfig::new()
+        .max_retries(100)
+        .initial_interval(Duration::from_secs(1))
+        .max_interval(Duration::from_secs(1))
+        .max_elapsed_time(Some(Duration::from_secs(10)));
+    let mut api = MockApi::with_retry_config(config.clone()).await;
+    let _mock = api.mock_error("GET", "/customers/cust-001", 503, "Unavailable");
+    let client = Client::with_config(
+        AccessToken::new("test_token".to_string(), 3600, None),
+        ClientConfig::new()
+            .base_url(api.server.url())
+            .clock(clock.clone())
+            .retry_config(config),
+    );
+
+    let result = client
+        .customers()
+        .get(&"cust-001".into())
+        .await
+        .map_err(Error::into_root);
+
+    // Backoff is slept in mock time, so the outcome does not depend on how
+    // long the requests take.
+    match result {
+        Err(Error::RetryDeadlineExceeded {
+            deadline,
+            elapsed,
+            attempts,
+            last_error,
+        }) => {
+            assert_eq!(deadline, Duration::from_secs(10));
+            // Another 1s of backoff would reach the deadline.
+            assert_eq!(elapsed, Duration::from_secs(9));
+            assert_eq!(attempts, 10);
+            assert_eq!(last_error.status_code(), Some(503));
+        }
+        other => panic!("Expected retry deadline error, got {:?}", other),
+    }
+    assert_eq!(clock.elapsed_since(start), Duration::from_secs(9));
+}
+
+#[tokio::test]
+async fn test_client_follows_status_policy() {
+    let config = RetryConfig::new()
+        .max_retries(3)
+        .initial_interval(Duration::from_millis(1))
+        .jitter(Jitter::Equal)
+        .status_policy(409, RetryPolicy::Retry)
+        .status_policy(503, RetryPolicy::Never);
+    let mut api = MockApi::with_retry_config(config).await;
+    let conflict = api
+        .server
+        .mock("GET", "/customers/cust-001")
+        .with_status(409)
+        .with_body("Locked")
+        .expect(4)
+        .create();
+    let unavailable = api
+        .server
+        .mock("GET", "/customers/cust-002")
+        .with_status(503)
+        .with_body("Unavailable")
+        .expect(1)
+        .create();
+
+    let err = api.client.customers().get(&"cust-001".into()).await;
+    assert_eq!(err.unwrap_err().status_code(), Some(409));
+    let err = api.client.customers().get(&"cust-002".into()).await;
+    assert_eq!(err.unwrap_err().status_code(), Some(503));
+    conflict.assert();
+    unavailable.assert();
+}
+
 // =============================================================================
 // Exponential Backoff Calculation Tests
 // =============================================================================
@@ -0,0 +1,113 @@
+//! Integration tests for the Supplier Invoice Payments endpoint.
+
+mod mock_server;
+
+use chrono::{TimeZone, Utc};
+use mock_server::{money, MockApi};
+use spiris::{SupplierInvoice, SupplierInvoicePayment};
+
+fn eur_invoice() -> SupplierInvoice {
+    SupplierInvoice {
+        id: Some("si-100".to_string()),
+        currency_code: Some("EUR".to_string()),
+        total_amount_including_vat: Some(money(1000.0)),
+        remaining_amount: Some(money(600.0)),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_register_supplier_invoice_payment() {
+    let mut api = MockApi::new().await;
+
+    let mock = api
+        .server
+        .mock("POST", "/supplierinvoices/si-100/payments")
+        .match_header("Authorization", "Bearer test_token")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "BankAccountId": "bank-001",
+            "PaymentReferenceNumber": "BG 123"
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "pay-1", "SupplierInvoiceId": "si-100", "Amount": 250.0}"#)
+        .create();
+
+    let payment = SupplierInvoicePayment::new(money(250.0), Utc::now())
+        .bank_account("bank-001")
+        .reference("BG 123");
+
+    let created = api
+        .client
+        .supplier_invoice_payments()
+        .register("si-100", &payment)
+        .await
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(created.id.as_deref(), Some("pay-1"));
+    assert_eq!(created.amount, Some(money(250.0)));
+}
+
+#[tokio::test]
+async fn test_register_partial_foreign_currency_payment() {
+    let mut api = MockApi::new().await;
+
+    let mock = api
+        .server
+        .mock("POST", "/supplierinvoices/si-100/payments")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "SupplierInvoiceId": "si-100",
+            "CurrencyCode": "EUR",
+            "CurrencyRate": 11.5,
+            "Amount": 500.0,
+            "PaymentDate": "2025-03-14T00:00:00Z"
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "pay-2", "SupplierInvoiceId": "si-100"}"#)
+        .create();
+
+    let date = Utc.with_ymd_and_hms(2025, 3, 14, 0, 0, 0).unwrap();
+    let payment =
+        SupplierInvoicePayment::new(money(500.0), date).foreign_currency("EUR", money(11.5));
+
+    let created = api
+        .client
+        .supplier_invoice_payments()
+        .register_for(&eur_invoice(), payment)
+        .await
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(created.id.as_deref(), Some("pay-2"));
+}
+
+#[tokio::test]
+async fn test_register_for_rejects_overpayment() {
+    let api = MockApi::new().await;
+
+    let payment = SupplierInvoicePayment::new(money(700.0), Utc::now());
+    let result = api
+        .client
+        .supplier_invoice_payments()
+        .register_for(&eur_invoice(), payment)
+        .await;
+
+    assert!(matches!(result, Err(spiris::Error::InvalidRequest(_))));
+    assert_eq!(api.request_count(), 0);
+}
+
+#[tokio::test]
+async fn test_register_rejects_non_positive_amount() {
+    let api = MockApi::new().await;
+
+    let payment = SupplierInvoicePayment::new(money(0.0), Utc::now());
+    let result = api
+        .client
+        .supplier_invoice_payments()
+        .register("si-100", &payment)
+        .await;
+
+    assert!(matches!(result, Err(spiris::Error::InvalidRequest(_))));
+}
@@ -0,0 +1,78 @@
+//! Integration tests for pushing local records with the sync module.
+
+mod mock_server;
+
+use mock_server::{paginated_response, MockApi};
+use spiris::sync::{self, SyncAction, SyncOptions};
+use spiris::Customer;
+
+const CURRENT: &str = r#"[
+    {"Id": "c1", "CustomerNumber": "1001", "Name": "Acme AB", "IsActive": true},
+    {"Id": "c2", "CustomerNumber": "1002", "Name": "Beta AB", "IsActive": true}
+]"#;
+
+fn local() -> Vec<Customer> {
+    let customer = |number: &str, name: &str| Customer {
+        customer_number: Some(number.to_string()),
+        name: Some(name.to_string()),
+        ..Default::default()
+    };
+    vec![
+        customer("1001", "Acme Sverige AB"),
+        customer("1003", "Gamma AB"),
+    ]
+}
+
+#[tokio::test]
+async fn test_sync_dry_run_sends_nothing() {
+    let mut api = MockApi::new().await;
+    let list = api.mock_get_any_query("/customers", &paginated_response(CURRENT, 0, 2));
+
+    let options = SyncOptions::new().delete_missing(true).dry_run(true);
+    let plan = sync::plan(&api.client, &local(), &options).await.unwrap();
+    let report = plan.apply(&api.client, &options).await;
+
+    list.assert();
+    assert!(report.dry_run);
+    assert!(report.is_complete());
+    assert_eq!(report.created[0].name.as_deref(), Some("Gamma AB"));
+    assert_eq!(report.created[0].id, None);
+    assert_eq!(report.updated[0].name.as_deref(), Some("Acme Sverige AB"));
+    assert_eq!(report.updated[0].id, Some("c1".into()));
+    assert_eq!(report.deleted, vec!["1002"]);
+}
+
+#[tokio::test]
+async fn test_sync_applies_changes_and_reports_failures() {
+    let mut api = MockApi::new().await;
+    api.mock_get_any_query("/customers", &paginated_response(CURRENT, 0, 2));
+    let create = api.mock_post(
+        "/customers",
+        r#"{"Id": "c3", "CustomerNumber": "1003", "Name": "Gamma AB"}"#,
+    );
+    let update = api.mock_put(
+        "/customers/c1",
+        r#"{"Id": "c1", "CustomerNumber": "1001", "Name": "Acme Sverige AB"}"#,
+    );
+    let delete = api.mock_error(
+        "DELETE",
+        "/customers/c2",
+        400,
+        r#"{"ErrorCode": 4000, "DeveloperErrorMessage": "Customer has invoices"}"#,
+    );
+
+    let options = SyncOptions::new().delete_missing(true);
+    let plan = sync::plan(&api.client, &local(), &options).await.unwrap();
+    assert_eq!(plan.unchanged, 0);
+    let report = plan.apply(&api.client, &options).await;
+
+    create.assert();
+    update.assert();
+    delete.assert();
+    assert_eq!(report.created[0].id, Some("c3".into()));
+    assert_eq!(report.updated.len(), 1);
+    assert!(report.deleted.is_empty());
+    assert!(!report.is_complete());
+    assert_eq!(report.failures[0].action, SyncAction::Delete);
+    assert_eq!(report.failures[0].key.as_deref(), Some("1002"));
+}
@@ -0,0 +1,94 @@
+//! Integration tests for the wiremock fixtures.
+
+#![cfg(feature = "test-fixtures")]
+
+use spiris::test_fixtures::{self, customer_id, invoice_id};
+use spiris::{Error, InvoiceStatus, PaymentStatus};
+use wiremock::MockServer;
+
+#[tokio::test]
+async fn test_mock_customers_list() {
+    let server = MockServer::start().await;
+    let expected = test_fixtures::mock_customers_list(&server, 12).await;
+
+    let page = test_fixtures::client(&server)
+        .customers()
+        .list(None)
+        .await
+        .unwrap();
+    assert_eq!(page.data.len(), 12);
+    assert_eq!(page.total_count(), 12);
+    assert!(!page.has_next_page());
+    assert_eq!(page.data[11].id, Some(customer_id(12)));
+    assert_eq!(page.data[0].name, expected[0].name);
+    assert_eq!(page.data[0].name.as_deref(), Some("Acme AB"));
+}
+
+#[tokio::test]
+async fn test_mock_get_by_id() {
+    let server = MockServer::start().await;
+    test_fixtures::mock_customer(&server, 2).await;
+    test_fixtures::mock_article(&server, 3).await;
+    let invoice = test_fixtures::mock_invoice(&server, 4).await;
+    let client = test_fixtures::client(&server);
+
+    let customer = client.customers().get(&customer_id(2)).await.unwrap();
+    assert_eq!(customer.customer_number.as_deref(), Some("1002"));
+
+    let article = client
+        .articles()
+        .get(&test_fixtures::article_id(3))
+        .await
+        .unwrap();
+    assert_eq!(article.unit.as_deref(), Some("mån"));
+
+    let fetched = client.invoices().get(&invoice_id(4)).await.unwrap();
+    assert_eq!(fetched.id, invoice.id);
+    assert_eq!(fetched.customer_id, Some(customer_id(4)));
+    assert_eq!(fetched.status, Some(InvoiceStatus::Booked));
+    assert_eq!(fetched.payment_status, Some(PaymentStatus::Paid));
+}
+
+#[tokio::test]
+async fn test_mock_invoices_list_and_pdf() {
+    let server = MockServer::start().await;
+    let invoices = test_fixtures::mock_invoices_list(&server, 4).await;
+    let pdf = test_fixtures::mock_invoice_pdf(&server, 1).await;
+    let client = test_fixtures::client(&server);
+
+    let page = client.invoices().list(None).await.unwrap();
+    assert_eq!(page.data.len(), invoices.len());
+    assert_eq!(page.data[0].status, Some(InvoiceStatus::Draft));
+    assert_eq!(
+        page.data[2].payment_status,
+        Some(PaymentStatus::PartiallyPaid)
+    );
+
+    let downloaded = client.invoices().get_pdf(&invoice_id(1)).await.unwrap();
+    assert_eq!(downloaded, pdf);
+    assert!(downloaded.starts_with(b"%PDF"));
+}
+
+#[tokio::test]
+async fn test_mock_error() {
+    let server = MockServer::start().await;
+    let path = format!("/customers/{}", customer_id(9));
+    test_fixtures::mock_error(&server, "GET", &path, 404).await;
+
+    let error = test_fixtures::client(&server)
+        .customers()
+        .get(&customer_id(9))
+        .await
+        .unwrap_err();
+    assert!(matches!(error.root(), Error::NotFound(_)));
+}
+
+#[test]
+fn test_fixtures_are_deterministic() {
+    assert_eq!(
+        test_fixtures::invoice_json(7),
+        test_fixtures::invoice_json(7)
+    );
+    assert_ne!(customer_id(1), customer_id(2));
+    assert_ne!(customer_id(1).as_ref(), invoice_id(1).as_ref());
+}
@@ -1,8 +1,8 @@
-// Skip these tests when decimal feature is enabled (uses f64 literals)
-#![cfg(not(feature = "decimal"))]
 //! Test utility functions and helpers for integration tests.
 
-use spiris::{AccessToken, Article, Customer, Error, Invoice, PaginatedResponse, ResponseMetadata};
+use spiris::{
+    AccessToken, Article, Customer, Error, Invoice, Money, PaginatedResponse, ResponseMetadata,
+};
 use std::fs;
 use std::path::Path;
 use std::time::Duration;
@@ -104,7 +104,7 @@ pub fn assert_pagination_meta(
 /// Assert that an error has a specific status code
 #[allow(dead_code)]
 pub fn assert_error_status(error: &Error, expected_status: u16) {
-    match error {
+    match error.root() {
         Error::ApiError { status_code, .. } => {
             assert_eq!(
                 *status_code, expected_status,
@@ -149,7 +149,7 @@ pub fn assert_result_error_status<T: std::fmt::Debug>(
 pub fn random_customer() -> Customer {
     let id = uuid_v4();
     Customer {
-        id: Some(id.clone()),
+        id: Some(id.clone().into()),
         customer_number: Some(format!("{}", rand_u32() % 10000)),
         name: Some(format!("Test Customer {}", &id[..8])),
         email: Some(format!("test-{}@example.com", &id[..8])),
@@ -163,10 +163,10 @@ pub fn random_customer() -> Customer {
 pub fn random_invoice(customer_id: &str) -> Invoice {
     let id = uuid_v4();
     Invoice {
-        id: Some(id.clone()),
+        id: Some(id.clone().into()),
         invoice_number: Some(format!("{}", 20000 + rand_u32() % 10000)),
-        customer_id: Some(customer_id.to_string()),
-        total_amount: Some((rand_u32() % 100000) as f64 / 100.0),
+        customer_id: Some(customer_id.into()),
+        total_amount: Some(random_amount(100000)),
         rows: vec![],
         ..Default::default()
     }
@@ -177,10 +177,10 @@ pub fn random_invoice(customer_id: &str) -> Invoice {
 pub fn random_article() -> Article {
     let id = uuid_v4();
     Article {
-        id: Some(id.clone()),
+        id: Some(id.clone().into()),
         article_number: Some(format!("ART-{}", rand_u32() % 10000)),
         name: Some(format!("Test Article {}", &id[..8])),
-        sales_price: Some((rand_u32() % 10000) as f64 / 100.0),
+        sales_price: Some(random_amount(10000)),
         is_active: Some(true),
         ..Default::default()
     }
@@ -266,6 +266,14 @@ fn rand_u32() -> u32 {
     RandomState::new().build_hasher().finish() as u32
 }
 
+/// Random amount below `max_cents / 100` with two decimals
+fn random_amount(max_cents: u32) -> Money {
+    let cents = rand_u32() % max_cents;
+    format!("{}.{:02}", cents / 100, cents % 100)
+        .parse()
+        .unwrap()
+}
+
 /// Simple random u64 (not cryptographically secure, for testing only)
 fn rand_u64() -> u64 {
     use std::collections::hash_map::RandomState;
@@ -281,7 +289,7 @@ pub fn make_paginated_response<T>(
     page_size: u32,
     total_count: u32,
 ) -> PaginatedResponse<T> {
-    let total_pages = (total_count + page_size - 1) / page_size;
+    let total_pages = total_count.div_ceil(page_size);
     PaginatedResponse {
         data,
         meta: ResponseMetadata {
@@ -0,0 +1,78 @@
+//! Integration tests for sandbox seeding.
+
+#![cfg(feature = "testing")]
+
+mod mock_server;
+
+use chrono::NaiveDate;
+use mock_server::MockApi;
+use mockito::Matcher;
+use spiris::testing::{seed_sandbox, SeedPlan, SeedStatus};
+use spiris::{AccessToken, Client, Error};
+
+#[tokio::test]
+async fn test_seed_sandbox() {
+    let mut api = MockApi::new().await;
+    api.mock_post("/customers", r#"{"Id": "cust-1", "IsActive": true}"#);
+    api.mock_post("/articles", r#"{"Id": "art-1", "SalesPrice": 100}"#);
+    let drafts = api
+        .server
+        .mock("POST", "/customerinvoicedrafts")
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "draft-1", "Rows": []}"#)
+        .expect(1)
+        .create();
+    let invoices = api
+        .server
+        .mock("POST", "/customerinvoices")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "CustomerId": "cust-1",
+            "Rows": [{"ArticleId": "art-1"}]
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "inv-1", "TotalAmountIncludingVat": 125, "Rows": []}"#)
+        .expect(4)
+        .create();
+    // One partly paid and one paid invoice
+    let payments = api
+        .server
+        .mock("POST", "/customerinvoices/inv-1/payments")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body("null")
+        .expect(2)
+        .create();
+
+    let plan = SeedPlan::new()
+        .customers(3)
+        .articles(1)
+        .invoices(5)
+        .as_of(NaiveDate::from_ymd_opt(2025, 6, 30).unwrap());
+    let report = seed_sandbox(&api.client, &plan).await.unwrap();
+
+    drafts.assert();
+    invoices.assert();
+    payments.assert();
+    assert_eq!(report.customers.len(), 3);
+    assert_eq!(report.drafts.len(), 1);
+    let statuses: Vec<SeedStatus> = report.invoices.iter().map(|(s, _)| *s).collect();
+    assert_eq!(
+        statuses,
+        [
+            SeedStatus::Unpaid,
+            SeedStatus::Overdue,
+            SeedStatus::PartlyPaid,
+            SeedStatus::Paid
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_seed_sandbox_refuses_production() {
+    let client = Client::new(AccessToken::new("token".to_string(), 3600, None));
+    let result = seed_sandbox(&client, &SeedPlan::new()).await;
+
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+}
@@ -11,7 +11,9 @@ mod mock_server;
 
 use mock_server::{MockApi, MockOAuthServer};
 use spiris::auth::OAuth2Config;
+use spiris::clock::{Clock, MockClock};
 use spiris::{AccessToken, Client, ClientConfig, Error, RetryConfig};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 // =============================================================================
@@ -166,7 +168,11 @@ async fn test_client_rejects_expired_token() {
     let config = ClientConfig::new().base_url(server.url());
     let client = Client::with_config(expired_token, config);
 
-    let result = client.customers().list(None).await;
+    let result = client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     assert!(
         matches!(result, Err(Error::TokenExpired)),
@@ -388,6 +394,7 @@ async fn test_auto_refresh_on_expired_token() {
         redirect_uri: "http://localhost:8080/callback".to_string(),
         auth_url: oauth.auth_url(),
         token_url: oauth.token_url(),
+        ..Default::default()
     };
 
     // Start with expired token that has refresh token
@@ -422,6 +429,55 @@ async fn test_auto_refresh_on_expired_token() {
     );
 }
 
+#[tokio::test]
+async fn test_auto_refresh_when_mock_clock_passes_expiry() {
+    let mut oauth = MockOAuthServer::new().await;
+    let refresh_mock = oauth.mock_token_refresh("my_refresh_token", "new_access_token", None, 3600);
+    let mut api_server = mockito::Server::new_async().await;
+    let customers_json = r#"{"Data": [], "Meta": {"CurrentPage": 0, "PageSize": 50, "TotalPages": 1, "TotalCount": 0, "HasNextPage": false, "HasPreviousPage": false}}"#;
+    let _api_mock = api_server
+        .mock("GET", "/customers")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(customers_json)
+        .create();
+
+    let oauth_config = OAuth2Config {
+        client_id: "test_client".to_string(),
+        client_secret: "test_secret".to_string(),
+        redirect_uri: "http://localhost:8080/callback".to_string(),
+        auth_url: oauth.auth_url(),
+        token_url: oauth.token_url(),
+        ..Default::default()
+    };
+    let clock = MockClock::new(chrono::Utc::now());
+    let config = ClientConfig::new()
+        .base_url(api_server.url())
+        .oauth_config(oauth_config)
+        .retry_config(RetryConfig::new().max_retries(0))
+        .clock(clock.clone());
+    let token = AccessToken::new(
+        "old_token".to_string(),
+        3600,
+        Some("my_refresh_token".to_string()),
+    );
+    let client = Client::with_config(token, config);
+
+    // Inside the 5 minute buffer before expiry
+    clock.advance(Duration::from_secs(3600 - 299));
+    assert!(client.is_token_expired());
+    client.customers().list(None).await.unwrap();
+
+    refresh_mock.assert();
+    let refreshed = client.get_access_token();
+    assert_eq!(refreshed.token, "new_access_token");
+    assert_eq!(
+        refreshed.expires_at,
+        clock.now() + chrono::Duration::seconds(3600)
+    );
+    assert!(!client.is_token_expired());
+}
+
 #[tokio::test]
 async fn test_auto_refresh_without_oauth_config_fails() {
     // Create client with expired token but NO OAuth config
@@ -438,7 +494,11 @@ async fn test_auto_refresh_without_oauth_config_fails() {
     // Note: no oauth_config set
     let client = Client::with_config(expired_token, config);
 
-    let result = client.customers().list(None).await;
+    let result = client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     assert!(
         matches!(result, Err(Error::TokenExpired)),
@@ -458,6 +518,7 @@ async fn test_auto_refresh_without_refresh_token_fails() {
         redirect_uri: "http://localhost:8080/callback".to_string(),
         auth_url: oauth.auth_url(),
         token_url: oauth.token_url(),
+        ..Default::default()
     };
 
     // Expired token WITHOUT refresh token
@@ -474,7 +535,11 @@ async fn test_auto_refresh_without_refresh_token_fails() {
         .retry_config(RetryConfig::new().max_retries(0));
     let client = Client::with_config(expired_token, config);
 
-    let result = client.customers().list(None).await;
+    let result = client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
 
     assert!(
         matches!(result, Err(Error::TokenExpired)),
@@ -511,6 +576,7 @@ async fn test_valid_token_does_not_refresh() {
         redirect_uri: "http://localhost:8080/callback".to_string(),
         auth_url: oauth.auth_url(),
         token_url: oauth.token_url(),
+        ..Default::default()
     };
 
     // Valid token (not expired)
@@ -564,6 +630,7 @@ async fn test_concurrent_requests_single_refresh() {
         redirect_uri: "http://localhost:8080/callback".to_string(),
         auth_url: oauth.auth_url(),
         token_url: oauth.token_url(),
+        ..Default::default()
     };
 
     // Expired token
@@ -592,3 +659,395 @@ async fn test_concurrent_requests_single_refresh() {
     assert!(r3.is_ok(), "Request 3 should succeed: {:?}", r3.err());
     // Mock will verify expect(1) for refresh when dropped
 }
+
+#[tokio::test]
+async fn test_clones_on_spawned_tasks_share_one_refresh() {
+    let mut oauth = MockOAuthServer::new().await;
+    let refresh_mock = oauth
+        .mock_token_refresh(
+            "my_refresh_token",
+            "new_access_token",
+            Some("new_refresh_token"),
+            3600,
+        )
+        .expect(1);
+
+    let mut api_server = mockito::Server::new_async().await;
+    let api_mock = api_server
+        .mock("GET", "/customers/cust-1")
+        .match_header("Authorization", "Bearer new_access_token")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "cust-1"}"#)
+        .expect(4)
+        .create();
+
+    let oauth_config = OAuth2Config {
+        client_id: "test_client".to_string(),
+        client_secret: "test_secret".to_string(),
+        redirect_uri: "http://localhost:8080/callback".to_string(),
+        auth_url: oauth.auth_url(),
+        token_url: oauth.token_url(),
+        ..Default::default()
+    };
+    let expired = AccessToken::new(
+        "old_token".to_string(),
+        -100,
+        Some("my_refresh_token".to_string()),
+    );
+    let config = ClientConfig::new()
+        .base_url(api_server.url())
+        .oauth_config(oauth_config)
+        .retry_config(RetryConfig::new().max_retries(0));
+    let client = Client::with_config(expired, config);
+
+    let tasks: Vec<_> = (0..4)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.customers().get(&"cust-1".into()).await })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+
+    refresh_mock.assert();
+    api_mock.assert();
+    // The refresh made by a task is visible through the original handle
+    assert_eq!(client.get_access_token().token, "new_access_token");
+}
+
+#[tokio::test]
+async fn test_unauthorized_response_refreshes_and_retries_once() {
+    let mut oauth = MockOAuthServer::new().await;
+    let refresh_mock = oauth.mock_token_refresh(
+        "my_refresh_token",
+        "new_access_token",
+        Some("new_refresh_token"),
+        3600,
+    );
+
+    let mut api_server = mockito::Server::new_async().await;
+    let rejected = api_server
+        .mock("GET", "/customers/cust-1")
+        .match_header("Authorization", "Bearer revoked_token")
+        .with_status(401)
+        .expect(1)
+        .create();
+    let accepted = api_server
+        .mock("GET", "/customers/cust-1")
+        .match_header("Authorization", "Bearer new_access_token")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "cust-1", "Name": "Acme AB"}"#)
+        .expect(1)
+        .create();
+
+    let oauth_config = OAuth2Config {
+        client_id: "test_client".to_string(),
+        client_secret: "test_secret".to_string(),
+        redirect_uri: "http://localhost:8080/callback".to_string(),
+        auth_url: oauth.auth_url(),
+        token_url: oauth.token_url(),
+        ..Default::default()
+    };
+    // Not expired, but revoked on the server
+    let token = AccessToken::new(
+        "revoked_token".to_string(),
+        3600,
+        Some("my_refresh_token".to_string()),
+    );
+    let config = ClientConfig::new()
+        .base_url(api_server.url())
+        .oauth_config(oauth_config)
+        .retry_config(RetryConfig::new().max_retries(0));
+    let client = Client::with_config(token, config);
+
+    let customer = client.customers().get(&"cust-1".into()).await.unwrap();
+
+    assert_eq!(customer.name.as_deref(), Some("Acme AB"));
+    rejected.assert();
+    accepted.assert();
+    refresh_mock.assert();
+    assert_eq!(client.get_access_token().token, "new_access_token");
+}
+
+#[tokio::test]
+async fn test_unauthorized_after_refresh_is_returned() {
+    let mut oauth = MockOAuthServer::new().await;
+    let refresh_mock = oauth
+        .mock_token_refresh("my_refresh_token", "new_access_token", None, 3600)
+        .expect(1);
+
+    let mut api_server = mockito::Server::new_async().await;
+    let api_mock = api_server
+        .mock("DELETE", "/customers/cust-1")
+        .with_status(401)
+        .expect(2)
+        .create();
+
+    let oauth_config = OAuth2Config {
+        client_id: "test_client".to_string(),
+        client_secret: "test_secret".to_string(),
+        redirect_uri: "http://localhost:8080/callback".to_string(),
+        auth_url: oauth.auth_url(),
+        token_url: oauth.token_url(),
+        ..Default::default()
+    };
+    let token = AccessToken::new(
+        "revoked_token".to_string(),
+        3600,
+        Some("my_refresh_token".to_string()),
+    );
+    let config = ClientConfig::new()
+        .base_url(api_server.url())
+        .oauth_config(oauth_config)
+        .retry_config(RetryConfig::new().max_retries(0));
+    let client = Client::with_config(token, config);
+
+    let result = client
+        .customers()
+        .delete(&"cust-1".into())
+        .await
+        .map_err(Error::into_root);
+
+    assert!(matches!(result, Err(Error::AuthError(_))), "{:?}", result);
+    api_mock.assert();
+    refresh_mock.assert();
+}
+
+#[tokio::test]
+async fn test_unauthorized_without_oauth_config_is_not_retried() {
+    let mut api = MockApi::new().await;
+    let mock = api
+        .server
+        .mock("GET", "/customers/cust-1")
+        .with_status(401)
+        .expect(1)
+        .create();
+
+    let result = api
+        .client
+        .customers()
+        .get(&"cust-1".into())
+        .await
+        .map_err(Error::into_root);
+
+    assert!(matches!(result, Err(Error::AuthError(_))), "{:?}", result);
+    mock.assert();
+}
+
+// =============================================================================
+// Required Scopes
+// =============================================================================
+
+#[tokio::test]
+async fn test_refreshed_token_missing_scopes_fails() {
+    let mut oauth = MockOAuthServer::new().await;
+    let _refresh_mock = oauth
+        .server
+        .mock("POST", "/connect/token")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"access_token": "new_access_token", "token_type": "Bearer", "expires_in": 3600, "scope": "ea:api offline_access"}"#)
+        .create();
+
+    let mut api_server = mockito::Server::new_async().await;
+    let api_mock = api_server.mock("GET", "/customers").expect(0).create();
+
+    let oauth_config = OAuth2Config {
+        client_id: "test_client".to_string(),
+        client_secret: "test_secret".to_string(),
+        redirect_uri: "http://localhost:8080/callback".to_string(),
+        auth_url: oauth.auth_url(),
+        token_url: oauth.token_url(),
+        ..Default::default()
+    };
+    let expired_token = AccessToken::new(
+        "old_token".to_string(),
+        -100,
+        Some("my_refresh_token".to_string()),
+    )
+    .with_scopes(["ea:api", "ea:sales", "offline_access"]);
+
+    let config = ClientConfig::new()
+        .base_url(api_server.url())
+        .oauth_config(oauth_config)
+        .required_scopes(["ea:api", "ea:sales"])
+        .retry_config(RetryConfig::new().max_retries(0));
+    let client = Client::with_config(expired_token, config);
+
+    let result = client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
+
+    match result {
+        Err(Error::InsufficientScopes { missing }) => assert_eq!(missing, ["ea:sales"]),
+        other => panic!("Expected InsufficientScopes: {:?}", other),
+    }
+    assert_eq!(
+        client.get_access_token().scopes,
+        Some(vec!["ea:api".to_string(), "offline_access".to_string()])
+    );
+    api_mock.assert();
+}
+
+#[test]
+fn test_check_scopes_on_loaded_token() {
+    let token = AccessToken::new("token".to_string(), 3600, None).with_scopes(["ea:api"]);
+
+    let client = Client::with_config(
+        token.clone(),
+        ClientConfig::new().required_scopes(["ea:api", "ea:purchase"]),
+    );
+    assert!(matches!(
+        client.check_scopes(),
+        Err(Error::InsufficientScopes { missing }) if missing == ["ea:purchase"]
+    ));
+
+    // Nothing is required by default
+    assert!(Client::new(token).check_scopes().is_ok());
+}
+
+#[tokio::test]
+async fn test_on_token_refresh_receives_new_token() {
+    let mut oauth = MockOAuthServer::new().await;
+    let _refresh_mock = oauth.mock_token_refresh(
+        "my_refresh_token",
+        "new_access_token",
+        Some("new_refresh_token"),
+        3600,
+    );
+
+    let mut api_server = mockito::Server::new_async().await;
+    let _api_mock = api_server
+        .mock("GET", "/customers/cust-1")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "cust-1"}"#)
+        .expect(2)
+        .create();
+
+    let oauth_config = OAuth2Config {
+        client_id: "test_client".to_string(),
+        client_secret: "test_secret".to_string(),
+        redirect_uri: "http://localhost:8080/callback".to_string(),
+        auth_url: oauth.auth_url(),
+        token_url: oauth.token_url(),
+        ..Default::default()
+    };
+    let saved = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&saved);
+    let config = ClientConfig::new()
+        .base_url(api_server.url())
+        .oauth_config(oauth_config)
+        .retry_config(RetryConfig::new().max_retries(0))
+        .on_token_refresh(move |token| {
+            sink.lock()
+                .unwrap()
+                .push((token.token.clone(), token.refresh_token.clone()));
+        });
+    let expired = AccessToken::new(
+        "old_token".to_string(),
+        -100,
+        Some("my_refresh_token".to_string()),
+    );
+    let client = Client::with_config(expired, config);
+
+    client.customers().get(&"cust-1".into()).await.unwrap();
+    // The refreshed token is still valid, so no second refresh
+    client.customers().get(&"cust-1".into()).await.unwrap();
+
+    assert_eq!(
+        *saved.lock().unwrap(),
+        vec![(
+            "new_access_token".to_string(),
+            Some("new_refresh_token".to_string())
+        )]
+    );
+}
+
+#[tokio::test]
+async fn test_on_token_refresh_not_called_when_refresh_fails() {
+    let server = mockito::Server::new_async().await;
+    let called = Arc::new(Mutex::new(false));
+    let flag = Arc::clone(&called);
+    let config = ClientConfig::new()
+        .base_url(server.url())
+        .retry_config(RetryConfig::new().max_retries(0))
+        .on_token_refresh(move |_| *flag.lock().unwrap() = true);
+    // No OAuth config, so the expired token can't be refreshed
+    let expired = AccessToken::new(
+        "old_token".to_string(),
+        -100,
+        Some("refresh_token".to_string()),
+    );
+    let client = Client::with_config(expired, config);
+
+    let result = client
+        .customers()
+        .list(None)
+        .await
+        .map_err(Error::into_root);
+
+    assert!(matches!(result, Err(Error::TokenExpired)));
+    assert!(!*called.lock().unwrap());
+}
+
+#[tokio::test]
+async fn test_custom_http_client_sends_api_and_token_requests() {
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("X-Proxy-Tag", "corp".parse().unwrap());
+    let http_client = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap();
+
+    let mut oauth = MockOAuthServer::new().await;
+    let refresh_mock = oauth
+        .server
+        .mock("POST", "/connect/token")
+        .match_header("X-Proxy-Tag", "corp")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"access_token": "new_access_token", "token_type": "Bearer", "expires_in": 3600}"#,
+        )
+        .create();
+
+    let mut api_server = mockito::Server::new_async().await;
+    let api_mock = api_server
+        .mock("GET", "/customers/cust-1")
+        .match_header("X-Proxy-Tag", "corp")
+        .match_header("Authorization", "Bearer new_access_token")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"Id": "cust-1"}"#)
+        .create();
+
+    let oauth_config = OAuth2Config {
+        client_id: "test_client".to_string(),
+        client_secret: "test_secret".to_string(),
+        redirect_uri: "http://localhost:8080/callback".to_string(),
+        auth_url: oauth.auth_url(),
+        token_url: oauth.token_url(),
+        ..Default::default()
+    };
+    let config = ClientConfig::new()
+        .base_url(api_server.url())
+        .oauth_config(oauth_config)
+        .with_http_client(http_client);
+    let expired = AccessToken::new(
+        "old_access_token".to_string(),
+        -100,
+        Some("my_refresh_token".to_string()),
+    );
+    let client = Client::with_config(expired, config);
+
+    client.customers().get(&"cust-1".into()).await.unwrap();
+
+    refresh_mock.assert();
+    api_mock.assert();
+}
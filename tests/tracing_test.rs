@@ -0,0 +1,184 @@
+//! Integration tests for request spans.
+
+#![cfg(feature = "tracing")]
+
+use mockito::Server;
+use spiris::{AccessToken, Client, ClientConfig, Customer, RetryConfig};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Collects formatted trace output.
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Captured {
+    fn text(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Captured {
+    type Writer = Captured;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+fn subscriber(captured: &Captured) -> impl tracing::Subscriber {
+    tracing_subscriber::fmt()
+        .with_writer(captured.clone())
+        .with_max_level(tracing::Level::DEBUG)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(false)
+        .finish()
+}
+
+fn client(server: &Server, config: ClientConfig) -> Client {
+    Client::with_config(
+        AccessToken::new("test_token".to_string(), 3600, None),
+        config.base_url(server.url()),
+    )
+}
+
+#[tokio::test]
+async fn test_request_span_has_status_and_correlation_id() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("GET", "/customers/cust-1")
+        .with_status(200)
+        .with_header("x-correlation-id", "corr-42")
+        .with_body(r#"{"Id": "cust-1"}"#)
+        .create_async()
+        .await;
+    let client = client(
+        &server,
+        ClientConfig::new().retry_config(RetryConfig::new().max_retries(0)),
+    );
+
+    let captured = Captured::default();
+    let _guard = tracing::subscriber::set_default(subscriber(&captured));
+    client.customers().get(&"cust-1".into()).await.unwrap();
+
+    let output = captured.text();
+    let close = output
+        .lines()
+        .find(|line| {
+            line.contains("api_request{") && !line.contains("api_attempt") && line.contains("close")
+        })
+        .unwrap_or_else(|| panic!("no closed request span in:\n{}", output));
+    assert!(close.contains("method=\"GET\""), "{}", close);
+    assert!(close.contains("path=\"customers/cust-1\""), "{}", close);
+    assert!(close.contains("status=200"), "{}", close);
+    assert!(close.contains("attempts=1"), "{}", close);
+    assert!(close.contains("correlation_id=\"corr-42\""), "{}", close);
+    assert!(close.contains("duration_ms="), "{}", close);
+    assert!(output.contains("api_attempt{attempt=1"), "{}", output);
+}
+
+#[tokio::test]
+async fn test_retries_are_numbered_attempts() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("GET", "/customers/cust-1")
+        .with_status(503)
+        .expect(4)
+        .create_async()
+        .await;
+    let retry = RetryConfig::new()
+        .max_retries(3)
+        .initial_interval(Duration::from_millis(1));
+    let client = client(&server, ClientConfig::new().retry_config(retry));
+
+    let captured = Captured::default();
+    let _guard = tracing::subscriber::set_default(subscriber(&captured));
+    client.customers().get(&"cust-1".into()).await.unwrap_err();
+
+    let output = captured.text();
+    let last = output
+        .lines()
+        .find(|line| line.contains("api_attempt{attempt=4 ") && line.contains("close"))
+        .unwrap_or_else(|| panic!("no fourth attempt in:\n{}", output));
+    assert!(last.contains("status=503"), "{}", last);
+    let close = output
+        .lines()
+        .find(|line| {
+            line.contains("api_request{") && !line.contains("api_attempt") && line.contains("close")
+        })
+        .unwrap();
+    assert!(close.contains("status=503"), "{}", close);
+    assert!(close.contains("attempts=4"), "{}", close);
+}
+
+#[tokio::test]
+async fn test_bodies_can_be_left_out() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("POST", "/customers")
+        .with_status(201)
+        .with_body(r#"{"Id": "cust-1"}"#)
+        .expect(2)
+        .create_async()
+        .await;
+    let customer = Customer {
+        name: Some("Acme AB".to_string()),
+        email: Some("anna@acme.se".to_string()),
+        ..Default::default()
+    };
+
+    let captured = Captured::default();
+    let _guard = tracing::subscriber::set_default(subscriber(&captured));
+    client(&server, ClientConfig::new())
+        .customers()
+        .create(&customer)
+        .await
+        .unwrap();
+    let output = captured.text();
+    assert!(output.contains("Acme AB"), "{}", output);
+    assert!(!output.contains("anna@acme.se"), "{}", output);
+
+    let captured = Captured::default();
+    let _guard = tracing::subscriber::set_default(subscriber(&captured));
+    client(&server, ClientConfig::new().trace_bodies(false))
+        .customers()
+        .create(&customer)
+        .await
+        .unwrap();
+    let output = captured.text();
+    assert!(output.contains("api_request{"), "{}", output);
+    assert!(!output.contains("Acme AB"), "{}", output);
+}
+
+#[tokio::test]
+async fn test_disabled_tracing_emits_nothing() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("GET", "/customers/cust-1")
+        .with_status(200)
+        .with_body(r#"{"Id": "cust-1"}"#)
+        .create_async()
+        .await;
+    let client = client(&server, ClientConfig::new().enable_tracing(false));
+
+    let captured = Captured::default();
+    let _guard = tracing::subscriber::set_default(subscriber(&captured));
+    client.customers().get(&"cust-1".into()).await.unwrap();
+
+    let output = captured.text();
+    assert!(!output.contains("api_request"), "{}", output);
+    assert!(!output.contains("API request"), "{}", output);
+}
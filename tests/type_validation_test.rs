@@ -7,9 +7,11 @@
 //! - Optional fields are handled correctly
 //! - All expected fields are present
 
+use chrono::NaiveDate;
 use spiris::{
-    AccessToken, Address, Article, Customer, Invoice, InvoiceRow, Money, PaginatedResponse,
-    PaginationParams, QueryParams, ResponseMetadata,
+    AccessToken, Address, Article, Customer, CustomerId, Invoice, InvoiceId, InvoiceRow,
+    InvoiceStatus, Money, PaginatedResponse, PaginationParams, PaymentStatus, QueryParams,
+    ResponseMetadata, SentStatus,
 };
 
 /// Helper to create Option<Money> for test assertions
@@ -42,7 +44,7 @@ fn test_customer_default() {
 #[test]
 fn test_customer_with_fields() {
     let customer = Customer {
-        id: Some("cust-001".to_string()),
+        id: Some("cust-001".into()),
         name: Some("Acme Corp".to_string()),
         email: Some("info@acme.com".to_string()),
         phone: Some("+1234567890".to_string()),
@@ -51,7 +53,7 @@ fn test_customer_with_fields() {
         ..Default::default()
     };
 
-    assert_eq!(customer.id, Some("cust-001".to_string()));
+    assert_eq!(customer.id, Some("cust-001".into()));
     assert_eq!(customer.name, Some("Acme Corp".to_string()));
     assert_eq!(customer.is_active, Some(true));
 }
@@ -59,7 +61,7 @@ fn test_customer_with_fields() {
 #[test]
 fn test_customer_serialization_pascal_case() {
     let customer = Customer {
-        id: Some("cust-001".to_string()),
+        id: Some("cust-001".into()),
         name: Some("Test".to_string()),
         is_active: Some(true),
         customer_number: Some("C001".to_string()),
@@ -87,12 +89,66 @@ fn test_customer_deserialization_pascal_case() {
 
     let customer: Customer = serde_json::from_str(json).unwrap();
 
-    assert_eq!(customer.id, Some("cust-001".to_string()));
+    assert_eq!(customer.id, Some("cust-001".into()));
     assert_eq!(customer.name, Some("Test Customer".to_string()));
     assert_eq!(customer.email, Some("test@example.com".to_string()));
     assert_eq!(customer.is_active, Some(true));
 }
 
+#[test]
+fn test_ids_serialize_as_plain_strings() {
+    let invoice: Invoice = serde_json::from_str(
+        r#"{"Id": "inv-001", "CustomerId": "cust-001", "Rows": [{"ArticleId": "art-001"}]}"#,
+    )
+    .unwrap();
+
+    assert_eq!(invoice.id, Some(InvoiceId::from("inv-001")));
+    assert_eq!(invoice.customer_id.as_deref(), Some("cust-001"));
+    assert_eq!(invoice.rows[0].article_id.as_ref().unwrap(), "art-001");
+
+    let json = serde_json::to_value(&invoice).unwrap();
+    assert_eq!(json["CustomerId"], "cust-001");
+    assert_eq!(json["Rows"][0]["ArticleId"], "art-001");
+    assert_eq!(CustomerId::new("cust-001").to_string(), "cust-001");
+}
+
+#[test]
+fn test_invoice_statuses() {
+    let invoice: Invoice = serde_json::from_str(
+        r#"{"Status": "Booked", "SentStatus": "Sent", "PaymentStatus": "PartiallyPaid", "Rows": []}"#,
+    )
+    .unwrap();
+
+    assert_eq!(invoice.status, Some(InvoiceStatus::Booked));
+    assert_eq!(invoice.sent_status, Some(SentStatus::Sent));
+    assert_eq!(invoice.payment_status, Some(PaymentStatus::PartiallyPaid));
+
+    let json = serde_json::to_value(&invoice).unwrap();
+    assert_eq!(json["Status"], "Booked");
+    assert_eq!(json["PaymentStatus"], "PartiallyPaid");
+}
+
+#[test]
+fn test_unknown_invoice_status_is_kept() {
+    let invoice: Invoice = serde_json::from_str(
+        r#"{"Status": "Archived", "SentStatus": 3, "PaymentStatus": "Paid", "Rows": []}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        invoice.status,
+        Some(InvoiceStatus::Unknown("Archived".to_string()))
+    );
+    assert_eq!(
+        invoice.sent_status,
+        Some(SentStatus::Unknown("3".to_string()))
+    );
+    assert_eq!(invoice.payment_status, Some(PaymentStatus::Paid));
+
+    let json = serde_json::to_value(&invoice).unwrap();
+    assert_eq!(json["Status"], "Archived");
+}
+
 #[test]
 fn test_customer_skip_none_fields() {
     let customer = Customer {
@@ -111,7 +167,7 @@ fn test_customer_skip_none_fields() {
 #[test]
 fn test_customer_clone() {
     let customer = Customer {
-        id: Some("cust-001".to_string()),
+        id: Some("cust-001".into()),
         name: Some("Test".to_string()),
         ..Default::default()
     };
@@ -125,7 +181,7 @@ fn test_customer_clone() {
 #[test]
 fn test_customer_debug() {
     let customer = Customer {
-        id: Some("cust-001".to_string()),
+        id: Some("cust-001".into()),
         ..Default::default()
     };
 
@@ -152,17 +208,17 @@ fn test_invoice_default() {
 #[test]
 fn test_invoice_with_rows() {
     let invoice = Invoice {
-        id: Some("inv-001".to_string()),
-        customer_id: Some("cust-001".to_string()),
+        id: Some("inv-001".into()),
+        customer_id: Some("cust-001".into()),
         rows: vec![
             InvoiceRow {
-                article_id: Some("art-001".to_string()),
+                article_id: Some("art-001".into()),
                 quantity: some_money(2.0),
                 unit_price: some_money(100.0),
                 ..Default::default()
             },
             InvoiceRow {
-                article_id: Some("art-002".to_string()),
+                article_id: Some("art-002".into()),
                 quantity: some_money(1.0),
                 unit_price: some_money(50.0),
                 ..Default::default()
@@ -172,13 +228,13 @@ fn test_invoice_with_rows() {
     };
 
     assert_eq!(invoice.rows.len(), 2);
-    assert_eq!(invoice.rows[0].article_id, Some("art-001".to_string()));
+    assert_eq!(invoice.rows[0].article_id, Some("art-001".into()));
 }
 
 #[test]
 fn test_invoice_serialization() {
     let invoice = Invoice {
-        id: Some("inv-001".to_string()),
+        id: Some("inv-001".into()),
         invoice_number: Some("1001".to_string()),
         total_amount: some_money(250.0),
         rows: vec![],
@@ -211,12 +267,33 @@ fn test_invoice_deserialization() {
 
     let invoice: Invoice = serde_json::from_str(json).unwrap();
 
-    assert_eq!(invoice.id, Some("inv-001".to_string()));
+    assert_eq!(invoice.id, Some("inv-001".into()));
     assert_eq!(invoice.invoice_number, Some("1001".to_string()));
     assert_eq!(invoice.total_amount, some_money(500.0));
     assert_eq!(invoice.rows.len(), 1);
 }
 
+#[test]
+fn test_invoice_dates_are_calendar_dates() {
+    let json = r#"{
+        "InvoiceDate": "2025-03-14T00:00:00",
+        "DueDate": "2025-04-13T00:00:00Z",
+        "DeliveryDate": "2025-03-31",
+        "Rows": []
+    }"#;
+
+    let invoice: Invoice = serde_json::from_str(json).unwrap();
+
+    assert_eq!(invoice.invoice_date, NaiveDate::from_ymd_opt(2025, 3, 14));
+    assert_eq!(invoice.due_date, NaiveDate::from_ymd_opt(2025, 4, 13));
+    assert_eq!(invoice.delivery_date, NaiveDate::from_ymd_opt(2025, 3, 31));
+
+    let json = serde_json::to_value(&invoice).unwrap();
+    assert_eq!(json["InvoiceDate"], "2025-03-14");
+    assert_eq!(json["DueDate"], "2025-04-13");
+    assert!(serde_json::from_str::<Invoice>(r#"{"DueDate": "13/04/2025", "Rows": []}"#).is_err());
+}
+
 // =============================================================================
 // InvoiceRow Type Tests
 // =============================================================================
@@ -233,14 +310,14 @@ fn test_invoice_row_default() {
 #[test]
 fn test_invoice_row_with_values() {
     let row = InvoiceRow {
-        article_id: Some("art-001".to_string()),
+        article_id: Some("art-001".into()),
         quantity: some_money(3.0),
         unit_price: some_money(25.50),
         discount_percentage: some_money(10.0),
         ..Default::default()
     };
 
-    assert_eq!(row.article_id, Some("art-001".to_string()));
+    assert_eq!(row.article_id, Some("art-001".into()));
     assert_eq!(row.quantity, some_money(3.0));
     assert_eq!(row.unit_price, some_money(25.50));
     assert_eq!(row.discount_percentage, some_money(10.0));
@@ -249,7 +326,7 @@ fn test_invoice_row_with_values() {
 #[test]
 fn test_invoice_row_serialization() {
     let row = InvoiceRow {
-        article_id: Some("art-001".to_string()),
+        article_id: Some("art-001".into()),
         quantity: some_money(2.0),
         unit_price: some_money(100.0),
         ..Default::default()
@@ -278,7 +355,7 @@ fn test_article_default() {
 #[test]
 fn test_article_with_fields() {
     let article = Article {
-        id: Some("art-001".to_string()),
+        id: Some("art-001".into()),
         article_number: Some("ART-001".to_string()),
         name: Some("Widget".to_string()),
         sales_price: some_money(99.99),
@@ -294,7 +371,7 @@ fn test_article_with_fields() {
 #[test]
 fn test_article_serialization() {
     let article = Article {
-        id: Some("art-001".to_string()),
+        id: Some("art-001".into()),
         name: Some("Test".to_string()),
         sales_price: some_money(49.99),
         is_active: Some(true),
@@ -321,7 +398,7 @@ fn test_article_deserialization() {
 
     let article: Article = serde_json::from_str(json).unwrap();
 
-    assert_eq!(article.id, Some("art-001".to_string()));
+    assert_eq!(article.id, Some("art-001".into()));
     assert_eq!(article.article_number, Some("ART-001".to_string()));
     assert_eq!(article.sales_price, some_money(149.99));
 }
@@ -0,0 +1,74 @@
+//! Integration tests for the Users endpoint and audit fields.
+
+mod mock_server;
+
+use mock_server::MockApi;
+use spiris::Audited;
+
+#[tokio::test]
+async fn test_resolve_users_for_audit_fields() {
+    let mut api = MockApi::new().await;
+
+    let invoice_mock = api.mock_get(
+        "/customerinvoices/inv-1",
+        r#"{
+            "Id": "inv-1",
+            "CreatedUtc": "2025-03-01T08:30:00Z",
+            "ModifiedUtc": "2025-03-04T14:00:00Z",
+            "CreatedByUserId": "user-1",
+            "ModifiedByUserId": "user-2",
+            "Rows": []
+        }"#,
+    );
+    let users_mock = api.mock_get_any_query(
+        "/users",
+        r#"{
+            "Data": [
+                {"Id": "user-1", "FirstName": "Anna", "LastName": "Berg"},
+                {"Id": "user-2", "Email": "ekonomi@example.com"},
+                {"Id": "user-3", "FirstName": "Olle", "LastName": "Ek"}
+            ],
+            "Meta": {
+                "CurrentPage": 0, "PageSize": 500, "TotalPages": 1,
+                "TotalCount": 3, "HasNextPage": false, "HasPreviousPage": false
+            }
+        }"#,
+    );
+
+    let invoice = api.client.invoices().get(&"inv-1".into()).await.unwrap();
+    assert_eq!(
+        invoice.created_utc().unwrap().to_rfc3339(),
+        "2025-03-01T08:30:00+00:00"
+    );
+    assert!(invoice.modified_utc() > invoice.created_utc());
+
+    let ids = [invoice.created_by_user_id(), invoice.modified_by_user_id()];
+    let names = api
+        .client
+        .users()
+        .resolve_users(ids.into_iter().flatten())
+        .await
+        .unwrap();
+
+    invoice_mock.assert();
+    users_mock.assert();
+    assert_eq!(names.len(), 2);
+    assert_eq!(names["user-1"], "Anna Berg");
+    assert_eq!(names["user-2"], "ekonomi@example.com");
+}
+
+#[tokio::test]
+async fn test_resolve_no_users_skips_request() {
+    let mut api = MockApi::new().await;
+    let users_mock = api.mock_get_any_query("/users", "{}").expect(0);
+
+    let names = api
+        .client
+        .users()
+        .resolve_users(Vec::<String>::new())
+        .await
+        .unwrap();
+
+    assert!(names.is_empty());
+    users_mock.assert();
+}
@@ -0,0 +1,128 @@
+//! Integration tests for client-side request validation.
+
+mod mock_server;
+
+use mock_server::MockApi;
+use mockito::Matcher;
+use spiris::{AccessToken, Address, Client, ClientConfig, Customer, Error, Invoice};
+
+fn validating_client(api: &MockApi) -> Client {
+    let config = ClientConfig::new()
+        .base_url(api.url())
+        .validate_requests(true);
+    Client::with_config(
+        AccessToken::new("test_token".to_string(), 3600, None),
+        config,
+    )
+}
+
+#[tokio::test]
+async fn test_invalid_create_is_not_sent() {
+    let mut api = MockApi::new().await;
+    let mock = api.server.mock("POST", "/customers").expect(0).create();
+
+    let customer = Customer {
+        email: Some("x".repeat(300)),
+        ..Default::default()
+    };
+    let err = validating_client(&api)
+        .customers()
+        .create(&customer)
+        .await
+        .unwrap_err();
+
+    mock.assert();
+    assert!(matches!(err, Error::Validation(_)));
+    let fields: Vec<_> = err
+        .validation_errors()
+        .unwrap()
+        .iter()
+        .map(|e| e.field.as_str())
+        .collect();
+    assert_eq!(fields, ["Name", "Email"]);
+    assert!(err
+        .to_string()
+        .starts_with("Validation failed: Name: is required; Email:"));
+}
+
+#[tokio::test]
+async fn test_invalid_update_is_not_sent() {
+    let mut api = MockApi::new().await;
+    let mock = api
+        .server
+        .mock("PUT", "/customerinvoices/inv-1")
+        .expect(0)
+        .create();
+
+    let err = validating_client(&api)
+        .invoices()
+        .update(&"inv-1".into(), &Invoice::default())
+        .await
+        .unwrap_err();
+
+    mock.assert();
+    assert_eq!(err.validation_errors().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_validation_is_opt_in() {
+    let mut api = MockApi::new().await;
+    let mock = api.mock_post("/customers", r#"{"Id": "cust-1"}"#);
+
+    // The default client leaves validation to the API
+    let result = api.client.customers().create(&Customer::default()).await;
+
+    mock.assert();
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_identity_number_is_normalized_only_when_validating() {
+    let mut api = MockApi::new().await;
+    let customer = |country: Option<&str>| Customer {
+        name: Some("Acme AB".to_string()),
+        corporate_identity_number: Some("5566778899".to_string()),
+        invoice_address: country.map(|c| Address {
+            country_code: Some(c.to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let sent_as = |number: &str| {
+        Matcher::PartialJson(serde_json::json!({ "CorporateIdentityNumber": number }))
+    };
+    let unchanged = api
+        .server
+        .mock("POST", "/customers")
+        .match_body(sent_as("5566778899"))
+        .with_body(r#"{"Id": "cust-1"}"#)
+        .expect(2)
+        .create();
+    let normalized = api
+        .server
+        .mock("POST", "/customers")
+        .match_body(sent_as("556677-8899"))
+        .with_body(r#"{"Id": "cust-2"}"#)
+        .expect(1)
+        .create();
+
+    api.client
+        .customers()
+        .create(&customer(Some("SE")))
+        .await
+        .unwrap();
+    // Without a country the number isn't assumed to be Swedish
+    validating_client(&api)
+        .customers()
+        .create(&customer(None))
+        .await
+        .unwrap();
+    validating_client(&api)
+        .customers()
+        .create(&customer(Some("SE")))
+        .await
+        .unwrap();
+
+    unchanged.assert();
+    normalized.assert();
+}
@@ -0,0 +1,216 @@
+//! Integration tests for cassette record and replay.
+
+#![cfg(feature = "vcr")]
+
+use mockito::Server;
+use spiris::redact::Redactor;
+use spiris::vcr::{Cassette, VcrMode};
+use spiris::{AccessToken, Client, ClientConfig, CustomerId, Error, RetryConfig};
+use std::path::PathBuf;
+use std::time::Duration;
+
+const CUSTOMER: &str = r#"{"Id": "cust-1", "Name": "Acme AB", "Email": "anna@acme.se"}"#;
+
+fn cassette_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("spiris-vcr-{}-{}.json", name, std::process::id()))
+}
+
+fn client(base_url: &str, cassette: &Cassette, retry_config: RetryConfig) -> Client {
+    let config = ClientConfig::new()
+        .base_url(base_url)
+        .retry_config(retry_config)
+        .cassette(cassette.clone());
+    Client::with_config(
+        AccessToken::new("secret_token".to_string(), 3600, None),
+        config,
+    )
+}
+
+#[tokio::test]
+async fn test_record_then_replay_offline() {
+    let path = cassette_path("replay");
+    let mut server = Server::new_async().await;
+    let base_url = server.url();
+    let mock = server
+        .mock("GET", "/customers/cust-1")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("etag", "\"v1\"")
+        .with_body(CUSTOMER)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let recorder = Cassette::record(&path);
+    let recorded = client(&base_url, &recorder, RetryConfig::new().max_retries(0))
+        .customers()
+        .get(&CustomerId::from("cust-1"))
+        .await
+        .unwrap();
+    recorder.save().unwrap();
+    mock.assert_async().await;
+    drop(server);
+
+    let cassette = Cassette::replay(&path).unwrap();
+    assert_eq!(cassette.mode(), VcrMode::Replay);
+    let replayed = client(&base_url, &cassette, RetryConfig::new().max_retries(0))
+        .customers()
+        .get(&CustomerId::from("cust-1"))
+        .await
+        .unwrap();
+    assert_eq!(replayed.name, recorded.name);
+    assert_eq!(replayed.email.as_deref(), Some("anna@acme.se"));
+
+    let interaction = &cassette.interactions()[0];
+    assert_eq!(interaction.request.method, "GET");
+    assert_eq!(interaction.response.headers["etag"], "\"v1\"");
+    let file = std::fs::read_to_string(&path).unwrap();
+    assert!(!file.contains("secret_token"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_replayed_retry_sees_recorded_failure() {
+    let path = cassette_path("retry");
+    let mut server = Server::new_async().await;
+    let base_url = server.url();
+    server
+        .mock("GET", "/customers/cust-1")
+        .with_status(503)
+        .expect(3)
+        .create_async()
+        .await;
+    let retry = RetryConfig::new()
+        .max_retries(2)
+        .initial_interval(Duration::from_millis(1));
+
+    let recorder = Cassette::record(&path);
+    let recorded = client(&base_url, &recorder, retry.clone())
+        .customers()
+        .get(&CustomerId::from("cust-1"))
+        .await
+        .unwrap_err();
+    recorder.save().unwrap();
+    assert_eq!(recorder.interactions().len(), 3);
+    drop(server);
+
+    let cassette = Cassette::replay(&path).unwrap();
+    let replayed = client(&base_url, &cassette, retry)
+        .customers()
+        .get(&CustomerId::from("cust-1"))
+        .await
+        .unwrap_err();
+    assert_eq!(replayed.status_code(), Some(503));
+    assert_eq!(replayed.status_code(), recorded.status_code());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_replay_in_recorded_order() {
+    let path = cassette_path("order");
+    std::fs::write(
+        &path,
+        r#"{"interactions": [
+            {"request": {"method": "GET", "url": "https://api.test/customers/cust-1"},
+             "response": {"status": 503}},
+            {"request": {"method": "GET", "url": "https://api.test/customers/cust-1"},
+             "response": {"status": 200, "headers": {"content-type": "application/json"},
+                          "body": "{\"Id\": \"cust-1\", \"Name\": \"Acme AB\"}"}}
+        ]}"#,
+    )
+    .unwrap();
+
+    let cassette = Cassette::open(&path).unwrap();
+    let retry = RetryConfig::new()
+        .max_retries(2)
+        .initial_interval(Duration::from_millis(1));
+    let client = client("https://api.test", &cassette, retry);
+    let customer = client
+        .customers()
+        .get(&CustomerId::from("cust-1"))
+        .await
+        .unwrap();
+    assert_eq!(customer.name.as_deref(), Some("Acme AB"));
+
+    let error = client
+        .customers()
+        .get(&CustomerId::from("cust-1"))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error.root(), Error::InvalidRequest(message) if message.contains("no recorded response"))
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_recording_redacts_bodies() {
+    let path = cassette_path("redact");
+    let mut server = Server::new_async().await;
+    server
+        .mock("POST", "/customers")
+        .with_status(201)
+        .with_body(r#"{"Id": "cust-2", "Email": "bo@example.se", "access_token": "leak"}"#)
+        .create_async()
+        .await;
+
+    let cassette = Cassette::record(&path).redactor(Redactor::default().field("access_token"));
+    let customer = spiris::Customer {
+        name: Some("Bo AB".to_string()),
+        email: Some("bo@example.se".to_string()),
+        ..Default::default()
+    };
+    let created = client(&server.url(), &cassette, RetryConfig::new().max_retries(0))
+        .customers()
+        .create(&customer)
+        .await
+        .unwrap();
+    // The caller gets the redacted recording, just like a later replay
+    assert_eq!(created.email.as_deref(), Some("***"));
+
+    let interaction = &cassette.interactions()[0];
+    let request_body = interaction.request.body.as_deref().unwrap();
+    assert!(request_body.contains("Bo AB"));
+    assert!(!request_body.contains("bo@example.se"));
+    assert!(!interaction.response.body.contains("leak"));
+    assert_eq!(interaction.response.status, 201);
+
+    cassette.save().unwrap();
+    assert_eq!(Cassette::open(&path).unwrap().mode(), VcrMode::Replay);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_binary_body_round_trips() {
+    let path = cassette_path("binary");
+    let pdf = vec![0x25, 0x50, 0x44, 0x46, 0xff, 0x00, 0xfe];
+    let mut server = Server::new_async().await;
+    let base_url = server.url();
+    server
+        .mock("GET", "/customerinvoices/inv-1/pdf")
+        .with_status(200)
+        .with_body(&pdf)
+        .create_async()
+        .await;
+
+    let recorder = Cassette::record(&path);
+    let invoice_id = spiris::InvoiceId::from("inv-1");
+    let recorded = client(&base_url, &recorder, RetryConfig::new().max_retries(0))
+        .invoices()
+        .get_pdf(&invoice_id)
+        .await
+        .unwrap();
+    recorder.save().unwrap();
+    assert!(recorder.interactions()[0].response.body_base64);
+    drop(server);
+
+    let cassette = Cassette::replay(&path).unwrap();
+    let replayed = client(&base_url, &cassette, RetryConfig::new().max_retries(0))
+        .invoices()
+        .get_pdf(&invoice_id)
+        .await
+        .unwrap();
+    assert_eq!(recorded, pdf);
+    assert_eq!(replayed, pdf);
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,283 @@
+//! Integration tests for VIES VAT number validation.
+
+#![cfg(feature = "vies")]
+
+use mockito::{Matcher, Server};
+use spiris::vat::EuSaleKind;
+use spiris::vies::ViesClient;
+use spiris::{Customer, Error, Invoice, InvoiceRow};
+use std::time::Duration;
+
+fn invoice() -> Invoice {
+    Invoice {
+        customer_id: Some("cust-1".into()),
+        rows: vec![InvoiceRow::default(), InvoiceRow::default()],
+        ..Default::default()
+    }
+}
+
+fn vies_response(valid: bool) -> String {
+    format!(
+        r#"{{
+            "countryCode": "DE",
+            "vatNumber": "123456789",
+            "requestDate": "2025-01-15T10:00:00.000Z",
+            "valid": {},
+            "userError": "{}",
+            "requestIdentifier": "WAPIAAAAW1234567",
+            "name": "Beispiel GmbH",
+            "address": "Musterstrasse 1, Berlin"
+        }}"#,
+        valid,
+        if valid { "VALID" } else { "INVALID" }
+    )
+}
+
+#[tokio::test]
+async fn test_check_sends_normalized_number_and_requester() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/check-vat-number")
+        .match_body(Matcher::PartialJsonString(
+            r#"{"countryCode":"DE","vatNumber":"123456789","requesterMemberStateCode":"SE","requesterNumber":"556677889901"}"#
+                .to_string(),
+        ))
+        .with_status(200)
+        .with_body(vies_response(true))
+        .create_async()
+        .await;
+
+    let vies = ViesClient::new()
+        .base_url(server.url())
+        .requester("SE556677889901");
+    let check = vies.check("de 123 456 789").await.unwrap();
+
+    mock.assert_async().await;
+    assert!(check.valid);
+    assert_eq!(check.full_vat_number(), "DE123456789");
+    assert_eq!(
+        check.request_identifier.as_deref(),
+        Some("WAPIAAAAW1234567")
+    );
+}
+
+#[tokio::test]
+async fn test_check_rejects_malformed_number_without_request() {
+    let server = Server::new_async().await;
+    let vies = ViesClient::new().base_url(server.url());
+
+    assert!(vies.check("123456789").await.is_err());
+}
+
+#[tokio::test]
+async fn test_apply_zero_vat_on_valid_number() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/check-vat-number")
+        .with_status(200)
+        .with_body(vies_response(true))
+        .create_async()
+        .await;
+
+    let vies = ViesClient::new().base_url(server.url());
+    let mut invoice = invoice();
+    let receipt = vies
+        .apply_intra_eu_zero_vat(&mut invoice, EuSaleKind::Goods, "DE123456789")
+        .await
+        .unwrap();
+
+    assert!(receipt.valid);
+    assert!(invoice.rows.iter().all(|r| r.is_vat_free == Some(true)));
+    assert_eq!(invoice.customer_vat_number.as_deref(), Some("DE123456789"));
+    assert_eq!(
+        invoice.remarks.as_deref(),
+        Some(EuSaleKind::Goods.invoice_text())
+    );
+}
+
+#[tokio::test]
+async fn test_apply_zero_vat_refuses_invalid_number() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/check-vat-number")
+        .with_status(200)
+        .with_body(vies_response(false))
+        .create_async()
+        .await;
+
+    let vies = ViesClient::new().base_url(server.url());
+    let mut invoice = invoice();
+    let result = vies
+        .apply_intra_eu_zero_vat(&mut invoice, EuSaleKind::Goods, "DE123456789")
+        .await;
+
+    assert!(result.is_err());
+    assert!(invoice.rows.iter().all(|r| r.is_vat_free.is_none()));
+    assert!(invoice.customer_vat_number.is_none());
+}
+
+#[tokio::test]
+async fn test_apply_zero_vat_refuses_domestic_buyer() {
+    let server = Server::new_async().await;
+    let vies = ViesClient::new().base_url(server.url());
+    let mut invoice = invoice();
+
+    let result = vies
+        .apply_intra_eu_zero_vat(&mut invoice, EuSaleKind::Goods, "SE556677889901")
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_service_error_is_reported() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/check-vat-number")
+        .with_status(500)
+        .with_body(r#"{"errorWrappers":[{"error":"MS_UNAVAILABLE"}]}"#)
+        .create_async()
+        .await;
+
+    let vies = ViesClient::new().base_url(server.url());
+    let err = vies.check("DE123456789").await.unwrap_err();
+
+    assert_eq!(err.status_code(), Some(500));
+}
+
+#[tokio::test]
+async fn test_check_caches_answers() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/check-vat-number")
+        .with_status(200)
+        .with_body(vies_response(true))
+        .expect(2)
+        .create_async()
+        .await;
+
+    let vies = ViesClient::new().base_url(server.url());
+    let customer = Customer {
+        vat_number: Some("DE123456789".to_string()),
+        ..Default::default()
+    };
+    let mut invoice = invoice();
+    vies.check("DE 123456789").await.unwrap();
+    vies.check_customer(&customer).await.unwrap();
+    vies.clone()
+        .apply_for_customer(&mut invoice, EuSaleKind::Services, &customer)
+        .await
+        .unwrap();
+    assert_eq!(invoice.customer_vat_number.as_deref(), Some("DE123456789"));
+
+    vies.clear_cache();
+    let uncached = vies.clone().cache_ttl(Duration::ZERO);
+    uncached.check("DE123456789").await.unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_check_customer_without_vat_number() {
+    let server = Server::new_async().await;
+    let vies = ViesClient::new().base_url(server.url());
+
+    let err = vies.check_customer(&Customer::default()).await.unwrap_err();
+    assert!(matches!(err, Error::Validation(errors) if errors[0].field == "VatNumber"));
+}
+
+#[tokio::test]
+async fn test_unavailable_member_state_is_an_error_and_not_cached() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/check-vat-number")
+        .with_status(200)
+        .with_body(
+            r#"{"countryCode":"DE","vatNumber":"123456789","valid":false,"userError":"MS_UNAVAILABLE"}"#,
+        )
+        .expect(2)
+        .create_async()
+        .await;
+
+    let vies = ViesClient::new().base_url(server.url());
+    let mut invoice = invoice();
+    let err = vies.check("DE123456789").await.unwrap_err();
+    assert_eq!(err.status_code(), Some(503));
+    assert!(err.is_retryable());
+    assert_eq!(
+        err.api_response().unwrap().error_code.as_deref(),
+        Some("MS_UNAVAILABLE")
+    );
+
+    assert!(vies
+        .apply_intra_eu_zero_vat(&mut invoice, EuSaleKind::Goods, "DE123456789")
+        .await
+        .is_err());
+    assert!(invoice.rows.iter().all(|r| r.is_vat_free.is_none()));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_invalid_answers_use_invalid_cache_ttl() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/check-vat-number")
+        .with_status(200)
+        .with_body(vies_response(false))
+        .expect(2)
+        .create_async()
+        .await;
+
+    let vies = ViesClient::new().base_url(server.url());
+    assert!(!vies.check("DE123456789").await.unwrap().valid);
+    assert!(!vies.check("DE123456789").await.unwrap().valid);
+
+    let uncached = ViesClient::new()
+        .base_url(server.url())
+        .invalid_cache_ttl(Duration::ZERO);
+    assert!(!uncached.check("DE123456789").await.unwrap().valid);
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_apply_zero_vat_checks_sale_before_asking_vies() {
+    let server = Server::new_async().await;
+    let vies = ViesClient::new().base_url(server.url());
+
+    let mut invoice = invoice();
+    assert!(vies
+        .apply_intra_eu_zero_vat(&mut invoice, EuSaleKind::Services, "XI123456789")
+        .await
+        .is_err());
+
+    invoice.reverse_charge_on_construction_services = Some(true);
+    assert!(vies
+        .apply_intra_eu_zero_vat(&mut invoice, EuSaleKind::Goods, "DE123456789")
+        .await
+        .is_err());
+    assert!(invoice.customer_vat_number.is_none());
+}
+
+#[tokio::test]
+async fn test_apply_zero_vat_uses_sale_kind() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/check-vat-number")
+        .with_status(200)
+        .with_body(vies_response(true))
+        .create_async()
+        .await;
+
+    let vies = ViesClient::new().base_url(server.url());
+    let mut invoice = invoice();
+    vies.apply_intra_eu_zero_vat(&mut invoice, EuSaleKind::Triangulation, "DE123456789")
+        .await
+        .unwrap();
+
+    assert_eq!(invoice.eu_third_party, Some(true));
+    assert_eq!(
+        invoice.remarks.as_deref(),
+        Some(EuSaleKind::Triangulation.invoice_text())
+    );
+}
@@ -1,11 +1,9 @@
-// Skip these tests when decimal feature is enabled (uses f64 literals)
-#![cfg(not(feature = "decimal"))]
 //! Integration tests for the Vouchers endpoint.
 
 mod mock_server;
 
 use mock_server::MockApi;
-use spiris::{PaginationParams, Voucher, VoucherRow};
+use spiris::{money, PaginationParams, Voucher, VoucherRow};
 
 #[tokio::test]
 async fn test_list_vouchers() {
@@ -79,8 +77,8 @@ async fn test_get_voucher() {
     assert_eq!(result.id, Some("vouch-123".to_string()));
     assert_eq!(result.voucher_number, Some("42".to_string()));
     assert_eq!(result.rows.len(), 2);
-    assert_eq!(result.rows[0].debit_amount, Some(10000.0));
-    assert_eq!(result.rows[1].credit_amount, Some(10000.0));
+    assert_eq!(result.rows[0].debit_amount, Some(money!(10000.0)));
+    assert_eq!(result.rows[1].credit_amount, Some(money!(10000.0)));
 }
 
 #[tokio::test]
@@ -105,14 +103,14 @@ async fn test_create_voucher() {
         rows: vec![
             VoucherRow {
                 account_number: Some("1920".to_string()),
-                debit_amount: Some(5000.0),
-                credit_amount: Some(0.0),
+                debit_amount: Some(money!(5000.0)),
+                credit_amount: Some(money!(0.0)),
                 ..Default::default()
             },
             VoucherRow {
                 account_number: Some("3000".to_string()),
-                debit_amount: Some(0.0),
-                credit_amount: Some(5000.0),
+                debit_amount: Some(money!(0.0)),
+                credit_amount: Some(money!(5000.0)),
                 ..Default::default()
             },
         ],
@@ -200,3 +198,199 @@ async fn test_list_vouchers_with_pagination() {
     assert_eq!(result.meta.current_page, 10);
     assert_eq!(result.meta.total_count, 500);
 }
+
+// =============================================================================
+// Batch posting
+// =============================================================================
+
+mod batch {
+    use super::mock_server::{money, MockApi};
+    use mockito::{Matcher, Mock};
+    use spiris::endpoints::vouchers::Atomicity;
+    use spiris::{Voucher, VoucherRow};
+
+    fn mock_list(api: &mut MockApi, path: &str, data: &str) -> Mock {
+        api.server
+            .mock("GET", path)
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(
+                r#"{{"Data": [{}], "Meta": {{"CurrentPage": 0, "PageSize": 500, "TotalPages": 1,
+                    "TotalCount": 1, "HasNextPage": false, "HasPreviousPage": false}}}}"#,
+                data
+            ))
+            .create()
+    }
+
+    /// Accounts 1930, 2710 and 7010, fiscal year 2025, locked through January.
+    fn mock_books(api: &mut MockApi) -> Vec<Mock> {
+        let mut mocks = vec![
+            mock_list(
+                api,
+                "/accounts",
+                r#"{"AccountNumber": "1930", "IsActive": true},
+                   {"AccountNumber": "2710", "IsActive": true},
+                   {"AccountNumber": "7010", "IsActive": true}"#,
+            ),
+            mock_list(
+                api,
+                "/fiscalyears",
+                r#"{"Id": "fy-2025", "StartDate": "2025-01-01T00:00:00Z", "EndDate": "2025-12-31T00:00:00Z"}"#,
+            ),
+            api.mock_get(
+                "/companysettings",
+                r#"{"Name": "Acme AB", "LockedThroughDate": "2025-01-31T00:00:00Z"}"#,
+            ),
+        ];
+        for path in [
+            "/vatcodes",
+            "/units",
+            "/termsofpayments",
+            "/currencies",
+            "/deliveryterms",
+        ] {
+            mocks.push(mock_list(api, path, ""));
+        }
+        mocks
+    }
+
+    fn voucher(text: &str, date: &str, debit_account: &str, amount: f64) -> Voucher {
+        Voucher {
+            voucher_date: Some(format!("{}T00:00:00Z", date).parse().unwrap()),
+            voucher_text: Some(text.to_string()),
+            rows: vec![
+                VoucherRow {
+                    account_number: Some(debit_account.to_string()),
+                    debit_amount: Some(money(amount)),
+                    ..Default::default()
+                },
+                VoucherRow {
+                    account_number: Some("1930".to_string()),
+                    credit_amount: Some(money(amount)),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn mock_post(api: &mut MockApi, text: &str, number: &str) -> Mock {
+        api.server
+            .mock("POST", "/vouchers")
+            .match_body(Matcher::PartialJson(
+                serde_json::json!({ "VoucherText": text }),
+            ))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(format!(
+                r#"{{"Id": "v-{0}", "VoucherNumber": "{0}", "Rows": []}}"#,
+                number
+            ))
+            .create()
+    }
+
+    fn batch() -> Vec<Voucher> {
+        vec![
+            voucher("Salaries March", "2025-03-25", "7010", 42000.0),
+            voucher("Salaries January", "2025-01-25", "7011", 41000.0),
+            voucher("Payroll tax March", "2025-03-25", "2710", 13196.4),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_best_effort_posts_valid_vouchers() {
+        let mut api = MockApi::new().await;
+        let _books = mock_books(&mut api);
+        let salaries = mock_post(&mut api, "Salaries March", "101");
+        let tax = mock_post(&mut api, "Payroll tax March", "102");
+
+        let result = api
+            .client
+            .vouchers()
+            .create_batch(&batch(), Atomicity::BestEffort)
+            .await
+            .unwrap();
+
+        salaries.assert();
+        tax.assert();
+        assert_eq!(result.voucher_numbers(), ["101", "102"]);
+        assert!(!result.is_complete());
+
+        let failure = &result.failures[0];
+        assert_eq!(failure.index, 1);
+        let fields: Vec<_> = failure
+            .error
+            .validation_errors()
+            .unwrap()
+            .iter()
+            .map(|e| e.field.as_str())
+            .collect();
+        assert_eq!(fields, ["VoucherDate", "Rows[0].AccountNumber"]);
+    }
+
+    #[tokio::test]
+    async fn test_abort_on_first_error_posts_nothing_if_any_voucher_is_invalid() {
+        let mut api = MockApi::new().await;
+        let _books = mock_books(&mut api);
+        let post = api.server.mock("POST", "/vouchers").expect(0).create();
+
+        let result = api
+            .client
+            .vouchers()
+            .create_batch(&batch(), Atomicity::AbortOnFirstError)
+            .await
+            .unwrap();
+
+        post.assert();
+        assert!(result.posted.is_empty());
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.skipped, [0, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_abort_on_first_error_stops_at_api_rejection() {
+        let mut api = MockApi::new().await;
+        let _books = mock_books(&mut api);
+        let salaries = mock_post(&mut api, "Salaries March", "101");
+        let rejected = api
+            .server
+            .mock("POST", "/vouchers")
+            .match_body(Matcher::PartialJson(
+                serde_json::json!({ "VoucherText": "Payroll tax March" }),
+            ))
+            .with_status(400)
+            .with_body(r#"{"Message": "Voucher series is full"}"#)
+            .create();
+        let last = api
+            .server
+            .mock("POST", "/vouchers")
+            .match_body(Matcher::PartialJson(
+                serde_json::json!({ "VoucherText": "Salaries April" }),
+            ))
+            .expect(0)
+            .create();
+
+        let mut vouchers = batch();
+        vouchers.remove(1);
+        vouchers.push(voucher("Salaries April", "2025-04-25", "7010", 42000.0));
+
+        let result = api
+            .client
+            .vouchers()
+            .create_batch(&vouchers, Atomicity::AbortOnFirstError)
+            .await
+            .unwrap();
+
+        salaries.assert();
+        rejected.assert();
+        last.assert();
+        assert_eq!(result.voucher_numbers(), ["101"]);
+        assert_eq!(result.failures[0].index, 1);
+        assert!(result.failures[0]
+            .error
+            .to_string()
+            .contains("Voucher series is full"));
+        assert_eq!(result.skipped, [2]);
+    }
+}
@@ -0,0 +1,94 @@
+//! Integration tests for the axum webhook receiver.
+
+#![cfg(feature = "webhooks-axum")]
+
+use spiris::webhooks::{
+    self, Action, Event, WebhookConfig, WebhookEventBuilder, WebhookHandler, WebhookTestHelper,
+};
+use tokio::sync::mpsc;
+
+const SECRET: &str = "test_secret_key";
+
+/// Serve the webhook router on a random port, returning its URL and the
+/// channel received events are sent to.
+async fn serve() -> (String, mpsc::UnboundedReceiver<Event>) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let handler = WebhookHandler::new(WebhookConfig::new(SECRET));
+    let app = axum::Router::new().nest(
+        "/webhooks",
+        webhooks::axum_router(handler, move |event| {
+            let sender = sender.clone();
+            async move {
+                sender.send(event).unwrap();
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/webhooks", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (url, receiver)
+}
+
+#[tokio::test]
+async fn test_verified_delivery_is_acknowledged() {
+    let (url, mut events) = serve().await;
+    let event = WebhookEventBuilder::new()
+        .id("evt_1")
+        .event_type("customer.created")
+        .resource_id("cust-1")
+        .build();
+    let (payload, signature) = WebhookTestHelper::new(SECRET).create_signed_payload(&event);
+
+    let response = reqwest::Client::new()
+        .post(&url)
+        .header("X-Webhook-Signature", signature)
+        .body(payload)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    match events.recv().await.unwrap() {
+        Event::Customer(customer) => {
+            assert_eq!(customer.event_id, "evt_1");
+            assert_eq!(customer.action, Action::Created);
+            assert_eq!(customer.id.as_deref(), Some("cust-1"));
+        }
+        other => panic!("expected a customer event, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_rejects_bad_signature_and_payload() {
+    let (url, mut events) = serve().await;
+    let helper = WebhookTestHelper::new(SECRET);
+    let client = reqwest::Client::new();
+
+    let unsigned = client
+        .post(&url)
+        .body(r#"{"id": "evt_1", "eventType": "invoice.paid", "timestamp": 0}"#)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(unsigned.status(), 401);
+
+    let forged = client
+        .post(&url)
+        .header("X-Webhook-Signature", helper.sign(b"something else"))
+        .body(r#"{"id": "evt_1", "eventType": "invoice.paid", "timestamp": 0}"#)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(forged.status(), 401);
+
+    let garbage = client
+        .post(&url)
+        .header("X-Webhook-Signature", helper.sign(b"not json"))
+        .body("not json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(garbage.status(), 400);
+
+    assert!(events.try_recv().is_err());
+}